@@ -9,7 +9,11 @@
 
 use criterion::criterion_main;
 
+pub mod cdt;
+pub mod ibe;
+pub mod ibe_comparison;
 pub mod pfdh;
 pub mod regev;
+pub mod sparse_trapdoor;
 
-criterion_main! {regev::benches, pfdh::benches}
+criterion_main! {regev::benches, pfdh::benches, ibe::benches, ibe_comparison::benches, sparse_trapdoor::benches, cdt::benches}
@@ -0,0 +1,52 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+use criterion::{criterion_group, Criterion};
+use qfall_crypto::{
+    primitive::psf::{PSF, PSFGPV},
+    sample::g_trapdoor::gadget_parameters::GadgetParameters,
+};
+use qfall_math::rational::Q;
+
+/// Returns a [`PSFGPV`] with `n = 8`, `q = 128` and `s = 20`.
+fn gpv_psf() -> PSFGPV {
+    PSFGPV {
+        gp: GadgetParameters::init_default(8, 128),
+        s: Q::from(20),
+        cdt: None,
+        cdt_cache: Default::default(),
+    }
+}
+
+/// Benchmark [bench_samp_p] with `n = 8` without and with a precomputed
+/// cumulative distribution table, i.e. [`PSFGPV::with_precomputed_cdt`].
+/// The trapdoor and the syndrome are generated once, s.t. only repeated calls
+/// of `samp_p` with the same Gaussian parameter are measured.
+///
+/// This benchmark can be run with for example:
+/// - `cargo criterion SampP\ GPV`
+/// - `cargo bench --bench benchmarks SampP\ GPV`
+/// - `cargo flamegraph --bench benchmarks -- --bench SampP\ GPV\ CDT\ n=8`
+///
+/// Shorter variants or regex expressions can also be used to specify the
+/// benchmark name. The `\ ` is used to escape the space, alternatively,
+/// quotation marks can be used.
+fn bench_samp_p(c: &mut Criterion) {
+    let psf = gpv_psf();
+    let (a, td) = psf.trap_gen();
+    let u = psf.f_a(&a, &psf.samp_d());
+
+    let psf_cdt = gpv_psf().with_precomputed_cdt();
+
+    c.bench_function("SampP GPV n=8", |b| b.iter(|| psf.samp_p(&a, &td, &u)));
+    c.bench_function("SampP GPV CDT n=8", |b| {
+        b.iter(|| psf_cdt.samp_p(&a, &td, &u))
+    });
+}
+
+criterion_group!(benches, bench_samp_p);
@@ -0,0 +1,75 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+use criterion::{criterion_group, Criterion};
+use qfall_crypto::construction::identity_based_encryption::{DualRegevIBE, IBEScheme};
+
+/// Benchmark the encryption of the same message for `k = 16` identities
+/// with [`DualRegevIBE::enc_batch_same_msg`] and with `k` independent calls of
+/// [`DualRegevIBE::enc`](IBEScheme::enc).
+///
+/// This benchmark can be run with for example:
+/// - `cargo criterion Batch\ Encryption\ DualRegevIBE`
+/// - `cargo bench --bench benchmarks Batch\ Encryption\ DualRegevIBE`
+/// - `cargo flamegraph --bench benchmarks -- --bench Batch\ Encryption\ DualRegevIBE`
+///
+/// Shorter variants or regex expressions can also be used to specify the
+/// benchmark name. The `\ ` is used to escape the space, alternatively,
+/// quotation marks can be used.
+fn bench_enc_batch_same_msg(c: &mut Criterion) {
+    let ibe = DualRegevIBE::default();
+    let (pk, _) = ibe.setup();
+    let identities: Vec<String> = (0..16).map(|i| format!("identity {i}")).collect();
+
+    let mut group = c.benchmark_group("Batch Encryption DualRegevIBE k=16");
+
+    group.bench_function("enc_batch_same_msg", |b| {
+        b.iter(|| ibe.enc_batch_same_msg(&pk, &identities, 1))
+    });
+    group.bench_function("independent enc", |b| {
+        b.iter(|| {
+            identities
+                .iter()
+                .map(|id| ibe.enc(&pk, id, 1))
+                .collect::<Vec<_>>()
+        })
+    });
+
+    group.finish();
+}
+
+/// Benchmark 100 encryptions for the same identity with a precomputed
+/// [`EncState`](qfall_crypto::construction::identity_based_encryption::EncState)
+/// and with 100 calls of [`DualRegevIBE::enc`](IBEScheme::enc).
+///
+/// This benchmark can be run with for example:
+/// - `cargo criterion Repeated\ Encryption\ DualRegevIBE`
+/// - `cargo bench --bench benchmarks Repeated\ Encryption\ DualRegevIBE`
+fn bench_enc_with_state(c: &mut Criterion) {
+    let ibe = DualRegevIBE::default();
+    let (pk, _) = ibe.setup();
+    let id = String::from("identity");
+
+    let mut group = c.benchmark_group("Repeated Encryption DualRegevIBE 100 messages");
+
+    group.bench_function("enc_with_state", |b| {
+        b.iter(|| {
+            let state = ibe.precompute_enc_state(&pk, &id);
+            (0..100)
+                .map(|_| ibe.enc_with_state(&state, 1))
+                .collect::<Vec<_>>()
+        })
+    });
+    group.bench_function("enc", |b| {
+        b.iter(|| (0..100).map(|_| ibe.enc(&pk, &id, 1)).collect::<Vec<_>>())
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_enc_batch_same_msg, bench_enc_with_state);
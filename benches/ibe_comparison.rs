@@ -0,0 +1,141 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+use criterion::{criterion_group, BenchmarkId, Criterion};
+use qfall_crypto::{
+    analysis::recommend::recommend_params,
+    construction::identity_based_encryption::{DualRegevIBE, IBEScheme, RingDualRegevIBE},
+};
+use serde::Serialize;
+
+/// The bit security levels at which both IBE variants are compared.
+const LAMBDAS: [u32; 2] = [80, 100];
+
+/// Returns the length of the JSON serialization of `value` in bytes,
+/// which is the size measure of [`SchemeSummary`](qfall_crypto::utils::scheme_summary::SchemeSummary).
+fn serialized_len(value: &impl Serialize) -> usize {
+    serde_json::to_vec(value).unwrap().len()
+}
+
+/// Prints the sizes of the master public key, the master secret key,
+/// an identity secret key and a ciphertext of a single bit.
+fn print_sizes(
+    name: &str,
+    lambda: u32,
+    mpk: &impl Serialize,
+    msk: &impl Serialize,
+    sk_id: &impl Serialize,
+    cipher: &impl Serialize,
+) {
+    println!(
+        "{name} λ={lambda}: master_pk {} B, master_sk {} B, sk_id {} B, cipher {} B",
+        serialized_len(mpk),
+        serialized_len(msk),
+        serialized_len(sk_id),
+        serialized_len(cipher)
+    );
+}
+
+/// Benchmark `setup`, `extract`, `enc` and `dec` of [`DualRegevIBE`] at `λ = 80`
+/// and `λ = 100` bits of security, where `n` is chosen by
+/// [`recommend_params`] for the classical setting.
+/// The sizes of keys and ciphertexts are printed once per security level.
+///
+/// **WARNING:** The public keys of these parameter sets are large,
+/// hence `setup` and `extract` are very slow.
+///
+/// This benchmark can be run with for example:
+/// - `cargo criterion IBE\ Comparison\ DualRegevIBE`
+/// - `cargo bench --bench benchmarks IBE\ Comparison\ DualRegevIBE`
+/// - `cargo flamegraph --bench benchmarks -- --bench IBE\ Comparison\ DualRegevIBE/extract/80`
+///
+/// Shorter variants or regex expressions can also be used to specify the
+/// benchmark name. The `\ ` is used to escape the space, alternatively,
+/// quotation marks can be used.
+fn bench_dual_regev_ibe(c: &mut Criterion) {
+    let mut group = c.benchmark_group("IBE Comparison DualRegevIBE");
+    group.sample_size(10);
+
+    for lambda in LAMBDAS {
+        let ibe = DualRegevIBE::new_from_n(recommend_params(lambda, false).n as u64).unwrap();
+        let (pk, sk) = ibe.setup();
+        let id = String::from("identity");
+        let id_sk = ibe.extract(&pk, &sk, &id).unwrap();
+        let cipher = ibe.enc(&pk, &id, 1);
+        print_sizes("DualRegevIBE", lambda, &pk, &sk, &id_sk, &cipher);
+
+        group.bench_function(BenchmarkId::new("setup", lambda), |b| {
+            b.iter(|| ibe.setup())
+        });
+        // fresh identities are used as extracted secret keys are stored
+        let mut counter = 0_u64;
+        group.bench_function(BenchmarkId::new("extract", lambda), |b| {
+            b.iter(|| {
+                counter += 1;
+                ibe.extract(&pk, &sk, &format!("identity {counter}"))
+            })
+        });
+        group.bench_function(BenchmarkId::new("enc", lambda), |b| {
+            b.iter(|| ibe.enc(&pk, &id, 1))
+        });
+        group.bench_function(BenchmarkId::new("dec", lambda), |b| {
+            b.iter(|| ibe.dec(&id_sk, &cipher))
+        });
+    }
+
+    group.finish();
+}
+
+/// Benchmark `setup`, `extract`, `enc` and `dec` of [`RingDualRegevIBE`] at `λ = 80`
+/// and `λ = 100` bits of security, where `n` is chosen by
+/// [`recommend_params`] for the ring setting.
+/// The sizes of keys and ciphertexts are printed once per security level.
+///
+/// This benchmark can be run with for example:
+/// - `cargo criterion IBE\ Comparison\ RingDualRegevIBE`
+/// - `cargo bench --bench benchmarks IBE\ Comparison\ RingDualRegevIBE`
+/// - `cargo flamegraph --bench benchmarks -- --bench IBE\ Comparison\ RingDualRegevIBE/extract/80`
+///
+/// Shorter variants or regex expressions can also be used to specify the
+/// benchmark name. The `\ ` is used to escape the space, alternatively,
+/// quotation marks can be used.
+fn bench_ring_dual_regev_ibe(c: &mut Criterion) {
+    let mut group = c.benchmark_group("IBE Comparison RingDualRegevIBE");
+    group.sample_size(10);
+
+    for lambda in LAMBDAS {
+        let ibe = RingDualRegevIBE::new_from_n(recommend_params(lambda, true).n as u64);
+        let (pk, sk) = ibe.setup();
+        let id = String::from("identity");
+        let id_sk = ibe.extract(&pk, &sk, &id).unwrap();
+        let cipher = ibe.enc(&pk, &id, 1);
+        print_sizes("RingDualRegevIBE", lambda, &pk, &sk, &id_sk, &cipher);
+
+        group.bench_function(BenchmarkId::new("setup", lambda), |b| {
+            b.iter(|| ibe.setup())
+        });
+        // fresh identities are used as extracted secret keys are stored
+        let mut counter = 0_u64;
+        group.bench_function(BenchmarkId::new("extract", lambda), |b| {
+            b.iter(|| {
+                counter += 1;
+                ibe.extract(&pk, &sk, &format!("identity {counter}"))
+            })
+        });
+        group.bench_function(BenchmarkId::new("enc", lambda), |b| {
+            b.iter(|| ibe.enc(&pk, &id, 1))
+        });
+        group.bench_function(BenchmarkId::new("dec", lambda), |b| {
+            b.iter(|| ibe.dec(&id_sk, &cipher))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_dual_regev_ibe, bench_ring_dual_regev_ibe);
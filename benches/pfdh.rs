@@ -16,7 +16,7 @@ fn pfdh_cycle(n: i64) {
     let m = "Hello World!";
 
     let (pk, sk) = pfdh.gen();
-    let sigma = pfdh.sign(m.to_owned(), &sk, &pk);
+    let sigma = pfdh.sign(m.to_owned(), &sk, &pk).unwrap();
 
     pfdh.vfy(m.to_owned(), &sigma, &pk);
 }
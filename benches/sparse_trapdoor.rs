@@ -0,0 +1,50 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+use criterion::{criterion_group, Criterion};
+use qfall_crypto::sample::g_trapdoor::{
+    gadget_default::gen_trapdoor_default, sparse_trapdoor::SparseTrapdoor,
+};
+use qfall_math::{
+    integer::MatZ,
+    traits::{GetNumColumns, GetNumRows},
+};
+use std::mem::size_of;
+
+/// Benchmark the multiplication of a G-Trapdoor for `n = 50` and `q = 2^16`
+/// with a matrix of `64` columns as dense [`MatZ`] and as [`SparseTrapdoor`].
+/// Before the benchmark, the memory required to store the entries of both
+/// representations is printed, where an entry of a [`MatZ`] occupies one `fmpz`,
+/// i.e. `8` bytes for small entries.
+///
+/// This benchmark can be run with for example:
+/// - `cargo criterion Sparse\ Trapdoor\ n=50`
+/// - `cargo bench --bench benchmarks Sparse\ Trapdoor\ n=50`
+fn bench_sparse_trapdoor(c: &mut Criterion) {
+    let (_, r) = gen_trapdoor_default(50, 65536);
+    let sparse_r = SparseTrapdoor::from(&r);
+    let rhs = MatZ::sample_uniform(r.get_num_columns(), 64, -4, 4).unwrap();
+
+    let dense_bytes = (r.get_num_rows() * r.get_num_columns()) as usize * size_of::<i64>();
+    let sparse_bytes = sparse_r.entries.len() * size_of::<(usize, usize, i32)>();
+    println!(
+        "Trapdoor of dimensions {}x{} with {} nonzero entries: dense {dense_bytes} bytes, sparse {sparse_bytes} bytes",
+        r.get_num_rows(),
+        r.get_num_columns(),
+        sparse_r.entries.len(),
+    );
+
+    let mut group = c.benchmark_group("Sparse Trapdoor n=50");
+
+    group.bench_function("dense mul", |b| b.iter(|| &r * &rhs));
+    group.bench_function("sparse mul", |b| b.iter(|| sparse_r.mul_dense(&rhs)));
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_sparse_trapdoor);
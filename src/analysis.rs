@@ -0,0 +1,28 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains tools to analyse the concrete security of
+//! lattice-based constructions by estimating the cost of known attacks.
+//!
+//! The main references are listed in the following
+//! and will be further referenced in submodules by these numbers:
+//! - \[1\] Alkim, E., Ducas, L., Pöppelmann, T., Schwabe, P. (2016).
+//!     Post-quantum key exchange - a new hope.
+//!     In: 25th USENIX Security Symposium.
+//!     <https://eprint.iacr.org/2015/1092.pdf>
+//! - \[2\] Albrecht, M. R., Player, R., Scott, S. (2015).
+//!     On the concrete hardness of Learning with Errors.
+//!     In: Journal of Mathematical Cryptology 9.3.
+//!     <https://eprint.iacr.org/2015/046.pdf>
+//! - \[3\] MATZOV (2022).
+//!     Report on the Security of LWE: Improved Dual Lattice Attack.
+//!     <https://doi.org/10.5281/zenodo.6493704>
+
+pub mod recommend;
+pub mod security;
+pub mod weak_params;
@@ -0,0 +1,176 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains recommendations of LWE parameters for a targeted bit security,
+//! which are found by estimating the cost of attacks using [`overall_security`].
+
+use super::security::overall_security;
+use crate::{
+    construction::pk_encryption::{DualRegev, RingLPR},
+    utils::conversion::q_to_f64,
+};
+use qfall_math::{integer_mod_q::Modulus, rational::Q};
+use serde::{Deserialize, Serialize};
+
+/// The public parameters of an LWE instance.
+///
+/// Attributes:
+/// - `n`: the dimension of the secret
+/// - `m`: the number of LWE samples
+/// - `q`: the modulus
+/// - `alpha`: the relative Gaussian parameter of the error
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LweParams {
+    pub n: usize,
+    pub m: usize,
+    pub q: Modulus,
+    pub alpha: Q,
+}
+
+impl LweParams {
+    /// Returns the parameters chosen by [`DualRegev::new_from_n`]
+    /// or by [`RingLPR::new_from_n`] if `use_ring` is `true`.
+    /// A public key of [`RingLPR`] contains a single ring sample, i.e. `m = n` LWE samples.
+    fn from_n(n: usize, use_ring: bool) -> Self {
+        if use_ring {
+            let scheme = RingLPR::new_from_n(n as u64);
+            Self {
+                n,
+                m: n,
+                q: Modulus::from(scheme.q.get_q()),
+                alpha: scheme.alpha,
+            }
+        } else {
+            let scheme = DualRegev::new_from_n(n as u64);
+            Self {
+                n,
+                m: i64::try_from(&scheme.m).unwrap() as usize,
+                q: scheme.q,
+                alpha: scheme.alpha,
+            }
+        }
+    }
+
+    /// Checks whether the primal attack costs at least `2^λ` and the quantum dual
+    /// attack at least `2^{λ/2}` operations.
+    /// The attacks are estimated with at most `2n` samples, which suffice
+    /// for the optimal attacks in the considered parameter range and keep the estimation fast.
+    fn is_secure(&self, lambda: u32) -> bool {
+        let estimate = overall_security(
+            self.n,
+            self.m.min(2 * self.n),
+            q_to_f64(&self.alpha),
+            q_to_f64(&Q::from(&self.q)),
+        );
+
+        estimate.primal >= lambda as f64 && estimate.quantum_dual >= lambda as f64 / 2.0
+    }
+}
+
+/// Searches the smallest `n` s.t. the parameters generated for `n` provide
+/// `λ` bits of security against the primal attack and `λ / 2` bits of security
+/// against the quantum dual attack according to [`overall_security`].
+///
+/// The parameters are generated by [`DualRegev::new_from_n`] and `n` is found
+/// by doubling and a subsequent binary search.
+/// If `use_ring` is `true`, the parameters are generated by [`RingLPR::new_from_n`]
+/// and the smallest suitable power of two is chosen for `n`.
+///
+/// Parameters:
+/// - `lambda`: the targeted bit security
+/// - `use_ring`: specifies whether parameters for ring-LWE should be recommended
+///
+/// Returns the recommended [`LweParams`].
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::analysis::recommend::recommend_params;
+///
+/// let params = recommend_params(40, false);
+/// ```
+pub fn recommend_params(lambda: u32, use_ring: bool) -> LweParams {
+    if use_ring {
+        let mut n = 16;
+        loop {
+            let params = LweParams::from_n(n, true);
+            if params.is_secure(lambda) {
+                return params;
+            }
+            n *= 2;
+        }
+    }
+
+    // find an insecure `lower` and a secure `upper` bound for `n`
+    let mut lower = 10;
+    let mut params = LweParams::from_n(lower, false);
+    if params.is_secure(lambda) {
+        return params;
+    }
+    let mut upper = 2 * lower;
+    loop {
+        params = LweParams::from_n(upper, false);
+        if params.is_secure(lambda) {
+            break;
+        }
+        lower = upper;
+        upper *= 2;
+    }
+
+    // `params` contains the parameters for `upper` during the binary search
+    while upper - lower > 1 {
+        let mid = (lower + upper) / 2;
+        let mid_params = LweParams::from_n(mid, false);
+        if mid_params.is_secure(lambda) {
+            upper = mid;
+            params = mid_params;
+        } else {
+            lower = mid;
+        }
+    }
+
+    params
+}
+
+#[cfg(test)]
+mod test_recommend_params {
+    use super::recommend_params;
+    use crate::{analysis::security::overall_security, utils::conversion::q_to_f64};
+    use qfall_math::rational::Q;
+
+    /// Ensures that 80 bits of security require `n >= 100`
+    /// and that the recommended parameters provide the targeted security.
+    #[test]
+    fn lwe_80() {
+        let params = recommend_params(80, false);
+        let estimate = overall_security(
+            params.n,
+            params.m.min(2 * params.n),
+            q_to_f64(&params.alpha),
+            q_to_f64(&Q::from(&params.q)),
+        );
+
+        assert!(params.n >= 100);
+        assert!(estimate.primal >= 80.0);
+        assert!(estimate.quantum_dual >= 40.0);
+    }
+
+    /// Ensures that ring parameters have a power of two as dimension.
+    #[test]
+    fn ring_128() {
+        let params = recommend_params(128, true);
+
+        assert!(params.n.is_power_of_two());
+        assert_eq!(params.n, params.m);
+    }
+
+    /// Ensures that a larger security level does not result in a smaller dimension.
+    #[test]
+    fn monotone_in_lambda() {
+        assert!(recommend_params(32, true).n <= recommend_params(64, true).n);
+    }
+}
@@ -0,0 +1,381 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains cost models of attacks against LWE in the
+//! core-SVP methodology of [\[1\]](<index.html#:~:text=[1]>), i.e. the cost of an attack
+//! is the cost of a single call to an SVP oracle in dimension `β`, which is
+//! `2^{0.292 β}` classically and `2^{0.265 β}` quantumly.
+//!
+//! All functions use the convention of the Lattice Estimator
+//! [\[2\]](<index.html#:~:text=[2]>), where `α * q` is the Gaussian parameter
+//! of the error, i.e. its standard deviation is `α * q / sqrt(2π)`.
+//! The secret is assumed to be distributed as the error.
+
+use std::f64::consts::{E, PI};
+
+/// The exponent of classical sieving, i.e. an SVP call in dimension `β` costs `2^{0.292 β}`.
+const CLASSICAL_SIEVING: f64 = 0.292;
+
+/// The exponent of quantum sieving using Grover's search,
+/// i.e. an SVP call in dimension `β` costs `2^{0.265 β}`.
+const QUANTUM_SIEVING: f64 = 0.265;
+
+/// The smallest block size considered, as smaller block sizes are not
+/// well described by the root Hermite factor.
+/// Lattices of smaller dimension `d` are reduced with block size `d`.
+const MIN_BLOCK_SIZE: usize = 40;
+
+/// Returns the root Hermite factor `δ` achieved by BKZ with block size `beta`, i.e.
+/// `δ = ((π β)^{1/β} * β / (2π e))^{1/(2(β - 1))}`.
+fn root_hermite_factor(beta: usize) -> f64 {
+    let beta = beta as f64;
+    ((PI * beta).powf(1.0 / beta) * beta / (2.0 * PI * E)).powf(1.0 / (2.0 * (beta - 1.0)))
+}
+
+/// Checks that `alpha` and `q` describe a valid LWE instance.
+fn assert_valid_parameters(alpha: f64, q: f64) {
+    assert!(alpha > 0.0, "alpha must be positive.");
+    assert!(q > 1.0, "q must be larger than 1.");
+}
+
+/// Returns the smallest block size `β` for which the primal attack using at most
+/// `m_max` LWE samples succeeds or `None` if no block size succeeds.
+///
+/// The primal attack embeds the LWE instance into a uSVP instance of dimension
+/// `d = m + n + 1` and volume `q^m`. According to [\[1\]](<index.html#:~:text=[1]>)
+/// BKZ with block size `β` finds the unique shortest vector if
+/// `σ * sqrt(β) <= δ^{2β - d} * q^{m / d}`.
+fn primal_block_size(n: usize, m_max: usize, alpha: f64, q: f64) -> Option<usize> {
+    let log_sigma = (alpha * q / (2.0 * PI).sqrt()).ln();
+    let log_q = q.ln();
+
+    let mut best: Option<usize> = None;
+    for m in 1..=m_max {
+        let d = m + n + 1;
+        for beta in MIN_BLOCK_SIZE.min(d)..=d {
+            if best.is_some_and(|best| beta >= best) {
+                break;
+            }
+
+            let lhs = log_sigma + 0.5 * (beta as f64).ln();
+            let rhs = (2.0 * beta as f64 - d as f64) * root_hermite_factor(beta).ln()
+                + m as f64 / d as f64 * log_q;
+            if lhs <= rhs {
+                best = Some(beta);
+                break;
+            }
+        }
+    }
+
+    best
+}
+
+/// Returns `sieving_exponent * β` for the smallest successful block size `β` of the
+/// primal attack using at most `m_max` samples or [`f64::INFINITY`] if there is none.
+fn primal_attack_cost(n: usize, m_max: usize, alpha: f64, q: f64, sieving_exponent: f64) -> f64 {
+    assert_valid_parameters(alpha, q);
+
+    match primal_block_size(n, m_max, alpha, q) {
+        Some(beta) => sieving_exponent * beta as f64,
+        None => f64::INFINITY,
+    }
+}
+
+/// Estimates the bit security of LWE against the classical primal (uSVP) attack
+/// as described in [\[1\]](<index.html#:~:text=[1]>).
+/// The number of samples `m <= 2n` and the block size `β` are chosen to minimize
+/// the cost `2^{0.292 β}`.
+///
+/// Parameters:
+/// - `n`: the dimension of the secret
+/// - `alpha`: the relative Gaussian parameter of the error
+/// - `q`: the modulus
+///
+/// Returns the base-2 logarithm of the cost of the attack or [`f64::INFINITY`]
+/// if the attack does not succeed for any block size.
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::analysis::security::primal_attack_bits;
+///
+/// let bits = primal_attack_bits(512, 0.0023, 3329.0);
+/// ```
+///
+/// # Panics ...
+/// - if `alpha <= 0` or `q <= 1`.
+pub fn primal_attack_bits(n: usize, alpha: f64, q: f64) -> f64 {
+    primal_attack_cost(n, 2 * n, alpha, q, CLASSICAL_SIEVING)
+}
+
+/// Estimates the bit security of LWE against the quantum primal (uSVP) attack,
+/// where the SVP oracle uses sieving accelerated by Grover's search costing `2^{0.265 β}`.
+/// Otherwise, it equals [`primal_attack_bits`].
+///
+/// Parameters:
+/// - `n`: the dimension of the secret
+/// - `alpha`: the relative Gaussian parameter of the error
+/// - `q`: the modulus
+///
+/// Returns the base-2 logarithm of the cost of the attack or [`f64::INFINITY`]
+/// if the attack does not succeed for any block size.
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::analysis::security::{primal_attack_bits, quantum_primal_bits};
+///
+/// assert!(quantum_primal_bits(512, 0.0023, 3329.0) < primal_attack_bits(512, 0.0023, 3329.0));
+/// ```
+///
+/// # Panics ...
+/// - if `alpha <= 0` or `q <= 1`.
+pub fn quantum_primal_bits(n: usize, alpha: f64, q: f64) -> f64 {
+    primal_attack_cost(n, 2 * n, alpha, q, QUANTUM_SIEVING)
+}
+
+/// The base-2 logarithm of the number of short vectors output by a sieve
+/// in dimension `β`, which is `0.2075 β`.
+const SIEVE_OUTPUT: f64 = 0.2075;
+
+/// Returns the cost of the dual attack using at most `m_max` samples,
+/// where an SVP call in dimension `β` costs `2^{sieving_exponent * β}`.
+///
+/// The dual attack reduces the lattice `{(v, w) ∈ Z^{m + n} : v^t A = w^t mod q}`
+/// of dimension `d = m + n` and volume `q^n` with BKZ of block size `β`.
+/// The final sieve outputs `2^{0.2075 β}` vectors of length
+/// `ℓ = sqrt(4/3) * δ^d * q^{n / d}`. The inner product of such a vector with
+/// an LWE sample `(A, b)` is distributed with Gaussian parameter `ℓ * α * q`,
+/// which is distinguishable from uniform with advantage `ε = exp(-π (ℓ α)^2)`.
+/// Distinguishing needs `1 / ε^2` vectors. Following the correction of
+/// [\[3\]](<index.html#:~:text=[3]>), the vectors of the final sieve are reused, s.t.
+/// only `max(1, ε^{-2} / 2^{0.2075 β})` reductions are needed.
+fn dual_attack_cost(n: usize, m_max: usize, alpha: f64, q: f64, sieving_exponent: f64) -> f64 {
+    assert_valid_parameters(alpha, q);
+
+    let log_q = q.log2();
+    let log_sieve_length = 0.5 * (4.0_f64 / 3.0).log2();
+
+    let mut best = f64::INFINITY;
+    for m in 1..=m_max {
+        let d = m + n;
+        for beta in MIN_BLOCK_SIZE.min(d)..=d {
+            let sieving_cost = sieving_exponent * beta as f64;
+            if sieving_cost >= best {
+                break;
+            }
+
+            let log_length = d as f64 * root_hermite_factor(beta).log2()
+                + n as f64 / d as f64 * log_q
+                + log_sieve_length;
+            // log_2(1 / ε^2) = 2π (ℓ α)^2 * log_2(e)
+            let log_repetitions = 2.0 * PI * (log_length.exp2() * alpha).powi(2) * E.log2();
+            let cost = sieving_cost + (log_repetitions - SIEVE_OUTPUT * beta as f64).max(0.0);
+
+            best = best.min(cost);
+        }
+    }
+
+    best
+}
+
+/// Estimates the bit security of LWE with `m` samples against the classical dual attack
+/// including the reuse of short vectors of [\[3\]](<index.html#:~:text=[3]>).
+/// The number of used samples and the block size are chosen to minimize the cost.
+///
+/// Parameters:
+/// - `n`: the dimension of the secret
+/// - `m`: the number of available LWE samples
+/// - `alpha`: the relative Gaussian parameter of the error
+/// - `q`: the modulus
+///
+/// Returns the base-2 logarithm of the cost of the attack or [`f64::INFINITY`]
+/// if `m = 0`.
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::analysis::security::dual_attack_bits;
+///
+/// let bits = dual_attack_bits(512, 512, 0.0023, 3329.0);
+/// ```
+///
+/// # Panics ...
+/// - if `alpha <= 0` or `q <= 1`.
+pub fn dual_attack_bits(n: usize, m: usize, alpha: f64, q: f64) -> f64 {
+    dual_attack_cost(n, m, alpha, q, CLASSICAL_SIEVING)
+}
+
+/// The estimated bit security of an LWE instance against the primal and the dual
+/// attack on classical and quantum computers.
+///
+/// Attributes:
+/// - `primal`: the estimate of [`primal_attack_bits`] using at most `m` samples
+/// - `dual`: the estimate of [`dual_attack_bits`]
+/// - `quantum_primal`: the estimate of [`quantum_primal_bits`] using at most `m` samples
+/// - `quantum_dual`: the estimate of [`dual_attack_bits`] with quantum sieving
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SecurityEstimate {
+    pub primal: f64,
+    pub dual: f64,
+    pub quantum_primal: f64,
+    pub quantum_dual: f64,
+}
+
+/// Estimates the bit security of LWE with `m` samples against the primal and
+/// the dual attack on classical and quantum computers.
+///
+/// Parameters:
+/// - `n`: the dimension of the secret
+/// - `m`: the number of available LWE samples
+/// - `alpha`: the relative Gaussian parameter of the error
+/// - `q`: the modulus
+///
+/// Returns a [`SecurityEstimate`] containing all four estimates.
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::analysis::security::overall_security;
+///
+/// let estimate = overall_security(256, 512, 0.0024, 3329.0);
+///
+/// assert!(estimate.quantum_primal < estimate.primal);
+/// assert!(estimate.quantum_dual < estimate.dual);
+/// ```
+///
+/// # Panics ...
+/// - if `alpha <= 0` or `q <= 1`.
+pub fn overall_security(n: usize, m: usize, alpha: f64, q: f64) -> SecurityEstimate {
+    SecurityEstimate {
+        primal: primal_attack_cost(n, m, alpha, q, CLASSICAL_SIEVING),
+        dual: dual_attack_cost(n, m, alpha, q, CLASSICAL_SIEVING),
+        quantum_primal: primal_attack_cost(n, m, alpha, q, QUANTUM_SIEVING),
+        quantum_dual: dual_attack_cost(n, m, alpha, q, QUANTUM_SIEVING),
+    }
+}
+
+#[cfg(test)]
+mod test_primal_attack {
+    use super::{primal_attack_bits, quantum_primal_bits};
+
+    /// Returns `α` for an error with standard deviation `sigma` modulo `q`.
+    fn alpha(sigma: f64, q: f64) -> f64 {
+        sigma * (2.0 * std::f64::consts::PI).sqrt() / q
+    }
+
+    /// Ensures that the estimates match the core-SVP estimates of the primal attack
+    /// on Kyber's key recovery, i.e. `(n, η) = (512, 3), (768, 2), (1024, 2)`
+    /// with `q = 3329`, which are `118 / 107`, `182 / 165` and `256 / 232` bits.
+    /// The centered binomial distribution with parameter `η` has standard deviation
+    /// `sqrt(η / 2)`.
+    #[test]
+    fn kyber_reference_values() {
+        let q = 3329.0;
+        for (n, eta, classical, quantum) in [
+            (512, 3.0, 118.0, 107.0),
+            (768, 2.0, 182.0, 165.0),
+            (1024, 2.0, 256.0, 232.0),
+        ] {
+            let alpha = alpha(f64::sqrt(eta / 2.0), q);
+
+            assert!((primal_attack_bits(n, alpha, q) - classical).abs() <= 2.0);
+            assert!((quantum_primal_bits(n, alpha, q) - quantum).abs() <= 2.0);
+        }
+    }
+
+    /// Ensures that a larger error increases the cost of the attack.
+    #[test]
+    fn monotone_in_alpha() {
+        let q = 3329.0;
+
+        assert!(
+            primal_attack_bits(256, alpha(1.0, q), q) < primal_attack_bits(256, alpha(4.0, q), q)
+        );
+    }
+
+    /// Ensures that instances whose error covers the whole of `Z_q` are not broken.
+    #[test]
+    fn trivially_hard() {
+        assert_eq!(f64::INFINITY, primal_attack_bits(64, 10.0, 3329.0));
+        assert_eq!(f64::INFINITY, quantum_primal_bits(64, 10.0, 3329.0));
+    }
+
+    /// Ensures that a non-positive `alpha` results in a panic.
+    #[test]
+    #[should_panic]
+    fn invalid_alpha() {
+        let _ = primal_attack_bits(64, 0.0, 3329.0);
+    }
+}
+
+#[cfg(test)]
+mod test_dual_attack {
+    use super::{dual_attack_bits, primal_attack_bits};
+
+    /// Returns `α` for an error with standard deviation `sigma` modulo `q`.
+    fn alpha(sigma: f64, q: f64) -> f64 {
+        sigma * (2.0 * std::f64::consts::PI).sqrt() / q
+    }
+
+    /// Ensures that the dual attack is estimated close to the primal attack
+    /// for Kyber-like parameters, as both attacks are known to perform similarly.
+    #[test]
+    fn close_to_primal() {
+        let q = 3329.0;
+        let alpha = alpha(3.2, q);
+
+        let dual = dual_attack_bits(256, 512, alpha, q);
+        let primal = primal_attack_bits(256, alpha, q);
+
+        assert!((dual - primal).abs() <= 10.0);
+    }
+
+    /// Ensures that more samples do not increase the cost of the attack.
+    #[test]
+    fn monotone_in_m() {
+        let q = 3329.0;
+        let alpha = alpha(3.2, q);
+
+        assert!(dual_attack_bits(128, 256, alpha, q) <= dual_attack_bits(128, 64, alpha, q));
+    }
+
+    /// Ensures that no samples result in an infinite cost.
+    #[test]
+    fn no_samples() {
+        assert_eq!(f64::INFINITY, dual_attack_bits(128, 0, 0.01, 3329.0));
+    }
+}
+
+#[cfg(test)]
+mod test_overall_security {
+    use super::overall_security;
+
+    /// Ensures that increasing `n` raises all four estimates.
+    #[test]
+    fn increasing_n() {
+        let q = 3329.0;
+        let alpha = 3.2 * (2.0 * std::f64::consts::PI).sqrt() / q;
+
+        let small = overall_security(128, 256, alpha, q);
+        let large = overall_security(256, 512, alpha, q);
+
+        assert!(small.primal < large.primal);
+        assert!(small.dual < large.dual);
+        assert!(small.quantum_primal < large.quantum_primal);
+        assert!(small.quantum_dual < large.quantum_dual);
+    }
+
+    /// Ensures that quantum estimates are smaller than the classical ones.
+    #[test]
+    fn quantum_smaller() {
+        let q = 3329.0;
+        let alpha = 3.2 * (2.0 * std::f64::consts::PI).sqrt() / q;
+
+        let estimate = overall_security(256, 512, alpha, q);
+
+        assert!(estimate.quantum_primal < estimate.primal);
+        assert!(estimate.quantum_dual < estimate.dual);
+    }
+}
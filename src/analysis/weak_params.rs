@@ -0,0 +1,121 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains a detection of LWE parameters, which look plausible,
+//! but are insecure or result in incorrect schemes.
+
+use qfall_math::{integer::Z, rational::Q};
+
+/// The smallest dimension `n` considered secure for production.
+const MIN_PRODUCTION_N: usize = 64;
+
+/// Checks the LWE parameters `(n, m, q, α)` for common mistakes and returns
+/// a warning for every detected weakness. The following weaknesses are detected:
+/// - `n < 64`, as such small dimensions are broken within seconds
+/// - `α * q < 1`, as the error is mostly zero and the secret
+///     can be computed via Gaussian elimination
+/// - `α >= 1/4`, as the error covers a quarter of `Z_q` and
+///     decryption fails for every ciphertext
+/// - `m <= n`, as constructions like [`DualRegev`](crate::construction::pk_encryption::DualRegev)
+///     require `m > (n + 1) log q` samples for correctness and security
+///
+/// As `α` is the relative Gaussian parameter, the absolute error `α * q` has to be
+/// larger than `1` for security. Hence, decryption failures are reported for `α >= 1/4`
+/// instead of `α * q > 1`, which holds for all secure parameter sets including
+/// [`recommend_params`](super::recommend::recommend_params).
+///
+/// An empty list does not imply that the parameters are secure.
+/// Use [`overall_security`](super::security::overall_security) to estimate their security.
+///
+/// Parameters:
+/// - `n`: the dimension of the secret
+/// - `m`: the number of LWE samples
+/// - `q`: the modulus
+/// - `alpha`: the relative Gaussian parameter of the error
+///
+/// Returns a list of warnings, which is empty if no weakness was detected.
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::analysis::weak_params::detect_weak_params;
+/// use qfall_math::{integer::Z, rational::Q};
+///
+/// let warnings = detect_weak_params(4, 40, &Z::from(113), &Q::from(0.01));
+///
+/// assert_eq!(vec!["n < 64: insecure for production"], warnings);
+/// ```
+pub fn detect_weak_params(n: usize, m: usize, q: &Z, alpha: &Q) -> Vec<String> {
+    let mut warnings = Vec::new();
+
+    if n < MIN_PRODUCTION_N {
+        warnings.push(String::from("n < 64: insecure for production"));
+    }
+    if alpha * q < Q::ONE {
+        warnings.push(String::from(
+            "alpha * q < 1: the error is too small, LWE is solvable by Gaussian elimination",
+        ));
+    }
+    if alpha >= &Q::from((1, 4)) {
+        warnings.push(String::from("alpha >= 1/4: decryption always fails"));
+    }
+    if m <= n {
+        warnings.push(String::from("m ≤ n: LWE trivially invertible"));
+    }
+
+    warnings
+}
+
+#[cfg(test)]
+mod test_detect_weak_params {
+    use super::detect_weak_params;
+    use crate::{
+        analysis::recommend::recommend_params,
+        construction::identity_based_encryption::DualRegevIBE,
+    };
+    use qfall_math::{integer::Z, rational::Q};
+
+    /// Ensures that the default parameters of [`DualRegevIBE`] with `n = 4` trigger a warning.
+    #[test]
+    fn default_ibe() {
+        let ibe = DualRegevIBE::default();
+        let dual_regev = &ibe.dual_regev;
+
+        let warnings = detect_weak_params(
+            i64::try_from(&dual_regev.n).unwrap() as usize,
+            i64::try_from(&dual_regev.m).unwrap() as usize,
+            &Z::from(&dual_regev.q),
+            &dual_regev.alpha,
+        );
+
+        assert!(!warnings.is_empty());
+        assert!(warnings.contains(&String::from("n < 64: insecure for production")));
+    }
+
+    /// Ensures that the recommended parameters for 128 bits of security trigger no warning.
+    #[test]
+    fn recommended_128() {
+        let params = recommend_params(128, false);
+
+        let warnings = detect_weak_params(params.n, params.m, &Z::from(&params.q), &params.alpha);
+
+        assert!(warnings.is_empty());
+    }
+
+    /// Ensures that every weakness is detected.
+    #[test]
+    fn all_weaknesses() {
+        let warnings = detect_weak_params(16, 16, &Z::from(17), &Q::from((1, 2)));
+        let tiny_error = detect_weak_params(128, 4096, &Z::from(7681), &Q::from((1, 10000)));
+
+        assert_eq!(3, warnings.len());
+        assert!(warnings[1].starts_with("alpha >= 1/4"));
+        assert!(warnings[2].starts_with("m ≤ n"));
+        assert_eq!(1, tiny_error.len());
+        assert!(tiny_error[0].starts_with("alpha * q < 1"));
+    }
+}
@@ -17,7 +17,30 @@
 //! 2. Explicit implementations of the trait, e.g.
 //!     [`RingLPR`](pk_encryption::RingLPR).
 
+pub mod anonymous_credential;
+pub mod attribute_based_encryption;
+pub mod auth_lattice_ke;
+pub mod broadcast_encryption;
+pub mod ciphertext_cache;
+pub mod coin_flipping;
+pub mod commitment;
+pub mod functional_encryption;
+pub mod functional_mac;
 pub mod hash;
+pub mod homomorphic_encryption;
 pub mod identity_based_encryption;
+pub mod kdf;
+pub mod kem;
+pub mod key_vault;
+pub mod lattice_dh;
+pub mod lattice_symmetric_enc;
+pub mod oblivious_prf;
+pub mod oblivious_transfer;
+pub mod pake;
+pub mod pir;
 pub mod pk_encryption;
+pub mod predicate_encryption;
+pub mod pvss;
+pub mod random_beacon;
 pub mod signature;
+pub mod vrf;
@@ -0,0 +1,525 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains an anonymous credential scheme, where an issuer extracts
+//! a [`DualRegevIBE`] secret key for a pseudonym of a user, i.e. a commitment
+//! to the user's real identity, and the user proves possession of the credential
+//! with a non-interactive zero-knowledge proof without revealing the pseudonym.
+
+use crate::{
+    construction::{
+        hash::sha256::sha256,
+        identity_based_encryption::{DualRegevIBE, IBEScheme},
+    },
+    primitive::psf::PSF,
+    utils::conversion::q_to_f64,
+};
+use qfall_math::{
+    integer::{MatZ, Z},
+    integer_mod_q::MatZq,
+    rational::{MatQ, Q},
+    traits::{Concatenate, GetNumColumns, GetNumRows, SetEntry},
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// The number of parallel repetitions of the sigma protocol with binary challenges,
+/// i.e. the soundness error of a [`CredProof`] is `2^{-REPETITIONS}`.
+const REPETITIONS: usize = 32;
+
+/// A credential issued by [`AnonymousCredential::issue`].
+///
+/// Attributes:
+/// - `preimage`: the short vector `x` with `A * x = u_0 + c`,
+///     where `c` is the commitment to the identity of the holder
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Credential {
+    pub preimage: MatZ,
+}
+
+/// A proof of possession of a [`Credential`] generated by [`AnonymousCredential::show`].
+///
+/// Attributes:
+/// - `commitments`: the first messages `w_i = [A | B] * y_i` of all repetitions
+/// - `responses`: the responses `z_i = y_i + b_i * [x | -r]^t` of all repetitions,
+///     where `b_i` is the `i`-th bit of the Fiat-Shamir challenge
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CredProof {
+    pub commitments: Vec<MatZq>,
+    pub responses: Vec<MatZ>,
+}
+
+/// This struct manages and stores the public parameters of an anonymous credential scheme
+/// based on [`DualRegevIBE`].
+///
+/// A user commits to its identity as `c = B * r`, where the binary opening `r`
+/// contains the bits of `SHA-256(identity)` and uniformly random bits.
+/// The issuer extracts the credential `x` for the pseudonym `c` with
+/// the [`PSF`] of the [`DualRegevIBE`], i.e. `A * x = u_0 + c`.
+/// The syndrome `u_0 + c` is used instead of `H(c)` as in [`DualRegevIBE::extract`],
+/// s.t. `[A | B] * [x | -r]^t = u_0` is a linear relation with a short witness
+/// and public statement.
+/// Possession of a credential is proven by a Fiat-Shamir transformed sigma protocol
+/// with rejection sampling for this relation, which is bound to a message
+/// and reveals neither `c` nor `x`. Hence, two showings can not be linked.
+///
+/// **WARNING:** The proof is only a proof of knowledge of a short witness
+/// for the relation, whose norm is slightly larger than the norm of `[x | -r]^t`.
+/// The issuer does not check that the pseudonym is a commitment with a binary opening.
+///
+/// Attributes:
+/// - `ibe`: the [`DualRegevIBE`] instance used to issue credentials
+/// - `commitment_key`: the commitment matrix `B ∈ Z_q^{n x l}`
+/// - `base`: the syndrome `u_0 ∈ Z_q^n`
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::construction::anonymous_credential::AnonymousCredential;
+///
+/// let scheme = AnonymousCredential::default();
+/// let (mpk, msk) = scheme.setup();
+///
+/// let (identity_commit, opening) = scheme.commit("Alice");
+/// let cred = scheme.issue(&mpk, &msk, &identity_commit);
+///
+/// let proof = scheme.show(&mpk, &cred, &opening, "message");
+/// assert!(scheme.verify(&mpk, "message", &proof));
+/// ```
+#[derive(Serialize, Deserialize)]
+pub struct AnonymousCredential {
+    pub ibe: DualRegevIBE,
+    pub commitment_key: MatZq,
+    pub base: MatZq,
+}
+
+impl AnonymousCredential {
+    /// Initializes an [`AnonymousCredential`] instance, which issues credentials
+    /// with `ibe`.
+    ///
+    /// The commitment matrix has `l = 256 + 2 * n * ⌈log_2(q)⌉` columns s.t.
+    /// a commitment to the 256 bits of `SHA-256(identity)` is hidden by the random bits.
+    ///
+    /// Parameters:
+    /// - `ibe`: the [`DualRegevIBE`] instance used to issue credentials
+    ///
+    /// Returns an [`AnonymousCredential`] instance.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::anonymous_credential::AnonymousCredential;
+    /// use qfall_crypto::construction::identity_based_encryption::DualRegevIBE;
+    ///
+    /// let scheme = AnonymousCredential::new(DualRegevIBE::new_from_n(4).unwrap());
+    /// ```
+    pub fn new(ibe: DualRegevIBE) -> Self {
+        let n = &ibe.dual_regev.n;
+        let q = &ibe.dual_regev.q;
+
+        // 2 * n * log(q) random bits ensure that B * r is statistically close to uniform
+        let l = Z::from(256) + 2 * n * Z::from(q).log_ceil(2).unwrap();
+        let commitment_key = MatZq::sample_uniform(n, &l, q);
+        let base = MatZq::sample_uniform(n, 1, q);
+
+        Self {
+            ibe,
+            commitment_key,
+            base,
+        }
+    }
+
+    /// Initializes an [`AnonymousCredential`] instance with `DualRegevIBE::new_from_n(n)`.
+    ///
+    /// Parameters:
+    /// - `n`: the security parameter
+    ///
+    /// Returns an [`AnonymousCredential`] instance.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::anonymous_credential::AnonymousCredential;
+    ///
+    /// let scheme = AnonymousCredential::new_from_n(4);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `n < 2`.
+    pub fn new_from_n(n: impl Into<Z>) -> Self {
+        Self::new(DualRegevIBE::new_from_n(n).unwrap())
+    }
+
+    /// Generates the master key pair of the issuer using [`DualRegevIBE::setup`](IBEScheme::setup).
+    ///
+    /// Returns the master public key `A` and the trapdoor of `A`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::anonymous_credential::AnonymousCredential;
+    ///
+    /// let scheme = AnonymousCredential::default();
+    /// let (mpk, msk) = scheme.setup();
+    /// ```
+    pub fn setup(&self) -> (MatZq, (MatZ, MatQ)) {
+        self.ibe.setup()
+    }
+
+    /// Commits to `identity` as `c = B * r`, where the first 256 entries of
+    /// the binary opening `r` are the bits of `SHA-256(identity)` and the remaining
+    /// entries are chosen uniformly at random.
+    ///
+    /// Parameters:
+    /// - `identity`: the real identity of the user
+    ///
+    /// Returns the commitment `c`, which is the pseudonym of the user,
+    /// and the opening `r`, which has to be kept secret.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::anonymous_credential::AnonymousCredential;
+    ///
+    /// let scheme = AnonymousCredential::default();
+    ///
+    /// let (identity_commit, opening) = scheme.commit("Alice");
+    /// ```
+    pub fn commit(&self, identity: &str) -> (MatZq, MatZ) {
+        let digest = Sha256::digest(identity.as_bytes());
+        let l = self.commitment_key.get_num_columns();
+
+        let mut opening = MatZ::new(l, 1);
+        for i in 0..l {
+            let bit = if i < 256 {
+                (digest[i as usize / 8] >> (7 - i % 8)) & 1 == 1
+            } else {
+                rand::random::<bool>()
+            };
+            opening.set_entry(i, 0, bit as i64).unwrap();
+        }
+
+        (&self.commitment_key * &opening, opening)
+    }
+
+    /// Issues a credential for the pseudonym `identity_commit` by sampling
+    /// a short preimage `x` of `u_0 + c` with the [`PSF`] of the [`DualRegevIBE`].
+    ///
+    /// Parameters:
+    /// - `master_pk`: the master public key `A` of the issuer
+    /// - `master_sk`: the trapdoor of `A`
+    /// - `identity_commit`: the pseudonym `c` of the user generated by [`AnonymousCredential::commit`]
+    ///
+    /// Returns the [`Credential`] for `identity_commit`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::anonymous_credential::AnonymousCredential;
+    ///
+    /// let scheme = AnonymousCredential::default();
+    /// let (mpk, msk) = scheme.setup();
+    /// let (identity_commit, _) = scheme.commit("Alice");
+    ///
+    /// let cred = scheme.issue(&mpk, &msk, &identity_commit);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if the dimensions or the modulus of `identity_commit` do not match `u_0`.
+    pub fn issue(
+        &self,
+        master_pk: &MatZq,
+        master_sk: &(MatZ, MatQ),
+        identity_commit: &MatZq,
+    ) -> Credential {
+        let syndrome = &self.base + identity_commit;
+        Credential {
+            preimage: self.ibe.psf.samp_p(master_pk, master_sk, &syndrome),
+        }
+    }
+
+    /// Proves possession of `cred` bound to `message` without revealing
+    /// the pseudonym or the credential.
+    ///
+    /// For every repetition, `y_i` is sampled from a discrete Gaussian and `w_i = [A | B] * y_i`.
+    /// The challenge bits `b_i` are derived from `SHA-256` of the statement, all `w_i`
+    /// and `message`, and `z_i = y_i + b_i * [x | -r]^t` is output with rejection sampling,
+    /// s.t. the distribution of `z_i` is independent of the witness.
+    ///
+    /// Parameters:
+    /// - `master_pk`: the master public key `A` of the issuer
+    /// - `cred`: the [`Credential`] of the user
+    /// - `opening`: the opening `r` of the pseudonym, for which `cred` was issued
+    /// - `message`: the message, to which the proof is bound
+    ///
+    /// Returns a [`CredProof`] of possession of `cred`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::anonymous_credential::AnonymousCredential;
+    ///
+    /// let scheme = AnonymousCredential::default();
+    /// let (mpk, msk) = scheme.setup();
+    /// let (identity_commit, opening) = scheme.commit("Alice");
+    /// let cred = scheme.issue(&mpk, &msk, &identity_commit);
+    ///
+    /// let proof = scheme.show(&mpk, &cred, &opening, "message");
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if the dimensions of `cred` or `opening` do not match the public parameters.
+    pub fn show(
+        &self,
+        master_pk: &MatZq,
+        cred: &Credential,
+        opening: &MatZ,
+        message: &str,
+    ) -> CredProof {
+        let witness = cred
+            .preimage
+            .concat_vertical(&(MatZ::new(opening.get_num_rows(), 1) - opening))
+            .unwrap();
+        let statement = self.statement_matrix(master_pk);
+        let s = self.gaussian_parameter(master_pk);
+        let s_f64 = q_to_f64(&s);
+        // ln(M) for the rejection sampling constant M = exp(12 / α + 1 / (2 * α^2)), where α = 12 * REPETITIONS
+        let log_m = (1.0 / REPETITIONS as f64) + 1.0 / (288.0 * (REPETITIONS * REPETITIONS) as f64);
+
+        // Fiat-Shamir with aborts: restart until all repetitions are accepted
+        'attempt: loop {
+            let masks: Vec<MatZ> = (0..REPETITIONS)
+                .map(|_| {
+                    MatZ::sample_d_common(witness.get_num_rows(), &self.ibe.dual_regev.n, &s)
+                        .unwrap()
+                })
+                .collect();
+            let commitments: Vec<MatZq> = masks.iter().map(|y| &statement * y).collect();
+            let challenge = self.challenge(master_pk, &commitments, message);
+
+            let mut responses = Vec::with_capacity(REPETITIONS);
+            for (y, bit) in masks.iter().zip(challenge) {
+                if !bit {
+                    responses.push(y.clone());
+                    continue;
+                }
+
+                let z = y + &witness;
+                // accept with probability min(1, D_s(z) / (M * D_{s, witness}(z)))
+                let exponent = Q::from(y.norm_eucl_sqrd().unwrap() - z.norm_eucl_sqrd().unwrap());
+                let log_ratio = std::f64::consts::PI * q_to_f64(&exponent) / (s_f64 * s_f64);
+                if rand::random::<f64>().ln() > log_ratio - log_m {
+                    continue 'attempt;
+                }
+                responses.push(z);
+            }
+
+            return CredProof {
+                commitments,
+                responses,
+            };
+        }
+    }
+
+    /// Verifies that `proof` is a valid proof of possession of a credential
+    /// issued under `master_pk`, which is bound to `message`.
+    ///
+    /// Parameters:
+    /// - `master_pk`: the master public key `A` of the issuer
+    /// - `message`: the message, to which the proof is bound
+    /// - `proof`: the [`CredProof`] that is verified
+    ///
+    /// Returns `true` if all responses are short and satisfy
+    /// `[A | B] * z_i = w_i + b_i * u_0`, and `false` otherwise.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::anonymous_credential::AnonymousCredential;
+    ///
+    /// let scheme = AnonymousCredential::default();
+    /// let (mpk, msk) = scheme.setup();
+    /// let (identity_commit, opening) = scheme.commit("Alice");
+    /// let cred = scheme.issue(&mpk, &msk, &identity_commit);
+    /// let proof = scheme.show(&mpk, &cred, &opening, "message");
+    ///
+    /// assert!(scheme.verify(&mpk, "message", &proof));
+    /// assert!(!scheme.verify(&mpk, "other message", &proof));
+    /// ```
+    pub fn verify(&self, master_pk: &MatZq, message: &str, proof: &CredProof) -> bool {
+        if proof.commitments.len() != REPETITIONS || proof.responses.len() != REPETITIONS {
+            return false;
+        }
+
+        let statement = self.statement_matrix(master_pk);
+        let dimension = statement.get_num_columns();
+        // ||z||^2 <= s^2 * dim holds with overwhelming probability for z <- D_s^dim
+        let s = self.gaussian_parameter(master_pk);
+        let bound = &s * &s * Q::from(dimension);
+
+        let challenge = self.challenge(master_pk, &proof.commitments, message);
+        for ((w, z), bit) in proof
+            .commitments
+            .iter()
+            .zip(&proof.responses)
+            .zip(challenge)
+        {
+            if z.get_num_rows() != dimension
+                || z.get_num_columns() != 1
+                || Q::from(z.norm_eucl_sqrd().unwrap()) > bound
+                || w.get_num_rows() != self.base.get_num_rows()
+                || w.get_num_columns() != 1
+                || w.get_mod() != self.base.get_mod()
+            {
+                return false;
+            }
+
+            let expected = if bit { w + &self.base } else { w.clone() };
+            if &statement * z != expected {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Returns the matrix `[A | B]` of the proven relation `[A | B] * [x | -r]^t = u_0`.
+    fn statement_matrix(&self, master_pk: &MatZq) -> MatZq {
+        master_pk.concat_horizontal(&self.commitment_key).unwrap()
+    }
+
+    /// Returns the Gaussian parameter `s = sqrt(2π) * 12 * REPETITIONS * T` of the masks,
+    /// where `T = sqrt(r^2 * m + l)` bounds the norm of the witness `[x | -r]^t`,
+    /// s.t. all repetitions are accepted with probability roughly `1 / e`.
+    fn gaussian_parameter(&self, master_pk: &MatZq) -> Q {
+        let r = &self.ibe.psf.s;
+        let witness_norm_sqrd = r * r * Q::from(master_pk.get_num_columns())
+            + Q::from(self.commitment_key.get_num_columns());
+        let factor = (2.0 * std::f64::consts::PI).sqrt() * 12.0 * REPETITIONS as f64;
+
+        Q::from(factor) * witness_norm_sqrd.sqrt()
+    }
+
+    /// Derives the challenge bits from `SHA-256` of the statement, the commitments
+    /// and the message.
+    fn challenge(&self, master_pk: &MatZq, commitments: &[MatZq], message: &str) -> Vec<bool> {
+        let mut transcript = format!("{master_pk} {} {}", self.commitment_key, self.base);
+        for commitment in commitments {
+            transcript.push_str(&format!(" {commitment}"));
+        }
+        transcript.push_str(&format!(" {message}"));
+
+        let digest = sha256(&transcript);
+        (0..REPETITIONS)
+            .map(|i| {
+                let nibble = u8::from_str_radix(&digest[i / 4..i / 4 + 1], 16).unwrap();
+                (nibble >> (3 - i % 4)) & 1 == 1
+            })
+            .collect()
+    }
+}
+
+impl Default for AnonymousCredential {
+    /// Initializes an [`AnonymousCredential`] instance with `DualRegevIBE::default()`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::anonymous_credential::AnonymousCredential;
+    ///
+    /// let scheme = AnonymousCredential::default();
+    /// ```
+    fn default() -> Self {
+        Self::new(DualRegevIBE::default())
+    }
+}
+
+#[cfg(test)]
+mod test_anonymous_credential {
+    use super::{AnonymousCredential, REPETITIONS};
+    use qfall_math::{integer::Z, traits::GetEntry};
+
+    /// Ensures that valid credentials verify for several users and messages.
+    #[test]
+    fn cycle() {
+        let scheme = AnonymousCredential::default();
+        let (mpk, msk) = scheme.setup();
+
+        for identity in ["Alice", "Bob"] {
+            let (identity_commit, opening) = scheme.commit(identity);
+            let cred = scheme.issue(&mpk, &msk, &identity_commit);
+
+            for message in ["", "Hello World!"] {
+                let proof = scheme.show(&mpk, &cred, &opening, message);
+                assert!(scheme.verify(&mpk, message, &proof));
+            }
+        }
+    }
+
+    /// Ensures that proofs are rejected for another message, another issuer,
+    /// a wrong opening and modified responses.
+    #[test]
+    fn invalid_proofs_rejected() {
+        let scheme = AnonymousCredential::default();
+        let (mpk, msk) = scheme.setup();
+        let (other_mpk, _) = scheme.setup();
+        let (identity_commit, opening) = scheme.commit("Alice");
+        let (_, other_opening) = scheme.commit("Bob");
+        let cred = scheme.issue(&mpk, &msk, &identity_commit);
+
+        let proof = scheme.show(&mpk, &cred, &opening, "message");
+        assert!(!scheme.verify(&mpk, "other message", &proof));
+        assert!(!scheme.verify(&other_mpk, "message", &proof));
+
+        let wrong_opening = scheme.show(&mpk, &cred, &other_opening, "message");
+        assert!(!scheme.verify(&mpk, "message", &wrong_opening));
+
+        let mut modified = proof.clone();
+        modified.responses.swap(0, 1);
+        assert!(!scheme.verify(&mpk, "message", &modified));
+
+        let mut truncated = proof;
+        truncated.responses.truncate(REPETITIONS - 1);
+        assert!(!scheme.verify(&mpk, "message", &truncated));
+    }
+
+    /// Ensures that the verifier can not link two showings, i.e. neither the
+    /// pseudonym nor the credential is part of a proof, and two showings of the same
+    /// credential for the same message share no commitment or response.
+    #[test]
+    fn showings_unlinkable() {
+        let scheme = AnonymousCredential::default();
+        let (mpk, msk) = scheme.setup();
+        let (identity_commit, opening) = scheme.commit("Alice");
+        let cred = scheme.issue(&mpk, &msk, &identity_commit);
+
+        let proof_1 = scheme.show(&mpk, &cred, &opening, "message");
+        let proof_2 = scheme.show(&mpk, &cred, &opening, "message");
+
+        for commitment in &proof_1.commitments {
+            assert!(!proof_2.commitments.contains(commitment));
+        }
+        for response in &proof_1.responses {
+            assert!(!proof_2.responses.contains(response));
+        }
+        for proof in [&proof_1, &proof_2] {
+            assert!(!proof.commitments.contains(&identity_commit));
+            assert!(!proof.responses.contains(&cred.preimage));
+            let json = serde_json::to_string(proof).unwrap();
+            assert!(!json.contains(&identity_commit.to_string()));
+        }
+    }
+
+    /// Ensures that two commitments to the same identity differ,
+    /// but their openings start with the same digest of the identity.
+    #[test]
+    fn commitments_hiding() {
+        let scheme = AnonymousCredential::default();
+
+        let (commit_1, opening_1) = scheme.commit("Alice");
+        let (commit_2, opening_2) = scheme.commit("Alice");
+
+        assert_ne!(commit_1, commit_2);
+        for i in 0..256 {
+            let entry_1: Z = opening_1.get_entry(i, 0).unwrap();
+            let entry_2: Z = opening_2.get_entry(i, 0).unwrap();
+            assert_eq!(entry_1, entry_2);
+        }
+    }
+}
@@ -0,0 +1,30 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains attribute-based encryption schemes, where a
+//! user's secret key is bound to a policy over attributes and ciphertexts
+//! are bound to a set of attributes.
+//!
+//! The main references are listed in the following
+//! and will be further referenced in submodules by these numbers:
+//! - \[1\] Gentry, Craig and Peikert, Chris and Vaikuntanathan, Vinod (2008).
+//!     Trapdoors for hard lattices and new cryptographic constructions.
+//!     In: Proceedings of the fortieth annual ACM symposium on Theory of computing.
+//!     <https://dl.acm.org/doi/pdf/10.1145/1374376.1374407>
+//! - \[2\] Boyen, Xavier (2013).
+//!     Attribute-Based Functional Encryption on Lattices.
+//!     In: Theory of Cryptography - TCC 2013.
+//!     <https://doi.org/10.1007/978-3-642-36594-2_8>
+//! - \[3\] Lewko, Allison and Waters, Brent (2011).
+//!     Decentralizing Attribute-Based Encryption.
+//!     In: Advances in Cryptology - EUROCRYPT 2011.
+//!     <https://eprint.iacr.org/2010/351.pdf>
+
+mod kp_abe;
+
+pub use kp_abe::{KPABECipher, KPABEMasterPublicKey, KPABEUserKey, MonotoneSpanProgram, KPABE};
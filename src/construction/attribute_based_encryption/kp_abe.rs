@@ -0,0 +1,639 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains an implementation of a key-policy attribute-based
+//! encryption scheme, whose policies are monotone span programs, i.e.
+//! a linear secret sharing scheme (LSSS), that is instantiated via
+//! the GPV preimage sampleable function using G-trapdoors.
+
+use crate::{
+    primitive::psf::{PSF, PSFGPV},
+    sample::g_trapdoor::gadget_parameters::GadgetParameters,
+};
+use qfall_math::{
+    integer::{MatZ, Z},
+    integer_mod_q::{MatZq, Modulus, Zq},
+    rational::{MatQ, Q},
+    traits::{Concatenate, Distance, GetEntry, GetNumColumns, GetNumRows, Pow, SetEntry},
+};
+use serde::{Deserialize, Serialize};
+
+/// A monotone span program `(M, ρ)` describing an access policy according to
+/// [\[3\]](<index.html#:~:text=[3]>).
+/// A set `S` of attributes satisfies the policy if and only if the vector
+/// `(1, 0, ..., 0)` lies in the span of the rows `M_j` with `ρ(j) ∈ S`.
+///
+/// The entries of `M` are restricted to `{-1, 0, 1}`, which is the case for
+/// the monotone span programs generated from AND/OR formulas according to
+/// [\[3\]](<index.html#:~:text=[3]>). For these, the reconstruction coefficients
+/// `ω_j` computed during decryption lie in `{-1, 0, 1}` as well, which keeps
+/// the accumulated noise small. For arbitrary matrices, the coefficients are
+/// only determined mod `q` and usually not short, such that decryption fails.
+///
+/// Attributes:
+/// - `matrix`: the share-generating matrix `M ∈ Z^{l x d}`
+/// - `label_fn`: the labeling function `ρ`, where `label_fn[j]` is the
+///     attribute that row `j` of `matrix` is assigned to
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::construction::attribute_based_encryption::MonotoneSpanProgram;
+/// use qfall_math::integer::MatZ;
+/// use std::str::FromStr;
+///
+/// // policy: attribute 0 AND attribute 1
+/// let and = MonotoneSpanProgram::new(MatZ::from_str("[[1, 1],[0, -1]]").unwrap(), vec![0, 1]);
+/// // policy: attribute 0 OR attribute 1
+/// let or = MonotoneSpanProgram::new(MatZ::from_str("[[1],[1]]").unwrap(), vec![0, 1]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MonotoneSpanProgram {
+    pub matrix: MatZ,
+    pub label_fn: Vec<usize>,
+}
+
+impl MonotoneSpanProgram {
+    /// Initializes a [`MonotoneSpanProgram`] from a share-generating matrix
+    /// and its labeling function.
+    ///
+    /// Parameters:
+    /// - `matrix`: the share-generating matrix `M`
+    /// - `label_fn`: the attribute assigned to each row of `M`
+    ///
+    /// Returns a [`MonotoneSpanProgram`] instance.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::attribute_based_encryption::MonotoneSpanProgram;
+    /// use qfall_math::integer::MatZ;
+    /// use std::str::FromStr;
+    ///
+    /// let or = MonotoneSpanProgram::new(MatZ::from_str("[[1],[1]]").unwrap(), vec![0, 1]);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if the number of rows of `matrix` does not match the length of `label_fn`.
+    /// - if `matrix` contains an entry outside of `{-1, 0, 1}`.
+    pub fn new(matrix: MatZ, label_fn: Vec<usize>) -> Self {
+        assert_eq!(
+            matrix.get_num_rows(),
+            label_fn.len() as i64,
+            "Each row of the matrix has to be labeled with exactly one attribute."
+        );
+        let msp = Self { matrix, label_fn };
+        assert!(
+            msp.has_unit_entries(),
+            "The entries of the matrix have to be in {{-1, 0, 1}}."
+        );
+
+        msp
+    }
+
+    /// Checks whether all entries of the share-generating matrix lie in `{-1, 0, 1}`.
+    ///
+    /// Returns `true` if all entries are `-1`, `0`, or `1` and `false` otherwise.
+    fn has_unit_entries(&self) -> bool {
+        (0..self.matrix.get_num_rows()).all(|i| {
+            (0..self.matrix.get_num_columns()).all(|j| {
+                let entry: Z = self.matrix.get_entry(i, j).unwrap();
+                entry.abs() <= Z::ONE
+            })
+        })
+    }
+}
+
+/// The master public key of a [`KPABE`] instance.
+///
+/// Attributes:
+/// - `a`: a matrix `A_i ∈ Z_q^{n x m}` for each attribute `i`
+/// - `u`: the vector `u ∈ Z_q^n`, which is secret shared in the user keys
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KPABEMasterPublicKey {
+    pub a: Vec<MatZq>,
+    pub u: MatZq,
+}
+
+/// The secret key of a user w.r.t. a policy of a [`KPABE`] instance.
+///
+/// Attributes:
+/// - `policy`: the policy that a set of attributes has to satisfy to decrypt
+/// - `keys`: a short vector `e_j` with `A_{ρ(j)} * e_j = u_j`
+///     for each share `u_j` of `u`, i.e. each row `j` of the policy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KPABEUserKey {
+    pub policy: MonotoneSpanProgram,
+    pub keys: Vec<MatZ>,
+}
+
+/// A ciphertext of a [`KPABE`] instance.
+///
+/// Attributes:
+/// - `attrs`: the attributes the ciphertext is bound to
+/// - `c_attrs`: the ciphertext component `s^t * A_i + x_i^t` for each attribute `i` in `attrs`
+/// - `c_0`: the ciphertext component `s^t * u + x_0 + msg * ⌊q/2⌋`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KPABECipher {
+    pub attrs: Vec<usize>,
+    pub c_attrs: Vec<MatZq>,
+    pub c_0: MatZq,
+}
+
+/// This struct manages and stores the public parameters of a key-policy
+/// attribute-based encryption scheme, which is an LSSS-based
+/// instantiation of [\[2\]](<index.html#:~:text=[2]>) with the GPV-PSF from
+/// [\[1\]](<index.html#:~:text=[1]>) using G-trapdoors.
+///
+/// Each attribute `i` has its own matrix `A_i` with trapdoor.
+/// A user key for a policy `(M, ρ)` consists of preimages `e_j` of the
+/// shares `u_j = [u | w_2 | ... | w_d] * M_j^t` under `A_{ρ(j)}`.
+/// If the attributes of a ciphertext satisfy the policy, the shares
+/// can be recombined to obtain `s^t * u` approximately.
+///
+/// Attributes:
+/// - `n`: specifies the security parameter, which is not equal to the bit-security level
+/// - `m`: defines the dimension of the underlying lattice
+/// - `q`: specifies the modulus over which the encryption is computed
+/// - `alpha`: specifies the Gaussian parameter used for independent
+///     sampling from the discrete Gaussian distribution
+/// - `psf`: specifies the PSF used for generating the user keys
+/// - `attr_count`: the number of attributes in the attribute universe
+/// - `max_depth`: the maximal depth of a policy, i.e. policies can have
+///     at most `2^max_depth` rows
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::construction::attribute_based_encryption::{KPABE, MonotoneSpanProgram};
+/// use qfall_math::integer::{MatZ, Z};
+/// use std::str::FromStr;
+///
+/// let abe = KPABE::new_from_n(4, 2, 1);
+/// let (mpk, msk) = abe.setup();
+///
+/// // policy: attribute 0 AND attribute 1
+/// let policy = MonotoneSpanProgram::new(MatZ::from_str("[[1, 1],[0, -1]]").unwrap(), vec![0, 1]);
+/// let user_key = abe.key_gen(&mpk, &msk, &policy);
+///
+/// let cipher = abe.enc(&mpk, &[0, 1], Z::ONE);
+/// assert_eq!(Some(Z::ONE), abe.dec(&user_key, &cipher));
+///
+/// let cipher = abe.enc(&mpk, &[0], Z::ONE);
+/// assert_eq!(None, abe.dec(&user_key, &cipher));
+/// ```
+#[derive(Serialize, Deserialize)]
+pub struct KPABE {
+    n: Z,
+    m: Z,
+    q: Modulus,
+    alpha: Q,
+    pub psf: PSFGPV,
+    attr_count: usize,
+    max_depth: usize,
+}
+
+impl KPABE {
+    /// Initializes a [`KPABE`] instance for security parameter `n`,
+    /// `attr_count` many attributes, and policies with at most `2^max_depth` rows.
+    ///
+    /// The parameters are chosen similar to
+    /// [`DualRegevIBE::new_from_n`](crate::construction::identity_based_encryption::DualRegevIBE::new_from_n),
+    /// but `alpha` is decreased by a factor `2^max_depth` as the noise of
+    /// up to `2^max_depth` shares is accumulated during decryption.
+    ///
+    /// **WARNING:** Due to the [`PSF`] this schemes key generation is slow for n > 5.
+    ///
+    /// Parameters:
+    /// - `n`: the security parameter
+    /// - `attr_count`: the number of attributes
+    /// - `max_depth`: the maximal depth of supported policies
+    ///
+    /// Returns a [`KPABE`] instance.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::attribute_based_encryption::KPABE;
+    ///
+    /// let abe = KPABE::new_from_n(4, 3, 2);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `n < 2` or `attr_count < 1`.
+    pub fn new_from_n(n: impl Into<Z>, attr_count: usize, max_depth: usize) -> Self {
+        let n: Z = n.into();
+        assert!(
+            n >= Z::from(2),
+            "Security parameter n has to be larger than 1"
+        );
+        assert!(attr_count >= 1, "At least one attribute is required.");
+
+        let n_i64 = i64::try_from(&n).unwrap();
+        // these powers are chosen according to experience as for [`DualRegevIBE`]
+        let power = match n_i64 {
+            2..=3 => 10,
+            4 => 7,
+            5..=7 => 6,
+            _ => 5,
+        };
+        // generate prime q in [n^power / 2, n^power], which is required
+        // to invert the policy matrices during decryption
+        let upper_bound: Z = n.pow(power).unwrap();
+        let lower_bound = upper_bound.div_ceil(2);
+        let q = Modulus::from(Z::sample_prime_uniform(&lower_bound, &upper_bound).unwrap());
+
+        let gadget = GadgetParameters::init_default(&n, &q);
+        let log_q = Z::from(&q).log_ceil(2).unwrap();
+        let m = &gadget.m_bar + &n * &log_q;
+
+        let r: Q = m.sqrt();
+        let max_nr_shares = 1_i64 << max_depth;
+        let alpha = 1 / (&r * (2 * max_nr_shares) * (&m + Z::ONE).sqrt() * n.log(2).unwrap());
+
+        Self {
+            n,
+            m,
+            q,
+            alpha,
+            psf: PSFGPV {
+                gp: gadget,
+                s: r,
+                cdt: None,
+                cdt_cache: Default::default(),
+            },
+            attr_count,
+            max_depth,
+        }
+    }
+
+    /// Generates a master key pair by generating a matrix `A_i` together with
+    /// a trapdoor for each attribute `i` and a uniform vector `u`.
+    ///
+    /// Returns a tuple `(mpk, msk)`, where `msk` contains the short basis of
+    /// each `A_i` together with its GSO.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::attribute_based_encryption::KPABE;
+    ///
+    /// let abe = KPABE::new_from_n(4, 2, 1);
+    /// let (mpk, msk) = abe.setup();
+    /// ```
+    pub fn setup(&self) -> (KPABEMasterPublicKey, Vec<(MatZ, MatQ)>) {
+        let mut a = Vec::with_capacity(self.attr_count);
+        let mut msk = Vec::with_capacity(self.attr_count);
+        for _ in 0..self.attr_count {
+            let (a_i, td_i) = self.psf.trap_gen();
+            a.push(a_i);
+            msk.push(td_i);
+        }
+        let u = MatZq::sample_uniform(&self.n, 1, &self.q);
+
+        (KPABEMasterPublicKey { a, u }, msk)
+    }
+
+    /// Generates a user key for `policy` by secret sharing `u` via
+    /// `u_j = [u | w_2 | ... | w_d] * M_j^t` with uniform `w_k` and sampling
+    /// a short preimage `e_j` of `u_j` under `A_{ρ(j)}` for each row `j`.
+    ///
+    /// Parameters:
+    /// - `mpk`: the master public key
+    /// - `msk`: the master secret key, i.e. the trapdoors of all `A_i`
+    /// - `policy`: the policy the user key is generated for
+    ///
+    /// Returns a [`KPABEUserKey`] for `policy`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::attribute_based_encryption::{KPABE, MonotoneSpanProgram};
+    /// use qfall_math::integer::MatZ;
+    /// use std::str::FromStr;
+    ///
+    /// let abe = KPABE::new_from_n(4, 2, 1);
+    /// let (mpk, msk) = abe.setup();
+    ///
+    /// let policy = MonotoneSpanProgram::new(MatZ::from_str("[[1],[1]]").unwrap(), vec![0, 1]);
+    /// let user_key = abe.key_gen(&mpk, &msk, &policy);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `policy` has more than `2^max_depth` rows.
+    /// - if `policy` labels a row with an attribute `>= attr_count`.
+    /// - if the matrix of `policy` contains an entry outside of `{-1, 0, 1}`.
+    pub fn key_gen(
+        &self,
+        mpk: &KPABEMasterPublicKey,
+        msk: &[(MatZ, MatQ)],
+        policy: &MonotoneSpanProgram,
+    ) -> KPABEUserKey {
+        assert!(
+            policy.label_fn.len() <= 1 << self.max_depth,
+            "The policy has more than 2^max_depth rows."
+        );
+        assert!(
+            policy.label_fn.iter().all(|attr| *attr < self.attr_count),
+            "The policy contains an attribute that is not part of the attribute universe."
+        );
+        assert!(
+            policy.has_unit_entries(),
+            "The matrix of the policy has to have entries in {{-1, 0, 1}}."
+        );
+
+        // W = [u | w_2 | ... | w_d]
+        let nr_cols = policy.matrix.get_num_columns();
+        let mat_w = if nr_cols > 1 {
+            let w = MatZq::sample_uniform(&self.n, nr_cols - 1, &self.q);
+            mpk.u.concat_horizontal(&w).unwrap()
+        } else {
+            mpk.u.clone()
+        };
+
+        let matrix_t = policy.matrix.transpose();
+        let mut keys = Vec::with_capacity(policy.label_fn.len());
+        for (j, attr) in policy.label_fn.iter().enumerate() {
+            // u_j = W * M_j^t
+            let u_j = &mat_w * &matrix_t.get_column(j as i64).unwrap();
+            keys.push(self.psf.samp_p(&mpk.a[*attr], &msk[*attr], &u_j));
+        }
+
+        KPABEUserKey {
+            policy: policy.clone(),
+            keys,
+        }
+    }
+
+    /// Generates an encryption of `msg mod 2` bound to the attributes `attrs`:
+    /// - s <- Z_q^n
+    /// - c_i = s^t * A_i + x_i^t for all `i ∈ attrs`
+    /// - c_0 = s^t * u + x_0 + msg * ⌊q/2⌋
+    ///     where `x_i` and `x_0` are discrete Gaussian distributed with
+    ///     center 0 and Gaussian parameter q * α.
+    ///
+    /// Parameters:
+    /// - `mpk`: the master public key
+    /// - `attrs`: the attributes the ciphertext should be bound to
+    /// - `msg`: the message that should be encrypted
+    ///
+    /// Returns a [`KPABECipher`].
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::attribute_based_encryption::KPABE;
+    /// use qfall_math::integer::Z;
+    ///
+    /// let abe = KPABE::new_from_n(4, 2, 1);
+    /// let (mpk, msk) = abe.setup();
+    ///
+    /// let cipher = abe.enc(&mpk, &[1], Z::ONE);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `attrs` contains an attribute `>= attr_count`.
+    pub fn enc(
+        &self,
+        mpk: &KPABEMasterPublicKey,
+        attrs: &[usize],
+        msg: impl Into<Z>,
+    ) -> KPABECipher {
+        assert!(
+            attrs.iter().all(|attr| *attr < self.attr_count),
+            "The ciphertext can not be bound to an attribute outside of the attribute universe."
+        );
+        let msg: Z = msg.into().modulo(2);
+        let s_gauss = &self.alpha * Z::from(&self.q);
+
+        // s <- Z_q^n
+        let vec_s_t = MatZq::sample_uniform(1, &self.n, &self.q);
+
+        let mut c_attrs = Vec::with_capacity(attrs.len());
+        for attr in attrs {
+            let vec_x_t =
+                MatZq::sample_discrete_gauss(1, &self.m, &self.q, &self.n, 0, &s_gauss).unwrap();
+            c_attrs.push(&vec_s_t * &mpk.a[*attr] + vec_x_t);
+        }
+
+        let x_0 = MatZq::sample_discrete_gauss(1, 1, &self.q, &self.n, 0, &s_gauss).unwrap();
+        let mut c_0 = &vec_s_t * &mpk.u + x_0;
+        let entry: Zq = c_0.get_entry(0, 0).unwrap();
+        c_0.set_entry(0, 0, entry + msg * Z::from(&self.q).div_floor(2))
+            .unwrap();
+
+        KPABECipher {
+            attrs: attrs.to_vec(),
+            c_attrs,
+            c_0,
+        }
+    }
+
+    /// Decrypts `cipher` using `user_key` if the attributes of `cipher`
+    /// satisfy the policy of `user_key` by following these steps:
+    /// - find coefficients `ω_j` with `Σ ω_j * M_j = (1, 0, ..., 0) mod q`
+    ///     over all rows `j` with `ρ(j) ∈ attrs` via Gaussian elimination
+    ///     and take their centered representatives, which lie in `{-1, 0, 1}`
+    ///     for monotone span programs generated from AND/OR formulas
+    /// - x = c_0 - Σ ω_j * c_{ρ(j)} * e_j
+    /// - if x mod q is closer to ⌊q/2⌋ than to 0, output 1. Otherwise, output 0.
+    ///
+    /// Parameters:
+    /// - `user_key`: the user key for a policy
+    /// - `cipher`: the ciphertext that should be decrypted
+    ///
+    /// Returns the decrypted bit or `None` if the attributes of `cipher`
+    /// do not satisfy the policy of `user_key`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::attribute_based_encryption::{KPABE, MonotoneSpanProgram};
+    /// use qfall_math::integer::{MatZ, Z};
+    /// use std::str::FromStr;
+    ///
+    /// let abe = KPABE::new_from_n(4, 2, 1);
+    /// let (mpk, msk) = abe.setup();
+    /// let policy = MonotoneSpanProgram::new(MatZ::from_str("[[1],[1]]").unwrap(), vec![0, 1]);
+    /// let user_key = abe.key_gen(&mpk, &msk, &policy);
+    /// let cipher = abe.enc(&mpk, &[1], Z::ZERO);
+    ///
+    /// assert_eq!(Some(Z::ZERO), abe.dec(&user_key, &cipher));
+    /// ```
+    pub fn dec(&self, user_key: &KPABEUserKey, cipher: &KPABECipher) -> Option<Z> {
+        let q = Z::from(&self.q);
+        let q_half = q.div_floor(2);
+
+        // collect all rows of the policy, whose attribute is part of the ciphertext
+        let rows: Vec<(usize, usize)> = user_key
+            .policy
+            .label_fn
+            .iter()
+            .enumerate()
+            .filter_map(|(j, attr)| {
+                cipher
+                    .attrs
+                    .iter()
+                    .position(|c_attr| c_attr == attr)
+                    .map(|pos| (j, pos))
+            })
+            .collect();
+        if rows.is_empty() {
+            return None;
+        }
+
+        // solve M_S^t * ω = (1, 0, ..., 0)^t
+        let matrix_t = user_key.policy.matrix.transpose();
+        let mut mat_m_s_t = matrix_t.get_column(rows[0].0 as i64).unwrap();
+        for (j, _) in rows.iter().skip(1) {
+            mat_m_s_t = mat_m_s_t
+                .concat_horizontal(&matrix_t.get_column(*j as i64).unwrap())
+                .unwrap();
+        }
+        let mut target = MatZ::new(matrix_t.get_num_rows(), 1);
+        target.set_entry(0, 0, 1).unwrap();
+        let omega = MatZq::from((&mat_m_s_t, &self.q))
+            .solve_gaussian_elimination(&MatZq::from((&target, &self.q)))?
+            .get_representative_0_modulus();
+
+        // x = c_0 - Σ ω_j * c_{ρ(j)} * e_j
+        let mut result: Z = cipher
+            .c_0
+            .get_representative_0_modulus()
+            .get_entry(0, 0)
+            .unwrap();
+        for (index, (j, pos)) in rows.iter().enumerate() {
+            let mut omega_j: Z = omega.get_entry(index as i64, 0).unwrap();
+            // choose the centered representative s.t. ω_j ∈ {-1, 0, 1}
+            if omega_j > q_half {
+                omega_j = omega_j - &q;
+            }
+            let share: Z = (&cipher.c_attrs[*pos] * &user_key.keys[*j])
+                .get_representative_0_modulus()
+                .get_entry(0, 0)
+                .unwrap();
+            result = result - omega_j * share;
+        }
+        let result = Zq::from((result, &self.q));
+
+        if result.distance(Z::ZERO) > result.distance(q_half) {
+            Some(Z::ONE)
+        } else {
+            Some(Z::ZERO)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_kp_abe {
+    use super::{MonotoneSpanProgram, KPABE};
+    use qfall_math::integer::{MatZ, Z};
+    use std::str::FromStr;
+
+    /// Returns the policy `attribute 0 AND attribute 1`.
+    fn and_2() -> MonotoneSpanProgram {
+        MonotoneSpanProgram::new(MatZ::from_str("[[1, 1],[0, -1]]").unwrap(), vec![0, 1])
+    }
+
+    /// Returns the policy `attribute 0 OR attribute 1`.
+    fn or_2() -> MonotoneSpanProgram {
+        MonotoneSpanProgram::new(MatZ::from_str("[[1],[1]]").unwrap(), vec![0, 1])
+    }
+
+    /// Checks whether the full-cycle of setup, key_gen, enc, dec works properly
+    /// for an AND policy and ciphertexts with both attributes.
+    #[test]
+    fn cycle_and_2() {
+        let abe = KPABE::new_from_n(4, 3, 1);
+        let (mpk, msk) = abe.setup();
+        let user_key = abe.key_gen(&mpk, &msk, &and_2());
+
+        for msg in [Z::ZERO, Z::ONE] {
+            let cipher = abe.enc(&mpk, &[0, 1], &msg);
+            assert_eq!(Some(msg.clone()), abe.dec(&user_key, &cipher));
+
+            let cipher = abe.enc(&mpk, &[2, 1, 0], &msg);
+            assert_eq!(Some(msg), abe.dec(&user_key, &cipher));
+        }
+    }
+
+    /// Ensures that ciphertexts with only one of both attributes can not
+    /// be decrypted with a key for an AND policy.
+    #[test]
+    fn and_2_unsatisfied() {
+        let abe = KPABE::new_from_n(4, 3, 1);
+        let (mpk, msk) = abe.setup();
+        let user_key = abe.key_gen(&mpk, &msk, &and_2());
+
+        assert_eq!(None, abe.dec(&user_key, &abe.enc(&mpk, &[0], 1)));
+        assert_eq!(None, abe.dec(&user_key, &abe.enc(&mpk, &[1, 2], 1)));
+        assert_eq!(None, abe.dec(&user_key, &abe.enc(&mpk, &[], 1)));
+    }
+
+    /// Checks whether the full-cycle of setup, key_gen, enc, dec works properly
+    /// for an OR policy and ciphertexts with any of both attributes.
+    #[test]
+    fn cycle_or_2() {
+        let abe = KPABE::new_from_n(4, 3, 1);
+        let (mpk, msk) = abe.setup();
+        let user_key = abe.key_gen(&mpk, &msk, &or_2());
+
+        for attrs in [vec![0], vec![1], vec![0, 1], vec![2, 1]] {
+            for msg in [Z::ZERO, Z::ONE] {
+                let cipher = abe.enc(&mpk, &attrs, &msg);
+                assert_eq!(Some(msg), abe.dec(&user_key, &cipher));
+            }
+        }
+    }
+
+    /// Ensures that ciphertexts without any of both attributes can not
+    /// be decrypted with a key for an OR policy.
+    #[test]
+    fn or_2_unsatisfied() {
+        let abe = KPABE::new_from_n(4, 3, 1);
+        let (mpk, msk) = abe.setup();
+        let user_key = abe.key_gen(&mpk, &msk, &or_2());
+
+        assert_eq!(None, abe.dec(&user_key, &abe.enc(&mpk, &[2], 1)));
+    }
+
+    /// Ensures that policies with too many rows are rejected.
+    #[test]
+    #[should_panic]
+    fn policy_too_deep() {
+        let abe = KPABE::new_from_n(4, 2, 0);
+        let (mpk, msk) = abe.setup();
+
+        let _ = abe.key_gen(&mpk, &msk, &or_2());
+    }
+
+    /// Ensures that share-generating matrices with entries outside of `{-1, 0, 1}`
+    /// are rejected, as their reconstruction coefficients are not short.
+    #[test]
+    #[should_panic]
+    fn large_entries() {
+        let _ = MonotoneSpanProgram::new(MatZ::from_str("[[2, 1],[1, 1]]").unwrap(), vec![0, 1]);
+    }
+
+    /// Ensures that policies with entries outside of `{-1, 0, 1}` are rejected
+    /// by `key_gen` even if they were not created via `new`.
+    #[test]
+    #[should_panic]
+    fn key_gen_large_entries() {
+        let abe = KPABE::new_from_n(4, 2, 1);
+        let (mpk, msk) = abe.setup();
+        let policy = MonotoneSpanProgram {
+            matrix: MatZ::from_str("[[3],[1]]").unwrap(),
+            label_fn: vec![0, 1],
+        };
+
+        let _ = abe.key_gen(&mpk, &msk, &policy);
+    }
+
+    /// Ensures that attributes outside of the attribute universe are rejected.
+    #[test]
+    #[should_panic]
+    fn invalid_attribute() {
+        let abe = KPABE::new_from_n(4, 2, 1);
+        let (mpk, _) = abe.setup();
+
+        let _ = abe.enc(&mpk, &[2], 1);
+    }
+}
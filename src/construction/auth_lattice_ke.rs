@@ -0,0 +1,421 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains a two-message authenticated key exchange, which
+//! combines ephemeral [`DualRegev`] encryption with long-term [`FDH`] signatures.
+//!
+//! The protocol consists of the following steps:
+//! 1. Alice generates an ephemeral [`DualRegev`] key pair, signs the ephemeral
+//!     public key with her long-term signing key and sends `(ephemeral_pk, sig_A)`.
+//! 2. Bob verifies `sig_A` under Alice's long-term public key, chooses a uniform
+//!     key `K`, encapsulates it bitwise under `ephemeral_pk`, signs the
+//!     transcript with his long-term signing key and sends `(encapsulation, sig_B)`.
+//! 3. Alice verifies `sig_B` under Bob's long-term public key and
+//!     decapsulates `K` with her ephemeral secret key.
+//!
+//! Both parties derive the shared key from `K` and the transcript using HKDF.
+
+use crate::{
+    construction::{
+        hash::sha256::HashMatZq,
+        kdf::{hkdf_expand, hkdf_extract},
+        pk_encryption::{DualRegev, PKEncryptionScheme},
+        signature::{SignatureScheme, FDH},
+    },
+    error::CryptoError,
+    primitive::psf::PSFGPV,
+};
+use qfall_math::{
+    integer::{MatZ, Z},
+    integer_mod_q::MatZq,
+    rational::{MatQ, Q},
+    traits::{GetEntry, Pow},
+};
+use serde::{Deserialize, Serialize};
+
+/// The number of bits of the encapsulated key `K`.
+const KEY_BITS: usize = 64;
+
+/// The `info` string used to derive the shared key.
+const KEY_INFO: &str = "qFALL-crypto authenticated lattice key exchange key";
+
+/// The first message sent from Alice to Bob.
+///
+/// Attributes:
+/// - `ephemeral_pk`: Alice's ephemeral [`DualRegev`] public key
+/// - `signature`: Alice's long-term signature of `ephemeral_pk`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Msg1 {
+    pub ephemeral_pk: MatZq,
+    pub signature: MatZ,
+}
+
+/// The second message sent from Bob to Alice.
+///
+/// Attributes:
+/// - `encapsulation`: the bitwise [`DualRegev`] encryption of the key `K`
+/// - `signature`: Bob's long-term signature of the transcript
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Msg2 {
+    pub encapsulation: Vec<MatZq>,
+    pub signature: MatZ,
+}
+
+/// The state of Alice between sending [`Msg1`] and receiving [`Msg2`],
+/// which has to be kept secret.
+#[derive(Debug, Clone)]
+pub struct AliceState {
+    ephemeral_sk: MatZ,
+    msg_1: Msg1,
+}
+
+/// The key shared by both parties after a successful protocol execution.
+///
+/// Attributes:
+/// - `key`: the `32` bytes of the shared key
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SharedKey {
+    pub key: [u8; 32],
+}
+
+/// This struct manages and stores the public parameters of a two-message
+/// authenticated key exchange based on [`DualRegev`] and [`FDH`].
+///
+/// Attributes:
+/// - `dual_regev`: the [`DualRegev`] instance used for the ephemeral keys
+/// - `fdh`: the [`FDH`] instance used for the long-term keys
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::construction::auth_lattice_ke::AuthLatticeKE;
+///
+/// let mut ke = AuthLatticeKE::new_from_n(10);
+/// let (alice_pk, alice_sk) = ke.gen_long_term_keys();
+/// let (bob_pk, bob_sk) = ke.gen_long_term_keys();
+///
+/// let (_, state, msg_1) = ke.init(&alice_sk, &alice_pk).unwrap();
+/// let (key_bob, msg_2) = ke.respond(&bob_sk, &bob_pk, &alice_pk, &msg_1).unwrap();
+/// let key_alice = ke.finish(&state, &bob_pk, &msg_2).unwrap();
+///
+/// assert_eq!(key_alice, key_bob);
+/// ```
+#[derive(Serialize)]
+pub struct AuthLatticeKE {
+    pub dual_regev: DualRegev,
+    pub fdh: FDH<MatZq, (MatZ, MatQ), MatZ, MatZq, PSFGPV, HashMatZq>,
+}
+
+impl AuthLatticeKE {
+    /// Initializes an [`AuthLatticeKE`] instance for security parameter `n`.
+    /// The ephemeral keys use `DualRegev::new_from_n(n)` and the long-term keys
+    /// use [`FDH::init_gpv`] with modulus `q = 2^k` for `k = 2 * ⌈log_2(n)⌉ + 2`
+    /// and Gaussian parameter `s = 2 * (sqrt(n * k) + 1) * log_2(2 * n * k)`.
+    ///
+    /// Parameters:
+    /// - `n`: the security parameter
+    ///
+    /// Returns an [`AuthLatticeKE`] instance.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::auth_lattice_ke::AuthLatticeKE;
+    ///
+    /// let ke = AuthLatticeKE::new_from_n(10);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `n < 10`.
+    /// - if `n` does not fit into an [`i64`].
+    pub fn new_from_n(n: impl Into<Z>) -> Self {
+        let n: Z = n.into();
+        let dual_regev = DualRegev::new_from_n(&n);
+
+        // `s >= ||\tilde short_base|| * omega(sqrt{log m})` (Theorem 4.1 - GPV08)
+        let k = Z::from(2) * n.log_ceil(2).unwrap() + 2;
+        let q = Z::from(2).pow(&k).unwrap();
+        let s: Q = ((&n * &k).sqrt() + 1) * Q::from(2) * (Z::from(2) * &n * &k).log(2).unwrap();
+        let fdh = FDH::init_gpv(&n, &q, &s);
+
+        Self { dual_regev, fdh }
+    }
+
+    /// Generates a long-term key pair of a party.
+    ///
+    /// Returns a tuple `(long_pk, long_sk)` of the [`FDH`] signature scheme.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::auth_lattice_ke::AuthLatticeKE;
+    ///
+    /// let mut ke = AuthLatticeKE::new_from_n(10);
+    /// let (long_pk, long_sk) = ke.gen_long_term_keys();
+    /// ```
+    pub fn gen_long_term_keys(&mut self) -> (MatZq, (MatZ, MatQ)) {
+        self.fdh.gen()
+    }
+
+    /// Returns the message signed by Alice for `msg_1`.
+    fn transcript_1(ephemeral_pk: &MatZq) -> String {
+        format!("1 {ephemeral_pk}")
+    }
+
+    /// Returns the message signed by Bob for `msg_2`, which binds
+    /// the ephemeral public key and the encapsulation to each other.
+    fn transcript_2(ephemeral_pk: &MatZq, encapsulation: &[MatZq]) -> String {
+        let mut transcript = format!("2 {ephemeral_pk}");
+        for cipher in encapsulation {
+            transcript.push_str(&format!(" {cipher}"));
+        }
+        transcript
+    }
+
+    /// Derives the shared key from the bits of `K` and the transcript.
+    fn derive_key(bits: &[bool], ephemeral_pk: &MatZq, encapsulation: &[MatZq]) -> SharedKey {
+        let mut ikm = vec![0u8; bits.len().div_ceil(8)];
+        for (i, bit) in bits.iter().enumerate() {
+            if *bit {
+                ikm[i / 8] |= 1 << (i % 8);
+            }
+        }
+        let transcript = Self::transcript_2(ephemeral_pk, encapsulation);
+
+        let prk = hkdf_extract(transcript.as_bytes(), &ikm);
+        let mut key = [0u8; 32];
+        key.copy_from_slice(&hkdf_expand(&prk, KEY_INFO, 32));
+        SharedKey { key }
+    }
+
+    /// Starts the protocol as Alice with her long-term key pair.
+    ///
+    /// Parameters:
+    /// - `long_sk`: Alice's long-term secret key
+    /// - `long_pk`: Alice's long-term public key
+    ///
+    /// Returns Alice's ephemeral public key, her secret [`AliceState`]
+    /// and the [`Msg1`] that is sent to Bob or a [`CryptoError`]
+    /// if the transcript could not be signed.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::auth_lattice_ke::AuthLatticeKE;
+    ///
+    /// let mut ke = AuthLatticeKE::new_from_n(10);
+    /// let (alice_pk, alice_sk) = ke.gen_long_term_keys();
+    ///
+    /// let (ephemeral_pk, state, msg_1) = ke.init(&alice_sk, &alice_pk).unwrap();
+    /// ```
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] of type [`CryptoError::VerificationFailed`]
+    ///   if `long_sk` does not belong to `long_pk`.
+    pub fn init(
+        &mut self,
+        long_sk: &(MatZ, MatQ),
+        long_pk: &MatZq,
+    ) -> Result<(MatZq, AliceState, Msg1), CryptoError> {
+        let (ephemeral_pk, ephemeral_sk) = self.dual_regev.gen();
+        let signature = self
+            .fdh
+            .sign(Self::transcript_1(&ephemeral_pk), long_sk, long_pk)?;
+
+        let msg_1 = Msg1 {
+            ephemeral_pk: ephemeral_pk.clone(),
+            signature,
+        };
+        let state = AliceState {
+            ephemeral_sk,
+            msg_1: msg_1.clone(),
+        };
+
+        Ok((ephemeral_pk, state, msg_1))
+    }
+
+    /// Responds to `msg_1` as Bob after verifying Alice's signature.
+    ///
+    /// Parameters:
+    /// - `long_sk`: Bob's long-term secret key
+    /// - `long_pk`: Bob's long-term public key
+    /// - `alice_long_pk`: Alice's long-term public key
+    /// - `msg_1`: the message received from Alice
+    ///
+    /// Returns the [`SharedKey`] of Bob and the [`Msg2`] that is sent to Alice
+    /// or a [`CryptoError`] if the signature in `msg_1` is invalid
+    /// or the transcript could not be signed.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::auth_lattice_ke::AuthLatticeKE;
+    ///
+    /// let mut ke = AuthLatticeKE::new_from_n(10);
+    /// let (alice_pk, alice_sk) = ke.gen_long_term_keys();
+    /// let (bob_pk, bob_sk) = ke.gen_long_term_keys();
+    /// let (_, _, msg_1) = ke.init(&alice_sk, &alice_pk).unwrap();
+    ///
+    /// let (key, msg_2) = ke.respond(&bob_sk, &bob_pk, &alice_pk, &msg_1).unwrap();
+    /// ```
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] of type [`CryptoError::VerificationFailed`]
+    ///   if `msg_1.signature` is not a valid signature under `alice_long_pk`
+    ///   or if `long_sk` does not belong to `long_pk`.
+    pub fn respond(
+        &mut self,
+        long_sk: &(MatZ, MatQ),
+        long_pk: &MatZq,
+        alice_long_pk: &MatZq,
+        msg_1: &Msg1,
+    ) -> Result<(SharedKey, Msg2), CryptoError> {
+        if !self.fdh.vfy(
+            Self::transcript_1(&msg_1.ephemeral_pk),
+            &msg_1.signature,
+            alice_long_pk,
+        ) {
+            return Err(CryptoError::VerificationFailed);
+        }
+
+        let key = MatZ::sample_uniform(KEY_BITS, 1, 0, 2).unwrap();
+        let mut bits = Vec::with_capacity(KEY_BITS);
+        let mut encapsulation = Vec::with_capacity(KEY_BITS);
+        for i in 0..KEY_BITS {
+            let bit: Z = key.get_entry(i, 0).unwrap();
+            encapsulation.push(self.dual_regev.enc(&msg_1.ephemeral_pk, &bit));
+            bits.push(bit == Z::ONE);
+        }
+
+        let signature = self.fdh.sign(
+            Self::transcript_2(&msg_1.ephemeral_pk, &encapsulation),
+            long_sk,
+            long_pk,
+        )?;
+        let shared_key = Self::derive_key(&bits, &msg_1.ephemeral_pk, &encapsulation);
+
+        Ok((
+            shared_key,
+            Msg2 {
+                encapsulation,
+                signature,
+            },
+        ))
+    }
+
+    /// Finishes the protocol as Alice after verifying Bob's signature.
+    ///
+    /// Parameters:
+    /// - `state`: the state of Alice generated by [`AuthLatticeKE::init`]
+    /// - `bob_long_pk`: Bob's long-term public key
+    /// - `msg_2`: the message received from Bob
+    ///
+    /// Returns the [`SharedKey`] of Alice, which matches the key of Bob
+    /// with overwhelming probability, or a [`CryptoError`] if the signature
+    /// in `msg_2` is invalid.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::auth_lattice_ke::AuthLatticeKE;
+    ///
+    /// let mut ke = AuthLatticeKE::new_from_n(10);
+    /// let (alice_pk, alice_sk) = ke.gen_long_term_keys();
+    /// let (bob_pk, bob_sk) = ke.gen_long_term_keys();
+    /// let (_, state, msg_1) = ke.init(&alice_sk, &alice_pk).unwrap();
+    /// let (_, msg_2) = ke.respond(&bob_sk, &bob_pk, &alice_pk, &msg_1).unwrap();
+    ///
+    /// let key = ke.finish(&state, &bob_pk, &msg_2).unwrap();
+    /// ```
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] of type [`CryptoError::VerificationFailed`]
+    ///   if `msg_2.signature` is not a valid signature under `bob_long_pk`.
+    pub fn finish(
+        &self,
+        state: &AliceState,
+        bob_long_pk: &MatZq,
+        msg_2: &Msg2,
+    ) -> Result<SharedKey, CryptoError> {
+        let ephemeral_pk = &state.msg_1.ephemeral_pk;
+        if !self.fdh.vfy(
+            Self::transcript_2(ephemeral_pk, &msg_2.encapsulation),
+            &msg_2.signature,
+            bob_long_pk,
+        ) {
+            return Err(CryptoError::VerificationFailed);
+        }
+
+        let bits: Vec<bool> = msg_2
+            .encapsulation
+            .iter()
+            .map(|cipher| self.dual_regev.dec(&state.ephemeral_sk, cipher) == Z::ONE)
+            .collect();
+
+        Ok(Self::derive_key(&bits, ephemeral_pk, &msg_2.encapsulation))
+    }
+}
+
+#[cfg(test)]
+mod test_auth_lattice_ke {
+    use super::AuthLatticeKE;
+
+    /// Ensures that both parties derive the same key.
+    #[test]
+    fn matching_keys() {
+        let mut ke = AuthLatticeKE::new_from_n(10);
+        let (alice_pk, alice_sk) = ke.gen_long_term_keys();
+        let (bob_pk, bob_sk) = ke.gen_long_term_keys();
+
+        let (ephemeral_pk, state, msg_1) = ke.init(&alice_sk, &alice_pk).unwrap();
+        let (key_bob, msg_2) = ke.respond(&bob_sk, &bob_pk, &alice_pk, &msg_1).unwrap();
+        let key_alice = ke.finish(&state, &bob_pk, &msg_2).unwrap();
+
+        assert_eq!(ephemeral_pk, msg_1.ephemeral_pk);
+        assert_eq!(key_alice, key_bob);
+    }
+
+    /// Ensures that Bob rejects `msg_1` if it was not signed by Alice.
+    #[test]
+    fn wrong_alice_key() {
+        let mut ke = AuthLatticeKE::new_from_n(10);
+        let (alice_pk, _) = ke.gen_long_term_keys();
+        let (eve_pk, eve_sk) = ke.gen_long_term_keys();
+        let (bob_pk, bob_sk) = ke.gen_long_term_keys();
+
+        let (_, _, msg_1) = ke.init(&eve_sk, &eve_pk).unwrap();
+
+        assert!(ke.respond(&bob_sk, &bob_pk, &alice_pk, &msg_1).is_err());
+    }
+
+    /// Ensures that Alice rejects `msg_2` if it was not signed by Bob
+    /// or if the encapsulation was modified.
+    #[test]
+    fn wrong_bob_key() {
+        let mut ke = AuthLatticeKE::new_from_n(10);
+        let (alice_pk, alice_sk) = ke.gen_long_term_keys();
+        let (eve_pk, eve_sk) = ke.gen_long_term_keys();
+        let (bob_pk, _) = ke.gen_long_term_keys();
+
+        let (_, state, msg_1) = ke.init(&alice_sk, &alice_pk).unwrap();
+        let (_, mut msg_2) = ke.respond(&eve_sk, &eve_pk, &alice_pk, &msg_1).unwrap();
+
+        assert!(ke.finish(&state, &bob_pk, &msg_2).is_err());
+
+        msg_2.encapsulation.swap(0, 1);
+        assert!(ke.finish(&state, &eve_pk, &msg_2).is_err());
+    }
+
+    /// Ensures that every execution results in a fresh key.
+    #[test]
+    fn fresh_keys() {
+        let mut ke = AuthLatticeKE::new_from_n(10);
+        let (alice_pk, alice_sk) = ke.gen_long_term_keys();
+        let (bob_pk, bob_sk) = ke.gen_long_term_keys();
+
+        let (_, _, msg_1) = ke.init(&alice_sk, &alice_pk).unwrap();
+        let (key_1, _) = ke.respond(&bob_sk, &bob_pk, &alice_pk, &msg_1).unwrap();
+        let (key_2, _) = ke.respond(&bob_sk, &bob_pk, &alice_pk, &msg_1).unwrap();
+
+        assert_ne!(key_1, key_2);
+    }
+}
@@ -0,0 +1,291 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains a broadcast encryption scheme, which encrypts a message
+//! for several recipients at once by encrypting a shared session key under
+//! each recipient's identity using [`DualRegevIBE`].
+
+use crate::construction::{
+    identity_based_encryption::{DualRegevIBE, IBEScheme},
+    kdf::{hkdf_expand, hkdf_extract},
+};
+use qfall_math::{
+    integer::{MatZ, Z},
+    integer_mod_q::MatZq,
+};
+use serde::{Deserialize, Serialize};
+
+/// The number of bytes of a session key.
+const SESSION_KEY_BYTES: usize = 16;
+
+/// The `info` string used to derive the mask from the session key.
+const MASK_INFO: &str = "qFALL-crypto broadcast encryption mask";
+
+/// A ciphertext of [`BroadcastEncryption`].
+///
+/// Attributes:
+/// - `recipients`: the identities of all recipients
+/// - `key_ciphers`: the bitwise [`DualRegevIBE`] encryptions of the session key
+///     for each recipient in the same order as `recipients`
+/// - `masked_msg`: the message XORed with the mask derived from the session key
+/// - `msg_len`: the number of bits of the message
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BroadcastCiphertext {
+    pub recipients: Vec<String>,
+    pub key_ciphers: Vec<Vec<MatZq>>,
+    pub masked_msg: Z,
+    pub msg_len: usize,
+}
+
+/// This struct manages a broadcast encryption scheme based on [`DualRegevIBE`].
+/// A random session key is encrypted bit by bit under the identity of each recipient
+/// and the message is XORed with a mask derived from the session key via HKDF.
+/// Hence, the ciphertext size is linear in the number of recipients, but only
+/// depends on the length of the message once.
+///
+/// Attributes:
+/// - `ibe`: the [`DualRegevIBE`] instance used to encrypt the session keys
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::construction::broadcast_encryption::BroadcastEncryption;
+/// use qfall_crypto::construction::identity_based_encryption::IBEScheme;
+/// use qfall_math::integer::Z;
+///
+/// let scheme = BroadcastEncryption::default();
+/// let (mpk, msk) = scheme.ibe.setup();
+///
+/// let recipients = [String::from("Alice"), String::from("Bob")];
+/// let cipher = scheme.broadcast_enc(&mpk, &recipients, Z::from(42));
+///
+/// let sk_bob = scheme.ibe.extract(&mpk, &msk, &recipients[1]).unwrap();
+/// assert_eq!(Some(Z::from(42)), scheme.broadcast_dec(&sk_bob, "Bob", &cipher));
+/// ```
+#[derive(Serialize, Deserialize)]
+pub struct BroadcastEncryption {
+    pub ibe: DualRegevIBE,
+}
+
+impl BroadcastEncryption {
+    /// Initializes a [`BroadcastEncryption`] instance using the given [`DualRegevIBE`].
+    ///
+    /// Parameters:
+    /// - `ibe`: the IBE used to encrypt the session key for each recipient
+    ///
+    /// Returns a [`BroadcastEncryption`] instance.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::broadcast_encryption::BroadcastEncryption;
+    /// use qfall_crypto::construction::identity_based_encryption::DualRegevIBE;
+    ///
+    /// let scheme = BroadcastEncryption::new(DualRegevIBE::new_from_n(4).unwrap());
+    /// ```
+    pub fn new(ibe: DualRegevIBE) -> Self {
+        Self { ibe }
+    }
+
+    /// Computes the mask of `nr_bits` bits derived from `session_key`.
+    fn mask(session_key: &[u8], nr_bits: usize) -> Vec<bool> {
+        let prk = hkdf_extract(&[], session_key);
+        let bytes = hkdf_expand(&prk, MASK_INFO, nr_bits.div_ceil(8));
+
+        (0..nr_bits)
+            .map(|i| (bytes[i / 8] >> (i % 8)) & 1 == 1)
+            .collect()
+    }
+
+    /// XORs the bits of `value` with `mask`.
+    fn apply_mask(value: &Z, mask: &[bool]) -> Z {
+        let mut bits = value.to_bits();
+        bits.resize(mask.len(), false);
+        let masked: Vec<bool> = bits.iter().zip(mask).map(|(bit, m)| bit ^ m).collect();
+
+        Z::from_bits(&masked)
+    }
+
+    /// Encrypts `msg` for all `recipients` by following these steps:
+    /// - sample a uniform session key `k`
+    /// - encrypt `k` bitwise under the identity of each recipient
+    /// - mask `msg` with the bits derived from `k` via HKDF
+    ///
+    /// Negative values are not allowed. Hence, the absolute value is being encrypted.
+    ///
+    /// Parameters:
+    /// - `mpk`: the master public key of the IBE
+    /// - `recipients`: the identities of all recipients
+    /// - `msg`: the message that should be encrypted
+    ///
+    /// Returns a [`BroadcastCiphertext`], which can be decrypted by every recipient.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::broadcast_encryption::BroadcastEncryption;
+    /// use qfall_crypto::construction::identity_based_encryption::IBEScheme;
+    /// use qfall_math::integer::Z;
+    ///
+    /// let scheme = BroadcastEncryption::default();
+    /// let (mpk, msk) = scheme.ibe.setup();
+    ///
+    /// let recipients = [String::from("Alice"), String::from("Bob")];
+    /// let cipher = scheme.broadcast_enc(&mpk, &recipients, Z::from(42));
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `msg` has more than `255 * 32 * 8` bits.
+    pub fn broadcast_enc(
+        &self,
+        mpk: &MatZq,
+        recipients: &[String],
+        msg: impl Into<Z>,
+    ) -> BroadcastCiphertext {
+        let msg: Z = msg.into().abs();
+        let session_key: [u8; SESSION_KEY_BYTES] = rand::random();
+
+        let key_ciphers = recipients
+            .iter()
+            .map(|identity| {
+                let mut ciphers = Vec::with_capacity(8 * SESSION_KEY_BYTES);
+                for byte in session_key {
+                    for i in 0..8 {
+                        ciphers.push(self.ibe.enc(mpk, identity, (byte >> i) & 1));
+                    }
+                }
+                ciphers
+            })
+            .collect();
+
+        let msg_len = msg.to_bits().len();
+        let masked_msg = Self::apply_mask(&msg, &Self::mask(&session_key, msg_len));
+
+        BroadcastCiphertext {
+            recipients: recipients.to_vec(),
+            key_ciphers,
+            masked_msg,
+            msg_len,
+        }
+    }
+
+    /// Decrypts `cipher` as recipient `my_id` by decrypting the session key
+    /// with the IBE secret key `id_sk` and unmasking the message.
+    ///
+    /// Parameters:
+    /// - `id_sk`: the IBE secret key of `my_id`
+    /// - `my_id`: the identity of the recipient
+    /// - `cipher`: the ciphertext that should be decrypted
+    ///
+    /// Returns the decrypted message or `None` if `my_id` is not a recipient of `cipher`
+    /// or `id_sk` does not fit the key ciphertexts.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::broadcast_encryption::BroadcastEncryption;
+    /// use qfall_crypto::construction::identity_based_encryption::IBEScheme;
+    /// use qfall_math::integer::Z;
+    ///
+    /// let scheme = BroadcastEncryption::default();
+    /// let (mpk, msk) = scheme.ibe.setup();
+    /// let recipients = [String::from("Alice"), String::from("Bob")];
+    /// let cipher = scheme.broadcast_enc(&mpk, &recipients, Z::from(42));
+    ///
+    /// let sk_alice = scheme.ibe.extract(&mpk, &msk, &recipients[0]).unwrap();
+    /// let msg = scheme.broadcast_dec(&sk_alice, "Alice", &cipher);
+    ///
+    /// assert_eq!(Some(Z::from(42)), msg);
+    /// assert_eq!(None, scheme.broadcast_dec(&sk_alice, "Eve", &cipher));
+    /// ```
+    pub fn broadcast_dec(
+        &self,
+        id_sk: &MatZ,
+        my_id: &str,
+        cipher: &BroadcastCiphertext,
+    ) -> Option<Z> {
+        let position = cipher.recipients.iter().position(|id| id == my_id)?;
+
+        let mut session_key = [0u8; SESSION_KEY_BYTES];
+        for (i, key_cipher) in cipher.key_ciphers[position].iter().enumerate() {
+            if self.ibe.dec(id_sk, key_cipher).ok()? == Z::ONE {
+                session_key[i / 8] |= 1 << (i % 8);
+            }
+        }
+
+        Some(Self::apply_mask(
+            &cipher.masked_msg,
+            &Self::mask(&session_key, cipher.msg_len),
+        ))
+    }
+}
+
+impl Default for BroadcastEncryption {
+    /// Initializes a [`BroadcastEncryption`] instance using [`DualRegevIBE::default`].
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::broadcast_encryption::BroadcastEncryption;
+    ///
+    /// let scheme = BroadcastEncryption::default();
+    /// ```
+    fn default() -> Self {
+        Self::new(DualRegevIBE::default())
+    }
+}
+
+#[cfg(test)]
+mod test_broadcast_encryption {
+    use super::BroadcastEncryption;
+    use crate::construction::identity_based_encryption::IBEScheme;
+    use qfall_math::integer::Z;
+
+    /// Ensures that all 5 recipients decrypt the same message.
+    #[test]
+    fn five_recipients() {
+        let scheme = BroadcastEncryption::default();
+        let (mpk, msk) = scheme.ibe.setup();
+        let recipients: Vec<String> = (0..5).map(|i| format!("recipient {i}")).collect();
+
+        for msg in [Z::ZERO, Z::ONE, Z::from(42), Z::from(u64::MAX)] {
+            let cipher = scheme.broadcast_enc(&mpk, &recipients, &msg);
+
+            assert_eq!(5, cipher.key_ciphers.len());
+            for id in &recipients {
+                let id_sk = scheme.ibe.extract(&mpk, &msk, id).unwrap();
+                assert_eq!(Some(msg.clone()), scheme.broadcast_dec(&id_sk, id, &cipher));
+            }
+        }
+    }
+
+    /// Ensures that identities, which are not recipients, can not decrypt.
+    #[test]
+    fn no_recipient() {
+        let scheme = BroadcastEncryption::default();
+        let (mpk, msk) = scheme.ibe.setup();
+        let recipients = [String::from("Alice"), String::from("Bob")];
+
+        let cipher = scheme.broadcast_enc(&mpk, &recipients, 17);
+        let id_sk = scheme
+            .ibe
+            .extract(&mpk, &msk, &String::from("Eve"))
+            .unwrap();
+
+        assert_eq!(None, scheme.broadcast_dec(&id_sk, "Eve", &cipher));
+    }
+
+    /// Ensures that the masked message hides the message.
+    #[test]
+    fn message_masked() {
+        let scheme = BroadcastEncryption::default();
+        let (mpk, _) = scheme.ibe.setup();
+        let recipients = [String::from("Alice")];
+        let msg = Z::from(u64::MAX);
+
+        let cipher_1 = scheme.broadcast_enc(&mpk, &recipients, &msg);
+        let cipher_2 = scheme.broadcast_enc(&mpk, &recipients, &msg);
+
+        assert_ne!(cipher_1.masked_msg, cipher_2.masked_msg);
+    }
+}
@@ -0,0 +1,220 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains a cache of previously seen ciphertexts,
+//! which allows to reject replayed ciphertexts.
+
+use crate::error::CryptoError;
+use qfall_math::integer_mod_q::MatZq;
+use sha2::{Digest, Sha256};
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+/// Stores the SHA-256 hashes of previously seen ciphertexts together with the
+/// time they were last accepted to detect replayed ciphertexts.
+///
+/// Attributes:
+/// - `expiry`: the duration after which entries are removed by
+///     [`CiphertextCache::evict_expired`]
+/// - `seen`: the hashes of all seen ciphertexts and the time they were accepted
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::construction::ciphertext_cache::CiphertextCache;
+/// use qfall_math::integer_mod_q::MatZq;
+/// use std::time::Duration;
+///
+/// let mut cache = CiphertextCache::new(Duration::from_secs(60));
+/// let cipher = MatZq::sample_uniform(10, 1, 1024);
+///
+/// assert!(cache.check_freshness(&cipher, 60).is_ok());
+/// assert!(cache.check_freshness(&cipher, 60).is_err());
+/// ```
+#[derive(Debug)]
+pub struct CiphertextCache {
+    expiry: Duration,
+    seen: HashMap<[u8; 32], Instant>,
+}
+
+impl CiphertextCache {
+    /// Instantiates an empty [`CiphertextCache`],
+    /// whose entries expire after `expiry`.
+    ///
+    /// Parameters:
+    /// - `expiry`: the duration after which entries are removed by
+    ///     [`CiphertextCache::evict_expired`]
+    ///
+    /// Returns an empty [`CiphertextCache`].
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::ciphertext_cache::CiphertextCache;
+    /// use std::time::Duration;
+    ///
+    /// let cache = CiphertextCache::new(Duration::from_secs(60));
+    /// ```
+    pub fn new(expiry: Duration) -> Self {
+        Self {
+            expiry,
+            seen: HashMap::new(),
+        }
+    }
+
+    /// Checks whether `cipher` was already accepted within the last `max_age_secs`
+    /// seconds. If not, `cipher` is recorded as seen at the current time.
+    ///
+    /// Parameters:
+    /// - `cipher`: the ciphertext that should be checked
+    /// - `max_age_secs`: the number of seconds, within which a repeated
+    ///     ciphertext is considered a replay
+    ///
+    /// Returns an empty result if `cipher` is fresh or a [`CryptoError`] otherwise.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::ciphertext_cache::CiphertextCache;
+    /// use qfall_math::integer_mod_q::MatZq;
+    /// use std::time::Duration;
+    /// let mut cache = CiphertextCache::new(Duration::from_secs(60));
+    /// let cipher = MatZq::sample_uniform(10, 1, 1024);
+    ///
+    /// assert!(cache.check_freshness(&cipher, 60).is_ok());
+    /// ```
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] of type [`CiphertextReplayed`](CryptoError::CiphertextReplayed)
+    ///     if `cipher` was accepted within the last `max_age_secs` seconds.
+    pub fn check_freshness(
+        &mut self,
+        cipher: &MatZq,
+        max_age_secs: u64,
+    ) -> Result<(), CryptoError> {
+        let hash = Self::hash(cipher);
+        let now = Instant::now();
+
+        if let Some(seen_at) = self.seen.get(&hash) {
+            if now.duration_since(*seen_at) < Duration::from_secs(max_age_secs) {
+                return Err(CryptoError::CiphertextReplayed);
+            }
+        }
+
+        self.seen.insert(hash, now);
+        Ok(())
+    }
+
+    /// Removes all entries that were accepted longer than `expiry` ago.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::ciphertext_cache::CiphertextCache;
+    /// use std::time::Duration;
+    /// let mut cache = CiphertextCache::new(Duration::from_secs(60));
+    ///
+    /// cache.evict_expired();
+    /// ```
+    pub fn evict_expired(&mut self) {
+        let expiry = self.expiry;
+        self.seen.retain(|_, seen_at| seen_at.elapsed() < expiry);
+    }
+
+    /// Returns the number of stored ciphertext hashes.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::ciphertext_cache::CiphertextCache;
+    /// use std::time::Duration;
+    /// let cache = CiphertextCache::new(Duration::from_secs(60));
+    ///
+    /// assert_eq!(0, cache.len());
+    /// ```
+    pub fn len(&self) -> usize {
+        self.seen.len()
+    }
+
+    /// Returns `true` if no ciphertext hash is stored.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::ciphertext_cache::CiphertextCache;
+    /// use std::time::Duration;
+    /// let cache = CiphertextCache::new(Duration::from_secs(60));
+    ///
+    /// assert!(cache.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.seen.is_empty()
+    }
+
+    /// Computes the SHA-256 hash of the string representation of `cipher`.
+    fn hash(cipher: &MatZq) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(cipher.to_string());
+        hasher.finalize().into()
+    }
+}
+
+#[cfg(test)]
+mod test_ciphertext_cache {
+    use super::CiphertextCache;
+    use crate::{
+        construction::identity_based_encryption::{DualRegevIBE, IBEScheme},
+        error::CryptoError,
+    };
+    use qfall_math::integer_mod_q::MatZq;
+    use std::time::Duration;
+
+    /// Ensures that a replayed ciphertext within the window is rejected
+    /// and a fresh one is accepted.
+    #[test]
+    fn replay_rejected() {
+        let ibe = DualRegevIBE::default();
+        let (pk, _) = ibe.setup();
+        let id = String::from("identity");
+        let mut cache = CiphertextCache::new(Duration::from_secs(60));
+
+        let cipher = ibe.enc(&pk, &id, 1);
+        let fresh_cipher = ibe.enc(&pk, &id, 1);
+
+        assert!(cache.check_freshness(&cipher, 60).is_ok());
+        assert!(matches!(
+            cache.check_freshness(&cipher, 60),
+            Err(CryptoError::CiphertextReplayed)
+        ));
+        assert!(cache.check_freshness(&fresh_cipher, 60).is_ok());
+        assert_eq!(2, cache.len());
+    }
+
+    /// Ensures that a repeated ciphertext outside of the window is accepted.
+    #[test]
+    fn outside_window() {
+        let mut cache = CiphertextCache::new(Duration::from_secs(60));
+        let cipher = MatZq::sample_uniform(10, 1, 1024);
+
+        assert!(cache.check_freshness(&cipher, 0).is_ok());
+        assert!(cache.check_freshness(&cipher, 0).is_ok());
+        assert!(cache.check_freshness(&cipher, 60).is_err());
+    }
+
+    /// Ensures that expired entries are evicted and unexpired ones are kept.
+    #[test]
+    fn evict_expired() {
+        let mut cache = CiphertextCache::new(Duration::from_millis(50));
+        let cipher = MatZq::sample_uniform(10, 1, 1024);
+        cache.check_freshness(&cipher, 60).unwrap();
+
+        cache.evict_expired();
+        assert_eq!(1, cache.len());
+
+        std::thread::sleep(Duration::from_millis(60));
+        cache.evict_expired();
+        assert!(cache.is_empty());
+        assert!(cache.check_freshness(&cipher, 60).is_ok());
+    }
+}
@@ -0,0 +1,330 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains a coin flipping protocol between two parties,
+//! where party A commits to its bit using an Ajtai commitment
+//! before party B chooses its bit.
+//!
+//! The protocol consists of the following steps:
+//! 1. Party A chooses a bit `b_a`, commits to it and sends the commitment to party B.
+//! 2. Party B chooses a bit `b_b` and sends it to party A.
+//! 3. Party A reveals the opening of its commitment.
+//! 4. Both parties check the opening and output the coin `b_a XOR b_b`.
+
+use qfall_math::{
+    integer::{MatZ, Z},
+    integer_mod_q::{MatZq, Modulus},
+    traits::{Concatenate, GetEntry, GetNumRows, SetEntry},
+};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// An Ajtai commitment `c = A * [b | r]^t` to a bit `b`.
+///
+/// Attributes:
+/// - `c`: the committed value
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Commitment {
+    pub c: MatZq,
+}
+
+/// The opening of a [`Commitment`].
+///
+/// Attributes:
+/// - `bit`: the committed bit
+/// - `r`: the binary randomness of the commitment
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Opening {
+    pub bit: bool,
+    pub r: MatZ,
+}
+
+/// This struct manages and stores the public parameters of a coin flipping protocol
+/// based on Ajtai commitments.
+///
+/// A bit `b` is committed to as `c = A * [b | r]^t` for a uniform matrix
+/// `A ∈ Z_q^{n x (m + 1)}` and a uniform binary vector `r ∈ {0, 1}^m`.
+/// The commitment is statistically hiding due to the leftover hash lemma and
+/// computationally binding, as two different openings yield a short solution of SIS.
+///
+/// Attributes:
+/// - `m`: defines the length of the randomness of a commitment
+/// - `q`: specifies the modulus
+/// - `a`: the matrix `A` used as common reference string of both parties
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::construction::coin_flipping::CoinFlipping;
+///
+/// let protocol = CoinFlipping::new_from_n(8);
+/// let mut rng = rand::thread_rng();
+///
+/// // party A commits to a random bit
+/// let (commitment, opening, _) = protocol.party_a_commit(rand::random(), &mut rng);
+///
+/// // party B responds with a random bit
+/// let bit_b = protocol.party_b_respond(&commitment);
+///
+/// // party A reveals its bit
+/// let (opening, _) = protocol.party_a_reveal(opening);
+///
+/// let coin = protocol.compute_coin(&commitment, &opening, bit_b);
+/// assert_eq!(Some(opening.bit ^ bit_b), coin);
+/// ```
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CoinFlipping {
+    m: Z,
+    q: Modulus,
+    pub a: MatZq,
+}
+
+impl CoinFlipping {
+    /// Initializes a [`CoinFlipping`] instance for security parameter `n`
+    /// by choosing a prime `q ∈ [n^3, 2 * n^3]`, `m = 2 * n * ⌈log_2(q)⌉`,
+    /// and a uniform matrix `A ∈ Z_q^{n x (m + 1)}`.
+    ///
+    /// Parameters:
+    /// - `n`: the security parameter
+    ///
+    /// Returns a [`CoinFlipping`] instance.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::coin_flipping::CoinFlipping;
+    ///
+    /// let protocol = CoinFlipping::new_from_n(8);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `n < 2`.
+    pub fn new_from_n(n: impl Into<Z>) -> Self {
+        let n: Z = n.into();
+        if n < Z::from(2) {
+            panic!("Security parameter n has to be larger than 1");
+        }
+
+        let lower_bound: Z = &n * &n * &n;
+        let upper_bound: Z = 2 * &lower_bound;
+        let q = Modulus::from(Z::sample_prime_uniform(&lower_bound, &upper_bound).unwrap());
+
+        // m >= 2 * n * log(q) ensures that A * r is statistically close to uniform
+        let m = 2 * &n * Z::from(&q).log_ceil(2).unwrap();
+        let a = MatZq::sample_uniform(&n, &(&m + Z::ONE), &q);
+
+        Self { m, q, a }
+    }
+
+    /// Computes the commitment `A * [bit | r]^t`.
+    fn commit(&self, bit: bool, r: &MatZ) -> Commitment {
+        let mut msg = MatZ::new(1, 1);
+        msg.set_entry(0, 0, bit as i64).unwrap();
+        let vector = msg.concat_vertical(r).unwrap();
+
+        Commitment {
+            c: &self.a * &MatZq::from((&vector, &self.q)),
+        }
+    }
+
+    /// Performs the first step of party A, i.e. it commits to `bit` using
+    /// binary randomness sampled from `rng`.
+    ///
+    /// Parameters:
+    /// - `bit`: the bit chosen by party A
+    /// - `rng`: the random number generator used to sample the randomness
+    ///
+    /// Returns the [`Commitment`] that is sent to party B, the [`Opening`]
+    /// kept secret by party A until the reveal step, and `bit`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::coin_flipping::CoinFlipping;
+    ///
+    /// let protocol = CoinFlipping::new_from_n(8);
+    /// let (commitment, opening, bit) = protocol.party_a_commit(true, &mut rand::thread_rng());
+    /// ```
+    pub fn party_a_commit(&self, bit: bool, rng: &mut impl Rng) -> (Commitment, Opening, bool) {
+        let m = i64::try_from(&self.m).unwrap();
+        let mut r = MatZ::new(m, 1);
+        for i in 0..m {
+            r.set_entry(i, 0, rng.gen_range(0..=1_i64)).unwrap();
+        }
+
+        let commitment = self.commit(bit, &r);
+
+        (commitment, Opening { bit, r }, bit)
+    }
+
+    /// Performs the step of party B after receiving the commitment of party A,
+    /// i.e. it chooses a uniform bit.
+    ///
+    /// Parameters:
+    /// - `commit`: the commitment received from party A
+    ///
+    /// Returns the uniform bit chosen by party B.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::coin_flipping::CoinFlipping;
+    ///
+    /// let protocol = CoinFlipping::new_from_n(8);
+    /// let (commitment, _, _) = protocol.party_a_commit(true, &mut rand::thread_rng());
+    ///
+    /// let bit_b = protocol.party_b_respond(&commitment);
+    /// ```
+    pub fn party_b_respond(&self, _commit: &Commitment) -> bool {
+        rand::random()
+    }
+
+    /// Performs the reveal step of party A after receiving the bit of party B.
+    ///
+    /// Parameters:
+    /// - `opening`: the opening of the commitment of party A
+    ///
+    /// Returns the opening, which is sent to party B, and the committed bit.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::coin_flipping::CoinFlipping;
+    ///
+    /// let protocol = CoinFlipping::new_from_n(8);
+    /// let (_, opening, _) = protocol.party_a_commit(true, &mut rand::thread_rng());
+    ///
+    /// let (opening, bit) = protocol.party_a_reveal(opening);
+    /// assert!(bit);
+    /// ```
+    pub fn party_a_reveal(&self, opening: Opening) -> (Opening, bool) {
+        let bit = opening.bit;
+        (opening, bit)
+    }
+
+    /// Verifies that `reveal_a` opens `commit_a` and computes the coin.
+    ///
+    /// Parameters:
+    /// - `commit_a`: the commitment of party A
+    /// - `reveal_a`: the opening revealed by party A
+    /// - `bit_b`: the bit chosen by party B
+    ///
+    /// Returns the coin `bit_a XOR bit_b` or `None` if `reveal_a` is not a
+    /// valid opening of `commit_a`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::coin_flipping::CoinFlipping;
+    ///
+    /// let protocol = CoinFlipping::new_from_n(8);
+    /// let (commitment, opening, _) = protocol.party_a_commit(true, &mut rand::thread_rng());
+    ///
+    /// assert_eq!(Some(false), protocol.compute_coin(&commitment, &opening, true));
+    /// ```
+    pub fn compute_coin(
+        &self,
+        commit_a: &Commitment,
+        reveal_a: &Opening,
+        bit_b: bool,
+    ) -> Option<bool> {
+        let r = &reveal_a.r;
+        if !r.is_column_vector() || Z::from(r.get_num_rows()) != self.m {
+            return None;
+        }
+        for i in 0..r.get_num_rows() {
+            let entry: Z = r.get_entry(i, 0).unwrap();
+            if entry != Z::ZERO && entry != Z::ONE {
+                return None;
+            }
+        }
+
+        if &self.commit(reveal_a.bit, r) != commit_a {
+            return None;
+        }
+
+        Some(reveal_a.bit ^ bit_b)
+    }
+}
+
+#[cfg(test)]
+mod test_coin_flipping {
+    use super::CoinFlipping;
+    use qfall_math::{integer::Z, traits::SetEntry};
+
+    /// Ensures that the coin is uniform over 1000 honest executions.
+    #[test]
+    fn uniform_coin() {
+        let protocol = CoinFlipping::new_from_n(8);
+        let mut rng = rand::thread_rng();
+
+        let mut nr_ones = 0;
+        for _ in 0..1000 {
+            let (commitment, opening, _) = protocol.party_a_commit(rand::random(), &mut rng);
+            let bit_b = protocol.party_b_respond(&commitment);
+            let (opening, bit_a) = protocol.party_a_reveal(opening);
+
+            let coin = protocol.compute_coin(&commitment, &opening, bit_b).unwrap();
+            assert_eq!(bit_a ^ bit_b, coin);
+            if coin {
+                nr_ones += 1;
+            }
+        }
+
+        // the standard deviation is about 16, hence this fails with negligible probability
+        assert!((400..=600).contains(&nr_ones));
+    }
+
+    /// Ensures that the coin is uniform if only party B chooses its bit uniformly.
+    #[test]
+    fn uniform_coin_fixed_bit_a() {
+        let protocol = CoinFlipping::new_from_n(8);
+        let mut rng = rand::thread_rng();
+
+        let mut nr_ones = 0;
+        for _ in 0..1000 {
+            let (commitment, opening, _) = protocol.party_a_commit(true, &mut rng);
+            let bit_b = protocol.party_b_respond(&commitment);
+            if protocol.compute_coin(&commitment, &opening, bit_b).unwrap() {
+                nr_ones += 1;
+            }
+        }
+
+        assert!((400..=600).contains(&nr_ones));
+    }
+
+    /// Ensures that invalid openings are rejected.
+    #[test]
+    fn invalid_opening() {
+        let protocol = CoinFlipping::new_from_n(8);
+        let mut rng = rand::thread_rng();
+        let (commitment, opening, _) = protocol.party_a_commit(true, &mut rng);
+
+        let mut flipped_bit = opening.clone();
+        flipped_bit.bit = false;
+        let mut non_binary = opening.clone();
+        non_binary.r.set_entry(0, 0, Z::from(2)).unwrap();
+        let (_, other_opening, _) = protocol.party_a_commit(true, &mut rng);
+
+        assert!(protocol
+            .compute_coin(&commitment, &flipped_bit, true)
+            .is_none());
+        assert!(protocol
+            .compute_coin(&commitment, &non_binary, true)
+            .is_none());
+        assert!(protocol
+            .compute_coin(&commitment, &other_opening, true)
+            .is_none());
+    }
+
+    /// Ensures that commitments to the same bit are randomized.
+    #[test]
+    fn randomized_commitments() {
+        let protocol = CoinFlipping::new_from_n(8);
+        let mut rng = rand::thread_rng();
+
+        let (commitment_1, _, _) = protocol.party_a_commit(false, &mut rng);
+        let (commitment_2, _, _) = protocol.party_a_commit(false, &mut rng);
+
+        assert_ne!(commitment_1, commitment_2);
+    }
+}
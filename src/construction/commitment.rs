@@ -0,0 +1,73 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module provides the trait a struct should implement if it is an
+//! instance of a commitment scheme. Furthermore, it contains cryptographic
+//! schemes implementing the [`CommitmentScheme`] trait.
+
+mod sis;
+
+pub use sis::SISCommitment;
+
+use crate::error::CryptoError;
+
+/// This trait should be implemented by every commitment scheme.
+/// It offers a simple interface to use and implement commitment schemes.
+pub trait CommitmentScheme {
+    type PublicParameters;
+    type Value;
+    type Randomness;
+    type Commitment;
+
+    /// Samples the public parameters of the commitment scheme.
+    ///
+    /// Returns the [`Self::PublicParameters`] used to commit and open.
+    fn gen_params(&self) -> Self::PublicParameters;
+
+    /// Commits to `value` using the randomness `randomness`.
+    ///
+    /// Parameters:
+    /// - `pp`: specifies the public parameters
+    /// - `value`: specifies the value to which is committed
+    /// - `randomness`: specifies the randomness of the commitment
+    ///
+    /// Returns the commitment to `value`.
+    fn commit(
+        &self,
+        pp: &Self::PublicParameters,
+        value: &Self::Value,
+        randomness: &Self::Randomness,
+    ) -> Self::Commitment;
+
+    /// Checks whether `commitment` is a commitment to `value` with randomness `randomness`.
+    ///
+    /// Parameters:
+    /// - `pp`: specifies the public parameters
+    /// - `commitment`: specifies the commitment that is opened
+    /// - `value`: specifies the value to which `commitment` is supposedly committed
+    /// - `randomness`: specifies the randomness of the commitment
+    ///
+    /// Returns `true` if the opening is valid and `false` otherwise.
+    fn open(
+        &self,
+        pp: &Self::PublicParameters,
+        commitment: &Self::Commitment,
+        value: &Self::Value,
+        randomness: &Self::Randomness,
+    ) -> bool;
+
+    /// Checks whether the parameters ensure that the scheme is binding.
+    ///
+    /// Returns an empty result if the scheme is binding or a [`CryptoError`] otherwise.
+    fn check_binding(&self) -> Result<(), CryptoError>;
+
+    /// Checks whether the parameters ensure that the scheme is hiding.
+    ///
+    /// Returns an empty result if the scheme is hiding or a [`CryptoError`] otherwise.
+    fn check_hiding(&self) -> Result<(), CryptoError>;
+}
@@ -0,0 +1,345 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains an implementation of the SIS-based commitment scheme,
+//! also known as Ajtai commitment.
+
+use super::CommitmentScheme;
+use crate::error::CryptoError;
+use qfall_math::{
+    integer::{MatZ, Z},
+    integer_mod_q::{MatZq, Modulus},
+    traits::{Concatenate, GetEntry, GetNumColumns, GetNumRows},
+};
+use serde::{Deserialize, Serialize};
+
+/// This struct manages and stores the public parameters of a SIS-based commitment scheme.
+///
+/// A binary value `v ∈ {0, 1}^l` is committed to as `c = A * [v || r] mod q`
+/// for a uniform matrix `A ∈ Z_q^{n x (l + m)}` and a uniform binary randomness `r ∈ {0, 1}^m`.
+/// The commitment is statistically hiding due to the leftover hash lemma and
+/// computationally binding, as two different openings yield a short solution of SIS.
+///
+/// Attributes:
+/// - `n`: specifies the security parameter, i.e. the number of rows of `A`
+/// - `l`: defines the length of the committed values
+/// - `m`: defines the length of the randomness
+/// - `q`: specifies the modulus
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::construction::commitment::{CommitmentScheme, SISCommitment};
+/// use qfall_math::integer::MatZ;
+///
+/// let scheme = SISCommitment::new(4, 16, 80, 1021);
+/// let a = scheme.gen_params();
+///
+/// let value = MatZ::sample_uniform(16, 1, 0, 2).unwrap();
+/// let randomness = MatZ::sample_uniform(80, 1, 0, 2).unwrap();
+/// let commitment = scheme.commit(&a, &value, &randomness);
+///
+/// assert!(scheme.open(&a, &commitment, &value, &randomness));
+/// ```
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SISCommitment {
+    pub n: Z,
+    pub l: Z,
+    pub m: Z,
+    pub q: Modulus,
+}
+
+impl SISCommitment {
+    /// Initializes a [`SISCommitment`] instance with the given parameters.
+    /// The parameters are not checked for binding or hiding, which can be done
+    /// using [`CommitmentScheme::check_binding`] and [`CommitmentScheme::check_hiding`].
+    ///
+    /// Parameters:
+    /// - `n`: specifies the security parameter
+    /// - `l`: defines the length of the committed values
+    /// - `m`: defines the length of the randomness
+    /// - `q`: specifies the modulus
+    ///
+    /// Returns a [`SISCommitment`] instance.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::commitment::SISCommitment;
+    ///
+    /// let scheme = SISCommitment::new(4, 16, 80, 1021);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `n`, `l`, or `m` is smaller than `1`.
+    /// - if `q <= 1`.
+    pub fn new(n: impl Into<Z>, l: impl Into<Z>, m: impl Into<Z>, q: impl Into<Modulus>) -> Self {
+        let n: Z = n.into();
+        let l: Z = l.into();
+        let m: Z = m.into();
+        let q: Modulus = q.into();
+
+        if n < Z::ONE || l < Z::ONE || m < Z::ONE {
+            panic!("n, l, and m have to be larger than 0.");
+        }
+
+        Self { n, l, m, q }
+    }
+
+    /// Checks whether `vector` is a binary column vector of length `length`.
+    fn is_binary_vector(vector: &MatZ, length: &Z) -> bool {
+        if vector.get_num_columns() != 1 || Z::from(vector.get_num_rows()) != *length {
+            return false;
+        }
+
+        (0..vector.get_num_rows()).all(|i| {
+            let entry: Z = vector.get_entry(i, 0).unwrap();
+            entry == Z::ZERO || entry == Z::ONE
+        })
+    }
+}
+
+impl CommitmentScheme for SISCommitment {
+    type PublicParameters = MatZq;
+    type Value = MatZ;
+    type Randomness = MatZ;
+    type Commitment = MatZq;
+
+    /// Samples a uniform matrix `A ∈ Z_q^{n x (l + m)}`.
+    ///
+    /// Returns the public matrix `A`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::commitment::{CommitmentScheme, SISCommitment};
+    ///
+    /// let scheme = SISCommitment::new(4, 16, 80, 1021);
+    /// let a = scheme.gen_params();
+    /// ```
+    fn gen_params(&self) -> Self::PublicParameters {
+        MatZq::sample_uniform(&self.n, &(&self.l + &self.m), &self.q)
+    }
+
+    /// Computes the commitment `c = A * [v || r] mod q`.
+    ///
+    /// Parameters:
+    /// - `a`: specifies the public matrix `A`
+    /// - `value`: specifies the binary value `v` of length `l`
+    /// - `randomness`: specifies the binary randomness `r` of length `m`
+    ///
+    /// Returns the commitment `c`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::commitment::{CommitmentScheme, SISCommitment};
+    /// use qfall_math::integer::MatZ;
+    ///
+    /// let scheme = SISCommitment::new(4, 16, 80, 1021);
+    /// let a = scheme.gen_params();
+    /// let value = MatZ::sample_uniform(16, 1, 0, 2).unwrap();
+    /// let randomness = MatZ::sample_uniform(80, 1, 0, 2).unwrap();
+    ///
+    /// let commitment = scheme.commit(&a, &value, &randomness);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `value` and `randomness` do not have the same number of columns.
+    /// - if the number of rows of `[v || r]` does not match the number of columns of `A`.
+    fn commit(
+        &self,
+        a: &Self::PublicParameters,
+        value: &Self::Value,
+        randomness: &Self::Randomness,
+    ) -> Self::Commitment {
+        let vector = value.concat_vertical(randomness).unwrap();
+
+        a * &vector
+    }
+
+    /// Checks whether `commitment` is a commitment to `value` with randomness `randomness`
+    /// by checking that `value` and `randomness` are binary vectors of the correct length
+    /// and recomputing the commitment.
+    ///
+    /// Parameters:
+    /// - `a`: specifies the public matrix `A`
+    /// - `commitment`: specifies the commitment `c`
+    /// - `value`: specifies the value `v`
+    /// - `randomness`: specifies the randomness `r`
+    ///
+    /// Returns `true` if `c = A * [v || r] mod q` and `false` otherwise.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::commitment::{CommitmentScheme, SISCommitment};
+    /// use qfall_math::integer::MatZ;
+    ///
+    /// let scheme = SISCommitment::new(4, 16, 80, 1021);
+    /// let a = scheme.gen_params();
+    /// let value = MatZ::sample_uniform(16, 1, 0, 2).unwrap();
+    /// let randomness = MatZ::sample_uniform(80, 1, 0, 2).unwrap();
+    /// let commitment = scheme.commit(&a, &value, &randomness);
+    ///
+    /// assert!(scheme.open(&a, &commitment, &value, &randomness));
+    /// ```
+    fn open(
+        &self,
+        a: &Self::PublicParameters,
+        commitment: &Self::Commitment,
+        value: &Self::Value,
+        randomness: &Self::Randomness,
+    ) -> bool {
+        if !Self::is_binary_vector(value, &self.l) || !Self::is_binary_vector(randomness, &self.m) {
+            return false;
+        }
+
+        &self.commit(a, value, randomness) == commitment
+    }
+
+    /// Checks whether the parameters ensure that the scheme is binding.
+    /// Two different openings yield a solution `x ∈ {-1, 0, 1}^{l + m}` of
+    /// `A * x = 0 mod q`, whose norm is at most `β = sqrt(l + m)`.
+    /// Hence, `q > β * sqrt(n)` is required s.t. SIS is hard.
+    ///
+    /// Returns an empty result if the scheme is binding or a [`CryptoError`] otherwise.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::commitment::{CommitmentScheme, SISCommitment};
+    /// let scheme = SISCommitment::new(4, 16, 80, 1021);
+    ///
+    /// assert!(scheme.check_binding().is_ok());
+    /// ```
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] of type [`InvalidParameters`](CryptoError::InvalidParameters)
+    ///     if `q <= sqrt(n * (l + m))`.
+    fn check_binding(&self) -> Result<(), CryptoError> {
+        let q = Z::from(&self.q);
+
+        // q^2 > β^2 * n, where β^2 = l + m
+        if &q * &q <= &self.n * (&self.l + &self.m) {
+            return Err(CryptoError::InvalidParameters {
+                message: String::from(
+                    "q was chosen smaller than sqrt(n * (l + m)), but it must be larger to ensure that SIS is hard.",
+                ),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Checks whether the parameters ensure that the scheme is hiding.
+    /// According to the leftover hash lemma, `A * r` is statistically close to uniform
+    /// if `m >= 2 * n * ⌈log_2(q)⌉`.
+    ///
+    /// Returns an empty result if the scheme is hiding or a [`CryptoError`] otherwise.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::commitment::{CommitmentScheme, SISCommitment};
+    /// let scheme = SISCommitment::new(4, 16, 80, 1021);
+    ///
+    /// assert!(scheme.check_hiding().is_ok());
+    /// ```
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] of type [`InvalidParameters`](CryptoError::InvalidParameters)
+    ///     if `m < 2 * n * ⌈log_2(q)⌉`.
+    fn check_hiding(&self) -> Result<(), CryptoError> {
+        let q = Z::from(&self.q);
+
+        if self.m < 2 * &self.n * q.log_ceil(2).unwrap() {
+            return Err(CryptoError::InvalidParameters {
+                message: String::from(
+                    "m was chosen smaller than 2 * n * ⌈log_2(q)⌉, but it must be larger to ensure that the commitment is statistically hiding.",
+                ),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_sis_commitment {
+    use super::{CommitmentScheme, SISCommitment};
+    use qfall_math::{
+        integer::{MatZ, Z},
+        traits::{GetEntry, SetEntry},
+    };
+
+    /// Ensures that honestly generated commitments can be opened.
+    #[test]
+    fn honest_open() {
+        let scheme = SISCommitment::new(4, 16, 80, 1021);
+        let a = scheme.gen_params();
+
+        for _ in 0..5 {
+            let value = MatZ::sample_uniform(16, 1, 0, 2).unwrap();
+            let randomness = MatZ::sample_uniform(80, 1, 0, 2).unwrap();
+
+            let commitment = scheme.commit(&a, &value, &randomness);
+
+            assert!(scheme.open(&a, &commitment, &value, &randomness));
+        }
+    }
+
+    /// Ensures that the commitment can not be opened to a value, which differs in one bit.
+    #[test]
+    fn changed_bit_fails() {
+        let scheme = SISCommitment::new(4, 16, 80, 1021);
+        let a = scheme.gen_params();
+        let mut value = MatZ::sample_uniform(16, 1, 0, 2).unwrap();
+        let randomness = MatZ::sample_uniform(80, 1, 0, 2).unwrap();
+        let commitment = scheme.commit(&a, &value, &randomness);
+
+        let bit: Z = value.get_entry(3, 0).unwrap();
+        value.set_entry(3, 0, Z::ONE - bit).unwrap();
+
+        assert!(!scheme.open(&a, &commitment, &value, &randomness));
+    }
+
+    /// Ensures that openings with non-binary randomness are rejected,
+    /// even if they satisfy the linear equation.
+    #[test]
+    fn non_binary_fails() {
+        let scheme = SISCommitment::new(4, 16, 80, 1021);
+        let a = scheme.gen_params();
+        let value = MatZ::sample_uniform(16, 1, 0, 2).unwrap();
+        let randomness = MatZ::sample_uniform(80, 1, 2, 1021).unwrap();
+
+        let commitment = scheme.commit(&a, &value, &randomness);
+
+        assert!(!scheme.open(&a, &commitment, &value, &randomness));
+    }
+
+    /// Ensures that `check_binding` and `check_hiding` accept suitable parameters.
+    #[test]
+    fn suitable_parameters() {
+        let scheme = SISCommitment::new(4, 16, 80, 1021);
+
+        assert!(scheme.check_binding().is_ok());
+        assert!(scheme.check_hiding().is_ok());
+    }
+
+    /// Ensures that `check_binding` catches obviously insecure parameters.
+    #[test]
+    fn insecure_binding() {
+        // q = 3 <= sqrt(4 * 96)
+        let scheme = SISCommitment::new(4, 16, 80, 3);
+
+        assert!(scheme.check_binding().is_err());
+    }
+
+    /// Ensures that `check_hiding` catches obviously insecure parameters.
+    #[test]
+    fn insecure_hiding() {
+        // m = 1 < 2 * 4 * 10
+        let scheme = SISCommitment::new(4, 16, 1, 1021);
+
+        assert!(scheme.check_hiding().is_err());
+    }
+}
@@ -0,0 +1,22 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains functional encryption schemes, where a functional key
+//! for a function `f` allows to compute `f(x)` from an encryption of `x`
+//! without revealing anything else about `x`.
+//!
+//! The main references are listed in the following
+//! and will be further referenced in submodules by these numbers:
+//! - \[1\] Abdalla, M., Bourse, F., De Caro, A., Pointcheval, D. (2015).
+//!     Simple Functional Encryption Schemes for Inner Products.
+//!     In: Public-Key Cryptography - PKC 2015.
+//!     <https://doi.org/10.1007/978-3-662-46447-2_33>
+
+mod linear_fe;
+
+pub use linear_fe::{FunctionalKey, LinearFE, LinearFECipher};
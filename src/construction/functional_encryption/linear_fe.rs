@@ -0,0 +1,369 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains an implementation of the LWE-based inner product
+//! functional encryption scheme [\[1\]](<index.html#:~:text=[1]>).
+
+use qfall_math::{
+    integer::{MatZ, Z},
+    integer_mod_q::{MatZq, Modulus, Zq},
+    traits::{GetEntry, Pow, SetEntry},
+};
+use serde::{Deserialize, Serialize};
+
+/// The number of bits of the plaintext modulus `p`.
+const PLAINTEXT_BITS: i64 = 32;
+
+/// The bound `B` such that every entry of a function vector lies in `[-B, B]`.
+const FUNCTION_BOUND: i64 = 256;
+
+/// A functional key for the linear function `x -> <f, x>`.
+///
+/// Attributes:
+/// - `f`: the function vector `f ∈ [-B, B]^ℓ`
+/// - `z_f`: the key vector `Z^t * f ∈ Z^m`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FunctionalKey {
+    pub f: MatZ,
+    pub z_f: MatZ,
+}
+
+/// A ciphertext of [`LinearFE`].
+///
+/// Attributes:
+/// - `c_0`: the vector `A * s + e_0 ∈ Z_q^m`
+/// - `c_1`: the vector `U * s + e_1 + q/p * x ∈ Z_q^ℓ`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LinearFECipher {
+    pub c_0: MatZq,
+    pub c_1: MatZq,
+}
+
+/// This struct manages and stores the public parameters of the LWE-based
+/// inner product functional encryption scheme [\[1\]](<index.html#:~:text=[1]>)
+/// for vectors of length `ℓ`.
+///
+/// The master secret key is a matrix `Z ∈ {-1, 0, 1}^{ℓ x m}` and the master public key
+/// consists of a uniform `A ∈ Z_q^{m x n}` and `U = Z * A ∈ Z_q^{ℓ x n}`.
+/// A vector `x ∈ Z_p^ℓ` is encrypted as `(A * s + e_0, U * s + e_1 + q/p * x)`.
+/// The functional key for `f ∈ [-256, 256]^ℓ` is `z_f = Z^t * f`, which allows to compute
+/// `<f, c_1> - <z_f, c_0> = <f, e_1> - <z_f, e_0> + q/p * <f, x>` and thus `<f, x> mod p`.
+///
+/// The modulus `q = p * q'` is chosen for `p = 2^32` and a prime `q'`,
+/// which bounds the error of any decryption.
+///
+/// **WARNING:** The errors are chosen uniformly from `{-1, 0, 1}`.
+/// This implementation is meant for educational purposes.
+///
+/// Attributes:
+/// - `n`: specifies the security parameter, which is not equal to the bit-security level
+/// - `m`: specifies the number of rows of `A`
+/// - `vec_len`: specifies the length `ℓ` of encrypted vectors
+/// - `q`: specifies the modulus
+/// - `p`: specifies the plaintext modulus
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::construction::functional_encryption::LinearFE;
+/// use qfall_math::integer::Z;
+///
+/// let fe = LinearFE::new_from_n(4, 3);
+/// let (mpk, msk) = fe.setup();
+///
+/// let fk = fe.key_gen(&msk, &[Z::from(1), Z::from(2), Z::from(3)]);
+/// let cipher = fe.enc(&mpk, &[Z::from(4), Z::from(5), Z::from(6)]);
+///
+/// assert_eq!(Z::from(32), fe.dec(&fk, &cipher));
+/// ```
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LinearFE {
+    n: Z,
+    m: Z,
+    vec_len: usize,
+    q: Modulus,
+    p: Z,
+}
+
+impl LinearFE {
+    /// Initializes a [`LinearFE`] instance for security parameter `n` and
+    /// vectors of length `vec_len`. Therefore, the smallest `k` is chosen such that
+    /// `2^{k - 1} > 2 * ℓ * B * (m + 1)` with `m = (n + 1) * (32 + k)`,
+    /// which bounds the error of any decryption, and `q = 2^32 * q'`
+    /// for a prime `q' ∈ [2^{k - 1}, 2^k)`.
+    ///
+    /// Parameters:
+    /// - `n`: the security parameter
+    /// - `vec_len`: the length of encrypted vectors
+    ///
+    /// Returns a [`LinearFE`] instance.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::functional_encryption::LinearFE;
+    ///
+    /// let fe = LinearFE::new_from_n(4, 3);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `n < 1` or `vec_len < 1`.
+    pub fn new_from_n(n: impl Into<Z>, vec_len: usize) -> Self {
+        let n: Z = n.into();
+        if n < Z::ONE || vec_len < 1 {
+            panic!("The security parameter and the vector length have to be larger than 0");
+        }
+
+        let p = Z::from(2).pow(PLAINTEXT_BITS).unwrap();
+        let function_bound = Z::from(2 * vec_len as u64) * Z::from(FUNCTION_BOUND);
+        let mut k = Z::from(8);
+        loop {
+            let m = (&n + Z::ONE) * (&k + Z::from(PLAINTEXT_BITS));
+            if Z::from(2).pow(&k - Z::ONE).unwrap() > &function_bound * (m + Z::ONE) {
+                break;
+            }
+            k = k + Z::ONE;
+        }
+
+        let upper_bound = Z::from(2).pow(&k).unwrap();
+        let lower_bound = upper_bound.div_floor(2);
+        let q_prime = Z::sample_prime_uniform(&lower_bound, &upper_bound).unwrap();
+        let q = Modulus::from(&p * q_prime);
+
+        let m = (&n + Z::ONE) * Z::from(&q).log_ceil(2).unwrap();
+
+        Self {
+            n,
+            m,
+            vec_len,
+            q,
+            p,
+        }
+    }
+
+    /// Converts `values` into a column vector of length `ℓ`.
+    ///
+    /// # Panics ...
+    /// - if the length of `values` does not match `ℓ`.
+    fn to_column_vector(&self, values: &[Z]) -> MatZ {
+        if values.len() != self.vec_len {
+            panic!(
+                "The vector has to be of length {}, but is of length {}",
+                self.vec_len,
+                values.len()
+            );
+        }
+
+        let mut out = MatZ::new(self.vec_len, 1);
+        for (i, value) in values.iter().enumerate() {
+            out.set_entry(i as i64, 0, value).unwrap();
+        }
+        out
+    }
+
+    /// Generates a (mpk, msk) pair.
+    ///
+    /// Returns the master public key `(A, U)` and the master secret key `Z`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::functional_encryption::LinearFE;
+    /// let fe = LinearFE::new_from_n(4, 3);
+    ///
+    /// let (mpk, msk) = fe.setup();
+    /// ```
+    pub fn setup(&self) -> ((MatZq, MatZq), MatZ) {
+        let mat_a = MatZq::sample_uniform(&self.m, &self.n, &self.q);
+        let msk = MatZ::sample_uniform(self.vec_len, &self.m, -1, 2).unwrap();
+        let mat_u = MatZq::from((&msk, &self.q)) * &mat_a;
+
+        ((mat_a, mat_u), msk)
+    }
+
+    /// Generates the functional key for the linear function `x -> <f, x>`.
+    ///
+    /// Parameters:
+    /// - `msk`: the master secret key
+    /// - `f`: the function vector with entries in `[-256, 256]`
+    ///
+    /// Returns the [`FunctionalKey`] for `f`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::functional_encryption::LinearFE;
+    /// use qfall_math::integer::Z;
+    /// let fe = LinearFE::new_from_n(4, 3);
+    /// let (mpk, msk) = fe.setup();
+    ///
+    /// let fk = fe.key_gen(&msk, &[Z::ONE, Z::ZERO, Z::MINUS_ONE]);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if the length of `f` does not match `ℓ`.
+    /// - if an entry of `f` is not in `[-256, 256]`.
+    pub fn key_gen(&self, msk: &MatZ, f: &[Z]) -> FunctionalKey {
+        if f.iter().any(|entry| entry.abs() > Z::from(FUNCTION_BOUND)) {
+            panic!("The entries of the function vector have to be in [-{FUNCTION_BOUND}, {FUNCTION_BOUND}]");
+        }
+
+        let f = self.to_column_vector(f);
+        let z_f = msk.transpose() * &f;
+
+        FunctionalKey { f, z_f }
+    }
+
+    /// Encrypts the vector `x mod p` under `mpk`.
+    ///
+    /// Parameters:
+    /// - `mpk`: the master public key
+    /// - `x`: the vector that should be encrypted
+    ///
+    /// Returns the [`LinearFECipher`] encrypting `x`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::functional_encryption::LinearFE;
+    /// use qfall_math::integer::Z;
+    /// let fe = LinearFE::new_from_n(4, 3);
+    /// let (mpk, msk) = fe.setup();
+    ///
+    /// let cipher = fe.enc(&mpk, &[Z::from(4), Z::from(5), Z::from(6)]);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if the length of `x` does not match `ℓ`.
+    pub fn enc(&self, mpk: &(MatZq, MatZq), x: &[Z]) -> LinearFECipher {
+        let vec_x = self.to_column_vector(x);
+        let (mat_a, mat_u) = mpk;
+
+        let vec_s = MatZq::sample_uniform(&self.n, 1, &self.q);
+        let vec_e_0 = MatZ::sample_uniform(&self.m, 1, -1, 2).unwrap();
+        let vec_e_1 = MatZ::sample_uniform(self.vec_len, 1, -1, 2).unwrap();
+
+        let delta = Z::from(&self.q).div_floor(&self.p);
+        let c_0 = mat_a * &vec_s + MatZq::from((&vec_e_0, &self.q));
+        let c_1 = mat_u * &vec_s + MatZq::from((&(vec_e_1 + &delta * &vec_x), &self.q));
+
+        LinearFECipher { c_0, c_1 }
+    }
+
+    /// Decrypts `cipher` using the functional key `fk` for `f`.
+    ///
+    /// Parameters:
+    /// - `fk`: the functional key for `f`
+    /// - `cipher`: the encryption of `x`
+    ///
+    /// Returns `<f, x> mod p` as a value in `[0, p)`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::functional_encryption::LinearFE;
+    /// use qfall_math::integer::Z;
+    /// let fe = LinearFE::new_from_n(4, 3);
+    /// let (mpk, msk) = fe.setup();
+    /// let fk = fe.key_gen(&msk, &[Z::ONE, Z::ONE, Z::ONE]);
+    /// let cipher = fe.enc(&mpk, &[Z::from(4), Z::from(5), Z::from(6)]);
+    ///
+    /// assert_eq!(Z::from(15), fe.dec(&fk, &cipher));
+    /// ```
+    pub fn dec(&self, fk: &FunctionalKey, cipher: &LinearFECipher) -> Z {
+        let f = MatZq::from((&fk.f.transpose(), &self.q));
+        let z_f = MatZq::from((&fk.z_f.transpose(), &self.q));
+
+        let result: Zq = (f * &cipher.c_1 - z_f * &cipher.c_0)
+            .get_entry(0, 0)
+            .unwrap();
+        let result = result.get_representative_0_modulus();
+
+        let q = Z::from(&self.q);
+        (result * &self.p + q.div_floor(2))
+            .div_floor(&q)
+            .modulo(&self.p)
+    }
+}
+
+#[cfg(test)]
+mod test_linear_fe {
+    use super::LinearFE;
+    use qfall_math::integer::Z;
+
+    /// Returns a vector of length `len` with uniform entries in `[0, 1000)`.
+    fn sample_vector(len: usize) -> Vec<Z> {
+        (0..len)
+            .map(|_| Z::sample_uniform(0, 1000).unwrap())
+            .collect()
+    }
+
+    /// Ensures that the first unit vector recovers the first entry.
+    #[test]
+    fn first_entry() {
+        let fe = LinearFE::new_from_n(4, 5);
+        let (mpk, msk) = fe.setup();
+        let x = sample_vector(5);
+
+        let mut f = vec![Z::ZERO; 5];
+        f[0] = Z::ONE;
+        let fk = fe.key_gen(&msk, &f);
+        let cipher = fe.enc(&mpk, &x);
+
+        assert_eq!(x[0], fe.dec(&fk, &cipher));
+    }
+
+    /// Ensures that the all-ones vector recovers the sum of all entries.
+    #[test]
+    fn sum() {
+        let fe = LinearFE::new_from_n(4, 5);
+        let (mpk, msk) = fe.setup();
+        let x = sample_vector(5);
+
+        let fk = fe.key_gen(&msk, &vec![Z::ONE; 5]);
+        let cipher = fe.enc(&mpk, &x);
+
+        let sum = x.iter().fold(Z::ZERO, |acc, entry| acc + entry);
+        assert_eq!(sum, fe.dec(&fk, &cipher));
+    }
+
+    /// Ensures that negative inner products are computed modulo `p`
+    /// and maximal function entries are decrypted correctly.
+    #[test]
+    fn negative_and_maximal_entries() {
+        let fe = LinearFE::new_from_n(4, 3);
+        let (mpk, msk) = fe.setup();
+        let x = [Z::from(1000), Z::from(7), Z::from(3)];
+
+        let fk = fe.key_gen(&msk, &[Z::from(-256), Z::from(256), Z::ZERO]);
+        let cipher = fe.enc(&mpk, &x);
+
+        let expected = Z::from(256 * 7 - 256 * 1000).modulo(Z::from(u32::MAX) + Z::ONE);
+        assert_eq!(expected, fe.dec(&fk, &cipher));
+    }
+
+    /// Ensures that a functional key of an unrelated master secret key
+    /// does not yield the inner product.
+    #[test]
+    fn unrelated_key() {
+        let fe = LinearFE::new_from_n(4, 5);
+        let (mpk, _) = fe.setup();
+        let (_, other_msk) = fe.setup();
+        let x = sample_vector(5);
+
+        let fk = fe.key_gen(&other_msk, &vec![Z::ONE; 5]);
+        let cipher = fe.enc(&mpk, &x);
+
+        let sum = x.iter().fold(Z::ZERO, |acc, entry| acc + entry);
+        assert_ne!(sum, fe.dec(&fk, &cipher));
+    }
+
+    /// Ensures that function vectors with too large entries are rejected.
+    #[test]
+    #[should_panic]
+    fn too_large_function() {
+        let fe = LinearFE::new_from_n(4, 2);
+        let (_, msk) = fe.setup();
+
+        let _ = fe.key_gen(&msk, &[Z::from(257), Z::ZERO]);
+    }
+}
@@ -0,0 +1,361 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains a linearly homomorphic message authentication code,
+//! whose tags are noisy images `A * m + e` of the messages under a secret matrix `A`.
+//! Linear combinations of tags authenticate the same linear combination of the messages.
+
+use qfall_math::{
+    integer::{MatZ, Z},
+    integer_mod_q::{MatZq, Modulus, Zq},
+    rational::Q,
+    traits::{Distance, GetEntry, GetNumColumns, GetNumRows, Pow},
+};
+use serde::{Deserialize, Serialize};
+
+/// A tag of a message generated by [`FunctionalMAC::mac`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Tag(pub MatZq);
+
+/// A tag of a linear combination of two messages generated by [`FunctionalMAC::eval`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EvalTag(pub MatZq);
+
+/// The linear combination `f(m_1, m_2) = c_1 * m_1 + c_2 * m_2`.
+///
+/// Attributes:
+/// - `c_1`: the coefficient of the first message
+/// - `c_2`: the coefficient of the second message
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LinearCombination {
+    pub c_1: Z,
+    pub c_2: Z,
+}
+
+/// This struct manages and stores the public parameters of a linearly
+/// homomorphic MAC for messages in `Z^n`.
+///
+/// A tag of `m` is `A * m + e mod q` for the secret key `A ∈ Z_q^{n x n}`
+/// and a discrete Gaussian `e`. A tag is accepted if `||A * m - tag||_∞ < threshold`.
+/// The tag `c_1 * tag_1 + c_2 * tag_2` of a linear combination
+/// has the error `c_1 * e_1 + c_2 * e_2` and is accepted
+/// with the threshold scaled by `|c_1| + |c_2|`.
+///
+/// **WARNING:** Anyone can evaluate a linear combination on tags. Hence, the tags
+/// only authenticate that a message is a linear combination of authenticated messages.
+///
+/// Attributes:
+/// - `n`: specifies the security parameter and the length of the messages
+/// - `q`: specifies the modulus
+/// - `s`: specifies the Gaussian parameter of the error
+/// - `threshold`: specifies the bound on the infinity norm of the error of a tag
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::construction::functional_mac::{FunctionalMAC, LinearCombination};
+/// use qfall_math::integer::{MatZ, Z};
+///
+/// let mac = FunctionalMAC::new_from_n(16);
+/// let key = mac.gen();
+/// let msg_1 = MatZ::sample_uniform(16, 1, 0, 256).unwrap();
+/// let msg_2 = MatZ::sample_uniform(16, 1, 0, 256).unwrap();
+///
+/// let tag_1 = mac.mac(&key, &msg_1);
+/// let tag_2 = mac.mac(&key, &msg_2);
+/// assert!(mac.verify(&key, &msg_1, &tag_1));
+///
+/// let f = LinearCombination { c_1: Z::from(3), c_2: Z::from(-2) };
+/// let eval_tag = mac.eval(&tag_1, &tag_2, &f);
+/// assert!(mac.verify_eval(&key, &msg_1, &msg_2, &f, &eval_tag));
+/// ```
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FunctionalMAC {
+    n: Z,
+    q: Modulus,
+    s: Q,
+    threshold: Z,
+}
+
+impl FunctionalMAC {
+    /// Initializes a [`FunctionalMAC`] instance for security parameter `n` by choosing
+    /// a prime `q ∈ [n^5 / 2, n^5]`, the Gaussian parameter `s = sqrt(n)`
+    /// and the threshold `⌈n * s⌉`.
+    ///
+    /// Parameters:
+    /// - `n`: the security parameter and the length of the messages
+    ///
+    /// Returns a [`FunctionalMAC`] instance.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::functional_mac::FunctionalMAC;
+    ///
+    /// let mac = FunctionalMAC::new_from_n(16);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `n < 2`.
+    pub fn new_from_n(n: impl Into<Z>) -> Self {
+        let n: Z = n.into();
+        if n < Z::from(2) {
+            panic!("Security parameter n has to be larger than 1");
+        }
+
+        let upper_bound: Z = n.pow(5).unwrap();
+        let lower_bound = upper_bound.div_ceil(2);
+        let q = Modulus::from(Z::sample_prime_uniform(&lower_bound, &upper_bound).unwrap());
+
+        let s = n.sqrt();
+        // the error is bounded by `n * s` except with negligible probability
+        let threshold = (&s * &n).ceil();
+
+        Self { n, q, s, threshold }
+    }
+
+    /// Generates a secret key `A ∈ Z_q^{n x n}` chosen uniformly at random.
+    ///
+    /// Returns the secret key `A`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::functional_mac::FunctionalMAC;
+    ///
+    /// let mac = FunctionalMAC::new_from_n(16);
+    /// let key = mac.gen();
+    /// ```
+    pub fn gen(&self) -> MatZq {
+        MatZq::sample_uniform(&self.n, &self.n, &self.q)
+    }
+
+    /// Computes the tag `A * msg + e mod q` for a discrete Gaussian `e`.
+    ///
+    /// Parameters:
+    /// - `key`: the secret key `A`
+    /// - `msg`: the message, which is a column vector of length `n`
+    ///
+    /// Returns the [`Tag`] of `msg`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::functional_mac::FunctionalMAC;
+    /// use qfall_math::integer::MatZ;
+    ///
+    /// let mac = FunctionalMAC::new_from_n(16);
+    /// let key = mac.gen();
+    ///
+    /// let tag = mac.mac(&key, &MatZ::new(16, 1));
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `msg` is not a column vector of length `n`.
+    pub fn mac(&self, key: &MatZq, msg: &MatZ) -> Tag {
+        assert!(
+            self.is_message(msg),
+            "The message has to be a column vector of length n."
+        );
+        let e = MatZq::sample_discrete_gauss(&self.n, 1, &self.q, &self.n, 0, &self.s).unwrap();
+
+        Tag(key * msg + e)
+    }
+
+    /// Checks whether `||A * msg - tag||_∞ < threshold`.
+    ///
+    /// Parameters:
+    /// - `key`: the secret key `A`
+    /// - `msg`: the message, which is a column vector of length `n`
+    /// - `tag`: the tag of `msg`
+    ///
+    /// Returns `true` if `tag` is a valid tag of `msg` and `false` otherwise.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::functional_mac::FunctionalMAC;
+    /// use qfall_math::integer::MatZ;
+    ///
+    /// let mac = FunctionalMAC::new_from_n(16);
+    /// let key = mac.gen();
+    /// let msg = MatZ::new(16, 1);
+    /// let tag = mac.mac(&key, &msg);
+    ///
+    /// assert!(mac.verify(&key, &msg, &tag));
+    /// ```
+    pub fn verify(&self, key: &MatZq, msg: &MatZ, tag: &Tag) -> bool {
+        self.check(key, msg, &tag.0, &self.threshold)
+    }
+
+    /// Evaluates `f` homomorphically on two tags, i.e. computes `c_1 * tag_1 + c_2 * tag_2`.
+    ///
+    /// Parameters:
+    /// - `tag_1`: the tag of the first message
+    /// - `tag_2`: the tag of the second message
+    /// - `f`: the linear combination that is evaluated
+    ///
+    /// Returns the [`EvalTag`] of `f(msg_1, msg_2)`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::functional_mac::{FunctionalMAC, LinearCombination};
+    /// use qfall_math::integer::{MatZ, Z};
+    ///
+    /// let mac = FunctionalMAC::new_from_n(16);
+    /// let key = mac.gen();
+    /// let tag_1 = mac.mac(&key, &MatZ::new(16, 1));
+    /// let tag_2 = mac.mac(&key, &MatZ::new(16, 1));
+    ///
+    /// let f = LinearCombination { c_1: Z::ONE, c_2: Z::ONE };
+    /// let eval_tag = mac.eval(&tag_1, &tag_2, &f);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if the dimensions or moduli of the tags do not match.
+    pub fn eval(&self, tag_1: &Tag, tag_2: &Tag, f: &LinearCombination) -> EvalTag {
+        EvalTag(&f.c_1 * &tag_1.0 + &f.c_2 * &tag_2.0)
+    }
+
+    /// Checks whether `eval_tag` is a valid tag of `f(msg_1, msg_2) = c_1 * msg_1 + c_2 * msg_2`,
+    /// i.e. whether `||A * f(msg_1, msg_2) - eval_tag||_∞ < (|c_1| + |c_2|) * threshold`.
+    ///
+    /// Parameters:
+    /// - `key`: the secret key `A`
+    /// - `msg_1`: the first message
+    /// - `msg_2`: the second message
+    /// - `f`: the linear combination, which was evaluated
+    /// - `eval_tag`: the tag generated by [`FunctionalMAC::eval`]
+    ///
+    /// Returns `true` if `eval_tag` is a valid tag of `f(msg_1, msg_2)` and `false` otherwise.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::functional_mac::{FunctionalMAC, LinearCombination};
+    /// use qfall_math::integer::{MatZ, Z};
+    ///
+    /// let mac = FunctionalMAC::new_from_n(16);
+    /// let key = mac.gen();
+    /// let msg_1 = MatZ::sample_uniform(16, 1, 0, 256).unwrap();
+    /// let msg_2 = MatZ::sample_uniform(16, 1, 0, 256).unwrap();
+    /// let f = LinearCombination { c_1: Z::from(2), c_2: Z::from(5) };
+    ///
+    /// let eval_tag = mac.eval(&mac.mac(&key, &msg_1), &mac.mac(&key, &msg_2), &f);
+    ///
+    /// assert!(mac.verify_eval(&key, &msg_1, &msg_2, &f, &eval_tag));
+    /// ```
+    pub fn verify_eval(
+        &self,
+        key: &MatZq,
+        msg_1: &MatZ,
+        msg_2: &MatZ,
+        f: &LinearCombination,
+        eval_tag: &EvalTag,
+    ) -> bool {
+        if !self.is_message(msg_1) || !self.is_message(msg_2) {
+            return false;
+        }
+
+        let msg = &f.c_1 * msg_1 + &f.c_2 * msg_2;
+        let threshold = (f.c_1.abs() + f.c_2.abs()) * &self.threshold;
+        self.check(key, &msg, &eval_tag.0, &threshold)
+    }
+
+    /// Checks whether `msg` is a column vector of length `n`.
+    fn is_message(&self, msg: &MatZ) -> bool {
+        Z::from(msg.get_num_rows()) == self.n && msg.get_num_columns() == 1
+    }
+
+    /// Checks whether `||A * msg - tag||_∞ < threshold` for matching dimensions and moduli.
+    fn check(&self, key: &MatZq, msg: &MatZ, tag: &MatZq, threshold: &Z) -> bool {
+        if !self.is_message(msg)
+            || tag.get_num_rows() != key.get_num_rows()
+            || tag.get_num_columns() != 1
+            || tag.get_mod() != key.get_mod()
+        {
+            return false;
+        }
+
+        let error = key * msg - tag;
+        (0..error.get_num_rows()).all(|i| {
+            let entry: Zq = error.get_entry(i, 0).unwrap();
+            &entry.distance(Z::ZERO) < threshold
+        })
+    }
+}
+
+#[cfg(test)]
+mod test_functional_mac {
+    use super::{FunctionalMAC, LinearCombination, Tag};
+    use qfall_math::{
+        integer::{MatZ, Z},
+        traits::{GetEntry, SetEntry},
+    };
+
+    /// Returns a message of length `16` with entries in `[0, 256)`.
+    fn message() -> MatZ {
+        MatZ::sample_uniform(16, 1, 0, 256).unwrap()
+    }
+
+    /// Ensures that tags of messages verify.
+    #[test]
+    fn cycle() {
+        let mac = FunctionalMAC::new_from_n(16);
+        let key = mac.gen();
+
+        for _ in 0..10 {
+            let msg = message();
+            let tag = mac.mac(&key, &msg);
+
+            assert!(mac.verify(&key, &msg, &tag));
+        }
+    }
+
+    /// Ensures that tags are rejected for other messages and other keys.
+    #[test]
+    fn invalid_tags_rejected() {
+        let mac = FunctionalMAC::new_from_n(16);
+        let key = mac.gen();
+        let msg = message();
+        let tag = mac.mac(&key, &msg);
+
+        let mut other_msg = msg.clone();
+        let entry: Z = other_msg.get_entry(0, 0).unwrap();
+        other_msg.set_entry(0, 0, entry + Z::ONE).unwrap();
+
+        assert!(!mac.verify(&key, &other_msg, &tag));
+        assert!(!mac.verify(&mac.gen(), &msg, &tag));
+        assert!(!mac.verify(&key, &MatZ::new(8, 1), &Tag(tag.0.clone())));
+    }
+
+    /// Ensures that the evaluation of a linear combination on two tags
+    /// verifies the same linear combination of the messages.
+    #[test]
+    fn eval_linear_combination() {
+        let mac = FunctionalMAC::new_from_n(16);
+        let key = mac.gen();
+        let msg_1 = message();
+        let msg_2 = message();
+        let tag_1 = mac.mac(&key, &msg_1);
+        let tag_2 = mac.mac(&key, &msg_2);
+
+        for (c_1, c_2) in [(1, 1), (3, -2), (0, 7), (-5, 0)] {
+            let f = LinearCombination {
+                c_1: Z::from(c_1),
+                c_2: Z::from(c_2),
+            };
+
+            let eval_tag = mac.eval(&tag_1, &tag_2, &f);
+
+            assert!(mac.verify_eval(&key, &msg_1, &msg_2, &f, &eval_tag));
+            // `msg_1 + msg_2` differs from `f(msg_1, msg_2)` except for `(1, 1)`
+            if (c_1, c_2) != (1, 1) {
+                let sum = LinearCombination {
+                    c_1: Z::ONE,
+                    c_2: Z::ONE,
+                };
+                assert!(!mac.verify_eval(&key, &msg_1, &msg_2, &sum, &eval_tag));
+            }
+        }
+    }
+}
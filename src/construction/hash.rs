@@ -14,9 +14,14 @@
 //!     In: Theoretical Computer Science 10.4.
 //!     <https://web.eecs.umich.edu/~cpeikert/pubs/lattice-survey.pdf>
 
+pub mod blake3;
+mod mock;
 pub mod sha256;
+mod shake256;
 mod sis;
 
+pub use mock::MockHash;
+pub use shake256::Shake256Hash;
 pub use sis::SISHash;
 
 /// This trait should be implemented by hashes with domain [`str`].
@@ -0,0 +1,204 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains hashes into different domains based on the
+//! extendable output function (XOF) of BLAKE3.
+
+use super::HashInto;
+use qfall_math::utils::index::evaluate_indices;
+use qfall_math::{
+    integer::Z,
+    integer_mod_q::{MatZq, Modulus},
+    traits::SetEntry,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// Hashes a given String literal into a [`MatZq`] using the XOF of BLAKE3.
+/// Each entry is sampled uniformly at random from `Z_q` by rejection sampling
+/// from the output stream of the XOF, i.e. `⌈log_2(q)⌉` bits are read and the
+/// value is rejected if it is not smaller than `q`.
+/// Hence, on average at most two tries are needed per entry.
+///
+/// Parameters:
+/// - `string`: specifies the value that is hashed
+/// - `num_rows`: specifies the number of rows of the result
+/// - `num_cols`: specifies the number of columns of the result
+/// - `modulus`: specifies the modulus of the returned [`MatZq`] value
+///
+/// Returns a [`MatZq`] as a hash for the given string.
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::construction::hash::blake3::hash_to_mat_zq_blake3;
+/// use qfall_math::integer_mod_q::MatZq;
+///
+/// let string = "Hello World!";
+///
+/// let hash: MatZq = hash_to_mat_zq_blake3(string, 2, 2, 7);
+/// assert_eq!(hash, hash_to_mat_zq_blake3(string, 2, 2, 7));
+/// ```
+///
+/// # Panics ...
+/// - if `modulus <= 1`.
+/// - if the number of rows or columns is less or equal to `0` or does not fit into an [`i64`].
+pub fn hash_to_mat_zq_blake3(
+    string: &str,
+    num_rows: impl TryInto<i64> + Display,
+    num_cols: impl TryInto<i64> + Display,
+    modulus: impl Into<Modulus>,
+) -> MatZq {
+    let modulus = modulus.into();
+    let (num_rows_new, num_cols_new) = evaluate_indices(num_rows, num_cols).unwrap();
+    let mut matrix = MatZq::new(num_rows_new, num_cols_new, modulus.clone());
+
+    let q = Z::from(&modulus);
+    let bits = (&q - Z::ONE).bits();
+    let num_bytes = bits.div_ceil(8) as usize;
+    // keeps only the lowest `bits mod 8` bits of the most significant byte (all if `0`)
+    let mask = (0xff_u16 >> (8 * num_bytes as u64 - bits)) as u8;
+
+    let mut hasher = ::blake3::Hasher::new();
+    hasher.update(format!("{q} {num_rows_new} {num_cols_new} {string}").as_bytes());
+    let mut xof = hasher.finalize_xof();
+
+    let mut buffer = vec![0_u8; num_bytes];
+    for i in 0..num_rows_new {
+        for j in 0..num_cols_new {
+            let entry = loop {
+                xof.fill(&mut buffer);
+                buffer[0] &= mask;
+
+                let hex: String = buffer.iter().map(|byte| format!("{byte:02x}")).collect();
+                let value = Z::from_str_b(&hex, 16).unwrap();
+                if value < q {
+                    break value;
+                }
+            };
+            matrix.set_entry(i, j, entry).unwrap();
+        }
+    }
+    matrix
+}
+
+/// Object for hashing Strings into a [`MatZq`] using the XOF of BLAKE3.
+/// The object fixes the modulus and the corresponding dimensions.
+///
+/// Parameters:
+/// - `modulus`: Defines the range in which each entry is hashed
+/// - `rows`: Defines the number of rows of the hash value
+/// - `cols`: Defines the number of columns of the hash value
+///
+/// Returns a [`MatZq`] as a hash for the given string.
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::construction::hash::{HashInto, blake3::HashMatZqBlake3};
+/// use qfall_math::integer_mod_q::Modulus;
+///
+/// let modulus = Modulus::from(7);
+///
+/// let hasher = HashMatZqBlake3 {
+///     modulus,
+///     rows: 17,
+///     cols: 3,
+/// };
+/// let hash_val = hasher.hash("Hello");
+/// ```
+#[derive(Serialize, Deserialize)]
+pub struct HashMatZqBlake3 {
+    pub modulus: Modulus,
+    pub rows: i64,
+    pub cols: i64,
+}
+
+impl HashInto<MatZq> for HashMatZqBlake3 {
+    /// Hashes a given String literal into a [`MatZq`] using [`hash_to_mat_zq_blake3`].
+    /// The dimensions and the modulus is fixed by the hash object.
+    ///
+    /// Parameters:
+    /// - `string`: specifies the value that is hashed
+    ///
+    /// Returns a [`MatZq`] as a hash for the given string.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::hash::{HashInto, blake3::HashMatZqBlake3};
+    /// use qfall_math::integer_mod_q::Modulus;
+    ///
+    /// let modulus = Modulus::from(7);
+    ///
+    /// let hasher = HashMatZqBlake3 {
+    ///     modulus,
+    ///     rows: 17,
+    ///     cols: 3,
+    /// };
+    /// let hash_val = hasher.hash("Hello");
+    /// ```
+    fn hash(&self, m: &str) -> MatZq {
+        hash_to_mat_zq_blake3(m, self.rows, self.cols, &self.modulus)
+    }
+}
+
+#[cfg(test)]
+mod test_hash_mat_zq_blake3 {
+    use super::{hash_to_mat_zq_blake3, HashInto, HashMatZqBlake3};
+    use qfall_math::{
+        integer::Z,
+        integer_mod_q::Modulus,
+        traits::{GetEntry, GetNumColumns, GetNumRows, Pow},
+    };
+
+    /// Ensures that the same string is hashed to the same matrix
+    /// and different strings are hashed to different matrices.
+    #[test]
+    fn deterministic() {
+        let hasher = HashMatZqBlake3 {
+            modulus: Modulus::from(113),
+            rows: 4,
+            cols: 2,
+        };
+
+        let hash_val = hasher.hash("Hello World!");
+
+        assert_eq!(hash_val, hasher.hash("Hello World!"));
+        assert_ne!(hash_val, hasher.hash("Hello World"));
+    }
+
+    /// Ensures that the hash value has the correct dimensions and that all
+    /// entries are in `[0, q)`, also for moduli that are not a power of two
+    /// and moduli larger than 64 bit.
+    #[test]
+    fn entries_in_range() {
+        for q in [
+            Z::from(2),
+            Z::from(113),
+            Z::from(256),
+            Z::from(271).pow(10).unwrap(),
+        ] {
+            let hash_val = hash_to_mat_zq_blake3("Hello World!", 5, 3, &q);
+
+            assert_eq!(5, hash_val.get_num_rows());
+            assert_eq!(3, hash_val.get_num_columns());
+            for i in 0..5 {
+                for j in 0..3 {
+                    let entry: Z = hash_val.get_entry(i, j).unwrap();
+                    assert!(entry >= Z::ZERO);
+                    assert!(entry < q);
+                }
+            }
+        }
+    }
+
+    /// Ensures that the function panics for invalid dimensions.
+    #[test]
+    #[should_panic]
+    fn invalid_dimensions() {
+        let _ = hash_to_mat_zq_blake3("Hello World!", 0, 0, 16);
+    }
+}
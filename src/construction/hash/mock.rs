@@ -0,0 +1,197 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains a mock hash, whose output is defined by a closure,
+//! to write unit tests independent of the output of actual hash functions.
+
+use super::HashInto;
+use qfall_math::integer_mod_q::MatZq;
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+/// A mock hash into [`MatZq`], whose output is determined by a user-supplied closure.
+///
+/// **WARNING:** This hash is only meant for testing and provides no security.
+/// The closure can not be serialized. Hence, a [`MockHash`] is serialized as unit
+/// s.t. it can be used in constructions like [`FDH`](crate::construction::signature::FDH),
+/// but deserializing a [`MockHash`] always fails.
+///
+/// Attributes:
+/// - `f`: the closure defining the output of the hash
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::construction::hash::{HashInto, MockHash};
+/// use qfall_math::integer_mod_q::MatZq;
+///
+/// let fixed = MatZq::sample_uniform(4, 1, 113);
+/// let output = fixed.clone();
+/// let hash = MockHash::new(move |_| output.clone());
+///
+/// assert_eq!(fixed, hash.hash("Hello World!"));
+/// ```
+pub struct MockHash {
+    f: Box<dyn Fn(&str) -> MatZq>,
+}
+
+impl MockHash {
+    /// Initializes a [`MockHash`], which hashes a message `m` into `f(m)`.
+    ///
+    /// Parameters:
+    /// - `f`: the closure defining the output of the hash
+    ///
+    /// Returns a [`MockHash`] instance.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::hash::MockHash;
+    /// use qfall_math::integer_mod_q::MatZq;
+    ///
+    /// let hash = MockHash::new(|m| MatZq::new(m.len(), 1, 113));
+    /// ```
+    pub fn new(f: impl Fn(&str) -> MatZq + 'static) -> Self {
+        Self { f: Box::new(f) }
+    }
+}
+
+impl HashInto<MatZq> for MockHash {
+    /// Hashes `m` by evaluating the closure of the [`MockHash`].
+    ///
+    /// Parameters:
+    /// - `m`: specifies the value that is hashed
+    ///
+    /// Returns `f(m)`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::hash::{HashInto, MockHash};
+    /// use qfall_math::integer_mod_q::MatZq;
+    ///
+    /// let hash = MockHash::new(|m| MatZq::new(m.len(), 1, 113));
+    ///
+    /// assert_eq!(MatZq::new(5, 1, 113), hash.hash("Hello"));
+    /// ```
+    fn hash(&self, m: &str) -> MatZq {
+        (self.f)(m)
+    }
+}
+
+impl fmt::Debug for MockHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("MockHash")
+    }
+}
+
+impl Serialize for MockHash {
+    /// Serializes a [`MockHash`] as unit, as its closure can not be serialized.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_unit()
+    }
+}
+
+impl<'de> Deserialize<'de> for MockHash {
+    /// Always fails, as the closure of a [`MockHash`] can not be deserialized.
+    fn deserialize<D: Deserializer<'de>>(_: D) -> Result<Self, D::Error> {
+        Err(D::Error::custom("A MockHash can not be deserialized."))
+    }
+}
+
+#[cfg(test)]
+mod test_mock_hash {
+    use super::MockHash;
+    use crate::{
+        construction::{
+            hash::HashInto,
+            identity_based_encryption::{DualRegevIBE, EncState, IBEScheme},
+            signature::{SignatureScheme, FDH},
+        },
+        primitive::psf::{PSF, PSFGPV},
+        sample::g_trapdoor::gadget_parameters::GadgetParameters,
+    };
+    use qfall_math::{
+        integer::Z,
+        integer_mod_q::MatZq,
+        rational::Q,
+        traits::{Concatenate, GetNumRows},
+    };
+    use std::{collections::HashMap, marker::PhantomData};
+
+    /// Ensures that the closure defines the output of the hash.
+    #[test]
+    fn output_defined_by_closure() {
+        let hash = MockHash::new(|m| MatZq::new(m.len(), 1, 113));
+
+        assert_eq!(MatZq::new(3, 1, 113), hash.hash("abc"));
+        assert_eq!(MatZq::new(1, 1, 113), hash.hash("a"));
+    }
+
+    /// Ensures that [`DualRegevIBE`] with a [`MockHash`] outputting a fixed matrix
+    /// maps all identities to the same identity-based public key,
+    /// s.t. a key extracted for one identity decrypts ciphertexts of any identity.
+    #[test]
+    fn dual_regev_ibe_fixed_output() {
+        let ibe = DualRegevIBE::default();
+        let (pk, sk) = ibe.setup();
+        let fixed = MatZq::sample_uniform(pk.get_num_rows(), 1, pk.get_mod());
+        let output = fixed.clone();
+        let hash = MockHash::new(move |_| output.clone());
+
+        // extraction with the mock hash is predictable
+        let id_sk = ibe.psf.samp_p(&pk, &sk, &hash.hash("Alice"));
+        assert_eq!(fixed, ibe.psf.f_a(&pk, &id_sk));
+
+        // encryption with the mock hash
+        let state = EncState {
+            identity_hash: hash.hash("Bob"),
+            identity_based_pk: pk.concat_horizontal(&hash.hash("Bob")).unwrap(),
+        };
+        for msg in [Z::ZERO, Z::ONE] {
+            let cipher = ibe.enc_with_state(&state, &msg);
+            assert_eq!(msg, ibe.dec(&id_sk, &cipher).unwrap());
+        }
+    }
+
+    /// Ensures that [`FDH`] with a [`MockHash`] outputting a fixed matrix
+    /// accepts the signature of one message for any message.
+    #[test]
+    fn fdh_fixed_output() {
+        let psf = PSFGPV {
+            gp: GadgetParameters::init_default(4, 113),
+            s: Q::from(17),
+            cdt: None,
+            cdt_cache: Default::default(),
+        };
+        let fixed = MatZq::sample_uniform(4, 1, 113);
+        let mut fdh = FDH {
+            psf: Box::new(psf),
+            storage: HashMap::new(),
+            key_storage: HashMap::new(),
+            hash: Box::new(MockHash::new(move |_| fixed.clone())),
+            _a_type: PhantomData,
+            _trapdoor_type: PhantomData,
+            _range_type: PhantomData,
+        };
+        let (pk, sk) = fdh.gen();
+
+        let sigma = fdh.sign(String::from("Hello World!"), &sk, &pk).unwrap();
+
+        assert!(fdh.vfy(String::from("Hello World!"), &sigma, &pk));
+        assert!(fdh.vfy(String::from("any other message"), &sigma, &pk));
+    }
+
+    /// Ensures that a [`MockHash`] is serialized, but can not be deserialized.
+    #[test]
+    fn serialization() {
+        let hash = MockHash::new(|_| MatZq::new(1, 1, 113));
+
+        let json = serde_json::to_string(&hash).unwrap();
+
+        assert_eq!("null", json);
+        assert!(serde_json::from_str::<MockHash>(&json).is_err());
+    }
+}
@@ -0,0 +1,286 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains the [`Shake256Hash`] that hashes into different
+//! domains based on the extendable output function SHAKE-256.
+
+use super::HashInto;
+use qfall_math::{
+    integer::{MatPolyOverZ, PolyOverZ, Z},
+    integer_mod_q::{MatZq, Modulus},
+    traits::{SetCoefficient, SetEntry},
+};
+use serde::{Deserialize, Serialize};
+use sha3::{
+    digest::{ExtendableOutput, Update, XofReader},
+    Shake256,
+};
+
+/// Object for hashing Strings into a [`MatZq`] or a [`MatPolyOverZ`] using SHAKE-256.
+/// The object fixes the modulus and the corresponding dimensions.
+/// Each entry resp. coefficient is sampled uniformly at random from `[0, q)`
+/// by rejection sampling from the output stream of SHAKE-256, i.e.
+/// `⌈log_2(q)⌉` bits are read and the value is rejected if it is not smaller than `q`.
+///
+/// Attributes:
+/// - `modulus`: Defines the range in which each entry resp. coefficient is hashed
+/// - `rows`: Defines the number of rows of the hash value
+/// - `cols`: Defines the number of columns of the hash value
+/// - `degree`: Defines the number of coefficients of each polynomial if hashed
+///     into a [`MatPolyOverZ`], i.e. each entry has degree smaller than `degree`.
+///     It is ignored if hashed into a [`MatZq`].
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::construction::hash::{HashInto, Shake256Hash};
+/// use qfall_math::integer::MatPolyOverZ;
+/// use qfall_math::integer_mod_q::{MatZq, Modulus};
+///
+/// let hasher = Shake256Hash {
+///     modulus: Modulus::from(7),
+///     rows: 17,
+///     cols: 3,
+///     degree: 8,
+/// };
+///
+/// let hash_val: MatZq = hasher.hash("Hello");
+/// let hash_val_ring: MatPolyOverZ = hasher.hash("Hello");
+/// ```
+#[derive(Serialize, Deserialize)]
+pub struct Shake256Hash {
+    pub modulus: Modulus,
+    pub rows: i64,
+    pub cols: i64,
+    pub degree: i64,
+}
+
+impl Shake256Hash {
+    /// Initializes the output stream of SHAKE-256 for the message `m`.
+    /// The modulus, the dimensions and the domain `domain` are prepended to `m`,
+    /// s.t. hash values of different domains and dimensions are independent.
+    fn reader(&self, domain: &str, m: &str) -> impl XofReader {
+        let mut hasher = Shake256::default();
+        hasher.update(
+            format!(
+                "{domain} {} {} {} {} {m}",
+                self.modulus, self.rows, self.cols, self.degree
+            )
+            .as_bytes(),
+        );
+        hasher.finalize_xof()
+    }
+}
+
+/// Samples a uniformly random value in `[0, q)` by rejection sampling from `reader`.
+///
+/// Parameters:
+/// - `reader`: the output stream of the XOF
+/// - `q`: the (exclusive) upper bound of the sampled value
+///
+/// Returns a uniformly random value in `[0, q)`.
+fn sample_below(reader: &mut impl XofReader, q: &Z) -> Z {
+    let bits = (q - Z::ONE).bits();
+    let num_bytes = bits.div_ceil(8) as usize;
+    // keeps only the lowest `bits mod 8` bits of the most significant byte (all if `0`)
+    let mask = (0xff_u16 >> (8 * num_bytes as u64 - bits)) as u8;
+
+    let mut buffer = vec![0_u8; num_bytes];
+    loop {
+        reader.read(&mut buffer);
+        buffer[0] &= mask;
+
+        let hex: String = buffer.iter().map(|byte| format!("{byte:02x}")).collect();
+        let value = Z::from_str_b(&hex, 16).unwrap();
+        if &value < q {
+            return value;
+        }
+    }
+}
+
+impl HashInto<MatZq> for Shake256Hash {
+    /// Hashes a given String literal into a [`MatZq`] using SHAKE-256.
+    /// The dimensions and the modulus is fixed by the hash object.
+    ///
+    /// Parameters:
+    /// - `m`: specifies the value that is hashed
+    ///
+    /// Returns a [`MatZq`] as a hash for the given string.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::hash::{HashInto, Shake256Hash};
+    /// use qfall_math::integer_mod_q::{MatZq, Modulus};
+    ///
+    /// let hasher = Shake256Hash {
+    ///     modulus: Modulus::from(7),
+    ///     rows: 17,
+    ///     cols: 3,
+    ///     degree: 1,
+    /// };
+    /// let hash_val: MatZq = hasher.hash("Hello");
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if the number of rows or columns is less or equal to `0`.
+    fn hash(&self, m: &str) -> MatZq {
+        let q = Z::from(&self.modulus);
+        let mut reader = self.reader("MatZq", m);
+
+        let mut matrix = MatZq::new(self.rows, self.cols, &self.modulus);
+        for i in 0..self.rows {
+            for j in 0..self.cols {
+                matrix
+                    .set_entry(i, j, sample_below(&mut reader, &q))
+                    .unwrap();
+            }
+        }
+        matrix
+    }
+}
+
+impl HashInto<MatPolyOverZ> for Shake256Hash {
+    /// Hashes a given String literal into a [`MatPolyOverZ`] using SHAKE-256.
+    /// The dimensions, the number of coefficients and the range of the
+    /// coefficients is fixed by the hash object.
+    ///
+    /// Parameters:
+    /// - `m`: specifies the value that is hashed
+    ///
+    /// Returns a [`MatPolyOverZ`] as a hash for the given string, whose
+    /// entries have `degree` coefficients in `[0, q)`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::hash::{HashInto, Shake256Hash};
+    /// use qfall_math::integer::MatPolyOverZ;
+    /// use qfall_math::integer_mod_q::Modulus;
+    ///
+    /// let hasher = Shake256Hash {
+    ///     modulus: Modulus::from(7),
+    ///     rows: 2,
+    ///     cols: 3,
+    ///     degree: 8,
+    /// };
+    /// let hash_val: MatPolyOverZ = hasher.hash("Hello");
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if the number of rows or columns is less or equal to `0`.
+    /// - if `degree` is negative.
+    fn hash(&self, m: &str) -> MatPolyOverZ {
+        let q = Z::from(&self.modulus);
+        let mut reader = self.reader("MatPolyOverZ", m);
+
+        let mut matrix = MatPolyOverZ::new(self.rows, self.cols);
+        for i in 0..self.rows {
+            for j in 0..self.cols {
+                let mut poly = PolyOverZ::default();
+                for k in 0..self.degree {
+                    poly.set_coeff(k, sample_below(&mut reader, &q)).unwrap();
+                }
+                matrix.set_entry(i, j, poly).unwrap();
+            }
+        }
+        matrix
+    }
+}
+
+#[cfg(test)]
+mod test_shake256_hash {
+    use super::{HashInto, Shake256Hash};
+    use qfall_math::{
+        integer::{MatPolyOverZ, PolyOverZ, Z},
+        integer_mod_q::{MatZq, Modulus},
+        traits::{GetCoefficient, GetEntry, GetNumColumns, GetNumRows},
+    };
+
+    /// Ensures that the same string is hashed to the same matrix
+    /// and different strings are hashed to different matrices.
+    #[test]
+    fn deterministic() {
+        let hasher = Shake256Hash {
+            modulus: Modulus::from(113),
+            rows: 4,
+            cols: 2,
+            degree: 8,
+        };
+
+        let hash_val_1: MatZq = hasher.hash("Hello World!");
+        let hash_val_2: MatZq = hasher.hash("Hello World!");
+        let hash_val_3: MatZq = hasher.hash("Hello World");
+        let hash_val_ring_1: MatPolyOverZ = hasher.hash("Hello World!");
+        let hash_val_ring_2: MatPolyOverZ = hasher.hash("Hello World!");
+        let hash_val_ring_3: MatPolyOverZ = hasher.hash("Hello World");
+
+        assert_eq!(hash_val_1, hash_val_2);
+        assert_ne!(hash_val_1, hash_val_3);
+        assert_eq!(hash_val_ring_1, hash_val_ring_2);
+        assert_ne!(hash_val_ring_1, hash_val_ring_3);
+    }
+
+    /// Ensures that the hash values have the correct dimensions and that all
+    /// coefficients of the ring variant are in `[0, q)`.
+    #[test]
+    fn correct_dimensions() {
+        let hasher = Shake256Hash {
+            modulus: Modulus::from(113),
+            rows: 3,
+            cols: 2,
+            degree: 8,
+        };
+
+        let hash_val: MatZq = hasher.hash("Hello World!");
+        let hash_val_ring: MatPolyOverZ = hasher.hash("Hello World!");
+
+        assert_eq!(3, hash_val.get_num_rows());
+        assert_eq!(2, hash_val.get_num_columns());
+        assert_eq!(3, hash_val_ring.get_num_rows());
+        assert_eq!(2, hash_val_ring.get_num_columns());
+        for i in 0..3 {
+            for j in 0..2 {
+                let entry: PolyOverZ = hash_val_ring.get_entry(i, j).unwrap();
+                assert!(entry.get_degree() < 8);
+                for k in 0..8 {
+                    let coeff: Z = entry.get_coeff(k).unwrap();
+                    assert!(coeff >= Z::ZERO);
+                    assert!(coeff < Z::from(113));
+                }
+            }
+        }
+    }
+
+    /// Ensures that the entries are uniformly distributed over `Z_q` by a
+    /// chi-squared test at a 5 % significance level, where
+    /// `18.307` is the critical value for `q - 1 = 10` degrees of freedom.
+    #[test]
+    fn chi_squared_uniformity() {
+        let q = 11;
+        let samples = 2200;
+        let hasher = Shake256Hash {
+            modulus: Modulus::from(q),
+            rows: 1,
+            cols: samples,
+            degree: 1,
+        };
+
+        let hash_val: MatZq = hasher.hash("Hello World!");
+        let mut counts = vec![0_i64; q as usize];
+        for j in 0..samples {
+            let entry: Z = hash_val.get_entry(0, j).unwrap();
+            counts[i64::try_from(&entry).unwrap() as usize] += 1;
+        }
+
+        let expected = (samples / q) as f64;
+        let chi_squared: f64 = counts
+            .iter()
+            .map(|count| (*count as f64 - expected).powi(2) / expected)
+            .sum();
+
+        assert!(chi_squared < 18.307, "chi-squared statistic {chi_squared}");
+    }
+}
@@ -0,0 +1,22 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains (leveled) homomorphic encryption schemes,
+//! which allow to evaluate circuits on encrypted data.
+//!
+//! The main references are listed in the following
+//! and will be further referenced in submodules by these numbers:
+//! - \[1\] Gentry, C., Sahai, A., Waters, B. (2013).
+//!     Homomorphic Encryption from Learning with Errors:
+//!     Conceptually-Simpler, Asymptotically-Faster, Attribute-Based.
+//!     In: Advances in Cryptology - CRYPTO 2013.
+//!     <https://doi.org/10.1007/978-3-642-40041-4_5>
+
+mod gsw;
+
+pub use gsw::{GswCtxt, GswLwe};
@@ -0,0 +1,327 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains an implementation of the leveled homomorphic
+//! GSW encryption scheme [\[1\]](<index.html#:~:text=[1]>).
+
+use crate::sample::g_trapdoor::gadget_classical::{find_solution_gadget_mat, gen_gadget_mat};
+use qfall_math::{
+    integer::{MatZ, Z},
+    integer_mod_q::{MatZq, Modulus, Zq},
+    traits::{Concatenate, Distance, GetEntry, GetNumColumns, Pow},
+};
+use serde::{Deserialize, Serialize};
+
+/// A GSW ciphertext, i.e. a matrix `C ∈ Z_q^{(n + 1) x (n + 1) * k}`.
+///
+/// Attributes:
+/// - `c`: the ciphertext matrix
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GswCtxt {
+    pub c: MatZq,
+}
+
+/// This struct manages and stores the public parameters of the leveled
+/// homomorphic GSW encryption scheme [\[1\]](<index.html#:~:text=[1]>).
+///
+/// Let `N = n + 1`, `m = N * k` and `G ∈ Z^{N x m}` be the gadget matrix with base `2`.
+/// The public key is `A = [A'; s^t * A' + e^t] ∈ Z_q^{N x m}` and the secret key
+/// is `t = [-s; 1]`, i.e. `t^t * A = e^t` is short.
+/// A bit `b` is encrypted as `C = A * R + b * G` for a uniform `R ∈ {0, 1}^{m x m}`.
+/// Homomorphic NOT is computed as `G - C` and homomorphic AND as `C_1 * G^{-1}(C_2)`,
+/// which is the matrix product of `C_1` and the flattened `C_2`.
+///
+/// The modulus `q` is chosen such that circuits of AND-depth `depth` decrypt correctly.
+///
+/// **WARNING:** The errors are chosen uniformly from `{-1, 0, 1}` and the
+/// parameters do not provide a meaningful security level.
+/// This implementation is meant for educational purposes.
+///
+/// Attributes:
+/// - `n`: specifies the security parameter, which is not equal to the bit-security level
+/// - `k`: specifies the gadget length `⌈log_2(q)⌉`
+/// - `q`: specifies the modulus
+/// - `depth`: specifies the supported AND-depth of evaluated circuits
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::construction::homomorphic_encryption::GswLwe;
+///
+/// let gsw = GswLwe::new_from_n(2, 1);
+/// let (pk, sk) = gsw.gen();
+///
+/// let c_0 = gsw.enc(&pk, true);
+/// let c_1 = gsw.enc(&pk, false);
+/// let c_nand = gsw.eval_not(&gsw.eval_and(&c_0, &c_1));
+///
+/// assert!(gsw.dec(&sk, &c_nand));
+/// ```
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GswLwe {
+    n: Z,
+    k: Z,
+    q: Modulus,
+    depth: usize,
+}
+
+impl GswLwe {
+    /// Initializes a [`GswLwe`] instance for security parameter `n` supporting
+    /// circuits of AND-depth `depth`. Therefore, the smallest `k` is chosen such that
+    /// `2^{k - 3} > (N * k + 1)^depth * N * k`, which bounds the error after evaluation,
+    /// and `q` is chosen as a prime in `[2^{k - 1}, 2^k)`.
+    ///
+    /// Parameters:
+    /// - `n`: the security parameter
+    /// - `depth`: the supported AND-depth of evaluated circuits
+    ///
+    /// Returns a [`GswLwe`] instance.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::homomorphic_encryption::GswLwe;
+    ///
+    /// let gsw = GswLwe::new_from_n(2, 2);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `n < 1`.
+    pub fn new_from_n(n: impl Into<Z>, depth: usize) -> Self {
+        let n: Z = n.into();
+        if n < Z::ONE {
+            panic!("Security parameter n has to be larger than 0");
+        }
+
+        let big_n = &n + Z::ONE;
+        let mut k = Z::from(4);
+        loop {
+            let m = &big_n * &k;
+            let error_bound = (&m + Z::ONE).pow(depth as u64).unwrap() * &m;
+            if Z::from(2).pow(&k - Z::from(3)).unwrap() > error_bound {
+                break;
+            }
+            k = k + Z::ONE;
+        }
+
+        let upper_bound = Z::from(2).pow(&k).unwrap();
+        let lower_bound = upper_bound.div_floor(2);
+        let q = Modulus::from(Z::sample_prime_uniform(&lower_bound, &upper_bound).unwrap());
+
+        Self { n, k, q, depth }
+    }
+
+    /// Returns the gadget matrix `G ∈ Z_q^{N x N * k}`.
+    fn gadget_mat(&self) -> MatZq {
+        let big_n = i64::try_from(&(&self.n + Z::ONE)).unwrap();
+        let k = i64::try_from(&self.k).unwrap();
+        MatZq::from((&gen_gadget_mat(big_n, k, &Z::from(2)), &self.q))
+    }
+
+    /// Generates a (pk, sk) pair.
+    ///
+    /// Returns the public key `A ∈ Z_q^{N x m}` and the secret key `t = [-s; 1] ∈ Z_q^N`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::homomorphic_encryption::GswLwe;
+    /// let gsw = GswLwe::new_from_n(2, 1);
+    ///
+    /// let (pk, sk) = gsw.gen();
+    /// ```
+    pub fn gen(&self) -> (MatZq, MatZq) {
+        let m = (&self.n + Z::ONE) * &self.k;
+
+        let mat_a_bar = MatZq::sample_uniform(&self.n, &m, &self.q);
+        let vec_s = MatZq::sample_uniform(&self.n, 1, &self.q);
+        let vec_e = MatZ::sample_uniform(1, &m, -1, 2).unwrap();
+
+        let vec_b = vec_s.transpose() * &mat_a_bar + MatZq::from((&vec_e, &self.q));
+        let pk = mat_a_bar.concat_vertical(&vec_b).unwrap();
+
+        let one = MatZq::from((&MatZ::identity(1, 1), &self.q));
+        let minus_s = MatZq::new(&self.n, 1, &self.q) - vec_s;
+        let sk = minus_s.concat_vertical(&one).unwrap();
+
+        (pk, sk)
+    }
+
+    /// Encrypts `bit` under `pk` as `C = A * R + bit * G`.
+    ///
+    /// Parameters:
+    /// - `pk`: specifies the public key
+    /// - `bit`: specifies the bit that should be encrypted
+    ///
+    /// Returns the [`GswCtxt`] encrypting `bit`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::homomorphic_encryption::GswLwe;
+    /// let gsw = GswLwe::new_from_n(2, 1);
+    /// let (pk, sk) = gsw.gen();
+    ///
+    /// let cipher = gsw.enc(&pk, true);
+    /// ```
+    pub fn enc(&self, pk: &MatZq, bit: bool) -> GswCtxt {
+        let m = pk.get_num_columns();
+        let mat_r = MatZ::sample_uniform(m, m, 0, 2).unwrap();
+
+        let mut c = pk * MatZq::from((&mat_r, &self.q));
+        if bit {
+            c = c + self.gadget_mat();
+        }
+
+        GswCtxt { c }
+    }
+
+    /// Homomorphically negates the bit encrypted in `cipher` by computing `G - C`.
+    ///
+    /// Parameters:
+    /// - `cipher`: specifies the ciphertext
+    ///
+    /// Returns a [`GswCtxt`] encrypting the negated bit.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::homomorphic_encryption::GswLwe;
+    /// let gsw = GswLwe::new_from_n(2, 1);
+    /// let (pk, sk) = gsw.gen();
+    /// let cipher = gsw.enc(&pk, true);
+    ///
+    /// let negated = gsw.eval_not(&cipher);
+    ///
+    /// assert!(!gsw.dec(&sk, &negated));
+    /// ```
+    pub fn eval_not(&self, cipher: &GswCtxt) -> GswCtxt {
+        GswCtxt {
+            c: self.gadget_mat() - &cipher.c,
+        }
+    }
+
+    /// Homomorphically computes the AND of the bits encrypted in `cipher_1`
+    /// and `cipher_2` by computing `C_1 * G^{-1}(C_2)`.
+    /// Each AND increases the depth of the evaluated circuit by one.
+    ///
+    /// Parameters:
+    /// - `cipher_1`: specifies the first ciphertext
+    /// - `cipher_2`: specifies the second ciphertext
+    ///
+    /// Returns a [`GswCtxt`] encrypting the AND of both bits.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::homomorphic_encryption::GswLwe;
+    /// let gsw = GswLwe::new_from_n(2, 1);
+    /// let (pk, sk) = gsw.gen();
+    /// let cipher_1 = gsw.enc(&pk, true);
+    /// let cipher_2 = gsw.enc(&pk, true);
+    ///
+    /// let and = gsw.eval_and(&cipher_1, &cipher_2);
+    ///
+    /// assert!(gsw.dec(&sk, &and));
+    /// ```
+    pub fn eval_and(&self, cipher_1: &GswCtxt, cipher_2: &GswCtxt) -> GswCtxt {
+        let flattened = find_solution_gadget_mat(&cipher_2.c, &self.k, &Z::from(2));
+
+        GswCtxt {
+            c: &cipher_1.c * MatZq::from((&flattened, &self.q)),
+        }
+    }
+
+    /// Decrypts `cipher` using the secret key `t` by checking whether the
+    /// entry of `t^t * C` in its last column but one is closer to `0` or `2^{k - 2}`.
+    ///
+    /// Parameters:
+    /// - `sk`: specifies the secret key
+    /// - `cipher`: specifies the ciphertext
+    ///
+    /// Returns the decrypted bit.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::homomorphic_encryption::GswLwe;
+    /// let gsw = GswLwe::new_from_n(2, 1);
+    /// let (pk, sk) = gsw.gen();
+    /// let cipher = gsw.enc(&pk, true);
+    ///
+    /// assert!(gsw.dec(&sk, &cipher));
+    /// ```
+    pub fn dec(&self, sk: &MatZq, cipher: &GswCtxt) -> bool {
+        let result = sk.transpose() * &cipher.c;
+        let value: Zq = result.get_entry(0, -2).unwrap();
+
+        let encoded_one = Z::from(2).pow(&self.k - Z::from(2)).unwrap();
+        value.distance(Z::ZERO) > value.distance(&encoded_one)
+    }
+
+    /// Returns the supported AND-depth of evaluated circuits.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::homomorphic_encryption::GswLwe;
+    /// let gsw = GswLwe::new_from_n(2, 3);
+    ///
+    /// assert_eq!(3, gsw.get_depth());
+    /// ```
+    pub fn get_depth(&self) -> usize {
+        self.depth
+    }
+}
+
+#[cfg(test)]
+mod test_gsw {
+    use super::{GswCtxt, GswLwe};
+
+    /// Ensures that fresh encryptions of both bits decrypt correctly.
+    #[test]
+    fn cycle() {
+        let gsw = GswLwe::new_from_n(4, 1);
+        let (pk, sk) = gsw.gen();
+
+        assert!(gsw.dec(&sk, &gsw.enc(&pk, true)));
+        assert!(!gsw.dec(&sk, &gsw.enc(&pk, false)));
+    }
+
+    /// Ensures that NOT and AND evaluate correctly on all inputs.
+    #[test]
+    fn gates() {
+        let gsw = GswLwe::new_from_n(4, 1);
+        let (pk, sk) = gsw.gen();
+
+        for bit_1 in [false, true] {
+            let cipher_1 = gsw.enc(&pk, bit_1);
+            assert_eq!(!bit_1, gsw.dec(&sk, &gsw.eval_not(&cipher_1)));
+
+            for bit_2 in [false, true] {
+                let cipher_2 = gsw.enc(&pk, bit_2);
+                let and = gsw.eval_and(&cipher_1, &cipher_2);
+                assert_eq!(bit_1 && bit_2, gsw.dec(&sk, &and));
+            }
+        }
+    }
+
+    /// Ensures that a balanced circuit of NAND gates with the supported
+    /// depth evaluates correctly.
+    #[test]
+    fn circuit_of_full_depth() {
+        let depth = 3;
+        let gsw = GswLwe::new_from_n(4, depth);
+        let (pk, sk) = gsw.gen();
+
+        let mut bits: Vec<bool> = (0..1 << depth).map(|_| rand::random()).collect();
+        let mut ciphers: Vec<GswCtxt> = bits.iter().map(|bit| gsw.enc(&pk, *bit)).collect();
+        for _ in 0..depth {
+            bits = bits.chunks(2).map(|pair| !(pair[0] && pair[1])).collect();
+            ciphers = ciphers
+                .chunks(2)
+                .map(|pair| gsw.eval_not(&gsw.eval_and(&pair[0], &pair[1])))
+                .collect();
+        }
+
+        assert_eq!(1, ciphers.len());
+        assert_eq!(bits[0], gsw.dec(&sk, &ciphers[0]));
+    }
+}
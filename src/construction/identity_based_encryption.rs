@@ -10,6 +10,24 @@
 //! instance of a identity based public key encryption scheme. Furthermore,
 //! it contains cryptographic schemes implementing the [`IBEScheme`] trait.
 //!
+//! The following table summarizes the trade-offs between [`DualRegevIBE`]
+//! and [`RingDualRegevIBE`] for an LWE dimension resp. ring degree `n`, modulus `q`,
+//! `k = ⌈log_2 q⌉` and `m̄` of the [`GadgetParameters`](crate::sample::g_trapdoor::gadget_parameters::GadgetParameters).
+//!
+//! | | [`DualRegevIBE`] | [`RingDualRegevIBE`] |
+//! |---|---|---|
+//! | hardness assumption | LWE | Ring-LWE |
+//! | master public key | `n * (m̄ + n * k)` entries of `Z_q` | `n * (k + 2)` coefficients of `Z_q` |
+//! | identity secret key | `m̄ + n * k` short integers | `n * (k + 2)` short coefficients |
+//! | ciphertext of a bit | `m̄ + n * k + 1` entries of `Z_q` | `n * (k + 3)` coefficients of `Z_q` |
+//! | extract | sampling over a basis of dimension `m̄ + n * k`, very slow | sampling over `k + 2` ring elements, faster |
+//!
+//! Hence, [`RingDualRegevIBE`] should be preferred unless the additional
+//! algebraic structure of Ring-LWE is not acceptable.
+//! The running times and the sizes of keys and ciphertexts of both variants
+//! at `λ = 80` and `λ = 100` bits of security are measured by the benchmarks
+//! in `benches/ibe_comparison.rs`.
+//!
 //! The main references are listed in the following
 //! and will be further referenced in submodules by these numbers:
 //! - \[1\] Gentry, Craig and Peikert, Chris and Vaikuntanathan, Vinod (2008).
@@ -22,9 +40,21 @@
 //!     <https://dl.acm.org/doi/pdf/10.1145/1568318.1568324>
 
 mod dual_regev_ibe;
+mod hibe;
+mod ring_dual_regev_ibe;
+#[cfg(feature = "experimental_threshold_ibe")]
+mod threshold_ibe;
 
-pub use dual_regev_ibe::DualRegevIBE;
+use crate::error::CryptoError;
+pub use dual_regev_ibe::{
+    load_params_toml, save_params_toml, DualRegevIBE, DualRegevIBEBuilder, EncState,
+    NamespacedDualRegevIBE,
+};
+pub use hibe::{ChildSecretKey, HierarchicalIBE};
 use qfall_math::integer::Z;
+pub use ring_dual_regev_ibe::RingDualRegevIBE;
+#[cfg(feature = "experimental_threshold_ibe")]
+pub use threshold_ibe::{KeyShare, PartialKey, ThresholdIBE};
 
 /// This trait should be implemented by every identity-based encryption scheme.
 /// It offers a simple interface to use and implements the main functions supported by
@@ -48,13 +78,20 @@ pub trait IBEScheme {
     /// - `master_sk`: specifies the master secret key used for extracting the secret of `identity`
     /// - `identity`: specifies the identity for which the secret key should be extracted
     ///
-    /// Returns a secret key for the specified `identity` as a [`Self::SecretKey`].
+    /// Returns a secret key for the specified `identity` as a [`Self::SecretKey`]
+    /// or a [`CryptoError`] if the secret key could not be extracted.
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] of type [`IncompatibleParameters`](CryptoError::IncompatibleParameters)
+    ///     if `master_pk` does not belong to the parameters of the scheme.
+    /// - Returns a [`CryptoError`] of type [`KeyExtractionFailed`](CryptoError::KeyExtractionFailed)
+    ///     if the extracted secret key is invalid, e.g. as `master_sk` does not belong to `master_pk`.
     fn extract(
-        &mut self,
+        &self,
         master_pk: &Self::MasterPublicKey,
         master_sk: &Self::MasterSecretKey,
         identity: &Self::Identity,
-    ) -> Self::SecretKey;
+    ) -> Result<Self::SecretKey, CryptoError>;
 
     /// Encrypts the provided `message` using the master public key `mpk` and `identity` of the recipient.
     ///
@@ -77,6 +114,294 @@ pub trait IBEScheme {
     /// - `sk`: specifies the extracted secret key used for decryption
     /// - `cipher`: specifies the ciphertext to be decrypted
     ///
-    /// Returns the decryption of `cipher` as a [`Z`] instance.
-    fn dec(&self, sk: &Self::SecretKey, cipher: &Self::Cipher) -> Z;
+    /// Returns the decryption of `cipher` as a [`Z`] instance
+    /// or a [`CryptoError`] if `cipher` could not be decrypted.
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] of type [`IncompatibleParameters`](CryptoError::IncompatibleParameters)
+    ///     if the dimensions of `sk` and `cipher` do not fit together.
+    fn dec(&self, sk: &Self::SecretKey, cipher: &Self::Cipher) -> Result<Z, CryptoError>;
+
+    /// Extracts a secret key corresponding to the specified `identity`
+    /// and caches it if the scheme uses a storage.
+    /// The default implementation calls [`IBEScheme::extract`], hence schemes
+    /// storing extracted keys in `extract`, e.g. [`DualRegevIBE`], cache them.
+    /// Schemes with a storage requiring mutable access should overwrite this function.
+    ///
+    /// Parameters:
+    /// - `master_pk`: specifies the master public key
+    /// - `master_sk`: specifies the master secret key used for extracting the secret of `identity`
+    /// - `identity`: specifies the identity for which the secret key should be extracted
+    ///
+    /// Returns a secret key for the specified `identity` as a [`Self::SecretKey`]
+    /// or a [`CryptoError`] if the secret key could not be extracted.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::identity_based_encryption::{DualRegevIBE, IBEScheme};
+    /// let mut ibe = DualRegevIBE::default();
+    /// let (pk, sk) = ibe.setup();
+    /// let id = String::from("identity");
+    ///
+    /// let id_sk = ibe.extract_cached(&pk, &sk, &id).unwrap();
+    ///
+    /// assert_eq!(id_sk, ibe.extract_cached(&pk, &sk, &id).unwrap());
+    /// ```
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] if [`IBEScheme::extract`] fails.
+    fn extract_cached(
+        &mut self,
+        master_pk: &Self::MasterPublicKey,
+        master_sk: &Self::MasterSecretKey,
+        identity: &Self::Identity,
+    ) -> Result<Self::SecretKey, CryptoError> {
+        self.extract(master_pk, master_sk, identity)
+    }
+
+    /// Extracts the secret key of `identity` and encrypts `message` for `identity`.
+    ///
+    /// Parameters:
+    /// - `master_pk`: specifies the master public key
+    /// - `master_sk`: specifies the master secret key used for extracting the secret of `identity`
+    /// - `identity`: specifies the identity for which the secret key is extracted
+    ///     and for which `message` is encrypted
+    /// - `message`: specifies the message to be encrypted
+    ///
+    /// Returns the secret key of `identity` and the encryption of `message`
+    /// or a [`CryptoError`] if the secret key could not be extracted.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::identity_based_encryption::{DualRegevIBE, IBEScheme};
+    /// use qfall_math::integer::Z;
+    /// let ibe = DualRegevIBE::default();
+    /// let (pk, sk) = ibe.setup();
+    /// let id = String::from("identity");
+    ///
+    /// let (id_sk, cipher) = ibe.enc_and_extract(&pk, &sk, &id, 1).unwrap();
+    ///
+    /// assert_eq!(Z::ONE, ibe.dec(&id_sk, &cipher).unwrap());
+    /// ```
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] if [`IBEScheme::extract`] fails.
+    fn enc_and_extract(
+        &self,
+        master_pk: &Self::MasterPublicKey,
+        master_sk: &Self::MasterSecretKey,
+        identity: &Self::Identity,
+        message: impl Into<Z>,
+    ) -> Result<(Self::SecretKey, Self::Cipher), CryptoError> {
+        let secret_key = self.extract(master_pk, master_sk, identity)?;
+        let cipher = self.enc(master_pk, identity, message);
+        Ok((secret_key, cipher))
+    }
+
+    /// Encrypts every message in `messages` separately for `identity`.
+    ///
+    /// Parameters:
+    /// - `master_pk`: specifies the master public key used for this IBE
+    /// - `identity`: specifies the recipient that should be able to decrypt the encrypted messages
+    /// - `messages`: specifies the messages to be encrypted
+    ///
+    /// Returns the encryptions of `messages` in the same order.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::identity_based_encryption::{DualRegevIBE, IBEScheme};
+    /// use qfall_math::integer::Z;
+    /// let ibe = DualRegevIBE::default();
+    /// let (pk, sk) = ibe.setup();
+    /// let id = String::from("identity");
+    ///
+    /// let ciphers = ibe.batch_enc(&pk, &id, &[Z::ONE, Z::ZERO]);
+    ///
+    /// let id_sk = ibe.extract(&pk, &sk, &id).unwrap();
+    /// assert_eq!(Z::ZERO, ibe.dec(&id_sk, &ciphers[1]).unwrap());
+    /// ```
+    fn batch_enc(
+        &self,
+        master_pk: &Self::MasterPublicKey,
+        identity: &Self::Identity,
+        messages: &[Z],
+    ) -> Vec<Self::Cipher> {
+        messages
+            .iter()
+            .map(|message| self.enc(master_pk, identity, message))
+            .collect()
+    }
+
+    /// Checks whether the parameters of the scheme guarantee security.
+    /// The default implementation performs no checks and should be overwritten
+    /// by schemes, which can validate their parameters.
+    ///
+    /// Returns an empty result or an error describing the violated requirement.
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] of type [`InvalidParameters`](CryptoError::InvalidParameters)
+    ///     if the parameters do not guarantee security.
+    fn check_security(&self) -> Result<(), CryptoError> {
+        Ok(())
+    }
+
+    /// Checks whether the parameters of the scheme guarantee correctness.
+    /// The default implementation performs no checks and should be overwritten
+    /// by schemes, which can validate their parameters.
+    ///
+    /// Returns an empty result or an error describing the violated requirement.
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] of type [`InvalidParameters`](CryptoError::InvalidParameters)
+    ///     if the parameters do not guarantee correctness.
+    fn check_correctness(&self) -> Result<(), CryptoError> {
+        Ok(())
+    }
+
+    /// Validates the scheme as a whole, which is e.g. useful at startup.
+    /// First, [`IBEScheme::check_security`] and [`IBEScheme::check_correctness`] are called.
+    /// Afterwards, a fresh master key pair is generated, the key of a test identity
+    /// is extracted using [`IBEScheme::extract_cached`] and the messages `0` and `1`
+    /// are encrypted and decrypted.
+    ///
+    /// Returns an empty result if all checks succeeded and an error otherwise.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::identity_based_encryption::{DualRegevIBE, IBEScheme};
+    /// let mut ibe = DualRegevIBE::default();
+    ///
+    /// assert!(ibe.check_cycle().is_ok());
+    /// ```
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] of type [`InvalidParameters`](CryptoError::InvalidParameters)
+    ///     if [`IBEScheme::check_security`] or [`IBEScheme::check_correctness`] failed.
+    /// - Returns a [`CryptoError`] if the key of the test identity could not be extracted
+    ///     or a ciphertext could not be decrypted.
+    /// - Returns a [`CryptoError`] of type [`SelfTestFailure`](CryptoError::SelfTestFailure)
+    ///     if a message was not decrypted correctly.
+    fn check_cycle(&mut self) -> Result<(), CryptoError>
+    where
+        Self::Identity: From<&'static str>,
+    {
+        self.check_security()?;
+        self.check_correctness()?;
+
+        let (master_pk, master_sk) = self.setup();
+        let identity = Self::Identity::from("check_cycle");
+        let secret_key = self.extract_cached(&master_pk, &master_sk, &identity)?;
+
+        for message in [Z::ZERO, Z::ONE] {
+            let cipher = self.enc(&master_pk, &identity, &message);
+            if self.dec(&secret_key, &cipher)? != message {
+                return Err(CryptoError::SelfTestFailure(format!(
+                    "The encryption of {message} was not decrypted correctly."
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_ibe_scheme {
+    use super::{DualRegevIBE, IBEScheme, NamespacedDualRegevIBE};
+    use crate::error::CryptoError;
+    use qfall_math::integer::Z;
+
+    /// Ensures that `extract_cached` returns the same key as `extract`,
+    /// as [`DualRegevIBE`] stores extracted keys.
+    #[test]
+    fn extract_cached() {
+        let mut ibe = DualRegevIBE::default();
+        let (pk, sk) = ibe.setup();
+        let id = String::from("identity");
+
+        let id_sk = ibe.extract_cached(&pk, &sk, &id).unwrap();
+
+        assert_eq!(id_sk, ibe.extract(&pk, &sk, &id).unwrap());
+        assert_eq!(id_sk, ibe.extract_cached(&pk, &sk, &id).unwrap());
+    }
+
+    /// Ensures that the key returned by `enc_and_extract` decrypts the returned ciphertext.
+    #[test]
+    fn enc_and_extract() {
+        let ibe = DualRegevIBE::default().with_namespace(String::from("test"));
+        let (pk, sk) = ibe.setup();
+        let id = String::from("identity");
+
+        for msg in [Z::ZERO, Z::ONE] {
+            let (id_sk, cipher) = ibe.enc_and_extract(&pk, &sk, &id, &msg).unwrap();
+
+            assert_eq!(msg, ibe.dec(&id_sk, &cipher).unwrap());
+            assert_eq!(id_sk, ibe.extract(&pk, &sk, &id).unwrap());
+        }
+    }
+
+    /// Ensures that `batch_enc` encrypts all messages in order.
+    #[test]
+    fn batch_enc() {
+        let ibe = DualRegevIBE::default();
+        let (pk, sk) = ibe.setup();
+        let id = String::from("identity");
+        let messages: Vec<Z> = (0..10).map(|i| Z::from(i % 2)).collect();
+
+        let ciphers = ibe.batch_enc(&pk, &id, &messages);
+
+        let id_sk = ibe.extract(&pk, &sk, &id).unwrap();
+        assert_eq!(messages.len(), ciphers.len());
+        for (msg, cipher) in messages.iter().zip(&ciphers) {
+            assert_eq!(msg, &ibe.dec(&id_sk, cipher).unwrap());
+        }
+        assert!(ibe.batch_enc(&pk, &id, &[]).is_empty());
+    }
+
+    /// Ensures that the default methods can be used for [`NamespacedDualRegevIBE`].
+    #[test]
+    fn namespaced() {
+        let mut ibe: NamespacedDualRegevIBE =
+            DualRegevIBE::default().with_namespace(String::from("a"));
+        let (pk, sk) = ibe.setup();
+        let id = String::from("identity");
+
+        let id_sk = ibe.extract_cached(&pk, &sk, &id).unwrap();
+        let ciphers = ibe.batch_enc(&pk, &id, &[Z::ONE]);
+
+        assert_eq!(Z::ONE, ibe.dec(&id_sk, &ciphers[0]).unwrap());
+    }
+
+    /// Ensures that `check_cycle` succeeds for the default parameters,
+    /// also if a namespace is used.
+    #[test]
+    fn check_cycle() {
+        let mut ibe = DualRegevIBE::default();
+        let mut namespaced = DualRegevIBE::default().with_namespace(String::from("a"));
+
+        assert!(ibe.check_cycle().is_ok());
+        assert!(namespaced.check_cycle().is_ok());
+    }
+
+    /// Ensures that `check_cycle` fails if the parameters are insecure or incorrect.
+    #[test]
+    fn check_cycle_bad_parameters() {
+        let mut insecure = DualRegevIBE::new(4, 16, 2, 0.0001);
+        let default = DualRegevIBE::default();
+        let mut incorrect = DualRegevIBE::new(
+            &default.dual_regev.n,
+            &default.dual_regev.q,
+            &default.psf.s,
+            1,
+        );
+
+        assert!(matches!(
+            insecure.check_cycle(),
+            Err(CryptoError::InvalidParameters { .. })
+        ));
+        assert!(matches!(
+            incorrect.check_cycle(),
+            Err(CryptoError::InvalidParameters { .. })
+        ));
+    }
 }
@@ -12,22 +12,52 @@
 
 use super::IBEScheme;
 use crate::{
+    analysis::weak_params::detect_weak_params,
     construction::{
         hash::sha256::hash_to_mat_zq_sha256,
         pk_encryption::{DualRegev, PKEncryptionScheme},
     },
+    error::CryptoError,
+    params::NistLevel,
     primitive::psf::{PSF, PSFGPV},
-    sample::g_trapdoor::gadget_parameters::GadgetParameters,
+    sample::g_trapdoor::{
+        basis_quality::{orthogonality_defect, trapdoor_spectral_norm},
+        gadget_parameters::GadgetParameters,
+        short_basis_classical::extend_short_basis,
+    },
+    utils::{
+        conversion::q_to_f64,
+        math_util::{rounding_failure_probability, ZqArith},
+    },
 };
+use dashmap::DashMap;
 use qfall_math::{
-    error::MathError,
     integer::{MatZ, Z},
-    integer_mod_q::{MatZq, Modulus},
+    integer_mod_q::{MatZq, Modulus, Zq},
     rational::{MatQ, Q},
-    traits::{Concatenate, GetNumRows, Pow},
+    traits::{Concatenate, GetEntry, GetNumColumns, GetNumRows, Pow, SetEntry},
 };
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use sha2::{Digest, Sha256};
+use std::time::{Duration, SystemTime};
+
+mod builder;
+mod enc_state;
+mod json;
+mod namespaced;
+mod params_file;
+mod storage;
+
+pub use builder::DualRegevIBEBuilder;
+pub use enc_state::EncState;
+pub use namespaced::NamespacedDualRegevIBE;
+pub use params_file::{load_params_toml, save_params_toml};
+use storage::{storage_key, StorageEntry, StorageKey};
+
+/// The number of bits of `SHA-256(ad)` that are encrypted by [`DualRegevIBE::enc_with_ad`]
+/// and checked by [`DualRegevIBE::dec_with_ad`].
+const AD_CHECK_BITS: usize = 32;
 
 /// This struct manages and stores the public parameters of a [`IBEScheme`]
 /// public key encryption instance based on [\[1\]](<index.html#:~:text=[1]>).
@@ -36,27 +66,30 @@ use std::collections::HashMap;
 /// - `r`: specifies the Gaussian parameter used by the [`PSF`]
 /// - `dual_regev`: a [`DualRegev`] instance with fitting parameters `n`, `m`, `q`, `alpha`
 /// - `psf`: specifies the PSF used for extracting secret keys
-/// - `storage`: is a [`HashMap`] which stores all previously computed secret keys
-///     corresponding to their identities
+/// - `storage`: is a [`DashMap`] which stores all previously computed secret keys
+///     corresponding to their identities. As [`DashMap`] allows concurrent
+///     access, keys can be extracted from several threads at once.
+///     The secret keys are stored under the key `SHA-256(CBOR(master_pk)) || SHA-256(identity)`
+///     together with the time they were inserted at, see [`DualRegevIBE::evict_expired`].
 ///
 /// # Examples
 /// ```
 /// use qfall_crypto::construction::identity_based_encryption::{DualRegevIBE, IBEScheme};
 /// use qfall_math::integer::Z;
 /// // setup public parameters and key pair
-/// let mut ibe = DualRegevIBE::default();
+/// let ibe = DualRegevIBE::default();
 /// let (pk, sk) = ibe.setup();
 ///
 /// // extract a identity based secret key
 /// let identity = String::from("identity");
-/// let id_sk = ibe.extract(&pk, &sk, &identity);
+/// let id_sk = ibe.extract(&pk, &sk, &identity).unwrap();
 ///
 /// // encrypt a bit
 /// let msg = Z::ZERO; // must be a bit, i.e. msg = 0 or 1
 /// let cipher = ibe.enc(&pk, &identity, &msg);
 ///
 /// // decrypt
-/// let m = ibe.dec(&id_sk, &cipher);
+/// let m = ibe.dec(&id_sk, &cipher).unwrap();
 ///
 /// assert_eq!(msg, m)
 /// ```
@@ -64,7 +97,8 @@ use std::collections::HashMap;
 pub struct DualRegevIBE {
     pub dual_regev: DualRegev,
     pub psf: PSFGPV,
-    storage: HashMap<String, MatZ>,
+    #[serde(with = "storage")]
+    storage: DashMap<StorageKey, StorageEntry>,
 }
 
 impl DualRegevIBE {
@@ -96,11 +130,16 @@ impl DualRegevIBE {
         let n_log_q = &n * &log_q;
         let m = &gadget.m_bar + n_log_q;
 
-        let psf = PSFGPV { gp: gadget, s: r };
+        let psf = PSFGPV {
+            gp: gadget,
+            s: r,
+            cdt: None,
+            cdt_cache: Default::default(),
+        };
         Self {
             psf,
             dual_regev: DualRegev::new(n, m, q, alpha),
-            storage: HashMap::new(),
+            storage: DashMap::new(),
         }
     }
 
@@ -108,18 +147,31 @@ impl DualRegevIBE {
     ///
     /// **WARNING:** Due to the [`PSF`] this schemes extract algorithm is slow for n > 5.
     ///
-    /// Returns an [`DualRegevIBE`] instance.
+    /// Weaknesses of the generated parameters found by [`detect_weak_params`]
+    /// are emitted as `tracing` warnings if the feature `logging` is enabled.
+    ///
+    /// Parameters:
+    /// - `n`: specifies the security parameter
+    ///
+    /// Returns an [`DualRegevIBE`] instance or a [`CryptoError`] if `n < 2`.
     ///
     /// # Examples
     /// ```
     /// use qfall_crypto::construction::identity_based_encryption::DualRegevIBE;
     ///
-    /// let dual_regev = DualRegevIBE::new_from_n(4);
+    /// let dual_regev = DualRegevIBE::new_from_n(4).unwrap();
+    /// assert!(DualRegevIBE::new_from_n(1).is_err());
     /// ```
-    pub fn new_from_n(n: impl Into<Z>) -> Self {
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] of type [`InvalidParameters`](CryptoError::InvalidParameters)
+    ///     if `n < 2`.
+    pub fn new_from_n(n: impl Into<Z>) -> Result<Self, CryptoError> {
         let n: Z = n.into();
         if n < Z::from(2) {
-            panic!("Security parameter n has to be larger than 1");
+            return Err(CryptoError::InvalidParameters {
+                message: String::from("Security parameter n has to be larger than 1"),
+            });
         }
 
         let n_i64 = i64::try_from(&n).unwrap();
@@ -150,12 +202,50 @@ impl DualRegevIBE {
         let r: Q = m.sqrt();
         let alpha = 1 / (&r * 2 * (&m + Z::ONE).sqrt() * (n).log(2).unwrap());
 
-        let psf = PSFGPV { gp: gadget, s: r };
-        Self {
+        for warning in detect_weak_params(
+            n_i64 as usize,
+            i64::try_from(&m).unwrap() as usize,
+            &Z::from(&q),
+            &alpha,
+        ) {
+            #[cfg(feature = "logging")]
+            tracing::warn!(%warning, "weak parameters");
+            #[cfg(not(feature = "logging"))]
+            let _ = warning;
+        }
+
+        let psf = PSFGPV {
+            gp: gadget,
+            s: r,
+            cdt: None,
+            cdt_cache: Default::default(),
+        };
+        Ok(Self {
             psf,
             dual_regev: DualRegev::new(n, m, q, alpha),
-            storage: HashMap::new(),
-        }
+            storage: DashMap::new(),
+        })
+    }
+
+    /// Instantiates a [`DualRegevIBE`] scheme with the parameter set of the NIST security `level`.
+    ///
+    /// **WARNING:** The public key of these parameter sets has more than `40000` columns,
+    /// hence [`DualRegevIBE::setup`](IBEScheme::setup) is very slow.
+    ///
+    /// Parameters:
+    /// - `level`: specifies the NIST security level
+    ///
+    /// Returns a [`DualRegevIBE`] instance with the parameters of `level`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::{construction::identity_based_encryption::DualRegevIBE, params::NistLevel};
+    ///
+    /// let ibe = DualRegevIBE::from_nist_level(NistLevel::Level1);
+    /// ```
+    pub fn from_nist_level(level: NistLevel) -> Self {
+        let params = level.params();
+        Self::new(params.n as u64, params.q, params.sigma, params.alpha)
     }
 
     /// Checks the public parameters for security according to Theorem 1.1
@@ -169,7 +259,7 @@ impl DualRegevIBE {
     /// - m > (n + 1) * log(q)
     ///
     /// Returns an empty result if the public parameters guarantees security w.r.t. `n`
-    /// or a [`MathError`] if the instance would not be secure.
+    /// or a [`CryptoError`] if the instance would not be secure.
     ///
     /// # Examples
     /// ```
@@ -180,33 +270,39 @@ impl DualRegevIBE {
     /// ```
     ///
     /// # Errors and Failures
-    /// - Returns a [`MathError`] of type [`InvalidIntegerInput`](MathError::InvalidIntegerInput)
+    /// - Returns a [`CryptoError`] of type [`InvalidParameters`](CryptoError::InvalidParameters)
     ///     if at least one parameter was not chosen appropriately for a
     ///     secure Dual Regev public key encryption instance.
-    pub fn check_security(&self) -> Result<(), MathError> {
+    pub fn check_security(&self) -> Result<(), CryptoError> {
         let q = Q::from(&self.dual_regev.q);
 
         // Security requirements
         // q >= 5 * r * (m + 1)
         if q < (5 * &self.psf.s) * (&self.dual_regev.m + Q::ONE) {
-            return Err(MathError::InvalidIntegerInput(String::from(
-                "Security is not guaranteed as q < 5 * r * (m + 1), but q >= 5 * r * (m + 1) is required.",
-            )));
+            return Err(CryptoError::InvalidParameters {
+                message: String::from(
+                    "Security is not guaranteed as q < 5 * r * (m + 1), but q >= 5 * r * (m + 1) is required.",
+                ),
+            });
         }
 
         // r >= sqrt(m)
         if self.psf.s < self.dual_regev.m.sqrt() {
-            return Err(MathError::InvalidIntegerInput(String::from(
-                "Security is not guaranteed as r < sqrt(m), but r >= sqrt(m) is required.",
-            )));
+            return Err(CryptoError::InvalidParameters {
+                message: String::from(
+                    "Security is not guaranteed as r < sqrt(m), but r >= sqrt(m) is required.",
+                ),
+            });
         }
 
         // m >= (n + 1) * log(q)
         if Q::from(&self.dual_regev.m) <= (&self.dual_regev.n + 1) * &q.log(2).unwrap() {
-            return Err(MathError::InvalidIntegerInput(String::from(
-                "Security is not guaranteed as m <= (n + 1) * log(q), \
-                but m > (n + 1) * log(q) is required.",
-            )));
+            return Err(CryptoError::InvalidParameters {
+                message: String::from(
+                    "Security is not guaranteed as m <= (n + 1) * log(q), \
+                    but m > (n + 1) * log(q) is required.",
+                ),
+            });
         }
 
         Ok(())
@@ -216,12 +312,13 @@ impl DualRegevIBE {
     /// correctness according to Lemma 5.1 of [\[2\]](<index.html#:~:text=[2]>).
     ///
     /// The required properties are:
-    /// - α <= 1/(2 * r * sqrt(m) * log(n))
+    /// - α <= 1/(2 * r * sqrt(m + 1) * log(n))
+    /// - the noise margin computed by [`DualRegevIBE::noise_margin`] is positive
     ///
     /// **WARNING:** Some requirements are missing to ensure overwhelming correctness of the scheme.
     ///
     /// Returns an empty result if the public parameters guarantee correctness
-    /// with overwhelming probability or a [`MathError`] if the instance would
+    /// with overwhelming probability or a [`CryptoError`] if the instance would
     /// not be correct.
     ///
     /// # Examples
@@ -233,233 +330,1370 @@ impl DualRegevIBE {
     /// ```
     ///
     /// # Errors and Failures
-    /// - Returns a [`MathError`] of type [`InvalidIntegerInput`](MathError::InvalidIntegerInput)
+    /// - Returns a [`CryptoError`] of type [`InvalidParameters`](CryptoError::InvalidParameters)
     ///     if at least one parameter was not chosen appropriately for a
     ///     correct Dual Regev IBE public key encryption instance.
-    pub fn check_correctness(&self) -> Result<(), MathError> {
+    pub fn check_correctness(&self) -> Result<(), CryptoError> {
         if self.dual_regev.n <= Z::ONE {
-            return Err(MathError::InvalidIntegerInput(String::from(
-                "n must be chosen bigger than 1.",
-            )));
+            return Err(CryptoError::InvalidParameters {
+                message: String::from("n must be chosen bigger than 1."),
+            });
+        }
+
+        // α <= 1/(2 * r * sqrt(m + 1) * log(n))
+        if self.dual_regev.alpha > self.max_alpha() {
+            return Err(CryptoError::InvalidParameters {
+                message: String::from(
+                    "Correctness is not guaranteed as α > 1/(2 * r * sqrt(m + 1) * log(n)), \
+                    but α <= 1/(2 * r * sqrt(m + 1) * log(n)) is required.",
+                ),
+            });
+        }
+
+        // q/4 - (α * q * sqrt(m) + r * sqrt(m_bar)) > 0
+        let noise_margin = self.noise_margin();
+        if noise_margin <= Q::ZERO {
+            return Err(CryptoError::InvalidParameters {
+                message: format!(
+                    "Correctness is not guaranteed as the expected decryption noise exceeds q/4 by {}, \
+                    but a positive noise margin is required.",
+                    Q::ZERO - noise_margin
+                ),
+            });
         }
 
-        // α <= 1/(2 * r * sqrt(m) * log(n))
-        if self.dual_regev.alpha
-            > 1 / (2 * &self.psf.s * (&self.dual_regev.m + Z::ONE).sqrt())
-                * self.dual_regev.n.log(2).unwrap()
+        Ok(())
+    }
+
+    /// Validates the parameters of a loaded instance, i.e. checks that `n`, `q`,
+    /// and `m` of the [`PSFGPV`] and the [`DualRegev`] instance match,
+    /// that `n > 1` and `m`, `r`, and `alpha` are positive, and that the
+    /// parameters pass [`DualRegevIBE::check_security`] and [`DualRegevIBE::check_correctness`].
+    ///
+    /// Returns an empty result if the parameters are valid or a [`CryptoError`] otherwise.
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] of type [`InvalidParameters`](CryptoError::InvalidParameters)
+    ///     if the parameters do not match, are too small, or do not pass
+    ///     the security or correctness checks.
+    fn validate_params(&self) -> Result<(), CryptoError> {
+        let gp = &self.psf.gp;
+        if gp.n != self.dual_regev.n
+            || gp.q != self.dual_regev.q
+            || &gp.m_bar + &gp.n * &gp.k != self.dual_regev.m
+        {
+            return Err(CryptoError::InvalidParameters {
+                message: format!(
+                    "The parameters n, q, and m = {} of the Dual Regev instance do not match \
+                    n, q, and m = {} of the PSF.",
+                    self.dual_regev.m,
+                    &gp.m_bar + &gp.n * &gp.k
+                ),
+            });
+        }
+        if self.dual_regev.n <= Z::ONE
+            || self.dual_regev.m <= Z::ZERO
+            || self.psf.s <= Q::ZERO
+            || self.dual_regev.alpha <= Q::ZERO
         {
-            return Err(MathError::InvalidIntegerInput(String::from(
-                "Correctness is not guaranteed as α > 1/(r * sqrt(m) * log(n)), but α <= 1/(2 * r * sqrt(m) * log(n)) is required.",
-            )));
+            return Err(CryptoError::InvalidParameters {
+                message: String::from(
+                    "n has to be larger than 1 and m, r, and alpha have to be positive.",
+                ),
+            });
         }
 
+        self.check_security()?;
+        self.check_correctness()?;
+
         Ok(())
     }
-}
 
-impl Default for DualRegevIBE {
-    /// Initializes a [`DualRegevIBE`] struct with parameters generated by `DualRegevIBE::new_from_n(4)`.
-    /// This parameter choice is not secure as the dimension of the lattice is too small,
-    /// but it provides an efficient working example.
+    /// Computes the largest `α` guaranteeing correctness according to
+    /// Section 7.1 of [GPV08 - eprint](https://eprint.iacr.org/2007/432.pdf),
+    /// i.e. `1/(2 * r * sqrt(m + 1) * log(n))`, where `sqrt(m + 1)` accounts for
+    /// the last entry of the identity-based public key `[A | H(id)]` and
+    /// `log(n)` instantiates `ω(sqrt(log n))`.
+    fn max_alpha(&self) -> Q {
+        1 / (2
+            * &self.psf.s
+            * (&self.dual_regev.m + Z::ONE).sqrt()
+            * self.dual_regev.n.log(2).unwrap())
+    }
+
+    /// Computes the expected size of the noise in `id_sk * cipher`,
+    /// which combines the noise of the LWE sample and the noise of the secret
+    /// key sampled by the [`PSF`], i.e. `α * q * sqrt(m) + r * sqrt(m_bar)`.
     ///
-    /// Returns an [`DualRegevIBE`] instance.
+    /// Returns the expected decryption noise as a [`Q`].
     ///
     /// # Examples
     /// ```
     /// use qfall_crypto::construction::identity_based_encryption::DualRegevIBE;
+    /// let ibe = DualRegevIBE::default();
+    ///
+    /// let noise = ibe.expected_decryption_noise();
+    /// ```
+    pub fn expected_decryption_noise(&self) -> Q {
+        let lwe_noise =
+            &self.dual_regev.alpha * Q::from(&self.dual_regev.q) * self.dual_regev.m.sqrt();
+        let psf_noise = &self.psf.s * self.psf.gp.m_bar.sqrt();
+
+        lwe_noise + psf_noise
+    }
+
+    /// Computes the difference between q/4 and the
+    /// [expected decryption noise](DualRegevIBE::expected_decryption_noise).
+    /// A positive margin indicates that ciphertexts are decrypted correctly.
+    ///
+    /// Returns `q/4 - (α * q * sqrt(m) + r * sqrt(m_bar))` as a [`Q`].
     ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::identity_based_encryption::DualRegevIBE;
+    /// use qfall_math::rational::Q;
     /// let ibe = DualRegevIBE::default();
+    ///
+    /// assert!(ibe.noise_margin() > Q::ZERO);
     /// ```
-    fn default() -> Self {
-        DualRegevIBE::new_from_n(4)
+    pub fn noise_margin(&self) -> Q {
+        Q::from(&self.dual_regev.q) / Q::from(4) - self.expected_decryption_noise()
     }
-}
 
-impl IBEScheme for DualRegevIBE {
-    type Cipher = MatZq;
-    type MasterPublicKey = MatZq;
-    type MasterSecretKey = (MatZ, MatQ);
-    type SecretKey = MatZ;
-    type Identity = String;
+    /// Computes the probability that the decryption noise reduced modulo `q`
+    /// exceeds q/4, i.e. that a ciphertext is decrypted incorrectly.
+    ///
+    /// The noise `e^t * [-id_sk | 1]` is approximated by a Gaussian with standard deviation
+    /// `α * q / sqrt(2π) * sqrt(m * r^2 / (2π) + 1)`, as the secret key `id_sk` is sampled
+    /// by the [`PSF`] with Gaussian parameter `r`, i.e. `|id_sk|^2 ≈ m * r^2 / (2π)`.
+    /// The probability is computed by [`rounding_failure_probability`].
+    ///
+    /// Returns the decryption failure probability as an [`f64`] in `[0, 1/2]`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::identity_based_encryption::DualRegevIBE;
+    /// let ibe = DualRegevIBE::default();
+    ///
+    /// assert!(ibe.decryption_failure_prob() < 0.5_f64.powi(20));
+    /// ```
+    pub fn decryption_failure_prob(&self) -> f64 {
+        let two_pi = 2.0 * std::f64::consts::PI;
+        let q = q_to_f64(&Q::from(Z::from(&self.dual_regev.q)));
+        let m = q_to_f64(&Q::from(&self.dual_regev.m));
+        let r = q_to_f64(&self.psf.s);
 
-    /// Generates a (pk, sk) pair for the Dual Regev public key encryption scheme
-    /// by following these steps:
-    /// - s <- Z_q^n
-    /// - A <- Z_q^{n x m}
-    /// - x <- χ^m
-    /// - p = A^t * s + x
+        let sigma = q_to_f64(&self.dual_regev.alpha) * q / two_pi.sqrt();
+        let noise_sigma = sigma * (m * r * r / two_pi + 1.0).sqrt();
+
+        rounding_failure_probability(q, noise_sigma)
+    }
+
+    /// Approximates the spectral norm of the short basis contained in the
+    /// master secret key using [`trapdoor_spectral_norm`].
+    /// The smaller the spectral norm, the better the quality of the master secret key.
     ///
-    /// Then, `pk = (A, p)` and `sk = s` is output.
+    /// Parameters:
+    /// - `sk`: the master secret key generated by [`DualRegevIBE::setup`](IBEScheme::setup)
+    ///
+    /// Returns an approximation of the spectral norm of the short basis as a [`Q`].
     ///
     /// # Examples
     /// ```
     /// use qfall_crypto::construction::identity_based_encryption::{DualRegevIBE, IBEScheme};
     /// let ibe = DualRegevIBE::default();
+    /// let (pk, sk) = ibe.setup();
+    ///
+    /// let norm = DualRegevIBE::master_key_spectral_norm(&sk);
+    /// ```
+    pub fn master_key_spectral_norm(sk: &(MatZ, MatQ)) -> Q {
+        trapdoor_spectral_norm(&sk.0)
+    }
+
+    /// Computes the [`orthogonality_defect`] of the short basis
+    /// contained in the master secret key.
+    /// The closer the defect is to `1`, the shorter is the Gram-Schmidt orthogonalization
+    /// of the short basis compared to the basis itself. Hence, a lower defect allows
+    /// the [`PSF`] to sample preimages, i.e. identity secret keys, of smaller norm,
+    /// which reduces the decryption failure probability.
+    ///
+    /// Parameters:
+    /// - `sk`: the short basis of the master secret key generated by
+    ///     [`DualRegevIBE::setup`](IBEScheme::setup)
     ///
+    /// Returns the orthogonality defect of the short basis as a [`Q`].
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::identity_based_encryption::{DualRegevIBE, IBEScheme};
+    /// let ibe = DualRegevIBE::default();
     /// let (pk, sk) = ibe.setup();
+    ///
+    /// let defect = DualRegevIBE::basis_quality(&sk.0);
     /// ```
-    fn setup(&self) -> (Self::MasterPublicKey, Self::MasterSecretKey) {
-        self.psf.trap_gen()
+    ///
+    /// # Panics ...
+    /// - if the columns of `sk` are linearly dependent.
+    pub fn basis_quality(sk: &MatZ) -> Q {
+        orthogonality_defect(sk)
     }
 
-    /// Given an identity it extracts a corresponding secret key by using samp_p
-    /// of the given [`PSF`].
+    /// Generates encryptions of `message mod 2` for all provided identities,
+    /// which share the same LWE secret `s` and the same first `m` entries.
+    /// For every identity `id_i` only the last entry is computed separately as
+    /// `s^t * H(id_i) + e_i + msg * ⌊q/2⌋` using fresh noise `e_i`.
+    /// This corresponds to an LWE sample `s^t * [A | H(id_1) | ... | H(id_k)] + e^t`,
+    /// hence the randomness has to be sampled only once instead of `k` times.
     ///
     /// Parameters:
-    /// - `master_pk`: The master public key for the encryption scheme
-    /// - `master_sk`: Zhe master secret key of the encryption scheme, namely
-    ///     the trapdoor for the [`PSF`]
-    /// - `identity`: The identity, for which the corresponding secret key
-    ///     should be returned
+    /// - `master_pk`: specifies the public key, which is matrix `pk = A`
+    /// - `identities`: specifies the identities used for encryption
+    /// - `message`: specifies the message that should be encrypted
     ///
-    /// Returns the corresponding secret key of `identity` under public key
-    /// `master_pk`.
+    /// Returns a cipher of type [`MatZq`] for every identity in `identities`,
+    /// which can be decrypted using [`DualRegevIBE::dec`](IBEScheme::dec).
     ///
     /// # Examples
     /// ```
-    /// use qfall_crypto::construction::identity_based_encryption::{IBEScheme, DualRegevIBE};
-    /// let mut ibe = DualRegevIBE::default();
-    /// let (master_pk, master_sk) = ibe.setup();
+    /// use qfall_crypto::construction::identity_based_encryption::{DualRegevIBE, IBEScheme};
+    /// use qfall_math::integer::Z;
+    /// let ibe = DualRegevIBE::default();
+    /// let (pk, sk) = ibe.setup();
+    /// let ids = [String::from("Alice"), String::from("Bob")];
     ///
-    /// let id = String::from("identity");
-    /// let sk = ibe.extract(&master_pk, &master_sk, &id);
+    /// let ciphers = ibe.enc_batch_same_msg(&pk, &ids, 1);
+    ///
+    /// let id_sk = ibe.extract(&pk, &sk, &ids[1]).unwrap();
+    /// assert_eq!(Z::ONE, ibe.dec(&id_sk, &ciphers[1]).unwrap());
     /// ```
-    fn extract(
-        &mut self,
-        master_pk: &Self::MasterPublicKey,
-        master_sk: &Self::MasterSecretKey,
-        identity: &Self::Identity,
-    ) -> Self::SecretKey {
-        // check if it is in the HashMap
-        if let Some(value) = self.storage.get(&format!(
-            "{master_pk} {} {} {identity}",
-            master_sk.0, master_sk.1
-        )) {
-            return value.clone();
-        }
+    pub fn enc_batch_same_msg(
+        &self,
+        master_pk: &MatZq,
+        identities: &[String],
+        message: impl Into<Z>,
+    ) -> Vec<MatZq> {
+        let n = &self.dual_regev.n;
+        let q = &self.dual_regev.q;
+        let gaussian_parameter = &self.dual_regev.alpha * Z::from(q);
 
-        let u = hash_to_mat_zq_sha256(identity, &self.dual_regev.n, 1, &self.dual_regev.q);
-        let secret_key = self.psf.samp_p(master_pk, master_sk, &u);
+        // compute (message mod 2) * ⌊q/2⌋
+        let msg_q_half = ZqArith::scale_by_half_q(&message.into(), &Z::from(q));
 
-        // insert secret key in HashMap
-        self.storage.insert(
-            format!("{master_pk} {} {} {identity}", master_sk.0, master_sk.1),
-            secret_key.clone(),
-        );
+        // s <- Z_q^n
+        let vec_s_t = MatZq::sample_uniform(1, n, q);
+        // e <- χ^m
+        let vec_e_t = MatZq::sample_discrete_gauss(
+            1,
+            master_pk.get_num_columns(),
+            q,
+            n,
+            0,
+            &gaussian_parameter,
+        )
+        .unwrap();
+        // s^t * A + e^t is shared by all ciphertexts
+        let c_shared_t = &vec_s_t * master_pk + vec_e_t;
 
-        secret_key
+        identities
+            .iter()
+            .map(|identity| {
+                let identity_based_pk =
+                    hash_to_mat_zq_sha256(identity, master_pk.get_num_rows(), 1, q);
+                // e_i <- χ
+                let e_i = MatZq::sample_discrete_gauss(1, 1, q, n, 0, &gaussian_parameter).unwrap();
+
+                // c_i = s^t * H(id_i) + e_i + msg * ⌊q/2⌋
+                let mut c_i = &vec_s_t * identity_based_pk + e_i;
+                let entry: Zq = c_i.get_entry(0, 0).unwrap();
+                c_i.set_entry(0, 0, entry + &msg_q_half).unwrap();
+
+                c_shared_t.concat_horizontal(&c_i).unwrap().transpose()
+            })
+            .collect()
     }
 
-    /// Generates an encryption of `message mod 2` for the provided public key
-    /// and identity by by calling [`DualRegev::enc()`] on
-    /// pk = [master_pk | H(id)] which corresponds to to [A | u] in
-    /// [GPV08 - eprint](https://eprint.iacr.org/2007/432.pdf).
-    /// Constructing the public key this way yields a identity based public key
-    /// which secret key can be extracted by the [`PSF`].
+    /// Rerandomizes `cipher` by adding a fresh encryption of `0` for `identity`,
+    /// i.e. `c_new = c + enc(mpk, id, 0)`.
+    /// The result encrypts the same message as `cipher` and is computationally
+    /// indistinguishable from a fresh encryption, which makes it useful e.g. in mix-networks.
     ///
-    /// Then, `cipher = [u | c]` is output.
+    /// **WARNING:** The noise of the resulting ciphertext is the sum of both noise terms.
+    /// Hence, every rerandomization reduces the noise margin of the ciphertext
+    /// and enough rerandomizations will cause decryption failures.
     ///
     /// Parameters:
-    /// - `master_pk`: specifies the public key, which cis matrix `pk = A`
+    /// - `master_pk`: specifies the public key, which is matrix `pk = A`
+    /// - `identity`: specifies the identity `cipher` was encrypted for
+    /// - `cipher`: specifies the ciphertext that should be rerandomized
+    ///
+    /// Returns a new cipher of type [`MatZq`] encrypting the same message as `cipher`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::identity_based_encryption::{DualRegevIBE, IBEScheme};
+    /// use qfall_math::integer::Z;
+    /// let ibe = DualRegevIBE::default();
+    /// let (pk, sk) = ibe.setup();
+    /// let cipher = ibe.enc(&pk, &String::from("Alice"), 1);
+    ///
+    /// let rerandomized = ibe.rerandomize_cipher(&pk, "Alice", &cipher);
+    ///
+    /// let id_sk = ibe.extract(&pk, &sk, &String::from("Alice")).unwrap();
+    /// assert_eq!(Z::ONE, ibe.dec(&id_sk, &rerandomized).unwrap());
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `cipher` does not have the dimensions of a ciphertext under `master_pk`.
+    pub fn rerandomize_cipher(&self, master_pk: &MatZq, identity: &str, cipher: &MatZq) -> MatZq {
+        let enc_zero = self.enc(master_pk, &identity.to_string(), 0);
+        cipher + enc_zero
+    }
+
+    /// Returns the identity `id || hex(SHA-256(ad))`, which binds the associated data `ad`
+    /// to `identity`. Secret keys for ciphertexts of [`DualRegevIBE::enc_with_ad`]
+    /// are extracted for this identity.
+    ///
+    /// Parameters:
+    /// - `identity`: specifies the identity of the recipient
+    /// - `ad`: specifies the associated data, e.g. the sender identity or a timestamp
+    ///
+    /// Returns the identity bound to `ad`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::identity_based_encryption::DualRegevIBE;
+    ///
+    /// let id = DualRegevIBE::identity_with_ad("Alice", b"2024-01-01");
+    ///
+    /// assert!(id.starts_with("Alice||"));
+    /// ```
+    pub fn identity_with_ad(identity: &str, ad: &[u8]) -> String {
+        let digest: String = Sha256::digest(ad)
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect();
+        format!("{identity}||{digest}")
+    }
+
+    /// Generates an encryption of `message mod 2`, which is bound to the associated data `ad`.
+    /// The message is encrypted for the identity [`DualRegevIBE::identity_with_ad`]`(id, ad)`.
+    /// Additionally, the first `32` bits of `SHA-256(ad)` are encrypted
+    /// for the same identity, s.t. [`DualRegevIBE::dec_with_ad`] can detect
+    /// associated data that does not match the ciphertext.
+    ///
+    /// Parameters:
+    /// - `master_pk`: specifies the public key, which is matrix `pk = A`
     /// - `identity`: specifies the identity used for encryption
     /// - `message`: specifies the message that should be encrypted
+    /// - `ad`: specifies the associated data bound to the ciphertext
     ///
-    /// Returns a cipher of type [`MatZq`] for master_pk an identity.
+    /// Returns a cipher of type [`MatZq`], whose first column encrypts `message`
+    /// and whose remaining columns encrypt the check bits.
     ///
     /// # Examples
     /// ```
     /// use qfall_crypto::construction::identity_based_encryption::{DualRegevIBE, IBEScheme};
+    /// use qfall_math::integer::Z;
     /// let ibe = DualRegevIBE::default();
     /// let (pk, sk) = ibe.setup();
     ///
-    /// let id = String::from("identity");
-    /// let cipher = ibe.enc(&pk, &id, 1);
+    /// let cipher = ibe.enc_with_ad(&pk, "Alice", 1, b"from Bob");
+    ///
+    /// let id_sk = ibe.extract(&pk, &sk, &DualRegevIBE::identity_with_ad("Alice", b"from Bob")).unwrap();
+    /// assert_eq!(Some(Z::ONE), ibe.dec_with_ad(&id_sk, &cipher, b"from Bob"));
     /// ```
-    fn enc(
+    pub fn enc_with_ad(
         &self,
-        master_pk: &Self::MasterPublicKey,
-        identity: &Self::Identity,
+        master_pk: &MatZq,
+        identity: &str,
         message: impl Into<Z>,
-    ) -> Self::Cipher {
+        ad: &[u8],
+    ) -> MatZq {
+        let identity = DualRegevIBE::identity_with_ad(identity, ad);
+        let check_bits = ad_check_bits(ad);
+
+        let mut cipher = self.enc(master_pk, &identity, message);
+        for bit in check_bits {
+            cipher = cipher
+                .concat_horizontal(&self.enc(master_pk, &identity, bit))
+                .unwrap();
+        }
+
+        cipher
+    }
+
+    /// Decrypts a cipher generated by [`DualRegevIBE::enc_with_ad`] after checking
+    /// that it is bound to the associated data `ad`.
+    /// The check fails if `ad` differs from the associated data used for encryption
+    /// or if `sk_id` was not extracted for [`DualRegevIBE::identity_with_ad`]`(id, ad)`.
+    /// In the latter case, the check bits decrypt to random bits and the check
+    /// fails with probability `1 - 2^{-32}`.
+    ///
+    /// Parameters:
+    /// - `sk_id`: specifies the secret key extracted for the identity bound to `ad`
+    /// - `cipher`: specifies the cipher generated by [`DualRegevIBE::enc_with_ad`]
+    /// - `ad`: specifies the associated data the cipher should be bound to
+    ///
+    /// Returns the decryption of `cipher` or `None` if the check fails,
+    /// `cipher` does not have `33` columns, or `sk_id` and `cipher` do not fit together.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::identity_based_encryption::{DualRegevIBE, IBEScheme};
+    /// let ibe = DualRegevIBE::default();
+    /// let (pk, sk) = ibe.setup();
+    /// let cipher = ibe.enc_with_ad(&pk, "Alice", 1, b"from Bob");
+    /// let id_sk = ibe.extract(&pk, &sk, &DualRegevIBE::identity_with_ad("Alice", b"from Bob")).unwrap();
+    ///
+    /// assert_eq!(None, ibe.dec_with_ad(&id_sk, &cipher, b"from Eve"));
+    /// ```
+    pub fn dec_with_ad(&self, sk_id: &MatZ, cipher: &MatZq, ad: &[u8]) -> Option<Z> {
+        if cipher.get_num_columns() as usize != 1 + AD_CHECK_BITS {
+            return None;
+        }
+
+        // decrypt all check bits before comparing s.t. the runtime does not
+        // depend on the position of the first mismatch
+        let mut valid = true;
+        for (i, bit) in ad_check_bits(ad).into_iter().enumerate() {
+            let column = cipher.get_column(i as i64 + 1).unwrap();
+            valid &= self.dec(sk_id, &column).ok() == Some(Z::from(bit));
+        }
+
+        if valid {
+            self.dec(sk_id, &cipher.get_column(0).unwrap()).ok()
+        } else {
+            None
+        }
+    }
+
+    /// Encrypts a vector of bits for `identity` using [`DualRegev::enc_vec`]
+    /// under the identity-based public key `[A | H(id)]`.
+    ///
+    /// Parameters:
+    /// - `master_pk`: specifies the master public key `A`
+    /// - `identity`: specifies the recipient that should be able to decrypt the encrypted message
+    /// - `message`: specifies the column vector of length `l` that should be encrypted
+    ///
+    /// Returns a cipher `C ∈ Z_q^{(m+1) x l}` of type [`MatZq`],
+    /// whose `i`-th column encrypts the `i`-th coordinate of `message mod 2`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::identity_based_encryption::{DualRegevIBE, IBEScheme};
+    /// use qfall_math::integer::MatZ;
+    /// use std::str::FromStr;
+    /// let ibe = DualRegevIBE::default();
+    /// let (pk, sk) = ibe.setup();
+    ///
+    /// let cipher = ibe.enc_vec(&pk, "identity", &MatZ::from_str("[[1],[0],[1]]").unwrap());
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `message` is not a column vector.
+    pub fn enc_vec(&self, master_pk: &MatZq, identity: &str, message: &MatZ) -> MatZq {
         let identity_based_pk =
             hash_to_mat_zq_sha256(identity, master_pk.get_num_rows(), 1, master_pk.get_mod());
-        self.dual_regev.enc(
+        self.dual_regev.enc_vec(
             &master_pk.concat_horizontal(&identity_based_pk).unwrap(),
             message,
         )
     }
 
-    /// Decrypts the provided `cipher` using the secret key `sk` by using
-    /// [`DualRegev::dec()`]
+    /// Decrypts a cipher generated by [`DualRegevIBE::enc_vec`]
+    /// using [`DualRegev::dec_vec`].
     ///
     /// Parameters:
     /// - `sk_id`: specifies the secret key `sk = s` obtained by extract
-    /// - `cipher`: specifies the cipher containing `cipher = c`
+    /// - `cipher`: specifies the cipher `C ∈ Z_q^{(m+1) x l}`
     ///
-    /// Returns the decryption of `cipher` as a [`Z`] instance.
+    /// Returns the decryption of `cipher` as a column vector of length `l` of type [`MatZ`].
     ///
     /// # Examples
     /// ```
     /// use qfall_crypto::construction::identity_based_encryption::{DualRegevIBE, IBEScheme};
-    /// use qfall_math::integer::Z;
-    /// // setup public parameters and key pair
-    /// let mut ibe = DualRegevIBE::default();
+    /// use qfall_math::integer::MatZ;
+    /// use std::str::FromStr;
+    /// let ibe = DualRegevIBE::default();
     /// let (pk, sk) = ibe.setup();
+    /// let id_sk = ibe.extract(&pk, &sk, &String::from("identity")).unwrap();
+    /// let msg = MatZ::from_str("[[1],[0],[1]]").unwrap();
+    /// let cipher = ibe.enc_vec(&pk, "identity", &msg);
     ///
-    /// // extract a identity based secret key
-    /// let identity = String::from("identity");
-    /// let id_sk = ibe.extract(&pk, &sk, &identity);
+    /// assert_eq!(msg, ibe.dec_vec(&id_sk, &cipher));
+    /// ```
+    pub fn dec_vec(&self, sk_id: &MatZ, cipher: &MatZq) -> MatZ {
+        self.dual_regev.dec_vec(sk_id, cipher)
+    }
+
+    /// Delegates the master secret key to `identity` following the bonsai tree
+    /// technique of [CHKP10](https://eprint.iacr.org/2009/351.pdf), see
+    /// [`delegate_trapdoor`](crate::sample::g_trapdoor::gadget_classical::delegate_trapdoor).
+    /// The master public key `A` is extended by the identity-based block
+    /// `B_id = H(id) ∈ Z_q^{n x nk}` and the short basis of `A` is extended to a
+    /// short basis of `Λ^⟂([A | B_id])`.
     ///
-    /// // encrypt a bit
-    /// let msg = Z::ZERO; // must be a bit, i.e. msg = 0 or 1
-    /// let cipher = ibe.enc(&pk, &identity, &msg);
+    /// The delegated authority can then extract keys of its children using
+    /// [`DualRegevIBE::extract_delegated`] without the master secret key.
     ///
-    /// // decrypt
-    /// let m = ibe.dec(&id_sk, &cipher);
+    /// Parameters:
+    /// - `master_pk`: the master public key `A`
+    /// - `master_sk`: the master secret key, i.e. a short basis of `Λ^⟂(A)` and its GSO
+    /// - `identity`: the identity to which the master secret key is delegated
     ///
-    /// assert_eq!(msg, m)
+    /// Returns the delegated public key `[A | B_id]` and a short basis of its lattice.
+    ///
+    /// # Examples
     /// ```
-    fn dec(&self, sk_id: &Self::SecretKey, cipher: &Self::Cipher) -> Z {
-        self.dual_regev.dec(sk_id, cipher)
-    }
-}
-
-#[cfg(test)]
-mod test_dual_regev_ibe {
-    use super::DualRegevIBE;
-    use crate::construction::identity_based_encryption::IBEScheme;
-    use qfall_math::integer::Z;
+    /// use qfall_crypto::construction::identity_based_encryption::{DualRegevIBE, IBEScheme};
+    /// let ibe = DualRegevIBE::default();
+    /// let (pk, sk) = ibe.setup();
+    ///
+    /// let (delegated_pk, delegated_sk) = ibe.delegate(&pk, &sk, "org");
+    /// ```
+    pub fn delegate(
+        &self,
+        master_pk: &MatZq,
+        master_sk: &(MatZ, MatQ),
+        identity: &str,
+    ) -> (MatZq, MatZ) {
+        let h_id = hash_to_mat_zq_sha256(
+            &format!("delegate {identity}"),
+            master_pk.get_num_rows(),
+            &(&self.psf.gp.n * &self.psf.gp.k),
+            master_pk.get_mod(),
+        );
+        let delegated_sk = extend_short_basis(master_pk, &master_sk.0, &h_id);
 
-    /// Checks whether `new` is available for types implementing [`Into<Z>`].
-    #[test]
-    fn new_availability() {
-        let _ = DualRegevIBE::new(2u8, 2u16, 2u32, 2u64);
-        let _ = DualRegevIBE::new(2u16, 2u64, 2i32, 2i64);
-        let _ = DualRegevIBE::new(2i16, 2i64, 2u32, 2u8);
-        let _ = DualRegevIBE::new(Z::from(2), Z::from(2), 2u8, 2i8);
+        (master_pk.concat_horizontal(&h_id).unwrap(), delegated_sk)
     }
 
-    /// Ensures that `new_from_n` is available for types implementing [`Into<Z>`].
+    /// Extracts the secret key of `identity` with a delegated key generated by
+    /// [`DualRegevIBE::delegate`] like [`DualRegevIBE::extract`](IBEScheme::extract).
+    /// As the Gram-Schmidt orthogonalization of the delegated basis is as short as
+    /// the one of the master secret key, the same [`PSF`] is used.
+    ///
+    /// Parameters:
+    /// - `delegated_pk`: the delegated public key `[A | B_id]`
+    /// - `delegated_sk`: the short basis of `Λ^⟂([A | B_id])`
+    /// - `identity`: the identity, for which the secret key should be returned
+    ///
+    /// Returns the secret key of `identity` under the delegated public key,
+    /// which decrypts ciphers generated by [`DualRegevIBE::enc_delegated`],
+    /// or a [`CryptoError`] if the key could not be extracted.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::identity_based_encryption::{DualRegevIBE, IBEScheme};
+    /// let ibe = DualRegevIBE::default();
+    /// let (pk, sk) = ibe.setup();
+    /// let (delegated_pk, delegated_sk) = ibe.delegate(&pk, &sk, "org");
+    ///
+    /// let id = String::from("alice");
+    /// let sk_id = ibe.extract_delegated(&delegated_pk, &delegated_sk, &id).unwrap();
+    /// ```
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] of type [`IncompatibleParameters`](CryptoError::IncompatibleParameters)
+    ///     if `delegated_pk` does not have `n` rows and modulus `q`.
+    /// - Returns a [`CryptoError`] of type [`KeyExtractionFailed`](CryptoError::KeyExtractionFailed)
+    ///     if the extracted secret key is no short preimage of `H(identity)`,
+    ///     e.g. as `delegated_sk` is no short basis of `Λ^⟂(delegated_pk)`.
+    pub fn extract_delegated(
+        &self,
+        delegated_pk: &MatZq,
+        delegated_sk: &MatZ,
+        identity: &String,
+    ) -> Result<MatZ, CryptoError> {
+        if Z::from(delegated_pk.get_num_rows()) != self.dual_regev.n
+            || delegated_pk.get_mod() != self.dual_regev.q
+        {
+            return Err(CryptoError::IncompatibleParameters);
+        }
+
+        let delegated_sk = (delegated_sk.clone(), MatQ::from(delegated_sk).gso());
+        self.extract_verified(delegated_pk, &delegated_sk, identity)
+    }
+
+    /// Generates an encryption of `message mod 2` for `identity` under the
+    /// delegated public key `[A | B_id]` like [`DualRegevIBE::enc`](IBEScheme::enc).
+    /// As the delegated lattice has `nk` additional dimensions, the Gaussian
+    /// parameter `alpha` is scaled by `sqrt((m + 1) / (m + nk + 1))`,
+    /// s.t. the decryption noise is distributed as for [`DualRegevIBE::enc`](IBEScheme::enc).
+    ///
+    /// Parameters:
+    /// - `delegated_pk`: the delegated public key `[A | B_id]`
+    /// - `identity`: specifies the identity used for encryption
+    /// - `message`: specifies the message that should be encrypted
+    ///
+    /// Returns a cipher of type [`MatZq`], which can be decrypted by
+    /// [`DualRegevIBE::dec`](IBEScheme::dec) with the key
+    /// extracted by [`DualRegevIBE::extract_delegated`].
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::identity_based_encryption::{DualRegevIBE, IBEScheme};
+    /// use qfall_math::integer::Z;
+    /// let ibe = DualRegevIBE::default();
+    /// let (pk, sk) = ibe.setup();
+    /// let (delegated_pk, delegated_sk) = ibe.delegate(&pk, &sk, "org");
+    /// let id = String::from("alice");
+    /// let sk_id = ibe.extract_delegated(&delegated_pk, &delegated_sk, &id).unwrap();
+    ///
+    /// let cipher = ibe.enc_delegated(&delegated_pk, &id, 1);
+    ///
+    /// assert_eq!(Z::ONE, ibe.dec(&sk_id, &cipher).unwrap());
+    /// ```
+    pub fn enc_delegated(
+        &self,
+        delegated_pk: &MatZq,
+        identity: &str,
+        message: impl Into<Z>,
+    ) -> MatZq {
+        let m = &self.dual_regev.m;
+        let m_delegated = Z::from(delegated_pk.get_num_columns());
+        let alpha = &self.dual_regev.alpha * (m + Z::ONE).sqrt() / (&m_delegated + Z::ONE).sqrt();
+        let dual_regev = DualRegev::new(
+            &self.dual_regev.n,
+            m_delegated,
+            self.dual_regev.q.clone(),
+            alpha,
+        );
+
+        let identity_based_pk = hash_to_mat_zq_sha256(
+            identity,
+            delegated_pk.get_num_rows(),
+            1,
+            delegated_pk.get_mod(),
+        );
+        dual_regev.enc(
+            &delegated_pk.concat_horizontal(&identity_based_pk).unwrap(),
+            message,
+        )
+    }
+
+    /// Decrypts the provided `cipher` using the secret key `sk_id` like
+    /// [`DualRegevIBE::dec`](IBEScheme::dec), but additionally checks that the
+    /// decryption noise did not overflow.
+    ///
+    /// The raw inner product `v = c_last - sk_id^t * c_A mod q` of a valid cipher
+    /// is close to `0` or `⌊q/2⌋`. If its distance to both values is larger than
+    /// `3q/16`, i.e. `v` is closer than `q/16` to the decision boundary `q/4`,
+    /// the cipher is rejected as malformed or affected by a noise overflow.
+    ///
+    /// Parameters:
+    /// - `sk_id`: specifies the secret key of the identity
+    /// - `cipher`: specifies the cipher containing `cipher = c`
+    ///
+    /// Returns the decryption of `cipher` or a [`CryptoError`] if the noise is too large.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::identity_based_encryption::{DualRegevIBE, IBEScheme};
+    /// use qfall_math::integer::Z;
+    /// let ibe = DualRegevIBE::default();
+    /// let (pk, sk) = ibe.setup();
+    /// let id = String::from("Alice");
+    /// let id_sk = ibe.extract(&pk, &sk, &id).unwrap();
+    /// let cipher = ibe.enc(&pk, &id, 1);
+    ///
+    /// assert_eq!(Z::ONE, ibe.dec_verified(&id_sk, &cipher).unwrap());
+    /// ```
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] of type [`IncompatibleParameters`](CryptoError::IncompatibleParameters)
+    ///     if `sk_id` and `cipher` do not fit together, see [`DualRegevIBE::dec`](IBEScheme::dec).
+    /// - Returns a [`CryptoError`] of type [`DecryptionFailed`](CryptoError::DecryptionFailed)
+    ///     if the distance of the raw inner product to `0` and `⌊q/2⌋` exceeds `3q/16`.
+    pub fn dec_verified(&self, sk_id: &MatZ, cipher: &MatZq) -> Result<Z, CryptoError> {
+        let message = self.dec(sk_id, cipher)?;
+        let q = Z::from(&self.dual_regev.q);
+        let v = self.raw_decryption(sk_id, cipher).abs();
+
+        // distance to the closest value of 0 and ⌊q/2⌋ given by the message
+        let noise = if message == Z::ZERO {
+            v
+        } else {
+            (q.div_floor(2) - v).abs()
+        };
+
+        if noise * 16 > q * 3 {
+            return Err(CryptoError::DecryptionFailed);
+        }
+
+        Ok(message)
+    }
+
+    /// Extracts the secret key of `identity` without checking the number of columns
+    /// `m'` of `master_pk`, s.t. delegated public keys can be used as well.
+    /// A secret key `sk_id` is only returned and stored if it is a column vector
+    /// of length `m'` with `||sk_id||^2 <= s^2 * m'` and `master_pk * sk_id = H(identity)`.
+    /// For `m' = m`, this matches the domain of the [`PSF`].
+    fn extract_verified(
+        &self,
+        master_pk: &MatZq,
+        master_sk: &(MatZ, MatQ),
+        identity: &str,
+    ) -> Result<MatZ, CryptoError> {
+        let key = storage_key(master_pk, identity);
+        let is_valid = |sk_id: &MatZ| self.is_valid_sk_id(master_pk, identity, sk_id);
+
+        if let Some(value) = self.storage.get(&key) {
+            if is_valid(&value.value().0) {
+                #[cfg(feature = "logging")]
+                tracing::debug!(cached = true, "extracted secret key");
+                return Ok(value.value().0.clone());
+            }
+        }
+
+        // the secret key is verified before it is stored s.t. concurrent calls
+        // can never observe an invalid secret key in the storage
+        let sk_id = self.compute_sk_id(master_pk, master_sk, identity);
+        if !is_valid(&sk_id) {
+            return Err(CryptoError::KeyExtractionFailed);
+        }
+
+        let mut entry = self
+            .storage
+            .entry(key)
+            .or_insert_with(|| (sk_id.clone(), SystemTime::now()));
+        if !is_valid(&entry.value().0) {
+            *entry.value_mut() = (sk_id, SystemTime::now());
+        }
+
+        #[cfg(feature = "logging")]
+        tracing::debug!(cached = false, "extracted secret key");
+        Ok(entry.value().0.clone())
+    }
+
+    /// Checks whether `sk_id` is a column vector of length `m'` with
+    /// `||sk_id||^2 <= s^2 * m'` and `master_pk * sk_id = H(identity)`,
+    /// where `m'` is the number of columns of `master_pk`.
+    fn is_valid_sk_id(&self, master_pk: &MatZq, identity: &str, sk_id: &MatZ) -> bool {
+        let m = Z::from(master_pk.get_num_columns());
+        let u = hash_to_mat_zq_sha256(identity, &self.dual_regev.n, 1, &self.dual_regev.q);
+
+        sk_id.is_column_vector()
+            && m == Z::from(sk_id.get_num_rows())
+            && Q::from(&sk_id.norm_eucl_sqrd().unwrap()) <= self.psf.s.pow(2).unwrap() * &m
+            && master_pk * sk_id == u
+    }
+
+    /// Computes the raw inner product `c_last - sk_id^t * c_A`
+    /// centered around `0`, i.e. in `[-q/2, q/2)`.
+    fn raw_decryption(&self, sk_id: &MatZ, cipher: &MatZq) -> Z {
+        let tmp = (Z::MINUS_ONE * sk_id)
+            .concat_vertical(&MatZ::identity(1, 1))
+            .unwrap();
+        let result: Zq = (cipher.transpose() * tmp).get_entry(0, 0).unwrap();
+
+        ZqArith::reduce_centered(
+            &result.get_representative_0_modulus(),
+            &Z::from(&self.dual_regev.q),
+        )
+    }
+
+    /// Extracts the secret key of `identity` like [`DualRegevIBE::extract`](IBEScheme::extract),
+    /// but only returns a cached secret key if it was inserted into the storage within `ttl`.
+    /// Otherwise, a new secret key is computed and replaces the cached one.
+    ///
+    /// Parameters:
+    /// - `master_pk`: The master public key for the encryption scheme
+    /// - `master_sk`: The master secret key of the encryption scheme, namely
+    ///     the trapdoor for the [`PSF`]
+    /// - `identity`: The identity, for which the corresponding secret key
+    ///     should be returned
+    /// - `ttl`: The maximal age of a cached secret key
+    ///
+    /// Returns the secret key of `identity` or a [`CryptoError`]
+    /// if the key could not be extracted.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::identity_based_encryption::{DualRegevIBE, IBEScheme};
+    /// use std::time::Duration;
+    /// let ibe = DualRegevIBE::default();
+    /// let (pk, sk) = ibe.setup();
+    /// let id = String::from("Alice");
+    ///
+    /// let id_sk = ibe.extract_with_ttl(&pk, &sk, &id, Duration::from_secs(3600)).unwrap();
+    ///
+    /// assert_eq!(id_sk, ibe.extract(&pk, &sk, &id).unwrap());
+    /// ```
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] of type [`IncompatibleParameters`](CryptoError::IncompatibleParameters)
+    ///     if the dimensions or the modulus of `master_pk` do not match the parameters.
+    /// - Returns a [`CryptoError`] of type [`KeyExtractionFailed`](CryptoError::KeyExtractionFailed)
+    ///     if the computed secret key is invalid, e.g. as `master_sk` is no trapdoor of `master_pk`.
+    pub fn extract_with_ttl(
+        &self,
+        master_pk: &MatZq,
+        master_sk: &(MatZ, MatQ),
+        identity: &str,
+        ttl: Duration,
+    ) -> Result<MatZ, CryptoError> {
+        if Z::from(master_pk.get_num_rows()) != self.dual_regev.n
+            || Z::from(master_pk.get_num_columns()) != self.dual_regev.m
+            || master_pk.get_mod() != self.dual_regev.q
+        {
+            return Err(CryptoError::IncompatibleParameters);
+        }
+
+        let key = storage_key(master_pk, identity);
+        let is_valid = |sk_id: &MatZ| self.is_valid_sk_id(master_pk, identity, sk_id);
+
+        if let Some(value) = self.storage.get(&key) {
+            let (sk_id, inserted) = value.value();
+            if is_fresh(inserted, ttl) && is_valid(sk_id) {
+                return Ok(sk_id.clone());
+            }
+        }
+
+        // the secret key is sampled and verified before the entry is locked
+        let sk_id = self.compute_sk_id(master_pk, master_sk, identity);
+        if !is_valid(&sk_id) {
+            return Err(CryptoError::KeyExtractionFailed);
+        }
+
+        let mut entry = self
+            .storage
+            .entry(key)
+            .or_insert_with(|| (sk_id.clone(), SystemTime::now()));
+        if !is_fresh(&entry.value().1, ttl) || !is_valid(&entry.value().0) {
+            *entry.value_mut() = (sk_id, SystemTime::now());
+        }
+
+        Ok(entry.value().0.clone())
+    }
+
+    /// Removes all secret keys from the storage, which were inserted more than `ttl` ago.
+    /// This bounds the memory of long-running applications, as evicted secret keys
+    /// are recomputed on their next extraction.
+    ///
+    /// Parameters:
+    /// - `ttl`: The maximal age of a cached secret key
+    ///
+    /// Returns the number of removed secret keys.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::identity_based_encryption::{DualRegevIBE, IBEScheme};
+    /// use std::time::Duration;
+    /// let mut ibe = DualRegevIBE::default();
+    /// let (pk, sk) = ibe.setup();
+    /// let _ = ibe.extract(&pk, &sk, &String::from("Alice")).unwrap();
+    ///
+    /// assert_eq!(0, ibe.evict_expired(Duration::from_secs(3600)));
+    /// ```
+    pub fn evict_expired(&mut self, ttl: Duration) -> usize {
+        let before = self.storage.len();
+        self.storage
+            .retain(|_, (_, inserted)| is_fresh(inserted, ttl));
+
+        before - self.storage.len()
+    }
+
+    /// Extracts the secret keys of all `identities` in parallel using [`rayon`].
+    /// Each secret key is extracted by [`DualRegevIBE::extract`](IBEScheme::extract),
+    /// i.e. cached secret keys are reused and newly computed secret keys are stored.
+    /// As the entry of an identity is locked while its secret key is computed,
+    /// every identity obtains the same secret key, even if it occurs several times
+    /// or is extracted concurrently by another thread.
+    ///
+    /// Parameters:
+    /// - `master_pk`: The master public key for the encryption scheme
+    /// - `master_sk`: The master secret key of the encryption scheme, namely
+    ///     the trapdoor for the [`PSF`]
+    /// - `identities`: The identities, for which the corresponding secret keys
+    ///     should be returned
+    ///
+    /// Returns the secret keys of `identities` in the same order as `identities`
+    /// or the first [`CryptoError`] returned by [`DualRegevIBE::extract`](IBEScheme::extract).
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::identity_based_encryption::{DualRegevIBE, IBEScheme};
+    /// let ibe = DualRegevIBE::default();
+    /// let (pk, sk) = ibe.setup();
+    /// let identities = vec![String::from("Alice"), String::from("Bob")];
+    ///
+    /// let id_sks = ibe.extract_batch(&pk, &sk, &identities).unwrap();
+    ///
+    /// assert_eq!(id_sks[1], ibe.extract(&pk, &sk, &identities[1]).unwrap());
+    /// ```
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] if the secret key of an identity could not be
+    ///     extracted, see [`DualRegevIBE::extract`](IBEScheme::extract).
+    pub fn extract_batch(
+        &self,
+        master_pk: &MatZq,
+        master_sk: &(MatZ, MatQ),
+        identities: &[String],
+    ) -> Result<Vec<MatZ>, CryptoError> {
+        identities
+            .par_iter()
+            .map(|identity| self.extract(master_pk, master_sk, identity))
+            .collect()
+    }
+
+    /// Computes a new secret key of `identity` by sampling a preimage of `H(identity)`.
+    fn compute_sk_id(&self, master_pk: &MatZq, master_sk: &(MatZ, MatQ), identity: &str) -> MatZ {
+        let u = hash_to_mat_zq_sha256(identity, &self.dual_regev.n, 1, &self.dual_regev.q);
+        self.psf.samp_p(master_pk, master_sk, &u)
+    }
+}
+
+/// Checks whether an entry inserted at `inserted` is at most `ttl` old.
+/// Entries inserted in the future, e.g. due to a changed system clock, are considered stale.
+fn is_fresh(inserted: &SystemTime, ttl: Duration) -> bool {
+    inserted.elapsed().is_ok_and(|age| age <= ttl)
+}
+
+/// Returns the first [`AD_CHECK_BITS`] bits of `SHA-256(ad)`,
+/// starting with the least significant bit of the first byte.
+fn ad_check_bits(ad: &[u8]) -> Vec<u8> {
+    let digest = Sha256::digest(ad);
+    (0..AD_CHECK_BITS)
+        .map(|i| (digest[i / 8] >> (i % 8)) & 1)
+        .collect()
+}
+
+impl Default for DualRegevIBE {
+    /// Initializes a [`DualRegevIBE`] struct with parameters generated by `DualRegevIBE::new_from_n(4)`.
+    /// This parameter choice is not secure as the dimension of the lattice is too small,
+    /// but it provides an efficient working example.
+    ///
+    /// Returns an [`DualRegevIBE`] instance.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::identity_based_encryption::DualRegevIBE;
+    ///
+    /// let ibe = DualRegevIBE::default();
+    /// ```
+    fn default() -> Self {
+        DualRegevIBE::new_from_n(4).unwrap()
+    }
+}
+
+impl IBEScheme for DualRegevIBE {
+    type Cipher = MatZq;
+    type MasterPublicKey = MatZq;
+    type MasterSecretKey = (MatZ, MatQ);
+    type SecretKey = MatZ;
+    type Identity = String;
+
+    /// Generates a (pk, sk) pair for the Dual Regev public key encryption scheme
+    /// by following these steps:
+    /// - s <- Z_q^n
+    /// - A <- Z_q^{n x m}
+    /// - x <- χ^m
+    /// - p = A^t * s + x
+    ///
+    /// Then, `pk = (A, p)` and `sk = s` is output.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::identity_based_encryption::{DualRegevIBE, IBEScheme};
+    /// let ibe = DualRegevIBE::default();
+    ///
+    /// let (pk, sk) = ibe.setup();
+    /// ```
+    #[cfg_attr(
+        feature = "logging",
+        tracing::instrument(name = "setup", skip_all, fields(n = %self.dual_regev.n))
+    )]
+    fn setup(&self) -> (Self::MasterPublicKey, Self::MasterSecretKey) {
+        let (master_pk, master_sk) = self.psf.trap_gen();
+
+        #[cfg(feature = "logging")]
+        tracing::debug!(
+            rows = master_pk.get_num_rows(),
+            columns = master_pk.get_num_columns(),
+            "generated master key pair"
+        );
+        (master_pk, master_sk)
+    }
+
+    /// Given an identity it extracts a corresponding secret key by using samp_p
+    /// of the given [`PSF`].
+    /// A secret key `sk_id` is only returned and stored if it is in the domain of
+    /// the [`PSF`] and satisfies `master_pk * sk_id = H(identity)`.
+    /// A stored secret key violating these conditions is replaced by a newly
+    /// computed one, if it is valid.
+    ///
+    /// Parameters:
+    /// - `master_pk`: The master public key for the encryption scheme
+    /// - `master_sk`: Zhe master secret key of the encryption scheme, namely
+    ///     the trapdoor for the [`PSF`]
+    /// - `identity`: The identity, for which the corresponding secret key
+    ///     should be returned
+    ///
+    /// Returns the corresponding secret key of `identity` under public key
+    /// `master_pk` or a [`CryptoError`] if the key could not be extracted.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::identity_based_encryption::{IBEScheme, DualRegevIBE};
+    /// let ibe = DualRegevIBE::default();
+    /// let (master_pk, master_sk) = ibe.setup();
+    ///
+    /// let id = String::from("identity");
+    /// let sk = ibe.extract(&master_pk, &master_sk, &id).unwrap();
+    /// ```
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] of type [`IncompatibleParameters`](CryptoError::IncompatibleParameters)
+    ///     if the dimensions or the modulus of `master_pk` do not match the parameters.
+    /// - Returns a [`CryptoError`] of type [`KeyExtractionFailed`](CryptoError::KeyExtractionFailed)
+    ///     if the extracted secret key is no short preimage of `H(identity)`,
+    ///     e.g. as `master_sk` is not the trapdoor of `master_pk`.
+    #[cfg_attr(
+        feature = "logging",
+        tracing::instrument(
+            name = "extract",
+            skip_all,
+            fields(
+                %identity,
+                rows = master_pk.get_num_rows(),
+                columns = master_pk.get_num_columns()
+            )
+        )
+    )]
+    fn extract(
+        &self,
+        master_pk: &Self::MasterPublicKey,
+        master_sk: &Self::MasterSecretKey,
+        identity: &Self::Identity,
+    ) -> Result<Self::SecretKey, CryptoError> {
+        if Z::from(master_pk.get_num_rows()) != self.dual_regev.n
+            || Z::from(master_pk.get_num_columns()) != self.dual_regev.m
+            || master_pk.get_mod() != self.dual_regev.q
+        {
+            return Err(CryptoError::IncompatibleParameters);
+        }
+
+        self.extract_verified(master_pk, master_sk, identity)
+    }
+
+    /// Generates an encryption of `message mod 2` for the provided public key
+    /// and identity by by calling [`DualRegev::enc()`] on
+    /// pk = [master_pk | H(id)] which corresponds to to [A | u] in
+    /// [GPV08 - eprint](https://eprint.iacr.org/2007/432.pdf).
+    /// Constructing the public key this way yields a identity based public key
+    /// which secret key can be extracted by the [`PSF`].
+    ///
+    /// Then, `cipher = [u | c]` is output.
+    ///
+    /// Parameters:
+    /// - `master_pk`: specifies the public key, which cis matrix `pk = A`
+    /// - `identity`: specifies the identity used for encryption
+    /// - `message`: specifies the message that should be encrypted
+    ///
+    /// Returns a cipher of type [`MatZq`] for master_pk an identity.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::identity_based_encryption::{DualRegevIBE, IBEScheme};
+    /// let ibe = DualRegevIBE::default();
+    /// let (pk, sk) = ibe.setup();
+    ///
+    /// let id = String::from("identity");
+    /// let cipher = ibe.enc(&pk, &id, 1);
+    /// ```
+    #[cfg_attr(
+        feature = "logging",
+        tracing::instrument(
+            name = "enc",
+            skip_all,
+            fields(
+                %identity,
+                rows = master_pk.get_num_rows(),
+                columns = master_pk.get_num_columns()
+            )
+        )
+    )]
+    fn enc(
+        &self,
+        master_pk: &Self::MasterPublicKey,
+        identity: &Self::Identity,
+        message: impl Into<Z>,
+    ) -> Self::Cipher {
+        let identity_based_pk =
+            hash_to_mat_zq_sha256(identity, master_pk.get_num_rows(), 1, master_pk.get_mod());
+        let cipher = self.dual_regev.enc(
+            &master_pk.concat_horizontal(&identity_based_pk).unwrap(),
+            message,
+        );
+
+        #[cfg(feature = "logging")]
+        tracing::debug!(cipher_rows = cipher.get_num_rows(), "encrypted message");
+        cipher
+    }
+
+    /// Decrypts the provided `cipher` using the secret key `sk` by using
+    /// [`DualRegev::dec()`]
+    ///
+    /// Parameters:
+    /// - `sk_id`: specifies the secret key `sk = s` obtained by extract
+    /// - `cipher`: specifies the cipher containing `cipher = c`
+    ///
+    /// Returns the decryption of `cipher` as a [`Z`] instance
+    /// or a [`CryptoError`] if `sk_id` and `cipher` do not fit together.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::identity_based_encryption::{DualRegevIBE, IBEScheme};
+    /// use qfall_math::integer::Z;
+    /// // setup public parameters and key pair
+    /// let ibe = DualRegevIBE::default();
+    /// let (pk, sk) = ibe.setup();
+    ///
+    /// // extract a identity based secret key
+    /// let identity = String::from("identity");
+    /// let id_sk = ibe.extract(&pk, &sk, &identity).unwrap();
+    ///
+    /// // encrypt a bit
+    /// let msg = Z::ZERO; // must be a bit, i.e. msg = 0 or 1
+    /// let cipher = ibe.enc(&pk, &identity, &msg);
+    ///
+    /// // decrypt
+    /// let m = ibe.dec(&id_sk, &cipher).unwrap();
+    ///
+    /// assert_eq!(msg, m)
+    /// ```
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] of type [`IncompatibleParameters`](CryptoError::IncompatibleParameters)
+    ///     if `sk_id` is no column vector, if `cipher` is no column vector with one
+    ///     entry more than `sk_id`, or if the modulus of `cipher` does not match `q`.
+    #[cfg_attr(
+        feature = "logging",
+        tracing::instrument(name = "dec", skip_all, fields(cipher_rows = cipher.get_num_rows()))
+    )]
+    fn dec(&self, sk_id: &Self::SecretKey, cipher: &Self::Cipher) -> Result<Z, CryptoError> {
+        if sk_id.get_num_columns() != 1
+            || cipher.get_num_columns() != 1
+            || cipher.get_num_rows() != sk_id.get_num_rows() + 1
+            || cipher.get_mod() != self.dual_regev.q
+        {
+            return Err(CryptoError::IncompatibleParameters);
+        }
+
+        let message = self.dual_regev.dec(sk_id, cipher);
+
+        // the decrypted message is not logged to keep it out of the logs
+        #[cfg(feature = "logging")]
+        tracing::debug!("decrypted cipher");
+        Ok(message)
+    }
+
+    /// Checks the parameters for security using [`DualRegevIBE::check_security`].
+    ///
+    /// Returns an empty result or an error describing the violated requirement.
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] of type [`InvalidParameters`](CryptoError::InvalidParameters)
+    ///     if at least one parameter was not chosen appropriately for a
+    ///     secure Dual Regev IBE instance.
+    fn check_security(&self) -> Result<(), CryptoError> {
+        DualRegevIBE::check_security(self)
+    }
+
+    /// Checks the parameters for correctness using [`DualRegevIBE::check_correctness`].
+    ///
+    /// Returns an empty result or an error describing the violated requirement.
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] of type [`InvalidParameters`](CryptoError::InvalidParameters)
+    ///     if at least one parameter was not chosen appropriately for a
+    ///     correct Dual Regev IBE instance.
+    fn check_correctness(&self) -> Result<(), CryptoError> {
+        DualRegevIBE::check_correctness(self)
+    }
+}
+
+#[cfg(test)]
+mod test_dual_regev_ibe {
+    use super::{storage_key, DualRegevIBE};
+    use crate::{
+        construction::identity_based_encryption::IBEScheme, error::CryptoError,
+        sample::g_trapdoor::basis_quality::frobenius_norm,
+    };
+    use qfall_math::{
+        integer::{MatZ, Z},
+        integer_mod_q::{MatZq, Zq},
+        rational::Q,
+        traits::{GetEntry, GetNumColumns, GetNumRows, SetEntry},
+    };
+    use std::time::{Duration, SystemTime};
+
+    /// Checks whether `new` is available for types implementing [`Into<Z>`].
+    #[test]
+    fn new_availability() {
+        let _ = DualRegevIBE::new(2u8, 2u16, 2u32, 2u64);
+        let _ = DualRegevIBE::new(2u16, 2u64, 2i32, 2i64);
+        let _ = DualRegevIBE::new(2i16, 2i64, 2u32, 2u8);
+        let _ = DualRegevIBE::new(Z::from(2), Z::from(2), 2u8, 2i8);
+    }
+
+    /// Checks that `ibe` passes its security check and that
+    /// the full-cycle of setup, extract, enc, dec works for messages 0 and 1.
+    fn assert_full_cycle(ibe: DualRegevIBE) {
+        assert!(ibe.check_security().is_ok());
+
+        let id = String::from("Hello World!");
+        let (pk, sk) = ibe.setup();
+        let id_sk = ibe.extract(&pk, &sk, &id).unwrap();
+        for msg in [Z::ZERO, Z::ONE] {
+            let cipher = ibe.enc(&pk, &id, &msg);
+            assert_eq!(msg, ibe.dec(&id_sk, &cipher).unwrap());
+        }
+    }
+
+    /// Ensures that the full-cycle works for all primitive integer types passed as `n`
+    /// and [`Z`], `&Z` and `Modulus` passed as `q`,
+    /// s.t. changes of type conversions can not silently break the scheme.
+    #[test]
+    fn full_cycle_type_conversions() {
+        let default = DualRegevIBE::default();
+        let q_z = Z::from(&default.dual_regev.q);
+        let q_modulus = default.dual_regev.q.clone();
+        let r = default.psf.s.clone();
+        let alpha = default.dual_regev.alpha.clone();
+
+        assert_full_cycle(DualRegevIBE::new(4u8, &q_z, &r, &alpha));
+        assert_full_cycle(DualRegevIBE::new(4u16, &q_z, &r, &alpha));
+        assert_full_cycle(DualRegevIBE::new(4u32, &q_z, &r, &alpha));
+        assert_full_cycle(DualRegevIBE::new(4u64, &q_z, &r, &alpha));
+        assert_full_cycle(DualRegevIBE::new(4i8, &q_z, &r, &alpha));
+        assert_full_cycle(DualRegevIBE::new(4i16, &q_z, &r, &alpha));
+        assert_full_cycle(DualRegevIBE::new(4i32, &q_z, &r, &alpha));
+        assert_full_cycle(DualRegevIBE::new(4i64, &q_z, &r, &alpha));
+
+        assert_full_cycle(DualRegevIBE::new(Z::from(4), q_z.clone(), &r, &alpha));
+        assert_full_cycle(DualRegevIBE::new(&Z::from(4), &q_z, &r, &alpha));
+        assert_full_cycle(DualRegevIBE::new(4, q_modulus.clone(), &r, &alpha));
+        assert_full_cycle(DualRegevIBE::new(4, &q_modulus, &r, &alpha));
+    }
+
+    /// Ensures that `new_from_n` is available for types implementing [`Into<Z>`].
     #[test]
     #[allow(clippy::needless_borrows_for_generic_args)]
     fn availability() {
-        let _ = DualRegevIBE::new_from_n(4u8);
-        let _ = DualRegevIBE::new_from_n(4u16);
-        let _ = DualRegevIBE::new_from_n(4u32);
-        let _ = DualRegevIBE::new_from_n(4u64);
-        let _ = DualRegevIBE::new_from_n(4i8);
-        let _ = DualRegevIBE::new_from_n(4i16);
-        let _ = DualRegevIBE::new_from_n(4i32);
-        let _ = DualRegevIBE::new_from_n(4i64);
-        let _ = DualRegevIBE::new_from_n(Z::from(4));
-        let _ = DualRegevIBE::new_from_n(&Z::from(4));
+        let _ = DualRegevIBE::new_from_n(4u8).unwrap();
+        let _ = DualRegevIBE::new_from_n(4u16).unwrap();
+        let _ = DualRegevIBE::new_from_n(4u32).unwrap();
+        let _ = DualRegevIBE::new_from_n(4u64).unwrap();
+        let _ = DualRegevIBE::new_from_n(4i8).unwrap();
+        let _ = DualRegevIBE::new_from_n(4i16).unwrap();
+        let _ = DualRegevIBE::new_from_n(4i32).unwrap();
+        let _ = DualRegevIBE::new_from_n(4i64).unwrap();
+        let _ = DualRegevIBE::new_from_n(Z::from(4)).unwrap();
+        let _ = DualRegevIBE::new_from_n(&Z::from(4)).unwrap();
     }
 
     /// Checks whether `new_from_n` returns an error for invalid input n.
     #[test]
-    #[should_panic]
     fn invalid_n() {
-        DualRegevIBE::new_from_n(1);
+        assert!(matches!(
+            DualRegevIBE::new_from_n(1),
+            Err(CryptoError::InvalidParameters { .. })
+        ));
+    }
+
+    /// Ensures that the spectral norm of the master secret key is positive and
+    /// bounded by its Frobenius norm.
+    #[test]
+    fn master_key_spectral_norm() {
+        let ibe = DualRegevIBE::default();
+        let (_, sk) = ibe.setup();
+
+        let norm = DualRegevIBE::master_key_spectral_norm(&sk);
+
+        assert!(norm > Q::ONE);
+        assert!(norm <= frobenius_norm(&sk.0));
+    }
+
+    /// Ensures that the orthogonality defect of the master secret key is at least 1.
+    #[test]
+    fn basis_quality() {
+        let ibe = DualRegevIBE::default();
+        let (_, sk) = ibe.setup();
+
+        let defect = DualRegevIBE::basis_quality(&sk.0);
+
+        assert!(defect >= Q::from(0.999999));
+    }
+
+    /// Ensures that the default parameters have a positive noise margin.
+    #[test]
+    fn noise_margin_default() {
+        let ibe = DualRegevIBE::default();
+
+        assert!(ibe.noise_margin() > Q::ZERO);
+        assert!(ibe.expected_decryption_noise() > Q::ZERO);
+        assert!(ibe.check_correctness().is_ok());
+    }
+
+    /// Ensures that `check_correctness` passes for α exactly at the bound
+    /// `1/(2 * r * sqrt(m + 1) * log(n))` and fails for α slightly above it.
+    #[test]
+    fn check_correctness_alpha_boundary() {
+        let default = DualRegevIBE::default();
+        let bound = default.max_alpha();
+        let at_bound = DualRegevIBE::new(
+            &default.dual_regev.n,
+            &default.dual_regev.q,
+            &default.psf.s,
+            &bound,
+        );
+        let above_bound = DualRegevIBE::new(
+            &default.dual_regev.n,
+            &default.dual_regev.q,
+            &default.psf.s,
+            &bound * Q::from((1_000_001, 1_000_000)),
+        );
+
+        assert_eq!(default.dual_regev.alpha, bound);
+        assert!(at_bound.check_correctness().is_ok());
+        assert!(above_bound.check_correctness().is_err());
+    }
+
+    /// Ensures that the bound is smaller than the bound `log(n)/(2 * r * sqrt(m + 1))`,
+    /// which was checked previously due to a missing pair of parentheses.
+    #[test]
+    fn check_correctness_divides_by_log_n() {
+        let default = DualRegevIBE::default();
+        let previous_bound = 1 / (2 * &default.psf.s * (&default.dual_regev.m + Z::ONE).sqrt())
+            * default.dual_regev.n.log(2).unwrap();
+        let ibe = DualRegevIBE::new(
+            &default.dual_regev.n,
+            &default.dual_regev.q,
+            &default.psf.s,
+            &previous_bound,
+        );
+
+        assert!(ibe.check_correctness().is_err());
+    }
+
+    /// Ensures that a very large α results in a negative noise margin
+    /// and that `check_correctness` fails.
+    #[test]
+    fn noise_margin_large_alpha() {
+        let default = DualRegevIBE::default();
+        let ibe = DualRegevIBE::new(
+            &default.dual_regev.n,
+            &default.dual_regev.q,
+            &default.psf.s,
+            1,
+        );
+
+        assert!(ibe.noise_margin() < Q::ZERO);
+        assert!(ibe.expected_decryption_noise() > Q::from(&ibe.dual_regev.q) / Q::from(4));
+        assert!(ibe.check_correctness().is_err());
     }
 
     /// Checks whether the full-cycle of gen, extract, enc, dec works properly
@@ -468,12 +1702,12 @@ mod test_dual_regev_ibe {
     fn cycle_zero_default() {
         let msg = Z::ZERO;
         let id = String::from("Hello World!");
-        let mut cryptosystem = DualRegevIBE::default();
+        let cryptosystem = DualRegevIBE::default();
 
         let (pk, sk) = cryptosystem.setup();
-        let id_sk = cryptosystem.extract(&pk, &sk, &id);
+        let id_sk = cryptosystem.extract(&pk, &sk, &id).unwrap();
         let cipher = cryptosystem.enc(&pk, &id, &msg);
-        let m = cryptosystem.dec(&id_sk, &cipher);
+        let m = cryptosystem.dec(&id_sk, &cipher).unwrap();
 
         assert_eq!(msg, m)
     }
@@ -484,12 +1718,12 @@ mod test_dual_regev_ibe {
     fn cycle_one_default() {
         let msg = Z::ONE;
         let id = String::from("Hello World!");
-        let mut cryptosystem = DualRegevIBE::default();
+        let cryptosystem = DualRegevIBE::default();
 
         let (pk, sk) = cryptosystem.setup();
-        let id_sk = cryptosystem.extract(&pk, &sk, &id);
+        let id_sk = cryptosystem.extract(&pk, &sk, &id).unwrap();
         let cipher = cryptosystem.enc(&pk, &id, &msg);
-        let m = cryptosystem.dec(&id_sk, &cipher);
+        let m = cryptosystem.dec(&id_sk, &cipher).unwrap();
 
         assert_eq!(msg, m)
     }
@@ -500,12 +1734,12 @@ mod test_dual_regev_ibe {
     fn cycle_zero_small_n() {
         let msg = Z::ZERO;
         let id = String::from("Hel213lo World!");
-        let mut cryptosystem = DualRegevIBE::new_from_n(5);
+        let cryptosystem = DualRegevIBE::new_from_n(5).unwrap();
 
         let (pk, sk) = cryptosystem.setup();
-        let id_sk = cryptosystem.extract(&pk, &sk, &id);
+        let id_sk = cryptosystem.extract(&pk, &sk, &id).unwrap();
         let cipher = cryptosystem.enc(&pk, &id, &msg);
-        let m = cryptosystem.dec(&id_sk, &cipher);
+        let m = cryptosystem.dec(&id_sk, &cipher).unwrap();
         assert_eq!(msg, m);
     }
 
@@ -515,12 +1749,12 @@ mod test_dual_regev_ibe {
     fn cycle_one_small_n() {
         let msg = Z::ONE;
         let id = String::from("Hel213lo World!");
-        let mut cryptosystem = DualRegevIBE::new_from_n(5);
+        let cryptosystem = DualRegevIBE::new_from_n(5).unwrap();
 
         let (pk, sk) = cryptosystem.setup();
-        let id_sk = cryptosystem.extract(&pk, &sk, &id);
+        let id_sk = cryptosystem.extract(&pk, &sk, &id).unwrap();
         let cipher = cryptosystem.enc(&pk, &id, &msg);
-        let m = cryptosystem.dec(&id_sk, &cipher);
+        let m = cryptosystem.dec(&id_sk, &cipher).unwrap();
         assert_eq!(msg, m);
     }
 
@@ -530,17 +1764,17 @@ mod test_dual_regev_ibe {
         for i in 1..=5 {
             let msg = Z::ONE;
             let id = format!("Hello World!{i}");
-            let mut cryptosystem = DualRegevIBE::default();
+            let cryptosystem = DualRegevIBE::default();
 
             cryptosystem.check_security().unwrap();
             cryptosystem.check_correctness().unwrap();
 
             let (pk, sk) = cryptosystem.setup();
 
-            let id_sk = cryptosystem.extract(&pk, &sk, &id);
+            let id_sk = cryptosystem.extract(&pk, &sk, &id).unwrap();
             for _j in 1..=100 {
                 let cipher = cryptosystem.enc(&pk, &id, &msg);
-                let m = cryptosystem.dec(&id_sk, &cipher);
+                let m = cryptosystem.dec(&id_sk, &cipher).unwrap();
 
                 assert_eq!(msg, m);
             }
@@ -551,11 +1785,11 @@ mod test_dual_regev_ibe {
     #[test]
     fn extract_storage_same_identity_mk_pk() {
         let id = "Hello World!".to_string();
-        let mut cryptosystem = DualRegevIBE::default();
+        let cryptosystem = DualRegevIBE::default();
         let (pk, sk) = cryptosystem.setup();
 
-        let id_sk_1 = cryptosystem.extract(&pk, &sk, &id);
-        let id_sk_2 = cryptosystem.extract(&pk, &sk, &id);
+        let id_sk_1 = cryptosystem.extract(&pk, &sk, &id).unwrap();
+        let id_sk_2 = cryptosystem.extract(&pk, &sk, &id).unwrap();
 
         assert_eq!(id_sk_1, id_sk_2)
     }
@@ -565,13 +1799,580 @@ mod test_dual_regev_ibe {
     #[test]
     fn extract_storage_same_identity_different_mk_pk() {
         let id = "Hello World!".to_string();
-        let mut cryptosystem = DualRegevIBE::default();
+        let cryptosystem = DualRegevIBE::default();
         let (pk_1, sk_1) = cryptosystem.setup();
         let (pk_2, sk_2) = cryptosystem.setup();
 
-        let id_sk_1 = cryptosystem.extract(&pk_1, &sk_1, &id);
-        let id_sk_2 = cryptosystem.extract(&pk_2, &sk_2, &id);
+        let id_sk_1 = cryptosystem.extract(&pk_1, &sk_1, &id).unwrap();
+        let id_sk_2 = cryptosystem.extract(&pk_2, &sk_2, &id).unwrap();
 
         assert_ne!(id_sk_1, id_sk_2)
     }
+
+    /// Ensures that secret keys can be extracted concurrently from several threads
+    /// and that all threads obtain the same secret key for the same identity.
+    #[test]
+    fn extract_concurrently() {
+        let cryptosystem = DualRegevIBE::default();
+        let (pk, sk) = cryptosystem.setup();
+        let identities: Vec<String> = (0..4).map(|i| format!("identity {i}")).collect();
+
+        std::thread::scope(|scope| {
+            for _ in 0..8 {
+                scope.spawn(|| {
+                    for id in &identities {
+                        let id_sk = cryptosystem.extract(&pk, &sk, id).unwrap();
+                        let cipher = cryptosystem.enc(&pk, id, 1);
+                        assert_eq!(Z::ONE, cryptosystem.dec(&id_sk, &cipher).unwrap());
+                    }
+                });
+            }
+        });
+
+        assert_eq!(4, cryptosystem.storage.len());
+        for id in &identities {
+            assert_eq!(
+                cryptosystem.extract(&pk, &sk, id).unwrap(),
+                cryptosystem.extract(&pk, &sk, id).unwrap()
+            );
+        }
+    }
+
+    /// Ensures that `extract` rejects master public keys of other parameters
+    /// and master secret keys, which are no trapdoor of the master public key.
+    #[test]
+    fn extract_invalid() {
+        let cryptosystem = DualRegevIBE::default();
+        let (pk, sk) = cryptosystem.setup();
+        let (pk_2, _) = cryptosystem.setup();
+        let id = String::from("Hello World!");
+        let other_pk = MatZq::new(
+            pk.get_num_rows() + 1,
+            pk.get_num_columns(),
+            &cryptosystem.dual_regev.q,
+        );
+
+        assert!(matches!(
+            cryptosystem.extract(&other_pk, &sk, &id),
+            Err(CryptoError::IncompatibleParameters)
+        ));
+        assert!(matches!(
+            cryptosystem.extract(&pk_2, &sk, &id),
+            Err(CryptoError::KeyExtractionFailed)
+        ));
+        assert!(cryptosystem.storage.is_empty());
+    }
+
+    /// Ensures that `extract` replaces an invalid secret key in the storage
+    /// by a valid one instead of returning it.
+    #[test]
+    fn extract_replaces_invalid() {
+        let cryptosystem = DualRegevIBE::default();
+        let (pk, sk) = cryptosystem.setup();
+        let id = String::from("Hello World!");
+        let invalid_sk = MatZ::new(pk.get_num_columns(), 1);
+        cryptosystem.storage.insert(
+            storage_key(&pk, &id),
+            (invalid_sk.clone(), SystemTime::now()),
+        );
+
+        let id_sk = cryptosystem.extract(&pk, &sk, &id).unwrap();
+
+        assert_ne!(invalid_sk, id_sk);
+        assert_eq!(id_sk, cryptosystem.extract(&pk, &sk, &id).unwrap());
+    }
+
+    /// Ensures that `dec` rejects secret keys and ciphers with incompatible dimensions.
+    #[test]
+    fn dec_incompatible() {
+        let cryptosystem = DualRegevIBE::default();
+        let (pk, sk) = cryptosystem.setup();
+        let id = String::from("Hello World!");
+        let id_sk = cryptosystem.extract(&pk, &sk, &id).unwrap();
+        let cipher = cryptosystem.enc(&pk, &id, 1);
+        let too_short = MatZq::new(cipher.get_num_rows() - 1, 1, &cryptosystem.dual_regev.q);
+
+        assert!(matches!(
+            cryptosystem.dec(&id_sk, &too_short),
+            Err(CryptoError::IncompatibleParameters)
+        ));
+        assert!(matches!(
+            cryptosystem.dec_verified(&id_sk, &too_short),
+            Err(CryptoError::IncompatibleParameters)
+        ));
+    }
+
+    /// Ensures that the secret keys extracted by `extract_batch` are identical to
+    /// the ones returned by `extract`, independent of which is called first,
+    /// and that they decrypt correctly.
+    #[test]
+    fn extract_batch_matches_extract() {
+        let cryptosystem = DualRegevIBE::default();
+        let (pk, sk) = cryptosystem.setup();
+        let identities: Vec<String> = (0..6).map(|i| format!("identity {i}")).collect();
+        let serial_sk = cryptosystem.extract(&pk, &sk, &identities[0]).unwrap();
+
+        let id_sks = cryptosystem.extract_batch(&pk, &sk, &identities).unwrap();
+
+        assert_eq!(6, id_sks.len());
+        assert_eq!(serial_sk, id_sks[0]);
+        for (id, id_sk) in identities.iter().zip(&id_sks) {
+            assert_eq!(&cryptosystem.extract(&pk, &sk, id).unwrap(), id_sk);
+
+            let cipher = cryptosystem.enc(&pk, id, 1);
+            assert_eq!(Z::ONE, cryptosystem.dec(id_sk, &cipher).unwrap());
+        }
+    }
+
+    /// Ensures that an identity occurring several times in a batch obtains
+    /// the same secret key and is stored only once.
+    #[test]
+    fn extract_batch_duplicate_identities() {
+        let cryptosystem = DualRegevIBE::default();
+        let (pk, sk) = cryptosystem.setup();
+        let identities = vec![String::from("Alice"); 8];
+
+        let id_sks = cryptosystem.extract_batch(&pk, &sk, &identities).unwrap();
+
+        assert_eq!(1, cryptosystem.storage.len());
+        assert!(id_sks.iter().all(|id_sk| id_sk == &id_sks[0]));
+    }
+
+    /// Ensures that the decryption failure probability is small for the [`Default`]
+    /// parameters and close to `1/2` for artificially large α.
+    #[test]
+    fn decryption_failure_prob() {
+        let cryptosystem = DualRegevIBE::default();
+        let large_alpha = DualRegevIBE::new(4, 54983, 14, 10);
+
+        assert!(cryptosystem.decryption_failure_prob() < 0.5_f64.powi(20));
+        assert!((large_alpha.decryption_failure_prob() - 0.5).abs() < 1e-6);
+    }
+
+    /// Ensures that the storage is restored after a serde round-trip
+    /// and that storages with keys of previous versions are migrated.
+    #[test]
+    fn storage_serde_roundtrip() {
+        let id = String::from("Hello World!");
+        let cryptosystem = DualRegevIBE::default();
+        let (pk, sk) = cryptosystem.setup();
+        let id_sk = cryptosystem.extract(&pk, &sk, &id).unwrap();
+
+        let json = serde_json::to_string(&cryptosystem).unwrap();
+        let restored: DualRegevIBE = serde_json::from_str(&json).unwrap();
+
+        let mut legacy = serde_json::to_value(&cryptosystem).unwrap();
+        let mut legacy_storage = serde_json::Map::new();
+        legacy_storage.insert(
+            format!("{pk} {} {} {id}", sk.0, sk.1),
+            serde_json::to_value(&id_sk).unwrap(),
+        );
+        legacy["storage"] = legacy_storage.into();
+        let migrated: DualRegevIBE = serde_json::from_value(legacy).unwrap();
+
+        assert_eq!(id_sk, restored.extract(&pk, &sk, &id).unwrap());
+        assert_eq!(1, migrated.storage.len());
+        assert_eq!(id_sk, migrated.extract(&pk, &sk, &id).unwrap());
+    }
+
+    /// Ensures that every ciphertext of a batch encryption decrypts correctly
+    /// under the secret key of its identity.
+    #[test]
+    fn enc_batch_same_msg_cycle() {
+        let cryptosystem = DualRegevIBE::default();
+        let (pk, sk) = cryptosystem.setup();
+        let identities: Vec<String> = (0..5).map(|i| format!("identity {i}")).collect();
+
+        for msg in [Z::ZERO, Z::ONE] {
+            let ciphers = cryptosystem.enc_batch_same_msg(&pk, &identities, &msg);
+
+            assert_eq!(identities.len(), ciphers.len());
+            for (id, cipher) in identities.iter().zip(ciphers.iter()) {
+                let id_sk = cryptosystem.extract(&pk, &sk, id).unwrap();
+                assert_eq!(msg, cryptosystem.dec(&id_sk, cipher).unwrap());
+            }
+        }
+    }
+
+    /// Ensures that all ciphertexts of a batch share the entries depending on `A`
+    /// and that an empty list of identities yields no ciphertexts.
+    #[test]
+    fn enc_batch_same_msg_shared_randomness() {
+        let cryptosystem = DualRegevIBE::default();
+        let (pk, _) = cryptosystem.setup();
+        let identities = [String::from("Alice"), String::from("Bob")];
+
+        let ciphers = cryptosystem.enc_batch_same_msg(&pk, &identities, 1);
+        let m = pk.get_num_columns();
+
+        for i in 0..m {
+            let entry_0: Zq = ciphers[0].get_entry(i, 0).unwrap();
+            let entry_1: Zq = ciphers[1].get_entry(i, 0).unwrap();
+            assert_eq!(entry_0, entry_1);
+        }
+        assert!(cryptosystem.enc_batch_same_msg(&pk, &[], 1).is_empty());
+    }
+
+    /// Ensures that rerandomized ciphertexts decrypt to the original message.
+    #[test]
+    fn rerandomize_cipher_cycle() {
+        let cryptosystem = DualRegevIBE::default();
+        let (pk, sk) = cryptosystem.setup();
+        let id = String::from("Hello World!");
+        let id_sk = cryptosystem.extract(&pk, &sk, &id).unwrap();
+
+        for msg in [Z::ZERO, Z::ONE] {
+            let cipher = cryptosystem.enc(&pk, &id, &msg);
+
+            let rerandomized = cryptosystem.rerandomize_cipher(&pk, &id, &cipher);
+
+            assert_eq!(msg, cryptosystem.dec(&id_sk, &rerandomized).unwrap());
+        }
+    }
+
+    /// Ensures that two rerandomizations of the same ciphertext differ
+    /// from each other and from the original ciphertext.
+    #[test]
+    fn rerandomize_cipher_distinct() {
+        let cryptosystem = DualRegevIBE::default();
+        let (pk, _) = cryptosystem.setup();
+        let id = String::from("Hello World!");
+        let cipher = cryptosystem.enc(&pk, &id, 1);
+
+        let rerandomized_0 = cryptosystem.rerandomize_cipher(&pk, &id, &cipher);
+        let rerandomized_1 = cryptosystem.rerandomize_cipher(&pk, &id, &cipher);
+
+        assert_ne!(rerandomized_0, rerandomized_1);
+        assert_ne!(cipher, rerandomized_0);
+    }
+
+    /// Ensures that ciphertexts bound to associated data decrypt to the original message.
+    #[test]
+    fn enc_with_ad_cycle() {
+        let cryptosystem = DualRegevIBE::default();
+        let (pk, sk) = cryptosystem.setup();
+        let ad = b"sender: Bob, time: 1700000000";
+        let id_sk = cryptosystem
+            .extract(&pk, &sk, &DualRegevIBE::identity_with_ad("Alice", ad))
+            .unwrap();
+
+        for msg in [Z::ZERO, Z::ONE] {
+            let cipher = cryptosystem.enc_with_ad(&pk, "Alice", &msg, ad);
+
+            assert_eq!(Some(msg), cryptosystem.dec_with_ad(&id_sk, &cipher, ad));
+        }
+    }
+
+    /// Ensures that decryption fails if the associated data is tampered with.
+    #[test]
+    fn dec_with_ad_tampered() {
+        let cryptosystem = DualRegevIBE::default();
+        let (pk, sk) = cryptosystem.setup();
+        let ad = b"sender: Bob";
+        let id_sk = cryptosystem
+            .extract(&pk, &sk, &DualRegevIBE::identity_with_ad("Alice", ad))
+            .unwrap();
+        let cipher = cryptosystem.enc_with_ad(&pk, "Alice", 1, ad);
+
+        assert_eq!(
+            None,
+            cryptosystem.dec_with_ad(&id_sk, &cipher, b"sender: Eve")
+        );
+        assert_eq!(None, cryptosystem.dec_with_ad(&id_sk, &cipher, b""));
+    }
+
+    /// Ensures that ciphers not generated by `enc_with_ad` are rejected instead of panicking.
+    #[test]
+    fn dec_with_ad_malformed() {
+        let cryptosystem = DualRegevIBE::default();
+        let (pk, sk) = cryptosystem.setup();
+        let ad = b"sender: Bob";
+        let id_sk = cryptosystem
+            .extract(&pk, &sk, &DualRegevIBE::identity_with_ad("Alice", ad))
+            .unwrap();
+        let cipher = cryptosystem.enc(&pk, &DualRegevIBE::identity_with_ad("Alice", ad), 1);
+
+        assert_eq!(None, cryptosystem.dec_with_ad(&id_sk, &cipher, ad));
+    }
+
+    /// Ensures that a ciphertext detached from its associated data can not be decrypted
+    /// with the secret key of the identity bound to different associated data.
+    #[test]
+    fn dec_with_ad_detached() {
+        let cryptosystem = DualRegevIBE::default();
+        let (pk, sk) = cryptosystem.setup();
+        let id_sk = cryptosystem
+            .extract(&pk, &sk, &DualRegevIBE::identity_with_ad("Alice", b"ad 1"))
+            .unwrap();
+        let plain_sk = cryptosystem
+            .extract(&pk, &sk, &String::from("Alice"))
+            .unwrap();
+        let cipher = cryptosystem.enc_with_ad(&pk, "Alice", 1, b"ad 0");
+
+        assert_eq!(None, cryptosystem.dec_with_ad(&id_sk, &cipher, b"ad 1"));
+        assert_eq!(None, cryptosystem.dec_with_ad(&plain_sk, &cipher, b"ad 0"));
+    }
+
+    /// Ensures that the identity bound to associated data depends on the associated data.
+    #[test]
+    fn identity_with_ad() {
+        let id = DualRegevIBE::identity_with_ad("Alice", b"");
+
+        assert_eq!(
+            "Alice||e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+            id
+        );
+        assert_ne!(id, DualRegevIBE::identity_with_ad("Alice", b"0"));
+    }
+
+    /// Ensures that fresh ciphertexts pass the noise check of `dec_verified`.
+    #[test]
+    fn dec_verified_valid() {
+        let cryptosystem = DualRegevIBE::default();
+        let (pk, sk) = cryptosystem.setup();
+        let id = String::from("Alice");
+        let id_sk = cryptosystem.extract(&pk, &sk, &id).unwrap();
+
+        for _ in 0..10 {
+            for msg in [Z::ZERO, Z::ONE] {
+                let cipher = cryptosystem.enc(&pk, &id, &msg);
+
+                assert_eq!(msg, cryptosystem.dec_verified(&id_sk, &cipher).unwrap());
+            }
+        }
+    }
+
+    /// Ensures that ciphertexts, whose raw inner product is shifted to
+    /// the decision boundary `q/4`, are rejected by `dec_verified`.
+    #[test]
+    fn dec_verified_corrupted() {
+        let cryptosystem = DualRegevIBE::default();
+        let (pk, sk) = cryptosystem.setup();
+        let id = String::from("Alice");
+        let id_sk = cryptosystem.extract(&pk, &sk, &id).unwrap();
+        let q_quarter = Z::from(&cryptosystem.dual_regev.q).div_floor(4);
+
+        for msg in [Z::ZERO, Z::ONE] {
+            let mut cipher = cryptosystem.enc(&pk, &id, &msg);
+            let raw = cryptosystem.raw_decryption(&id_sk, &cipher);
+            let last: Zq = cipher.get_entry(-1, 0).unwrap();
+            cipher.set_entry(-1, 0, last + (&q_quarter - raw)).unwrap();
+
+            assert!(matches!(
+                cryptosystem.dec_verified(&id_sk, &cipher),
+                Err(CryptoError::DecryptionFailed)
+            ));
+        }
+    }
+
+    /// Ensures that uniformly random ciphertexts are rejected by `dec_verified`
+    /// with noticeable probability.
+    #[test]
+    fn dec_verified_random() {
+        let cryptosystem = DualRegevIBE::default();
+        let (pk, sk) = cryptosystem.setup();
+        let id = String::from("Alice");
+        let id_sk = cryptosystem.extract(&pk, &sk, &id).unwrap();
+        let cipher = cryptosystem.enc(&pk, &id, 0);
+
+        let rejected = (0..50)
+            .filter(|_| {
+                let random =
+                    MatZq::sample_uniform(cipher.get_num_rows(), 1, &cryptosystem.dual_regev.q);
+                cryptosystem.dec_verified(&id_sk, &random).is_err()
+            })
+            .count();
+
+        assert!(rejected > 0);
+    }
+
+    /// Ensures that `extract_with_ttl` returns the cached secret key within the TTL
+    /// and recomputes it afterwards.
+    #[test]
+    fn extract_with_ttl() {
+        let cryptosystem = DualRegevIBE::default();
+        let (pk, sk) = cryptosystem.setup();
+        let id = String::from("Alice");
+
+        let id_sk = cryptosystem.extract(&pk, &sk, &id).unwrap();
+        let cached = cryptosystem
+            .extract_with_ttl(&pk, &sk, &id, Duration::from_secs(3600))
+            .unwrap();
+        std::thread::sleep(Duration::from_millis(20));
+        let recomputed = cryptosystem
+            .extract_with_ttl(&pk, &sk, &id, Duration::from_millis(10))
+            .unwrap();
+
+        assert_eq!(id_sk, cached);
+        assert_ne!(id_sk, recomputed);
+        assert_eq!(1, cryptosystem.storage.len());
+        assert_eq!(recomputed, cryptosystem.extract(&pk, &sk, &id).unwrap());
+        let cipher = cryptosystem.enc(&pk, &id, 1);
+        assert_eq!(Z::ONE, cryptosystem.dec(&recomputed, &cipher).unwrap());
+    }
+
+    /// Ensures that `extract_with_ttl` neither returns nor caches secret keys
+    /// computed with a master secret key, which is no trapdoor of the master public key.
+    #[test]
+    fn extract_with_ttl_invalid() {
+        let cryptosystem = DualRegevIBE::default();
+        let (pk, _) = cryptosystem.setup();
+        let (_, sk_2) = cryptosystem.setup();
+        let id = String::from("Alice");
+
+        assert!(matches!(
+            cryptosystem.extract_with_ttl(&pk, &sk_2, &id, Duration::from_secs(3600)),
+            Err(CryptoError::KeyExtractionFailed)
+        ));
+        assert!(cryptosystem.storage.is_empty());
+    }
+
+    /// Ensures that `evict_expired` removes exactly the entries older than the TTL
+    /// and that evicted secret keys are recomputed on the next extraction.
+    #[test]
+    fn evict_expired() {
+        let mut cryptosystem = DualRegevIBE::default();
+        let (pk, sk) = cryptosystem.setup();
+        let (id_0, id_1, id_2) = (
+            String::from("Alice"),
+            String::from("Bob"),
+            String::from("Carol"),
+        );
+
+        let id_sk_0 = cryptosystem.extract(&pk, &sk, &id_0).unwrap();
+        let _ = cryptosystem.extract(&pk, &sk, &id_1).unwrap();
+        std::thread::sleep(Duration::from_millis(50));
+        let id_sk_2 = cryptosystem.extract(&pk, &sk, &id_2).unwrap();
+
+        assert_eq!(0, cryptosystem.evict_expired(Duration::from_secs(3600)));
+        assert_eq!(2, cryptosystem.evict_expired(Duration::from_millis(25)));
+        assert_eq!(1, cryptosystem.storage.len());
+        assert_eq!(id_sk_2, cryptosystem.extract(&pk, &sk, &id_2).unwrap());
+
+        let recomputed = cryptosystem.extract(&pk, &sk, &id_0).unwrap();
+        assert_ne!(id_sk_0, recomputed);
+        assert_eq!(2, cryptosystem.storage.len());
+        let cipher = cryptosystem.enc(&pk, &id_0, 1);
+        assert_eq!(Z::ONE, cryptosystem.dec(&recomputed, &cipher).unwrap());
+    }
+
+    /// Ensures that `enc_vec` and `dec_vec` round-trip messages of every
+    /// length from `1` to `n` and that other identities can not decrypt them.
+    #[test]
+    fn enc_vec_round_trip() {
+        let cryptosystem = DualRegevIBE::default();
+        let (pk, sk) = cryptosystem.setup();
+        let id = String::from("identity");
+        let id_sk = cryptosystem.extract(&pk, &sk, &id).unwrap();
+        let n = i64::try_from(&cryptosystem.dual_regev.n).unwrap();
+
+        for length in 1..=n {
+            let msg = MatZ::sample_uniform(length, 1, 0, 2).unwrap();
+
+            let cipher = cryptosystem.enc_vec(&pk, &id, &msg);
+
+            assert_eq!(length, cipher.get_num_columns());
+            assert_eq!(msg, cryptosystem.dec_vec(&id_sk, &cipher));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_delegate {
+    use super::DualRegevIBE;
+    use crate::construction::identity_based_encryption::IBEScheme;
+    use qfall_math::{
+        integer::Z,
+        integer_mod_q::MatZq,
+        traits::{GetNumColumns, GetNumRows},
+    };
+
+    /// Ensures that keys extracted with a delegated key decrypt ciphers
+    /// encrypted for their identity under the delegated public key.
+    #[test]
+    fn cycle() {
+        let ibe = DualRegevIBE::default();
+        let (pk, sk) = ibe.setup();
+        let (delegated_pk, delegated_sk) = ibe.delegate(&pk, &sk, "org");
+
+        for id in ["alice", "bob"] {
+            let id = String::from(id);
+            let sk_id = ibe
+                .extract_delegated(&delegated_pk, &delegated_sk, &id)
+                .unwrap();
+
+            for msg in [Z::ZERO, Z::ONE] {
+                let cipher = ibe.enc_delegated(&delegated_pk, &id, &msg);
+                assert_eq!(msg, ibe.dec(&sk_id, &cipher).unwrap());
+            }
+        }
+    }
+
+    /// Ensures that the delegated public key extends the master public key
+    /// and that the delegated key is a basis of its lattice.
+    #[test]
+    fn delegated_basis() {
+        let ibe = DualRegevIBE::default();
+        let (pk, sk) = ibe.setup();
+
+        let (delegated_pk, delegated_sk) = ibe.delegate(&pk, &sk, "org");
+
+        assert_eq!(
+            pk,
+            delegated_pk
+                .get_submatrix(0, pk.get_num_rows() - 1, 0, pk.get_num_columns() - 1)
+                .unwrap()
+        );
+        assert_eq!(delegated_pk.get_num_columns(), delegated_sk.get_num_rows());
+        let zero = MatZq::new(
+            pk.get_num_rows(),
+            delegated_sk.get_num_columns(),
+            pk.get_mod(),
+        );
+        assert_eq!(zero, &delegated_pk * &delegated_sk);
+        assert_ne!(delegated_pk, ibe.delegate(&pk, &sk, "other org").0);
+    }
+}
+
+#[cfg(all(test, feature = "logging"))]
+mod test_logging {
+    use super::DualRegevIBE;
+    use crate::construction::identity_based_encryption::IBEScheme;
+    use qfall_math::traits::GetNumRows;
+    use tracing_test::traced_test;
+
+    /// Ensures that setup, extract, enc and dec emit their spans
+    /// including the identity and the dimensions of the master public key.
+    #[test]
+    #[traced_test]
+    fn spans_emitted() {
+        let ibe = DualRegevIBE::default();
+        let (pk, sk) = ibe.setup();
+        let id = String::from("Alice");
+
+        let id_sk = ibe.extract(&pk, &sk, &id).unwrap();
+        let _ = ibe.extract(&pk, &sk, &id).unwrap();
+        let cipher = ibe.enc(&pk, &id, 1);
+        let _ = ibe.dec(&id_sk, &cipher).unwrap();
+
+        let rows = format!("rows={}", pk.get_num_rows());
+        assert!(logs_contain("setup{n=4}"));
+        assert!(logs_contain("generated master key pair"));
+        assert!(logs_contain("extract{identity=Alice"));
+        assert!(logs_contain(&rows));
+        assert!(logs_contain("cached=false"));
+        assert!(logs_contain("cached=true"));
+        assert!(logs_contain("enc{identity=Alice"));
+        assert!(logs_contain("dec{cipher_rows="));
+        assert!(logs_contain("decrypted cipher"));
+    }
+    /// Ensures that `new_from_n` emits the weaknesses of its parameters as warnings.
+    #[test]
+    #[traced_test]
+    fn weak_params_warned() {
+        let _ = DualRegevIBE::new_from_n(4).unwrap();
+
+        assert!(logs_contain("weak parameters"));
+        assert!(logs_contain("n < 64: insecure for production"));
+    }
 }
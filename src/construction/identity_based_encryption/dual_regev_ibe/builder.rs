@@ -0,0 +1,294 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains the [`DualRegevIBEBuilder`], which constructs
+//! [`DualRegevIBE`] instances with validated parameters.
+
+use super::DualRegevIBE;
+use crate::{
+    error::CryptoError,
+    params::{NistParams, NIST_LEVEL_1, NIST_LEVEL_3},
+};
+use qfall_math::{integer::Z, integer_mod_q::Modulus, rational::Q};
+
+/// Pre-validated parameter sets `(n, q, r, α)` for the supported bit security levels,
+/// where `sigma` is used as the Gaussian parameter `r` of the PSF.
+/// All sets pass [`DualRegevIBE::check_security`] and [`DualRegevIBE::check_correctness`]
+/// and reach the bit security level according to [`NistParams::estimate_security`].
+const SECURITY_LEVELS: [(u32, NistParams); 3] = [
+    (
+        80,
+        NistParams {
+            n: 576,
+            q: 24710377,
+            sigma: 171.0,
+            alpha: 0.00000186,
+        },
+    ),
+    (128, NIST_LEVEL_1),
+    (192, NIST_LEVEL_3),
+];
+
+/// This struct collects the parameters of a [`DualRegevIBE`] instance
+/// and only builds instances that pass [`DualRegevIBE::check_security`]
+/// and [`DualRegevIBE::check_correctness`].
+///
+/// Attributes:
+/// - `n`: specifies the security parameter
+/// - `q`: specifies the modulus
+/// - `r`: specifies the Gaussian parameter used by the PSF
+/// - `alpha`: specifies the Gaussian parameter of the LWE noise
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::construction::identity_based_encryption::DualRegevIBEBuilder;
+///
+/// let ibe = DualRegevIBEBuilder::default()
+///     .security_parameter(576)
+///     .modulus(24710377)
+///     .gaussian_r(171)
+///     .gaussian_alpha(0.00000186)
+///     .build()
+///     .unwrap();
+///
+/// let ibe_128 = DualRegevIBEBuilder::from_security_level(128)
+///     .unwrap()
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct DualRegevIBEBuilder {
+    n: Option<Z>,
+    q: Option<Modulus>,
+    r: Option<Q>,
+    alpha: Option<Q>,
+}
+
+impl DualRegevIBEBuilder {
+    /// Initializes a [`DualRegevIBEBuilder`] with the pre-validated parameter set
+    /// of the bit security level `bits`.
+    ///
+    /// Parameters:
+    /// - `bits`: specifies the bit security level, i.e. `80`, `128`, or `192`
+    ///
+    /// Returns a [`DualRegevIBEBuilder`] with all parameters set
+    /// or a [`CryptoError`] if `bits` is not supported.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::identity_based_encryption::DualRegevIBEBuilder;
+    ///
+    /// let builder = DualRegevIBEBuilder::from_security_level(80).unwrap();
+    /// ```
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] of type [`InvalidParameters`](CryptoError::InvalidParameters)
+    ///     if `bits` is not one of `80`, `128`, or `192`.
+    pub fn from_security_level(bits: u32) -> Result<Self, CryptoError> {
+        let (_, params) = SECURITY_LEVELS
+            .iter()
+            .find(|(level, _)| *level == bits)
+            .ok_or_else(|| CryptoError::InvalidParameters {
+                message: format!(
+                    "No parameter set for {bits}-bit security, only 80, 128, and 192 are supported."
+                ),
+            })?;
+
+        Ok(Self::default()
+            .security_parameter(params.n as u64)
+            .modulus(params.q)
+            .gaussian_r(params.sigma)
+            .gaussian_alpha(params.alpha))
+    }
+
+    /// Sets the security parameter `n`.
+    ///
+    /// Parameters:
+    /// - `n`: specifies the security parameter
+    ///
+    /// Returns the [`DualRegevIBEBuilder`] with `n` set.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::identity_based_encryption::DualRegevIBEBuilder;
+    ///
+    /// let builder = DualRegevIBEBuilder::default().security_parameter(576);
+    /// ```
+    pub fn security_parameter(mut self, n: impl Into<Z>) -> Self {
+        self.n = Some(n.into());
+        self
+    }
+
+    /// Sets the modulus `q`.
+    ///
+    /// Parameters:
+    /// - `q`: specifies the modulus
+    ///
+    /// Returns the [`DualRegevIBEBuilder`] with `q` set.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::identity_based_encryption::DualRegevIBEBuilder;
+    ///
+    /// let builder = DualRegevIBEBuilder::default().modulus(24710377);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `q <= 1`.
+    pub fn modulus(mut self, q: impl Into<Modulus>) -> Self {
+        self.q = Some(q.into());
+        self
+    }
+
+    /// Sets the Gaussian parameter `r` used by the PSF to sample secret keys.
+    ///
+    /// Parameters:
+    /// - `r`: specifies the Gaussian parameter of the PSF
+    ///
+    /// Returns the [`DualRegevIBEBuilder`] with `r` set.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::identity_based_encryption::DualRegevIBEBuilder;
+    ///
+    /// let builder = DualRegevIBEBuilder::default().gaussian_r(171);
+    /// ```
+    pub fn gaussian_r(mut self, r: impl Into<Q>) -> Self {
+        self.r = Some(r.into());
+        self
+    }
+
+    /// Sets the Gaussian parameter `alpha` of the LWE noise.
+    ///
+    /// Parameters:
+    /// - `alpha`: specifies the Gaussian parameter of the LWE noise
+    ///
+    /// Returns the [`DualRegevIBEBuilder`] with `alpha` set.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::identity_based_encryption::DualRegevIBEBuilder;
+    ///
+    /// let builder = DualRegevIBEBuilder::default().gaussian_alpha(0.00000186);
+    /// ```
+    pub fn gaussian_alpha(mut self, alpha: impl Into<Q>) -> Self {
+        self.alpha = Some(alpha.into());
+        self
+    }
+
+    /// Builds a [`DualRegevIBE`] instance with [`DualRegevIBE::new`] and checks it
+    /// using [`DualRegevIBE::check_security`] and [`DualRegevIBE::check_correctness`].
+    ///
+    /// Returns a secure and correct [`DualRegevIBE`] instance
+    /// or a [`CryptoError`] if a parameter is missing or the checks fail.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::identity_based_encryption::DualRegevIBEBuilder;
+    ///
+    /// let ibe = DualRegevIBEBuilder::from_security_level(128)
+    ///     .unwrap()
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] of type [`InvalidParameters`](CryptoError::InvalidParameters)
+    ///     if a parameter was not set, if `n <= 1`, or if the parameters are not
+    ///     secure or correct according to [`DualRegevIBE::check_security`]
+    ///     and [`DualRegevIBE::check_correctness`].
+    pub fn build(self) -> Result<DualRegevIBE, CryptoError> {
+        let missing = |name: &str| CryptoError::InvalidParameters {
+            message: format!("The parameter {name} was not set."),
+        };
+        let n = self.n.ok_or_else(|| missing("n"))?;
+        let q = self.q.ok_or_else(|| missing("q"))?;
+        let r = self.r.ok_or_else(|| missing("r"))?;
+        let alpha = self.alpha.ok_or_else(|| missing("alpha"))?;
+
+        // checked here, as the gadget parameters can not be initialized for n < 1
+        if n <= Z::ONE {
+            return Err(CryptoError::InvalidParameters {
+                message: String::from("n must be chosen bigger than 1."),
+            });
+        }
+
+        let ibe = DualRegevIBE::new(n, q, r, alpha);
+        ibe.check_security()?;
+        ibe.check_correctness()?;
+
+        Ok(ibe)
+    }
+}
+
+#[cfg(test)]
+mod test_dual_regev_ibe_builder {
+    use super::DualRegevIBEBuilder;
+    use qfall_math::{integer::Z, rational::Q};
+
+    /// Ensures that all pre-validated parameter sets pass both checks.
+    #[test]
+    fn security_levels_valid() {
+        for bits in [80, 128, 192] {
+            let ibe = DualRegevIBEBuilder::from_security_level(bits)
+                .unwrap()
+                .build()
+                .unwrap();
+
+            assert!(ibe.check_security().is_ok());
+            assert!(ibe.check_correctness().is_ok());
+        }
+    }
+
+    /// Ensures that the 128-bit parameter set matches the NIST level 1 parameters.
+    #[test]
+    fn security_level_128() {
+        let ibe = DualRegevIBEBuilder::from_security_level(128)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        assert_eq!(Z::from(864), ibe.dual_regev.n);
+        assert_eq!(Q::from(217), ibe.psf.s);
+    }
+
+    /// Ensures that unsupported security levels are rejected.
+    #[test]
+    fn unsupported_security_level() {
+        assert!(DualRegevIBEBuilder::from_security_level(100).is_err());
+        assert!(DualRegevIBEBuilder::from_security_level(256).is_err());
+    }
+
+    /// Ensures that the build fails if a parameter is missing.
+    #[test]
+    fn missing_parameter() {
+        let builder = DualRegevIBEBuilder::default()
+            .security_parameter(576)
+            .modulus(24710377)
+            .gaussian_r(171);
+
+        assert!(builder.build().is_err());
+    }
+
+    /// Ensures that invalid combinations of parameters are rejected.
+    #[test]
+    fn invalid_combinations() {
+        let valid = DualRegevIBEBuilder::from_security_level(80).unwrap();
+
+        // n <= 1
+        assert!(valid.clone().security_parameter(1).build().is_err());
+        // r < sqrt(m)
+        assert!(valid.clone().gaussian_r(10).build().is_err());
+        // q < 5 * r * (m + 1)
+        assert!(valid.clone().modulus(1000003).build().is_err());
+        // α > 1/(2 * r * sqrt(m + 1) * log(n))
+        assert!(valid.clone().gaussian_alpha(0.01).build().is_err());
+        // unchanged parameters are still valid
+        assert!(valid.build().is_ok());
+    }
+}
@@ -0,0 +1,143 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains a precomputed state of [`DualRegevIBE`] for an identity,
+//! which speeds up repeated encryptions for the same identity.
+
+use super::DualRegevIBE;
+use crate::construction::{hash::sha256::hash_to_mat_zq_sha256, pk_encryption::PKEncryptionScheme};
+use qfall_math::{
+    integer::Z,
+    integer_mod_q::MatZq,
+    traits::{Concatenate, GetNumRows},
+};
+use serde::{Deserialize, Serialize};
+
+/// The precomputed state of an identity for encryptions with [`DualRegevIBE::enc_with_state`].
+///
+/// Attributes:
+/// - `identity_hash`: the hash `H(id)` of the identity
+/// - `identity_based_pk`: the identity-based public key `[A | H(id)]`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EncState {
+    pub identity_hash: MatZq,
+    pub identity_based_pk: MatZq,
+}
+
+impl DualRegevIBE {
+    /// Hashes `identity` and computes the identity-based public key `[A | H(id)]`
+    /// once, s.t. repeated encryptions for the same identity do not have to rehash it.
+    ///
+    /// Parameters:
+    /// - `master_pk`: specifies the public key, which is matrix `pk = A`
+    /// - `identity`: specifies the identity used for encryption
+    ///
+    /// Returns the [`EncState`] of `identity`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::identity_based_encryption::{DualRegevIBE, IBEScheme};
+    /// let ibe = DualRegevIBE::default();
+    /// let (pk, sk) = ibe.setup();
+    ///
+    /// let state = ibe.precompute_enc_state(&pk, "identity");
+    /// ```
+    pub fn precompute_enc_state(&self, master_pk: &MatZq, identity: &str) -> EncState {
+        let identity_hash =
+            hash_to_mat_zq_sha256(identity, master_pk.get_num_rows(), 1, master_pk.get_mod());
+        let identity_based_pk = master_pk.concat_horizontal(&identity_hash).unwrap();
+
+        EncState {
+            identity_hash,
+            identity_based_pk,
+        }
+    }
+
+    /// Generates an encryption of `message mod 2` for the identity of `state`.
+    /// The ciphertext is distributed identically to a ciphertext of
+    /// [`DualRegevIBE::enc`](crate::construction::identity_based_encryption::IBEScheme::enc),
+    /// as the randomness is sampled freshly for every call.
+    ///
+    /// Parameters:
+    /// - `state`: specifies the precomputed state of the identity
+    /// - `message`: specifies the message that should be encrypted
+    ///
+    /// Returns a cipher of type [`MatZq`] for the identity of `state`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::identity_based_encryption::{DualRegevIBE, IBEScheme};
+    /// use qfall_math::integer::Z;
+    /// let ibe = DualRegevIBE::default();
+    /// let (pk, sk) = ibe.setup();
+    /// let id = String::from("identity");
+    /// let state = ibe.precompute_enc_state(&pk, &id);
+    ///
+    /// let cipher = ibe.enc_with_state(&state, 1);
+    ///
+    /// let id_sk = ibe.extract(&pk, &sk, &id).unwrap();
+    /// assert_eq!(Z::ONE, ibe.dec(&id_sk, &cipher).unwrap());
+    /// ```
+    pub fn enc_with_state(&self, state: &EncState, message: impl Into<Z>) -> MatZq {
+        self.dual_regev.enc(&state.identity_based_pk, message)
+    }
+}
+
+#[cfg(test)]
+mod test_enc_state {
+    use crate::construction::{
+        hash::sha256::hash_to_mat_zq_sha256,
+        identity_based_encryption::{DualRegevIBE, IBEScheme},
+    };
+    use qfall_math::{
+        integer::Z,
+        traits::{Concatenate, GetNumColumns, GetNumRows},
+    };
+
+    /// Ensures that the state contains the hash of the identity and
+    /// the identity-based public key used by `enc`.
+    #[test]
+    fn state_matches_enc() {
+        let ibe = DualRegevIBE::default();
+        let (pk, _) = ibe.setup();
+        let id = String::from("identity");
+
+        let state = ibe.precompute_enc_state(&pk, &id);
+
+        let identity_hash = hash_to_mat_zq_sha256(&id, pk.get_num_rows(), 1, pk.get_mod());
+        assert_eq!(identity_hash, state.identity_hash);
+        assert_eq!(
+            pk.concat_horizontal(&identity_hash).unwrap(),
+            state.identity_based_pk
+        );
+    }
+
+    /// Ensures that ciphertexts generated with a state decrypt correctly,
+    /// have the same dimensions as ciphertexts of `enc` and use fresh randomness.
+    #[test]
+    fn cycle() {
+        let ibe = DualRegevIBE::default();
+        let (pk, sk) = ibe.setup();
+        let id = String::from("identity");
+        let id_sk = ibe.extract(&pk, &sk, &id).unwrap();
+        let state = ibe.precompute_enc_state(&pk, &id);
+
+        for msg in [Z::ZERO, Z::ONE] {
+            for _ in 0..10 {
+                let cipher = ibe.enc_with_state(&state, &msg);
+                let reference = ibe.enc(&pk, &id, &msg);
+
+                assert_eq!(msg, ibe.dec(&id_sk, &cipher).unwrap());
+                assert_eq!(reference.get_num_rows(), cipher.get_num_rows());
+                assert_eq!(reference.get_num_columns(), cipher.get_num_columns());
+                assert_eq!(reference.get_mod(), cipher.get_mod());
+            }
+        }
+        assert_ne!(ibe.enc_with_state(&state, 1), ibe.enc_with_state(&state, 1));
+    }
+}
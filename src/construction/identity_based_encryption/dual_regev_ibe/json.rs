@@ -0,0 +1,161 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains the JSON (de-)serialization of [`DualRegevIBE`] instances,
+//! which validates deserialized parameters before an instance is returned.
+
+use super::DualRegevIBE;
+use crate::error::CryptoError;
+
+impl DualRegevIBE {
+    /// Serializes this instance as JSON including the storage of
+    /// previously extracted secret keys.
+    ///
+    /// **WARNING:** The output contains all stored secret keys of identities.
+    ///
+    /// Returns the JSON representation of this instance.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::identity_based_encryption::DualRegevIBE;
+    ///
+    /// let ibe = DualRegevIBE::default();
+    /// let json = ibe.to_json();
+    /// ```
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// Deserializes a [`DualRegevIBE`] instance from its JSON representation
+    /// generated by [`DualRegevIBE::to_json`] and checks that its parameters are
+    /// consistent and pass [`DualRegevIBE::check_security`] and
+    /// [`DualRegevIBE::check_correctness`].
+    ///
+    /// Parameters:
+    /// - `s`: the JSON representation of the instance
+    ///
+    /// Returns the deserialized [`DualRegevIBE`] instance
+    /// or a [`CryptoError`] if `s` is invalid or its parameters are not suitable.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::identity_based_encryption::DualRegevIBE;
+    ///
+    /// let json = DualRegevIBE::default().to_json();
+    /// let ibe = DualRegevIBE::from_json(&json).unwrap();
+    /// ```
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] of type [`InvalidParameterFile`](CryptoError::InvalidParameterFile)
+    ///     if `s` is no valid JSON representation of a [`DualRegevIBE`] instance.
+    /// - Returns a [`CryptoError`] of type [`InvalidParameters`](CryptoError::InvalidParameters)
+    ///     if `n`, `q`, or `m` of the [`PSF`](crate::primitive::psf::PSF) and the
+    ///     [`DualRegev`](crate::construction::pk_encryption::DualRegev) instance do not match or
+    ///     if `n`, `m`, `r`, or `alpha` is too small or
+    ///     if the parameters do not pass the security or correctness checks.
+    pub fn from_json(s: &str) -> Result<Self, CryptoError> {
+        let ibe = Self::from_json_unchecked(s)?;
+        ibe.validate_params()?;
+
+        Ok(ibe)
+    }
+
+    /// Deserializes a [`DualRegevIBE`] instance from its JSON representation
+    /// generated by [`DualRegevIBE::to_json`] without checking its parameters.
+    ///
+    /// **WARNING:** The caller is responsible for the security and correctness
+    /// of the parameters, e.g. by calling [`DualRegevIBE::check_security`] and
+    /// [`DualRegevIBE::check_correctness`]. Use [`DualRegevIBE::from_json`]
+    /// for untrusted input.
+    ///
+    /// Parameters:
+    /// - `s`: the JSON representation of the instance
+    ///
+    /// Returns the deserialized [`DualRegevIBE`] instance
+    /// or a [`CryptoError`] if `s` is invalid.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::identity_based_encryption::DualRegevIBE;
+    ///
+    /// let json = DualRegevIBE::new(4, 13933, 5, 0.01).to_json();
+    /// let ibe = DualRegevIBE::from_json_unchecked(&json).unwrap();
+    ///
+    /// assert!(ibe.check_security().is_err());
+    /// ```
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] of type [`InvalidParameterFile`](CryptoError::InvalidParameterFile)
+    ///     if `s` is no valid JSON representation of a [`DualRegevIBE`] instance.
+    pub fn from_json_unchecked(s: &str) -> Result<Self, CryptoError> {
+        serde_json::from_str(s).map_err(|e| CryptoError::InvalidParameterFile(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod test_json {
+    use crate::{
+        construction::identity_based_encryption::{DualRegevIBE, IBEScheme},
+        error::CryptoError,
+    };
+    use qfall_math::{integer::Z, rational::Q};
+    use serde_json::Value;
+
+    /// Ensures that a default instance including its storage survives a round-trip.
+    #[test]
+    fn round_trip() {
+        let ibe = DualRegevIBE::default();
+        let (pk, sk) = ibe.setup();
+        let id = String::from("identity");
+        let id_sk = ibe.extract(&pk, &sk, &id).unwrap();
+
+        let loaded = DualRegevIBE::from_json(&ibe.to_json()).unwrap();
+
+        assert_eq!(ibe.dual_regev.n, loaded.dual_regev.n);
+        assert_eq!(ibe.dual_regev.m, loaded.dual_regev.m);
+        assert_eq!(Z::from(&ibe.dual_regev.q), Z::from(&loaded.dual_regev.q));
+        assert_eq!(ibe.dual_regev.alpha, loaded.dual_regev.alpha);
+        assert_eq!(ibe.psf.s, loaded.psf.s);
+        assert_eq!(id_sk, loaded.extract(&pk, &sk, &id).unwrap());
+
+        let cipher = loaded.enc(&pk, &id, 1);
+        assert_eq!(Z::ONE, loaded.dec(&id_sk, &cipher).unwrap());
+    }
+
+    /// Ensures that a hand-crafted JSON object with insecure parameters
+    /// is rejected by `from_json`, but accepted by `from_json_unchecked`.
+    #[test]
+    fn insecure_rejected() {
+        let mut json: Value = serde_json::from_str(&DualRegevIBE::default().to_json()).unwrap();
+        // r < sqrt(m)
+        json["psf"]["s"] = serde_json::to_value(Q::from(5)).unwrap();
+        let json = json.to_string();
+
+        assert!(matches!(
+            DualRegevIBE::from_json(&json),
+            Err(CryptoError::InvalidParameters { .. })
+        ));
+        assert!(DualRegevIBE::from_json_unchecked(&json).is_ok());
+    }
+
+    /// Ensures that inconsistent parameters and invalid JSON are rejected.
+    #[test]
+    fn invalid_json() {
+        let mut json: Value = serde_json::from_str(&DualRegevIBE::default().to_json()).unwrap();
+        json["dual_regev"]["n"] = serde_json::to_value(Z::from(5)).unwrap();
+
+        assert!(matches!(
+            DualRegevIBE::from_json(&json.to_string()),
+            Err(CryptoError::InvalidParameters { .. })
+        ));
+        assert!(matches!(
+            DualRegevIBE::from_json("{\"dual_regev\": 4}"),
+            Err(CryptoError::InvalidParameterFile(_))
+        ));
+    }
+}
@@ -0,0 +1,280 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains a wrapper of [`DualRegevIBE`], which separates
+//! the identities of different applications sharing a master key.
+
+use super::DualRegevIBE;
+use crate::{construction::identity_based_encryption::IBEScheme, error::CryptoError};
+use qfall_math::{
+    integer::{MatZ, Z},
+    integer_mod_q::MatZq,
+    rational::MatQ,
+};
+use serde::{Deserialize, Serialize};
+
+/// This struct wraps a [`DualRegevIBE`] instance and prepends
+/// `namespace || "||"` to every identity before it is hashed.
+/// Hence, the same identity string in two different namespaces results
+/// in two independent identity-based public keys.
+///
+/// Attributes:
+/// - `ibe`: the wrapped [`DualRegevIBE`] instance
+/// - `namespace`: the namespace prepended to all identities
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::construction::identity_based_encryption::{DualRegevIBE, IBEScheme};
+/// use qfall_math::integer::Z;
+///
+/// let ibe = DualRegevIBE::default().with_namespace(String::from("application"));
+/// let (pk, sk) = ibe.setup();
+///
+/// let id = String::from("admin");
+/// let id_sk = ibe.extract(&pk, &sk, &id).unwrap();
+/// let cipher = ibe.enc(&pk, &id, 1);
+///
+/// assert_eq!(Z::ONE, ibe.dec(&id_sk, &cipher).unwrap());
+/// ```
+#[derive(Serialize, Deserialize)]
+pub struct NamespacedDualRegevIBE {
+    pub ibe: DualRegevIBE,
+    pub namespace: String,
+}
+
+impl DualRegevIBE {
+    /// Wraps `self` in a [`NamespacedDualRegevIBE`], which prepends
+    /// `namespace || "||"` to every identity.
+    ///
+    /// Parameters:
+    /// - `namespace`: the namespace prepended to all identities
+    ///
+    /// Returns a [`NamespacedDualRegevIBE`] instance.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::identity_based_encryption::DualRegevIBE;
+    ///
+    /// let ibe = DualRegevIBE::default().with_namespace(String::from("application"));
+    /// ```
+    pub fn with_namespace(self, namespace: String) -> NamespacedDualRegevIBE {
+        NamespacedDualRegevIBE {
+            ibe: self,
+            namespace,
+        }
+    }
+}
+
+impl NamespacedDualRegevIBE {
+    /// Returns the identity `namespace || "||" || identity`, which is passed to [`DualRegevIBE`].
+    fn namespaced_identity(&self, identity: &str) -> String {
+        format!("{}||{identity}", self.namespace)
+    }
+}
+
+impl IBEScheme for NamespacedDualRegevIBE {
+    type Cipher = MatZq;
+    type MasterPublicKey = MatZq;
+    type MasterSecretKey = (MatZ, MatQ);
+    type SecretKey = MatZ;
+    type Identity = String;
+
+    /// Generates a master key pair using [`DualRegevIBE::setup`].
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::identity_based_encryption::{DualRegevIBE, IBEScheme};
+    /// let ibe = DualRegevIBE::default().with_namespace(String::from("application"));
+    ///
+    /// let (pk, sk) = ibe.setup();
+    /// ```
+    fn setup(&self) -> (Self::MasterPublicKey, Self::MasterSecretKey) {
+        self.ibe.setup()
+    }
+
+    /// Extracts the secret key of `namespace || "||" || identity`
+    /// using [`DualRegevIBE::extract`].
+    ///
+    /// Parameters:
+    /// - `master_pk`: The master public key for the encryption scheme
+    /// - `master_sk`: The master secret key of the encryption scheme
+    /// - `identity`: The identity, for which the corresponding secret key
+    ///     should be returned
+    ///
+    /// Returns the corresponding secret key of `identity` in the namespace
+    /// under public key `master_pk` or a [`CryptoError`] if the key could not be extracted.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::identity_based_encryption::{DualRegevIBE, IBEScheme};
+    /// let ibe = DualRegevIBE::default().with_namespace(String::from("application"));
+    /// let (master_pk, master_sk) = ibe.setup();
+    ///
+    /// let id = String::from("identity");
+    /// let sk = ibe.extract(&master_pk, &master_sk, &id).unwrap();
+    /// ```
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] if [`DualRegevIBE::extract`] fails.
+    fn extract(
+        &self,
+        master_pk: &Self::MasterPublicKey,
+        master_sk: &Self::MasterSecretKey,
+        identity: &Self::Identity,
+    ) -> Result<Self::SecretKey, CryptoError> {
+        self.ibe
+            .extract(master_pk, master_sk, &self.namespaced_identity(identity))
+    }
+
+    /// Encrypts `message mod 2` for `namespace || "||" || identity`
+    /// using [`DualRegevIBE::enc`].
+    ///
+    /// Parameters:
+    /// - `master_pk`: specifies the master public key
+    /// - `identity`: specifies the identity used for encryption
+    /// - `message`: specifies the message that should be encrypted
+    ///
+    /// Returns a cipher of type [`MatZq`] for master_pk and the namespaced identity.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::identity_based_encryption::{DualRegevIBE, IBEScheme};
+    /// let ibe = DualRegevIBE::default().with_namespace(String::from("application"));
+    /// let (pk, sk) = ibe.setup();
+    ///
+    /// let id = String::from("identity");
+    /// let cipher = ibe.enc(&pk, &id, 1);
+    /// ```
+    fn enc(
+        &self,
+        master_pk: &Self::MasterPublicKey,
+        identity: &Self::Identity,
+        message: impl Into<Z>,
+    ) -> Self::Cipher {
+        self.ibe
+            .enc(master_pk, &self.namespaced_identity(identity), message)
+    }
+
+    /// Decrypts the provided `cipher` using [`DualRegevIBE::dec`].
+    ///
+    /// Parameters:
+    /// - `sk_id`: specifies the secret key obtained by extract
+    /// - `cipher`: specifies the cipher
+    ///
+    /// Returns the decryption of `cipher` as a [`Z`] instance
+    /// or a [`CryptoError`] if `sk_id` and `cipher` do not fit together.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::identity_based_encryption::{DualRegevIBE, IBEScheme};
+    /// use qfall_math::integer::Z;
+    /// let ibe = DualRegevIBE::default().with_namespace(String::from("application"));
+    /// let (pk, sk) = ibe.setup();
+    /// let identity = String::from("identity");
+    /// let id_sk = ibe.extract(&pk, &sk, &identity).unwrap();
+    /// let cipher = ibe.enc(&pk, &identity, 0);
+    ///
+    /// assert_eq!(Z::ZERO, ibe.dec(&id_sk, &cipher).unwrap());
+    /// ```
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] if [`DualRegevIBE::dec`] fails.
+    fn dec(&self, sk_id: &Self::SecretKey, cipher: &Self::Cipher) -> Result<Z, CryptoError> {
+        self.ibe.dec(sk_id, cipher)
+    }
+
+    /// Checks the parameters for security using [`DualRegevIBE::check_security`].
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] of type [`InvalidParameters`](CryptoError::InvalidParameters)
+    ///     if the parameters of the wrapped [`DualRegevIBE`] are insecure.
+    fn check_security(&self) -> Result<(), CryptoError> {
+        IBEScheme::check_security(&self.ibe)
+    }
+
+    /// Checks the parameters for correctness using [`DualRegevIBE::check_correctness`].
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] of type [`InvalidParameters`](CryptoError::InvalidParameters)
+    ///     if the parameters of the wrapped [`DualRegevIBE`] are incorrect.
+    fn check_correctness(&self) -> Result<(), CryptoError> {
+        IBEScheme::check_correctness(&self.ibe)
+    }
+}
+
+#[cfg(test)]
+mod test_namespaced_dual_regev_ibe {
+    use crate::construction::identity_based_encryption::{DualRegevIBE, IBEScheme};
+    use qfall_math::integer::Z;
+
+    /// Ensures that encryption and decryption within one namespace works.
+    #[test]
+    fn cycle() {
+        let ibe = DualRegevIBE::default().with_namespace(String::from("A"));
+        let (pk, sk) = ibe.setup();
+        let id = String::from("admin");
+        let id_sk = ibe.extract(&pk, &sk, &id).unwrap();
+
+        for msg in [Z::ZERO, Z::ONE] {
+            let cipher = ibe.enc(&pk, &id, &msg);
+            assert_eq!(msg, ibe.dec(&id_sk, &cipher).unwrap());
+        }
+    }
+
+    /// Ensures that the keys of the same identity in different namespaces differ
+    /// and differ from the key of the plain identity.
+    #[test]
+    fn different_keys() {
+        let ibe_a = DualRegevIBE::default().with_namespace(String::from("A"));
+        let (pk, sk) = ibe_a.setup();
+        let ibe_b = DualRegevIBE::new(
+            &ibe_a.ibe.dual_regev.n,
+            &ibe_a.ibe.dual_regev.q,
+            &ibe_a.ibe.psf.s,
+            &ibe_a.ibe.dual_regev.alpha,
+        )
+        .with_namespace(String::from("B"));
+        let id = String::from("admin");
+
+        let id_sk_a = ibe_a.extract(&pk, &sk, &id).unwrap();
+        let id_sk_b = ibe_b.extract(&pk, &sk, &id).unwrap();
+
+        assert_ne!(id_sk_a, id_sk_b);
+        assert_ne!(id_sk_a, ibe_a.ibe.extract(&pk, &sk, &id).unwrap());
+    }
+
+    /// Ensures that a key extracted in namespace `A` can not decrypt
+    /// ciphertexts for the same identity in namespace `B`.
+    #[test]
+    fn cross_namespace_decryption_fails() {
+        let ibe_a = DualRegevIBE::default().with_namespace(String::from("A"));
+        let (pk, sk) = ibe_a.setup();
+        let ibe_b = DualRegevIBE::new(
+            &ibe_a.ibe.dual_regev.n,
+            &ibe_a.ibe.dual_regev.q,
+            &ibe_a.ibe.psf.s,
+            &ibe_a.ibe.dual_regev.alpha,
+        )
+        .with_namespace(String::from("B"));
+        let id = String::from("admin");
+        let id_sk_a = ibe_a.extract(&pk, &sk, &id).unwrap();
+
+        // a wrong key decrypts each ciphertext to a uniform bit,
+        // hence 40 ciphertexts are decrypted correctly with probability 2^{-40}
+        let mut nr_correct = 0;
+        for _ in 0..40 {
+            let msg = Z::from(rand::random::<bool>() as i64);
+            let cipher = ibe_b.enc(&pk, &id, &msg);
+            if ibe_b.dec(&id_sk_a, &cipher).unwrap() == msg {
+                nr_correct += 1;
+            }
+        }
+
+        assert!(nr_correct < 40);
+    }
+}
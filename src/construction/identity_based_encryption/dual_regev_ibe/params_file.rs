@@ -0,0 +1,295 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains functions to store the public parameters of a
+//! [`DualRegevIBE`] instance in a TOML file and to load an instance from it.
+//!
+//! A parameter file has the following form, where all parameters are stored
+//! as strings such that rationals like `alpha` can be stored without loss of precision.
+//! ```toml
+//! [params]
+//! n = "4"
+//! m = "116"
+//! q = "13933"
+//! r = "11"
+//! alpha = "1/476"
+//!
+//! [metadata]
+//! crate_version = "0.1.0"
+//! creation_timestamp = 1700000000
+//! ```
+
+use super::DualRegevIBE;
+use crate::error::CryptoError;
+use qfall_math::{integer::Z, rational::Q};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs, io,
+    path::Path,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Layout of a parameter file.
+#[derive(Serialize, Deserialize)]
+struct ParamsFile {
+    params: Params,
+    metadata: Metadata,
+}
+
+/// The public parameters of a [`DualRegevIBE`] instance.
+#[derive(Serialize, Deserialize)]
+struct Params {
+    n: String,
+    m: String,
+    q: String,
+    r: String,
+    alpha: String,
+}
+
+/// Information about the creation of the parameter file.
+#[derive(Serialize, Deserialize)]
+struct Metadata {
+    crate_version: String,
+    creation_timestamp: u64,
+}
+
+/// Stores the public parameters `n`, `m`, `q`, `r`, and `alpha` of `scheme`
+/// in a TOML file at `path` together with the version of this crate and
+/// the UNIX timestamp of the file's creation.
+/// The storage of previously extracted secret keys is not stored.
+///
+/// Parameters:
+/// - `scheme`: the [`DualRegevIBE`] instance whose parameters are stored
+/// - `path`: the path of the file, which is created or overwritten
+///
+/// Returns an empty result if the file was written successfully.
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::construction::identity_based_encryption::{
+///     load_params_toml, save_params_toml, DualRegevIBE,
+/// };
+///
+/// let ibe = DualRegevIBE::default();
+/// let path = std::env::temp_dir().join("qfall_dual_regev_ibe_doc.toml");
+///
+/// save_params_toml(&ibe, &path).unwrap();
+/// let loaded = load_params_toml(&path).unwrap();
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+///
+/// # Errors and Failures
+/// - Returns an [`io::Error`] if the file could not be written.
+pub fn save_params_toml(scheme: &DualRegevIBE, path: &Path) -> io::Result<()> {
+    let creation_timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0);
+
+    let file = ParamsFile {
+        params: Params {
+            n: scheme.dual_regev.n.to_string(),
+            m: scheme.dual_regev.m.to_string(),
+            q: Z::from(&scheme.dual_regev.q).to_string(),
+            r: scheme.psf.s.to_string(),
+            alpha: scheme.dual_regev.alpha.to_string(),
+        },
+        metadata: Metadata {
+            crate_version: String::from(env!("CARGO_PKG_VERSION")),
+            creation_timestamp,
+        },
+    };
+
+    let content =
+        toml::to_string(&file).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    fs::write(path, content)
+}
+
+/// Loads the public parameters stored at `path` by [`save_params_toml`]
+/// and initializes a [`DualRegevIBE`] instance with them.
+/// The loaded parameters have to pass [`DualRegevIBE::check_security`]
+/// and [`DualRegevIBE::check_correctness`].
+///
+/// Parameters:
+/// - `path`: the path of the TOML file containing the parameters
+///
+/// Returns a [`DualRegevIBE`] instance with the parameters from the file
+/// or a [`CryptoError`] if the file could not be read or its parameters are invalid.
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::construction::identity_based_encryption::{
+///     load_params_toml, save_params_toml, DualRegevIBE,
+/// };
+///
+/// let ibe = DualRegevIBE::default();
+/// let path = std::env::temp_dir().join("qfall_dual_regev_ibe_load_doc.toml");
+/// save_params_toml(&ibe, &path).unwrap();
+///
+/// let loaded = load_params_toml(&path).unwrap();
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+///
+/// # Errors and Failures
+/// - Returns a [`CryptoError`] of type [`Io`](CryptoError::Io)
+///     if the file could not be read.
+/// - Returns a [`CryptoError`] of type [`InvalidParameterFile`](CryptoError::InvalidParameterFile)
+///     if the file is not a valid parameter file.
+/// - Returns a [`CryptoError`] of type [`InvalidParameters`](CryptoError::InvalidParameters)
+///     if `m` does not match the `m` induced by `n` and `q` or
+///     if `n`, `q`, `r`, or `alpha` is too small or
+///     if the parameters do not pass the security or correctness checks.
+pub fn load_params_toml(path: &Path) -> Result<DualRegevIBE, CryptoError> {
+    let content = fs::read_to_string(path)?;
+    let file: ParamsFile =
+        toml::from_str(&content).map_err(|e| CryptoError::InvalidParameterFile(e.to_string()))?;
+
+    let n: Z = parse_param(&file.params.n, "n")?;
+    let m: Z = parse_param(&file.params.m, "m")?;
+    let q: Z = parse_param(&file.params.q, "q")?;
+    let r: Q = parse_param(&file.params.r, "r")?;
+    let alpha: Q = parse_param(&file.params.alpha, "alpha")?;
+
+    // required to initialize the gadget parameters, all other parameters are validated afterwards
+    if n <= Z::ONE || q <= Z::ONE {
+        return Err(CryptoError::InvalidParameters {
+            message: String::from("n and q have to be larger than 1."),
+        });
+    }
+
+    let mut ibe = DualRegevIBE::new(n, q, r, alpha);
+    ibe.dual_regev.m = m;
+    ibe.validate_params()?;
+
+    Ok(ibe)
+}
+
+/// Parses the parameter `value` with name `name` from its string representation.
+fn parse_param<T: FromStr>(value: &str, name: &str) -> Result<T, CryptoError> {
+    T::from_str(value).map_err(|_| {
+        CryptoError::InvalidParameterFile(format!(
+            "The parameter {name} = \"{value}\" could not be parsed."
+        ))
+    })
+}
+
+#[cfg(test)]
+mod test_params_file {
+    use super::{load_params_toml, save_params_toml};
+    use crate::{
+        construction::identity_based_encryption::{DualRegevIBE, IBEScheme},
+        error::CryptoError,
+    };
+    use qfall_math::integer::Z;
+    use std::{fs, path::PathBuf};
+
+    /// Returns the path of the sample parameter file in the `tests` directory.
+    fn sample_path() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/dual_regev_ibe_params.toml")
+    }
+
+    /// Ensures that storing and loading parameters yields the same parameters.
+    #[test]
+    fn round_trip() {
+        let ibe = DualRegevIBE::default();
+        let path = std::env::temp_dir().join("qfall_test_params_file_round_trip.toml");
+
+        save_params_toml(&ibe, &path).unwrap();
+        let loaded = load_params_toml(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(ibe.dual_regev.n, loaded.dual_regev.n);
+        assert_eq!(ibe.dual_regev.m, loaded.dual_regev.m);
+        assert_eq!(Z::from(&ibe.dual_regev.q), Z::from(&loaded.dual_regev.q));
+        assert_eq!(ibe.dual_regev.alpha, loaded.dual_regev.alpha);
+        assert_eq!(ibe.psf.s, loaded.psf.s);
+    }
+
+    /// Ensures that the sample file can be loaded, its parameters are valid,
+    /// and the loaded scheme works properly.
+    #[test]
+    fn sample_file() {
+        let ibe = load_params_toml(&sample_path()).unwrap();
+
+        assert!(ibe.check_security().is_ok());
+        assert!(ibe.check_correctness().is_ok());
+        assert_eq!(Z::from(116), ibe.dual_regev.m);
+
+        let id = String::from("identity");
+        let (pk, sk) = ibe.setup();
+        let id_sk = ibe.extract(&pk, &sk, &id).unwrap();
+        let cipher = ibe.enc(&pk, &id, 1);
+        assert_eq!(Z::ONE, ibe.dec(&id_sk, &cipher).unwrap());
+    }
+
+    /// Ensures that the metadata is written to the file.
+    #[test]
+    fn metadata_written() {
+        let ibe = DualRegevIBE::default();
+        let path = std::env::temp_dir().join("qfall_test_params_file_metadata.toml");
+
+        save_params_toml(&ibe, &path).unwrap();
+        let content = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert!(content.contains("[params]"));
+        assert!(content.contains("[metadata]"));
+        assert!(content.contains(&format!(
+            "crate_version = \"{}\"",
+            env!("CARGO_PKG_VERSION")
+        )));
+        assert!(content.contains("creation_timestamp = "));
+    }
+
+    /// Ensures that a stored `m` not matching `n` and `q` results in an error.
+    #[test]
+    fn mismatching_m() {
+        let content = fs::read_to_string(sample_path())
+            .unwrap()
+            .replace("m = \"116\"", "m = \"117\"");
+        let path = std::env::temp_dir().join("qfall_test_params_file_mismatching_m.toml");
+        fs::write(&path, content).unwrap();
+
+        let res = load_params_toml(&path);
+        fs::remove_file(&path).unwrap();
+
+        assert!(matches!(res, Err(CryptoError::InvalidParameters { .. })));
+    }
+
+    /// Ensures that insecure parameters are rejected.
+    #[test]
+    fn insecure_parameters() {
+        let content = fs::read_to_string(sample_path())
+            .unwrap()
+            .replace("r = \"11\"", "r = \"5\"");
+        let path = std::env::temp_dir().join("qfall_test_params_file_insecure.toml");
+        fs::write(&path, content).unwrap();
+
+        let res = load_params_toml(&path);
+        fs::remove_file(&path).unwrap();
+
+        assert!(matches!(res, Err(CryptoError::InvalidParameters { .. })));
+    }
+
+    /// Ensures that malformed files and missing files are rejected.
+    #[test]
+    fn invalid_file() {
+        let path = std::env::temp_dir().join("qfall_test_params_file_invalid.toml");
+        fs::write(&path, "[params]\nn = \"four\"\n").unwrap();
+
+        let res = load_params_toml(&path);
+        fs::remove_file(&path).unwrap();
+
+        assert!(matches!(res, Err(CryptoError::InvalidParameterFile(_))));
+        assert!(matches!(
+            load_params_toml(&std::env::temp_dir().join("qfall_does_not_exist.toml")),
+            Err(CryptoError::Io(_))
+        ));
+    }
+}
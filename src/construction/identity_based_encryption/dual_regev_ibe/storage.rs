@@ -0,0 +1,210 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains the canonical keys of the storage of [`DualRegevIBE`](super::DualRegevIBE)
+//! and the (de-)serialization of the storage including a migration of storages
+//! serialized with the previous [`Display`](std::fmt::Display)-based keys.
+//! The insertion times of the entries are not serialized, i.e. deserialized
+//! entries are treated as inserted at the time of deserialization.
+//! Storages containing keys, which can not be parsed, are rejected.
+
+use dashmap::DashMap;
+use qfall_math::{integer::MatZ, integer_mod_q::MatZq};
+use serde::{de::Error, ser::SerializeMap, Deserialize, Deserializer, Serializer};
+use sha2::{Digest, Sha256};
+use std::{collections::HashMap, str::FromStr, time::SystemTime};
+
+/// The key of a secret key in the storage, i.e. `SHA-256(CBOR(master_pk)) || SHA-256(identity)`.
+pub(super) type StorageKey = [u8; 64];
+
+/// An entry of the storage, i.e. the secret key and the time it was inserted at.
+pub(super) type StorageEntry = (MatZ, SystemTime);
+
+/// Computes the canonical storage key `SHA-256(CBOR(master_pk)) || SHA-256(identity)`.
+///
+/// Parameters:
+/// - `master_pk`: the master public key under which the secret key was extracted
+/// - `identity`: the identity of the secret key
+///
+/// Returns the storage key as a byte array.
+pub(super) fn storage_key(master_pk: &MatZq, identity: &str) -> StorageKey {
+    let mut cbor = Vec::new();
+    ciborium::into_writer(master_pk, &mut cbor).unwrap();
+
+    let mut key = [0u8; 64];
+    key[..32].copy_from_slice(&Sha256::digest(&cbor));
+    key[32..].copy_from_slice(&Sha256::digest(identity.as_bytes()));
+    key
+}
+
+/// Serializes the storage as a map, whose keys are the hex encodings of the storage keys.
+///
+/// Parameters:
+/// - `storage`: the storage of extracted secret keys
+/// - `serializer`: the serializer, which serializes the map
+///
+/// Returns the output of `serializer` or an error if the serialization failed.
+pub(super) fn serialize<S: Serializer>(
+    storage: &DashMap<StorageKey, StorageEntry>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let mut map = serializer.serialize_map(Some(storage.len()))?;
+    for entry in storage.iter() {
+        let hex: String = entry
+            .key()
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect();
+        map.serialize_entry(&hex, &entry.value().0)?;
+    }
+    map.end()
+}
+
+/// Deserializes a storage serialized by [`serialize`].
+/// Keys of the form `"{master_pk} {master_sk.0} {master_sk.1} {identity}"`
+/// used by previous versions are converted into canonical storage keys.
+///
+/// Parameters:
+/// - `deserializer`: the deserializer, which provides the serialized map
+///
+/// Returns the deserialized storage or an error if the map could not be deserialized
+/// or one of its keys is neither a hex encoded storage key nor a key of a previous version.
+pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<DashMap<StorageKey, StorageEntry>, D::Error> {
+    let map = HashMap::<String, MatZ>::deserialize(deserializer)?;
+    let now = SystemTime::now();
+
+    // the key is not part of the error, as keys of previous versions contain `master_sk`
+    map.into_iter()
+        .map(|(key, value)| {
+            let parsed = parse_hex_key(&key)
+                .or_else(|| parse_legacy_key(&key))
+                .ok_or_else(|| D::Error::custom("a key of the storage could not be parsed"))?;
+            Ok((parsed, (value, now)))
+        })
+        .collect()
+}
+
+/// Parses a storage key from its hex encoding.
+///
+/// Parameters:
+/// - `hex`: the hex encoding of the storage key consisting of `128` characters
+///
+/// Returns the storage key or `None` if `hex` is no hex encoding of a storage key.
+fn parse_hex_key(hex: &str) -> Option<StorageKey> {
+    if hex.len() != 128 || !hex.is_ascii() {
+        return None;
+    }
+
+    let mut key = [0u8; 64];
+    for (i, byte) in key.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[2 * i..2 * i + 2], 16).ok()?;
+    }
+    Some(key)
+}
+
+/// Parses a key of the form `"{master_pk} {master_sk.0} {master_sk.1} {identity}"`,
+/// where `master_pk` is displayed as `[[..]] mod q` and both parts of `master_sk`
+/// as `[[..]]`.
+///
+/// Parameters:
+/// - `key`: the key of a storage serialized by a previous version
+///
+/// Returns the corresponding canonical storage key or `None` if `key` can not be parsed.
+fn parse_legacy_key(key: &str) -> Option<StorageKey> {
+    // `master_pk` ends with the first space after `]] mod `
+    let mod_start = key.find("]] mod ")? + "]] mod ".len();
+    let pk_end = mod_start + key[mod_start..].find(' ')?;
+    let master_pk = MatZq::from_str(&key[..pk_end]).ok()?;
+
+    // skip both parts of `master_sk`, each of which ends with `]]`
+    let mut rest = &key[pk_end + 1..];
+    for _ in 0..2 {
+        let end = rest.find("]]")? + "]]".len();
+        rest = rest.get(end + 1..)?;
+    }
+
+    Some(storage_key(&master_pk, rest))
+}
+
+#[cfg(test)]
+mod test_storage_key {
+    use super::{deserialize, parse_hex_key, parse_legacy_key, storage_key};
+    use crate::construction::identity_based_encryption::{DualRegevIBE, IBEScheme};
+    use qfall_math::{integer::MatZ, integer_mod_q::MatZq};
+    use std::collections::HashSet;
+
+    /// Ensures that 10,000 random pairs of public keys and identities
+    /// result in 10,000 distinct storage keys.
+    #[test]
+    fn no_collisions() {
+        let mut keys = HashSet::new();
+        for i in 0..10_000 {
+            let master_pk = MatZq::sample_uniform(2, 4, 257);
+            let identity = format!("identity {}", rand::random::<u64>() ^ i);
+
+            keys.insert(storage_key(&master_pk, &identity));
+        }
+
+        assert_eq!(10_000, keys.len());
+    }
+
+    /// Ensures that the storage key depends on the public key and the identity.
+    #[test]
+    fn depends_on_pk_and_identity() {
+        let pk_1 = MatZq::sample_uniform(2, 4, 257);
+        let pk_2 = MatZq::sample_uniform(2, 4, 257);
+
+        assert_eq!(storage_key(&pk_1, "a"), storage_key(&pk_1, "a"));
+        assert_ne!(storage_key(&pk_1, "a"), storage_key(&pk_2, "a"));
+        assert_ne!(storage_key(&pk_1, "a"), storage_key(&pk_1, "b"));
+    }
+
+    /// Ensures that the hex encoding of a storage key is parsed correctly.
+    #[test]
+    fn hex_key() {
+        let key = storage_key(&MatZq::sample_uniform(2, 4, 257), "identity");
+        let hex: String = key.iter().map(|byte| format!("{byte:02x}")).collect();
+
+        assert_eq!(Some(key), parse_hex_key(&hex));
+        assert_eq!(None, parse_hex_key("no hex"));
+    }
+
+    /// Ensures that keys of previous versions are converted into canonical storage keys,
+    /// even if the identity contains spaces.
+    #[test]
+    fn legacy_key() {
+        let ibe = DualRegevIBE::default();
+        let (pk, sk) = ibe.setup();
+        let identity = "Hello World!";
+        let legacy_key = format!("{pk} {} {} {identity}", sk.0, sk.1);
+
+        assert_eq!(
+            Some(storage_key(&pk, identity)),
+            parse_legacy_key(&legacy_key)
+        );
+        assert_eq!(None, parse_legacy_key("invalid key"));
+    }
+
+    /// Ensures that storages with hex encoded keys are deserialized and
+    /// that storages containing a key, which can not be parsed, are rejected.
+    #[test]
+    fn deserialize_keys() {
+        let key = storage_key(&MatZq::sample_uniform(2, 4, 257), "identity");
+        let hex: String = key.iter().map(|byte| format!("{byte:02x}")).collect();
+        let sk = serde_json::to_string(&MatZ::identity(2, 1)).unwrap();
+
+        let valid = format!("{{\"{hex}\": {sk}}}");
+        let invalid = format!("{{\"{hex}\": {sk}, \"invalid key\": {sk}}}");
+        let storage = deserialize(&mut serde_json::Deserializer::from_str(&valid)).unwrap();
+
+        assert_eq!(MatZ::identity(2, 1), storage.get(&key).unwrap().0);
+        assert!(deserialize(&mut serde_json::Deserializer::from_str(&invalid)).is_err());
+    }
+}
@@ -0,0 +1,462 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains a hierarchical identity-based encryption scheme (HIBE)
+//! on top of [`DualRegevIBE`], where every level delegates to the next one by
+//! extending the short basis of its lattice following the basis delegation
+//! of [CHKP10](https://eprint.iacr.org/2009/351.pdf) in the random oracle model.
+//!
+//! The lattice of an identity `(id_1, ..., id_l)` is `Λ^⟂([A | B_1 | ... | B_l])`,
+//! where `A` is the master public key and `B_i = H(id_1, ..., id_i)` is hashed
+//! into `Z_q^{n x nk}`. An identity at level `l` holds a short basis of its lattice,
+//! which allows to derive keys of its children, and a short preimage of
+//! `u = H(id_1, ..., id_l)` used for decryption like in [`DualRegevIBE`].
+
+use super::{DualRegevIBE, IBEScheme};
+use crate::{
+    construction::{
+        hash::sha256::hash_to_mat_zq_sha256,
+        pk_encryption::{DualRegev, PKEncryptionScheme},
+    },
+    error::CryptoError,
+    primitive::psf::PSF,
+    sample::g_trapdoor::short_basis_classical::extend_short_basis,
+};
+use qfall_math::{
+    integer::{MatZ, Z},
+    integer_mod_q::MatZq,
+    rational::MatQ,
+    traits::{Concatenate, GetNumColumns, GetNumRows, SetEntry},
+};
+use serde::{Deserialize, Serialize};
+
+/// The secret key of an identity in the hierarchy.
+///
+/// Attributes:
+/// - `identity`: the path of the identity in the hierarchy
+/// - `basis`: a short basis of the lattice of `identity` used to delegate keys
+/// - `basis_gso`: the Gram-Schmidt orthogonalization of `basis`
+/// - `sk_id`: the short preimage of `H(identity)` used for decryption
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChildSecretKey {
+    pub identity: Vec<String>,
+    pub basis: MatZ,
+    pub basis_gso: MatQ,
+    pub sk_id: MatZ,
+}
+
+/// This struct manages and stores the public parameters of a hierarchical
+/// [`IBEScheme`] based on [`DualRegevIBE`],
+/// whose identities are paths `(id_1, ..., id_l)` of type [`Vec<String>`].
+///
+/// **WARNING:** The delegated bases are re-randomized by an upper unitriangular
+/// transformation, which preserves their Gram-Schmidt orthogonalization,
+/// instead of RandBasis of [CHKP10](https://eprint.iacr.org/2009/351.pdf).
+/// Hence, the distribution of a child basis is not independent of its parent basis.
+///
+/// Attributes:
+/// - `ibe`: the [`DualRegevIBE`] defining the public parameters and the [`PSF`]
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::construction::identity_based_encryption::{HierarchicalIBE, IBEScheme};
+/// use qfall_math::integer::Z;
+/// let hibe = HierarchicalIBE::default();
+/// let (pk, sk) = hibe.setup();
+///
+/// let org = vec![String::from("org")];
+/// let sk_org = hibe.extract(&pk, &sk, &org).unwrap();
+/// let sk_alice = hibe.delegate(&pk, &sk_org, "alice");
+///
+/// let cipher = hibe.enc(&pk, &sk_alice.identity, 1);
+///
+/// assert_eq!(Z::ONE, hibe.dec(&sk_alice, &cipher).unwrap());
+/// ```
+#[derive(Serialize, Deserialize)]
+pub struct HierarchicalIBE {
+    pub ibe: DualRegevIBE,
+}
+
+impl HierarchicalIBE {
+    /// Initializes a [`HierarchicalIBE`] for identities with at most `max_depth` levels
+    /// based on `DualRegevIBE::new_from_n(n)`.
+    /// The Gaussian parameter of the encryption noise is reduced to
+    /// `α = 1/(2 * r * sqrt(m_max + 1) * log(n))`, where `m_max = m + max_depth * nk`
+    /// is the dimension of lattices at level `max_depth`.
+    ///
+    /// Parameters:
+    /// - `n`: the security parameter
+    /// - `max_depth`: the maximal number of levels of an identity
+    ///
+    /// Returns a [`HierarchicalIBE`] instance or a [`CryptoError`] if `n < 2`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::identity_based_encryption::HierarchicalIBE;
+    ///
+    /// let hibe = HierarchicalIBE::new_from_n(4, 2).unwrap();
+    /// ```
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] of type [`InvalidParameters`](CryptoError::InvalidParameters)
+    ///     if `n < 2`.
+    pub fn new_from_n(n: impl Into<Z>, max_depth: usize) -> Result<Self, CryptoError> {
+        let mut ibe = DualRegevIBE::new_from_n(n)?;
+
+        let hibe_m = &ibe.dual_regev.m + Z::from(max_depth as u64) * Self::level_width(&ibe);
+        ibe.dual_regev.alpha =
+            1 / (&ibe.psf.s * 2 * (hibe_m + Z::ONE).sqrt() * ibe.dual_regev.n.log(2).unwrap());
+
+        Ok(Self { ibe })
+    }
+
+    /// Returns the number of columns `nk` of each matrix `B_i`.
+    fn level_width(ibe: &DualRegevIBE) -> Z {
+        &ibe.psf.gp.n * &ibe.psf.gp.k
+    }
+
+    /// Hashes the prefix `identity` of a path with the domain `domain`.
+    fn hash_path(&self, domain: &str, identity: &[String], num_cols: &Z) -> MatZq {
+        let path = serde_json::to_string(identity).unwrap();
+        hash_to_mat_zq_sha256(
+            &format!("{domain} {path}"),
+            &self.ibe.dual_regev.n,
+            num_cols,
+            &self.ibe.dual_regev.q,
+        )
+    }
+
+    /// Computes the matrix `B_l = H(id_1, ..., id_l)` of the last level of `identity`.
+    fn level_matrix(&self, identity: &[String]) -> MatZq {
+        self.hash_path("HIBE level", identity, &Self::level_width(&self.ibe))
+    }
+
+    /// Computes the parity check matrix `[A | B_1 | ... | B_l]` of `identity`.
+    fn lattice_matrix(&self, master_pk: &MatZq, identity: &[String]) -> MatZq {
+        let mut matrix = master_pk.clone();
+        for level in 1..=identity.len() {
+            matrix = matrix
+                .concat_horizontal(&self.level_matrix(&identity[..level]))
+                .unwrap();
+        }
+        matrix
+    }
+
+    /// Returns a [`DualRegev`] instance whose dimension fits `lattice_matrix`.
+    fn dual_regev(&self, lattice_matrix: &MatZq) -> DualRegev {
+        DualRegev::new(
+            &self.ibe.dual_regev.n,
+            lattice_matrix.get_num_columns(),
+            self.ibe.dual_regev.q.clone(),
+            &self.ibe.dual_regev.alpha,
+        )
+    }
+
+    /// Re-randomizes `basis` by multiplying it with a uniform upper unitriangular
+    /// matrix with entries in `{-1, 0, 1}`, which generates the same lattice
+    /// and preserves the Gram-Schmidt orthogonalization of `basis`.
+    fn rerandomize_basis(basis: &MatZ) -> MatZ {
+        let dimension = basis.get_num_columns();
+        let mut transformation = MatZ::sample_uniform(dimension, dimension, -1, 2).unwrap();
+        for i in 0..dimension {
+            transformation.set_entry(i, i, 1).unwrap();
+            for j in 0..i {
+                transformation.set_entry(i, j, 0).unwrap();
+            }
+        }
+        basis * transformation
+    }
+
+    /// Delegates a key to `child_identity` given the secret key `sk_id`
+    /// of its parent. The short basis of the parent's lattice is extended to the
+    /// lattice of the child using [`extend_short_basis`] and re-randomized.
+    /// Afterwards, a short preimage of `H(identity || child_identity)` is sampled
+    /// with the new basis by the [`PSF`] of [`DualRegevIBE`].
+    ///
+    /// Parameters:
+    /// - `master_pk`: the master public key
+    /// - `sk_id`: the secret key of the parent identity
+    /// - `child_identity`: the name of the child on the next level
+    ///
+    /// Returns the [`ChildSecretKey`] of the path `sk_id.identity || child_identity`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::identity_based_encryption::{HierarchicalIBE, IBEScheme};
+    /// let hibe = HierarchicalIBE::default();
+    /// let (pk, sk) = hibe.setup();
+    /// let sk_org = hibe.extract(&pk, &sk, &vec![String::from("org")]).unwrap();
+    ///
+    /// let sk_alice = hibe.delegate(&pk, &sk_org, "alice");
+    ///
+    /// assert_eq!(vec![String::from("org"), String::from("alice")], sk_alice.identity);
+    /// ```
+    pub fn delegate(
+        &self,
+        master_pk: &MatZq,
+        sk_id: &ChildSecretKey,
+        child_identity: &str,
+    ) -> ChildSecretKey {
+        let mut identity = sk_id.identity.clone();
+        identity.push(String::from(child_identity));
+
+        self.derive_key(master_pk, &sk_id.basis, identity)
+    }
+
+    /// Derives the key of `identity` from a short basis of the lattice of its parent.
+    fn derive_key(
+        &self,
+        master_pk: &MatZq,
+        parent_basis: &MatZ,
+        identity: Vec<String>,
+    ) -> ChildSecretKey {
+        let parent_matrix = self.lattice_matrix(master_pk, &identity[..identity.len() - 1]);
+        let extended_basis =
+            extend_short_basis(&parent_matrix, parent_basis, &self.level_matrix(&identity));
+        let basis = Self::rerandomize_basis(&extended_basis);
+        let basis_gso = MatQ::from(&basis).gso();
+
+        let lattice_matrix = self.lattice_matrix(master_pk, &identity);
+        let u = self.hash_path("HIBE syndrome", &identity, &Z::ONE);
+        let sk_id = self
+            .ibe
+            .psf
+            .samp_p(&lattice_matrix, &(basis.clone(), basis_gso.clone()), &u);
+
+        ChildSecretKey {
+            identity,
+            basis,
+            basis_gso,
+            sk_id,
+        }
+    }
+}
+
+impl Default for HierarchicalIBE {
+    /// Initializes a [`HierarchicalIBE`] struct with parameters generated by
+    /// `HierarchicalIBE::new_from_n(4, 2)`.
+    /// This parameter choice is not secure as the dimension of the lattice is too small,
+    /// but it provides an efficient working example.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::identity_based_encryption::HierarchicalIBE;
+    ///
+    /// let hibe = HierarchicalIBE::default();
+    /// ```
+    fn default() -> Self {
+        HierarchicalIBE::new_from_n(4, 2).unwrap()
+    }
+}
+
+impl IBEScheme for HierarchicalIBE {
+    type Cipher = MatZq;
+    type MasterPublicKey = MatZq;
+    type MasterSecretKey = (MatZ, MatQ);
+    type SecretKey = ChildSecretKey;
+    type Identity = Vec<String>;
+
+    /// Generates a master key pair `(A, (S, S~))` using the [`PSF`] of [`DualRegevIBE`].
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::identity_based_encryption::{HierarchicalIBE, IBEScheme};
+    /// let hibe = HierarchicalIBE::default();
+    ///
+    /// let (pk, sk) = hibe.setup();
+    /// ```
+    fn setup(&self) -> (Self::MasterPublicKey, Self::MasterSecretKey) {
+        self.ibe.psf.trap_gen()
+    }
+
+    /// Extracts the secret key of the path `identity` by delegating
+    /// from the master secret key level by level.
+    ///
+    /// Parameters:
+    /// - `master_pk`: The master public key for the encryption scheme
+    /// - `master_sk`: The master secret key of the encryption scheme
+    /// - `identity`: The path of the identity, for which the corresponding secret key
+    ///     should be returned
+    ///
+    /// Returns the [`ChildSecretKey`] of `identity`.
+    /// The extraction of [`HierarchicalIBE`] does not return an error.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::identity_based_encryption::{HierarchicalIBE, IBEScheme};
+    /// let hibe = HierarchicalIBE::default();
+    /// let (pk, sk) = hibe.setup();
+    ///
+    /// let id = vec![String::from("org"), String::from("alice")];
+    /// let sk_id = hibe.extract(&pk, &sk, &id).unwrap();
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `identity` is empty.
+    fn extract(
+        &self,
+        master_pk: &Self::MasterPublicKey,
+        master_sk: &Self::MasterSecretKey,
+        identity: &Self::Identity,
+    ) -> Result<Self::SecretKey, CryptoError> {
+        assert!(
+            !identity.is_empty(),
+            "The identity has to consist of at least one level."
+        );
+
+        let mut sk_id = self.derive_key(master_pk, &master_sk.0, vec![identity[0].clone()]);
+        for child_identity in &identity[1..] {
+            sk_id = self.delegate(master_pk, &sk_id, child_identity);
+        }
+        Ok(sk_id)
+    }
+
+    /// Generates an encryption of `message mod 2` for the path `identity` by calling
+    /// [`DualRegev::enc`] on `[A | B_1 | ... | B_l | H(identity)]`.
+    ///
+    /// Parameters:
+    /// - `master_pk`: specifies the master public key `A`
+    /// - `identity`: specifies the path of the identity used for encryption
+    /// - `message`: specifies the message that should be encrypted
+    ///
+    /// Returns a cipher of type [`MatZq`] for `master_pk` and `identity`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::identity_based_encryption::{HierarchicalIBE, IBEScheme};
+    /// let hibe = HierarchicalIBE::default();
+    /// let (pk, sk) = hibe.setup();
+    ///
+    /// let id = vec![String::from("org"), String::from("alice")];
+    /// let cipher = hibe.enc(&pk, &id, 1);
+    /// ```
+    fn enc(
+        &self,
+        master_pk: &Self::MasterPublicKey,
+        identity: &Self::Identity,
+        message: impl Into<Z>,
+    ) -> Self::Cipher {
+        let lattice_matrix = self.lattice_matrix(master_pk, identity);
+        let u = self.hash_path("HIBE syndrome", identity, &Z::ONE);
+
+        self.dual_regev(&lattice_matrix)
+            .enc(&lattice_matrix.concat_horizontal(&u).unwrap(), message)
+    }
+
+    /// Decrypts the provided `cipher` using the secret key `sk` by calling [`DualRegev::dec`].
+    ///
+    /// Parameters:
+    /// - `sk`: specifies the [`ChildSecretKey`] of the identity
+    /// - `cipher`: specifies the cipher containing `cipher = c`
+    ///
+    /// Returns the decryption of `cipher` as a [`Z`] instance
+    /// or a [`CryptoError`] if `sk` and `cipher` do not fit together.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::identity_based_encryption::{HierarchicalIBE, IBEScheme};
+    /// use qfall_math::integer::Z;
+    /// let hibe = HierarchicalIBE::default();
+    /// let (pk, sk) = hibe.setup();
+    /// let id = vec![String::from("org")];
+    /// let sk_id = hibe.extract(&pk, &sk, &id).unwrap();
+    /// let cipher = hibe.enc(&pk, &id, 1);
+    ///
+    /// assert_eq!(Z::ONE, hibe.dec(&sk_id, &cipher).unwrap());
+    /// ```
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] of type [`IncompatibleParameters`](CryptoError::IncompatibleParameters)
+    ///     if `sk` and `cipher` do not belong to the same level of the hierarchy
+    ///     or if the modulus of `cipher` does not match `q`.
+    fn dec(&self, sk: &Self::SecretKey, cipher: &Self::Cipher) -> Result<Z, CryptoError> {
+        if sk.sk_id.get_num_columns() != 1
+            || cipher.get_num_columns() != 1
+            || cipher.get_num_rows() != sk.sk_id.get_num_rows() + 1
+            || cipher.get_mod() != self.ibe.dual_regev.q
+        {
+            return Err(CryptoError::IncompatibleParameters);
+        }
+
+        Ok(self.ibe.dual_regev.dec(&sk.sk_id, cipher))
+    }
+}
+
+#[cfg(test)]
+mod test_hibe {
+    use super::HierarchicalIBE;
+    use crate::construction::identity_based_encryption::IBEScheme;
+    use qfall_math::{integer::Z, integer_mod_q::MatZq, traits::GetNumRows};
+
+    /// Ensures that keys delegated over two levels decrypt correctly.
+    #[test]
+    fn two_level_delegation() {
+        let hibe = HierarchicalIBE::default();
+        let (pk, sk) = hibe.setup();
+        let org = vec![String::from("org")];
+
+        let sk_org = hibe.extract(&pk, &sk, &org).unwrap();
+        let sk_alice = hibe.delegate(&pk, &sk_org, "alice");
+
+        for msg in [Z::ZERO, Z::ONE] {
+            let cipher_org = hibe.enc(&pk, &org, &msg);
+            let cipher_alice = hibe.enc(&pk, &sk_alice.identity, &msg);
+
+            assert_eq!(msg, hibe.dec(&sk_org, &cipher_org).unwrap());
+            assert_eq!(msg, hibe.dec(&sk_alice, &cipher_alice).unwrap());
+        }
+    }
+
+    /// Ensures that directly extracted keys of the second level decrypt correctly.
+    #[test]
+    fn extract_two_levels() {
+        let hibe = HierarchicalIBE::default();
+        let (pk, sk) = hibe.setup();
+        let id = vec![String::from("org"), String::from("bob")];
+
+        let sk_id = hibe.extract(&pk, &sk, &id).unwrap();
+
+        assert_eq!(id, sk_id.identity);
+        for msg in [Z::ZERO, Z::ONE] {
+            let cipher = hibe.enc(&pk, &id, &msg);
+            assert_eq!(msg, hibe.dec(&sk_id, &cipher).unwrap());
+        }
+    }
+
+    /// Ensures that the delegated basis is a basis of the child lattice
+    /// and that the delegated secret key is a preimage of its syndrome.
+    #[test]
+    fn delegated_key_valid() {
+        let hibe = HierarchicalIBE::default();
+        let (pk, sk) = hibe.setup();
+        let sk_org = hibe.extract(&pk, &sk, &vec![String::from("org")]).unwrap();
+
+        let sk_alice = hibe.delegate(&pk, &sk_org, "alice");
+
+        let lattice_matrix = hibe.lattice_matrix(&pk, &sk_alice.identity);
+        let u = hibe.hash_path("HIBE syndrome", &sk_alice.identity, &Z::ONE);
+        let zero = MatZq::new(
+            lattice_matrix.get_num_rows(),
+            sk_alice.basis.get_num_rows(),
+            &hibe.ibe.dual_regev.q,
+        );
+        assert_eq!(zero, &lattice_matrix * &sk_alice.basis);
+        assert_eq!(u, &lattice_matrix * &sk_alice.sk_id);
+        assert_ne!(sk_alice.basis, hibe.delegate(&pk, &sk_org, "alice").basis);
+    }
+
+    /// Ensures that `extract` panics for an empty identity.
+    #[test]
+    #[should_panic]
+    fn empty_identity() {
+        let hibe = HierarchicalIBE::default();
+        let (pk, sk) = hibe.setup();
+
+        let _ = hibe.extract(&pk, &sk, &vec![]).unwrap();
+    }
+}
@@ -0,0 +1,625 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains a Ring-LWE variant of the [`DualRegevIBE`](super::DualRegevIBE)
+//! based on [\[1\]](<index.html#:~:text=[1]>), which uses the ring G-trapdoors
+//! of [`PSFGPVRing`] instead of classical G-trapdoors.
+
+use super::IBEScheme;
+use crate::{
+    construction::hash::{
+        sha256::{sha256, HashMatPolynomialRingZq},
+        HashInto,
+    },
+    error::CryptoError,
+    primitive::psf::{PSFGPVRing, PSF},
+    sample::g_trapdoor::gadget_parameters::GadgetParametersRing,
+    utils::math_util::ZqArith,
+};
+use dashmap::DashMap;
+use qfall_math::{
+    error::MathError,
+    integer::{MatPolyOverZ, PolyOverZ, Z},
+    integer_mod_q::{MatPolynomialRingZq, Modulus, PolynomialRingZq},
+    rational::Q,
+    traits::{Concatenate, GetCoefficient, GetEntry, GetNumColumns, GetNumRows, Pow, SetEntry},
+};
+use serde::{Deserialize, Serialize};
+
+/// This struct manages and stores the public parameters of a Ring-LWE based
+/// [`IBEScheme`] instance following the dual Regev IBE of [\[1\]](<index.html#:~:text=[1]>)
+/// over the ring `R_q = Z_q[X]/(X^n + 1)`.
+///
+/// Attributes:
+/// - `psf`: specifies the [`PSFGPVRing`] used for extracting secret keys,
+///     whose gadget parameters define `n`, `q` and the ring `R_q`
+/// - `alpha`: specifies the Gaussian parameter used for independent
+///     sampling of the encryption noise from the discrete Gaussian distribution
+/// - `storage`: is a [`DashMap`] which stores all previously computed secret keys
+///     under the key `SHA-256(master_pk) || SHA-256(identity)`
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::construction::identity_based_encryption::{RingDualRegevIBE, IBEScheme};
+/// use qfall_math::integer::Z;
+/// // setup public parameters and key pair
+/// let ibe = RingDualRegevIBE::default();
+/// let (pk, sk) = ibe.setup();
+///
+/// // extract a identity based secret key
+/// let identity = String::from("identity");
+/// let id_sk = ibe.extract(&pk, &sk, &identity).unwrap();
+///
+/// // encrypt a bit
+/// let msg = Z::ONE; // must be a bit, i.e. msg = 0 or 1
+/// let cipher = ibe.enc(&pk, &identity, &msg);
+///
+/// // decrypt
+/// let m = ibe.dec(&id_sk, &cipher).unwrap();
+///
+/// assert_eq!(msg, m)
+/// ```
+#[derive(Serialize, Deserialize)]
+pub struct RingDualRegevIBE {
+    pub psf: PSFGPVRing,
+    pub alpha: Q,
+    storage: DashMap<String, MatPolyOverZ>,
+}
+
+impl RingDualRegevIBE {
+    /// Initializes a [`RingDualRegevIBE`] struct with the ring `Z_q[X]/(X^n + 1)`
+    /// and the default [`GadgetParametersRing`].
+    /// The trapdoor is sampled with a Gaussian parameter of 1.005 as done by
+    /// [`FDH::init_gpv_ring`](crate::construction::signature::FDH::init_gpv_ring).
+    ///
+    /// Parameters:
+    /// - `n`: specifies the degree of the ring and the security parameter
+    /// - `q`: specifies the modulus
+    /// - `r`: specifies the Gaussian parameter for sampleD
+    /// - `alpha`: specifies the Gaussian parameter of the encryption noise
+    ///
+    /// Returns a [`RingDualRegevIBE`] instance.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::identity_based_encryption::RingDualRegevIBE;
+    ///
+    /// let ibe = RingDualRegevIBE::new(8, 16411, 100, 0.0001);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if the security parameter `n` is not in `[1, i64::MAX]`.
+    /// - if `q <= 1`.
+    pub fn new(
+        n: impl Into<Z>,
+        q: impl Into<Modulus>,
+        r: impl Into<Q>,
+        alpha: impl Into<Q>,
+    ) -> Self {
+        let psf = PSFGPVRing {
+            gp: GadgetParametersRing::init_default(n, q),
+            s: r.into(),
+            s_td: Q::from(1.005_f64),
+        };
+
+        Self {
+            psf,
+            alpha: alpha.into(),
+            storage: DashMap::new(),
+        }
+    }
+
+    /// Initializes a [`RingDualRegevIBE`] struct for security parameter `n`,
+    /// which should be a power of two s.t. `X^n + 1` is irreducible, by choosing
+    /// - a prime `q ∈ [n^5 / 2, n^5]`,
+    /// - the Gaussian parameter `r = 8 * (4 * 1.005 * sqrt(n) + 1)` of the [`PSFGPVRing`]
+    ///     as in the tests of [`FDH::init_gpv_ring`](crate::construction::signature::FDH::init_gpv_ring),
+    /// - `α = 1/(2 * r * sqrt(n * (m + 1)) * log(n))` for `m = k + 2`,
+    ///     where `n * (m + 1)` accounts for the coefficients of `[a | H(id)]`.
+    ///
+    /// Returns a [`RingDualRegevIBE`] instance.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::identity_based_encryption::RingDualRegevIBE;
+    ///
+    /// let ibe = RingDualRegevIBE::new_from_n(8);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `n < 2`.
+    pub fn new_from_n(n: impl Into<Z>) -> Self {
+        let n: Z = n.into();
+        if n < Z::from(2) {
+            panic!("Security parameter n has to be larger than 1");
+        }
+
+        // generate prime q in [n^5 / 2, n^5]
+        let upper_bound: Z = n.pow(5).unwrap();
+        let lower_bound = upper_bound.div_ceil(2);
+        let q = Modulus::from(Z::sample_prime_uniform(&lower_bound, &upper_bound).unwrap());
+
+        let gadget = GadgetParametersRing::init_default(&n, &q);
+        // m = k + 2 ring elements, see `gen_trapdoor_ring_lwe`
+        let m = &gadget.k + 2;
+        let r: Q = (4 * Q::from(1.005_f64) * n.sqrt() + 1) * 8;
+        let alpha = 1 / (&r * 2 * (&n * (&m + Z::ONE)).sqrt() * n.log(2).unwrap());
+
+        let psf = PSFGPVRing {
+            gp: gadget,
+            s: r,
+            s_td: Q::from(1.005_f64),
+        };
+        Self {
+            psf,
+            alpha,
+            storage: DashMap::new(),
+        }
+    }
+
+    /// Returns the number `m = k + 2` of ring elements of the master public key.
+    fn m(&self) -> Z {
+        &self.psf.gp.k + 2
+    }
+
+    /// Returns the modulus `q` of the ring.
+    fn q(&self) -> Z {
+        self.psf.gp.modulus.get_q()
+    }
+
+    /// Checks the public parameters for security according to the requirements
+    /// of [`DualRegevIBE::check_security`](super::DualRegevIBE::check_security),
+    /// where the dimension of the lattice is `m * n`.
+    ///
+    /// The required properties are:
+    /// - q >= 5 * r * (m + 1)
+    /// - r >= sqrt(m * n)
+    /// - m * n > (n + 1) * log(q)
+    ///
+    /// Returns an empty result if the public parameters guarantees security w.r.t. `n`
+    /// or a [`MathError`] if the instance would not be secure.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::identity_based_encryption::RingDualRegevIBE;
+    /// let ibe = RingDualRegevIBE::default();
+    ///
+    /// assert!(ibe.check_security().is_ok());
+    /// ```
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`MathError`] of type [`InvalidIntegerInput`](MathError::InvalidIntegerInput)
+    ///     if at least one parameter was not chosen appropriately for a
+    ///     secure Ring Dual Regev IBE instance.
+    pub fn check_security(&self) -> Result<(), MathError> {
+        let q = Q::from(self.q());
+        let n = &self.psf.gp.n;
+        let m = self.m();
+
+        // q >= 5 * r * (m + 1)
+        if q < (5 * &self.psf.s) * (&m + Q::ONE) {
+            return Err(MathError::InvalidIntegerInput(String::from(
+                "Security is not guaranteed as q < 5 * r * (m + 1), but q >= 5 * r * (m + 1) is required.",
+            )));
+        }
+        // r >= sqrt(m * n)
+        if self.psf.s < (&m * n).sqrt() {
+            return Err(MathError::InvalidIntegerInput(String::from(
+                "Security is not guaranteed as r < sqrt(m * n), but r >= sqrt(m * n) is required.",
+            )));
+        }
+        // m * n > (n + 1) * log(q)
+        if Q::from(&m * n) <= (n + 1) * &q.log(2).unwrap() {
+            return Err(MathError::InvalidIntegerInput(String::from(
+                "Security is not guaranteed as m * n <= (n + 1) * log(q), but m * n > (n + 1) * log(q) is required.",
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Checks the public parameters for correctness according to the requirements
+    /// of [`DualRegevIBE::check_correctness`](super::DualRegevIBE::check_correctness),
+    /// where every ring element contributes `n` coefficients to the decryption noise.
+    ///
+    /// The required properties are:
+    /// - α <= 1/(2 * r * sqrt(n * (m + 1)) * log(n))
+    /// - q/4 - (α * q + r) * sqrt(m * n) > 0
+    ///
+    /// Returns an empty result if the public parameters guarantee correctness
+    /// with overwhelming probability or a [`MathError`] if the instance would
+    /// not be correct.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::identity_based_encryption::RingDualRegevIBE;
+    /// let ibe = RingDualRegevIBE::default();
+    ///
+    /// assert!(ibe.check_correctness().is_ok());
+    /// ```
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`MathError`] of type [`InvalidIntegerInput`](MathError::InvalidIntegerInput)
+    ///     if at least one parameter was not chosen appropriately for a
+    ///     correct Ring Dual Regev IBE instance.
+    pub fn check_correctness(&self) -> Result<(), MathError> {
+        let n = &self.psf.gp.n;
+        let m = self.m();
+        if n <= &Z::ONE {
+            return Err(MathError::InvalidIntegerInput(String::from(
+                "n must be chosen bigger than 1.",
+            )));
+        }
+
+        // α <= 1/(2 * r * sqrt(n * (m + 1)) * log(n))
+        let max_alpha = 1 / (2 * &self.psf.s * (n * (&m + Z::ONE)).sqrt() * n.log(2).unwrap());
+        if self.alpha > max_alpha {
+            return Err(MathError::InvalidIntegerInput(String::from(
+                "Correctness is not guaranteed as α > 1/(2 * r * sqrt(n * (m + 1)) * log(n)), \
+                but α <= 1/(2 * r * sqrt(n * (m + 1)) * log(n)) is required.",
+            )));
+        }
+
+        // q/4 - (α * q + r) * sqrt(m * n) > 0
+        let q = Q::from(self.q());
+        let noise = (&self.alpha * &q + &self.psf.s) * (&m * n).sqrt();
+        if q / Q::from(4) <= noise {
+            return Err(MathError::InvalidIntegerInput(String::from(
+                "Correctness is not guaranteed as the expected decryption noise exceeds q/4, \
+                but a positive noise margin is required.",
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Hashes `identity` into the identity-based syndrome `H(id) ∈ R_q^{1 x 1}`.
+    fn hash_identity(&self, identity: &str) -> MatPolynomialRingZq {
+        HashMatPolynomialRingZq {
+            modulus: self.psf.gp.modulus.clone(),
+            rows: 1,
+            cols: 1,
+        }
+        .hash(identity)
+    }
+}
+
+impl Default for RingDualRegevIBE {
+    /// Initializes a [`RingDualRegevIBE`] struct with parameters generated by
+    /// `RingDualRegevIBE::new_from_n(8)`.
+    /// This parameter choice is not secure as the dimension of the lattice is too small,
+    /// but it provides an efficient working example.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::identity_based_encryption::RingDualRegevIBE;
+    ///
+    /// let ibe = RingDualRegevIBE::default();
+    /// ```
+    fn default() -> Self {
+        RingDualRegevIBE::new_from_n(8)
+    }
+}
+
+impl IBEScheme for RingDualRegevIBE {
+    type Cipher = MatPolynomialRingZq;
+    type MasterPublicKey = MatPolynomialRingZq;
+    type MasterSecretKey = (MatPolyOverZ, MatPolyOverZ);
+    type SecretKey = MatPolyOverZ;
+    type Identity = String;
+
+    /// Generates a master key pair `(a, (r, e))` by calling
+    /// [`gen_trapdoor_ring_lwe`](crate::sample::g_trapdoor::gadget_ring::gen_trapdoor_ring_lwe)
+    /// via the [`PSFGPVRing`], i.e. `a = [1 | a_bar | g^t - a_bar * r + e] ∈ R_q^{1 x m}`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::identity_based_encryption::{RingDualRegevIBE, IBEScheme};
+    /// let ibe = RingDualRegevIBE::default();
+    ///
+    /// let (pk, sk) = ibe.setup();
+    /// ```
+    fn setup(&self) -> (Self::MasterPublicKey, Self::MasterSecretKey) {
+        self.psf.trap_gen()
+    }
+
+    /// Given an identity it extracts a corresponding secret key `x ∈ R^{m x 1}`
+    /// with `a * x = H(id)` by using the ring-variant of sampleD via samp_p of the [`PSFGPVRing`].
+    /// Secret keys are stored s.t. every identity obtains a single secret key.
+    ///
+    /// Parameters:
+    /// - `master_pk`: The master public key for the encryption scheme
+    /// - `master_sk`: The master secret key of the encryption scheme, namely
+    ///     the trapdoor for the [`PSFGPVRing`]
+    /// - `identity`: The identity, for which the corresponding secret key
+    ///     should be returned
+    ///
+    /// Returns the corresponding secret key of `identity` under public key
+    /// `master_pk`. The extraction of [`RingDualRegevIBE`] does not fail.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::identity_based_encryption::{IBEScheme, RingDualRegevIBE};
+    /// let ibe = RingDualRegevIBE::default();
+    /// let (master_pk, master_sk) = ibe.setup();
+    ///
+    /// let id = String::from("identity");
+    /// let sk = ibe.extract(&master_pk, &master_sk, &id).unwrap();
+    /// ```
+    fn extract(
+        &self,
+        master_pk: &Self::MasterPublicKey,
+        master_sk: &Self::MasterSecretKey,
+        identity: &Self::Identity,
+    ) -> Result<Self::SecretKey, CryptoError> {
+        let key = format!("{}{}", sha256(&master_pk.to_string()), sha256(identity));
+
+        // the entry is locked while the secret key is computed s.t. concurrent
+        // calls for the same identity can not compute two different secret keys
+        Ok(self
+            .storage
+            .entry(key)
+            .or_insert_with(|| {
+                let u = self.hash_identity(identity);
+                self.psf.samp_p(master_pk, master_sk, &u)
+            })
+            .value()
+            .clone())
+    }
+
+    /// Generates an encryption of `message mod 2` for the provided public key
+    /// and identity by following these steps:
+    /// - s <- R_q
+    /// - e <- χ^{1 x (m+1)}
+    /// - c^t = s * [a | H(id)] + e^t + [0^{1 x m} | msg * ⌊q/2⌋]
+    ///     where χ samples every coefficient from the discrete Gaussian
+    ///     distribution with center 0 and Gaussian parameter q * α.
+    ///
+    /// Parameters:
+    /// - `master_pk`: specifies the master public key `a`
+    /// - `identity`: specifies the identity used for encryption
+    /// - `message`: specifies the message that should be encrypted
+    ///
+    /// Returns the cipher `c^t ∈ R_q^{1 x (m+1)}` of type [`MatPolynomialRingZq`].
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::identity_based_encryption::{RingDualRegevIBE, IBEScheme};
+    /// let ibe = RingDualRegevIBE::default();
+    /// let (pk, sk) = ibe.setup();
+    ///
+    /// let id = String::from("identity");
+    /// let cipher = ibe.enc(&pk, &id, 1);
+    /// ```
+    fn enc(
+        &self,
+        master_pk: &Self::MasterPublicKey,
+        identity: &Self::Identity,
+        message: impl Into<Z>,
+    ) -> Self::Cipher {
+        let message: Z = message.into().modulo(2);
+        let modulus = &self.psf.gp.modulus;
+        let q = self.q();
+
+        // pk = [a | H(id)]
+        let identity_based_pk = master_pk
+            .concat_horizontal(&self.hash_identity(identity))
+            .unwrap();
+        let num_columns = identity_based_pk.get_num_columns();
+
+        // s <- R_q
+        let mut vec_s = MatPolyOverZ::new(1, 1);
+        vec_s
+            .set_entry(
+                0,
+                0,
+                PolyOverZ::sample_uniform(modulus.get_degree() - 1, 0, &q).unwrap(),
+            )
+            .unwrap();
+        let vec_s = MatPolynomialRingZq::from((&vec_s, modulus));
+
+        // e^t + [0^{1 x m} | msg * ⌊q/2⌋]
+        let gaussian_parameter = &self.alpha * Q::from(&q);
+        let mut vec_e_t = MatPolyOverZ::new(1, num_columns);
+        for i in 0..num_columns {
+            let mut e_i = PolyOverZ::sample_discrete_gauss(
+                modulus.get_degree() - 1,
+                &self.psf.gp.n,
+                0,
+                &gaussian_parameter,
+            )
+            .unwrap();
+            if i == num_columns - 1 {
+                e_i = e_i + PolyOverZ::from(ZqArith::scale_by_half_q(&message, &q));
+            }
+            vec_e_t.set_entry(0, i, e_i).unwrap();
+        }
+
+        // c^t = s * [a | H(id)] + e^t + [0^{1 x m} | msg * ⌊q/2⌋]
+        vec_s * identity_based_pk + MatPolynomialRingZq::from((&vec_e_t, modulus))
+    }
+
+    /// Decrypts the provided `cipher` using the secret key `sk_id` by following these steps:
+    /// - x = c^t * [-sk_id^t | 1]^t
+    /// - if the constant coefficient of x is closer to ⌊q/2⌋ than to 0, output 1.
+    ///     Otherwise, output 0.
+    ///
+    /// Parameters:
+    /// - `sk_id`: specifies the secret key `x` obtained by extract
+    /// - `cipher`: specifies the cipher `c^t`
+    ///
+    /// Returns the decryption of `cipher` as a [`Z`] instance
+    /// or a [`CryptoError`] if `sk_id` and `cipher` do not fit together.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::identity_based_encryption::{RingDualRegevIBE, IBEScheme};
+    /// use qfall_math::integer::Z;
+    /// let ibe = RingDualRegevIBE::default();
+    /// let (pk, sk) = ibe.setup();
+    /// let identity = String::from("identity");
+    /// let id_sk = ibe.extract(&pk, &sk, &identity).unwrap();
+    /// let cipher = ibe.enc(&pk, &identity, 1);
+    ///
+    /// let m = ibe.dec(&id_sk, &cipher).unwrap();
+    ///
+    /// assert_eq!(Z::ONE, m);
+    /// ```
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] of type [`IncompatibleParameters`](CryptoError::IncompatibleParameters)
+    ///     if `sk_id` is no column vector or `cipher` is no row vector with
+    ///     one entry more than `sk_id`.
+    fn dec(&self, sk_id: &Self::SecretKey, cipher: &Self::Cipher) -> Result<Z, CryptoError> {
+        if sk_id.get_num_columns() != 1
+            || cipher.get_num_rows() != 1
+            || cipher.get_num_columns() != sk_id.get_num_rows() + 1
+        {
+            return Err(CryptoError::IncompatibleParameters);
+        }
+
+        // [-sk_id^t | 1]^t
+        let mut one = MatPolyOverZ::new(1, 1);
+        one.set_entry(0, 0, PolyOverZ::from(1)).unwrap();
+        let minus_sk_id = MatPolyOverZ::new(sk_id.get_num_rows(), 1) - sk_id;
+        let tmp = minus_sk_id.concat_vertical(&one).unwrap();
+        let tmp = MatPolynomialRingZq::from((&tmp, &self.psf.gp.modulus));
+
+        let result: PolynomialRingZq = (cipher * tmp).get_entry(0, 0).unwrap();
+        let constant_coefficient: Z = result.get_coeff(0).unwrap();
+
+        Ok(ZqArith::round_to_bit(&constant_coefficient, &self.q()))
+    }
+
+    /// Checks the parameters for security using [`RingDualRegevIBE::check_security`].
+    ///
+    /// Returns an empty result or an error describing the violated requirement.
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] of type [`InvalidParameters`](CryptoError::InvalidParameters)
+    ///     if at least one parameter was not chosen appropriately for a
+    ///     secure Ring Dual Regev IBE instance.
+    fn check_security(&self) -> Result<(), CryptoError> {
+        RingDualRegevIBE::check_security(self).map_err(|e| CryptoError::InvalidParameters {
+            message: e.to_string(),
+        })
+    }
+
+    /// Checks the parameters for correctness using [`RingDualRegevIBE::check_correctness`].
+    ///
+    /// Returns an empty result or an error describing the violated requirement.
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] of type [`InvalidParameters`](CryptoError::InvalidParameters)
+    ///     if at least one parameter was not chosen appropriately for a
+    ///     correct Ring Dual Regev IBE instance.
+    fn check_correctness(&self) -> Result<(), CryptoError> {
+        RingDualRegevIBE::check_correctness(self).map_err(|e| CryptoError::InvalidParameters {
+            message: e.to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test_ring_dual_regev_ibe {
+    use super::RingDualRegevIBE;
+    use crate::construction::identity_based_encryption::IBEScheme;
+    use qfall_math::integer::Z;
+
+    /// Checks whether the [`Default`] parameter choice is suitable.
+    #[test]
+    fn default_suitable() {
+        let ibe = RingDualRegevIBE::default();
+
+        assert!(ibe.check_correctness().is_ok());
+        assert!(ibe.check_security().is_ok());
+    }
+
+    /// Checks whether `new_from_n` outputs suitable parameters for different `n`.
+    #[test]
+    fn new_from_n_suitable() {
+        for n in [8, 16] {
+            let ibe = RingDualRegevIBE::new_from_n(n);
+
+            assert!(ibe.check_correctness().is_ok());
+            assert!(ibe.check_security().is_ok());
+        }
+    }
+
+    /// Checks whether `new_from_n` panics for invalid input n.
+    #[test]
+    #[should_panic]
+    fn invalid_n() {
+        RingDualRegevIBE::new_from_n(1);
+    }
+
+    /// Checks whether the full-cycle of setup, extract, enc, dec works properly
+    /// for message 0.
+    #[test]
+    fn cycle_zero() {
+        let msg = Z::ZERO;
+        let id = String::from("Hello World!");
+        let ibe = RingDualRegevIBE::default();
+
+        let (pk, sk) = ibe.setup();
+        let id_sk = ibe.extract(&pk, &sk, &id).unwrap();
+        let cipher = ibe.enc(&pk, &id, &msg);
+        let m = ibe.dec(&id_sk, &cipher).unwrap();
+
+        assert_eq!(msg, m);
+    }
+
+    /// Checks whether the full-cycle of setup, extract, enc, dec works properly
+    /// for message 1.
+    #[test]
+    fn cycle_one() {
+        let msg = Z::ONE;
+        let id = String::from("Hello World!");
+        let ibe = RingDualRegevIBE::default();
+
+        let (pk, sk) = ibe.setup();
+        let id_sk = ibe.extract(&pk, &sk, &id).unwrap();
+        let cipher = ibe.enc(&pk, &id, &msg);
+        let m = ibe.dec(&id_sk, &cipher).unwrap();
+
+        assert_eq!(msg, m);
+    }
+
+    /// Checks whether the full-cycle works for several messages and identities.
+    #[test]
+    fn cycle_multiple_identities() {
+        let ibe = RingDualRegevIBE::default();
+        let (pk, sk) = ibe.setup();
+
+        for i in 0..4 {
+            let id = format!("identity {i}");
+            let id_sk = ibe.extract(&pk, &sk, &id).unwrap();
+
+            for msg in [Z::ZERO, Z::ONE] {
+                let cipher = ibe.enc(&pk, &id, &msg);
+                assert_eq!(msg, ibe.dec(&id_sk, &cipher).unwrap());
+            }
+        }
+    }
+
+    /// Ensures that the same secret key is returned for the same identity.
+    #[test]
+    fn extract_stored() {
+        let ibe = RingDualRegevIBE::default();
+        let (pk, sk) = ibe.setup();
+        let id = String::from("identity");
+
+        let id_sk = ibe.extract(&pk, &sk, &id).unwrap();
+
+        assert_eq!(id_sk, ibe.extract(&pk, &sk, &id).unwrap());
+        assert_ne!(
+            id_sk,
+            ibe.extract(&pk, &sk, &String::from("other")).unwrap()
+        );
+    }
+}
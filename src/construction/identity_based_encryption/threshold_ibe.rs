@@ -0,0 +1,416 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains a threshold variant of [`DualRegevIBE`], where the
+//! G-Trapdoor `R` of the master public key `A = [A_bar | G - A_bar * R]`
+//! is split via Shamir's secret sharing over `Z_q` among several key generation servers.
+//! Every set of at least `threshold` servers can jointly extract identity keys,
+//! while no single server holds `R`.
+//!
+//! The identity key of `id` is `x = [R * z ; z]` for `z = G^{-1}(H(id))`,
+//! which satisfies `A * x = G * z = H(id)`.
+//! As `R * z` is linear in `R`, every server computes its partial key `R_i * z`
+//! from its share `R_i` and the partial keys are combined by Lagrange interpolation.
+//!
+//! **WARNING:** In contrast to [`DualRegevIBE::extract`](super::IBEScheme::extract), the identity
+//! keys are not sampled from a discrete Gaussian, i.e. no perturbation hides `R`.
+//! Every identity key reveals `R * z` for a known `z`, hence about `n * ⌈log q⌉`
+//! identity keys suffice to recover `R`. This construction is meant for prototyping
+//! distributed extraction and does not provide security against key holders.
+//! Hence, it is only available with the non-default feature `experimental_threshold_ibe`
+//! and must not be used in production.
+
+use super::DualRegevIBE;
+use crate::{
+    construction::hash::sha256::hash_to_mat_zq_sha256,
+    error::CryptoError,
+    sample::g_trapdoor::gadget_classical::{find_solution_gadget_mat, gen_trapdoor},
+    utils::math_util::mod_inverse,
+};
+use qfall_math::{
+    integer::{MatZ, Z},
+    integer_mod_q::MatZq,
+    traits::{Concatenate, GetEntry, GetNumColumns, GetNumRows, SetEntry},
+};
+use serde::{Deserialize, Serialize};
+
+/// This struct manages and stores the public parameters of a [`ThresholdIBE`].
+///
+/// Attributes:
+/// - `ibe`: the [`DualRegevIBE`] defining the parameters,
+///     which is also used to encrypt and decrypt
+/// - `num_servers`: the number of key generation servers
+/// - `threshold`: the number of servers required to extract an identity key
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::construction::identity_based_encryption::{IBEScheme, ThresholdIBE};
+/// use qfall_math::integer::Z;
+/// let (ibe, pk, shares) = ThresholdIBE::setup(3, 2, 4);
+/// let id = "identity";
+///
+/// let partials = [
+///     ibe.distributed_extract(&pk, &shares[0], id),
+///     ibe.distributed_extract(&pk, &shares[2], id),
+/// ];
+/// let id_sk = ibe.combine_keys(&partials).unwrap();
+///
+/// let cipher = ibe.ibe.enc(&pk, &String::from(id), 1);
+/// assert_eq!(Z::ONE, ibe.ibe.dec(&id_sk, &cipher).unwrap());
+/// ```
+#[derive(Serialize, Deserialize)]
+pub struct ThresholdIBE {
+    pub ibe: DualRegevIBE,
+    num_servers: usize,
+    threshold: usize,
+}
+
+/// The share of the master secret key held by a single server.
+///
+/// Attributes:
+/// - `index`: the index of the server, which holds the share `R_i = f(index + 1)`
+/// - `share`: the evaluation of the sharing polynomial `f` with `f(0) = R`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KeyShare {
+    pub index: usize,
+    pub share: MatZq,
+}
+
+/// The contribution of a single server to the identity key of an identity.
+///
+/// Attributes:
+/// - `index`: the index of the server, which computed the partial key
+/// - `partial`: the partial key `R_i * z`
+/// - `gadget_preimage`: the public vector `z = G^{-1}(H(id))`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PartialKey {
+    pub index: usize,
+    pub partial: MatZq,
+    pub gadget_preimage: MatZ,
+}
+
+impl ThresholdIBE {
+    /// Generates a master public key `A = [A_bar | G - A_bar * R]` with the parameters of
+    /// [`DualRegevIBE::new_from_n`] and splits its trapdoor `R` into `num_servers` shares,
+    /// s.t. any `threshold` of them can extract identity keys.
+    /// The shares are the evaluations `f(1), ..., f(num_servers)` of the polynomial
+    /// `f(X) = R + C_1 * X + ... + C_{threshold - 1} * X^{threshold - 1}`
+    /// with uniform coefficients `C_j` over `Z_q`.
+    ///
+    /// Parameters:
+    /// - `num_servers`: the number of key generation servers
+    /// - `threshold`: the number of servers required to extract an identity key
+    /// - `security_param`: the security parameter `n`
+    ///
+    /// Returns the [`ThresholdIBE`], the master public key and one [`KeyShare`] per server.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::identity_based_encryption::ThresholdIBE;
+    ///
+    /// let (ibe, pk, shares) = ThresholdIBE::setup(5, 3, 4);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `threshold < 1` or `threshold > num_servers`.
+    /// - if `num_servers` is not smaller than the modulus `q`.
+    /// - if `security_param < 2`.
+    pub fn setup(
+        num_servers: usize,
+        threshold: usize,
+        security_param: impl Into<Z>,
+    ) -> (Self, MatZq, Vec<KeyShare>) {
+        assert!(
+            (1..=num_servers).contains(&threshold),
+            "The threshold has to be in [1, num_servers]."
+        );
+        let ibe = DualRegevIBE::new_from_n(security_param).unwrap();
+        let gp = &ibe.psf.gp;
+        assert!(
+            Z::from(num_servers as u64) < Z::from(&gp.q),
+            "The number of servers has to be smaller than q."
+        );
+
+        let a_bar = MatZq::sample_uniform(&gp.n, &gp.m_bar, &gp.q);
+        let tag = MatZq::identity(&gp.n, &gp.n, &gp.q);
+        let (master_pk, r) = gen_trapdoor(gp, &a_bar, &tag).unwrap();
+        let r = MatZq::from((&r, &gp.q));
+
+        let coefficients: Vec<MatZq> = (1..threshold)
+            .map(|_| MatZq::sample_uniform(r.get_num_rows(), r.get_num_columns(), &gp.q))
+            .collect();
+        let shares = (0..num_servers)
+            .map(|index| {
+                // evaluate f(x) for x = index + 1 using Horner's method
+                let x = Z::from(index as u64 + 1);
+                let mut share = MatZq::new(r.get_num_rows(), r.get_num_columns(), &gp.q);
+                for coefficient in coefficients.iter().rev() {
+                    share = &x * &share + coefficient;
+                }
+                share = &x * &share + &r;
+
+                KeyShare { index, share }
+            })
+            .collect();
+
+        (
+            Self {
+                ibe,
+                num_servers,
+                threshold,
+            },
+            master_pk,
+            shares,
+        )
+    }
+
+    /// Computes the contribution `R_i * z` of the server holding `share`
+    /// to the identity key of `identity`, where `z = G^{-1}(H(id))`.
+    ///
+    /// Parameters:
+    /// - `master_pk`: specifies the master public key
+    /// - `share`: the share of the master secret key held by this server
+    /// - `identity`: the identity for which the identity key is extracted
+    ///
+    /// Returns the [`PartialKey`] of this server.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::identity_based_encryption::ThresholdIBE;
+    /// let (ibe, pk, shares) = ThresholdIBE::setup(3, 2, 4);
+    ///
+    /// let partial = ibe.distributed_extract(&pk, &shares[0], "identity");
+    /// ```
+    pub fn distributed_extract(
+        &self,
+        master_pk: &MatZq,
+        share: &KeyShare,
+        identity: &str,
+    ) -> PartialKey {
+        let u = hash_to_mat_zq_sha256(identity, master_pk.get_num_rows(), 1, master_pk.get_mod());
+        let gadget_preimage =
+            find_solution_gadget_mat(&u, &self.ibe.psf.gp.k, &self.ibe.psf.gp.base);
+
+        PartialKey {
+            index: share.index,
+            partial: &share.share * &gadget_preimage,
+            gadget_preimage,
+        }
+    }
+
+    /// Combines the partial keys of at least `threshold` distinct servers
+    /// to the identity key `x = [R * z ; z]`, which can be used with
+    /// [`DualRegevIBE::dec`](super::IBEScheme::dec).
+    /// If more than `threshold` partial keys are provided, the first `threshold`
+    /// ones of distinct servers are used.
+    ///
+    /// Parameters:
+    /// - `partials`: the partial keys of the participating servers
+    ///
+    /// Returns the identity key or a [`CryptoError`] if it can not be reconstructed.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::identity_based_encryption::ThresholdIBE;
+    /// let (ibe, pk, shares) = ThresholdIBE::setup(3, 2, 4);
+    ///
+    /// let partials: Vec<_> = shares
+    ///     .iter()
+    ///     .map(|share| ibe.distributed_extract(&pk, share, "identity"))
+    ///     .collect();
+    ///
+    /// assert!(ibe.combine_keys(&partials).is_ok());
+    /// assert!(ibe.combine_keys(&partials[..1]).is_err());
+    /// ```
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] of type [`InvalidParameters`](CryptoError::InvalidParameters)
+    ///     if less than `threshold` partial keys of distinct servers are provided,
+    ///     if a server index is out of range or if the partial keys belong to different identities.
+    pub fn combine_keys(&self, partials: &[PartialKey]) -> Result<MatZ, CryptoError> {
+        let mut distinct: Vec<&PartialKey> = vec![];
+        for partial in partials {
+            if partial.index >= self.num_servers {
+                return Err(CryptoError::InvalidParameters {
+                    message: format!("There is no server with index {}.", partial.index),
+                });
+            }
+            if partial.gadget_preimage != partials[0].gadget_preimage {
+                return Err(CryptoError::InvalidParameters {
+                    message: String::from("The partial keys belong to different identities."),
+                });
+            }
+            if distinct.iter().all(|other| other.index != partial.index) {
+                distinct.push(partial);
+            }
+        }
+        if distinct.len() < self.threshold {
+            return Err(CryptoError::InvalidParameters {
+                message: format!(
+                    "At least {} partial keys of distinct servers are required, but only {} were provided.",
+                    self.threshold,
+                    distinct.len()
+                ),
+            });
+        }
+        distinct.truncate(self.threshold);
+
+        let r_z = centered_representative(&interpolate(&distinct));
+        Ok(r_z.concat_vertical(&distinct[0].gadget_preimage)?)
+    }
+}
+
+/// Computes `f(0) * z = Σ_i λ_i * R_i * z` from the partial keys of distinct servers
+/// using the Lagrange coefficients `λ_i = Π_{j != i} x_j / (x_j - x_i)` for `x_i = index + 1`.
+/// The result is only equal to `R * z` if at least `threshold` partial keys are provided.
+fn interpolate(partials: &[&PartialKey]) -> MatZq {
+    let q = Z::from(&partials[0].partial.get_mod());
+
+    let mut result = MatZq::new(
+        partials[0].partial.get_num_rows(),
+        partials[0].partial.get_num_columns(),
+        partials[0].partial.get_mod(),
+    );
+    for partial_i in partials {
+        let x_i = Z::from(partial_i.index as u64 + 1);
+        let mut numerator = Z::ONE;
+        let mut denominator = Z::ONE;
+        for partial_j in partials {
+            if partial_i.index != partial_j.index {
+                let x_j = Z::from(partial_j.index as u64 + 1);
+                numerator = (numerator * &x_j).modulo(&q);
+                denominator = (denominator * (x_j - &x_i)).modulo(&q);
+            }
+        }
+        // the denominator is invertible as q is prime and all x_j are distinct and smaller than q
+        let lagrange = numerator * mod_inverse(&denominator, &q).unwrap();
+        result = result + &lagrange * &partial_i.partial;
+    }
+
+    result
+}
+
+/// Returns the representative of every entry of `value` in `(-q/2, q/2]`.
+fn centered_representative(value: &MatZq) -> MatZ {
+    let q = Z::from(&value.get_mod());
+    let q_half = q.div_floor(2);
+
+    let mut out = value.get_representative_0_modulus();
+    for row in 0..out.get_num_rows() {
+        for column in 0..out.get_num_columns() {
+            let entry: Z = out.get_entry(row, column).unwrap();
+            if entry > q_half {
+                out.set_entry(row, column, entry - &q).unwrap();
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod test_threshold_ibe {
+    use super::{centered_representative, interpolate, ThresholdIBE};
+    use crate::construction::{
+        hash::sha256::hash_to_mat_zq_sha256, identity_based_encryption::IBEScheme,
+    };
+    use qfall_math::{
+        integer::{MatZ, Z},
+        integer_mod_q::MatZq,
+        traits::{Concatenate, GetNumRows},
+    };
+
+    /// Returns `H(identity)` for `master_pk`.
+    fn syndrome(master_pk: &MatZq, identity: &str) -> MatZq {
+        hash_to_mat_zq_sha256(identity, master_pk.get_num_rows(), 1, master_pk.get_mod())
+    }
+
+    /// Ensures that `threshold` partial keys combine to a valid identity key,
+    /// which decrypts ciphertexts of [`DualRegevIBE`](super::DualRegevIBE).
+    #[test]
+    fn threshold_keys_combine() {
+        let (ibe, pk, shares) = ThresholdIBE::setup(5, 3, 4);
+        let id = "identity";
+
+        let partials: Vec<_> = [0, 2, 4]
+            .iter()
+            .map(|i| ibe.distributed_extract(&pk, &shares[*i], id))
+            .collect();
+        let id_sk = ibe.combine_keys(&partials).unwrap();
+
+        assert_eq!(syndrome(&pk, id), &pk * &id_sk);
+        for msg in [Z::ZERO, Z::ONE] {
+            let cipher = ibe.ibe.enc(&pk, &String::from(id), &msg);
+            assert_eq!(msg, ibe.ibe.dec(&id_sk, &cipher).unwrap());
+        }
+    }
+
+    /// Ensures that all sets of `threshold` servers reconstruct the same identity key.
+    #[test]
+    fn subsets_agree() {
+        let (ibe, pk, shares) = ThresholdIBE::setup(4, 2, 4);
+        let partials: Vec<_> = shares
+            .iter()
+            .map(|share| ibe.distributed_extract(&pk, share, "identity"))
+            .collect();
+
+        let id_sk = ibe.combine_keys(&partials[..2]).unwrap();
+
+        assert_eq!(id_sk, ibe.combine_keys(&partials[2..]).unwrap());
+        assert_eq!(
+            id_sk,
+            ibe.combine_keys(&[partials[3].clone(), partials[0].clone()])
+                .unwrap()
+        );
+        assert_eq!(id_sk, ibe.combine_keys(&partials).unwrap());
+    }
+
+    /// Ensures that `threshold - 1` partial keys are rejected and do not interpolate
+    /// to a valid identity key.
+    #[test]
+    fn too_few_partial_keys() {
+        let (ibe, pk, shares) = ThresholdIBE::setup(5, 3, 4);
+        let id = "identity";
+        let partials: Vec<_> = shares[..2]
+            .iter()
+            .map(|share| ibe.distributed_extract(&pk, share, id))
+            .collect();
+
+        let r_z = centered_representative(&interpolate(&[&partials[0], &partials[1]]));
+        let candidate: MatZ = r_z.concat_vertical(&partials[0].gadget_preimage).unwrap();
+
+        assert!(ibe.combine_keys(&partials).is_err());
+        assert!(ibe
+            .combine_keys(&[
+                partials[0].clone(),
+                partials[0].clone(),
+                partials[0].clone()
+            ])
+            .is_err());
+        assert_ne!(syndrome(&pk, id), &pk * &candidate);
+    }
+
+    /// Ensures that partial keys of different identities or unknown servers are rejected.
+    #[test]
+    fn invalid_partial_keys() {
+        let (ibe, pk, shares) = ThresholdIBE::setup(3, 2, 4);
+        let partial_a = ibe.distributed_extract(&pk, &shares[0], "a");
+        let partial_b = ibe.distributed_extract(&pk, &shares[1], "b");
+        let mut unknown = ibe.distributed_extract(&pk, &shares[1], "a");
+        unknown.index = 3;
+
+        assert!(ibe.combine_keys(&[partial_a.clone(), partial_b]).is_err());
+        assert!(ibe.combine_keys(&[partial_a, unknown]).is_err());
+    }
+
+    /// Ensures that an invalid threshold results in a panic.
+    #[test]
+    #[should_panic]
+    fn invalid_threshold() {
+        let _ = ThresholdIBE::setup(2, 3, 4);
+    }
+}
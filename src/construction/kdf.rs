@@ -0,0 +1,281 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains the HMAC-based key derivation function (HKDF)
+//! using HMAC-SHA256 as specified in [RFC 5869](https://www.rfc-editor.org/rfc/rfc5869),
+//! which can be used to derive symmetric keys from values shared via
+//! lattice-based constructions.
+
+use hmac::{Hmac, Mac};
+use qfall_math::integer::Z;
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// The length of the output of SHA256 in bytes.
+const HASH_LEN: usize = 32;
+
+/// The `info` string used by [`lwe_to_symmetric_key`].
+const LWE_KEY_INFO: &str = "qFALL-crypto LWE symmetric key";
+
+/// Computes `HMAC-SHA256(key, data)`.
+fn hmac_sha256(key: &[u8], data: &[&[u8]]) -> [u8; HASH_LEN] {
+    // HMAC accepts keys of any length, hence `new_from_slice` can not fail
+    let mut mac = HmacSha256::new_from_slice(key).unwrap();
+    for chunk in data {
+        mac.update(chunk);
+    }
+
+    let mut out = [0u8; HASH_LEN];
+    out.copy_from_slice(&mac.finalize().into_bytes());
+    out
+}
+
+/// Performs the extract step of HKDF, i.e. it computes a pseudorandom key
+/// `PRK = HMAC-SHA256(salt, ikm)` according to Section 2.2 of RFC 5869.
+///
+/// Parameters:
+/// - `salt`: an optional salt value; if it is empty, a string of 32 zeros is used
+/// - `ikm`: the input keying material
+///
+/// Returns the pseudorandom key of 32 bytes.
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::construction::kdf::hkdf_extract;
+///
+/// let prk = hkdf_extract(b"salt", b"input keying material");
+/// ```
+pub fn hkdf_extract(salt: &[u8], ikm: &[u8]) -> [u8; 32] {
+    if salt.is_empty() {
+        hmac_sha256(&[0u8; HASH_LEN], &[ikm])
+    } else {
+        hmac_sha256(salt, &[ikm])
+    }
+}
+
+/// Performs the expand step of HKDF according to Section 2.3 of RFC 5869
+/// for arbitrary `info` bytes.
+///
+/// # Panics ...
+/// - if `out_len > 255 * 32`.
+fn hkdf_expand_bytes(prk: &[u8; 32], info: &[u8], out_len: usize) -> Vec<u8> {
+    assert!(
+        out_len <= 255 * HASH_LEN,
+        "HKDF can output at most {} bytes, but {out_len} were requested.",
+        255 * HASH_LEN
+    );
+
+    let mut okm = Vec::with_capacity(out_len);
+    let mut block: Vec<u8> = vec![];
+    let mut counter: u8 = 1;
+    while okm.len() < out_len {
+        block = hmac_sha256(prk, &[block.as_slice(), info, &[counter]]).to_vec();
+        okm.extend_from_slice(&block);
+        counter = counter.wrapping_add(1);
+    }
+    okm.truncate(out_len);
+
+    okm
+}
+
+/// Performs the expand step of HKDF, i.e. it expands the pseudorandom key `prk`
+/// to `out_len` bytes of output keying material bound to `info`
+/// according to Section 2.3 of RFC 5869.
+///
+/// Parameters:
+/// - `prk`: a pseudorandom key, e.g. the output of [`hkdf_extract`]
+/// - `info`: context and application specific information
+/// - `out_len`: the number of bytes of the output
+///
+/// Returns the output keying material of length `out_len`.
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::construction::kdf::{hkdf_expand, hkdf_extract};
+///
+/// let prk = hkdf_extract(b"salt", b"input keying material");
+/// let okm = hkdf_expand(&prk, "application key", 64);
+///
+/// assert_eq!(64, okm.len());
+/// ```
+///
+/// # Panics ...
+/// - if `out_len > 255 * 32`.
+pub fn hkdf_expand(prk: &[u8; 32], info: &str, out_len: usize) -> Vec<u8> {
+    hkdf_expand_bytes(prk, info.as_bytes(), out_len)
+}
+
+/// Derives a 256-bit symmetric key from a value shared via an LWE-based construction.
+/// The value is reduced modulo `q` and quantized to `⌈log_2(q) / 8⌉` bytes
+/// in little-endian order, which are used as input keying material for HKDF.
+///
+/// Parameters:
+/// - `lwe_value`: the shared value, e.g. a noisy inner product
+/// - `q`: the modulus of the shared value
+///
+/// Returns a symmetric key of 32 bytes.
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::construction::kdf::lwe_to_symmetric_key;
+/// use qfall_math::integer::Z;
+///
+/// let key = lwe_to_symmetric_key(&Z::from(12345), &Z::from(65521));
+/// let same_key = lwe_to_symmetric_key(&Z::from(12345 + 65521), &Z::from(65521));
+///
+/// assert_eq!(key, same_key);
+/// ```
+///
+/// # Panics ...
+/// - if `q < 1`.
+pub fn lwe_to_symmetric_key(lwe_value: &Z, q: &Z) -> [u8; 32] {
+    let bits = lwe_value.modulo(q).to_bits();
+    let nr_bytes = (q.bits() as usize).div_ceil(8).max(1);
+
+    let mut ikm = vec![0u8; nr_bytes];
+    for (i, bit) in bits.iter().enumerate() {
+        if *bit {
+            ikm[i / 8] |= 1 << (i % 8);
+        }
+    }
+
+    let prk = hkdf_extract(&[], &ikm);
+    let mut key = [0u8; 32];
+    key.copy_from_slice(&hkdf_expand(&prk, LWE_KEY_INFO, 32));
+    key
+}
+
+#[cfg(test)]
+mod test_hkdf {
+    use super::{hkdf_expand, hkdf_expand_bytes, hkdf_extract};
+
+    /// Converts bytes to a lowercase hex string.
+    fn to_hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+    }
+
+    /// Checks test case 1 of RFC 5869.
+    #[test]
+    fn rfc_test_case_1() {
+        let ikm = [0x0b; 22];
+        let salt: Vec<u8> = (0x00..=0x0c).collect();
+        let info: Vec<u8> = (0xf0..=0xf9).collect();
+
+        let prk = hkdf_extract(&salt, &ikm);
+        let okm = hkdf_expand_bytes(&prk, &info, 42);
+
+        assert_eq!(
+            "077709362c2e32df0ddc3f0dc47bba6390b6c73bb50f9c3122ec844ad7c2b3e5",
+            to_hex(&prk)
+        );
+        assert_eq!(
+            "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf\
+            34007208d5b887185865",
+            to_hex(&okm)
+        );
+    }
+
+    /// Checks test case 2 of RFC 5869 with longer inputs and outputs.
+    #[test]
+    fn rfc_test_case_2() {
+        let ikm: Vec<u8> = (0x00..=0x4f).collect();
+        let salt: Vec<u8> = (0x60..=0xaf).collect();
+        let info: Vec<u8> = (0xb0..=0xff).collect();
+
+        let prk = hkdf_extract(&salt, &ikm);
+        let okm = hkdf_expand_bytes(&prk, &info, 82);
+
+        assert_eq!(
+            "06a6b88c5853361a06104c9ceb35b45cef760014904671014a193f40c15fc244",
+            to_hex(&prk)
+        );
+        assert_eq!(
+            "b11e398dc80327a1c8e7f78c596a49344f012eda2d4efad8a050cc4c19afa97c\
+            59045a99cac7827271cb41c65e590e09da3275600c2f09b8367793a9aca3db71\
+            cc30c58179ec3e87c14c01d5c1f3434f1d87",
+            to_hex(&okm)
+        );
+    }
+
+    /// Checks test case 3 of RFC 5869 with empty salt and info.
+    #[test]
+    fn rfc_test_case_3() {
+        let ikm = [0x0b; 22];
+
+        let prk = hkdf_extract(&[], &ikm);
+        let okm = hkdf_expand(&prk, "", 42);
+
+        assert_eq!(
+            "19ef24a32c717b167f33a91d6f648bdf96596776afdb6377ac434c1c293ccb04",
+            to_hex(&prk)
+        );
+        assert_eq!(
+            "8da4e775a563c18f715f802a063c5a31b8a11f5c5ee1879ec3454e5f3c738d2d\
+            9d201395faa4b61a96c8",
+            to_hex(&okm)
+        );
+    }
+
+    /// Ensures that the output has the requested length and
+    /// shorter outputs are prefixes of longer ones.
+    #[test]
+    fn output_length() {
+        let prk = hkdf_extract(b"salt", b"ikm");
+
+        let short = hkdf_expand(&prk, "info", 1);
+        let long = hkdf_expand(&prk, "info", 255 * 32);
+
+        assert_eq!(1, short.len());
+        assert_eq!(255 * 32, long.len());
+        assert_eq!(short[..], long[..1]);
+    }
+
+    /// Ensures that too long outputs are rejected.
+    #[test]
+    #[should_panic]
+    fn too_long_output() {
+        let prk = hkdf_extract(b"salt", b"ikm");
+
+        let _ = hkdf_expand(&prk, "info", 255 * 32 + 1);
+    }
+}
+
+#[cfg(test)]
+mod test_lwe_to_symmetric_key {
+    use super::lwe_to_symmetric_key;
+    use qfall_math::integer::Z;
+
+    /// Checks the derived key for a fixed value, which was computed
+    /// independently with HKDF-SHA256 on the input keying material `[0x39, 0x30]`.
+    #[test]
+    fn fixed_value() {
+        let key = lwe_to_symmetric_key(&Z::from(12345), &Z::from(65521));
+
+        let hex: String = key.iter().map(|byte| format!("{byte:02x}")).collect();
+        assert_eq!(
+            "0bf874c2ccbc2b7b3c7ba3af9cfee1995339a21481f1fb8808ae5e823499adfd",
+            hex
+        );
+    }
+
+    /// Ensures that values are reduced modulo `q` before deriving the key.
+    #[test]
+    fn reduced_mod_q() {
+        let q = Z::from(65521);
+
+        assert_eq!(
+            lwe_to_symmetric_key(&Z::from(65520), &q),
+            lwe_to_symmetric_key(&Z::MINUS_ONE, &q)
+        );
+        assert_ne!(
+            lwe_to_symmetric_key(&Z::from(1), &q),
+            lwe_to_symmetric_key(&Z::from(2), &q)
+        );
+    }
+}
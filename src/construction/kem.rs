@@ -0,0 +1,57 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module provides the trait a struct should implement if it is an
+//! instance of a key encapsulation mechanism (KEM). Furthermore, it contains
+//! cryptographic schemes implementing the [`KemScheme`] trait.
+
+mod dual_regev_kem;
+
+use crate::error::CryptoError;
+
+pub use dual_regev_kem::{DualRegevKem, SharedSecret};
+
+/// This trait should be implemented by every key encapsulation mechanism.
+/// It offers a simple interface to use and implement KEMs.
+pub trait KemScheme {
+    type PublicKey;
+    type SecretKey;
+    type Cipher;
+    type SharedSecret;
+
+    /// Generates a public key pair `(pk, sk)` suitable for the specific scheme.
+    ///
+    /// Returns a tuple `(pk, sk)` consisting of [`Self::PublicKey`] and [`Self::SecretKey`].
+    fn gen(&self) -> (Self::PublicKey, Self::SecretKey);
+
+    /// Samples a fresh shared secret and encapsulates it with the public key `pk`.
+    ///
+    /// Parameters:
+    /// - `pk`: specifies the public key
+    ///
+    /// Returns the shared secret and the cipher encapsulating it.
+    fn encaps(&self, pk: &Self::PublicKey) -> (Self::SharedSecret, Self::Cipher);
+
+    /// Decapsulates the shared secret from `cipher` using the secret key `sk`.
+    ///
+    /// Parameters:
+    /// - `sk`: specifies the secret key
+    /// - `cipher`: specifies the cipher encapsulating the shared secret
+    ///
+    /// Returns the shared secret encapsulated in `cipher` or a [`CryptoError`]
+    /// if `sk` and `cipher` do not fit the parameters of the scheme.
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] of type [`IncompatibleParameters`](CryptoError::IncompatibleParameters)
+    ///     if the dimensions or the modulus of `sk` or `cipher` do not match the scheme.
+    fn decaps(
+        &self,
+        sk: &Self::SecretKey,
+        cipher: &Self::Cipher,
+    ) -> Result<Self::SharedSecret, CryptoError>;
+}
@@ -0,0 +1,376 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains a key encapsulation mechanism based on the
+//! public key Dual Regev encryption scheme.
+
+use super::KemScheme;
+use crate::{
+    construction::pk_encryption::{DualRegev, PKEncryptionScheme},
+    error::CryptoError,
+};
+use qfall_math::{
+    integer::{MatZ, Z},
+    integer_mod_q::MatZq,
+    traits::{GetEntry, GetNumColumns, GetNumRows},
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// The number of bits encapsulated by [`DualRegevKem::encaps`].
+const KEY_BITS: i64 = 256;
+
+/// The shared secret of the [`DualRegevKem`].
+pub type SharedSecret = [u8; 32];
+
+/// This struct manages and stores the public parameters of a key encapsulation
+/// mechanism based on [`DualRegev`].
+/// A uniform key of `256` bits is encrypted with [`DualRegev::enc_vec`] and
+/// the shared secret is derived by hashing the key together with the cipher
+/// using SHA-256. Hence, any modification of the cipher results in an
+/// unrelated shared secret during decapsulation.
+///
+/// Attributes:
+/// - `dual_regev`: the [`DualRegev`] instance used to encapsulate the key
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::construction::kem::{DualRegevKem, KemScheme};
+///
+/// let kem = DualRegevKem::default();
+/// let (pk, sk) = kem.gen();
+///
+/// let (secret, cipher) = kem.encaps(&pk);
+///
+/// assert_eq!(secret, kem.decaps(&sk, &cipher).unwrap());
+/// ```
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct DualRegevKem {
+    pub dual_regev: DualRegev,
+}
+
+impl DualRegevKem {
+    /// Initializes a [`DualRegevKem`] instance based on `DualRegev::new_from_n(n)`.
+    ///
+    /// Parameters:
+    /// - `n`: specifies the security parameter
+    ///
+    /// Returns a correct and secure [`DualRegevKem`] instance
+    /// or a [`CryptoError`] if `n` is too small.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::kem::DualRegevKem;
+    ///
+    /// let kem = DualRegevKem::new_from_n(15).unwrap();
+    /// ```
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] of type [`InvalidParameters`](CryptoError::InvalidParameters)
+    ///     if `n < 10`.
+    ///
+    /// # Panics ...
+    /// - if `n` does not fit into an [`i64`].
+    pub fn new_from_n(n: impl Into<Z>) -> Result<Self, CryptoError> {
+        let n = n.into();
+        if n < Z::from(10) {
+            return Err(CryptoError::InvalidParameters {
+                message: String::from(
+                    "Choose n >= 10 as the parameters do not ensure correctness otherwise.",
+                ),
+            });
+        }
+
+        Ok(Self {
+            dual_regev: DualRegev::new_from_n(n),
+        })
+    }
+
+    /// Checks the public parameters for correctness using [`DualRegev::check_correctness`].
+    ///
+    /// Returns an empty result if the public parameters guarantee correctness
+    /// or a [`CryptoError`] if the instance would not be correct.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::kem::DualRegevKem;
+    /// let kem = DualRegevKem::default();
+    ///
+    /// assert!(kem.check_correctness().is_ok());
+    /// ```
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] of type [`InvalidParameters`](CryptoError::InvalidParameters)
+    ///     if at least one parameter was not chosen appropriately for a
+    ///     correct Dual Regev public key encryption instance.
+    pub fn check_correctness(&self) -> Result<(), CryptoError> {
+        self.dual_regev
+            .check_correctness()
+            .map_err(|err| CryptoError::InvalidParameters {
+                message: err.to_string(),
+            })
+    }
+
+    /// Checks the public parameters for security using [`DualRegev::check_security`].
+    ///
+    /// Returns an empty result if the public parameters guarantee security w.r.t. `n`
+    /// or a [`CryptoError`] if the instance would not be secure.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::kem::DualRegevKem;
+    /// let kem = DualRegevKem::default();
+    ///
+    /// assert!(kem.check_security().is_ok());
+    /// ```
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] of type [`InvalidParameters`](CryptoError::InvalidParameters)
+    ///     if at least one parameter was not chosen appropriately for a
+    ///     secure Dual Regev public key encryption instance.
+    pub fn check_security(&self) -> Result<(), CryptoError> {
+        self.dual_regev
+            .check_security()
+            .map_err(|err| CryptoError::InvalidParameters {
+                message: err.to_string(),
+            })
+    }
+
+    /// Derives the shared secret as `SHA-256(key || cipher)`.
+    fn derive_secret(key: &MatZ, cipher: &MatZq) -> SharedSecret {
+        let mut bytes = vec![0u8; KEY_BITS as usize / 8];
+        for i in 0..KEY_BITS {
+            let bit: Z = key.get_entry(i, 0).unwrap();
+            if bit == Z::ONE {
+                bytes[i as usize / 8] |= 1 << (i % 8);
+            }
+        }
+
+        let mut hasher = Sha256::new();
+        hasher.update(bytes);
+        hasher.update(cipher.to_string());
+        hasher.finalize().into()
+    }
+}
+
+impl KemScheme for DualRegevKem {
+    type PublicKey = MatZq;
+    type SecretKey = MatZ;
+    type Cipher = MatZq;
+    type SharedSecret = SharedSecret;
+
+    /// Generates a (pk, sk) pair using [`DualRegev::gen`].
+    ///
+    /// Then, `pk = A` of type [`MatZq`] and `sk = x` of type [`MatZ`] are returned.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::kem::{DualRegevKem, KemScheme};
+    /// let kem = DualRegevKem::default();
+    ///
+    /// let (pk, sk) = kem.gen();
+    /// ```
+    fn gen(&self) -> (Self::PublicKey, Self::SecretKey) {
+        self.dual_regev.gen()
+    }
+
+    /// Encapsulates a fresh shared secret by following these steps:
+    /// - k <- {0,1}^256
+    /// - c = [`DualRegev::enc_vec`](pk, k)
+    /// - K = SHA-256(k || c)
+    ///
+    /// Parameters:
+    /// - `pk`: specifies the public key `pk = A`
+    ///
+    /// Returns the shared secret `K` and the cipher `c`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::kem::{DualRegevKem, KemScheme};
+    /// let kem = DualRegevKem::default();
+    /// let (pk, sk) = kem.gen();
+    ///
+    /// let (secret, cipher) = kem.encaps(&pk);
+    /// ```
+    fn encaps(&self, pk: &Self::PublicKey) -> (Self::SharedSecret, Self::Cipher) {
+        let key = MatZ::sample_uniform(KEY_BITS, 1, 0, 2).unwrap();
+        let cipher = self.dual_regev.enc_vec(pk, &key);
+
+        (Self::derive_secret(&key, &cipher), cipher)
+    }
+
+    /// Decapsulates the shared secret by following these steps:
+    /// - k = [`DualRegev::dec_vec`](sk, c)
+    /// - K = SHA-256(k || c)
+    ///
+    /// Parameters:
+    /// - `sk`: specifies the secret key `sk = x`
+    /// - `cipher`: specifies the cipher `c` generated by [`DualRegevKem::encaps`]
+    ///
+    /// Returns the shared secret `K` or a [`CryptoError`] if `sk` or `cipher`
+    /// do not fit the parameters of this instance.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::kem::{DualRegevKem, KemScheme};
+    /// let kem = DualRegevKem::default();
+    /// let (pk, sk) = kem.gen();
+    /// let (secret, cipher) = kem.encaps(&pk);
+    ///
+    /// assert_eq!(secret, kem.decaps(&sk, &cipher).unwrap());
+    /// ```
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] of type [`IncompatibleParameters`](CryptoError::IncompatibleParameters)
+    ///     if `sk` is no column vector of length `m`, `cipher` is no `(m+1) x 256` matrix,
+    ///     or the modulus of `cipher` is not `q`.
+    fn decaps(
+        &self,
+        sk: &Self::SecretKey,
+        cipher: &Self::Cipher,
+    ) -> Result<Self::SharedSecret, CryptoError> {
+        let m = &self.dual_regev.m;
+        if !sk.is_column_vector()
+            || Z::from(sk.get_num_rows()) != *m
+            || Z::from(cipher.get_num_rows()) != m + Z::ONE
+            || cipher.get_num_columns() != KEY_BITS
+            || cipher.get_mod() != self.dual_regev.q
+        {
+            return Err(CryptoError::IncompatibleParameters);
+        }
+
+        let key = self.dual_regev.dec_vec(sk, cipher);
+
+        Ok(Self::derive_secret(&key, cipher))
+    }
+}
+
+#[cfg(test)]
+mod test_dual_regev_kem {
+    use super::{DualRegevKem, KemScheme};
+    use crate::{construction::pk_encryption::DualRegev, error::CryptoError};
+    use qfall_math::{
+        integer_mod_q::Zq,
+        traits::{GetEntry, SetEntry},
+    };
+
+    /// Ensures that the default parameters are correct and secure.
+    #[test]
+    fn default_suitable() {
+        let kem = DualRegevKem::default();
+
+        assert!(kem.check_correctness().is_ok());
+        assert!(kem.check_security().is_ok());
+    }
+
+    /// Ensures that unsuitable parameters are reported as invalid parameters.
+    #[test]
+    fn unsuitable_parameters() {
+        let kem = DualRegevKem {
+            dual_regev: DualRegev::new(2, 2, 2, 1),
+        };
+
+        assert!(matches!(
+            kem.check_correctness(),
+            Err(CryptoError::InvalidParameters { .. })
+        ));
+        assert!(matches!(
+            kem.check_security(),
+            Err(CryptoError::InvalidParameters { .. })
+        ));
+    }
+
+    /// Ensures that the decapsulated secret matches the encapsulated secret.
+    #[test]
+    fn cycle() {
+        let kem = DualRegevKem::default();
+        let (pk, sk) = kem.gen();
+
+        for _ in 0..5 {
+            let (secret, cipher) = kem.encaps(&pk);
+
+            assert_eq!(secret, kem.decaps(&sk, &cipher).unwrap());
+        }
+    }
+
+    /// Ensures that the decapsulated secret matches for parameters from [`DualRegevKem::new_from_n`].
+    #[test]
+    fn cycle_new_from_n() {
+        let kem = DualRegevKem::new_from_n(15).unwrap();
+        let (pk, sk) = kem.gen();
+
+        let (secret, cipher) = kem.encaps(&pk);
+
+        assert_eq!(secret, kem.decaps(&sk, &cipher).unwrap());
+    }
+
+    /// Ensures that two encapsulations output different secrets.
+    #[test]
+    fn fresh_secrets() {
+        let kem = DualRegevKem::default();
+        let (pk, _) = kem.gen();
+
+        let (secret_1, _) = kem.encaps(&pk);
+        let (secret_2, _) = kem.encaps(&pk);
+
+        assert_ne!(secret_1, secret_2);
+    }
+
+    /// Ensures that modifying a single entry of the cipher results in a different secret.
+    #[test]
+    fn tampered_cipher() {
+        let kem = DualRegevKem::default();
+        let (pk, sk) = kem.gen();
+        let (secret, mut cipher) = kem.encaps(&pk);
+
+        let entry: Zq = cipher.get_entry(0, 0).unwrap();
+        cipher.set_entry(0, 0, entry + 1).unwrap();
+
+        assert_ne!(secret, kem.decaps(&sk, &cipher).unwrap());
+    }
+
+    /// Ensures that decapsulation with another secret key results in a different secret.
+    #[test]
+    fn wrong_secret_key() {
+        let kem = DualRegevKem::default();
+        let (pk, _) = kem.gen();
+        let (_, sk) = kem.gen();
+        let (secret, cipher) = kem.encaps(&pk);
+
+        assert_ne!(secret, kem.decaps(&sk, &cipher).unwrap());
+    }
+
+    /// Ensures that too small `n` is rejected by [`DualRegevKem::new_from_n`].
+    #[test]
+    fn new_from_n_invalid() {
+        assert!(matches!(
+            DualRegevKem::new_from_n(9),
+            Err(CryptoError::InvalidParameters { .. })
+        ));
+    }
+
+    /// Ensures that secret keys and ciphers of other dimensions are rejected.
+    #[test]
+    fn decaps_incompatible() {
+        let kem = DualRegevKem::default();
+        let (pk, sk) = kem.gen();
+        let (_, cipher) = kem.encaps(&pk);
+        let other = DualRegevKem::new_from_n(15).unwrap();
+        let (other_pk, other_sk) = other.gen();
+        let (_, other_cipher) = other.encaps(&other_pk);
+
+        assert!(matches!(
+            kem.decaps(&other_sk, &cipher),
+            Err(CryptoError::IncompatibleParameters)
+        ));
+        assert!(matches!(
+            kem.decaps(&sk, &other_cipher),
+            Err(CryptoError::IncompatibleParameters)
+        ));
+    }
+}
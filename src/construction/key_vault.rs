@@ -0,0 +1,413 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains an encrypted storage for secret keys of type [`MatZ`],
+//! which protects the stored keys with a password.
+//!
+//! A key file is a JSON envelope of the following form, where `ciphertext` is the
+//! AES-256-GCM encryption of the JSON serialization of the secret key under a key
+//! derived from the password and `salt` via Argon2id.
+//! ```json
+//! {
+//!     "version": 1,
+//!     "salt": [...],
+//!     "nonce": [...],
+//!     "ciphertext": [...]
+//! }
+//! ```
+//!
+//! Furthermore, [`export_secret_key`] and [`import_secret_key`] provide the same
+//! protection for secret keys that are stored or transmitted as raw bytes.
+
+use crate::error::CryptoError;
+use aes_gcm::{
+    aead::{Aead, KeyInit},
+    Aes256Gcm, Nonce,
+};
+use argon2::{Algorithm, Argon2, Params, Version};
+use qfall_math::integer::MatZ;
+use serde::{Deserialize, Serialize};
+use std::{fs, io, path::Path};
+
+/// The version of the envelope format written by [`EncryptedKeyVault::store`].
+const VERSION: u32 = 1;
+
+/// The number of bytes of the salt used for Argon2id.
+const SALT_LEN: usize = 16;
+
+/// The number of bytes of the nonce used for AES-256-GCM.
+const NONCE_LEN: usize = 12;
+
+/// The memory cost of Argon2id in KiB used by [`export_secret_key`].
+const EXPORT_M_COST: u32 = 65536;
+
+/// The number of iterations of Argon2id used by [`export_secret_key`].
+const EXPORT_T_COST: u32 = 3;
+
+/// The degree of parallelism of Argon2id used by [`export_secret_key`].
+const EXPORT_P_COST: u32 = 4;
+
+/// Layout of a key file.
+#[derive(Serialize, Deserialize)]
+struct Envelope {
+    version: u32,
+    salt: Vec<u8>,
+    nonce: Vec<u8>,
+    ciphertext: Vec<u8>,
+}
+
+/// Stores secret keys of type [`MatZ`] encrypted under a password in a file.
+///
+/// The secret key is serialized to JSON and encrypted with AES-256-GCM, where the
+/// key is derived from the password and a uniform salt via Argon2id with default parameters.
+/// As AES-256-GCM is authenticated, a wrong password or a modified file is detected.
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::construction::key_vault::EncryptedKeyVault;
+/// use qfall_math::integer::MatZ;
+///
+/// let sk = MatZ::sample_uniform(2, 2, -5, 5).unwrap();
+/// let path = std::env::temp_dir().join("qfall_key_vault_doc.json");
+///
+/// EncryptedKeyVault::store(&path, &sk, "password").unwrap();
+/// let recovered = EncryptedKeyVault::recover(&path, "password").unwrap();
+/// # std::fs::remove_file(&path).unwrap();
+///
+/// assert_eq!(sk, recovered);
+/// ```
+pub struct EncryptedKeyVault;
+
+impl EncryptedKeyVault {
+    /// Derives the AES-256 key from `password` and `salt` via Argon2id.
+    fn derive_key(password: &str, salt: &[u8]) -> Result<Aes256Gcm, argon2::Error> {
+        let mut key = [0u8; 32];
+        Argon2::default().hash_password_into(password.as_bytes(), salt, &mut key)?;
+
+        // the key has the correct length, hence `new_from_slice` can not fail
+        Ok(Aes256Gcm::new_from_slice(&key).unwrap())
+    }
+
+    /// Encrypts `sk` under `password` and stores it together with
+    /// the used salt and nonce in a JSON envelope at `path`.
+    ///
+    /// Parameters:
+    /// - `path`: the path of the file, which is created or overwritten
+    /// - `sk`: the secret key that should be stored
+    /// - `password`: the password from which the encryption key is derived
+    ///
+    /// Returns an empty result if the file was written successfully.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::key_vault::EncryptedKeyVault;
+    /// use qfall_math::integer::MatZ;
+    ///
+    /// let sk = MatZ::identity(2, 2);
+    /// let path = std::env::temp_dir().join("qfall_key_vault_store_doc.json");
+    ///
+    /// EncryptedKeyVault::store(&path, &sk, "password").unwrap();
+    /// # std::fs::remove_file(&path).unwrap();
+    /// ```
+    ///
+    /// # Errors and Failures
+    /// - Returns an [`io::Error`] if the key could not be encrypted
+    ///     or the file could not be written.
+    pub fn store(path: &Path, sk: &MatZ, password: &str) -> io::Result<()> {
+        let salt: [u8; SALT_LEN] = rand::random();
+        let nonce: [u8; NONCE_LEN] = rand::random();
+
+        let plaintext = serde_json::to_vec(sk)?;
+        let cipher = Self::derive_key(password, &salt)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e.to_string()))?;
+        let ciphertext = cipher
+            .encrypt(Nonce::from_slice(&nonce), plaintext.as_slice())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+
+        let envelope = Envelope {
+            version: VERSION,
+            salt: salt.to_vec(),
+            nonce: nonce.to_vec(),
+            ciphertext,
+        };
+        fs::write(path, serde_json::to_string(&envelope)?)
+    }
+
+    /// Loads the JSON envelope stored at `path` by [`EncryptedKeyVault::store`]
+    /// and decrypts the contained secret key using `password`.
+    ///
+    /// Parameters:
+    /// - `path`: the path of the key file
+    /// - `password`: the password from which the encryption key is derived
+    ///
+    /// Returns the stored secret key or a [`CryptoError`] if the file could not be
+    /// read or the key could not be decrypted.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::key_vault::EncryptedKeyVault;
+    /// use qfall_math::integer::MatZ;
+    /// let sk = MatZ::identity(2, 2);
+    /// let path = std::env::temp_dir().join("qfall_key_vault_recover_doc.json");
+    /// EncryptedKeyVault::store(&path, &sk, "password").unwrap();
+    ///
+    /// let recovered = EncryptedKeyVault::recover(&path, "password").unwrap();
+    /// # std::fs::remove_file(&path).unwrap();
+    ///
+    /// assert_eq!(sk, recovered);
+    /// ```
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] of type [`Io`](CryptoError::Io)
+    ///     if the file could not be read.
+    /// - Returns a [`CryptoError`] of type [`DecryptionFailed`](CryptoError::DecryptionFailed)
+    ///     if the file is not a valid key file, the password is wrong,
+    ///     or the file was modified.
+    pub fn recover(path: &Path, password: &str) -> Result<MatZ, CryptoError> {
+        let content = fs::read_to_string(path)?;
+        let envelope: Envelope =
+            serde_json::from_str(&content).map_err(|_| CryptoError::DecryptionFailed)?;
+
+        if envelope.version != VERSION || envelope.nonce.len() != NONCE_LEN {
+            return Err(CryptoError::DecryptionFailed);
+        }
+
+        let cipher = Self::derive_key(password, &envelope.salt)
+            .map_err(|_| CryptoError::DecryptionFailed)?;
+        let plaintext = cipher
+            .decrypt(
+                Nonce::from_slice(&envelope.nonce),
+                envelope.ciphertext.as_slice(),
+            )
+            .map_err(|_| CryptoError::DecryptionFailed)?;
+
+        serde_json::from_slice(&plaintext).map_err(|_| CryptoError::DecryptionFailed)
+    }
+}
+
+/// Derives the AES-256 key from `password` and `salt` via Argon2id with
+/// the parameters of [`export_secret_key`].
+fn derive_export_key(password: &str, salt: &[u8]) -> Result<Aes256Gcm, argon2::Error> {
+    let params = Params::new(EXPORT_M_COST, EXPORT_T_COST, EXPORT_P_COST, Some(32))?;
+    let mut key = [0u8; 32];
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params).hash_password_into(
+        password.as_bytes(),
+        salt,
+        &mut key,
+    )?;
+
+    // the key has the correct length, hence `new_from_slice` can not fail
+    Ok(Aes256Gcm::new_from_slice(&key).unwrap())
+}
+
+/// Encrypts the secret key `sk` under `password` by following these steps:
+/// - serialize `sk` to JSON
+/// - derive a 32 byte key from `password` and a uniform 16 byte salt via Argon2id
+/// - encrypt the JSON with AES-256-GCM under a uniform 12 byte nonce
+///
+/// Argon2id is used with memory cost `m = 65536` KiB (64 MiB), `t = 3` iterations and
+/// parallelism `p = 4`, which follows the recommendation of RFC 9106 for
+/// memory-constrained environments and makes guessing passwords expensive.
+///
+/// The output has the form `salt || nonce || ciphertext`.
+///
+/// Parameters:
+/// - `sk`: the secret key that should be exported
+/// - `password`: the password from which the encryption key is derived
+///
+/// Returns the encrypted secret key as bytes or a [`CryptoError`] if the encryption failed.
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::construction::key_vault::{export_secret_key, import_secret_key};
+/// use qfall_math::integer::MatZ;
+/// let sk = MatZ::identity(2, 2);
+///
+/// let data = export_secret_key(&sk, "password").unwrap();
+/// let recovered = import_secret_key(&data, "password").unwrap();
+///
+/// assert_eq!(sk, recovered);
+/// ```
+///
+/// # Errors and Failures
+/// - Returns a [`CryptoError`] of type [`InvalidParameters`](CryptoError::InvalidParameters)
+///     if the secret key could not be serialized or encrypted.
+pub fn export_secret_key(sk: &MatZ, password: &str) -> Result<Vec<u8>, CryptoError> {
+    let salt: [u8; SALT_LEN] = rand::random();
+    let nonce: [u8; NONCE_LEN] = rand::random();
+
+    let plaintext = serde_json::to_vec(sk).map_err(|e| CryptoError::InvalidParameters {
+        message: format!("Invalid secret key: {e}"),
+    })?;
+    let cipher =
+        derive_export_key(password, &salt).map_err(|e| CryptoError::InvalidParameters {
+            message: e.to_string(),
+        })?;
+    let ciphertext = cipher
+        .encrypt(Nonce::from_slice(&nonce), plaintext.as_slice())
+        .map_err(|e| CryptoError::InvalidParameters {
+            message: e.to_string(),
+        })?;
+
+    let mut out = Vec::with_capacity(SALT_LEN + NONCE_LEN + ciphertext.len());
+    out.extend_from_slice(&salt);
+    out.extend_from_slice(&nonce);
+    out.extend(ciphertext);
+    Ok(out)
+}
+
+/// Decrypts a secret key exported by [`export_secret_key`] using `password`.
+///
+/// Parameters:
+/// - `data`: the exported secret key of the form `salt || nonce || ciphertext`
+/// - `password`: the password from which the encryption key is derived
+///
+/// Returns the secret key or a [`CryptoError`] if it could not be decrypted.
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::construction::key_vault::{export_secret_key, import_secret_key};
+/// use qfall_math::integer::MatZ;
+/// let sk = MatZ::identity(2, 2);
+/// let data = export_secret_key(&sk, "password").unwrap();
+///
+/// let recovered = import_secret_key(&data, "password").unwrap();
+///
+/// assert_eq!(sk, recovered);
+/// ```
+///
+/// # Errors and Failures
+/// - Returns a [`CryptoError`] of type [`DecryptionFailed`](CryptoError::DecryptionFailed)
+///     if `data` is too short, the password is wrong, or `data` was modified.
+pub fn import_secret_key(data: &[u8], password: &str) -> Result<MatZ, CryptoError> {
+    if data.len() < SALT_LEN + NONCE_LEN {
+        return Err(CryptoError::DecryptionFailed);
+    }
+    let (salt, rest) = data.split_at(SALT_LEN);
+    let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+
+    let cipher = derive_export_key(password, salt).map_err(|_| CryptoError::DecryptionFailed)?;
+    let plaintext = cipher
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| CryptoError::DecryptionFailed)?;
+
+    serde_json::from_slice(&plaintext).map_err(|_| CryptoError::DecryptionFailed)
+}
+
+#[cfg(test)]
+mod test_key_vault {
+    use super::{export_secret_key, import_secret_key, EncryptedKeyVault};
+    use crate::error::CryptoError;
+    use qfall_math::integer::MatZ;
+    use std::fs;
+
+    /// Ensures that storing and recovering a key yields the same key.
+    #[test]
+    fn round_trip() {
+        let sk = MatZ::sample_uniform(10, 10, -100, 100).unwrap();
+        let path = std::env::temp_dir().join("qfall_test_key_vault_round_trip.json");
+
+        EncryptedKeyVault::store(&path, &sk, "correct horse battery staple").unwrap();
+        let recovered = EncryptedKeyVault::recover(&path, "correct horse battery staple");
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(sk, recovered.unwrap());
+    }
+
+    /// Ensures that a wrong password results in a decryption failure.
+    #[test]
+    fn wrong_password() {
+        let sk = MatZ::sample_uniform(4, 4, -100, 100).unwrap();
+        let path = std::env::temp_dir().join("qfall_test_key_vault_wrong_password.json");
+
+        EncryptedKeyVault::store(&path, &sk, "password").unwrap();
+        let recovered = EncryptedKeyVault::recover(&path, "passw0rd");
+        fs::remove_file(&path).unwrap();
+
+        assert!(matches!(recovered, Err(CryptoError::DecryptionFailed)));
+    }
+
+    /// Ensures that the stored file does not contain the secret key in plaintext.
+    #[test]
+    fn no_plaintext_key_material() {
+        let sk = MatZ::sample_uniform(4, 4, 1000, 10000).unwrap();
+        let path = std::env::temp_dir().join("qfall_test_key_vault_no_plaintext.json");
+
+        EncryptedKeyVault::store(&path, &sk, "password").unwrap();
+        let content = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert!(!content.contains(&sk.to_string()));
+        assert!(!content.contains(&serde_json::to_string(&sk).unwrap()));
+        assert!(content.contains("\"salt\""));
+        assert!(content.contains("\"ciphertext\""));
+    }
+
+    /// Ensures that a modified ciphertext results in a decryption failure.
+    #[test]
+    fn modified_file() {
+        let sk = MatZ::identity(3, 3);
+        let path = std::env::temp_dir().join("qfall_test_key_vault_modified.json");
+
+        EncryptedKeyVault::store(&path, &sk, "password").unwrap();
+        let mut envelope: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        let byte = envelope["ciphertext"][0].as_u64().unwrap();
+        envelope["ciphertext"][0] = serde_json::Value::from(byte ^ 1);
+        fs::write(&path, envelope.to_string()).unwrap();
+        let recovered = EncryptedKeyVault::recover(&path, "password");
+        fs::remove_file(&path).unwrap();
+
+        assert!(matches!(recovered, Err(CryptoError::DecryptionFailed)));
+    }
+
+    /// Ensures that exporting and importing a key yields the same key.
+    #[test]
+    fn export_round_trip() {
+        let sk = MatZ::sample_uniform(10, 10, -100, 100).unwrap();
+
+        let data = export_secret_key(&sk, "correct horse battery staple").unwrap();
+        let recovered = import_secret_key(&data, "correct horse battery staple");
+
+        assert_eq!(sk, recovered.unwrap());
+    }
+
+    /// Ensures that importing with a wrong password results in a decryption failure.
+    #[test]
+    fn export_wrong_password() {
+        let sk = MatZ::sample_uniform(4, 4, -100, 100).unwrap();
+
+        let data = export_secret_key(&sk, "password").unwrap();
+        let recovered = import_secret_key(&data, "passw0rd");
+
+        assert!(matches!(recovered, Err(CryptoError::DecryptionFailed)));
+    }
+
+    /// Ensures that the exported bytes do not contain the secret key in plaintext
+    /// and that truncated or modified exports are rejected.
+    #[test]
+    fn export_modified() {
+        let sk = MatZ::sample_uniform(4, 4, 1000, 10000).unwrap();
+        let mut data = export_secret_key(&sk, "password").unwrap();
+        let json = serde_json::to_vec(&sk).unwrap();
+
+        assert!(!data
+            .windows(json.len())
+            .any(|window| window == json.as_slice()));
+        assert!(matches!(
+            import_secret_key(&data[..20], "password"),
+            Err(CryptoError::DecryptionFailed)
+        ));
+        let last = data.len() - 1;
+        data[last] ^= 1;
+        assert!(matches!(
+            import_secret_key(&data, "password"),
+            Err(CryptoError::DecryptionFailed)
+        ));
+    }
+}
@@ -0,0 +1,354 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains Peikert's two-party key agreement based on LWE
+//! with reconciliation, which can be seen as a lattice-based analogue of the
+//! Diffie-Hellman key exchange.
+//!
+//! The protocol consists of the following steps:
+//! 1. Alice sends `P_A = A * S_A + E_A`.
+//! 2. Bob sends `P_B = S_B^t * A + E_B^t` together with the hint `⌊4 * V / q⌋ mod 2`
+//!     for `V = S_B^t * P_A + E`, and derives his key by rounding `V`.
+//! 3. Alice derives her key from `W = P_B * S_A` and the hint using [`LatticeDH::reconcile`].
+//!
+//! As `V - W` is small, both keys are equal with overwhelming probability.
+
+use qfall_math::{
+    integer::{MatZ, Z},
+    integer_mod_q::{MatZq, Modulus},
+    rational::Q,
+    traits::{GetEntry, Pow, SetEntry},
+};
+use serde::{Deserialize, Serialize};
+
+/// The number of rows and columns of `V` and `W`,
+/// i.e. the shared key consists of `KEY_DIM^2 = 256` bits.
+const KEY_DIM: i64 = 16;
+
+/// The key shared by both parties after a successful protocol execution.
+pub type SharedK = [u8; 32];
+
+/// The message sent from Bob to Alice.
+///
+/// Attributes:
+/// - `b`: the LWE samples `P_B = S_B^t * A + E_B^t`
+/// - `hint`: the binary reconciliation hint `⌊4 * V / q⌋ mod 2`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PublicKeyB {
+    pub b: MatZq,
+    pub hint: MatZ,
+}
+
+/// This struct manages and stores the public parameters of Peikert's
+/// LWE-based key agreement with reconciliation.
+///
+/// Attributes:
+/// - `n`: specifies the security parameter, which is not equal to the bit-security level
+/// - `q`: specifies the modulus, which is a power of two
+/// - `s`: specifies the Gaussian parameter of all secrets and errors
+/// - `a`: the public uniform matrix `A ∈ Z_q^{n x n}`
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::construction::lattice_dh::LatticeDH;
+///
+/// let dh = LatticeDH::new_from_n(16);
+///
+/// let (pk_a, sk_a) = dh.alice_gen();
+/// let (key_b, pk_b) = dh.bob_respond(&pk_a);
+/// let key_a = dh.alice_finish(&pk_b, &sk_a);
+///
+/// assert_eq!(key_a, key_b);
+/// ```
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LatticeDH {
+    n: Z,
+    q: Modulus,
+    s: Q,
+    pub a: MatZq,
+}
+
+impl LatticeDH {
+    /// Initializes a [`LatticeDH`] instance for security parameter `n` by choosing
+    /// `q = 2^{⌈log_2(n^5)⌉}`, the Gaussian parameter `s = sqrt(n)`,
+    /// and a uniform matrix `A ∈ Z_q^{n x n}`.
+    ///
+    /// Parameters:
+    /// - `n`: the security parameter
+    ///
+    /// Returns a [`LatticeDH`] instance.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::lattice_dh::LatticeDH;
+    ///
+    /// let dh = LatticeDH::new_from_n(16);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `n < 2`.
+    pub fn new_from_n(n: impl Into<Z>) -> Self {
+        let n: Z = n.into();
+        if n < Z::from(2) {
+            panic!("Security parameter n has to be larger than 1");
+        }
+
+        // a power of two ensures that q is divisible by 8, which is required
+        // by the reconciliation and every key bit is uniform given the hint
+        let log_q = i64::try_from(&n.pow(5).unwrap().log_ceil(2).unwrap()).unwrap();
+        let q = Modulus::from(Z::from(2).pow(log_q).unwrap());
+
+        let s = n.sqrt();
+        let a = MatZq::sample_uniform(&n, &n, &q);
+
+        Self { n, q, s, a }
+    }
+
+    /// Samples a matrix of dimensions `num_rows x num_cols` from the
+    /// discrete Gaussian distribution with Gaussian parameter `s`.
+    fn sample_short(&self, num_rows: &Z, num_cols: i64) -> MatZq {
+        MatZq::sample_discrete_gauss(num_rows, num_cols, &self.q, &self.n, 0, &self.s).unwrap()
+    }
+
+    /// Packs the bits of a `KEY_DIM x KEY_DIM` matrix row by row into a [`SharedK`].
+    fn pack(bit: impl Fn(i64, i64) -> bool) -> SharedK {
+        let mut key = [0u8; 32];
+        for i in 0..KEY_DIM {
+            for j in 0..KEY_DIM {
+                if bit(i, j) {
+                    let index = (i * KEY_DIM + j) as usize;
+                    key[index / 8] |= 1 << (index % 8);
+                }
+            }
+        }
+        key
+    }
+
+    /// Generates Alice's message `P_A = A * S_A + E_A` and her secret `S_A`.
+    ///
+    /// Returns the public key `P_A` that is sent to Bob and the secret key `S_A`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::lattice_dh::LatticeDH;
+    ///
+    /// let dh = LatticeDH::new_from_n(16);
+    /// let (pk_a, sk_a) = dh.alice_gen();
+    /// ```
+    pub fn alice_gen(&self) -> (MatZq, MatZq) {
+        let s_a = self.sample_short(&self.n, KEY_DIM);
+        let e_a = self.sample_short(&self.n, KEY_DIM);
+        let p_a = &self.a * &s_a + e_a;
+
+        (p_a, s_a)
+    }
+
+    /// Responds to Alice's message `pk_a` by computing `P_B = S_B^t * A + E_B^t`
+    /// and `V = S_B^t * P_A + E`, whose rounding `⌊2 * V / q⌉ mod 2` is Bob's key.
+    ///
+    /// Parameters:
+    /// - `pk_a`: the public key `P_A` received from Alice
+    ///
+    /// Returns the [`SharedK`] of Bob and the [`PublicKeyB`] that is sent to Alice.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::lattice_dh::LatticeDH;
+    ///
+    /// let dh = LatticeDH::new_from_n(16);
+    /// let (pk_a, _) = dh.alice_gen();
+    ///
+    /// let (key_b, pk_b) = dh.bob_respond(&pk_a);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `pk_a` is not of dimensions `n x 16`.
+    pub fn bob_respond(&self, pk_a: &MatZq) -> (SharedK, PublicKeyB) {
+        let s_b = self.sample_short(&self.n, KEY_DIM).transpose();
+        let e_b = self.sample_short(&Z::from(KEY_DIM), i64::try_from(&self.n).unwrap());
+        let p_b = &s_b * &self.a + e_b;
+
+        let e = self.sample_short(&Z::from(KEY_DIM), KEY_DIM);
+        let v = &s_b * pk_a + e;
+
+        let q = Z::from(&self.q);
+        let mut hint = MatZ::new(KEY_DIM, KEY_DIM);
+        let mut entries = vec![];
+        for i in 0..KEY_DIM {
+            for j in 0..KEY_DIM {
+                let entry: Z = v.get_entry(i, j).unwrap();
+                // ⌊4 * v / q⌋ is the index of the quadrant of `Z_q` containing `v`
+                let quadrant = (4 * &entry).div_floor(&q);
+                hint.set_entry(i, j, quadrant.modulo(2)).unwrap();
+                entries.push(quadrant);
+            }
+        }
+
+        // ⌊2 * v / q⌉ mod 2 = 1 iff `v` is in the second or third quadrant
+        let key = Self::pack(|i, j| {
+            let quadrant = &entries[(i * KEY_DIM + j) as usize];
+            quadrant == &Z::ONE || quadrant == &Z::from(2)
+        });
+
+        (key, PublicKeyB { b: p_b, hint })
+    }
+
+    /// Finishes the protocol as Alice by computing `W = P_B * S_A` and
+    /// reconciling it with the hint of `pk_b`.
+    ///
+    /// Parameters:
+    /// - `pk_b`: the message received from Bob
+    /// - `sk_a`: the secret key `S_A` generated by [`LatticeDH::alice_gen`]
+    ///
+    /// Returns the [`SharedK`] of Alice, which matches the key of Bob
+    /// with overwhelming probability.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::lattice_dh::LatticeDH;
+    ///
+    /// let dh = LatticeDH::new_from_n(16);
+    /// let (pk_a, sk_a) = dh.alice_gen();
+    /// let (_, pk_b) = dh.bob_respond(&pk_a);
+    ///
+    /// let key_a = dh.alice_finish(&pk_b, &sk_a);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if the dimensions of `pk_b` or `sk_a` do not match the public parameters.
+    pub fn alice_finish(&self, pk_b: &PublicKeyB, sk_a: &MatZq) -> SharedK {
+        let w = &pk_b.b * sk_a;
+        self.reconcile(&w, &pk_b.hint)
+    }
+
+    /// Reconciles `w` with the binary `hint = ⌊4 * v / q⌋ mod 2` of a `v`,
+    /// s.t. the result equals `⌊2 * v / q⌉ mod 2` if every entry of `v - w`
+    /// has an absolute value smaller than `q / 8`.
+    ///
+    /// Given the hint, `v` lies in one of two opposite quadrants of `Z_q`. Hence,
+    /// the key bit is `1` iff `w ∈ [3q/8, 7q/8)` for `hint = 0`
+    /// or `w ∈ [q/8, 5q/8)` for `hint = 1`.
+    ///
+    /// Parameters:
+    /// - `w`: the `16 x 16` matrix close to `v`
+    /// - `hint`: the reconciliation hint of `v`
+    ///
+    /// Returns the [`SharedK`] containing the bits `⌊2 * v / q⌉ mod 2` row by row.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::lattice_dh::LatticeDH;
+    ///
+    /// let dh = LatticeDH::new_from_n(16);
+    /// let (pk_a, sk_a) = dh.alice_gen();
+    /// let (key_b, pk_b) = dh.bob_respond(&pk_a);
+    ///
+    /// let key_a = dh.reconcile(&(&pk_b.b * &sk_a), &pk_b.hint);
+    /// assert_eq!(key_a, key_b);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `w` or `hint` is not of dimensions `16 x 16`.
+    pub fn reconcile(&self, w: &MatZq, hint: &MatZ) -> SharedK {
+        let q = Z::from(&self.q);
+
+        Self::pack(|i, j| {
+            let entry: Z = w.get_entry(i, j).unwrap();
+            let hint_entry: Z = hint.get_entry(i, j).unwrap();
+            let eight_w = 8 * entry;
+            let (lower, upper) = if hint_entry == Z::ZERO {
+                (3, 7)
+            } else {
+                (1, 5)
+            };
+
+            eight_w >= lower * &q && eight_w < upper * &q
+        })
+    }
+}
+
+#[cfg(test)]
+mod test_lattice_dh {
+    use super::{LatticeDH, KEY_DIM};
+    use qfall_math::{
+        integer::{MatZ, Z},
+        integer_mod_q::MatZq,
+        traits::SetEntry,
+    };
+
+    /// Ensures that both parties derive the same key for valid protocol runs.
+    #[test]
+    fn keys_match() {
+        for n in [4, 16, 32] {
+            let dh = LatticeDH::new_from_n(n);
+
+            for _ in 0..10 {
+                let (pk_a, sk_a) = dh.alice_gen();
+                let (key_b, pk_b) = dh.bob_respond(&pk_a);
+                let key_a = dh.alice_finish(&pk_b, &sk_a);
+
+                assert_eq!(key_a, key_b);
+            }
+        }
+    }
+
+    /// Ensures that every protocol run results in a fresh key.
+    #[test]
+    fn fresh_keys() {
+        let dh = LatticeDH::new_from_n(16);
+
+        let (pk_a, _) = dh.alice_gen();
+        let (key_1, _) = dh.bob_respond(&pk_a);
+        let (key_2, _) = dh.bob_respond(&pk_a);
+
+        assert_ne!(key_1, key_2);
+    }
+
+    /// Ensures that the keys diverge if Alice uses another secret key.
+    #[test]
+    fn wrong_secret_key() {
+        let dh = LatticeDH::new_from_n(16);
+
+        let (pk_a, _) = dh.alice_gen();
+        let (_, other_sk_a) = dh.alice_gen();
+        let (key_b, pk_b) = dh.bob_respond(&pk_a);
+
+        assert_ne!(key_b, dh.alice_finish(&pk_b, &other_sk_a));
+    }
+
+    /// Ensures that reconciliation recovers `⌊2 * v / q⌉ mod 2` for `w`
+    /// with a distance of up to `q / 8 - 1` from `v`.
+    #[test]
+    fn reconcile_boundaries() {
+        let dh = LatticeDH::new_from_n(2);
+        let q = Z::from(&dh.q);
+        let max_distance = q.div_floor(8) - Z::ONE;
+
+        for (v, expected_bit) in [
+            (Z::ZERO, false),
+            (q.div_floor(4) - Z::ONE, false),
+            (q.div_floor(4), true),
+            (q.div_floor(2), true),
+            (3 * q.div_floor(4) - Z::ONE, true),
+            (3 * q.div_floor(4), false),
+            (&q - Z::ONE, false),
+        ] {
+            let hint_entry = (4 * &v).div_floor(&q).modulo(2);
+            for w in [&v - &max_distance, v.clone(), &v + &max_distance] {
+                let mut w_mat = MatZq::new(KEY_DIM, KEY_DIM, &q);
+                let mut hint = MatZ::new(KEY_DIM, KEY_DIM);
+                w_mat.set_entry(0, 0, &w).unwrap();
+                hint.set_entry(0, 0, &hint_entry).unwrap();
+
+                let key = dh.reconcile(&w_mat, &hint);
+
+                assert_eq!(expected_bit, key[0] & 1 == 1);
+            }
+        }
+    }
+}
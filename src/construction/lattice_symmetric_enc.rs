@@ -0,0 +1,328 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains a symmetric-key encryption scheme, which derives
+//! a Regev public key from a symmetric key `k ∈ {0,1}^256` and encrypts bits
+//! like [`Regev`](crate::construction::pk_encryption::Regev).
+//!
+//! **WARNING:** This construction is not a standard scheme and has not been analysed.
+//! It is only IND-CPA secure if AES-256 in counter mode is a secure PRG and
+//! LWE with binomial errors is hard for the chosen parameters.
+//! The ciphertexts are not authenticated.
+
+use crate::{
+    construction::hash::sha256::hash_to_mat_zq_sha256,
+    utils::{constant_time::sample_uniform_with_rng, math_util::ZqArith},
+};
+use aes::Aes256;
+use ctr::{
+    cipher::{KeyIvInit, StreamCipher},
+    Ctr128BE,
+};
+use qfall_math::{
+    integer::{MatZ, Z},
+    integer_mod_q::{MatZq, Modulus, Zq},
+    traits::{Concatenate, GetEntry, SetEntry},
+};
+use rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+
+/// This struct manages and stores the public parameters of a [`LatticeSymmetricEnc`]
+/// symmetric-key encryption instance.
+///
+/// A symmetric key `k` determines the following values:
+/// - `A' = PRG_AES_CTR(k) ∈ Z_q^{n x m}` and `e ∈ Z^m` from the centered binomial distribution
+///     with parameter `2`, which are both derived from the key stream of AES-256-CTR under `k`
+/// - `s = SHA-256(hex(k) || "SECRET") mod q ∈ Z_q^n`, see [`hash_to_mat_zq_sha256`]
+/// - `A = [A'^t | (s^t * A' + e^t)^t]^t`
+///
+/// Then, each bit `msg_i` is encrypted as `c_i = A * r_i + [0^{1 x n} | msg_i * ⌊q/2⌋]^t`
+/// for a fresh uniform `r_i ∈ {0,1}^m`, where the error `e_i = e^t * r_i` is contained in
+/// the last entry of `A * r_i`.
+///
+/// **WARNING:** This construction is not a standard scheme, see the module documentation.
+///
+/// Attributes:
+/// - `n`: specifies the dimension of the secret `s`
+/// - `m`: specifies the number of columns of `A`
+/// - `q`: specifies the modulus over which the encryption is computed
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::construction::lattice_symmetric_enc::LatticeSymmetricEnc;
+/// let scheme = LatticeSymmetricEnc::default();
+/// let key = [7u8; 32];
+/// let plaintext = [true, false, true, true];
+///
+/// let ciphers = scheme.enc(&key, &plaintext);
+/// let decrypted = scheme.dec(&key, &ciphers);
+///
+/// assert_eq!(plaintext.to_vec(), decrypted);
+/// ```
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LatticeSymmetricEnc {
+    pub(crate) n: Z,       // dimension of the secret
+    pub(crate) m: Z,       // number of columns of A
+    pub(crate) q: Modulus, // modulus
+}
+
+/// A PRG outputting the key stream of AES-256 in counter mode with a zero IV.
+struct AesCtrPrg(Ctr128BE<Aes256>);
+
+impl AesCtrPrg {
+    /// Initializes the key stream of AES-256-CTR under `key`.
+    fn new(key: &[u8; 32]) -> Self {
+        Self(Ctr128BE::<Aes256>::new(key.into(), &[0u8; 16].into()))
+    }
+}
+
+impl RngCore for AesCtrPrg {
+    fn next_u32(&mut self) -> u32 {
+        let mut bytes = [0u8; 4];
+        self.fill_bytes(&mut bytes);
+        u32::from_le_bytes(bytes)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut bytes = [0u8; 8];
+        self.fill_bytes(&mut bytes);
+        u64::from_le_bytes(bytes)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        dest.fill(0);
+        self.0.apply_keystream(dest);
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl CryptoRng for AesCtrPrg {}
+
+impl LatticeSymmetricEnc {
+    /// Instantiates a [`LatticeSymmetricEnc`] instance with the specified parameters.
+    ///
+    /// **WARNING:** The given parameters are not checked for security nor
+    /// correctness of the scheme.
+    ///
+    /// Parameters:
+    /// - `n`: specifies the dimension of the secret `s`
+    /// - `m`: specifies the number of columns of `A`
+    /// - `q`: specifies the modulus
+    ///
+    /// Returns a [`LatticeSymmetricEnc`] instance.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::lattice_symmetric_enc::LatticeSymmetricEnc;
+    ///
+    /// let scheme = LatticeSymmetricEnc::new(64, 512, 7681);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if the given modulus `q <= 1`.
+    pub fn new(n: impl Into<Z>, m: impl Into<Z>, q: impl Into<Modulus>) -> Self {
+        Self {
+            n: n.into(),
+            m: m.into(),
+            q: q.into(),
+        }
+    }
+
+    /// Derives the matrix `A = [A'^t | (s^t * A' + e^t)^t]^t` and the secret `s` from `key`.
+    fn derive_key_material(&self, key: &[u8; 32]) -> (MatZq, MatZq) {
+        let n = i64::try_from(&self.n).unwrap();
+        let m = i64::try_from(&self.m).unwrap();
+        let mut prg = AesCtrPrg::new(key);
+
+        // A' = PRG_AES_CTR(k)
+        let mat_a = sample_uniform_with_rng(n, m, &self.q, &mut prg);
+
+        // e^t <- centered binomial distribution with parameter 2
+        let mut vec_e_t = MatZq::new(1, m, &self.q);
+        for j in 0..m {
+            let bits = prg.next_u32();
+            let entry = (bits & 1) as i64 + ((bits >> 1) & 1) as i64
+                - ((bits >> 2) & 1) as i64
+                - ((bits >> 3) & 1) as i64;
+            vec_e_t.set_entry(0, j, Z::from(entry)).unwrap();
+        }
+
+        // s = SHA-256(hex(k) || "SECRET") mod q
+        let hex: String = key.iter().map(|byte| format!("{byte:02x}")).collect();
+        let vec_s = hash_to_mat_zq_sha256(&format!("{hex}SECRET"), n, 1, &self.q);
+
+        let vec_b_t = vec_s.transpose() * &mat_a + vec_e_t;
+        (mat_a.concat_vertical(&vec_b_t).unwrap(), vec_s)
+    }
+
+    /// Encrypts every bit of `plaintext` under `key` by following these steps:
+    /// - derive `A` from `key` as described in [`LatticeSymmetricEnc`]
+    /// - r_i <- Z_2^m
+    /// - c_i = A * r_i + [0^{1 x n} | msg_i * ⌊q/2⌋]^t
+    ///
+    /// Parameters:
+    /// - `key`: specifies the symmetric key `k`
+    /// - `plaintext`: specifies the bits that should be encrypted
+    ///
+    /// Returns a cipher `c_i` of type [`MatZq`] for every bit of `plaintext`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::lattice_symmetric_enc::LatticeSymmetricEnc;
+    /// let scheme = LatticeSymmetricEnc::default();
+    ///
+    /// let ciphers = scheme.enc(&[0u8; 32], &[true, false]);
+    /// ```
+    pub fn enc(&self, key: &[u8; 32], plaintext: &[bool]) -> Vec<MatZq> {
+        let (mat_a, _) = self.derive_key_material(key);
+        let q = Z::from(&self.q);
+
+        plaintext
+            .iter()
+            .map(|&msg| {
+                // r_i <- Z_2^m
+                let vec_r = MatZ::sample_uniform(&self.m, 1, 0, 2).unwrap();
+                let mut c = &mat_a * vec_r;
+
+                // hide message in last entry
+                let msg_q_half = ZqArith::scale_by_half_q(&Z::from(msg as u8), &q);
+                let last_entry: Zq = c.get_entry(-1, 0).unwrap();
+                c.set_entry(-1, 0, last_entry + msg_q_half).unwrap();
+                c
+            })
+            .collect()
+    }
+
+    /// Decrypts every cipher in `ciphers` under `key` by following these steps:
+    /// - derive `s` from `key` as described in [`LatticeSymmetricEnc`]
+    /// - x = [-s^t | 1] * c_i
+    /// - if x mod q is closer to ⌊q/2⌋ than to 0, output 1. Otherwise, output 0.
+    ///
+    /// Parameters:
+    /// - `key`: specifies the symmetric key `k`
+    /// - `ciphers`: specifies the ciphers generated by [`LatticeSymmetricEnc::enc`]
+    ///
+    /// Returns the decrypted bits.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::lattice_symmetric_enc::LatticeSymmetricEnc;
+    /// let scheme = LatticeSymmetricEnc::default();
+    /// let ciphers = scheme.enc(&[0u8; 32], &[true, false]);
+    ///
+    /// let plaintext = scheme.dec(&[0u8; 32], &ciphers);
+    ///
+    /// assert_eq!(vec![true, false], plaintext);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if a cipher does not have `n + 1` rows.
+    pub fn dec(&self, key: &[u8; 32], ciphers: &[MatZq]) -> Vec<bool> {
+        let (_, vec_s) = self.derive_key_material(key);
+        let q = Z::from(&self.q);
+        let vec_s_t = (Z::MINUS_ONE * vec_s.transpose())
+            .concat_horizontal(&MatZq::identity(1, 1, &self.q))
+            .unwrap();
+
+        ciphers
+            .iter()
+            .map(|c| {
+                let x: Zq = (&vec_s_t * c).get_entry(0, 0).unwrap();
+                ZqArith::round_to_bit(&x.get_representative_0_modulus(), &q) == Z::ONE
+            })
+            .collect()
+    }
+}
+
+impl Default for LatticeSymmetricEnc {
+    /// Initializes a [`LatticeSymmetricEnc`] struct with parameters `n = 32`,
+    /// `m = 256` and `q = 3329`.
+    /// This parameter choice is not secure as the dimension of the lattice is too small,
+    /// but it provides an efficient working example.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::lattice_symmetric_enc::LatticeSymmetricEnc;
+    ///
+    /// let scheme = LatticeSymmetricEnc::default();
+    /// ```
+    fn default() -> Self {
+        Self::new(32, 256, 3329)
+    }
+}
+
+#[cfg(test)]
+mod test_lattice_symmetric_enc {
+    use super::LatticeSymmetricEnc;
+
+    /// Returns `len` pseudorandom bits derived from `seed`.
+    fn bits(len: usize, seed: u64) -> Vec<bool> {
+        (0..len)
+            .map(|i| (seed.wrapping_mul(i as u64 + 1) >> (i % 61)) & 1 == 1)
+            .collect()
+    }
+
+    /// Ensures that 64-bit plaintexts are decrypted correctly.
+    #[test]
+    fn cycle_64_bits() {
+        let scheme = LatticeSymmetricEnc::default();
+        let key = [42u8; 32];
+        let plaintext = bits(64, 0x9e37_79b9_7f4a_7c15);
+
+        let ciphers = scheme.enc(&key, &plaintext);
+
+        assert_eq!(64, ciphers.len());
+        assert_eq!(plaintext, scheme.dec(&key, &ciphers));
+    }
+
+    /// Ensures that 256-bit plaintexts are decrypted correctly.
+    #[test]
+    fn cycle_256_bits() {
+        let scheme = LatticeSymmetricEnc::new(64, 512, 7681);
+        let key: [u8; 32] = core::array::from_fn(|i| i as u8);
+        let plaintext = bits(256, 0xd1b5_4a32_d192_ed03);
+
+        let ciphers = scheme.enc(&key, &plaintext);
+
+        assert_eq!(256, ciphers.len());
+        assert_eq!(plaintext, scheme.dec(&key, &ciphers));
+    }
+
+    /// Ensures that the key material is deterministic in the key
+    /// and differs for different keys.
+    #[test]
+    fn key_material_deterministic() {
+        let scheme = LatticeSymmetricEnc::default();
+
+        let (a_0, s_0) = scheme.derive_key_material(&[1u8; 32]);
+        let (a_1, s_1) = scheme.derive_key_material(&[1u8; 32]);
+        let (a_2, s_2) = scheme.derive_key_material(&[2u8; 32]);
+
+        assert_eq!(a_0, a_1);
+        assert_eq!(s_0, s_1);
+        assert_ne!(a_0, a_2);
+        assert_ne!(s_0, s_2);
+    }
+
+    /// Ensures that encryptions of the same plaintext are randomized
+    /// and that decryption under a wrong key does not recover the plaintext.
+    #[test]
+    fn wrong_key() {
+        let scheme = LatticeSymmetricEnc::default();
+        let plaintext = vec![true; 64];
+
+        let ciphers = scheme.enc(&[3u8; 32], &plaintext);
+
+        assert_ne!(ciphers, scheme.enc(&[3u8; 32], &plaintext));
+        assert_ne!(plaintext, scheme.dec(&[4u8; 32], &ciphers));
+    }
+}
@@ -0,0 +1,372 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains an oblivious pseudorandom function (OPRF) based on LWE,
+//! i.e. a two-party protocol in which a client learns `F_K(x)` for its input `x`
+//! and a key `K` held by the server, while the server learns nothing about `x`.
+//!
+//! The protocol consists of the following steps:
+//! 1. The server publishes `A` and `B = K * A + E` for its short key `K`.
+//! 2. The client masks its input as `c = H(x) + A * r + e'` for a short `r`.
+//! 3. The server evaluates `d = K * c + e` on the masked input.
+//! 4. The client removes `B * r` from `d` and rounds the result to bits,
+//!     which yields `F_K(x) = ⌊K * H(x)⌉_2`.
+
+use crate::construction::hash::sha256::hash_to_mat_zq_sha256;
+use qfall_math::{
+    integer::Z,
+    integer_mod_q::{MatZq, Modulus, Zq},
+    rational::Q,
+    traits::{Distance, GetEntry},
+};
+use serde::{Deserialize, Serialize};
+
+/// The number of bits of a [`PRFOutput`].
+const OUTPUT_LEN: i64 = 128;
+
+/// The masked input `c = H(x) + A * r + e'` sent from the client to the server.
+///
+/// Attributes:
+/// - `c`: the masked hash of the input
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MaskedInput {
+    pub c: MatZq,
+}
+
+/// The masked output `d = K * c + e` sent from the server to the client.
+///
+/// Attributes:
+/// - `d`: the evaluation of the key on the masked input
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MaskedOutput {
+    pub d: MatZq,
+}
+
+/// The output `F_K(x)` of the PRF.
+///
+/// Attributes:
+/// - `bits`: the `128` output bits
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PRFOutput {
+    pub bits: Vec<bool>,
+}
+
+/// This struct manages and stores the public parameters of an LWE-based
+/// oblivious pseudorandom function.
+///
+/// The PRF is defined as `F_K(x) = ⌊K * H(x)⌉_2`, where `H` hashes into `Z_q^n`
+/// and `K ∈ Z^{128 x n}` is a short key. The client's mask `A * r + e'` is
+/// pseudorandom under LWE and removing `B * r` from the server's answer
+/// leaves the small noise `K * e' + e - E * r`, which is removed by rounding
+/// with overwhelming probability if `alpha * q` is small compared to `q`.
+///
+/// Attributes:
+/// - `n`: specifies the security parameter, which is not equal to the bit-security level
+/// - `q`: specifies the modulus
+/// - `alpha`: specifies the Gaussian parameter used for the key, the randomness,
+///     and the noise
+/// - `a`: the public matrix `A ∈ Z_q^{n x n}`
+/// - `b`: the public matrix `B = K * A + E ∈ Z_q^{128 x n}` of the server
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::construction::oblivious_prf::ObliviousPRF;
+///
+/// let (oprf, key) = ObliviousPRF::server_setup(16, 1_i64 << 40, 0.5_f64.powi(37));
+/// let input = [true, false, true];
+///
+/// // client
+/// let r = oprf.sample_randomness();
+/// let masked = oprf.client_mask(&input, &r);
+///
+/// // server
+/// let output = oprf.server_eval(&key, &masked);
+///
+/// // client
+/// let prf_output = oprf.client_unmask(&output, &r);
+///
+/// assert_eq!(oprf.eval(&key, &input), prf_output);
+/// ```
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ObliviousPRF {
+    n: Z,
+    q: Modulus,
+    alpha: Q,
+    pub a: MatZq,
+    pub b: MatZq,
+}
+
+impl ObliviousPRF {
+    /// Generates the public parameters and the key of the server.
+    ///
+    /// Parameters:
+    /// - `n`: the security parameter
+    /// - `q`: the modulus
+    /// - `alpha`: the Gaussian parameter, i.e. all short values are sampled
+    ///     with Gaussian parameter `alpha * q`
+    ///
+    /// Returns an [`ObliviousPRF`] instance containing the public parameters
+    /// and the secret key `K` of the server.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::oblivious_prf::ObliviousPRF;
+    ///
+    /// let (oprf, key) = ObliviousPRF::server_setup(16, 1_i64 << 40, 0.5_f64.powi(37));
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `n < 1`, `q <= 1`, or `alpha <= 0`.
+    pub fn server_setup(
+        n: impl Into<Z>,
+        q: impl Into<Modulus>,
+        alpha: impl Into<Q>,
+    ) -> (Self, MatZq) {
+        let n: Z = n.into();
+        let q: Modulus = q.into();
+        let alpha: Q = alpha.into();
+        assert!(n >= Z::ONE, "The security parameter n has to be positive.");
+        assert!(
+            alpha > Q::ZERO,
+            "The Gaussian parameter alpha has to be positive."
+        );
+
+        let n_i64 = i64::try_from(&n).unwrap();
+        let a = MatZq::sample_uniform(&n, &n, &q);
+
+        // K <- χ^{128 x n}, E <- χ^{128 x n}
+        let key = sample_short(OUTPUT_LEN, n_i64, &n, &q, &alpha);
+        let mat_e = sample_short(OUTPUT_LEN, n_i64, &n, &q, &alpha);
+        let b = &key * &a + mat_e;
+
+        (Self { n, q, alpha, a, b }, key)
+    }
+
+    /// Hashes `input` into `Z_q^n`.
+    fn hash(&self, input: &[bool]) -> MatZq {
+        let string: String = input
+            .iter()
+            .map(|bit| if *bit { '1' } else { '0' })
+            .collect();
+        hash_to_mat_zq_sha256(&string, &self.n, 1, &self.q)
+    }
+
+    /// Rounds every entry of `vector` to the closer value of `0` and `⌊q/2⌋`.
+    fn round(&self, vector: &MatZq) -> PRFOutput {
+        let q_half = Z::from(&self.q).div_floor(2);
+
+        let bits = (0..OUTPUT_LEN)
+            .map(|i| {
+                let entry: Zq = vector.get_entry(i, 0).unwrap();
+                entry.distance(Z::ZERO) > entry.distance(&q_half)
+            })
+            .collect();
+
+        PRFOutput { bits }
+    }
+
+    /// Samples the short randomness `r ∈ Z^n` of the client, which
+    /// has to be kept secret until the output is unmasked.
+    ///
+    /// Returns a short vector `r`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::oblivious_prf::ObliviousPRF;
+    ///
+    /// let (oprf, _) = ObliviousPRF::server_setup(16, 1_i64 << 40, 0.5_f64.powi(37));
+    /// let r = oprf.sample_randomness();
+    /// ```
+    pub fn sample_randomness(&self) -> MatZq {
+        sample_short(
+            i64::try_from(&self.n).unwrap(),
+            1,
+            &self.n,
+            &self.q,
+            &self.alpha,
+        )
+    }
+
+    /// Masks `input` for the server by computing `c = H(x) + A * r + e'`.
+    ///
+    /// Parameters:
+    /// - `input`: the input of the client
+    /// - `r`: the short randomness of the client, see [`ObliviousPRF::sample_randomness`]
+    ///
+    /// Returns the [`MaskedInput`] that is sent to the server.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::oblivious_prf::ObliviousPRF;
+    ///
+    /// let (oprf, _) = ObliviousPRF::server_setup(16, 1_i64 << 40, 0.5_f64.powi(37));
+    /// let r = oprf.sample_randomness();
+    ///
+    /// let masked = oprf.client_mask(&[true, false], &r);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `r` is not a vector of length `n` with modulus `q`.
+    pub fn client_mask(&self, input: &[bool], r: &MatZq) -> MaskedInput {
+        let vec_e = sample_short(
+            i64::try_from(&self.n).unwrap(),
+            1,
+            &self.n,
+            &self.q,
+            &self.alpha,
+        );
+
+        MaskedInput {
+            c: self.hash(input) + &self.a * r + vec_e,
+        }
+    }
+
+    /// Evaluates the key of the server on the masked input by computing `d = K * c + e`.
+    ///
+    /// Parameters:
+    /// - `key`: the secret key `K` of the server
+    /// - `masked`: the masked input received from the client
+    ///
+    /// Returns the [`MaskedOutput`] that is sent back to the client.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::oblivious_prf::ObliviousPRF;
+    ///
+    /// let (oprf, key) = ObliviousPRF::server_setup(16, 1_i64 << 40, 0.5_f64.powi(37));
+    /// let masked = oprf.client_mask(&[true, false], &oprf.sample_randomness());
+    ///
+    /// let output = oprf.server_eval(&key, &masked);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `key` or `masked` do not match the public parameters.
+    pub fn server_eval(&self, key: &MatZq, masked: &MaskedInput) -> MaskedOutput {
+        let vec_e = sample_short(OUTPUT_LEN, 1, &self.n, &self.q, &self.alpha);
+
+        MaskedOutput {
+            d: key * &masked.c + vec_e,
+        }
+    }
+
+    /// Unmasks the output of the server by computing `d - B * r` and rounding the result.
+    ///
+    /// Parameters:
+    /// - `output`: the masked output received from the server
+    /// - `r`: the randomness used to mask the input
+    ///
+    /// Returns the [`PRFOutput`] `F_K(x)`, which is correct with overwhelming probability.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::oblivious_prf::ObliviousPRF;
+    ///
+    /// let (oprf, key) = ObliviousPRF::server_setup(16, 1_i64 << 40, 0.5_f64.powi(37));
+    /// let r = oprf.sample_randomness();
+    /// let output = oprf.server_eval(&key, &oprf.client_mask(&[true, false], &r));
+    ///
+    /// let prf_output = oprf.client_unmask(&output, &r);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `output` or `r` do not match the public parameters.
+    pub fn client_unmask(&self, output: &MaskedOutput, r: &MatZq) -> PRFOutput {
+        self.round(&(&output.d - &self.b * r))
+    }
+
+    /// Evaluates the PRF directly with the key, i.e. it computes `⌊K * H(x)⌉_2`.
+    ///
+    /// Parameters:
+    /// - `key`: the secret key `K` of the server
+    /// - `input`: the input on which the PRF is evaluated
+    ///
+    /// Returns the [`PRFOutput`] `F_K(x)`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::oblivious_prf::ObliviousPRF;
+    ///
+    /// let (oprf, key) = ObliviousPRF::server_setup(16, 1_i64 << 40, 0.5_f64.powi(37));
+    ///
+    /// let prf_output = oprf.eval(&key, &[true, false]);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `key` does not match the public parameters.
+    pub fn eval(&self, key: &MatZq, input: &[bool]) -> PRFOutput {
+        self.round(&(key * &self.hash(input)))
+    }
+}
+
+/// Samples a matrix of dimensions `num_rows x num_cols` from the
+/// discrete Gaussian distribution with Gaussian parameter `alpha * q`.
+fn sample_short(num_rows: i64, num_cols: i64, n: &Z, q: &Modulus, alpha: &Q) -> MatZq {
+    MatZq::sample_discrete_gauss(num_rows, num_cols, q, n, 0, alpha * Z::from(q)).unwrap()
+}
+
+#[cfg(test)]
+mod test_oblivious_prf {
+    use super::{ObliviousPRF, PRFOutput, OUTPUT_LEN};
+    use qfall_math::integer_mod_q::MatZq;
+
+    /// Runs the protocol for `input` and returns the output received by the client.
+    fn run_protocol(oprf: &ObliviousPRF, key: &MatZq, input: &[bool]) -> PRFOutput {
+        let r = oprf.sample_randomness();
+        let masked = oprf.client_mask(input, &r);
+        let output = oprf.server_eval(key, &masked);
+        oprf.client_unmask(&output, &r)
+    }
+
+    /// Ensures that the protocol outputs the PRF evaluated on the input.
+    #[test]
+    fn correctness() {
+        let (oprf, key) = ObliviousPRF::server_setup(16, 1_i64 << 40, 0.5_f64.powi(37));
+
+        for input in [vec![], vec![true], vec![false, true, true, false]] {
+            let prf_output = run_protocol(&oprf, &key, &input);
+
+            assert_eq!(OUTPUT_LEN as usize, prf_output.bits.len());
+            assert_eq!(oprf.eval(&key, &input), prf_output);
+        }
+    }
+
+    /// Ensures that repeated executions yield the same output,
+    /// although the masked inputs differ.
+    #[test]
+    fn deterministic_output() {
+        let (oprf, key) = ObliviousPRF::server_setup(16, 1_i64 << 40, 0.5_f64.powi(37));
+        let input = [true, true, false];
+
+        let r_1 = oprf.sample_randomness();
+        let r_2 = oprf.sample_randomness();
+        let masked_1 = oprf.client_mask(&input, &r_1);
+        let masked_2 = oprf.client_mask(&input, &r_2);
+
+        assert_ne!(masked_1, masked_2);
+        assert_eq!(
+            oprf.client_unmask(&oprf.server_eval(&key, &masked_1), &r_1),
+            oprf.client_unmask(&oprf.server_eval(&key, &masked_2), &r_2)
+        );
+    }
+
+    /// Ensures that different inputs and different keys yield different outputs.
+    #[test]
+    fn different_outputs() {
+        let (oprf, key) = ObliviousPRF::server_setup(16, 1_i64 << 40, 0.5_f64.powi(37));
+        let (other_oprf, other_key) = ObliviousPRF::server_setup(16, 1_i64 << 40, 0.5_f64.powi(37));
+
+        assert_ne!(
+            run_protocol(&oprf, &key, &[true]),
+            run_protocol(&oprf, &key, &[false])
+        );
+        assert_ne!(
+            run_protocol(&oprf, &key, &[true]),
+            run_protocol(&other_oprf, &other_key, &[true])
+        );
+    }
+}
@@ -0,0 +1,318 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains a 1-out-of-2 oblivious transfer protocol based on LWE,
+//! which allows a receiver to obtain one of two bits `m_0, m_1` of a sender
+//! without revealing its choice `b` and without learning `m_{1-b}`.
+//!
+//! The protocol uses a common reference string `(A, v)` and consists of the following steps:
+//! 1. The receiver samples a Regev key pair `(p_b, s)` w.r.t. `A`, sets
+//!     `p_{1-b} = v - p_b` and sends `p_0` to the sender.
+//! 2. The sender computes `p_1 = v - p_0` and encrypts `m_0` under `p_0`
+//!     and `m_1` under `p_1`.
+//! 3. The receiver decrypts the ciphertext `c_b` with its secret key `s`.
+//!
+//! As `p_b` is an LWE sample and `v` is uniform, `p_0` is computationally independent of `b`.
+//! The receiver does not know a short secret for `p_{1-b} = v - p_b`, and hence
+//! can not decrypt `c_{1-b}`. The protocol is secure against semi-honest parties.
+
+use crate::construction::pk_encryption::{PKEncryptionScheme, Regev};
+use qfall_math::{integer::Z, integer_mod_q::MatZq, traits::Concatenate};
+use serde::{Deserialize, Serialize};
+
+/// The secret state of the receiver, i.e. its choice bit `b` and
+/// the secret key `s` of the Regev public key `p_b`.
+///
+/// Attributes:
+/// - `b`: the choice bit of the receiver
+/// - `s`: the secret key corresponding to `p_b`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OtReceiverKey {
+    pub(crate) b: Z,
+    pub(crate) s: MatZq,
+}
+
+/// This struct manages and stores the public parameters of a [`OtLwe`]
+/// oblivious transfer instance.
+///
+/// Attributes:
+/// - `regev`: the Regev encryption instance used to encrypt the sender's bits
+/// - `mat_a`: the uniform matrix `A ∈ Z_q^{n x m}` of the common reference string
+/// - `vec_v`: the uniform vector `v ∈ Z_q^{1 x m}` of the common reference string
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::construction::oblivious_transfer::OtLwe;
+/// use qfall_math::integer::Z;
+/// let ot = OtLwe::default();
+///
+/// // receiver chooses the bit b = 1
+/// let (pk, sk) = ot.receiver_choose(1);
+///
+/// // sender encrypts both bits
+/// let (c_0, c_1) = ot.sender_setup(0, 1, &pk);
+///
+/// // receiver obtains m_1
+/// let m_b = ot.decode(&sk, &c_0, &c_1);
+///
+/// assert_eq!(Z::ONE, m_b);
+/// ```
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OtLwe {
+    pub(crate) regev: Regev,
+    pub(crate) mat_a: MatZq,
+    pub(crate) vec_v: MatZq,
+}
+
+impl OtLwe {
+    /// Instantiates a [`OtLwe`] instance, whose Regev parameters are chosen
+    /// via [`Regev::new_from_n`] and whose common reference string `(A, v)` is
+    /// sampled uniformly at random.
+    ///
+    /// Parameters:
+    /// - `n`: specifies the security parameter
+    ///
+    /// Returns a [`OtLwe`] instance.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::oblivious_transfer::OtLwe;
+    ///
+    /// let ot = OtLwe::new_from_n(16);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `n < 10`.
+    pub fn new_from_n(n: impl Into<Z>) -> Self {
+        Self::from_regev(Regev::new_from_n(n))
+    }
+
+    /// Samples a uniform common reference string for the parameters of `regev`.
+    fn from_regev(regev: Regev) -> Self {
+        let mat_a = MatZq::sample_uniform(&regev.n, &regev.m, &regev.q);
+        let vec_v = MatZq::sample_uniform(1, &regev.m, &regev.q);
+
+        Self {
+            regev,
+            mat_a,
+            vec_v,
+        }
+    }
+
+    /// Generates the message of the receiver for its choice `b mod 2` by following these steps:
+    /// - s <- Z_q^n, e^t <- χ^m
+    /// - p_b = s^t * A + e^t
+    /// - p_0 = p_b if b = 0 and p_0 = v - p_b otherwise
+    ///     where χ is discrete Gaussian distributed with center 0 and Gaussian parameter q * α.
+    ///
+    /// Parameters:
+    /// - `b`: specifies the choice of the receiver
+    ///
+    /// Returns the public key `p_0`, which is sent to the sender, and the
+    /// secret state [`OtReceiverKey`] of the receiver.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::oblivious_transfer::OtLwe;
+    /// let ot = OtLwe::default();
+    ///
+    /// let (pk, sk) = ot.receiver_choose(0);
+    /// ```
+    pub fn receiver_choose(&self, b: impl Into<Z>) -> (MatZq, OtReceiverKey) {
+        let b = b.into().modulo(2);
+        let regev = &self.regev;
+
+        // s <- Z_q^n
+        let vec_s = MatZq::sample_uniform(&regev.n, 1, &regev.q);
+        // e^t <- χ^m
+        let vec_e_t = MatZq::sample_discrete_gauss(
+            1,
+            &regev.m,
+            &regev.q,
+            &regev.n,
+            0,
+            &regev.alpha * Z::from(&regev.q),
+        )
+        .unwrap();
+
+        // p_b = s^t * A + e^t
+        let p_b = vec_s.transpose() * &self.mat_a + vec_e_t;
+        let p_0 = if b == Z::ZERO { p_b } else { &self.vec_v - p_b };
+
+        (p_0, OtReceiverKey { b, s: vec_s })
+    }
+
+    /// Encrypts `m_0 mod 2` and `m_1 mod 2` w.r.t. the receiver's message `p_0`
+    /// by following these steps:
+    /// - p_1 = v - p_0
+    /// - c_i = Regev.enc([A^t | p_i^t]^t, m_i) for i ∈ {0, 1}
+    ///
+    /// Parameters:
+    /// - `m_0`: specifies the first bit of the sender
+    /// - `m_1`: specifies the second bit of the sender
+    /// - `pk`: specifies the message `p_0` of the receiver
+    ///
+    /// Returns the ciphertexts `(c_0, c_1)`, which are sent to the receiver.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::oblivious_transfer::OtLwe;
+    /// let ot = OtLwe::default();
+    /// let (pk, sk) = ot.receiver_choose(0);
+    ///
+    /// let (c_0, c_1) = ot.sender_setup(1, 0, &pk);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `pk` is not a row vector of length `m` with modulus `q`.
+    pub fn sender_setup(&self, m_0: impl Into<Z>, m_1: impl Into<Z>, pk: &MatZq) -> (MatZq, MatZq) {
+        let p_1 = &self.vec_v - pk;
+
+        let pk_0 = self.mat_a.concat_vertical(pk).unwrap();
+        let pk_1 = self.mat_a.concat_vertical(&p_1).unwrap();
+
+        (self.regev.enc(&pk_0, m_0), self.regev.enc(&pk_1, m_1))
+    }
+
+    /// Decrypts the ciphertext `c_b` chosen by the receiver.
+    ///
+    /// Parameters:
+    /// - `sk`: specifies the secret state of the receiver
+    /// - `c_0`: specifies the encryption of `m_0`
+    /// - `c_1`: specifies the encryption of `m_1`
+    ///
+    /// Returns the chosen bit `m_b` as a [`Z`] instance.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::oblivious_transfer::OtLwe;
+    /// use qfall_math::integer::Z;
+    /// let ot = OtLwe::default();
+    /// let (pk, sk) = ot.receiver_choose(0);
+    /// let (c_0, c_1) = ot.sender_setup(1, 0, &pk);
+    ///
+    /// let m_b = ot.decode(&sk, &c_0, &c_1);
+    ///
+    /// assert_eq!(Z::ONE, m_b);
+    /// ```
+    pub fn decode(&self, sk: &OtReceiverKey, c_0: &MatZq, c_1: &MatZq) -> Z {
+        let cipher = if sk.b == Z::ZERO { c_0 } else { c_1 };
+
+        self.regev.dec(&sk.s, cipher)
+    }
+}
+
+impl Default for OtLwe {
+    /// Initializes a [`OtLwe`] struct with the parameters of [`Regev::default`]
+    /// and a uniform common reference string.
+    /// This parameter choice is not secure as the dimension of the lattice is too small,
+    /// but it provides an efficient working example.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::oblivious_transfer::OtLwe;
+    ///
+    /// let ot = OtLwe::default();
+    /// ```
+    fn default() -> Self {
+        Self::from_regev(Regev::default())
+    }
+}
+
+#[cfg(test)]
+mod test_oblivious_transfer {
+    use super::OtLwe;
+    use crate::construction::pk_encryption::PKEncryptionScheme;
+    use qfall_math::{
+        integer::Z,
+        traits::{Concatenate, GetNumColumns, GetNumRows},
+    };
+
+    /// Runs the protocol between a receiver with choice `b` and a sender with bits `m_0, m_1`.
+    fn run_protocol(ot: &OtLwe, b: i64, m_0: i64, m_1: i64) -> Z {
+        // receiver -> sender
+        let (pk, sk) = ot.receiver_choose(b);
+        // sender -> receiver
+        let (c_0, c_1) = ot.sender_setup(m_0, m_1, &pk);
+
+        ot.decode(&sk, &c_0, &c_1)
+    }
+
+    /// Ensures that the receiver obtains the chosen bit for all choices and messages.
+    #[test]
+    fn correct_transfer() {
+        let ot = OtLwe::default();
+
+        for b in 0..2 {
+            for m_0 in 0..2 {
+                for m_1 in 0..2 {
+                    let expected = Z::from(if b == 0 { m_0 } else { m_1 });
+
+                    assert_eq!(expected, run_protocol(&ot, b, m_0, m_1));
+                }
+            }
+        }
+    }
+
+    /// Ensures that the protocol works for parameters generated by `new_from_n`.
+    #[test]
+    fn correct_transfer_new_from_n() {
+        let ot = OtLwe::new_from_n(16);
+
+        assert_eq!(Z::ONE, run_protocol(&ot, 0, 1, 0));
+        assert_eq!(Z::ONE, run_protocol(&ot, 1, 0, 1));
+    }
+
+    /// Ensures that choices and messages are reduced modulo 2.
+    #[test]
+    fn bits_mod_two() {
+        let ot = OtLwe::default();
+
+        assert_eq!(Z::ONE, run_protocol(&ot, 2, 3, 0));
+        assert_eq!(Z::ZERO, run_protocol(&ot, 3, 1, 2));
+    }
+
+    /// Ensures that the receiver's message for both choices has the
+    /// same shape and that `p_0 + p_1 = v`.
+    #[test]
+    fn receiver_message_shape() {
+        let ot = OtLwe::default();
+
+        let (pk_0, _) = ot.receiver_choose(0);
+        let (pk_1, _) = ot.receiver_choose(1);
+
+        assert_eq!(ot.vec_v.get_num_columns(), pk_0.get_num_columns());
+        assert_eq!(pk_0.get_num_columns(), pk_1.get_num_columns());
+        assert_eq!(pk_0.get_num_rows(), pk_1.get_num_rows());
+    }
+
+    /// Ensures that the ciphertext `c_{1-b}` is encrypted under `p_{1-b} = v - p_b`,
+    /// for which the receiver's secret does not yield the correct message.
+    #[test]
+    fn other_message_hidden() {
+        let ot = OtLwe::default();
+        let (pk, sk) = ot.receiver_choose(0);
+        let p_1 = &ot.vec_v - &pk;
+
+        // p_0 is a valid Regev key under s, while p_1 is not
+        let pk_0 = ot.mat_a.concat_vertical(&pk).unwrap();
+        let pk_1 = ot.mat_a.concat_vertical(&p_1).unwrap();
+        let mut failures = 0;
+        for _ in 0..20 {
+            let c_0 = ot.regev.enc(&pk_0, 1);
+            let c_1 = ot.regev.enc(&pk_1, 1);
+
+            assert_eq!(Z::ONE, ot.regev.dec(&sk.s, &c_0));
+            if ot.regev.dec(&sk.s, &c_1) != Z::ONE {
+                failures += 1;
+            }
+        }
+
+        assert!(failures > 0);
+    }
+}
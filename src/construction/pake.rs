@@ -0,0 +1,334 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains a simplified password-authenticated key exchange (PAKE)
+//! based on LWE, where both parties mask their LWE samples with a hash of
+//! the shared password.
+//!
+//! The protocol consists of the following steps:
+//! 1. The initiator sends `B_1 + H_1(pw)` for `B_1 = A * S_1 + E_1`.
+//! 2. The responder removes the mask, chooses a uniform key `K` and sends
+//!     `B_2 + H_2(pw)` for `B_2 = A^t * S_2 + E_2` together with
+//!     `C = S_2^t * B_1 + E + ⌊q/2⌋ * K`.
+//! 3. The initiator removes the mask and recovers `K` by rounding `C - B_2^t * S_1`.
+//!
+//! Both parties derive the shared key from `K` and the transcript using HKDF.
+//! If the passwords differ, the removed masks are wrong and the keys diverge.
+
+use crate::construction::{
+    hash::sha256::hash_to_mat_zq_sha256,
+    kdf::{hkdf_expand, hkdf_extract},
+};
+use qfall_math::{
+    integer::{MatZ, Z},
+    integer_mod_q::{MatZq, Modulus, Zq},
+    rational::Q,
+    traits::{Distance, GetEntry, Pow},
+};
+use serde::{Deserialize, Serialize};
+
+/// The number of rows and columns of the exchanged key matrix `K`,
+/// i.e. `K` contains `KEY_DIM^2 = 64` bits.
+const KEY_DIM: i64 = 8;
+
+/// The `info` string used to derive the shared key.
+const KEY_INFO: &str = "qFALL-crypto LWE PAKE key";
+
+/// The first message sent from the initiator to the responder.
+///
+/// Attributes:
+/// - `b`: the masked LWE samples `A * S_1 + E_1 + H_1(pw)`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Msg1 {
+    pub b: MatZq,
+}
+
+/// The second message sent from the responder to the initiator.
+///
+/// Attributes:
+/// - `b`: the masked LWE samples `A^t * S_2 + E_2 + H_2(pw)`
+/// - `c`: the encapsulation `S_2^t * B_1 + E + ⌊q/2⌋ * K` of the key matrix `K`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Msg2 {
+    pub b: MatZq,
+    pub c: MatZq,
+}
+
+/// The state of the initiator between sending [`Msg1`] and receiving [`Msg2`],
+/// which has to be kept secret.
+#[derive(Debug, Clone)]
+pub struct State {
+    s_1: MatZq,
+    msg_1: Msg1,
+    password: String,
+}
+
+/// The key shared by both parties after a successful protocol execution.
+///
+/// Attributes:
+/// - `key`: the `32` bytes of the shared key
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SharedKey {
+    pub key: [u8; 32],
+}
+
+/// This struct manages and stores the public parameters of a simplified
+/// LWE-based password-authenticated key exchange.
+///
+/// **WARNING:** This scheme has no proof of security against offline
+/// dictionary attacks and is meant for educational purposes.
+///
+/// Attributes:
+/// - `n`: specifies the security parameter, which is not equal to the bit-security level
+/// - `q`: specifies the modulus
+/// - `s`: specifies the Gaussian parameter of all secrets and errors
+/// - `a`: the public uniform matrix `A ∈ Z_q^{n x n}`
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::construction::pake::LwePake;
+///
+/// let pake = LwePake::new_from_n(16);
+///
+/// let (state, msg_1) = pake.start("password");
+/// let (key_responder, msg_2) = pake.respond("password", &msg_1);
+/// let key_initiator = pake.finish(&state, &msg_2);
+///
+/// assert_eq!(key_initiator, key_responder);
+/// ```
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LwePake {
+    n: Z,
+    q: Modulus,
+    s: Q,
+    pub a: MatZq,
+}
+
+impl LwePake {
+    /// Initializes an [`LwePake`] instance for security parameter `n` by choosing
+    /// a prime `q ∈ [n^5 / 2, n^5]`, the Gaussian parameter `s = sqrt(n)`,
+    /// and a uniform matrix `A ∈ Z_q^{n x n}`.
+    ///
+    /// Parameters:
+    /// - `n`: the security parameter
+    ///
+    /// Returns an [`LwePake`] instance.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pake::LwePake;
+    ///
+    /// let pake = LwePake::new_from_n(16);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `n < 2`.
+    pub fn new_from_n(n: impl Into<Z>) -> Self {
+        let n: Z = n.into();
+        if n < Z::from(2) {
+            panic!("Security parameter n has to be larger than 1");
+        }
+
+        let upper_bound: Z = n.pow(5).unwrap();
+        let lower_bound = upper_bound.div_ceil(2);
+        let q = Modulus::from(Z::sample_prime_uniform(&lower_bound, &upper_bound).unwrap());
+
+        let s = n.sqrt();
+        let a = MatZq::sample_uniform(&n, &n, &q);
+
+        Self { n, q, s, a }
+    }
+
+    /// Samples a matrix of dimensions `num_rows x num_cols` from the
+    /// discrete Gaussian distribution with Gaussian parameter `s`.
+    fn sample_short(&self, num_rows: &Z, num_cols: i64) -> MatZq {
+        MatZq::sample_discrete_gauss(num_rows, num_cols, &self.q, &self.n, 0, &self.s).unwrap()
+    }
+
+    /// Hashes `password` into `Z_q^{n x KEY_DIM}` separated by `domain`.
+    fn mask(&self, domain: u8, password: &str) -> MatZq {
+        hash_to_mat_zq_sha256(&format!("{domain} {password}"), &self.n, KEY_DIM, &self.q)
+    }
+
+    /// Derives the shared key from the bits of the key matrix and the transcript.
+    fn derive_key(bits: &[bool], msg_1: &Msg1, msg_2: &Msg2) -> SharedKey {
+        let mut ikm = vec![0u8; bits.len().div_ceil(8)];
+        for (i, bit) in bits.iter().enumerate() {
+            if *bit {
+                ikm[i / 8] |= 1 << (i % 8);
+            }
+        }
+        let transcript = format!("{} {} {}", msg_1.b, msg_2.b, msg_2.c);
+
+        let prk = hkdf_extract(transcript.as_bytes(), &ikm);
+        let mut key = [0u8; 32];
+        key.copy_from_slice(&hkdf_expand(&prk, KEY_INFO, 32));
+        SharedKey { key }
+    }
+
+    /// Starts the protocol as initiator with the shared `password`.
+    ///
+    /// Parameters:
+    /// - `password`: the password shared by both parties
+    ///
+    /// Returns the secret [`State`] of the initiator and the [`Msg1`]
+    /// that is sent to the responder.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pake::LwePake;
+    ///
+    /// let pake = LwePake::new_from_n(16);
+    /// let (state, msg_1) = pake.start("password");
+    /// ```
+    pub fn start(&self, password: &str) -> (State, Msg1) {
+        // B_1 = A * S_1 + E_1
+        let s_1 = self.sample_short(&self.n, KEY_DIM);
+        let e_1 = self.sample_short(&self.n, KEY_DIM);
+        let b_1 = &self.a * &s_1 + e_1;
+
+        let msg_1 = Msg1 {
+            b: b_1 + self.mask(1, password),
+        };
+        let state = State {
+            s_1,
+            msg_1: msg_1.clone(),
+            password: String::from(password),
+        };
+
+        (state, msg_1)
+    }
+
+    /// Responds to `msg_1` of the initiator with the shared `password`.
+    ///
+    /// Parameters:
+    /// - `password`: the password shared by both parties
+    /// - `msg_1`: the message received from the initiator
+    ///
+    /// Returns the [`SharedKey`] of the responder and the [`Msg2`]
+    /// that is sent to the initiator.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pake::LwePake;
+    ///
+    /// let pake = LwePake::new_from_n(16);
+    /// let (_, msg_1) = pake.start("password");
+    ///
+    /// let (key, msg_2) = pake.respond("password", &msg_1);
+    /// ```
+    pub fn respond(&self, password: &str, msg_1: &Msg1) -> (SharedKey, Msg2) {
+        let b_1 = &msg_1.b - &self.mask(1, password);
+
+        // B_2 = A^t * S_2 + E_2
+        let s_2 = self.sample_short(&self.n, KEY_DIM);
+        let e_2 = self.sample_short(&self.n, KEY_DIM);
+        let b_2 = self.a.transpose() * &s_2 + e_2;
+
+        // C = S_2^t * B_1 + E + ⌊q/2⌋ * K
+        let key = MatZ::sample_uniform(KEY_DIM, KEY_DIM, 0, 2).unwrap();
+        let e = self.sample_short(&Z::from(KEY_DIM), KEY_DIM);
+        let key_q_half = Z::from(&self.q).div_floor(2) * &key;
+        let c = s_2.transpose() * &b_1 + e + MatZq::from((&key_q_half, &self.q));
+
+        let msg_2 = Msg2 {
+            b: b_2 + self.mask(2, password),
+            c,
+        };
+
+        let mut bits = vec![];
+        for i in 0..KEY_DIM {
+            for j in 0..KEY_DIM {
+                let entry: Z = key.get_entry(i, j).unwrap();
+                bits.push(entry == Z::ONE);
+            }
+        }
+
+        (Self::derive_key(&bits, msg_1, &msg_2), msg_2)
+    }
+
+    /// Finishes the protocol as initiator after receiving `msg_2`.
+    ///
+    /// Parameters:
+    /// - `state`: the state of the initiator generated by [`LwePake::start`]
+    /// - `msg_2`: the message received from the responder
+    ///
+    /// Returns the [`SharedKey`] of the initiator, which matches the key of
+    /// the responder with overwhelming probability if both used the same password.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pake::LwePake;
+    ///
+    /// let pake = LwePake::new_from_n(16);
+    /// let (state, msg_1) = pake.start("password");
+    /// let (_, msg_2) = pake.respond("password", &msg_1);
+    ///
+    /// let key = pake.finish(&state, &msg_2);
+    /// ```
+    pub fn finish(&self, state: &State, msg_2: &Msg2) -> SharedKey {
+        let b_2 = &msg_2.b - &self.mask(2, &state.password);
+
+        // C - B_2^t * S_1 = ⌊q/2⌋ * K + small noise
+        let noisy_key = &msg_2.c - b_2.transpose() * &state.s_1;
+
+        let q_half = Z::from(&self.q).div_floor(2);
+        let mut bits = vec![];
+        for i in 0..KEY_DIM {
+            for j in 0..KEY_DIM {
+                let entry: Zq = noisy_key.get_entry(i, j).unwrap();
+                bits.push(entry.distance(Z::ZERO) > entry.distance(&q_half));
+            }
+        }
+
+        Self::derive_key(&bits, &state.msg_1, msg_2)
+    }
+}
+
+#[cfg(test)]
+mod test_lwe_pake {
+    use super::LwePake;
+
+    /// Ensures that both parties derive the same key for the same password.
+    #[test]
+    fn matching_passwords() {
+        let pake = LwePake::new_from_n(16);
+
+        for password in ["", "password", "correct horse battery staple"] {
+            let (state, msg_1) = pake.start(password);
+            let (key_responder, msg_2) = pake.respond(password, &msg_1);
+            let key_initiator = pake.finish(&state, &msg_2);
+
+            assert_eq!(key_initiator, key_responder);
+        }
+    }
+
+    /// Ensures that the keys diverge if the passwords differ.
+    #[test]
+    fn wrong_password() {
+        let pake = LwePake::new_from_n(16);
+
+        let (state, msg_1) = pake.start("password");
+        let (key_responder, msg_2) = pake.respond("passw0rd", &msg_1);
+        let key_initiator = pake.finish(&state, &msg_2);
+
+        assert_ne!(key_initiator, key_responder);
+    }
+
+    /// Ensures that every execution results in a fresh key.
+    #[test]
+    fn fresh_keys() {
+        let pake = LwePake::new_from_n(16);
+
+        let (_, msg_1) = pake.start("password");
+        let (key_1, _) = pake.respond("password", &msg_1);
+        let (key_2, _) = pake.respond("password", &msg_1);
+
+        assert_ne!(key_1, key_2);
+    }
+}
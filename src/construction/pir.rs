@@ -0,0 +1,335 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains a single-server private information retrieval protocol
+//! based on LWE, which allows a client to retrieve an entry of a database
+//! without revealing the index of the entry to the server.
+//!
+//! The protocol consists of the following steps:
+//! 1. The client encrypts the unit vector `e_i` for its index `i` and sends the [`Query`].
+//! 2. The server computes the inner product of the encrypted unit vector
+//!     with its database and returns the encrypted [`Response`].
+//! 3. The client decrypts the response to obtain the `i`-th entry of the database.
+
+use qfall_math::{
+    integer::{MatZ, Z},
+    integer_mod_q::{MatZq, Modulus, Zq},
+    traits::{GetEntry, GetNumColumns, SetEntry},
+};
+use serde::{Deserialize, Serialize};
+
+/// The query of the client, i.e. LWE encryptions `(a_j, b_j)` of the
+/// entries of the unit vector `e_i` for all indices `j` of the database.
+///
+/// Attributes:
+/// - `a`: the matrix `A ∈ Z_q^{n x N}` containing all vectors `a_j` as columns
+/// - `b`: the vector `b = A^t * s + e + ⌊q/p⌋ * e_i ∈ Z_q^N`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Query {
+    pub a: MatZq,
+    pub b: MatZq,
+}
+
+/// The response of the server, i.e. an LWE encryption of the `i`-th entry of the database.
+///
+/// Attributes:
+/// - `a`: the vector `A * d ∈ Z_q^n` for the database `d`
+/// - `b`: the value `b^t * d ∈ Z_q`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Response {
+    pub a: MatZq,
+    pub b: Zq,
+}
+
+/// This struct manages and stores the public parameters of an LWE-based
+/// private information retrieval protocol for databases of `N` entries in `[0, p)`.
+///
+/// The client's query `(A, A^t * s + e + ⌊q/p⌋ * e_i)` hides the index `i` under LWE.
+/// The plaintext modulus `p` is chosen as the largest power of two
+/// such that `2 * p^2 * (N + 1) < q`, which ensures correctness
+/// as the errors are chosen uniformly from `{-1, 0, 1}`.
+///
+/// **WARNING:** Every pair of keys should only be used for a single query.
+/// This implementation is meant for educational purposes.
+///
+/// Attributes:
+/// - `database_size`: the number of entries `N` of the database
+/// - `n`: specifies the security parameter, which is not equal to the bit-security level
+/// - `q`: specifies the modulus
+/// - `p`: specifies the plaintext modulus, i.e. every entry of the database is in `[0, p)`
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::construction::pir::PIRLWE;
+/// use qfall_math::{integer::Z, integer_mod_q::Modulus};
+///
+/// let pir = PIRLWE::new(4, 16, Modulus::from(u32::MAX));
+/// let database = [Z::from(17), Z::from(42), Z::from(5), Z::from(1000)];
+///
+/// let (pk, sk) = pir.gen();
+/// let query = pir.query(1, &pk, &sk);
+/// let response = pir.answer(&database, &query);
+///
+/// assert_eq!(Z::from(42), pir.decode(&sk, &response));
+/// ```
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PIRLWE {
+    database_size: usize,
+    n: Z,
+    q: Modulus,
+    p: Z,
+}
+
+impl PIRLWE {
+    /// Initializes a [`PIRLWE`] instance for databases of `database_size` entries
+    /// and chooses the largest power of two `p` with `2 * p^2 * (N + 1) < q`
+    /// as plaintext modulus.
+    ///
+    /// Parameters:
+    /// - `database_size`: the number of entries `N` of the database
+    /// - `n`: the security parameter
+    /// - `q`: the modulus
+    ///
+    /// Returns a [`PIRLWE`] instance.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pir::PIRLWE;
+    /// use qfall_math::integer_mod_q::Modulus;
+    ///
+    /// let pir = PIRLWE::new(8, 16, Modulus::from(u32::MAX));
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `database_size < 1` or `n < 1`.
+    /// - if `q` is too small to ensure correctness for a plaintext modulus `p >= 2`.
+    pub fn new(database_size: usize, n: usize, q: Modulus) -> Self {
+        if database_size < 1 || n < 1 {
+            panic!("The database size and the security parameter have to be larger than 0");
+        }
+
+        let q_z = Z::from(&q);
+        let factor = Z::from(2 * (database_size as u64 + 1));
+        let mut p = Z::ONE;
+        while &factor * &p * &p * Z::from(4) < q_z {
+            p = &p * Z::from(2);
+        }
+        if p < Z::from(2) {
+            panic!("The modulus q is too small to ensure correctness of the protocol");
+        }
+
+        Self {
+            database_size,
+            n: Z::from(n as u64),
+            q,
+            p,
+        }
+    }
+
+    /// Returns the plaintext modulus `p`, i.e. every entry of the database has to be in `[0, p)`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pir::PIRLWE;
+    /// use qfall_math::integer_mod_q::Modulus;
+    /// let pir = PIRLWE::new(8, 16, Modulus::from(u32::MAX));
+    ///
+    /// let p = pir.get_plaintext_modulus();
+    /// ```
+    pub fn get_plaintext_modulus(&self) -> &Z {
+        &self.p
+    }
+
+    /// Generates a (pk, sk) pair of the client.
+    ///
+    /// Returns the public matrix `A ∈ Z_q^{n x N}` and the secret `s ∈ Z_q^n`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pir::PIRLWE;
+    /// use qfall_math::integer_mod_q::Modulus;
+    /// let pir = PIRLWE::new(8, 16, Modulus::from(u32::MAX));
+    ///
+    /// let (pk, sk) = pir.gen();
+    /// ```
+    pub fn gen(&self) -> (MatZq, MatZq) {
+        let pk = MatZq::sample_uniform(&self.n, self.database_size, &self.q);
+        let sk = MatZq::sample_uniform(&self.n, 1, &self.q);
+        (pk, sk)
+    }
+
+    /// Generates the query for index `i`, i.e. it encrypts the unit vector `e_i`.
+    ///
+    /// Parameters:
+    /// - `i`: the index of the requested entry
+    /// - `pk`: the public matrix `A` of the client
+    /// - `sk`: the secret `s` of the client
+    ///
+    /// Returns the [`Query`] that is sent to the server.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pir::PIRLWE;
+    /// use qfall_math::integer_mod_q::Modulus;
+    /// let pir = PIRLWE::new(8, 16, Modulus::from(u32::MAX));
+    /// let (pk, sk) = pir.gen();
+    ///
+    /// let query = pir.query(3, &pk, &sk);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `i` is not smaller than the database size.
+    pub fn query(&self, i: usize, pk: &MatZq, sk: &MatZq) -> Query {
+        if i >= self.database_size {
+            panic!("The index has to be smaller than the database size");
+        }
+
+        let vec_e = MatZ::sample_uniform(self.database_size, 1, -1, 2).unwrap();
+        let mut unit_vec = MatZ::new(self.database_size, 1);
+        unit_vec
+            .set_entry(i as i64, 0, Z::from(&self.q).div_floor(&self.p))
+            .unwrap();
+
+        let b = pk.transpose() * sk + MatZq::from((&(vec_e + unit_vec), &self.q));
+
+        Query { a: pk.clone(), b }
+    }
+
+    /// Computes the response to `query` for `database`,
+    /// i.e. the inner product of the encrypted unit vector and the database.
+    ///
+    /// Parameters:
+    /// - `database`: the `N` entries of the database, which are in `[0, p)`
+    /// - `query`: the query of the client
+    ///
+    /// Returns the [`Response`] that is sent back to the client.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pir::PIRLWE;
+    /// use qfall_math::{integer::Z, integer_mod_q::Modulus};
+    /// let pir = PIRLWE::new(2, 16, Modulus::from(u32::MAX));
+    /// let (pk, sk) = pir.gen();
+    /// let query = pir.query(0, &pk, &sk);
+    ///
+    /// let response = pir.answer(&[Z::from(3), Z::from(4)], &query);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if the length of `database` does not match the database size.
+    /// - if an entry of `database` is not in `[0, p)`.
+    /// - if `query` does not match the database size.
+    pub fn answer(&self, database: &[Z], query: &Query) -> Response {
+        if database.len() != self.database_size
+            || query.a.get_num_columns() != self.database_size as i64
+        {
+            panic!("The database and the query have to match the database size");
+        }
+
+        let mut vec_d = MatZ::new(self.database_size, 1);
+        for (j, entry) in database.iter().enumerate() {
+            if entry < &Z::ZERO || entry >= &self.p {
+                panic!("The entries of the database have to be in [0, p)");
+            }
+            vec_d.set_entry(j as i64, 0, entry).unwrap();
+        }
+        let vec_d = MatZq::from((&vec_d, &self.q));
+
+        let a = &query.a * &vec_d;
+        let b = (query.b.transpose() * &vec_d).get_entry(0, 0).unwrap();
+
+        Response { a, b }
+    }
+
+    /// Decrypts `response` to obtain the requested entry of the database
+    /// by rounding `p / q * (b - <a, s>)`.
+    ///
+    /// Parameters:
+    /// - `sk`: the secret `s` of the client
+    /// - `response`: the response of the server
+    ///
+    /// Returns the requested entry of the database.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pir::PIRLWE;
+    /// use qfall_math::{integer::Z, integer_mod_q::Modulus};
+    /// let pir = PIRLWE::new(2, 16, Modulus::from(u32::MAX));
+    /// let (pk, sk) = pir.gen();
+    /// let query = pir.query(1, &pk, &sk);
+    /// let response = pir.answer(&[Z::from(3), Z::from(4)], &query);
+    ///
+    /// assert_eq!(Z::from(4), pir.decode(&sk, &response));
+    /// ```
+    pub fn decode(&self, sk: &MatZq, response: &Response) -> Z {
+        let inner_product: Zq = (response.a.transpose() * sk).get_entry(0, 0).unwrap();
+        let noisy_entry = (&response.b - inner_product).get_representative_0_modulus();
+
+        let q = Z::from(&self.q);
+        (noisy_entry * &self.p + q.div_floor(2))
+            .div_floor(&q)
+            .modulo(&self.p)
+    }
+}
+
+#[cfg(test)]
+mod test_pir {
+    use super::PIRLWE;
+    use qfall_math::{integer::Z, integer_mod_q::Modulus};
+
+    /// Retrieves the entry at index `i` of a uniform database with `8` entries.
+    fn retrieve(i: usize) {
+        let pir = PIRLWE::new(8, 16, Modulus::from(u64::MAX));
+        let p = pir.get_plaintext_modulus();
+        let database: Vec<Z> = (0..8).map(|_| Z::sample_uniform(0, p).unwrap()).collect();
+
+        let (pk, sk) = pir.gen();
+        let query = pir.query(i, &pk, &sk);
+        let response = pir.answer(&database, &query);
+
+        assert_eq!(database[i], pir.decode(&sk, &response));
+    }
+
+    /// Ensures that the first entry is retrieved correctly.
+    #[test]
+    fn first_index() {
+        retrieve(0);
+    }
+
+    /// Ensures that an entry in the middle is retrieved correctly.
+    #[test]
+    fn middle_index() {
+        retrieve(4);
+    }
+
+    /// Ensures that the last entry is retrieved correctly.
+    #[test]
+    fn last_index() {
+        retrieve(7);
+    }
+
+    /// Ensures that the largest entries are retrieved correctly.
+    #[test]
+    fn largest_entries() {
+        let pir = PIRLWE::new(8, 16, Modulus::from(u64::MAX));
+        let database = vec![pir.get_plaintext_modulus() - Z::ONE; 8];
+
+        let (pk, sk) = pir.gen();
+        let query = pir.query(5, &pk, &sk);
+        let response = pir.answer(&database, &query);
+
+        assert_eq!(database[5], pir.decode(&sk, &response));
+    }
+
+    /// Ensures that too small moduli are rejected.
+    #[test]
+    #[should_panic]
+    fn too_small_modulus() {
+        let _ = PIRLWE::new(8, 16, Modulus::from(50));
+    }
+}
@@ -32,23 +32,40 @@
 //!     Chosen-ciphertext security from identity-based encryption.
 //!     In: Advances in Cryptology - EUROCRYPT 2004.
 //!     <https://link.springer.com/content/pdf/10.1007/b97182.pdf>
+//! - \[6\] Hoffstein, J., Pipher, J., and Silverman, J. H. (1998).
+//!     NTRU: A ring-based public key cryptosystem.
+//!     In: Algorithmic Number Theory - ANTS-III, Springer.
+//!     <https://doi.org/10.1007/BFb0054868>
 
+mod cca_dual_regev;
 mod ccs_from_ibe;
 mod dual_regev;
 mod dual_regev_discrete_gauss;
 mod lpr;
+mod lwe_stream_cipher;
+mod non_malleable_dual_regev;
+mod ntru_encrypt;
 mod regev;
 mod regev_discrete_gauss;
 mod ring_lpr;
+mod selective_opening;
+mod updatable_encryption;
 
+use crate::error::CryptoError;
+pub use cca_dual_regev::{CcaDualRegev, MacTag};
 pub use ccs_from_ibe::CCSfromIBE;
 pub use dual_regev::DualRegev;
 pub use dual_regev_discrete_gauss::DualRegevWithDiscreteGaussianRegularity;
 pub use lpr::LPR;
+pub use lwe_stream_cipher::LWEStreamCipher;
+pub use non_malleable_dual_regev::{NonMalleableCipher, NonMalleableDualRegev};
+pub use ntru_encrypt::NtruEncrypt;
 use qfall_math::integer::Z;
 pub use regev::Regev;
 pub use regev_discrete_gauss::RegevWithDiscreteGaussianRegularity;
 pub use ring_lpr::RingLPR;
+pub use selective_opening::{SelectiveOpeningEnc, SoCipher};
+pub use updatable_encryption::{UpdatableEncryption, UpdateToken};
 
 /// This trait should be implemented by every public key encryption scheme.
 /// It offers a simple interface to use and implement PKEs.
@@ -108,8 +125,13 @@ pub trait PKEncryptionSchemeMut {
     /// - `sk`: specifies the secret key used for decryption
     /// - `cipher`: specifies the ciphertext to be decrypted
     ///
-    /// Returns the decryption of `cipher` as a [`Z`] instance.
-    fn dec(&mut self, sk: &Self::SecretKey, cipher: &Self::Cipher) -> Z;
+    /// Returns the decryption of `cipher` as a [`Z`] instance
+    /// or a [`CryptoError`] if `cipher` is rejected.
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] if `cipher` is invalid, e.g. of type
+    ///     [`VerificationFailed`](CryptoError::VerificationFailed) if its integrity check fails.
+    fn dec(&mut self, sk: &Self::SecretKey, cipher: &Self::Cipher) -> Result<Z, CryptoError>;
 }
 
 /// This trait generically implements multi-bit encryption
@@ -0,0 +1,348 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains a wrapper of [`DualRegev`] following the
+//! encrypt-then-MAC paradigm, whose MAC key is encapsulated in the ciphertext.
+
+use super::{DualRegev, PKEncryptionScheme};
+use crate::{
+    construction::{
+        hash::sha256::hash_to_mat_zq_sha256,
+        kdf::{hkdf_expand, hkdf_extract},
+    },
+    error::CryptoError,
+};
+use qfall_math::{
+    integer::{MatZ, Z},
+    integer_mod_q::MatZq,
+    traits::{Concatenate, GetNumColumns, GetNumRows},
+};
+use serde::{Deserialize, Serialize};
+
+/// The number of bits of the encapsulated MAC key.
+const MAC_KEY_BITS: usize = 128;
+
+/// The info string used to derive the MAC key via HKDF.
+const MAC_KEY_INFO: &str = "qFALL-crypto CcaDualRegev MAC key";
+
+/// The tag of a [`CcaDualRegev`] ciphertext.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MacTag(pub MatZq);
+
+/// This struct manages and stores the public parameters of a [`DualRegev`]
+/// instance, whose ciphertexts are authenticated via encrypt-then-MAC.
+///
+/// The secret key is unknown to the encrypting party, hence the MAC key can not be
+/// derived from it. Instead, every encryption samples a fresh 128-bit key `κ`,
+/// which is encrypted bit by bit together with the message.
+/// The MAC key `A_κ ∈ Z_q^{n x (m+1)}` is derived from `κ` via HKDF.
+/// The tag is `A_κ * C`, i.e. the SIS function of `A_κ` applied to every column of the
+/// ciphertext `C`. Decryption recovers `κ` with the secret key, verifies the tag
+/// and only afterwards decrypts the message.
+/// Hence, any modification of an honestly generated ciphertext is detected,
+/// except with probability about `q^{-n}`.
+///
+/// **WARNING:** An adversary can replace the encryption of `κ` by an encryption
+/// of a key of its choice and recompute the tag. Thus, this construction
+/// does not provide IND-CCA2 security. Use [`CCSfromIBE`](super::CCSfromIBE)
+/// for an IND-CCA2 secure scheme.
+///
+/// Attributes:
+/// - `dual_regev`: the [`DualRegev`] instance used for encryption
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::construction::pk_encryption::CcaDualRegev;
+/// use qfall_math::integer::Z;
+///
+/// let scheme = CcaDualRegev::default();
+/// let (pk, sk) = scheme.gen();
+///
+/// let (cipher, tag) = scheme.enc(&pk, 1);
+///
+/// assert_eq!(Z::ONE, scheme.dec(&sk, &cipher, &tag).unwrap());
+/// ```
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CcaDualRegev {
+    pub dual_regev: DualRegev,
+}
+
+impl CcaDualRegev {
+    /// Wraps the provided [`DualRegev`] instance.
+    ///
+    /// Parameters:
+    /// - `dual_regev`: the [`DualRegev`] instance used for encryption
+    ///
+    /// Returns a [`CcaDualRegev`] instance.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pk_encryption::{CcaDualRegev, DualRegev};
+    ///
+    /// let scheme = CcaDualRegev::new(DualRegev::default());
+    /// ```
+    pub fn new(dual_regev: DualRegev) -> Self {
+        Self { dual_regev }
+    }
+
+    /// Generates a [`CcaDualRegev`] instance using [`DualRegev::new_from_n`].
+    ///
+    /// Parameters:
+    /// - `n`: specifies the security parameter
+    ///
+    /// Returns a [`CcaDualRegev`] instance.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pk_encryption::CcaDualRegev;
+    ///
+    /// let scheme = CcaDualRegev::new_from_n(15);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `n < 10`.
+    /// - if `n` does not fit into an [`i64`].
+    pub fn new_from_n(n: impl Into<Z>) -> Self {
+        Self::new(DualRegev::new_from_n(n))
+    }
+
+    /// Generates a key pair using [`DualRegev::gen`](PKEncryptionScheme::gen).
+    ///
+    /// Returns a key pair `(pk, sk)`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pk_encryption::CcaDualRegev;
+    /// let scheme = CcaDualRegev::default();
+    ///
+    /// let (pk, sk) = scheme.gen();
+    /// ```
+    pub fn gen(&self) -> (MatZq, MatZ) {
+        self.dual_regev.gen()
+    }
+
+    /// Encrypts `message mod 2` together with a fresh MAC key `κ` and
+    /// computes the tag of the resulting ciphertext.
+    ///
+    /// Parameters:
+    /// - `pk`: specifies the public key
+    /// - `message`: specifies the message that should be encrypted
+    ///
+    /// Returns a matrix, whose first 128 columns encrypt the bits of `κ` and
+    /// whose last column encrypts `message`, and the [`MacTag`] of this matrix.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pk_encryption::CcaDualRegev;
+    /// let scheme = CcaDualRegev::default();
+    /// let (pk, sk) = scheme.gen();
+    ///
+    /// let (cipher, tag) = scheme.enc(&pk, 1);
+    /// ```
+    pub fn enc(&self, pk: &MatZq, message: impl Into<Z>) -> (MatZq, MacTag) {
+        let key: [u8; MAC_KEY_BITS / 8] = rand::random();
+
+        let mut cipher = self.dual_regev.enc(pk, key[0] & 1);
+        for i in 1..MAC_KEY_BITS {
+            let bit = (key[i / 8] >> (i % 8)) & 1;
+            cipher = cipher
+                .concat_horizontal(&self.dual_regev.enc(pk, bit))
+                .unwrap();
+        }
+        cipher = cipher
+            .concat_horizontal(&self.dual_regev.enc(pk, message))
+            .unwrap();
+
+        let tag = self.mac(&key, &cipher);
+        (cipher, tag)
+    }
+
+    /// Decrypts the MAC key `κ` contained in `cipher`, verifies `tag` and
+    /// decrypts the message if the tag is valid.
+    ///
+    /// Parameters:
+    /// - `sk`: specifies the secret key
+    /// - `cipher`: specifies the ciphertext generated by [`CcaDualRegev::enc`]
+    /// - `tag`: specifies the tag of `cipher`
+    ///
+    /// Returns the decrypted message or a [`CryptoError`] if the ciphertext
+    /// was modified.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pk_encryption::CcaDualRegev;
+    /// use qfall_math::integer::Z;
+    /// let scheme = CcaDualRegev::default();
+    /// let (pk, sk) = scheme.gen();
+    /// let (cipher, tag) = scheme.enc(&pk, 0);
+    ///
+    /// let message = scheme.dec(&sk, &cipher, &tag).unwrap();
+    ///
+    /// assert_eq!(Z::ZERO, message);
+    /// ```
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] of type [`DecryptionFailed`](CryptoError::DecryptionFailed)
+    ///     if the dimensions or the modulus of `cipher` do not match the public parameters
+    ///     or if `tag` is not a valid tag of `cipher`.
+    pub fn dec(&self, sk: &MatZ, cipher: &MatZq, tag: &MacTag) -> Result<Z, CryptoError> {
+        if cipher.get_mod() != self.dual_regev.q
+            || Z::from(cipher.get_num_rows()) != &self.dual_regev.m + Z::ONE
+            || cipher.get_num_columns() != MAC_KEY_BITS as i64 + 1
+        {
+            return Err(CryptoError::DecryptionFailed);
+        }
+
+        let mut key = [0u8; MAC_KEY_BITS / 8];
+        for i in 0..MAC_KEY_BITS {
+            let bit = self
+                .dual_regev
+                .dec(sk, &cipher.get_column(i as i64).unwrap());
+            if bit == Z::ONE {
+                key[i / 8] |= 1 << (i % 8);
+            }
+        }
+
+        if &self.mac(&key, cipher) != tag {
+            return Err(CryptoError::DecryptionFailed);
+        }
+
+        Ok(self
+            .dual_regev
+            .dec(sk, &cipher.get_column(MAC_KEY_BITS as i64).unwrap()))
+    }
+
+    /// Derives the MAC key `A_κ` from `key` via HKDF and returns `A_κ * cipher`.
+    fn mac(&self, key: &[u8], cipher: &MatZq) -> MacTag {
+        let prk = hkdf_extract(&[], key);
+        let seed: String = hkdf_expand(&prk, MAC_KEY_INFO, 32)
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect();
+
+        let mac_key = hash_to_mat_zq_sha256(
+            &seed,
+            &self.dual_regev.n,
+            cipher.get_num_rows(),
+            &self.dual_regev.q,
+        );
+        MacTag(mac_key * cipher)
+    }
+}
+
+impl Default for CcaDualRegev {
+    /// Initializes a [`CcaDualRegev`] struct with [`DualRegev::default`].
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pk_encryption::CcaDualRegev;
+    ///
+    /// let scheme = CcaDualRegev::default();
+    /// ```
+    fn default() -> Self {
+        Self::new(DualRegev::default())
+    }
+}
+
+#[cfg(test)]
+mod test_cca_dual_regev {
+    use super::{CcaDualRegev, MAC_KEY_BITS};
+    use crate::error::CryptoError;
+    use qfall_math::{
+        integer::Z,
+        integer_mod_q::MatZq,
+        traits::{GetEntry, GetNumRows, SetEntry},
+    };
+
+    /// Returns a copy of `cipher`, where the `bit`-th bit of the
+    /// representative of entry `(row, column)` is flipped.
+    fn flip_bit(cipher: &MatZq, row: i64, column: i64, bit: u32) -> MatZq {
+        let mut flipped = cipher.clone();
+        let value: Z = cipher.get_entry(row, column).unwrap();
+        let power = Z::from(1_i64 << bit);
+        let new_value = if value.div_floor(&power).modulo(2) == Z::ZERO {
+            value + power
+        } else {
+            value - power
+        };
+        flipped.set_entry(row, column, new_value).unwrap();
+        flipped
+    }
+
+    /// Ensures that encryption and decryption work for both bits.
+    #[test]
+    fn cycle() {
+        let scheme = CcaDualRegev::default();
+        let (pk, sk) = scheme.gen();
+
+        for msg in [Z::ZERO, Z::ONE] {
+            let (cipher, tag) = scheme.enc(&pk, &msg);
+
+            assert_eq!(msg, scheme.dec(&sk, &cipher, &tag).unwrap());
+        }
+    }
+
+    /// Ensures that `new_from_n` generates a working instance.
+    #[test]
+    fn cycle_new_from_n() {
+        let scheme = CcaDualRegev::new_from_n(15);
+        let (pk, sk) = scheme.gen();
+
+        let (cipher, tag) = scheme.enc(&pk, 1);
+
+        assert_eq!(Z::ONE, scheme.dec(&sk, &cipher, &tag).unwrap());
+    }
+
+    /// Ensures that flipping a single bit of any entry in the column of the message
+    /// and in the first column of the key results in a decryption failure.
+    #[test]
+    fn single_bit_flip() {
+        let scheme = CcaDualRegev::default();
+        let (pk, sk) = scheme.gen();
+        let (cipher, tag) = scheme.enc(&pk, 1);
+
+        for column in [0, MAC_KEY_BITS as i64] {
+            for row in 0..cipher.get_num_rows() {
+                for bit in [0, 3] {
+                    let flipped = flip_bit(&cipher, row, column, bit);
+
+                    assert!(matches!(
+                        scheme.dec(&sk, &flipped, &tag),
+                        Err(CryptoError::DecryptionFailed)
+                    ));
+                }
+            }
+        }
+    }
+
+    /// Ensures that modifying the tag results in a decryption failure.
+    #[test]
+    fn modified_tag() {
+        let scheme = CcaDualRegev::default();
+        let (pk, sk) = scheme.gen();
+        let (cipher, mut tag) = scheme.enc(&pk, 0);
+
+        tag.0 = flip_bit(&tag.0, 0, 0, 0);
+
+        assert!(matches!(
+            scheme.dec(&sk, &cipher, &tag),
+            Err(CryptoError::DecryptionFailed)
+        ));
+    }
+
+    /// Ensures that a ciphertext of the wrong dimensions is rejected.
+    #[test]
+    fn wrong_dimensions() {
+        let scheme = CcaDualRegev::default();
+        let (pk, sk) = scheme.gen();
+        let (_, tag) = scheme.enc(&pk, 0);
+        let cipher = scheme.dual_regev.enc_str(&pk, "a").remove(0);
+
+        assert!(scheme.dec(&sk, &cipher, &tag).is_err());
+    }
+}
@@ -11,12 +11,40 @@
 //! via an [`IBEScheme`] and a [`SignatureScheme`].
 
 use super::PKEncryptionSchemeMut;
-use crate::construction::{identity_based_encryption::IBEScheme, signature::SignatureScheme};
+use crate::{
+    construction::{identity_based_encryption::IBEScheme, signature::SignatureScheme},
+    error::CryptoError,
+};
 use qfall_math::integer::Z;
 use serde::{Deserialize, Serialize};
 
 pub mod dual_regev_ibe_pfdh;
 
+/// This trait is only used by [`assert_ccs_from_ibe_bounds`] to report
+/// a missing [`ToString`] implementation of the IBE cipher with a helpful message.
+#[diagnostic::on_unimplemented(
+    message = "the cipher `{Self}` of the IBE scheme does not implement `ToString`",
+    label = "`CCSfromIBE` signs the cipher as a string, hence it must implement `ToString`",
+    note = "implement `Display` for the cipher of the IBE scheme, which provides `ToString`"
+)]
+pub(crate) trait CipherToString: ToString {}
+impl<T: ToString> CipherToString for T {}
+
+/// Checks at compile time that `IBE` and `Signature` satisfy all bounds required
+/// by the [`PKEncryptionSchemeMut`] implementation of [`CCSfromIBE`].
+/// It should be evaluated in a `const` item s.t. errors occur where a combination is
+/// defined, instead of where it is used, e.g.
+/// `const _: () = assert_ccs_from_ibe_bounds::<IBE, Signature>();`.
+pub(crate) const fn assert_ccs_from_ibe_bounds<IBE, Signature>()
+where
+    IBE: IBEScheme,
+    Signature: SignatureScheme,
+    IBE::Cipher: CipherToString,
+    IBE::MasterPublicKey: Clone,
+    Signature::PublicKey: Into<IBE::Identity> + Clone,
+{
+}
+
 /// This struct manages and stores the public parameters of an [`CCSfromIBE`]
 /// public key encryption construction based on [\[5\]](<index.html#:~:text=[5]>).
 ///
@@ -32,7 +60,7 @@ pub mod dual_regev_ibe_pfdh;
 ///
 /// let (pk, sk) = scheme.gen();
 /// let cipher = scheme.enc(&pk, 0);
-/// let m = scheme.dec(&sk, &cipher);
+/// let m = scheme.dec(&sk, &cipher).unwrap();
 ///
 /// assert_eq!(Z::ZERO, m);
 /// ```
@@ -63,12 +91,22 @@ where
     ///
     /// Then, `pk = mpk` and `sk = (mpk, msk)` are returned.
     ///
+    /// As `sk` is a tuple of the generic keys of the IBE, it does not implement
+    /// [`Zeroize`](zeroize::Zeroize) itself. If both keys implement [`ZeroizeEntries`](crate::utils::zeroize::ZeroizeEntries),
+    /// `sk` can be wrapped in a [`ZeroizingWrapper`](crate::utils::zeroize::ZeroizingWrapper)
+    /// to overwrite it with zeros once it goes out of scope.
+    ///
     /// # Examples
     /// ```
     /// use qfall_crypto::construction::pk_encryption::{CCSfromIBE, PKEncryptionSchemeMut};
+    /// use qfall_crypto::utils::zeroize::ZeroizingWrapper;
     /// let mut scheme = CCSfromIBE::init_dr_pfdh_from_n(4);
     ///
     /// let (pk, sk) = scheme.gen();
+    /// let sk = ZeroizingWrapper::from(sk);
+    ///
+    /// let cipher = scheme.enc(&pk, 1);
+    /// assert!(scheme.dec(&sk, &cipher).is_ok());
     /// ```
     fn gen(&mut self) -> (Self::PublicKey, Self::SecretKey) {
         let (pk, sk) = self.ibe.setup();
@@ -88,6 +126,9 @@ where
     ///
     /// Returns a cipher consisting of a tuple `cipher = (vrfy_key, c, sigma)`.
     ///
+    /// # Panics ...
+    /// - if the signature scheme fails to sign `c` with its freshly generated key pair.
+    ///
     /// # Examples
     /// ```
     /// use qfall_crypto::construction::pk_encryption::{CCSfromIBE, PKEncryptionSchemeMut};
@@ -100,12 +141,17 @@ where
         let (vrfy_key, sign_key) = self.signature.gen();
 
         let c = self.ibe.enc(pk, &vrfy_key.clone().into(), message);
-        let sigma = self.signature.sign(c.to_string(), &sign_key, &vrfy_key);
+        // the key pair was generated right before, hence signing only fails
+        // if the signature scheme is broken
+        let sigma = self
+            .signature
+            .sign(c.to_string(), &sign_key, &vrfy_key)
+            .unwrap();
         (vrfy_key, c, sigma)
     }
 
     /// Decrypts the provided `cipher` using the secret key `sk` by following these steps:
-    /// - if signature.vrfy(c, sigma, vrfy_key) is not successful, output -1, otherwise proceed
+    /// - if signature.vrfy(c, sigma, vrfy_key) is not successful, output an error, otherwise proceed
     /// - secret_key = ibe.extract(mpk, msk, vrfy_key), i.e. extract the secret key for identity `vrfy_key`
     /// - ibe.dec(secret_key, c)
     ///
@@ -115,7 +161,8 @@ where
     /// - `sk`: specifies the secret key `sk = (mpk, msk)`
     /// - `cipher`: specifies the cipher containing `cipher = (vrfy_key, c, sigma)`
     ///
-    /// Returns the decryption of `cipher` as a [`Z`] instance.
+    /// Returns the decryption of `cipher` as a [`Z`] instance
+    /// or a [`CryptoError`] if `cipher` is rejected.
     ///
     /// # Examples
     /// ```
@@ -125,19 +172,134 @@ where
     ///
     /// let (pk, sk) = scheme.gen();
     /// let cipher = scheme.enc(&pk, 1);
-    /// let m = scheme.dec(&sk, &cipher);
+    /// let m = scheme.dec(&sk, &cipher).unwrap();
     ///
     /// assert_eq!(Z::ONE, m);
     /// ```
-    fn dec(&mut self, sk: &Self::SecretKey, cipher: &Self::Cipher) -> Z {
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] of type [`VerificationFailed`](CryptoError::VerificationFailed)
+    ///     if `sigma` is not a valid signature of `c` under `vrfy_key`.
+    /// - Returns a [`CryptoError`] if the secret key of `vrfy_key` could not be extracted
+    ///     or `c` could not be decrypted by the IBE scheme.
+    fn dec(&mut self, sk: &Self::SecretKey, cipher: &Self::Cipher) -> Result<Z, CryptoError> {
         if !self
             .signature
             .vfy(cipher.1.to_string(), &cipher.2, &cipher.0)
         {
-            return Z::MINUS_ONE;
+            return Err(CryptoError::VerificationFailed);
         }
 
-        let secret = self.ibe.extract(&sk.0, &sk.1, &cipher.0.clone().into());
+        let secret = self.ibe.extract(&sk.0, &sk.1, &cipher.0.clone().into())?;
         self.ibe.dec(&secret, &cipher.1)
     }
 }
+
+impl<IBE, Signature> CCSfromIBE<IBE, Signature>
+where
+    IBE: IBEScheme,
+    Signature: SignatureScheme,
+    IBE::Cipher: ToString,
+    IBE::MasterPublicKey: Clone,
+    Signature::PublicKey: Into<IBE::Identity> + Clone,
+{
+    /// Encrypts a byte slice bit by bit, where each byte is encrypted
+    /// via 8 successive calls of [`PKEncryptionSchemeMut::enc`].
+    /// The order of the ciphers is `[c_0, c_1, ..., c_{8l-1}]`, where `c_{8i}`
+    /// is the encryption of the least significant bit of the `i`-th byte.
+    /// Hence, every bit is encrypted with respect to a fresh verification key.
+    ///
+    /// Parameters:
+    /// - `pk`: specifies the public key `pk = mpk`
+    /// - `message`: specifies the bytes that should be encrypted
+    ///
+    /// Returns a [`Vec`] of `8 * message.len()` ciphers of the form `(vrfy_key, c, sigma)`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pk_encryption::{CCSfromIBE, PKEncryptionSchemeMut};
+    /// let mut scheme = CCSfromIBE::init_dr_pfdh_from_n(4);
+    ///
+    /// let (pk, sk) = scheme.gen();
+    /// let ciphers = scheme.enc_bytes(&pk, b"Hi");
+    ///
+    /// assert_eq!(16, ciphers.len());
+    /// ```
+    pub fn enc_bytes(
+        &mut self,
+        pk: &IBE::MasterPublicKey,
+        message: &[u8],
+    ) -> Vec<(Signature::PublicKey, IBE::Cipher, Signature::Signature)> {
+        let mut ciphers = Vec::with_capacity(8 * message.len());
+        for byte in message {
+            for i in 0..8 {
+                ciphers.push(self.enc(pk, (byte >> i) & 1));
+            }
+        }
+
+        ciphers
+    }
+
+    /// Decrypts a byte slice encrypted via [`CCSfromIBE::enc_bytes`].
+    /// The signature of every cipher is verified before any bit is decrypted.
+    ///
+    /// Parameters:
+    /// - `sk`: specifies the secret key `sk = (mpk, msk)`
+    /// - `ciphers`: specifies the ciphers of the bits of the message,
+    ///     where 8 successive ciphers encode a byte starting with the least significant bit
+    ///
+    /// Returns the decrypted bytes or a [`CryptoError`] if a signature is invalid.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pk_encryption::{CCSfromIBE, PKEncryptionSchemeMut};
+    /// let mut scheme = CCSfromIBE::init_dr_pfdh_from_n(4);
+    ///
+    /// let (pk, sk) = scheme.gen();
+    /// let ciphers = scheme.enc_bytes(&pk, b"Hi");
+    ///
+    /// let message = scheme.dec_bytes(&sk, &ciphers).unwrap();
+    ///
+    /// assert_eq!(b"Hi".to_vec(), message);
+    /// ```
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] of type [`VerificationFailed`](CryptoError::VerificationFailed)
+    ///     if the signature of any cipher is invalid.
+    /// - Returns a [`CryptoError`] of type [`DecryptionFailed`](CryptoError::DecryptionFailed)
+    ///     if the number of ciphers is not a multiple of 8 or
+    ///     if a cipher does not decrypt to a bit.
+    /// - Returns a [`CryptoError`] if a secret key could not be extracted
+    ///     or a cipher could not be decrypted by the IBE scheme.
+    pub fn dec_bytes(
+        &mut self,
+        sk: &(IBE::MasterPublicKey, IBE::MasterSecretKey),
+        ciphers: &[(Signature::PublicKey, IBE::Cipher, Signature::Signature)],
+    ) -> Result<Vec<u8>, CryptoError> {
+        if ciphers.len() % 8 != 0 {
+            return Err(CryptoError::DecryptionFailed);
+        }
+        for (vrfy_key, c, sigma) in ciphers {
+            if !self.signature.vfy(c.to_string(), sigma, vrfy_key) {
+                return Err(CryptoError::VerificationFailed);
+            }
+        }
+
+        let mut bytes = Vec::with_capacity(ciphers.len() / 8);
+        for byte_ciphers in ciphers.chunks(8) {
+            let mut byte = 0u8;
+            for (i, (vrfy_key, c, _)) in byte_ciphers.iter().enumerate() {
+                let secret = self.ibe.extract(&sk.0, &sk.1, &vrfy_key.clone().into())?;
+                let bit = self.ibe.dec(&secret, c)?;
+                if bit == Z::ONE {
+                    byte |= 1 << i;
+                } else if bit != Z::ZERO {
+                    return Err(CryptoError::DecryptionFailed);
+                }
+            }
+            bytes.push(byte);
+        }
+
+        Ok(bytes)
+    }
+}
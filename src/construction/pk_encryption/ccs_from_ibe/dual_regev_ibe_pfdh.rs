@@ -9,7 +9,7 @@
 //! A classical implementation of the [`CCSfromIBE`] scheme using
 //! the [`DualRegevIBE`] and [`PFDH`].
 
-use super::CCSfromIBE;
+use super::{assert_ccs_from_ibe_bounds, CCSfromIBE};
 use crate::{
     construction::{
         hash::sha256::HashMatZq, identity_based_encryption::DualRegevIBE, signature::PFDH,
@@ -22,6 +22,12 @@ use qfall_math::{
     rational::{MatQ, Q},
 };
 
+// ensures that this combination satisfies the bounds of `CCSfromIBE` at compile time
+const _: () = assert_ccs_from_ibe_bounds::<
+    DualRegevIBE,
+    PFDH<MatZq, (MatZ, MatQ), MatZ, MatZq, PSFGPV, HashMatZq>,
+>();
+
 impl CCSfromIBE<DualRegevIBE, PFDH<MatZq, (MatZ, MatQ), MatZ, MatZq, PSFGPV, HashMatZq>> {
     /// Initializes a [`CCSfromIBE`] PK encryption scheme from a [`DualRegevIBE`] and a [`PFDH`] signature.
     ///
@@ -90,7 +96,7 @@ impl CCSfromIBE<DualRegevIBE, PFDH<MatZq, (MatZ, MatQ), MatZ, MatZq, PSFGPV, Has
             "n needs to be chosen larger than 3 for this function to work properly."
         );
 
-        let ibe = DualRegevIBE::new_from_n(&n);
+        let ibe = DualRegevIBE::new_from_n(&n).unwrap();
         let pfdh = PFDH::init_gpv(&n, &ibe.dual_regev.q, &ibe.psf.s, &n);
 
         Self {
@@ -102,9 +108,64 @@ impl CCSfromIBE<DualRegevIBE, PFDH<MatZq, (MatZ, MatQ), MatZ, MatZq, PSFGPV, Has
 
 #[cfg(test)]
 mod test_ccs_from_ibe {
-    use super::CCSfromIBE;
-    use crate::construction::pk_encryption::PKEncryptionSchemeMut;
-    use qfall_math::integer::Z;
+    use super::{assert_ccs_from_ibe_bounds, CCSfromIBE};
+    use crate::{
+        construction::{
+            hash::sha256::HashMatZq, identity_based_encryption::DualRegevIBE,
+            pk_encryption::PKEncryptionSchemeMut, signature::PFDH,
+        },
+        error::CryptoError,
+        primitive::psf::PSFGPV,
+    };
+    use qfall_math::{
+        integer::{MatZ, Z},
+        integer_mod_q::MatZq,
+        rational::MatQ,
+    };
+
+    /// Verifies the bounds of [`CCSfromIBE`] for the [`DualRegevIBE`] + [`PFDH`] instantiation.
+    /// This function does not compile if a bound is violated.
+    const fn type_check_ccs_from_ibe() {
+        assert_ccs_from_ibe_bounds::<
+            DualRegevIBE,
+            PFDH<MatZq, (MatZ, MatQ), MatZ, MatZq, PSFGPV, HashMatZq>,
+        >()
+    }
+
+    /// Ensures that the default instantiation satisfies all bounds and can be used.
+    #[test]
+    fn default_instantiation_compiles() {
+        const _: () = type_check_ccs_from_ibe();
+
+        let mut scheme: CCSfromIBE<
+            DualRegevIBE,
+            PFDH<MatZq, (MatZ, MatQ), MatZ, MatZq, PSFGPV, HashMatZq>,
+        > = CCSfromIBE::init_dr_pfdh_from_n(4);
+        let (pk, sk) = scheme.gen();
+        let cipher = scheme.enc(&pk, 1);
+
+        assert_eq!(Z::ONE, scheme.dec(&sk, &cipher).unwrap());
+    }
+
+    // A wrong combination does not compile, e.g. an IBE scheme whose cipher
+    // does not implement `ToString` fails with the following error.
+    //
+    // #[test]
+    // fn wrong_combination() {
+    //     struct VecCipherIBE;
+    //     impl IBEScheme for VecCipherIBE {
+    //         type Cipher = Vec<u8>;
+    //         type Identity = MatZq;
+    //         ...
+    //     }
+    //     const _: () = assert_ccs_from_ibe_bounds::<
+    //         VecCipherIBE,
+    //         PFDH<MatZq, (MatZ, MatQ), MatZ, MatZq, PSFGPV, HashMatZq>,
+    //     >();
+    // }
+    //
+    // error[E0277]: the cipher `Vec<u8>` of the IBE scheme does not implement `ToString`
+    //    = note: implement `Display` for the cipher of the IBE scheme, which provides `ToString`
 
     /// Checks whether the full-cycle of gen, enc, dec works properly
     /// for message 0 and small n.
@@ -115,7 +176,7 @@ mod test_ccs_from_ibe {
 
         let (pk, sk) = scheme.gen();
         let cipher = scheme.enc(&pk, &msg);
-        let m = scheme.dec(&sk, &cipher);
+        let m = scheme.dec(&sk, &cipher).unwrap();
         assert_eq!(msg, m);
     }
 
@@ -128,7 +189,49 @@ mod test_ccs_from_ibe {
 
         let (pk, sk) = scheme.gen();
         let cipher = scheme.enc(&pk, &msg);
-        let m = scheme.dec(&sk, &cipher);
+        let m = scheme.dec(&sk, &cipher).unwrap();
+        assert_eq!(msg, m);
+    }
+
+    /// Checks whether a byte slice is recovered by `enc_bytes` and `dec_bytes`.
+    #[test]
+    fn cycle_bytes() {
+        let msg = "Hello World!".as_bytes();
+        let mut scheme = CCSfromIBE::init_dr_pfdh_from_n(4);
+
+        let (pk, sk) = scheme.gen();
+        let ciphers = scheme.enc_bytes(&pk, msg);
+        let m = scheme.dec_bytes(&sk, &ciphers).unwrap();
+
+        assert_eq!(8 * msg.len(), ciphers.len());
         assert_eq!(msg, m);
     }
+
+    /// Ensures that `dec_bytes` returns an error if the signature of a cipher
+    /// does not match its ciphertext.
+    #[test]
+    fn bytes_invalid_signature() {
+        let mut scheme = CCSfromIBE::init_dr_pfdh_from_n(4);
+
+        let (pk, sk) = scheme.gen();
+        let mut ciphers = scheme.enc_bytes(&pk, &[1]);
+        ciphers[3].2 = ciphers[0].2.clone();
+
+        assert!(matches!(
+            scheme.dec_bytes(&sk, &ciphers),
+            Err(CryptoError::VerificationFailed)
+        ));
+    }
+
+    /// Ensures that `dec_bytes` returns an error if the number of ciphers
+    /// is not a multiple of 8.
+    #[test]
+    fn bytes_incomplete() {
+        let mut scheme = CCSfromIBE::init_dr_pfdh_from_n(4);
+
+        let (pk, sk) = scheme.gen();
+        let ciphers = scheme.enc_bytes(&pk, &[1]);
+
+        assert!(scheme.dec_bytes(&sk, &ciphers[..7]).is_err());
+    }
 }
@@ -10,12 +10,20 @@
 //! public key Dual Regev encryption scheme.
 
 use super::{GenericMultiBitEncryption, PKEncryptionScheme};
+use crate::{
+    error::CryptoError,
+    params::NistLevel,
+    utils::{
+        conversion::q_to_f64,
+        math_util::{rounding_failure_probability, ZqArith},
+    },
+};
 use qfall_math::{
     error::MathError,
     integer::{MatZ, Z},
     integer_mod_q::{MatZq, Modulus, Zq},
     rational::Q,
-    traits::{Concatenate, Distance, GetEntry, Pow, SetEntry},
+    traits::{Concatenate, GetEntry, GetNumRows, Pow, SetEntry},
 };
 use serde::{Deserialize, Serialize};
 
@@ -206,6 +214,11 @@ impl DualRegev {
     /// The required properties are:
     /// - α = o (1 / ( sqrt(n) * log n ) )
     /// - concentration bound with r=5: r * sqrt(m) * α > q/4
+    /// - the decryption failure probability computed by
+    ///     [`DualRegev::failure_probability`] is at most 2^{-10}
+    /// - the decryption failure probability of a message vector of length `n`
+    ///     encrypted via [`DualRegev::enc_vec`] is at most 2^{-10},
+    ///     where every coordinate is bounded independently by [`DualRegev::failure_probability`]
     ///
     /// **WARNING:** Some requirements are missing to ensure overwhelming correctness of the scheme.
     ///
@@ -247,10 +260,84 @@ impl DualRegev {
                 "Correctness is not guaranteed as 5 * sqrt(m) * α > q/4, but 5 * sqrt(m) * α <= q/4 is required."
             )));
         }
+        // quantitative bound on the decryption failure probability
+        let failure_probability = self.failure_probability();
+        if failure_probability > Q::from(0.5_f64.powi(10)) {
+            return Err(MathError::InvalidIntegerInput(format!(
+                "Correctness is not guaranteed as the decryption failure probability is bounded by {failure_probability}, but at most 2^(-10) is required."
+            )));
+        }
+        // union bound over the n coordinates of a message encrypted via `enc_vec`
+        let failure_probability_vec = &self.n * &failure_probability;
+        if failure_probability_vec > Q::from(0.5_f64.powi(10)) {
+            return Err(MathError::InvalidIntegerInput(format!(
+                "Correctness is not guaranteed for messages of length n encrypted via enc_vec as the decryption failure probability is bounded by {failure_probability_vec}, but at most 2^(-10) is required."
+            )));
+        }
 
         Ok(())
     }
 
+    /// Computes the probability that the decryption noise reduced modulo `q`
+    /// exceeds q/4, i.e. that a ciphertext is decrypted incorrectly.
+    ///
+    /// The noise `e^t * [-x | 1]` is the sum of at most `m + 1` independent
+    /// samples of a discrete Gaussian with Gaussian parameter `α * q`,
+    /// i.e. standard deviation `σ = α * q / sqrt(2π)`.
+    /// It is approximated by a Gaussian with standard deviation `σ * sqrt(m + 1)`,
+    /// i.e. the secret key is assumed to be all-one, and the probability that it is
+    /// decoded incorrectly after the reduction modulo `q` is computed by
+    /// [`rounding_failure_probability`].
+    /// Hence, the probability is close to `1/2` for an overly large `α`.
+    ///
+    /// This deviates from the plain Gaussian tail `erfc(q / (4 * α * q * sqrt(m)) / sqrt(2))`,
+    /// which treats the Gaussian parameter `α * q` as standard deviation, ignores the
+    /// last noise coordinate and the reduction modulo `q`, and hence tends to `1`
+    /// instead of `1/2` for large `α`. As `σ * sqrt(m + 1) <= α * q * sqrt(m)`,
+    /// the returned probability never exceeds this tail bound, i.e. it is a tighter estimate
+    /// that agrees with [`DualRegev::decryption_failure_prob`].
+    ///
+    /// Returns the decryption failure probability as a [`Q`] in `[0, 1/2]`,
+    /// which is [`Q::ZERO`] if it is smaller than 2^{-128}.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pk_encryption::DualRegev;
+    /// use qfall_math::rational::Q;
+    /// let dual_regev = DualRegev::secure128();
+    ///
+    /// let failure_probability = dual_regev.failure_probability();
+    ///
+    /// assert!(failure_probability < Q::from(0.5_f64.powi(40)));
+    /// ```
+    pub fn failure_probability(&self) -> Q {
+        let q = q_to_f64(&Q::from(Z::from(&self.q)));
+        let sigma = q_to_f64(&self.alpha) * q / (2.0 * std::f64::consts::PI).sqrt();
+        let noise_sigma = sigma * q_to_f64(&Q::from(&self.m + Z::ONE)).sqrt();
+
+        let probability = rounding_failure_probability(q, noise_sigma);
+
+        if probability < 0.5_f64.powi(128) {
+            Q::ZERO
+        } else {
+            Q::from(probability)
+        }
+    }
+
+    /// Returns the decryption failure probability of [`DualRegev::failure_probability`]
+    /// as an [`f64`] in `[0, 1/2]`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pk_encryption::DualRegev;
+    /// let dual_regev = DualRegev::secure128();
+    ///
+    /// assert!(dual_regev.decryption_failure_prob() < 0.5_f64.powi(40));
+    /// ```
+    pub fn decryption_failure_prob(&self) -> f64 {
+        q_to_f64(&self.failure_probability())
+    }
+
     /// Checks the public parameters for security according to Theorem 1.1
     /// and Lemma 5.4 of [\[3\]](<index.html#:~:text=[3]>).
     ///
@@ -300,6 +387,207 @@ impl DualRegev {
     pub fn secure128() -> Self {
         Self::new(230, 5313, 7764299, 0.0011)
     }
+
+    /// Instantiates a [`DualRegev`] scheme with the parameter set of the NIST security `level`,
+    /// where `m` is chosen as [`NistParams::m`](crate::params::NistParams::m).
+    ///
+    /// Parameters:
+    /// - `level`: specifies the NIST security level
+    ///
+    /// Returns a [`DualRegev`] instance with the parameters of `level`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::{construction::pk_encryption::DualRegev, params::NistLevel};
+    ///
+    /// let dual_regev = DualRegev::from_nist_level(NistLevel::Level1);
+    /// ```
+    pub fn from_nist_level(level: NistLevel) -> Self {
+        let params = level.params();
+        Self::new(params.n as u64, params.m() as u64, params.q, params.alpha)
+    }
+
+    /// Encrypts a UTF-8 string byte by byte, where each byte is encrypted
+    /// via 8 successive calls of [`DualRegev::enc`].
+    /// The order of the ciphers is `[c_0, c_1, ..., c_{8l-1}]`, where `c_{8i}`
+    /// is the encryption of the least significant bit of the `i`-th byte.
+    ///
+    /// Parameters:
+    /// - `pk`: specifies the public key `pk = A`
+    /// - `message`: specifies the string that should be encrypted
+    ///
+    /// Returns a [`Vec`] of `8 * message.len()` ciphers of type [`MatZq`].
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pk_encryption::{PKEncryptionScheme, DualRegev};
+    /// let dual_regev = DualRegev::default();
+    /// let (pk, sk) = dual_regev.gen();
+    ///
+    /// let ciphers = dual_regev.enc_str(&pk, "Hello World!");
+    ///
+    /// assert_eq!(8 * 12, ciphers.len());
+    /// ```
+    pub fn enc_str(&self, pk: &MatZq, message: &str) -> Vec<MatZq> {
+        let mut ciphers = Vec::with_capacity(8 * message.len());
+        for byte in message.bytes() {
+            for i in 0..8 {
+                ciphers.push(self.enc(pk, (byte >> i) & 1));
+            }
+        }
+
+        ciphers
+    }
+
+    /// Decrypts a UTF-8 string encrypted via [`DualRegev::enc_str`].
+    ///
+    /// Parameters:
+    /// - `sk`: specifies the secret key `sk = x`
+    /// - `ciphers`: specifies the ciphers of the bits of the string,
+    ///     where 8 successive ciphers encode a byte starting with the least significant bit
+    ///
+    /// Returns the decrypted string or a [`CryptoError`] if the decrypted
+    /// bytes are not a valid UTF-8 string.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pk_encryption::{PKEncryptionScheme, DualRegev};
+    /// let dual_regev = DualRegev::default();
+    /// let (pk, sk) = dual_regev.gen();
+    /// let ciphers = dual_regev.enc_str(&pk, "Hello World!");
+    ///
+    /// let message = dual_regev.dec_str(&sk, &ciphers).unwrap();
+    ///
+    /// assert_eq!("Hello World!", message);
+    /// ```
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] of type [`DecryptionFailed`](CryptoError::DecryptionFailed)
+    ///     if the number of ciphers is not a multiple of 8 or
+    ///     if the decrypted bytes are not a valid UTF-8 string.
+    pub fn dec_str(&self, sk: &MatZ, ciphers: &[MatZq]) -> Result<String, CryptoError> {
+        if ciphers.len() % 8 != 0 {
+            return Err(CryptoError::DecryptionFailed);
+        }
+
+        let mut bytes = Vec::with_capacity(ciphers.len() / 8);
+        for byte_ciphers in ciphers.chunks(8) {
+            let mut byte = 0u8;
+            for (i, cipher) in byte_ciphers.iter().enumerate() {
+                if self.dec(sk, cipher) == Z::ONE {
+                    byte |= 1 << i;
+                }
+            }
+            bytes.push(byte);
+        }
+
+        String::from_utf8(bytes).map_err(|_| CryptoError::DecryptionFailed)
+    }
+
+    /// Encrypts a vector of bits, where each coordinate of `message` is
+    /// encoded into its own column of the cipher by following these steps:
+    /// - S <- Z_q^{n x l}
+    /// - E <- χ^{(m+1) x l}
+    /// - C^t = S^t * A + E^t + [0^{l x m} | msg * ⌊q/2⌋]
+    ///     where χ is discrete Gaussian distributed with center 0 and Gaussian parameter q * α.
+    ///
+    /// Hence, every column of `C` is an independent encryption of the corresponding
+    /// coordinate of `message mod 2` as generated by [`DualRegev::enc`].
+    ///
+    /// Parameters:
+    /// - `pk`: specifies the public key `pk = A`
+    /// - `message`: specifies the column vector of length `l` that should be encrypted
+    ///
+    /// Returns a cipher `C ∈ Z_q^{(m+1) x l}` of type [`MatZq`].
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pk_encryption::{PKEncryptionScheme, DualRegev};
+    /// use qfall_math::integer::MatZ;
+    /// use std::str::FromStr;
+    /// let dual_regev = DualRegev::default();
+    /// let (pk, sk) = dual_regev.gen();
+    ///
+    /// let cipher = dual_regev.enc_vec(&pk, &MatZ::from_str("[[1],[0],[1]]").unwrap());
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `message` is not a column vector.
+    pub fn enc_vec(&self, pk: &MatZq, message: &MatZ) -> MatZq {
+        if !message.is_column_vector() {
+            panic!("The message has to be a column vector.");
+        }
+        let length = message.get_num_rows();
+
+        // S^t <- Z_q^{l x n}
+        let mat_s_t = MatZq::sample_uniform(length, &self.n, &self.q);
+        // E^t <- χ^{l x (m+1)}
+        let mat_e_t = MatZq::sample_discrete_gauss(
+            length,
+            &(&self.m + 1),
+            &self.q,
+            &self.n,
+            0,
+            &self.alpha * Z::from(&self.q),
+        )
+        .unwrap();
+
+        // C^t = S^t * A + E^t + [0^{l x m} | msg * ⌊q/2⌋]
+        let mut c = (mat_s_t * pk + mat_e_t).transpose();
+
+        // hide the i-th coordinate in the last entry of the i-th column
+        let q = Z::from(&self.q);
+        for i in 0..length {
+            let bit: Z = message.get_entry(i, 0).unwrap();
+            let msg_q_half = ZqArith::scale_by_half_q(&bit.modulo(2), &q);
+            let last_entry: Zq = c.get_entry(-1, i).unwrap();
+            c.set_entry(-1, i, last_entry + msg_q_half).unwrap();
+        }
+
+        c
+    }
+
+    /// Decrypts a cipher generated by [`DualRegev::enc_vec`] by following these steps:
+    /// - x = C^t * [-sk^t | 1]^t
+    /// - for every coordinate of x: if it is closer to ⌊q/2⌋ than to 0, output 1.
+    ///     Otherwise, output 0.
+    ///
+    /// Parameters:
+    /// - `sk`: specifies the secret key `sk = x`
+    /// - `cipher`: specifies the cipher `C ∈ Z_q^{(m+1) x l}`
+    ///
+    /// Returns the decryption of `cipher` as a column vector of length `l` of type [`MatZ`].
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pk_encryption::{PKEncryptionScheme, DualRegev};
+    /// use qfall_math::integer::MatZ;
+    /// use std::str::FromStr;
+    /// let dual_regev = DualRegev::default();
+    /// let (pk, sk) = dual_regev.gen();
+    /// let msg = MatZ::from_str("[[1],[0],[1]]").unwrap();
+    /// let cipher = dual_regev.enc_vec(&pk, &msg);
+    ///
+    /// let m = dual_regev.dec_vec(&sk, &cipher);
+    ///
+    /// assert_eq!(msg, m);
+    /// ```
+    pub fn dec_vec(&self, sk: &MatZ, cipher: &MatZq) -> MatZ {
+        let tmp = (Z::MINUS_ONE * sk)
+            .concat_vertical(&MatZ::identity(1, 1))
+            .unwrap();
+        let result = cipher.transpose() * tmp;
+
+        let q = Z::from(&self.q);
+        let mut out = MatZ::new(result.get_num_rows(), 1);
+        for i in 0..result.get_num_rows() {
+            let entry: Zq = result.get_entry(i, 0).unwrap();
+            let bit = ZqArith::round_to_bit(&entry.get_representative_0_modulus(), &q);
+            out.set_entry(i, 0, bit).unwrap();
+        }
+
+        out
+    }
 }
 
 impl Default for DualRegev {
@@ -404,7 +692,7 @@ impl PKEncryptionScheme for DualRegev {
 
         // hide message in last entry
         // compute msg * ⌊q/2⌋
-        let msg_q_half = message * Z::from(&self.q).div_floor(2);
+        let msg_q_half = ZqArith::scale_by_half_q(&message, &Z::from(&self.q));
         // set last entry of c = last_entry + msg * ⌊q/2⌋
         let last_entry: Zq = c.get_entry(-1, 0).unwrap();
         c.set_entry(-1, 0, last_entry + msg_q_half).unwrap();
@@ -415,6 +703,7 @@ impl PKEncryptionScheme for DualRegev {
     /// Decrypts the provided `cipher` using the secret key `sk` by following these steps:
     /// - x = c^t * [-sk^t | 1]^t
     /// - if x mod q is closer to ⌊q/2⌋ than to 0, output 1. Otherwise, output 0.
+    ///     The rounding is computed by [`ZqArith::round_to_bit`] without branching on x.
     ///
     /// Parameters:
     /// - `sk`: specifies the secret key `sk = x`
@@ -440,13 +729,8 @@ impl PKEncryptionScheme for DualRegev {
             .unwrap();
         let result: Zq = (cipher.transpose() * tmp).get_entry(0, 0).unwrap();
 
-        let q_half = Z::from(&self.q).div_floor(2);
-
-        if result.distance(Z::ZERO) > result.distance(q_half) {
-            Z::ONE
-        } else {
-            Z::ZERO
-        }
+        // round to the nearest multiple of q/2 without branching on the result
+        ZqArith::round_to_bit(&result.get_representative_0_modulus(), &Z::from(&self.q))
     }
 }
 
@@ -535,6 +819,98 @@ mod test_pp_generation {
     }
 }
 
+#[cfg(test)]
+mod test_failure_probability {
+    use super::DualRegev;
+    use crate::utils::{conversion::q_to_f64, math_util::erfc};
+    use qfall_math::{integer::Z, rational::Q};
+
+    /// Ensures that the parameters of `secure128` yield a failure probability
+    /// below 2^{-40} and the [`Default`] parameters one below 2^{-20}.
+    #[test]
+    fn suitable_parameters() {
+        let secure = DualRegev::secure128();
+        let default = DualRegev::default();
+
+        assert!(secure.failure_probability() < Q::from(0.5_f64.powi(40)));
+        assert!(default.failure_probability() < Q::from(0.5_f64.powi(20)));
+        assert!(default.failure_probability() > Q::ZERO);
+    }
+
+    /// Ensures that the failure probability grows with α and that
+    /// `check_correctness` fails once it exceeds 2^{-10}.
+    #[test]
+    fn increasing_alpha() {
+        let default = DualRegev::default();
+        let larger = DualRegev::new(13, 154, 1427, 0.02);
+        let too_large = DualRegev::new(13, 154, 1427, 0.05);
+
+        assert!(default.failure_probability() < larger.failure_probability());
+        assert!(larger.failure_probability() < too_large.failure_probability());
+        assert!(too_large.failure_probability() > Q::from(0.5_f64.powi(10)));
+        assert!(too_large.check_correctness().is_err());
+    }
+
+    /// Ensures that the failure probability never exceeds the plain Gaussian tail
+    /// bound `erfc(q / (4 * α * q * sqrt(m)) / sqrt(2))`.
+    #[test]
+    fn below_gaussian_tail_bound() {
+        for dr in [
+            DualRegev::default(),
+            DualRegev::secure128(),
+            DualRegev::new(13, 154, 1427, 0.02),
+            DualRegev::new(13, 154, 1427, 0.05),
+            DualRegev::new(13, 154, 1427, 10),
+        ] {
+            let q = q_to_f64(&Q::from(Z::from(&dr.q)));
+            let m = q_to_f64(&Q::from(&dr.m));
+            let alpha_q = q_to_f64(&dr.alpha) * q;
+            let bound = erfc(q / (4.0 * alpha_q * m.sqrt()) / std::f64::consts::SQRT_2);
+
+            assert!(dr.decryption_failure_prob() <= bound);
+        }
+    }
+
+    /// Ensures that negligible failure probabilities are returned as zero.
+    #[test]
+    fn negligible() {
+        let dr = DualRegev::new(13, 154, 1427, 0.001);
+
+        assert_eq!(Q::ZERO, dr.failure_probability());
+    }
+
+    /// Ensures that the decryption failure probability is small for suitable
+    /// parameters and matches `failure_probability`.
+    /// The [`Default`] parameters only reach 2^{-20}, as the noise of all-one
+    /// secret keys is considered.
+    #[test]
+    fn decryption_failure_prob_suitable() {
+        let secure = DualRegev::secure128();
+        let default = DualRegev::default();
+
+        assert!(secure.decryption_failure_prob() < 0.5_f64.powi(40));
+        assert!(default.decryption_failure_prob() < 0.5_f64.powi(20));
+        assert!(default.decryption_failure_prob() > 0.0);
+        assert_eq!(
+            Q::from(default.decryption_failure_prob()),
+            default.failure_probability()
+        );
+    }
+
+    /// Ensures that the decryption failure probability grows with α
+    /// and is close to `1/2` for artificially large α.
+    #[test]
+    fn decryption_failure_prob_large_alpha() {
+        let default = DualRegev::default();
+        let larger = DualRegev::new(13, 154, 1427, 0.05);
+        let huge = DualRegev::new(13, 154, 1427, 10);
+
+        assert!(default.decryption_failure_prob() < larger.decryption_failure_prob());
+        assert!(larger.decryption_failure_prob() < huge.decryption_failure_prob());
+        assert!((huge.decryption_failure_prob() - 0.5).abs() < 1e-6);
+    }
+}
+
 #[cfg(test)]
 mod test_dual_regev {
     use super::DualRegev;
@@ -611,6 +987,80 @@ mod test_dual_regev {
     }
 }
 
+#[cfg(test)]
+mod test_str {
+    use super::{DualRegev, PKEncryptionScheme};
+    use crate::error::CryptoError;
+
+    /// Checks whether the string encryption cycle works properly for ASCII strings.
+    #[test]
+    fn ascii() {
+        let scheme = DualRegev::default();
+        let (pk, sk) = scheme.gen();
+
+        let ciphers = scheme.enc_str(&pk, "Hello World!");
+        let message = scheme.dec_str(&sk, &ciphers).unwrap();
+
+        assert_eq!(8 * 12, ciphers.len());
+        assert_eq!("Hello World!", message);
+    }
+
+    /// Checks whether the string encryption cycle works properly
+    /// for strings containing multi-byte UTF-8 characters.
+    #[test]
+    fn emoji() {
+        let msg = "Gitter 🔒 über ℤ_q 🦀";
+        let scheme = DualRegev::default();
+        let (pk, sk) = scheme.gen();
+
+        let ciphers = scheme.enc_str(&pk, msg);
+        let message = scheme.dec_str(&sk, &ciphers).unwrap();
+
+        assert_eq!(8 * msg.len(), ciphers.len());
+        assert_eq!(msg, message);
+    }
+
+    /// Checks whether the string encryption cycle works properly for the empty string.
+    #[test]
+    fn empty() {
+        let scheme = DualRegev::default();
+        let (pk, sk) = scheme.gen();
+
+        let ciphers = scheme.enc_str(&pk, "");
+        let message = scheme.dec_str(&sk, &ciphers).unwrap();
+
+        assert!(ciphers.is_empty());
+        assert_eq!("", message);
+    }
+
+    /// Ensures that invalid UTF-8 bytes result in a [`CryptoError`].
+    #[test]
+    fn invalid_utf8() {
+        let scheme = DualRegev::default();
+        let (pk, sk) = scheme.gen();
+
+        // 0xff is never a valid byte in UTF-8
+        let ciphers: Vec<_> = (0..8).map(|_| scheme.enc(&pk, 1)).collect();
+        let res = scheme.dec_str(&sk, &ciphers);
+
+        assert!(matches!(res, Err(CryptoError::DecryptionFailed)));
+    }
+
+    /// Ensures that a number of ciphers, which is not a multiple of 8,
+    /// results in a [`CryptoError`].
+    #[test]
+    fn incomplete_byte() {
+        let scheme = DualRegev::default();
+        let (pk, sk) = scheme.gen();
+
+        let mut ciphers = scheme.enc_str(&pk, "a");
+        ciphers.pop();
+        let res = scheme.dec_str(&sk, &ciphers);
+
+        assert!(matches!(res, Err(CryptoError::DecryptionFailed)));
+    }
+}
+
 #[cfg(test)]
 mod test_multi_bits {
     use super::{DualRegev, GenericMultiBitEncryption, PKEncryptionScheme};
@@ -667,3 +1117,57 @@ mod test_multi_bits {
         }
     }
 }
+
+#[cfg(test)]
+mod test_enc_vec {
+    use super::DualRegev;
+    use crate::construction::pk_encryption::PKEncryptionScheme;
+    use qfall_math::{
+        integer::{MatZ, Z},
+        traits::{GetEntry, GetNumColumns, GetNumRows},
+    };
+
+    /// Ensures that messages of every length from `1` to `n` are decrypted correctly.
+    #[test]
+    fn round_trip() {
+        let dr = DualRegev::default();
+        let (pk, sk) = dr.gen();
+
+        for length in 1..=13 {
+            let msg = MatZ::sample_uniform(length, 1, 0, 2).unwrap();
+
+            let cipher = dr.enc_vec(&pk, &msg);
+
+            assert_eq!(&dr.m + 1, Z::from(cipher.get_num_rows()));
+            assert_eq!(length, cipher.get_num_columns());
+            assert_eq!(msg, dr.dec_vec(&sk, &cipher));
+        }
+    }
+
+    /// Ensures that every coordinate is encrypted `mod 2`.
+    #[test]
+    fn reduced_mod_2() {
+        let dr = DualRegev::new_from_n(20);
+        let (pk, sk) = dr.gen();
+        let msg = MatZ::sample_uniform(20, 1, -5, 5).unwrap();
+
+        let cipher = dr.enc_vec(&pk, &msg);
+        let m = dr.dec_vec(&sk, &cipher);
+
+        for i in 0..20 {
+            let entry: Z = msg.get_entry(i, 0).unwrap();
+            let bit: Z = m.get_entry(i, 0).unwrap();
+            assert_eq!(entry.modulo(2), bit);
+        }
+    }
+
+    /// Ensures that `enc_vec` panics if the message is not a column vector.
+    #[test]
+    #[should_panic]
+    fn not_column_vector() {
+        let dr = DualRegev::default();
+        let (pk, _) = dr.gen();
+
+        let _ = dr.enc_vec(&pk, &MatZ::new(1, 2));
+    }
+}
@@ -0,0 +1,341 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains an LWE-based stream cipher, which encrypts
+//! messages of arbitrary length by XORing them with LWE-based keystream blocks.
+
+use crate::{
+    construction::{
+        hash::sha256::hash_to_mat_zq_sha256,
+        kdf::{hkdf_expand, hkdf_extract},
+    },
+    error::CryptoError,
+};
+use qfall_math::{
+    integer::{MatZ, Z},
+    integer_mod_q::{MatZq, Modulus},
+    traits::{GetEntry, Pow, SetEntry},
+};
+use serde::{Deserialize, Serialize};
+
+/// The domain separator used to hash nonces into `Z_q^n`.
+const NONCE_DOMAIN: &str = "qFALL-crypto LWE stream cipher nonce";
+
+/// The `info` string used to derive the error of a keystream block.
+const ERROR_INFO: &str = "qFALL-crypto LWE stream cipher error";
+
+/// The number of bytes of the nonce prepended to every ciphertext.
+const NONCE_LEN: usize = 8;
+
+/// The bound `b` such that every entry of the error lies in `[-b, b]`.
+const ERROR_BOUND: i64 = 4;
+
+/// This struct manages and stores the public parameters of an LWE-based
+/// stream cipher. For a secret key `s ∈ Z_q^n`, the keystream block for
+/// nonce `nonce` is `A^t * (s + H(nonce)) + e ∈ Z_q^m`, where `e` is a short
+/// error vector deterministically derived from `s` and `nonce`.
+/// Every entry `v` of a keystream block yields the keystream byte `⌊256 * v / q⌋`.
+///
+/// **WARNING:** The error is derived deterministically from the secret key via HKDF,
+/// which is required to recompute the keystream during decryption.
+/// This scheme has no proof of security and is meant for educational purposes.
+///
+/// Attributes:
+/// - `n`: specifies the security parameter, which is not equal to the bit-security level
+/// - `m`: specifies the number of keystream bytes per block
+/// - `q`: specifies the modulus
+/// - `a`: the public uniform matrix `A ∈ Z_q^{n x m}`
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::construction::pk_encryption::LWEStreamCipher;
+///
+/// let cipher = LWEStreamCipher::new_from_n(16);
+/// let s = cipher.setup();
+///
+/// let ciphertext = cipher.encrypt_stream(&s, b"Hello World!");
+/// let plaintext = cipher.decrypt_stream(&s, &ciphertext).unwrap();
+///
+/// assert_eq!(b"Hello World!".to_vec(), plaintext);
+/// ```
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LWEStreamCipher {
+    n: Z,
+    m: Z,
+    q: Modulus,
+    pub a: MatZq,
+}
+
+impl LWEStreamCipher {
+    /// Initializes an [`LWEStreamCipher`] instance for security parameter `n`
+    /// by choosing a prime `q ∈ [n^5 / 2, n^5]`, `m = n * ⌈log_2(q)⌉`,
+    /// and a uniform matrix `A ∈ Z_q^{n x m}`.
+    ///
+    /// Parameters:
+    /// - `n`: the security parameter
+    ///
+    /// Returns an [`LWEStreamCipher`] instance.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pk_encryption::LWEStreamCipher;
+    ///
+    /// let cipher = LWEStreamCipher::new_from_n(16);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `n < 2`.
+    pub fn new_from_n(n: impl Into<Z>) -> Self {
+        let n: Z = n.into();
+        if n < Z::from(2) {
+            panic!("Security parameter n has to be larger than 1");
+        }
+
+        let upper_bound: Z = n.pow(5).unwrap();
+        let lower_bound = upper_bound.div_ceil(2);
+        let q = Modulus::from(Z::sample_prime_uniform(&lower_bound, &upper_bound).unwrap());
+
+        let m = &n * Z::from(&q).log_ceil(2).unwrap();
+        let a = MatZq::sample_uniform(&n, &m, &q);
+
+        Self { n, m, q, a }
+    }
+
+    /// Samples a uniform secret key `s ∈ Z_q^n`.
+    ///
+    /// Returns the secret key `s`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pk_encryption::LWEStreamCipher;
+    /// let cipher = LWEStreamCipher::new_from_n(16);
+    ///
+    /// let s = cipher.setup();
+    /// ```
+    pub fn setup(&self) -> MatZq {
+        MatZq::sample_uniform(&self.n, 1, &self.q)
+    }
+
+    /// Computes the keystream block `A^t * (s + H(nonce)) + e ∈ Z_q^m` for `nonce`,
+    /// where `e ∈ [-4, 4]^m` is derived from `s` and `nonce` via HKDF.
+    ///
+    /// Parameters:
+    /// - `s`: the secret key
+    /// - `nonce`: the nonce identifying the block
+    ///
+    /// Returns the keystream block as a [`MatZq`] column vector.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pk_encryption::LWEStreamCipher;
+    /// let cipher = LWEStreamCipher::new_from_n(16);
+    /// let s = cipher.setup();
+    ///
+    /// let block = cipher.keystream_block(&s, 0);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `s` is not a column vector of length `n` with modulus `q`.
+    pub fn keystream_block(&self, s: &MatZq, nonce: u64) -> MatZq {
+        let hashed_nonce =
+            hash_to_mat_zq_sha256(&format!("{NONCE_DOMAIN} {nonce}"), &self.n, 1, &self.q);
+        let shifted_secret = s + hashed_nonce;
+
+        let m = i64::try_from(&self.m).unwrap();
+        let prk = hkdf_extract(&nonce.to_be_bytes(), s.to_string().as_bytes());
+        let error_bytes = hkdf_expand(&prk, ERROR_INFO, m as usize);
+        let mut error = MatZ::new(m, 1);
+        for (i, byte) in error_bytes.iter().enumerate() {
+            let value = i64::from(*byte) % (2 * ERROR_BOUND + 1) - ERROR_BOUND;
+            error.set_entry(i as i64, 0, value).unwrap();
+        }
+
+        self.a.transpose() * shifted_secret + MatZq::from((&error, &self.q))
+    }
+
+    /// Computes `len` keystream bytes starting with the block for `nonce`
+    /// and continuing with the blocks for the following nonces.
+    fn keystream(&self, s: &MatZq, nonce: u64, len: usize) -> Vec<u8> {
+        let q = Z::from(&self.q);
+        let mut keystream = Vec::with_capacity(len);
+        let mut block_nonce = nonce;
+        while keystream.len() < len {
+            let block = self
+                .keystream_block(s, block_nonce)
+                .get_representative_0_modulus();
+            for i in 0..block.get_num_rows() {
+                if keystream.len() == len {
+                    break;
+                }
+                let entry: Z = block.get_entry(i, 0).unwrap();
+                let byte = (entry * Z::from(256)).div_floor(&q);
+                keystream.push(i64::try_from(&byte).unwrap() as u8);
+            }
+            block_nonce = block_nonce.wrapping_add(1);
+        }
+        keystream
+    }
+
+    /// Encrypts `plaintext` under the secret key `s` by choosing a uniform
+    /// nonce and XORing `plaintext` with the keystream starting at this nonce.
+    ///
+    /// Parameters:
+    /// - `s`: the secret key
+    /// - `plaintext`: the bytes that should be encrypted
+    ///
+    /// Returns the ciphertext, i.e. the `8` bytes of the nonce followed
+    /// by the encrypted bytes of `plaintext`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pk_encryption::LWEStreamCipher;
+    /// let cipher = LWEStreamCipher::new_from_n(16);
+    /// let s = cipher.setup();
+    ///
+    /// let ciphertext = cipher.encrypt_stream(&s, b"Hello World!");
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `s` is not a column vector of length `n` with modulus `q`.
+    pub fn encrypt_stream(&self, s: &MatZq, plaintext: &[u8]) -> Vec<u8> {
+        let nonce: u64 = rand::random();
+        let keystream = self.keystream(s, nonce, plaintext.len());
+
+        let mut ciphertext = nonce.to_be_bytes().to_vec();
+        ciphertext.extend(plaintext.iter().zip(keystream).map(|(p, k)| p ^ k));
+        ciphertext
+    }
+
+    /// Decrypts `ciphertext` under the secret key `s` by recomputing the keystream
+    /// starting at the prepended nonce and XORing it with the encrypted bytes.
+    ///
+    /// Parameters:
+    /// - `s`: the secret key
+    /// - `ciphertext`: the output of [`LWEStreamCipher::encrypt_stream`]
+    ///
+    /// Returns the decrypted bytes or a [`CryptoError`] if the ciphertext is too short.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pk_encryption::LWEStreamCipher;
+    /// let cipher = LWEStreamCipher::new_from_n(16);
+    /// let s = cipher.setup();
+    /// let ciphertext = cipher.encrypt_stream(&s, b"Hello World!");
+    ///
+    /// let plaintext = cipher.decrypt_stream(&s, &ciphertext).unwrap();
+    ///
+    /// assert_eq!(b"Hello World!".to_vec(), plaintext);
+    /// ```
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] of type [`DecryptionFailed`](CryptoError::DecryptionFailed)
+    ///     if `ciphertext` is shorter than the nonce.
+    ///
+    /// # Panics ...
+    /// - if `s` is not a column vector of length `n` with modulus `q`.
+    pub fn decrypt_stream(&self, s: &MatZq, ciphertext: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        if ciphertext.len() < NONCE_LEN {
+            return Err(CryptoError::DecryptionFailed);
+        }
+
+        let (nonce_bytes, encrypted) = ciphertext.split_at(NONCE_LEN);
+        let nonce = u64::from_be_bytes(nonce_bytes.try_into().unwrap());
+        let keystream = self.keystream(s, nonce, encrypted.len());
+
+        Ok(encrypted
+            .iter()
+            .zip(keystream)
+            .map(|(c, k)| c ^ k)
+            .collect())
+    }
+}
+
+impl Default for LWEStreamCipher {
+    /// Initializes an [`LWEStreamCipher`] instance with `n = 16`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pk_encryption::LWEStreamCipher;
+    ///
+    /// let cipher = LWEStreamCipher::default();
+    /// ```
+    fn default() -> Self {
+        Self::new_from_n(16)
+    }
+}
+
+#[cfg(test)]
+mod test_lwe_stream_cipher {
+    use super::LWEStreamCipher;
+
+    /// Encrypts and decrypts `len` uniform bytes and checks correctness.
+    fn cycle(len: usize) {
+        let cipher = LWEStreamCipher::default();
+        let s = cipher.setup();
+        let plaintext: Vec<u8> = (0..len).map(|_| rand::random()).collect();
+
+        let ciphertext = cipher.encrypt_stream(&s, &plaintext);
+
+        assert_eq!(len + 8, ciphertext.len());
+        assert_eq!(plaintext, cipher.decrypt_stream(&s, &ciphertext).unwrap());
+    }
+
+    /// Ensures that a single byte is encrypted and decrypted correctly.
+    #[test]
+    fn cycle_one_byte() {
+        cycle(1);
+    }
+
+    /// Ensures that `1 KB` are encrypted and decrypted correctly.
+    #[test]
+    fn cycle_one_kilobyte() {
+        cycle(1024);
+    }
+
+    /// Ensures that `10 KB` are encrypted and decrypted correctly.
+    #[test]
+    fn cycle_ten_kilobytes() {
+        cycle(10 * 1024);
+    }
+
+    /// Ensures that the keystream is deterministic and depends on the nonce and key.
+    #[test]
+    fn keystream_block() {
+        let cipher = LWEStreamCipher::default();
+        let s_0 = cipher.setup();
+        let s_1 = cipher.setup();
+
+        assert_eq!(
+            cipher.keystream_block(&s_0, 0),
+            cipher.keystream_block(&s_0, 0)
+        );
+        assert_ne!(
+            cipher.keystream_block(&s_0, 0),
+            cipher.keystream_block(&s_0, 1)
+        );
+        assert_ne!(
+            cipher.keystream_block(&s_0, 0),
+            cipher.keystream_block(&s_1, 0)
+        );
+    }
+
+    /// Ensures that decryption under a different key does not reveal the plaintext
+    /// and that too short ciphertexts are rejected.
+    #[test]
+    fn wrong_key_and_short_ciphertext() {
+        let cipher = LWEStreamCipher::default();
+        let s_0 = cipher.setup();
+        let s_1 = cipher.setup();
+        let plaintext = vec![0u8; 64];
+
+        let ciphertext = cipher.encrypt_stream(&s_0, &plaintext);
+
+        assert_ne!(plaintext, cipher.decrypt_stream(&s_1, &ciphertext).unwrap());
+        assert!(cipher.decrypt_stream(&s_0, &ciphertext[..7]).is_err());
+    }
+}
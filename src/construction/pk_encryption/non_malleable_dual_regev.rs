@@ -0,0 +1,255 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains a wrapper of [`DualRegev`], which appends an
+//! [`SISHash`] tag to every ciphertext and rejects modified ciphertexts.
+
+use super::{DualRegev, PKEncryptionScheme};
+use crate::{construction::hash::SISHash, error::CryptoError};
+use qfall_math::{
+    integer::{MatZ, Z},
+    integer_mod_q::MatZq,
+};
+use serde::{Deserialize, Serialize};
+
+/// A ciphertext of [`NonMalleableDualRegev`].
+///
+/// Attributes:
+/// - `cipher`: the [`DualRegev`] ciphertext
+/// - `tag`: the [`SISHash`] of `cipher`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NonMalleableCipher {
+    pub cipher: MatZq,
+    pub tag: MatZq,
+}
+
+/// This struct manages and stores the public parameters of a [`DualRegev`]
+/// instance, whose ciphertexts are tagged with the [`SISHash`] of the ciphertext.
+/// Decryption fails for every ciphertext whose tag does not match, e.g. if
+/// a small vector was added to the ciphertext.
+///
+/// **WARNING:** The hash key is public, hence an adversary can recompute the tag
+/// of a modified ciphertext. The tag only detects modifications by parties,
+/// which do not recompute it, and does not provide CCA security.
+///
+/// Attributes:
+/// - `dual_regev`: the [`DualRegev`] instance used for encryption
+/// - `hash`: the [`SISHash`] instance computing the tags
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::construction::pk_encryption::NonMalleableDualRegev;
+/// use qfall_math::integer::Z;
+///
+/// let scheme = NonMalleableDualRegev::default();
+/// let (pk, sk) = scheme.gen();
+///
+/// let cipher = scheme.enc(&pk, 1);
+///
+/// assert_eq!(Z::ONE, scheme.dec(&sk, &cipher).unwrap());
+/// ```
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NonMalleableDualRegev {
+    pub dual_regev: DualRegev,
+    pub hash: SISHash,
+}
+
+impl NonMalleableDualRegev {
+    /// Initializes a [`NonMalleableDualRegev`] instance wrapping `dual_regev`
+    /// with a freshly generated [`SISHash`] mapping `Z_q^{m + 1}` to `Z_q^n`.
+    ///
+    /// Parameters:
+    /// - `dual_regev`: the [`DualRegev`] instance used for encryption
+    ///
+    /// Returns a [`NonMalleableDualRegev`] instance.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pk_encryption::{DualRegev, NonMalleableDualRegev};
+    ///
+    /// let scheme = NonMalleableDualRegev::new(DualRegev::default());
+    /// ```
+    pub fn new(dual_regev: DualRegev) -> Self {
+        let hash = SISHash::gen(
+            &dual_regev.n,
+            &dual_regev.m + Z::ONE,
+            Z::from(&dual_regev.q),
+        )
+        .unwrap();
+
+        Self { dual_regev, hash }
+    }
+
+    /// Initializes a [`NonMalleableDualRegev`] instance wrapping `DualRegev::new_from_n(n)`.
+    ///
+    /// Parameters:
+    /// - `n`: the security parameter
+    ///
+    /// Returns a [`NonMalleableDualRegev`] instance.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pk_encryption::NonMalleableDualRegev;
+    ///
+    /// let scheme = NonMalleableDualRegev::new_from_n(15);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `n < 10`.
+    pub fn new_from_n(n: impl Into<Z>) -> Self {
+        Self::new(DualRegev::new_from_n(n))
+    }
+
+    /// Generates a (pk, sk) pair using [`DualRegev`].
+    ///
+    /// Returns the public key and the secret key.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pk_encryption::NonMalleableDualRegev;
+    /// let scheme = NonMalleableDualRegev::default();
+    ///
+    /// let (pk, sk) = scheme.gen();
+    /// ```
+    pub fn gen(&self) -> (MatZq, MatZ) {
+        self.dual_regev.gen()
+    }
+
+    /// Encrypts `message mod 2` under `pk` using [`DualRegev`] and
+    /// tags the ciphertext with its [`SISHash`].
+    ///
+    /// Parameters:
+    /// - `pk`: specifies the public key
+    /// - `message`: specifies the message that should be encrypted
+    ///
+    /// Returns a [`NonMalleableCipher`] containing the ciphertext and its tag.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pk_encryption::NonMalleableDualRegev;
+    /// let scheme = NonMalleableDualRegev::default();
+    /// let (pk, sk) = scheme.gen();
+    ///
+    /// let cipher = scheme.enc(&pk, 1);
+    /// ```
+    pub fn enc(&self, pk: &MatZq, message: impl Into<Z>) -> NonMalleableCipher {
+        let cipher = self.dual_regev.enc(pk, message);
+        let tag = self.hash.hash(&cipher);
+
+        NonMalleableCipher { cipher, tag }
+    }
+
+    /// Verifies the tag of `cipher` and decrypts it using [`DualRegev`].
+    ///
+    /// Parameters:
+    /// - `sk`: specifies the secret key
+    /// - `cipher`: specifies the tagged ciphertext
+    ///
+    /// Returns the decryption of `cipher` or a [`CryptoError`] if the tag is invalid.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pk_encryption::NonMalleableDualRegev;
+    /// use qfall_math::integer::Z;
+    /// let scheme = NonMalleableDualRegev::default();
+    /// let (pk, sk) = scheme.gen();
+    /// let cipher = scheme.enc(&pk, 1);
+    ///
+    /// assert_eq!(Z::ONE, scheme.dec(&sk, &cipher).unwrap());
+    /// ```
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] of type [`DecryptionFailed`](CryptoError::DecryptionFailed)
+    ///     if the ciphertext has an invalid shape or its tag does not match.
+    pub fn dec(&self, sk: &MatZ, cipher: &NonMalleableCipher) -> Result<Z, CryptoError> {
+        if !cipher.cipher.is_column_vector()
+            || cipher.cipher.get_mod() != self.dual_regev.q
+            || Z::from(cipher.cipher.get_num_rows()) != &self.dual_regev.m + Z::ONE
+        {
+            return Err(CryptoError::DecryptionFailed);
+        }
+        if self.hash.hash(&cipher.cipher) != cipher.tag {
+            return Err(CryptoError::DecryptionFailed);
+        }
+
+        Ok(self.dual_regev.dec(sk, &cipher.cipher))
+    }
+}
+
+impl Default for NonMalleableDualRegev {
+    /// Initializes a [`NonMalleableDualRegev`] instance wrapping [`DualRegev::default`].
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pk_encryption::NonMalleableDualRegev;
+    ///
+    /// let scheme = NonMalleableDualRegev::default();
+    /// ```
+    fn default() -> Self {
+        Self::new(DualRegev::default())
+    }
+}
+
+#[cfg(test)]
+mod test_non_malleable_dual_regev {
+    use super::NonMalleableDualRegev;
+    use crate::error::CryptoError;
+    use qfall_math::{
+        integer::Z,
+        integer_mod_q::{MatZq, Zq},
+        traits::{GetEntry, SetEntry},
+    };
+
+    /// Ensures that honestly generated ciphertexts always decrypt correctly.
+    #[test]
+    fn cycle() {
+        let scheme = NonMalleableDualRegev::default();
+        let (pk, sk) = scheme.gen();
+
+        for _ in 0..10 {
+            for msg in [Z::ZERO, Z::ONE] {
+                let cipher = scheme.enc(&pk, &msg);
+                assert_eq!(msg, scheme.dec(&sk, &cipher).unwrap());
+            }
+        }
+    }
+
+    /// Ensures that changing one entry of the ciphertext by one results in an error.
+    #[test]
+    fn modified_cipher() {
+        let scheme = NonMalleableDualRegev::default();
+        let (pk, sk) = scheme.gen();
+        let mut cipher = scheme.enc(&pk, 1);
+
+        let entry: Zq = cipher.cipher.get_entry(-1, 0).unwrap();
+        cipher.cipher.set_entry(-1, 0, entry + 1).unwrap();
+
+        assert!(matches!(
+            scheme.dec(&sk, &cipher),
+            Err(CryptoError::DecryptionFailed)
+        ));
+    }
+
+    /// Ensures that modified tags and ciphertexts of the wrong shape are rejected.
+    #[test]
+    fn invalid_tag_and_shape() {
+        let scheme = NonMalleableDualRegev::default();
+        let (pk, sk) = scheme.gen();
+        let cipher = scheme.enc(&pk, 0);
+
+        let mut modified_tag = cipher.clone();
+        let entry: Zq = modified_tag.tag.get_entry(0, 0).unwrap();
+        modified_tag.tag.set_entry(0, 0, entry + 1).unwrap();
+
+        let mut wrong_shape = cipher.clone();
+        wrong_shape.cipher = MatZq::new(2, 1, cipher.cipher.get_mod());
+
+        assert!(scheme.dec(&sk, &modified_tag).is_err());
+        assert!(scheme.dec(&sk, &wrong_shape).is_err());
+    }
+}
@@ -0,0 +1,358 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains an implementation of an NTRU-based
+//! public key encryption scheme over `Z_q[X]/(X^n + 1)`.
+
+use super::PKEncryptionScheme;
+use crate::utils::{
+    common_moduli::new_anticyclic,
+    math_util::{mat_inverse_mod, ZqArith},
+    rotation_matrix::rot_minus,
+};
+use qfall_math::{
+    integer::{PolyOverZ, Z},
+    integer_mod_q::{MatZq, Modulus, ModulusPolynomialRingZq, PolynomialRingZq},
+    rational::Q,
+    traits::{FromCoefficientEmbedding, GetCoefficient, IntoCoefficientEmbedding},
+};
+use serde::{Deserialize, Serialize};
+
+/// The tail cut `τ` used by [`NtruEncrypt::validate_keypair`], i.e. the coefficients
+/// of `g` are expected to be at most `τ * σ` in absolute value.
+const TAIL_CUT: i64 = 12;
+
+/// This struct manages and stores the public parameters of a [`NtruEncrypt`]
+/// public key encryption instance based on [\[6\]](<index.html#:~:text=[6]>),
+/// where bits are encoded by `⌊q/2⌋` as in the LWE-based schemes.
+///
+/// The secret key is chosen as `f = 1 + 2f'` for a short `f'`, s.t.
+/// `f * ⌊q/2⌋ = ⌊q/2⌋ - (q mod 2) * f' mod q` only adds a small error
+/// and the message can be recovered from `f * c`.
+///
+/// **WARNING:** The given parameters are neither checked for security nor correctness.
+/// The default parameters are not secure, but provide an efficient working example.
+///
+/// Attributes:
+/// - `n`: specifies the degree of `X^n + 1`
+/// - `q`: specifies the modulus `X^n + 1 mod q` over which the encryption is computed
+/// - `sigma`: specifies the Gaussian parameter of the short polynomials `f'`, `g`, `r` and `e`
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::construction::pk_encryption::{NtruEncrypt, PKEncryptionScheme};
+/// use qfall_math::integer::Z;
+/// // setup public parameters and key pair
+/// let ntru = NtruEncrypt::default();
+/// let (pk, sk) = ntru.gen();
+///
+/// // encrypt a bit
+/// let msg = Z::ONE; // must be a bit, i.e. msg = 0 or 1
+/// let cipher = ntru.enc(&pk, &msg);
+///
+/// // decrypt
+/// let m = ntru.dec(&sk, &cipher);
+///
+/// assert_eq!(msg, m);
+/// ```
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NtruEncrypt {
+    pub(crate) n: Z,                       // degree of X^n + 1
+    pub(crate) q: ModulusPolynomialRingZq, // modulus
+    pub(crate) sigma: Q,                   // Gaussian parameter of short polynomials
+}
+
+impl NtruEncrypt {
+    /// Instantiates a [`NtruEncrypt`] PK encryption instance with the
+    /// specified parameters.
+    ///
+    /// **WARNING:** The given parameters are not checked for security nor
+    /// correctness of the scheme.
+    ///
+    /// Parameters:
+    /// - `n`: specifies the degree of `X^n + 1`
+    /// - `q`: specifies the modulus, which should be prime s.t. most `f` are invertible
+    /// - `sigma`: specifies the Gaussian parameter of the short polynomials
+    ///
+    /// Returns a [`NtruEncrypt`] PK encryption instance.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pk_encryption::NtruEncrypt;
+    ///
+    /// let ntru = NtruEncrypt::new(32, 12289, 2);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if the given modulus `q <= 1`.
+    /// - if `n < 1`.
+    pub fn new(n: impl Into<Z>, q: impl Into<Modulus>, sigma: impl Into<Q>) -> Self {
+        let n: Z = n.into();
+        assert!(n >= Z::ONE, "The degree n has to be positive.");
+
+        // mod = (X^n + 1) mod q
+        let q = new_anticyclic(&n, q).unwrap();
+
+        Self {
+            n,
+            q,
+            sigma: sigma.into(),
+        }
+    }
+
+    /// Checks whether `(pk, sk) = (h, f)` is a valid key pair, i.e.
+    /// - `f = 1 + 2f'` has an odd constant coefficient and even other coefficients,
+    /// - `f` has coefficients of absolute value at most `2 * τ * σ + 1`,
+    /// - `g = f * h` has coefficients of absolute value at most `τ * σ`,
+    ///
+    /// where the tail cut is `τ = 12`.
+    ///
+    /// Parameters:
+    /// - `pk`: specifies the public key `pk = h`
+    /// - `sk`: specifies the secret key `sk = f`
+    ///
+    /// Returns `true` if the key pair is valid and `false` otherwise.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pk_encryption::{NtruEncrypt, PKEncryptionScheme};
+    /// let ntru = NtruEncrypt::default();
+    /// let (pk, sk) = ntru.gen();
+    ///
+    /// assert!(ntru.validate_keypair(&pk, &sk));
+    /// ```
+    pub fn validate_keypair(&self, pk: &PolynomialRingZq, sk: &PolynomialRingZq) -> bool {
+        let q = self.q.get_q();
+        let bound_g = Q::from(TAIL_CUT) * &self.sigma;
+        let bound_f = Q::from(2 * TAIL_CUT) * &self.sigma + Q::ONE;
+        let g = sk * pk;
+
+        let mut valid = true;
+        for i in 0..self.q.get_degree() {
+            let f_i: Z = sk.get_coeff(i).unwrap();
+            let g_i: Z = g.get_coeff(i).unwrap();
+            let f_i = ZqArith::reduce_centered(&f_i, &q);
+            let g_i = ZqArith::reduce_centered(&g_i, &q);
+
+            let parity = if i == 0 { Z::ONE } else { Z::ZERO };
+            valid &= f_i.modulo(2) == parity;
+            valid &= Q::from(f_i.abs()) <= bound_f;
+            valid &= Q::from(g_i.abs()) <= bound_g;
+        }
+
+        valid
+    }
+
+    /// Samples a polynomial of degree at most `n - 1` from the discrete Gaussian
+    /// distribution with Gaussian parameter `sigma`.
+    fn sample_short(&self) -> PolyOverZ {
+        PolyOverZ::sample_discrete_gauss(self.q.get_degree() - 1, &self.n, 0, &self.sigma).unwrap()
+    }
+}
+
+impl Default for NtruEncrypt {
+    /// Initializes a [`NtruEncrypt`] struct with parameters `n = 16`, `q = 12289`
+    /// and `sigma = 2`.
+    /// This parameter choice is not secure as the dimension of the lattice is too small,
+    /// but it provides an efficient working example.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pk_encryption::NtruEncrypt;
+    ///
+    /// let ntru = NtruEncrypt::default();
+    /// ```
+    fn default() -> Self {
+        Self::new(16, 12289, 2)
+    }
+}
+
+impl PKEncryptionScheme for NtruEncrypt {
+    type Cipher = PolynomialRingZq;
+    type PublicKey = PolynomialRingZq;
+    type SecretKey = PolynomialRingZq;
+
+    /// Generates a (pk, sk) pair for the NTRU public key encryption scheme
+    /// by following these steps:
+    /// - f' <- χ, g <- χ
+    /// - f = 1 + 2f'
+    /// - restart if f is not invertible in R_q
+    /// - h = g * f^{-1}
+    ///     where χ is discrete Gaussian distributed with center 0 and Gaussian parameter σ.
+    ///
+    /// The inverse of `f` is computed by inverting the negacyclic rotation matrix of `f`.
+    ///
+    /// Then, `pk = h` and `sk = f` are returned.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pk_encryption::{NtruEncrypt, PKEncryptionScheme};
+    /// let ntru = NtruEncrypt::default();
+    ///
+    /// let (pk, sk) = ntru.gen();
+    /// ```
+    fn gen(&self) -> (Self::PublicKey, Self::SecretKey) {
+        let degree = self.q.get_degree();
+        let q = self.q.get_q();
+
+        loop {
+            // f = 1 + 2f'
+            let f_prime = self.sample_short();
+            let f = &f_prime + &f_prime + PolyOverZ::from(1);
+            let g = self.sample_short();
+
+            // the columns of rot^-(f) are the coefficients of f * X^j
+            let rot_f = MatZq::from((&rot_minus(&f.into_coefficient_embedding(degree)), &q));
+            if let Some(rot_f_inv) = mat_inverse_mod(&rot_f) {
+                // h = f^{-1} * g
+                let g_embedded = MatZq::from((&g.into_coefficient_embedding(degree), &q));
+                let h = PolyOverZ::from_coefficient_embedding(
+                    &(rot_f_inv * g_embedded).get_representative_0_modulus(),
+                );
+
+                return (
+                    PolynomialRingZq::from((&h, &self.q)),
+                    PolynomialRingZq::from((&f, &self.q)),
+                );
+            }
+        }
+    }
+
+    /// Generates an encryption of `message mod 2` for the provided public key by following these steps:
+    /// - r <- χ, e <- χ
+    /// - c = r * h + e + msg * ⌊q/2⌋
+    ///     where χ is discrete Gaussian distributed with center 0 and Gaussian parameter σ
+    ///     and `msg * ⌊q/2⌋` is added to the constant coefficient.
+    ///
+    /// Then, cipher `c` as a polynomial of type [`PolynomialRingZq`] is returned.
+    ///
+    /// Parameters:
+    /// - `pk`: specifies the public key `pk = h`
+    /// - `message`: specifies the message that should be encrypted
+    ///
+    /// Returns a cipher `c` of type [`PolynomialRingZq`].
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pk_encryption::{NtruEncrypt, PKEncryptionScheme};
+    /// let ntru = NtruEncrypt::default();
+    /// let (pk, sk) = ntru.gen();
+    ///
+    /// let cipher = ntru.enc(&pk, 1);
+    /// ```
+    fn enc(&self, pk: &Self::PublicKey, message: impl Into<Z>) -> Self::Cipher {
+        let msg_q_half = ZqArith::scale_by_half_q(&message.into(), &self.q.get_q());
+        let msg_q_half = PolynomialRingZq::from((&PolyOverZ::from(msg_q_half), &self.q));
+
+        // r <- χ, e <- χ
+        let r = PolynomialRingZq::from((&self.sample_short(), &self.q));
+        let e = PolynomialRingZq::from((&self.sample_short(), &self.q));
+
+        // c = r * h + e + msg * ⌊q/2⌋
+        r * pk + e + msg_q_half
+    }
+
+    /// Decrypts the provided `cipher` using the secret key `sk` by following these steps:
+    /// - t = f * c = r * g + f * e + f * msg * ⌊q/2⌋
+    /// - if the constant coefficient of t mod q is closer to ⌊q/2⌋ than to 0,
+    ///     output 1. Otherwise, output 0.
+    ///
+    /// Parameters:
+    /// - `sk`: specifies the secret key `sk = f`
+    /// - `cipher`: specifies the cipher containing `cipher = c`
+    ///
+    /// Returns the decryption of `cipher` as a [`Z`] instance.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pk_encryption::{NtruEncrypt, PKEncryptionScheme};
+    /// use qfall_math::integer::Z;
+    /// let ntru = NtruEncrypt::default();
+    /// let (pk, sk) = ntru.gen();
+    /// let cipher = ntru.enc(&pk, 1);
+    ///
+    /// let m = ntru.dec(&sk, &cipher);
+    ///
+    /// assert_eq!(Z::ONE, m);
+    /// ```
+    fn dec(&self, sk: &Self::SecretKey, cipher: &Self::Cipher) -> Z {
+        let t = sk * cipher;
+        let constant: Z = t.get_coeff(0).unwrap();
+
+        ZqArith::round_to_bit(&constant, &self.q.get_q())
+    }
+}
+
+#[cfg(test)]
+mod test_ntru_encrypt {
+    use super::NtruEncrypt;
+    use crate::construction::pk_encryption::PKEncryptionScheme;
+    use qfall_math::{integer::Z, integer_mod_q::PolynomialRingZq};
+
+    /// Checks whether `new` is available for types implementing [`Into<Z>`].
+    #[test]
+    fn new_availability() {
+        let _ = NtruEncrypt::new(8u8, 97u32, 2u64);
+        let _ = NtruEncrypt::new(8i64, Z::from(97), 1.5);
+    }
+
+    /// Checks whether the full-cycle of gen, enc, dec works properly
+    /// for messages 0 and 1 and the default.
+    #[test]
+    fn cycle_default() {
+        let ntru = NtruEncrypt::default();
+        let (pk, sk) = ntru.gen();
+
+        for _ in 0..10 {
+            for msg in [Z::ZERO, Z::ONE] {
+                let cipher = ntru.enc(&pk, &msg);
+
+                assert_eq!(msg, ntru.dec(&sk, &cipher));
+            }
+        }
+    }
+
+    /// Checks whether the full-cycle of gen, enc, dec works properly for larger `n`.
+    #[test]
+    fn cycle_larger_n() {
+        let ntru = NtruEncrypt::new(64, 12289, 2);
+        let (pk, sk) = ntru.gen();
+
+        for msg in [Z::ZERO, Z::ONE] {
+            let cipher = ntru.enc(&pk, &msg);
+
+            assert_eq!(msg, ntru.dec(&sk, &cipher));
+        }
+    }
+
+    /// Checks whether messages are reduced modulo 2.
+    #[test]
+    fn message_mod_two() {
+        let ntru = NtruEncrypt::default();
+        let (pk, sk) = ntru.gen();
+
+        assert_eq!(Z::ZERO, ntru.dec(&sk, &ntru.enc(&pk, 2)));
+        assert_eq!(Z::ONE, ntru.dec(&sk, &ntru.enc(&pk, 3)));
+    }
+
+    /// Ensures that generated key pairs are valid and that
+    /// mismatching or random key pairs are rejected.
+    #[test]
+    fn validate_keypair() {
+        let ntru = NtruEncrypt::default();
+        let (pk_0, sk_0) = ntru.gen();
+        let (pk_1, sk_1) = ntru.gen();
+        let random_pk = PolynomialRingZq::sample_uniform(&ntru.q);
+
+        assert!(ntru.validate_keypair(&pk_0, &sk_0));
+        assert!(ntru.validate_keypair(&pk_1, &sk_1));
+        assert!(!ntru.validate_keypair(&pk_0, &sk_1));
+        assert!(!ntru.validate_keypair(&random_pk, &sk_0));
+        assert!(!ntru.validate_keypair(&pk_0, &random_pk));
+    }
+}
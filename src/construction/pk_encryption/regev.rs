@@ -48,10 +48,10 @@ use serde::{Deserialize, Serialize};
 /// ```
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Regev {
-    n: Z,       // security parameter
-    m: Z,       // number of rows of matrix A
-    q: Modulus, // modulus
-    alpha: Q,   // Gaussian parameter for sampleZ
+    pub(crate) n: Z,       // security parameter
+    pub(crate) m: Z,       // number of rows of matrix A
+    pub(crate) q: Modulus, // modulus
+    pub(crate) alpha: Q,   // Gaussian parameter for sampleZ
 }
 
 impl Regev {
@@ -50,9 +50,9 @@ use serde::{Deserialize, Serialize};
 /// ```
 #[derive(Debug, Serialize, Deserialize)]
 pub struct RingLPR {
-    n: Z,                       // security parameter
-    q: ModulusPolynomialRingZq, // modulus
-    alpha: Q,                   // Gaussian parameter for sampleZ
+    pub(crate) n: Z,                       // security parameter
+    pub(crate) q: ModulusPolynomialRingZq, // modulus
+    pub(crate) alpha: Q,                   // Gaussian parameter for sampleZ
 }
 
 impl RingLPR {
@@ -0,0 +1,294 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains an encryption scheme based on [`CCSfromIBE`], which allows
+//! to open selected ciphertexts of a batch without affecting the security
+//! of the remaining ciphertexts.
+//!
+//! Every ciphertext of [`CCSfromIBE`] is encrypted under a fresh one-time verification
+//! key used as IBE identity. Hence, the secret key of this identity decrypts
+//! exactly one ciphertext and serves as opening information.
+//! Lattice-based encryption does not allow the receiver to recover the encryption
+//! randomness `(s, e)` from a ciphertext. Therefore, the identity secret key
+//! takes the role of the revealed randomness.
+//!
+//! Why unopened ciphertexts remain secure: opening reveals only the identity
+//! secret keys of the opened ciphertexts. The identities of the unopened
+//! ciphertexts differ from those, except with negligible probability, because
+//! every identity is a freshly generated verification key. The IBE scheme is
+//! secure for every identity whose secret key was not given out. So an adversary
+//! learns nothing about the unopened plaintexts, even if it sees the opened keys.
+//! An identity key can not be reused for another ciphertext either,
+//! since the one-time signature binds the ciphertext to its identity.
+
+use super::{CCSfromIBE, PKEncryptionSchemeMut};
+use crate::{
+    construction::{identity_based_encryption::IBEScheme, signature::SignatureScheme},
+    error::CryptoError,
+};
+use qfall_math::integer::Z;
+use serde::{Deserialize, Serialize};
+
+/// A ciphertext of [`SelectiveOpeningEnc`], which is a ciphertext of [`CCSfromIBE`],
+/// i.e. `(vrfy_key, c, sigma)`.
+pub type SoCipher<IBE, Signature> = (
+    <Signature as SignatureScheme>::PublicKey,
+    <IBE as IBEScheme>::Cipher,
+    <Signature as SignatureScheme>::Signature,
+);
+
+/// This struct wraps a [`CCSfromIBE`] instance and allows to encrypt batches of
+/// messages and to open selected ciphertexts of such a batch.
+///
+/// Attributes:
+/// - `ccs`: the wrapped [`CCSfromIBE`] construction
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::construction::pk_encryption::{CCSfromIBE, SelectiveOpeningEnc};
+/// use qfall_math::integer::Z;
+/// let mut scheme = SelectiveOpeningEnc::new(CCSfromIBE::init_dr_pfdh_from_n(4));
+/// let (pk, sk) = scheme.gen();
+///
+/// let ciphers = scheme.enc_batch(&pk, &[Z::ZERO, Z::ONE]);
+/// let openings = scheme.open(&sk, &[1], &ciphers).unwrap();
+///
+/// assert_eq!(Z::ONE, openings[0].0);
+/// assert!(scheme.verify_opening(&ciphers[1], &openings[0].0, &openings[0].1));
+/// ```
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SelectiveOpeningEnc<IBE: IBEScheme, Signature: SignatureScheme>
+where
+    IBE::Cipher: ToString,
+{
+    pub ccs: CCSfromIBE<IBE, Signature>,
+}
+
+impl<IBE, Signature> SelectiveOpeningEnc<IBE, Signature>
+where
+    IBE: IBEScheme,
+    Signature: SignatureScheme,
+    IBE::Cipher: ToString,
+    IBE::MasterPublicKey: Clone,
+    Signature::PublicKey: Into<IBE::Identity> + Clone,
+{
+    /// Wraps the provided [`CCSfromIBE`] construction.
+    ///
+    /// Parameters:
+    /// - `ccs`: the [`CCSfromIBE`] construction used for encryption
+    ///
+    /// Returns a [`SelectiveOpeningEnc`] instance.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pk_encryption::{CCSfromIBE, SelectiveOpeningEnc};
+    ///
+    /// let scheme = SelectiveOpeningEnc::new(CCSfromIBE::init_dr_pfdh_from_n(4));
+    /// ```
+    pub fn new(ccs: CCSfromIBE<IBE, Signature>) -> Self {
+        Self { ccs }
+    }
+
+    /// Generates a key pair using [`CCSfromIBE::gen`](PKEncryptionSchemeMut::gen).
+    ///
+    /// Returns a key pair `(pk, sk) = (mpk, (mpk, msk))`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pk_encryption::{CCSfromIBE, SelectiveOpeningEnc};
+    /// let mut scheme = SelectiveOpeningEnc::new(CCSfromIBE::init_dr_pfdh_from_n(4));
+    ///
+    /// let (pk, sk) = scheme.gen();
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn gen(
+        &mut self,
+    ) -> (
+        IBE::MasterPublicKey,
+        (IBE::MasterPublicKey, IBE::MasterSecretKey),
+    ) {
+        self.ccs.gen()
+    }
+
+    /// Encrypts every message in `messages` with a fresh one-time identity
+    /// using [`CCSfromIBE::enc`](PKEncryptionSchemeMut::enc).
+    ///
+    /// Parameters:
+    /// - `pk`: specifies the public key
+    /// - `messages`: specifies the messages that should be encrypted
+    ///
+    /// Returns a [`SoCipher`] for every message in `messages` in the same order.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pk_encryption::{CCSfromIBE, SelectiveOpeningEnc};
+    /// use qfall_math::integer::Z;
+    /// let mut scheme = SelectiveOpeningEnc::new(CCSfromIBE::init_dr_pfdh_from_n(4));
+    /// let (pk, sk) = scheme.gen();
+    ///
+    /// let ciphers = scheme.enc_batch(&pk, &[Z::ZERO, Z::ONE]);
+    /// ```
+    pub fn enc_batch(
+        &mut self,
+        pk: &IBE::MasterPublicKey,
+        messages: &[Z],
+    ) -> Vec<SoCipher<IBE, Signature>> {
+        messages
+            .iter()
+            .map(|message| self.ccs.enc(pk, message))
+            .collect()
+    }
+
+    /// Opens the ciphertexts at the positions `indices` in `ciphers`
+    /// by decrypting them and extracting the secret key of their one-time identity.
+    /// The secret keys of all other identities are not revealed.
+    ///
+    /// Parameters:
+    /// - `sk`: specifies the secret key `sk = (mpk, msk)`
+    /// - `indices`: specifies the positions of the ciphertexts that should be opened
+    /// - `ciphers`: specifies the batch of ciphertexts
+    ///
+    /// Returns the plaintext and the identity secret key for every position in `indices`
+    /// or a [`CryptoError`] if an opened ciphertext is rejected.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pk_encryption::{CCSfromIBE, SelectiveOpeningEnc};
+    /// use qfall_math::integer::Z;
+    /// let mut scheme = SelectiveOpeningEnc::new(CCSfromIBE::init_dr_pfdh_from_n(4));
+    /// let (pk, sk) = scheme.gen();
+    /// let ciphers = scheme.enc_batch(&pk, &[Z::ZERO, Z::ONE]);
+    ///
+    /// let openings = scheme.open(&sk, &[0], &ciphers).unwrap();
+    ///
+    /// assert_eq!(Z::ZERO, openings[0].0);
+    /// ```
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] if [`CCSfromIBE::dec`](PKEncryptionSchemeMut::dec)
+    ///     rejects an opened ciphertext, e.g. as its signature is invalid.
+    ///
+    /// # Panics ...
+    /// - if an index in `indices` is out of bounds for `ciphers`.
+    pub fn open(
+        &mut self,
+        sk: &(IBE::MasterPublicKey, IBE::MasterSecretKey),
+        indices: &[usize],
+        ciphers: &[SoCipher<IBE, Signature>],
+    ) -> Result<Vec<(Z, IBE::SecretKey)>, CryptoError> {
+        indices
+            .iter()
+            .map(|index| {
+                let cipher = &ciphers[*index];
+                let message = self.ccs.dec(sk, cipher)?;
+                let identity_sk = self
+                    .ccs
+                    .ibe
+                    .extract(&sk.0, &sk.1, &cipher.0.clone().into())?;
+
+                Ok((message, identity_sk))
+            })
+            .collect()
+    }
+
+    /// Checks whether `opening` is a valid opening of `cipher` to `message`,
+    /// i.e. whether the signature of `cipher` is valid and
+    /// `cipher` decrypts to `message` under the identity secret key `opening`.
+    ///
+    /// Parameters:
+    /// - `cipher`: specifies the opened ciphertext
+    /// - `message`: specifies the claimed plaintext
+    /// - `opening`: specifies the identity secret key output by [`SelectiveOpeningEnc::open`]
+    ///
+    /// Returns `true` if the opening is valid and `false` otherwise.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pk_encryption::{CCSfromIBE, SelectiveOpeningEnc};
+    /// use qfall_math::integer::Z;
+    /// let mut scheme = SelectiveOpeningEnc::new(CCSfromIBE::init_dr_pfdh_from_n(4));
+    /// let (pk, sk) = scheme.gen();
+    /// let ciphers = scheme.enc_batch(&pk, &[Z::ONE]);
+    /// let openings = scheme.open(&sk, &[0], &ciphers).unwrap();
+    ///
+    /// assert!(scheme.verify_opening(&ciphers[0], &Z::ONE, &openings[0].1));
+    /// ```
+    pub fn verify_opening(
+        &self,
+        cipher: &SoCipher<IBE, Signature>,
+        message: &Z,
+        opening: &IBE::SecretKey,
+    ) -> bool {
+        self.ccs
+            .signature
+            .vfy(cipher.1.to_string(), &cipher.2, &cipher.0)
+            && self.ccs.ibe.dec(opening, &cipher.1).ok().as_ref() == Some(message)
+    }
+}
+
+#[cfg(test)]
+mod test_selective_opening {
+    use super::SelectiveOpeningEnc;
+    use crate::construction::pk_encryption::CCSfromIBE;
+    use qfall_math::integer::Z;
+
+    /// Ensures that opened ciphertexts reveal the correct plaintexts
+    /// and that their openings verify.
+    #[test]
+    fn open_selected() {
+        let mut scheme = SelectiveOpeningEnc::new(CCSfromIBE::init_dr_pfdh_from_n(4));
+        let (pk, sk) = scheme.gen();
+        let messages = [Z::ZERO, Z::ONE, Z::ONE, Z::ZERO];
+        let ciphers = scheme.enc_batch(&pk, &messages);
+
+        let openings = scheme.open(&sk, &[0, 2], &ciphers).unwrap();
+
+        assert_eq!(2, openings.len());
+        for (index, (message, opening)) in [0, 2].into_iter().zip(openings.iter()) {
+            assert_eq!(messages[index], *message);
+            assert!(scheme.verify_opening(&ciphers[index], message, opening));
+        }
+    }
+
+    /// Ensures that every ciphertext has its own opening information,
+    /// i.e. opening one ciphertext does not reveal the key of another one.
+    #[test]
+    fn independent_openings() {
+        let mut scheme = SelectiveOpeningEnc::new(CCSfromIBE::init_dr_pfdh_from_n(4));
+        let (pk, sk) = scheme.gen();
+        let ciphers = scheme.enc_batch(&pk, &[Z::ONE, Z::ONE]);
+
+        let openings = scheme.open(&sk, &[0, 1], &ciphers).unwrap();
+
+        assert_ne!(ciphers[0].0, ciphers[1].0);
+        assert_ne!(openings[0].1, openings[1].1);
+    }
+
+    /// Ensures that a valid opening does not verify for a different plaintext.
+    #[test]
+    fn wrong_message_rejected() {
+        let mut scheme = SelectiveOpeningEnc::new(CCSfromIBE::init_dr_pfdh_from_n(4));
+        let (pk, sk) = scheme.gen();
+        let ciphers = scheme.enc_batch(&pk, &[Z::ONE]);
+
+        let openings = scheme.open(&sk, &[0], &ciphers).unwrap();
+
+        assert!(!scheme.verify_opening(&ciphers[0], &Z::ZERO, &openings[0].1));
+    }
+
+    /// Ensures that opening an index out of bounds panics.
+    #[test]
+    #[should_panic]
+    fn index_out_of_bounds() {
+        let mut scheme = SelectiveOpeningEnc::new(CCSfromIBE::init_dr_pfdh_from_n(4));
+        let (pk, sk) = scheme.gen();
+        let ciphers = scheme.enc_batch(&pk, &[Z::ONE]);
+
+        let _ = scheme.open(&sk, &[1], &ciphers).unwrap();
+    }
+}
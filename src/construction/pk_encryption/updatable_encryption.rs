@@ -0,0 +1,349 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains an updatable encryption scheme based on [`Regev`],
+//! which allows to rotate the key of a ciphertext without decrypting it.
+
+use super::{PKEncryptionScheme, Regev};
+use qfall_math::{
+    integer::{MatZ, Z},
+    integer_mod_q::{MatZq, Modulus, Zq},
+    rational::Q,
+    traits::{GetEntry, Pow, SetEntry},
+};
+use serde::{Deserialize, Serialize};
+
+/// An update token transforming ciphertexts under an old key into
+/// ciphertexts under a new key.
+///
+/// Attributes:
+/// - `keys`: the encryptions of `s_old[i] * 2^k` under the new public key
+///     for every entry `i` of the old secret key and every `k < ⌈log_2(q)⌉`
+///     in the order `(i, k)`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateToken {
+    pub keys: Vec<MatZq>,
+}
+
+/// This struct manages and stores the public parameters of an updatable
+/// encryption scheme based on [`Regev`].
+///
+/// A ciphertext `c = [u | v]^t` under the old secret key `s_old` is updated
+/// by key switching, i.e. the update token contains encryptions of `s_old[i] * 2^k`
+/// under the new public key and the updated ciphertext is
+/// `[0 | v]^t - Σ_{i, k} u[i]_k * Enc(s_old[i] * 2^k)`, where `u[i]_k` is the
+/// `k`-th bit of `u[i]`. This adds an encryption of `-<s_old, u>` under the new key,
+/// which removes the dependency on `s_old` from `v`.
+/// The noise grows with the number of additions, hence `alpha` is chosen
+/// smaller than for [`Regev::new_from_n`].
+///
+/// Attributes:
+/// - `regev`: the [`Regev`] instance used for encryption and decryption
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::construction::pk_encryption::{PKEncryptionScheme, UpdatableEncryption};
+/// use qfall_math::integer::Z;
+///
+/// let scheme = UpdatableEncryption::new_from_n(13);
+/// let (pk_1, sk_1) = scheme.gen();
+/// let (pk_2, sk_2) = scheme.gen();
+///
+/// let cipher = scheme.enc(&pk_1, 1);
+///
+/// let token = scheme.gen_update_token(&sk_1, &pk_2);
+/// let updated = scheme.update_ciphertext(&cipher, &token);
+///
+/// assert_eq!(Z::ONE, scheme.dec(&sk_2, &updated));
+/// ```
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdatableEncryption {
+    pub regev: Regev,
+}
+
+impl UpdatableEncryption {
+    /// Initializes an [`UpdatableEncryption`] instance for security parameter `n`
+    /// by choosing a prime `q ∈ [n^5 / 2, n^5]`, `m = (n + 1) * ⌈log_2(q)⌉ + 1`, and
+    /// `α = 1 / (8 * sqrt(n * ⌈log_2(q)⌉ * m))` such that updated ciphertexts
+    /// can be decrypted with overwhelming probability.
+    ///
+    /// Parameters:
+    /// - `n`: the security parameter
+    ///
+    /// Returns an [`UpdatableEncryption`] instance.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pk_encryption::UpdatableEncryption;
+    ///
+    /// let scheme = UpdatableEncryption::new_from_n(13);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `n < 2`.
+    pub fn new_from_n(n: impl Into<Z>) -> Self {
+        let n: Z = n.into();
+        if n < Z::from(2) {
+            panic!("Security parameter n has to be larger than 1");
+        }
+
+        // generate prime q in [n^5 / 2, n^5]
+        let upper_bound: Z = n.pow(5).unwrap();
+        let lower_bound = upper_bound.div_ceil(2);
+        let q = Z::sample_prime_uniform(&lower_bound, &upper_bound).unwrap();
+
+        let log_q = q.log_ceil(2).unwrap();
+        let m = (&n + Z::ONE) * &log_q + Z::ONE;
+
+        // the updated noise is a sum of up to n * log(q) noise terms
+        let alpha: Q = 1 / (8 * (&n * &log_q * &m).sqrt());
+
+        Self {
+            regev: Regev::new(n, m, Modulus::from(q), alpha),
+        }
+    }
+
+    /// Returns the number of bits `⌈log_2(q)⌉` used to decompose ciphertexts.
+    fn nr_bits(&self) -> i64 {
+        i64::try_from(&Z::from(&self.regev.q).log_ceil(2).unwrap()).unwrap()
+    }
+
+    /// Encrypts `value ∈ Z_q` under `pk`, i.e. computes `A * x + [0^{1 x n} | value]^t`.
+    fn enc_value(&self, pk: &MatZq, value: &Z) -> MatZq {
+        let vec_x = MatZ::sample_uniform(&self.regev.m, 1, 0, 2).unwrap();
+        let mut c = pk * vec_x;
+
+        let last_entry: Zq = c.get_entry(-1, 0).unwrap();
+        c.set_entry(-1, 0, last_entry + value).unwrap();
+
+        c
+    }
+
+    /// Generates an update token transforming ciphertexts under the public key
+    /// of `old_sk` into ciphertexts under `new_pk`.
+    ///
+    /// Parameters:
+    /// - `old_sk`: the secret key of the old key pair
+    /// - `new_pk`: the public key of the new key pair
+    ///
+    /// Returns an [`UpdateToken`] containing encryptions of the bit-decomposed
+    /// old secret key under the new public key.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pk_encryption::{PKEncryptionScheme, UpdatableEncryption};
+    ///
+    /// let scheme = UpdatableEncryption::new_from_n(13);
+    /// let (_, sk_1) = scheme.gen();
+    /// let (pk_2, _) = scheme.gen();
+    ///
+    /// let token = scheme.gen_update_token(&sk_1, &pk_2);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `old_sk` or `new_pk` do not match the public parameters.
+    pub fn gen_update_token(&self, old_sk: &MatZq, new_pk: &MatZq) -> UpdateToken {
+        let old_sk = old_sk.get_representative_0_modulus();
+        let n = i64::try_from(&self.regev.n).unwrap();
+
+        let mut keys = vec![];
+        for i in 0..n {
+            let mut value: Z = old_sk.get_entry(i, 0).unwrap();
+            for _ in 0..self.nr_bits() {
+                keys.push(self.enc_value(new_pk, &value));
+                value = value * 2;
+            }
+        }
+
+        UpdateToken { keys }
+    }
+
+    /// Updates `cipher` using `token` such that it can be decrypted with
+    /// the new secret key instead of the old one.
+    ///
+    /// Parameters:
+    /// - `cipher`: a ciphertext under the old public key
+    /// - `token`: the update token from the old to the new key
+    ///
+    /// Returns a ciphertext of the same message under the new public key.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pk_encryption::{PKEncryptionScheme, UpdatableEncryption};
+    ///
+    /// let scheme = UpdatableEncryption::new_from_n(13);
+    /// let (pk_1, sk_1) = scheme.gen();
+    /// let (pk_2, _) = scheme.gen();
+    /// let cipher = scheme.enc(&pk_1, 0);
+    ///
+    /// let token = scheme.gen_update_token(&sk_1, &pk_2);
+    /// let updated = scheme.update_ciphertext(&cipher, &token);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `cipher` or `token` do not match the public parameters.
+    pub fn update_ciphertext(&self, cipher: &MatZq, token: &UpdateToken) -> MatZq {
+        let n = i64::try_from(&self.regev.n).unwrap();
+        let nr_bits = self.nr_bits() as usize;
+        let cipher_z = cipher.get_representative_0_modulus();
+
+        // [0 | v]^t
+        let mut updated = MatZq::new(n + 1, 1, &self.regev.q);
+        let v: Z = cipher_z.get_entry(-1, 0).unwrap();
+        updated.set_entry(-1, 0, v).unwrap();
+
+        // subtract Σ_{i, k} u[i]_k * Enc(s_old[i] * 2^k)
+        for i in 0..n {
+            let u_i: Z = cipher_z.get_entry(i, 0).unwrap();
+            for (k, bit) in u_i.to_bits().iter().enumerate() {
+                if *bit {
+                    updated = updated - &token.keys[i as usize * nr_bits + k];
+                }
+            }
+        }
+
+        updated
+    }
+}
+
+impl Default for UpdatableEncryption {
+    /// Initializes an [`UpdatableEncryption`] instance with `UpdatableEncryption::new_from_n(13)`.
+    /// This parameter choice is not secure as the dimension of the lattice is too small,
+    /// but it provides an efficient working example.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pk_encryption::UpdatableEncryption;
+    ///
+    /// let scheme = UpdatableEncryption::default();
+    /// ```
+    fn default() -> Self {
+        Self::new_from_n(13)
+    }
+}
+
+impl PKEncryptionScheme for UpdatableEncryption {
+    type Cipher = MatZq;
+    type PublicKey = MatZq;
+    type SecretKey = MatZq;
+
+    /// Generates a (pk, sk) pair using [`Regev`].
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pk_encryption::{PKEncryptionScheme, UpdatableEncryption};
+    /// let scheme = UpdatableEncryption::default();
+    ///
+    /// let (pk, sk) = scheme.gen();
+    /// ```
+    fn gen(&self) -> (Self::PublicKey, Self::SecretKey) {
+        self.regev.gen()
+    }
+
+    /// Encrypts `message mod 2` under `pk` using [`Regev`].
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pk_encryption::{PKEncryptionScheme, UpdatableEncryption};
+    /// let scheme = UpdatableEncryption::default();
+    /// let (pk, sk) = scheme.gen();
+    ///
+    /// let cipher = scheme.enc(&pk, 1);
+    /// ```
+    fn enc(&self, pk: &Self::PublicKey, message: impl Into<Z>) -> Self::Cipher {
+        self.regev.enc(pk, message)
+    }
+
+    /// Decrypts `cipher` using `sk` using [`Regev`].
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pk_encryption::{PKEncryptionScheme, UpdatableEncryption};
+    /// use qfall_math::integer::Z;
+    /// let scheme = UpdatableEncryption::default();
+    /// let (pk, sk) = scheme.gen();
+    /// let cipher = scheme.enc(&pk, 1);
+    ///
+    /// assert_eq!(Z::ONE, scheme.dec(&sk, &cipher));
+    /// ```
+    fn dec(&self, sk: &Self::SecretKey, cipher: &Self::Cipher) -> Z {
+        self.regev.dec(sk, cipher)
+    }
+}
+
+#[cfg(test)]
+mod test_updatable_encryption {
+    use super::UpdatableEncryption;
+    use crate::construction::pk_encryption::PKEncryptionScheme;
+    use qfall_math::integer::Z;
+
+    /// Ensures that the chosen parameters are secure and correct for [`Regev`](super::Regev).
+    #[test]
+    fn suitable_parameters() {
+        let scheme = UpdatableEncryption::default();
+
+        assert!(scheme.regev.check_correctness().is_ok());
+        assert!(scheme.regev.check_security().is_ok());
+    }
+
+    /// Ensures that updated ciphertexts decrypt to the original message under the new key.
+    #[test]
+    fn update_cycle() {
+        let scheme = UpdatableEncryption::default();
+        let (pk_1, sk_1) = scheme.gen();
+        let (pk_2, sk_2) = scheme.gen();
+        let token = scheme.gen_update_token(&sk_1, &pk_2);
+
+        for msg in [Z::ZERO, Z::ONE] {
+            for _ in 0..10 {
+                let cipher = scheme.enc(&pk_1, &msg);
+                let updated = scheme.update_ciphertext(&cipher, &token);
+
+                assert_eq!(msg, scheme.dec(&sk_1, &cipher));
+                assert_eq!(msg, scheme.dec(&sk_2, &updated));
+            }
+        }
+    }
+
+    /// Ensures that ciphertexts can be updated several times.
+    #[test]
+    fn multiple_updates() {
+        let scheme = UpdatableEncryption::default();
+        let (pk_1, sk_1) = scheme.gen();
+        let (pk_2, sk_2) = scheme.gen();
+        let (pk_3, sk_3) = scheme.gen();
+
+        let cipher = scheme.enc(&pk_1, 1);
+        let cipher = scheme.update_ciphertext(&cipher, &scheme.gen_update_token(&sk_1, &pk_2));
+        let cipher = scheme.update_ciphertext(&cipher, &scheme.gen_update_token(&sk_2, &pk_3));
+
+        assert_eq!(Z::ONE, scheme.dec(&sk_3, &cipher));
+    }
+
+    /// Ensures that the old secret key can not decrypt updated ciphertexts.
+    #[test]
+    fn old_key_fails() {
+        let scheme = UpdatableEncryption::default();
+        let (pk_1, sk_1) = scheme.gen();
+        let (pk_2, _) = scheme.gen();
+        let token = scheme.gen_update_token(&sk_1, &pk_2);
+
+        // an updated ciphertext decrypts to a uniform bit under the old key,
+        // so the probability that all 40 decryptions are correct is negligible
+        let mut nr_correct = 0;
+        for i in 0..40 {
+            let msg = Z::from(i % 2);
+            let updated = scheme.update_ciphertext(&scheme.enc(&pk_1, &msg), &token);
+            if scheme.dec(&sk_1, &updated) == msg {
+                nr_correct += 1;
+            }
+        }
+
+        assert!(nr_correct < 40);
+    }
+}
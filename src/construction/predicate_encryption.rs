@@ -0,0 +1,30 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains predicate encryption schemes, where a ciphertext
+//! can only be decrypted with a functional key whose predicate is satisfied
+//! by the attribute the ciphertext was generated for.
+//!
+//! The main references are listed in the following
+//! and will be further referenced in submodules by these numbers:
+//! - \[1\] Gentry, Craig and Peikert, Chris and Vaikuntanathan, Vinod (2008).
+//!     Trapdoors for hard lattices and new cryptographic constructions.
+//!     In: Proceedings of the fortieth annual ACM symposium on Theory of computing.
+//!     <https://dl.acm.org/doi/pdf/10.1145/1374376.1374407>
+//! - \[2\] Katz, Jonathan and Sahai, Amit and Waters, Brent (2008).
+//!     Predicate Encryption Supporting Disjunctions, Polynomial Equations, and Inner Products.
+//!     In: Advances in Cryptology - EUROCRYPT 2008.
+//!     <https://eprint.iacr.org/2007/404.pdf>
+//! - \[3\] Agrawal, Shweta and Freeman, David Mandell and Vaikuntanathan, Vinod (2011).
+//!     Functional Encryption for Inner Product Predicates from Learning with Errors.
+//!     In: Advances in Cryptology - ASIACRYPT 2011.
+//!     <https://eprint.iacr.org/2011/410.pdf>
+
+mod inner_product;
+
+pub use inner_product::{IPECipher, IPEFunctionalKey, IPEMasterPublicKey, InnerProductEncryption};
@@ -0,0 +1,458 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains an implementation of a predicate encryption scheme
+//! for inner-product predicates, i.e. a functional key for `v` can decrypt
+//! ciphertexts for `x` if and only if `<v, x> = 0`.
+
+use crate::{
+    primitive::psf::{PSF, PSFGPV},
+    sample::g_trapdoor::gadget_parameters::GadgetParameters,
+};
+use qfall_math::{
+    integer::{MatZ, Z},
+    integer_mod_q::{MatZq, Modulus, Zq},
+    rational::{MatQ, Q},
+    traits::{Concatenate, Distance, GetEntry, Pow, SetEntry, Tensor},
+};
+use serde::{Deserialize, Serialize};
+
+/// The number of additional ciphertext slots encrypting `0`, which are used to
+/// detect whether a ciphertext could be decrypted, i.e. whether `<v, x> = 0`.
+/// A non-matching functional key passes the check with probability
+/// at most `4^{-NR_CHECK_SLOTS}`.
+const NR_CHECK_SLOTS: i64 = 16;
+
+/// The master public key of an [`InnerProductEncryption`] instance.
+///
+/// Attributes:
+/// - `a`: the matrix `A ∈ Z_q^{n x m}` whose trapdoor is the master secret key
+/// - `b`: a uniform matrix `B_i ∈ Z_q^{n x m}` for each entry of the attribute vectors
+/// - `b_0`: a uniform matrix `B ∈ Z_q^{n x m}` binding the attribute vector
+/// - `u`: a uniform matrix `U ∈ Z_q^{n x (1 + NR_CHECK_SLOTS)}`, whose first column
+///     is used to encrypt the message and the others to detect decryption failures
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IPEMasterPublicKey {
+    pub a: MatZq,
+    pub b: Vec<MatZq>,
+    pub b_0: MatZq,
+    pub u: MatZq,
+}
+
+/// A functional key for the predicate `f_v(x) = (<v, x> = 0)`.
+///
+/// Attributes:
+/// - `v`: the predicate vector
+/// - `keys`: a short matrix `E` with `[A | Σ v_i B_i] * E = U`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IPEFunctionalKey {
+    pub v: Vec<Z>,
+    pub keys: MatZ,
+}
+
+/// A ciphertext of an [`InnerProductEncryption`] instance.
+///
+/// Attributes:
+/// - `c_0`: the component `s^t * A + x_0^t`
+/// - `c`: the component `s^t * (B_i + x_i * B) + y_i^t` for each entry `x_i`
+///     of the attribute vector
+/// - `c_msg`: the component `s^t * U + z^t + [msg * ⌊q/2⌋ | 0 | ... | 0]`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IPECipher {
+    pub c_0: MatZq,
+    pub c: Vec<MatZq>,
+    pub c_msg: MatZq,
+}
+
+/// This struct manages and stores the public parameters of a predicate
+/// encryption scheme for inner-product predicates, which adapts
+/// the inner-product predicate encryption of [\[2\]](<index.html#:~:text=[2]>)
+/// to lattices according to [\[3\]](<index.html#:~:text=[3]>) using the GPV-PSF
+/// of [\[1\]](<index.html#:~:text=[1]>) and Dual Regev style ciphertexts.
+///
+/// A functional key for `v` is a short preimage `E` of `U` under `[A | Σ v_i B_i]`.
+/// Combining the ciphertext components with `v` yields
+/// `s^t * [A | Σ v_i B_i + <v, x> * B]`, which equals `s^t * [A | Σ v_i B_i]`
+/// if and only if `<v, x> = 0`. Otherwise, the decryption yields uniform values,
+/// which is detected by additional slots encrypting `0`.
+///
+/// In contrast to [\[3\]](<index.html#:~:text=[3]>), the noise terms `y_i` of the
+/// ciphertext are sampled independently, which is sufficient for correctness.
+/// The noise grows with the norm of `v`, hence `v` should have small entries.
+///
+/// Attributes:
+/// - `n`: specifies the security parameter, which is not equal to the bit-security level
+/// - `m`: defines the dimension of the underlying lattice
+/// - `q`: specifies the modulus over which the encryption is computed
+/// - `alpha`: specifies the Gaussian parameter used for independent
+///     sampling from the discrete Gaussian distribution
+/// - `psf`: specifies the PSF used for generating the functional keys
+/// - `vec_len`: the length of attribute and predicate vectors
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::construction::predicate_encryption::InnerProductEncryption;
+/// use qfall_math::integer::Z;
+///
+/// let ipe = InnerProductEncryption::new_from_n(4, 2);
+/// let (mpk, msk) = ipe.setup();
+///
+/// let fk = ipe.key_gen(&mpk, &msk, &[Z::from(1), Z::from(-1)]);
+///
+/// let cipher = ipe.enc(&mpk, &[Z::from(2), Z::from(2)], Z::ONE);
+/// assert_eq!(Some(Z::ONE), ipe.dec(&fk, &cipher));
+///
+/// let cipher = ipe.enc(&mpk, &[Z::from(1), Z::from(2)], Z::ONE);
+/// assert_eq!(None, ipe.dec(&fk, &cipher));
+/// ```
+#[derive(Serialize, Deserialize)]
+pub struct InnerProductEncryption {
+    n: Z,
+    m: Z,
+    q: Modulus,
+    alpha: Q,
+    pub psf: PSFGPV,
+    vec_len: usize,
+}
+
+impl InnerProductEncryption {
+    /// Initializes an [`InnerProductEncryption`] instance for security parameter `n`
+    /// and vectors of length `vec_len`.
+    ///
+    /// The parameters are chosen similar to
+    /// [`DualRegevIBE::new_from_n`](crate::construction::identity_based_encryption::DualRegevIBE::new_from_n),
+    /// but `alpha` is decreased by a factor `4 * vec_len` to account for the
+    /// noise of all `vec_len` ciphertext components and the smaller decryption threshold.
+    ///
+    /// **WARNING:** Due to the [`PSF`] this schemes key generation is slow for n > 5.
+    ///
+    /// Parameters:
+    /// - `n`: the security parameter
+    /// - `vec_len`: the length of the attribute and predicate vectors
+    ///
+    /// Returns an [`InnerProductEncryption`] instance.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::predicate_encryption::InnerProductEncryption;
+    ///
+    /// let ipe = InnerProductEncryption::new_from_n(4, 3);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `n < 2` or `vec_len < 1`.
+    pub fn new_from_n(n: impl Into<Z>, vec_len: usize) -> Self {
+        let n: Z = n.into();
+        assert!(
+            n >= Z::from(2),
+            "Security parameter n has to be larger than 1"
+        );
+        assert!(vec_len >= 1, "The vectors need to have at least one entry.");
+
+        let n_i64 = i64::try_from(&n).unwrap();
+        // these powers are chosen according to experience as for [`DualRegevIBE`]
+        let power = match n_i64 {
+            2..=3 => 10,
+            4 => 7,
+            5..=7 => 6,
+            _ => 5,
+        };
+        // generate prime q in [n^power / 2, n^power]
+        let upper_bound: Z = n.pow(power).unwrap();
+        let lower_bound = upper_bound.div_ceil(2);
+        let q = Modulus::from(Z::sample_prime_uniform(&lower_bound, &upper_bound).unwrap());
+
+        let gadget = GadgetParameters::init_default(&n, &q);
+        let log_q = Z::from(&q).log_ceil(2).unwrap();
+        let m = &gadget.m_bar + &n * &log_q;
+
+        let r: Q = m.sqrt();
+        let alpha = 1 / (&r * (8 * vec_len as i64) * (&m + Z::ONE).sqrt() * n.log(2).unwrap());
+
+        Self {
+            n,
+            m,
+            q,
+            alpha,
+            psf: PSFGPV {
+                gp: gadget,
+                s: r,
+                cdt: None,
+                cdt_cache: Default::default(),
+            },
+            vec_len,
+        }
+    }
+
+    /// Generates a master key pair, where `A` is generated together with its
+    /// trapdoor and all other matrices of the master public key are uniform.
+    ///
+    /// Returns a tuple `(mpk, msk)`, where `msk` is the short basis of `A`
+    /// together with its GSO.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::predicate_encryption::InnerProductEncryption;
+    ///
+    /// let ipe = InnerProductEncryption::new_from_n(4, 2);
+    /// let (mpk, msk) = ipe.setup();
+    /// ```
+    pub fn setup(&self) -> (IPEMasterPublicKey, (MatZ, MatQ)) {
+        let (a, msk) = self.psf.trap_gen();
+        let b = (0..self.vec_len)
+            .map(|_| MatZq::sample_uniform(&self.n, &self.m, &self.q))
+            .collect();
+        let b_0 = MatZq::sample_uniform(&self.n, &self.m, &self.q);
+        let u = MatZq::sample_uniform(&self.n, 1 + NR_CHECK_SLOTS, &self.q);
+
+        (IPEMasterPublicKey { a, b, b_0, u }, msk)
+    }
+
+    /// Computes `[M_1 | ... | M_l] * (v ⊗ I_m) = Σ v_i M_i` for matrices
+    /// `M_i` with `m` columns.
+    fn combine(&self, matrices: &[MatZq], v: &[Z]) -> MatZq {
+        let mut mat_v = MatZ::new(v.len(), 1);
+        for (i, v_i) in v.iter().enumerate() {
+            mat_v.set_entry(i, 0, v_i).unwrap();
+        }
+        let combination = mat_v.tensor_product(&MatZ::identity(&self.m, &self.m));
+
+        let mut concatenated = matrices[0].clone();
+        for matrix in matrices.iter().skip(1) {
+            concatenated = concatenated.concat_horizontal(matrix).unwrap();
+        }
+
+        concatenated * combination
+    }
+
+    /// Generates a functional key for the predicate `f_v(x) = (<v, x> = 0)`
+    /// by sampling a short `E` with `[A | Σ v_i B_i] * E = U` column by column:
+    /// - e_2 <- D_{Z^m, r}
+    /// - e_1 = samp_p(A, msk, u_k - (Σ v_i B_i) * e_2)
+    ///
+    /// Parameters:
+    /// - `mpk`: the master public key
+    /// - `msk`: the master secret key, i.e. the trapdoor of `A`
+    /// - `v`: the predicate vector
+    ///
+    /// Returns an [`IPEFunctionalKey`] for `v`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::predicate_encryption::InnerProductEncryption;
+    /// use qfall_math::integer::Z;
+    ///
+    /// let ipe = InnerProductEncryption::new_from_n(4, 2);
+    /// let (mpk, msk) = ipe.setup();
+    ///
+    /// let fk = ipe.key_gen(&mpk, &msk, &[Z::from(1), Z::from(-1)]);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `v` does not have length `vec_len`.
+    pub fn key_gen(
+        &self,
+        mpk: &IPEMasterPublicKey,
+        msk: &(MatZ, MatQ),
+        v: &[Z],
+    ) -> IPEFunctionalKey {
+        assert_eq!(
+            self.vec_len,
+            v.len(),
+            "The vector v has to have length vec_len."
+        );
+
+        let mat_c = self.combine(&mpk.b, v);
+
+        let mut keys: Option<MatZ> = None;
+        for k in 0..=NR_CHECK_SLOTS {
+            let e_2 = self.psf.samp_d();
+            let target = mpk.u.get_column(k).unwrap() - &mat_c * &e_2;
+            let e_1 = self.psf.samp_p(&mpk.a, msk, &target);
+            let column = e_1.concat_vertical(&e_2).unwrap();
+
+            keys = Some(match keys {
+                Some(keys) => keys.concat_horizontal(&column).unwrap(),
+                None => column,
+            });
+        }
+
+        IPEFunctionalKey {
+            v: v.to_vec(),
+            keys: keys.unwrap(),
+        }
+    }
+
+    /// Generates an encryption of `msg mod 2` for the attribute vector `x`:
+    /// - s <- Z_q^n
+    /// - c_0 = s^t * A + x_0^t
+    /// - c_i = s^t * (B_i + x_i * B) + y_i^t
+    /// - c_msg = s^t * U + z^t + [msg * ⌊q/2⌋ | 0 | ... | 0]
+    ///     where `x_0`, `y_i` and `z` are discrete Gaussian distributed with
+    ///     center 0 and Gaussian parameter q * α.
+    ///
+    /// Parameters:
+    /// - `mpk`: the master public key
+    /// - `x`: the attribute vector
+    /// - `msg`: the message that should be encrypted
+    ///
+    /// Returns an [`IPECipher`].
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::predicate_encryption::InnerProductEncryption;
+    /// use qfall_math::integer::Z;
+    ///
+    /// let ipe = InnerProductEncryption::new_from_n(4, 2);
+    /// let (mpk, msk) = ipe.setup();
+    ///
+    /// let cipher = ipe.enc(&mpk, &[Z::from(2), Z::from(2)], Z::ONE);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `x` does not have length `vec_len`.
+    pub fn enc(&self, mpk: &IPEMasterPublicKey, x: &[Z], msg: impl Into<Z>) -> IPECipher {
+        assert_eq!(
+            self.vec_len,
+            x.len(),
+            "The vector x has to have length vec_len."
+        );
+        let msg: Z = msg.into().modulo(2);
+        let s_gauss = &self.alpha * Z::from(&self.q);
+
+        // s <- Z_q^n
+        let vec_s_t = MatZq::sample_uniform(1, &self.n, &self.q);
+        let vec_s_t_b = &vec_s_t * &mpk.b_0;
+        let identity = MatZ::identity(&self.m, &self.m);
+
+        let noise =
+            || MatZq::sample_discrete_gauss(1, &self.m, &self.q, &self.n, 0, &s_gauss).unwrap();
+
+        let c_0 = &vec_s_t * &mpk.a + noise();
+        let c = mpk
+            .b
+            .iter()
+            .zip(x)
+            .map(|(b_i, x_i)| &vec_s_t * b_i + &vec_s_t_b * (x_i.clone() * &identity) + noise())
+            .collect();
+
+        let vec_z_t =
+            MatZq::sample_discrete_gauss(1, 1 + NR_CHECK_SLOTS, &self.q, &self.n, 0, &s_gauss)
+                .unwrap();
+        let mut c_msg = &vec_s_t * &mpk.u + vec_z_t;
+        let entry: Zq = c_msg.get_entry(0, 0).unwrap();
+        c_msg
+            .set_entry(0, 0, entry + msg * Z::from(&self.q).div_floor(2))
+            .unwrap();
+
+        IPECipher { c_0, c, c_msg }
+    }
+
+    /// Decrypts `cipher` using the functional key `fk` by following these steps:
+    /// - d = c_msg - [c_0 | Σ v_i c_i] * E
+    /// - if any entry of `d` except for the first is not close to 0, the
+    ///     decryption failed as `<v, x> != 0`
+    /// - if d_0 is closer to ⌊q/2⌋ than to 0, output 1. Otherwise, output 0.
+    ///
+    /// Parameters:
+    /// - `fk`: the functional key for `v`
+    /// - `cipher`: the ciphertext for the attribute vector `x`
+    ///
+    /// Returns the decrypted bit or `None` if `<v, x> != 0`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::predicate_encryption::InnerProductEncryption;
+    /// use qfall_math::integer::Z;
+    ///
+    /// let ipe = InnerProductEncryption::new_from_n(4, 2);
+    /// let (mpk, msk) = ipe.setup();
+    /// let fk = ipe.key_gen(&mpk, &msk, &[Z::from(1), Z::from(-1)]);
+    /// let cipher = ipe.enc(&mpk, &[Z::from(3), Z::from(3)], Z::ZERO);
+    ///
+    /// assert_eq!(Some(Z::ZERO), ipe.dec(&fk, &cipher));
+    /// ```
+    pub fn dec(&self, fk: &IPEFunctionalKey, cipher: &IPECipher) -> Option<Z> {
+        let q_half = Z::from(&self.q).div_floor(2);
+        let threshold = Z::from(&self.q).div_floor(8);
+
+        let c_v = self.combine(&cipher.c, &fk.v);
+        let d = &cipher.c_msg - cipher.c_0.concat_horizontal(&c_v).unwrap() * &fk.keys;
+
+        for k in 1..=NR_CHECK_SLOTS {
+            let entry: Zq = d.get_entry(0, k).unwrap();
+            if entry.distance(Z::ZERO) > threshold {
+                return None;
+            }
+        }
+
+        let entry: Zq = d.get_entry(0, 0).unwrap();
+        let distance_zero = entry.distance(Z::ZERO);
+        let distance_q_half = entry.distance(&q_half);
+        if distance_zero > threshold && distance_q_half > threshold {
+            return None;
+        }
+
+        if distance_zero > distance_q_half {
+            Some(Z::ONE)
+        } else {
+            Some(Z::ZERO)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_inner_product_encryption {
+    use super::InnerProductEncryption;
+    use qfall_math::integer::Z;
+
+    /// Converts a slice of integers into a vector of [`Z`].
+    fn to_z(values: &[i64]) -> Vec<Z> {
+        values.iter().map(|value| Z::from(*value)).collect()
+    }
+
+    /// Ensures that ciphertexts for `x` with `<v, x> = 0` are decrypted correctly.
+    #[test]
+    fn inner_product_zero() {
+        let ipe = InnerProductEncryption::new_from_n(4, 3);
+        let (mpk, msk) = ipe.setup();
+        let fk = ipe.key_gen(&mpk, &msk, &to_z(&[1, -1, 1]));
+
+        for x in [[0, 0, 0], [1, 1, 0], [2, 3, 1], [-4, 1, 5]] {
+            for msg in [Z::ZERO, Z::ONE] {
+                let cipher = ipe.enc(&mpk, &to_z(&x), &msg);
+                assert_eq!(Some(msg), ipe.dec(&fk, &cipher));
+            }
+        }
+    }
+
+    /// Ensures that ciphertexts for `x` with `<v, x> != 0` can not be decrypted.
+    #[test]
+    fn inner_product_non_zero() {
+        let ipe = InnerProductEncryption::new_from_n(4, 3);
+        let (mpk, msk) = ipe.setup();
+        let fk = ipe.key_gen(&mpk, &msk, &to_z(&[1, -1, 1]));
+
+        for x in [[1, 0, 0], [1, 1, 1], [2, 3, 0], [0, 0, -1]] {
+            for msg in [Z::ZERO, Z::ONE] {
+                let cipher = ipe.enc(&mpk, &to_z(&x), &msg);
+                assert_eq!(None, ipe.dec(&fk, &cipher));
+            }
+        }
+    }
+
+    /// Ensures that vectors of the wrong length are rejected.
+    #[test]
+    #[should_panic]
+    fn wrong_length() {
+        let ipe = InnerProductEncryption::new_from_n(4, 2);
+        let (mpk, _) = ipe.setup();
+
+        let _ = ipe.enc(&mpk, &to_z(&[1, 2, 3]), 1);
+    }
+}
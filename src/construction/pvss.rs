@@ -0,0 +1,552 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains a publicly verifiable secret sharing scheme (PVSS),
+//! where a dealer shares a secret among several parties using Shamir's secret sharing
+//! and encrypts each share under the [`DualRegev`] public key of its recipient.
+
+use crate::{
+    construction::{
+        hash::sha256::sha256,
+        pk_encryption::{DualRegev, PKEncryptionScheme},
+    },
+    error::CryptoError,
+};
+use qfall_math::{
+    integer::{MatZ, Z},
+    integer_mod_q::{MatZq, Modulus},
+    traits::{GetEntry, GetNumRows, Pow, SetEntry},
+};
+use serde::{Deserialize, Serialize};
+
+/// A dealing of [`PVSS`] consisting of the public commitments to the sharing
+/// polynomial and an encrypted share together with a proof for each party.
+///
+/// Attributes:
+/// - `commitments`: the Ajtai commitments `C_j = A * r_j + a_j * u` to the
+///     coefficients `a_j` of the sharing polynomial
+/// - `ciphers`: the bitwise encryption of the share `[f(i + 1) | ρ_i]` of party `i`
+/// - `proofs`: the proof of each party binding its ciphertexts to the commitments
+///     and its public key
+/// - `rho_bits`: the number of bits used to encode an entry of `ρ_i`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PVSSDealing {
+    pub commitments: Vec<MatZq>,
+    pub ciphers: Vec<Vec<MatZq>>,
+    pub proofs: Vec<String>,
+    pub rho_bits: usize,
+}
+
+/// This struct manages and stores the public parameters of a publicly verifiable
+/// secret sharing scheme for secrets in `Z_q`.
+///
+/// The dealer chooses a polynomial `f` of degree `threshold - 1` with `f(0) = secret`
+/// and commits to each coefficient `a_j` as `C_j = A * r_j + a_j * u`
+/// for binary `r_j`. The share of party `i` is `[f(i + 1) | ρ_i]^t`
+/// with `ρ_i = Σ_j (i + 1)^j * r_j`, which satisfies `A * ρ_i + f(i + 1) * u = Σ_j (i + 1)^j * C_j`.
+///
+/// The proof of a share is a Fiat-Shamir style hash binding the ciphertexts of
+/// party `i` to the commitments and the party's public key, which can be checked by
+/// everyone using [`PVSS::verify_share`].
+/// **WARNING:** This proof does not guarantee that the encrypted share is consistent
+/// with the commitments. This is checked by the recipient in [`PVSS::decrypt_share`].
+///
+/// Attributes:
+/// - `q`: specifies the modulus of the secret and the commitments
+/// - `m`: defines the length of the randomness of a commitment
+/// - `threshold`: the number of shares required to reconstruct the secret
+/// - `dual_regev`: the [`DualRegev`] instance used to encrypt the shares
+/// - `a`: the commitment matrix `A ∈ Z_q^{n x m}`
+/// - `u`: the commitment vector `u ∈ Z_q^n`
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::construction::pk_encryption::PKEncryptionScheme;
+/// use qfall_crypto::construction::pvss::PVSS;
+/// use qfall_math::integer::Z;
+///
+/// let pvss = PVSS::new_from_n(10, 2);
+/// let keys: Vec<_> = (0..3).map(|_| pvss.dual_regev.gen()).collect();
+/// let pks: Vec<_> = keys.iter().map(|(pk, _)| pk.clone()).collect();
+///
+/// let dealing = pvss.share(&Z::from(42), &pks);
+///
+/// let mut shares = vec![];
+/// for i in [0, 2] {
+///     assert!(pvss.verify_share(&dealing, i, &pks[i]));
+///     shares.push((i, pvss.decrypt_share(&dealing, i, &keys[i].1).unwrap()));
+/// }
+///
+/// assert_eq!(Z::from(42), pvss.reconstruct(&shares).unwrap());
+/// ```
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PVSS {
+    q: Modulus,
+    m: Z,
+    threshold: usize,
+    pub dual_regev: DualRegev,
+    pub a: MatZq,
+    pub u: MatZq,
+}
+
+impl PVSS {
+    /// Initializes a [`PVSS`] instance for security parameter `n`, where
+    /// `threshold` many shares are required to reconstruct the secret.
+    ///
+    /// The modulus `q` is a prime in `[n^5 / 2, n^5]`, `m = 2 * n * ⌈log_2(q)⌉`,
+    /// and the shares are encrypted with `DualRegev::new_from_n(n)`.
+    ///
+    /// Parameters:
+    /// - `n`: the security parameter
+    /// - `threshold`: the number of shares required to reconstruct the secret
+    ///
+    /// Returns a [`PVSS`] instance.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pvss::PVSS;
+    ///
+    /// let pvss = PVSS::new_from_n(10, 2);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `n < 10`.
+    /// - if `threshold < 1`.
+    pub fn new_from_n(n: impl Into<Z>, threshold: usize) -> Self {
+        let n: Z = n.into();
+        assert!(threshold >= 1, "The threshold has to be at least 1.");
+        let dual_regev = DualRegev::new_from_n(&n);
+
+        let upper_bound: Z = n.pow(5).unwrap();
+        let lower_bound = upper_bound.div_ceil(2);
+        let q = Modulus::from(Z::sample_prime_uniform(&lower_bound, &upper_bound).unwrap());
+
+        // m >= 2 * n * log(q) ensures that A * r is statistically close to uniform
+        let m = 2 * &n * Z::from(&q).log_ceil(2).unwrap();
+        let a = MatZq::sample_uniform(&n, &m, &q);
+        let u = MatZq::sample_uniform(&n, 1, &q);
+
+        Self {
+            q,
+            m,
+            threshold,
+            dual_regev,
+            a,
+            u,
+        }
+    }
+
+    /// Computes `Σ_j x^j * C_j`, i.e. the commitment to the share for `x`.
+    fn share_commitment(&self, commitments: &[MatZq], x: &Z) -> MatZq {
+        let mut result = MatZq::new(self.u.get_num_rows(), 1, &self.q);
+        let mut power = Z::ONE;
+        for commitment in commitments {
+            result = result + &power * commitment;
+            power = power * x;
+        }
+        result
+    }
+
+    /// Encrypts `share` bitwise under `pk`, where the first entry is encoded
+    /// with `⌈log_2(q)⌉` bits and all other entries with `rho_bits` bits.
+    fn encrypt_share(&self, pk: &MatZq, share: &MatZ, rho_bits: usize) -> Vec<MatZq> {
+        let q_bits = Z::from(&self.q).bits() as usize;
+
+        let mut ciphers = vec![];
+        for i in 0..share.get_num_rows() {
+            let entry: Z = share.get_entry(i, 0).unwrap();
+            let mut bits = entry.to_bits();
+            bits.resize(if i == 0 { q_bits } else { rho_bits }, false);
+
+            for bit in bits {
+                ciphers.push(self.dual_regev.enc(pk, bit as i64));
+            }
+        }
+        ciphers
+    }
+
+    /// Computes the proof binding the ciphertexts of party `i`
+    /// to the commitments and the party's public key.
+    fn proof(commitments: &[MatZq], i: usize, pk: &MatZq, ciphers: &[MatZq]) -> String {
+        let mut transcript = format!("{i} {pk}");
+        for commitment in commitments {
+            transcript.push_str(&format!(" {commitment}"));
+        }
+        for cipher in ciphers {
+            transcript.push_str(&format!(" {cipher}"));
+        }
+
+        sha256(&transcript)
+    }
+
+    /// Shares `secret` among the owners of `pks`, where the share of party `i`
+    /// is encrypted under `pks[i]`.
+    ///
+    /// Parameters:
+    /// - `secret`: the secret that is shared, which is reduced modulo `q`
+    /// - `pks`: the [`DualRegev`] public keys of all parties
+    ///
+    /// Returns a [`PVSSDealing`] containing the commitments and the encrypted shares.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pk_encryption::PKEncryptionScheme;
+    /// use qfall_crypto::construction::pvss::PVSS;
+    /// use qfall_math::integer::Z;
+    ///
+    /// let pvss = PVSS::new_from_n(10, 2);
+    /// let pks: Vec<_> = (0..3).map(|_| pvss.dual_regev.gen().0).collect();
+    ///
+    /// let dealing = pvss.share(&Z::from(42), &pks);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `pks` contains less than `threshold` public keys.
+    pub fn share(&self, secret: &Z, pks: &[MatZq]) -> PVSSDealing {
+        assert!(
+            pks.len() >= self.threshold,
+            "At least {} public keys are required, but only {} were provided.",
+            self.threshold,
+            pks.len()
+        );
+        let q = Z::from(&self.q);
+
+        // f(X) = secret + a_1 * X + ... + a_{t-1} * X^{t-1}
+        let mut coefficients = vec![secret.modulo(&q)];
+        for _ in 1..self.threshold {
+            coefficients.push(Z::sample_uniform(0, &q).unwrap());
+        }
+
+        let mut randomness = vec![];
+        let mut commitments = vec![];
+        for coefficient in &coefficients {
+            let r = MatZ::sample_uniform(&self.m, 1, 0, 2).unwrap();
+            commitments.push(&self.a * &r + coefficient * &self.u);
+            randomness.push(r);
+        }
+
+        // every entry of ρ_i is at most Σ_j (#parties)^j
+        let nr_parties = Z::from(pks.len() as u64);
+        let rho_bound: Z = (0..self.threshold as i64)
+            .map(|j| nr_parties.pow(j).unwrap())
+            .fold(Z::ZERO, |sum, power| sum + power);
+        let rho_bits = rho_bound.bits() as usize;
+
+        let mut ciphers = vec![];
+        let mut proofs = vec![];
+        for (i, pk) in pks.iter().enumerate() {
+            let x = Z::from(i as u64 + 1);
+
+            let mut value = Z::ZERO;
+            let mut rho = MatZ::new(&self.m, 1);
+            let mut power = Z::ONE;
+            for (coefficient, r) in coefficients.iter().zip(&randomness) {
+                value = value + &power * coefficient;
+                rho = rho + &power * r;
+                power = power * &x;
+            }
+
+            let mut share = MatZ::new(&(&self.m + Z::ONE), 1);
+            share.set_entry(0, 0, value.modulo(&q)).unwrap();
+            for j in 0..rho.get_num_rows() {
+                let entry: Z = rho.get_entry(j, 0).unwrap();
+                share.set_entry(j + 1, 0, entry).unwrap();
+            }
+
+            let party_ciphers = self.encrypt_share(pk, &share, rho_bits);
+            proofs.push(Self::proof(&commitments, i, pk, &party_ciphers));
+            ciphers.push(party_ciphers);
+        }
+
+        PVSSDealing {
+            commitments,
+            ciphers,
+            proofs,
+            rho_bits,
+        }
+    }
+
+    /// Publicly verifies that the encrypted share of party `i` in `dealing`
+    /// is bound to the commitments of `dealing` and the public key `pk`.
+    ///
+    /// Parameters:
+    /// - `dealing`: the dealing containing the encrypted share
+    /// - `i`: the index of the party
+    /// - `pk`: the public key of party `i`
+    ///
+    /// Returns `true` if the proof of party `i` is valid and `false` otherwise.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pk_encryption::PKEncryptionScheme;
+    /// use qfall_crypto::construction::pvss::PVSS;
+    /// use qfall_math::integer::Z;
+    ///
+    /// let pvss = PVSS::new_from_n(10, 2);
+    /// let pks: Vec<_> = (0..3).map(|_| pvss.dual_regev.gen().0).collect();
+    /// let dealing = pvss.share(&Z::from(42), &pks);
+    ///
+    /// assert!(pvss.verify_share(&dealing, 1, &pks[1]));
+    /// assert!(!pvss.verify_share(&dealing, 1, &pks[0]));
+    /// ```
+    pub fn verify_share(&self, dealing: &PVSSDealing, i: usize, pk: &MatZq) -> bool {
+        let q_bits = Z::from(&self.q).bits() as usize;
+        let m = usize::try_from(i64::try_from(&self.m).unwrap()).unwrap();
+
+        if dealing.commitments.len() != self.threshold
+            || i >= dealing.ciphers.len()
+            || dealing.ciphers.len() != dealing.proofs.len()
+            || dealing.ciphers[i].len() != q_bits + m * dealing.rho_bits
+        {
+            return false;
+        }
+
+        Self::proof(&dealing.commitments, i, pk, &dealing.ciphers[i]) == dealing.proofs[i]
+    }
+
+    /// Decrypts the share of party `i` and checks its consistency with the commitments.
+    ///
+    /// Parameters:
+    /// - `dealing`: the dealing containing the encrypted share
+    /// - `i`: the index of the party
+    /// - `sk`: the secret key of party `i`
+    ///
+    /// Returns the share `[f(i + 1) | ρ_i]^t` of party `i` or a [`CryptoError`]
+    /// if the share is not consistent with the commitments.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pk_encryption::PKEncryptionScheme;
+    /// use qfall_crypto::construction::pvss::PVSS;
+    /// use qfall_math::integer::Z;
+    ///
+    /// let pvss = PVSS::new_from_n(10, 2);
+    /// let (pk, sk) = pvss.dual_regev.gen();
+    /// let dealing = pvss.share(&Z::from(42), &[pk.clone(), pk]);
+    ///
+    /// let share = pvss.decrypt_share(&dealing, 0, &sk).unwrap();
+    /// ```
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] of type [`InvalidParameters`](CryptoError::InvalidParameters)
+    ///     if `dealing` contains no ciphertexts of the expected size for party `i`.
+    /// - Returns a [`CryptoError`] of type [`DecryptionFailed`](CryptoError::DecryptionFailed)
+    ///     if the decrypted share is not consistent with the commitments.
+    pub fn decrypt_share(
+        &self,
+        dealing: &PVSSDealing,
+        i: usize,
+        sk: &MatZ,
+    ) -> Result<MatZ, CryptoError> {
+        let q_bits = Z::from(&self.q).bits() as usize;
+        let m = i64::try_from(&self.m).unwrap();
+        let ciphers = match dealing.ciphers.get(i) {
+            Some(ciphers) if ciphers.len() == q_bits + m as usize * dealing.rho_bits => ciphers,
+            _ => {
+                return Err(CryptoError::InvalidParameters {
+                    message: format!(
+                        "The dealing contains no share of the expected size for party {i}."
+                    ),
+                })
+            }
+        };
+
+        let bits: Vec<bool> = ciphers
+            .iter()
+            .map(|cipher| self.dual_regev.dec(sk, cipher) == Z::ONE)
+            .collect();
+
+        let mut share = MatZ::new(m + 1, 1);
+        share
+            .set_entry(0, 0, Z::from_bits(&bits[..q_bits]))
+            .unwrap();
+        for (j, chunk) in bits[q_bits..].chunks(dealing.rho_bits).enumerate() {
+            share
+                .set_entry(j as i64 + 1, 0, Z::from_bits(chunk))
+                .unwrap();
+        }
+
+        // check A * ρ_i + f(i + 1) * u = Σ_j (i + 1)^j * C_j
+        let value: Z = share.get_entry(0, 0).unwrap();
+        let mut rho = MatZ::new(m, 1);
+        for j in 0..m {
+            let entry: Z = share.get_entry(j + 1, 0).unwrap();
+            rho.set_entry(j, 0, entry).unwrap();
+        }
+        let expected = self.share_commitment(&dealing.commitments, &Z::from(i as u64 + 1));
+        if &self.a * &rho + &value * &self.u != expected {
+            return Err(CryptoError::DecryptionFailed);
+        }
+
+        Ok(share)
+    }
+
+    /// Reconstructs the secret from `shares` using Lagrange interpolation.
+    ///
+    /// Parameters:
+    /// - `shares`: pairs of the index of a party and its decrypted share
+    ///
+    /// Returns the secret or a [`CryptoError`] if too few shares are provided.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pk_encryption::PKEncryptionScheme;
+    /// use qfall_crypto::construction::pvss::PVSS;
+    /// use qfall_math::integer::Z;
+    ///
+    /// let pvss = PVSS::new_from_n(10, 2);
+    /// let (pk, sk) = pvss.dual_regev.gen();
+    /// let dealing = pvss.share(&Z::from(42), &[pk.clone(), pk]);
+    ///
+    /// let shares = vec![
+    ///     (0, pvss.decrypt_share(&dealing, 0, &sk).unwrap()),
+    ///     (1, pvss.decrypt_share(&dealing, 1, &sk).unwrap()),
+    /// ];
+    ///
+    /// assert_eq!(Z::from(42), pvss.reconstruct(&shares).unwrap());
+    /// ```
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] of type [`InvalidParameters`](CryptoError::InvalidParameters)
+    ///     if less than `threshold` shares of distinct parties are provided.
+    pub fn reconstruct(&self, shares: &[(usize, MatZ)]) -> Result<Z, CryptoError> {
+        let mut points: Vec<(Z, Z)> = vec![];
+        for (i, share) in shares {
+            let x = Z::from(*i as u64 + 1);
+            if points.iter().all(|(other, _)| other != &x) {
+                points.push((x, share.get_entry(0, 0)?));
+            }
+        }
+        if points.len() < self.threshold {
+            return Err(CryptoError::InvalidParameters {
+                message: format!(
+                "At least {} shares of distinct parties are required, but only {} were provided.",
+                self.threshold,
+                points.len()
+            ),
+            });
+        }
+        points.truncate(self.threshold);
+
+        // f(0) = Σ_i y_i * Π_{j != i} x_j / (x_j - x_i)
+        let q = Z::from(&self.q);
+        let mut secret = Z::ZERO;
+        for (i, (x_i, y_i)) in points.iter().enumerate() {
+            let mut numerator = Z::ONE;
+            let mut denominator = Z::ONE;
+            for (j, (x_j, _)) in points.iter().enumerate() {
+                if i != j {
+                    numerator = (numerator * x_j).modulo(&q);
+                    denominator = (denominator * (x_j - x_i)).modulo(&q);
+                }
+            }
+            let lagrange = numerator * inverse_mod_prime(&denominator, &q);
+            secret = (secret + y_i * lagrange).modulo(&q);
+        }
+
+        Ok(secret)
+    }
+}
+
+/// Computes the inverse of `value` modulo the prime `q` as `value^(q - 2) mod q`.
+fn inverse_mod_prime(value: &Z, q: &Z) -> Z {
+    let exponent = q - Z::from(2);
+
+    let mut result = Z::ONE;
+    for bit in exponent.to_bits().iter().rev() {
+        result = (&result * &result).modulo(q);
+        if *bit {
+            result = (result * value).modulo(q);
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod test_pvss {
+    use super::{PVSSDealing, PVSS};
+    use crate::construction::pk_encryption::PKEncryptionScheme;
+    use qfall_math::{
+        integer::{MatZ, Z},
+        integer_mod_q::MatZq,
+        traits::{GetEntry, SetEntry},
+    };
+
+    /// Generates a [`PVSS`] instance with threshold 2, key pairs of 3 parties,
+    /// and a dealing of `secret`.
+    fn setup(secret: &Z) -> (PVSS, Vec<(MatZq, MatZ)>, PVSSDealing) {
+        let pvss = PVSS::new_from_n(10, 2);
+        let keys: Vec<_> = (0..3).map(|_| pvss.dual_regev.gen()).collect();
+        let pks: Vec<_> = keys.iter().map(|(pk, _)| pk.clone()).collect();
+
+        let dealing = pvss.share(secret, &pks);
+        (pvss, keys, dealing)
+    }
+
+    /// Ensures that every pair of 2 out of 3 shares reconstructs the secret.
+    #[test]
+    fn two_of_three() {
+        let secret = Z::from(1234);
+        let (pvss, keys, dealing) = setup(&secret);
+
+        let shares: Vec<_> = keys
+            .iter()
+            .enumerate()
+            .map(|(i, (pk, sk))| {
+                assert!(pvss.verify_share(&dealing, i, pk));
+                (i, pvss.decrypt_share(&dealing, i, sk).unwrap())
+            })
+            .collect();
+
+        for (i, j) in [(0, 1), (0, 2), (1, 2)] {
+            let subset = [shares[i].clone(), shares[j].clone()];
+            assert_eq!(secret, pvss.reconstruct(&subset).unwrap());
+        }
+        assert_eq!(secret, pvss.reconstruct(&shares).unwrap());
+    }
+
+    /// Ensures that a single share is not sufficient for reconstruction.
+    #[test]
+    fn too_few_shares() {
+        let (pvss, keys, dealing) = setup(&Z::from(1234));
+        let share = pvss.decrypt_share(&dealing, 0, &keys[0].1).unwrap();
+
+        assert!(pvss.reconstruct(&[(0, share.clone())]).is_err());
+        assert!(pvss.reconstruct(&[(0, share.clone()), (0, share)]).is_err());
+    }
+
+    /// Ensures that replaced ciphertexts and wrong public keys are rejected by the
+    /// public verification.
+    #[test]
+    fn tampered_dealing_rejected() {
+        let (pvss, keys, mut dealing) = setup(&Z::from(1234));
+
+        assert!(!pvss.verify_share(&dealing, 1, &keys[0].0));
+        assert!(!pvss.verify_share(&dealing, 3, &keys[0].0));
+
+        dealing.ciphers[1] = dealing.ciphers[2].clone();
+        assert!(!pvss.verify_share(&dealing, 1, &keys[1].0));
+    }
+
+    /// Ensures that a share inconsistent with the commitments is rejected,
+    /// even if its proof is valid.
+    #[test]
+    fn inconsistent_share_rejected() {
+        let (pvss, keys, mut dealing) = setup(&Z::from(1234));
+        let (pk, sk) = &keys[1];
+
+        let mut share = pvss.decrypt_share(&dealing, 1, sk).unwrap();
+        let value: Z = share.get_entry(0, 0).unwrap();
+        share.set_entry(0, 0, value + 1).unwrap();
+        dealing.ciphers[1] = pvss.encrypt_share(pk, &share, dealing.rho_bits);
+        dealing.proofs[1] = PVSS::proof(&dealing.commitments, 1, pk, &dealing.ciphers[1]);
+
+        assert!(pvss.verify_share(&dealing, 1, pk));
+        assert!(pvss.decrypt_share(&dealing, 1, sk).is_err());
+    }
+}
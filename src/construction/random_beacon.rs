@@ -0,0 +1,337 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains a random beacon protocol between several parties,
+//! where each party commits to a random value using an Ajtai commitment
+//! before any value is revealed.
+//!
+//! The protocol consists of the following steps:
+//! 1. Each party chooses `32` random bytes, commits to them and broadcasts the commitment.
+//! 2. After all commitments are received, each party broadcasts its opening.
+//! 3. All parties check the openings and output the XOR of all committed values.
+//!
+//! As long as one party chooses its value uniformly at random,
+//! the output is uniform, since the commitments are hiding and binding.
+
+use crate::error::CryptoError;
+use qfall_math::{
+    integer::{MatZ, Z},
+    integer_mod_q::{MatZq, Modulus},
+    traits::{Concatenate, GetEntry, GetNumRows, SetEntry},
+};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// The number of bits of the value committed to by each party.
+const VALUE_BITS: i64 = 256;
+
+/// An Ajtai commitment `c = A * [x | r]^t` to the bits `x` of `32` bytes.
+///
+/// Attributes:
+/// - `c`: the committed value
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Commitment {
+    pub c: MatZq,
+}
+
+/// The opening of a [`Commitment`].
+///
+/// Attributes:
+/// - `value`: the committed bytes
+/// - `r`: the binary randomness of the commitment
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Opening {
+    pub value: [u8; 32],
+    pub r: MatZ,
+}
+
+/// This struct manages and stores the public parameters of a random beacon
+/// protocol based on Ajtai commitments.
+///
+/// The `32` bytes `x` of a party are committed to as `c = A * [x | r]^t`, where
+/// `x` is interpreted as a vector of `256` bits, `A ∈ Z_q^{n x (256 + m)}` is uniform,
+/// and `r ∈ {0, 1}^m` is a uniform binary vector.
+/// The commitment is statistically hiding due to the leftover hash lemma and
+/// computationally binding, as two different openings yield a short solution of SIS.
+///
+/// Attributes:
+/// - `n_parties`: the number of participating parties
+/// - `m`: defines the length of the randomness of a commitment
+/// - `q`: specifies the modulus
+/// - `a`: the matrix `A` used as common reference string of all parties
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::construction::random_beacon::RandomBeacon;
+///
+/// let beacon = RandomBeacon::new_from_n(3, 8);
+///
+/// // every party commits to its local randomness
+/// let commits: Vec<_> = (0..3)
+///     .map(|_| beacon.commit(&rand::random::<[u8; 32]>()))
+///     .collect();
+///
+/// // after all commitments are exchanged, the openings are revealed and combined
+/// let output = beacon.combine(&commits).unwrap();
+/// ```
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RandomBeacon {
+    n_parties: usize,
+    m: Z,
+    q: Modulus,
+    pub a: MatZq,
+}
+
+impl RandomBeacon {
+    /// Initializes a [`RandomBeacon`] instance for `n_parties` parties and
+    /// security parameter `n_lattice` by choosing a prime `q ∈ [n^3, 2 * n^3]`,
+    /// `m = 2 * n * ⌈log_2(q)⌉`, and a uniform matrix `A ∈ Z_q^{n x (256 + m)}`.
+    ///
+    /// Parameters:
+    /// - `n_parties`: the number of participating parties
+    /// - `n_lattice`: the security parameter of the commitment scheme
+    ///
+    /// Returns a [`RandomBeacon`] instance.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::random_beacon::RandomBeacon;
+    ///
+    /// let beacon = RandomBeacon::new_from_n(3, 8);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `n_parties < 1`.
+    /// - if `n_lattice < 2`.
+    pub fn new_from_n(n_parties: usize, n_lattice: usize) -> Self {
+        if n_parties < 1 {
+            panic!("At least one party has to participate");
+        }
+        if n_lattice < 2 {
+            panic!("Security parameter n has to be larger than 1");
+        }
+
+        let n = Z::from(n_lattice as u64);
+        let lower_bound: Z = &n * &n * &n;
+        let upper_bound: Z = 2 * &lower_bound;
+        let q = Modulus::from(Z::sample_prime_uniform(&lower_bound, &upper_bound).unwrap());
+
+        // m >= 2 * n * log(q) ensures that A * r is statistically close to uniform
+        let m = 2 * &n * Z::from(&q).log_ceil(2).unwrap();
+        let a = MatZq::sample_uniform(&n, &(&m + Z::from(VALUE_BITS)), &q);
+
+        Self { n_parties, m, q, a }
+    }
+
+    /// Computes the commitment `A * [value | r]^t`.
+    fn compute_commitment(&self, value: &[u8; 32], r: &MatZ) -> Commitment {
+        let mut bits = MatZ::new(VALUE_BITS, 1);
+        for (i, byte) in value.iter().enumerate() {
+            for j in 0..8_usize {
+                bits.set_entry((8 * i + j) as i64, 0, i64::from((byte >> j) & 1))
+                    .unwrap();
+            }
+        }
+        let vector = bits.concat_vertical(r).unwrap();
+
+        Commitment {
+            c: &self.a * &MatZq::from((&vector, &self.q)),
+        }
+    }
+
+    /// Commits to `local_randomness` using uniform binary randomness.
+    ///
+    /// Parameters:
+    /// - `local_randomness`: the bytes chosen by the party
+    ///
+    /// Returns the [`Commitment`] that is broadcast in the first step and
+    /// the [`Opening`], which is kept secret until all commitments are received.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::random_beacon::RandomBeacon;
+    /// let beacon = RandomBeacon::new_from_n(3, 8);
+    ///
+    /// let (commitment, opening) = beacon.commit(&[0; 32]);
+    /// ```
+    pub fn commit(&self, local_randomness: &[u8; 32]) -> (Commitment, Opening) {
+        let mut rng = rand::thread_rng();
+        let m = i64::try_from(&self.m).unwrap();
+        let mut r = MatZ::new(m, 1);
+        for i in 0..m {
+            r.set_entry(i, 0, rng.gen_range(0..=1_i64)).unwrap();
+        }
+
+        let commitment = self.compute_commitment(local_randomness, &r);
+        let opening = Opening {
+            value: *local_randomness,
+            r,
+        };
+
+        (commitment, opening)
+    }
+
+    /// Checks whether `open` is a valid opening of `c`, i.e. whether
+    /// its randomness is binary and it is mapped to `c`.
+    ///
+    /// Parameters:
+    /// - `c`: the commitment
+    /// - `open`: the revealed opening
+    ///
+    /// Returns `true` if `open` is a valid opening of `c` and `false` otherwise.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::random_beacon::RandomBeacon;
+    /// let beacon = RandomBeacon::new_from_n(3, 8);
+    /// let (commitment, opening) = beacon.commit(&[0; 32]);
+    ///
+    /// assert!(beacon.verify_commit(&commitment, &opening));
+    /// ```
+    pub fn verify_commit(&self, c: &Commitment, open: &Opening) -> bool {
+        let r = &open.r;
+        if !r.is_column_vector() || Z::from(r.get_num_rows()) != self.m {
+            return false;
+        }
+        for i in 0..r.get_num_rows() {
+            let entry: Z = r.get_entry(i, 0).unwrap();
+            if entry != Z::ZERO && entry != Z::ONE {
+                return false;
+            }
+        }
+
+        &self.compute_commitment(&open.value, r) == c
+    }
+
+    /// Verifies the openings of all parties and combines their values.
+    ///
+    /// Parameters:
+    /// - `opens`: the commitment and opening of every party
+    ///
+    /// Returns the XOR of all committed values or a [`CryptoError`]
+    /// if any opening is invalid.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::random_beacon::RandomBeacon;
+    /// let beacon = RandomBeacon::new_from_n(2, 8);
+    /// let opens = vec![beacon.commit(&[1; 32]), beacon.commit(&[2; 32])];
+    ///
+    /// assert_eq!([3; 32], beacon.combine(&opens).unwrap());
+    /// ```
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] of type [`InvalidParameters`](CryptoError::InvalidParameters)
+    ///     if the number of openings does not match the number of parties
+    ///     or an opening does not match its commitment.
+    pub fn combine(&self, opens: &[(Commitment, Opening)]) -> Result<[u8; 32], CryptoError> {
+        if opens.len() != self.n_parties {
+            return Err(CryptoError::InvalidParameters {
+                message: format!(
+                    "Expected {} openings, but received {}.",
+                    self.n_parties,
+                    opens.len()
+                ),
+            });
+        }
+
+        let mut output = [0u8; 32];
+        for (i, (commitment, opening)) in opens.iter().enumerate() {
+            if !self.verify_commit(commitment, opening) {
+                return Err(CryptoError::InvalidParameters {
+                    message: format!("The opening of party {i} does not match its commitment."),
+                });
+            }
+            for (out, byte) in output.iter_mut().zip(opening.value) {
+                *out ^= byte;
+            }
+        }
+
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod test_random_beacon {
+    use super::RandomBeacon;
+    use qfall_math::{integer::Z, traits::SetEntry};
+
+    /// Ensures that the output is the XOR of all committed values.
+    #[test]
+    fn correctness() {
+        let beacon = RandomBeacon::new_from_n(3, 8);
+        let values: Vec<[u8; 32]> = (0..3).map(|_| rand::random()).collect();
+
+        let opens: Vec<_> = values.iter().map(|value| beacon.commit(value)).collect();
+
+        let mut expected = [0u8; 32];
+        for value in &values {
+            for (out, byte) in expected.iter_mut().zip(value) {
+                *out ^= byte;
+            }
+        }
+        assert_eq!(expected, beacon.combine(&opens).unwrap());
+    }
+
+    /// Ensures that the output changes whenever a single party changes its input.
+    #[test]
+    fn output_changes() {
+        let beacon = RandomBeacon::new_from_n(3, 8);
+        let values: Vec<[u8; 32]> = (0..3).map(|_| rand::random()).collect();
+        let opens: Vec<_> = values.iter().map(|value| beacon.commit(value)).collect();
+        let output = beacon.combine(&opens).unwrap();
+
+        for i in 0..3 {
+            let mut changed_value = values[i];
+            changed_value[0] ^= 1;
+            let mut changed_opens = opens.clone();
+            changed_opens[i] = beacon.commit(&changed_value);
+
+            assert_ne!(output, beacon.combine(&changed_opens).unwrap());
+        }
+    }
+
+    /// Ensures that the bits of the output are uniform over 100 executions
+    /// if only one party chooses its input uniformly at random.
+    #[test]
+    fn uniform_output() {
+        let beacon = RandomBeacon::new_from_n(2, 8);
+
+        let mut nr_ones = 0;
+        for _ in 0..100 {
+            let opens = vec![beacon.commit(&[0xff; 32]), beacon.commit(&rand::random())];
+            let output = beacon.combine(&opens).unwrap();
+            nr_ones += output.iter().map(|byte| byte.count_ones()).sum::<u32>();
+        }
+
+        // 25_600 bits with standard deviation 80, i.e. this test fails with
+        // probability smaller than 2^{-40}
+        assert!((12_000..=13_600).contains(&nr_ones));
+    }
+
+    /// Ensures that invalid openings and a wrong number of openings are rejected.
+    #[test]
+    fn invalid_openings() {
+        let beacon = RandomBeacon::new_from_n(2, 8);
+        let opens = vec![beacon.commit(&[1; 32]), beacon.commit(&[2; 32])];
+
+        let mut changed_value = opens.clone();
+        changed_value[1].1.value = [3; 32];
+        let mut changed_randomness = opens.clone();
+        changed_randomness[0]
+            .1
+            .r
+            .set_entry(0, 0, Z::from(2))
+            .unwrap();
+
+        assert!(!beacon.verify_commit(&changed_value[1].0, &changed_value[1].1));
+        assert!(beacon.combine(&changed_value).is_err());
+        assert!(beacon.combine(&changed_randomness).is_err());
+        assert!(beacon.combine(&opens[..1]).is_err());
+    }
+}
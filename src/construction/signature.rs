@@ -15,12 +15,18 @@
 //!     Proceedings of the fortieth annual ACM symposium on Theory of computing. 2008.
 //!     <https://doi.org/10.1145/1374376.1374407>
 
+mod aggregate;
+mod blind_fdh;
 mod fdh;
 mod pfdh;
 
-pub use fdh::FDH;
+pub use aggregate::AggregateSignature;
+pub use blind_fdh::BlindFdh;
+pub use fdh::{StatelessFdh, FDH};
 pub use pfdh::PFDH;
 
+use crate::error::CryptoError;
+
 /// This trait should be implemented by every signature scheme.
 /// It captures the essential functionalities each signature scheme has to support.
 ///
@@ -42,11 +48,65 @@ pub trait SignatureScheme {
 
     /// Signs a message using the secret key (and potentially the public key).
     ///
-    /// Returns the resulting signature.
-    fn sign(&mut self, m: String, sk: &Self::SecretKey, pk: &Self::PublicKey) -> Self::Signature;
+    /// Returns the resulting signature or a [`CryptoError`]
+    /// if no valid signature could be computed.
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] of type [`VerificationFailed`](CryptoError::VerificationFailed)
+    ///     if the computed signature is invalid under `pk`, e.g. as `sk` does not belong to `pk`.
+    fn sign(
+        &mut self,
+        m: String,
+        sk: &Self::SecretKey,
+        pk: &Self::PublicKey,
+    ) -> Result<Self::Signature, CryptoError>;
 
     /// Verifies that a signature is valid for a message by using the public key.
     ///
     /// Returns the result of the verification as a boolean.
     fn vfy(&self, m: String, sigma: &Self::Signature, pk: &Self::PublicKey) -> bool;
 }
+
+/// This trait should be implemented by every blind signature scheme.
+/// It captures the functionalities to sign a message without learning it,
+/// i.e. the user blinds the message, the signer signs the blinded message and
+/// the user unblinds the signature, which is then a signature of the message
+/// of the underlying [`SignatureScheme`].
+pub trait BlindSignatureScheme {
+    /// The type of the secret key.
+    type SecretKey;
+    /// The type of the public key.
+    type PublicKey;
+    /// The type of the blinded message sent to the signer.
+    type BlindedMessage;
+    /// The type of the secret blinding factor kept by the user.
+    type BlindingFactor;
+    /// The type of the signature of a blinded message.
+    type BlindedSignature;
+    /// The type of the unblinded signature.
+    type Signature;
+
+    /// Blinds a message `m` for the signer with public key `pk`.
+    ///
+    /// Returns the blinded message and the blinding factor.
+    fn blind(&self, m: &str, pk: &Self::PublicKey) -> (Self::BlindedMessage, Self::BlindingFactor);
+
+    /// Signs a blinded message using the secret key (and potentially the public key).
+    ///
+    /// Returns the signature of the blinded message.
+    fn sign_blind(
+        &self,
+        blinded: &Self::BlindedMessage,
+        sk: &Self::SecretKey,
+        pk: &Self::PublicKey,
+    ) -> Self::BlindedSignature;
+
+    /// Unblinds the signature of a blinded message using the blinding factor.
+    ///
+    /// Returns the signature of the original message.
+    fn unblind(
+        &self,
+        blinded_sig: &Self::BlindedSignature,
+        factor: &Self::BlindingFactor,
+    ) -> Self::Signature;
+}
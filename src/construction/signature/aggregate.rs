@@ -0,0 +1,321 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains the aggregation of [`FDH`](super::FDH) signatures
+//! generated with [`PSFGPV`] into a single signature.
+
+use crate::{
+    construction::hash::{sha256::HashMatZq, HashInto},
+    error::CryptoError,
+    primitive::psf::{PSF, PSFGPV},
+    sample::g_trapdoor::gadget_parameters::GadgetParameters,
+};
+use qfall_math::{
+    integer::{MatZ, Z},
+    integer_mod_q::{MatZq, Modulus},
+    rational::Q,
+    traits::{Concatenate, GetNumRows},
+};
+use serde::{Deserialize, Serialize};
+
+/// This struct manages the public parameters to aggregate signatures of
+/// [`FDH::init_gpv`](super::FDH::init_gpv) with the same parameters.
+///
+/// Signatures `σ_i` of `m_i` under the public keys `A_i` satisfy `A_i * σ_i = H(m_i)`.
+/// The signatures are aggregated into `σ = [σ_1^t | ... | σ_l^t]^t`, which
+/// satisfies `[A_1 | ... | A_l] * σ = H(m_1) + ... + H(m_l)`.
+/// In contrast to the sum `σ_1 + ... + σ_l`, this works for independent public
+/// keys, as the products `A_i * σ_j` for `i != j` do not cancel out.
+///
+/// **WARNING:** The summed equation alone would allow a signer, who knows a
+/// trapdoor of `A_j`, to compensate an arbitrary `σ_i` of another signer.
+/// Hence, [`AggregateSignature::verify_aggregate`] checks every block of `σ`
+/// individually, i.e. the aggregate signature does not compress the signatures.
+///
+/// Attributes:
+/// - `psf`: The [`PSFGPV`] used by the aggregated signatures
+/// - `hash`: The hash function used by the aggregated signatures
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::construction::signature::{AggregateSignature, SignatureScheme, FDH};
+///
+/// let mut fdh = FDH::init_gpv(4, 113, 17);
+/// let aggregate_signature = AggregateSignature::init_gpv(4, 113, 17);
+///
+/// let (pk_1, sk_1) = fdh.gen();
+/// let (pk_2, sk_2) = fdh.gen();
+/// let m_1 = String::from("Hello");
+/// let m_2 = String::from("World!");
+/// let sigma_1 = fdh.sign(m_1.clone(), &sk_1, &pk_1).unwrap();
+/// let sigma_2 = fdh.sign(m_2.clone(), &sk_2, &pk_2).unwrap();
+///
+/// let sigma = aggregate_signature
+///     .aggregate(&[(m_1.clone(), &pk_1, &sigma_1), (m_2.clone(), &pk_2, &sigma_2)])
+///     .unwrap();
+///
+/// assert!(aggregate_signature.verify_aggregate(&[m_1, m_2], &[pk_1, pk_2], &sigma));
+/// ```
+#[derive(Serialize, Deserialize)]
+pub struct AggregateSignature {
+    pub psf: PSFGPV,
+    pub hash: HashMatZq,
+}
+
+impl AggregateSignature {
+    /// Initializes an [`AggregateSignature`] for signatures of
+    /// [`FDH::init_gpv`](super::FDH::init_gpv) with the same parameters.
+    ///
+    /// Parameters:
+    /// - `n`: The security parameter
+    /// - `q`: The modulus used for the G-Trapdoors
+    /// - `s`: The Gaussian parameter with which is sampled
+    ///
+    /// Returns an [`AggregateSignature`] instance.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::signature::AggregateSignature;
+    ///
+    /// let aggregate_signature = AggregateSignature::init_gpv(4, 113, 17);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `q <= 1`.
+    pub fn init_gpv(n: impl Into<Z>, q: impl Into<Modulus>, s: impl Into<Q>) -> Self {
+        let n = n.into();
+        let n_i64 = i64::try_from(&n).unwrap();
+        let q = q.into();
+        let psf = PSFGPV {
+            gp: GadgetParameters::init_default(&n, &q),
+            s: s.into(),
+            cdt: None,
+            cdt_cache: Default::default(),
+        };
+        Self {
+            psf,
+            hash: HashMatZq {
+                modulus: q,
+                rows: n_i64,
+                cols: 1,
+            },
+        }
+    }
+
+    /// Returns the number of rows `m` of a single signature.
+    fn signature_length(&self) -> i64 {
+        i64::try_from(&(&self.psf.gp.n * &self.psf.gp.k + &self.psf.gp.m_bar)).unwrap()
+    }
+
+    /// Checks that `sigma` is a valid signature of `m` under `pk`.
+    fn vfy(&self, m: &str, sigma: &MatZ, pk: &MatZq) -> bool {
+        self.psf.check_domain(sigma) && self.psf.f_a(pk, sigma) == self.hash.hash(m)
+    }
+
+    /// Aggregates the signatures `σ_i` in `sigs` into `σ = [σ_1^t | ... | σ_l^t]^t`.
+    /// Every signature is verified before it is aggregated.
+    ///
+    /// Parameters:
+    /// - `sigs`: the list of messages, public keys and signatures `(m_i, A_i, σ_i)`
+    ///
+    /// Returns the aggregate signature `σ` or a [`CryptoError`]
+    /// if one of the signatures is not valid.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::signature::{AggregateSignature, SignatureScheme, FDH};
+    ///
+    /// let mut fdh = FDH::init_gpv(4, 113, 17);
+    /// let aggregate_signature = AggregateSignature::init_gpv(4, 113, 17);
+    /// let (pk, sk) = fdh.gen();
+    /// let m = String::from("Hello World!");
+    /// let sigma = fdh.sign(m.clone(), &sk, &pk).unwrap();
+    ///
+    /// let aggregate = aggregate_signature.aggregate(&[(m, &pk, &sigma)]).unwrap();
+    /// ```
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] of type [`InvalidParameters`](CryptoError::InvalidParameters)
+    ///     if `sigs` is empty.
+    /// - Returns a [`CryptoError`] of type [`VerificationFailed`](CryptoError::VerificationFailed)
+    ///     if one of the signatures is not valid for its message and public key.
+    pub fn aggregate(&self, sigs: &[(String, &MatZq, &MatZ)]) -> Result<MatZ, CryptoError> {
+        let mut aggregate: Option<MatZ> = None;
+        for (m, pk, sigma) in sigs {
+            if !self.vfy(m, sigma, pk) {
+                return Err(CryptoError::VerificationFailed);
+            }
+            aggregate = Some(match aggregate {
+                Some(aggregate) => aggregate.concat_vertical(*sigma).unwrap(),
+                None => (*sigma).clone(),
+            });
+        }
+
+        aggregate.ok_or_else(|| CryptoError::InvalidParameters {
+            message: String::from("There are no signatures to aggregate."),
+        })
+    }
+
+    /// Verifies the aggregate signature `σ = [σ_1^t | ... | σ_l^t]^t` of the messages
+    /// `m_i` under the public keys `A_i` by checking that every `σ_i` is in the
+    /// domain and satisfies `A_i * σ_i = H(m_i)`.
+    /// Hence, `[A_1 | ... | A_l] * σ = H(m_1) + ... + H(m_l)`.
+    ///
+    /// Parameters:
+    /// - `messages`: the signed messages `m_i`
+    /// - `pks`: the public keys `A_i` in the same order as `messages`
+    /// - `agg_sig`: the aggregate signature `σ`
+    ///
+    /// Returns `true` if `agg_sig` is a valid aggregate signature of `messages`
+    /// under `pks` and `false` otherwise.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::signature::{AggregateSignature, SignatureScheme, FDH};
+    ///
+    /// let mut fdh = FDH::init_gpv(4, 113, 17);
+    /// let aggregate_signature = AggregateSignature::init_gpv(4, 113, 17);
+    /// let (pk, sk) = fdh.gen();
+    /// let m = String::from("Hello World!");
+    /// let sigma = fdh.sign(m.clone(), &sk, &pk).unwrap();
+    /// let aggregate = aggregate_signature.aggregate(&[(m.clone(), &pk, &sigma)]).unwrap();
+    ///
+    /// assert!(aggregate_signature.verify_aggregate(&[m], &[pk], &aggregate));
+    /// ```
+    pub fn verify_aggregate(&self, messages: &[String], pks: &[MatZq], agg_sig: &MatZ) -> bool {
+        let length = self.signature_length();
+        if messages.is_empty()
+            || messages.len() != pks.len()
+            || !agg_sig.is_column_vector()
+            || agg_sig.get_num_rows() != length * messages.len() as i64
+        {
+            return false;
+        }
+
+        messages.iter().zip(pks).enumerate().all(|(i, (m, pk))| {
+            let start = i as i64 * length;
+            let sigma = agg_sig
+                .get_submatrix(start, start + length - 1, 0, 0)
+                .unwrap();
+            self.vfy(m, &sigma, pk)
+        })
+    }
+}
+
+#[cfg(test)]
+mod test_aggregate_signature {
+    use super::AggregateSignature;
+    use crate::construction::{
+        hash::HashInto,
+        signature::{SignatureScheme, FDH},
+    };
+    use qfall_math::{
+        integer::{MatZ, Z},
+        integer_mod_q::MatZq,
+        traits::{Concatenate, GetEntry, SetEntry},
+    };
+
+    /// Generates `l` key pairs and signs the message `i` with the `i`-th key.
+    fn sign_with(l: usize) -> (Vec<String>, Vec<MatZq>, Vec<MatZ>) {
+        let mut fdh = FDH::init_gpv(4, 113, 17);
+        let mut messages = Vec::new();
+        let mut pks = Vec::new();
+        let mut sigmas = Vec::new();
+        for i in 0..l {
+            let (pk, sk) = fdh.gen();
+            let m = format!("Hello World! {i}");
+            sigmas.push(fdh.sign(m.clone(), &sk, &pk).unwrap());
+            messages.push(m);
+            pks.push(pk);
+        }
+        (messages, pks, sigmas)
+    }
+
+    /// Aggregates the signatures generated by [`sign_with`].
+    fn aggregate(
+        aggregate_signature: &AggregateSignature,
+        messages: &[String],
+        pks: &[MatZq],
+        sigmas: &[MatZ],
+    ) -> MatZ {
+        let sigs: Vec<(String, &MatZq, &MatZ)> = messages
+            .iter()
+            .zip(pks)
+            .zip(sigmas)
+            .map(|((m, pk), sigma)| (m.clone(), pk, sigma))
+            .collect();
+        aggregate_signature.aggregate(&sigs).unwrap()
+    }
+
+    /// Ensures that the aggregate of two signatures under different keys is valid.
+    #[test]
+    fn two_party() {
+        let aggregate_signature = AggregateSignature::init_gpv(4, 113, 17);
+        let (messages, pks, sigmas) = sign_with(2);
+
+        let sigma = aggregate(&aggregate_signature, &messages, &pks, &sigmas);
+
+        assert!(aggregate_signature.verify_aggregate(&messages, &pks, &sigma));
+    }
+
+    /// Ensures that the aggregate of three signatures under different keys is valid
+    /// and satisfies the summed equation.
+    #[test]
+    fn three_party() {
+        let aggregate_signature = AggregateSignature::init_gpv(4, 113, 17);
+        let (messages, pks, sigmas) = sign_with(3);
+
+        let sigma = aggregate(&aggregate_signature, &messages, &pks, &sigmas);
+
+        assert!(aggregate_signature.verify_aggregate(&messages, &pks, &sigma));
+        let mut sum = MatZq::new(4, 1, 113);
+        let mut joint_pk = pks[0].clone();
+        for (i, (m, pk)) in messages.iter().zip(&pks).enumerate() {
+            sum = sum + aggregate_signature.hash.hash(m);
+            if i > 0 {
+                joint_pk = joint_pk.concat_horizontal(pk).unwrap();
+            }
+        }
+        assert_eq!(sum, joint_pk * sigma);
+    }
+
+    /// Ensures that the aggregate signature is not valid for swapped messages,
+    /// swapped keys, a missing message or a modified signature.
+    #[test]
+    fn invalid_aggregate() {
+        let aggregate_signature = AggregateSignature::init_gpv(4, 113, 17);
+        let (messages, pks, sigmas) = sign_with(2);
+        let sigma = aggregate(&aggregate_signature, &messages, &pks, &sigmas);
+
+        let swapped_messages = [messages[1].clone(), messages[0].clone()];
+        let swapped_pks = [pks[1].clone(), pks[0].clone()];
+        let mut modified_sigma = sigma.clone();
+        let entry: Z = modified_sigma.get_entry(0, 0).unwrap();
+        modified_sigma.set_entry(0, 0, entry + 1).unwrap();
+
+        assert!(!aggregate_signature.verify_aggregate(&swapped_messages, &pks, &sigma));
+        assert!(!aggregate_signature.verify_aggregate(&messages, &swapped_pks, &sigma));
+        assert!(!aggregate_signature.verify_aggregate(&messages[..1], &pks[..1], &sigma));
+        assert!(!aggregate_signature.verify_aggregate(&messages, &pks, &modified_sigma));
+    }
+
+    /// Ensures that invalid signatures and empty lists are not aggregated.
+    #[test]
+    fn invalid_signatures_not_aggregated() {
+        let aggregate_signature = AggregateSignature::init_gpv(4, 113, 17);
+        let (messages, pks, sigmas) = sign_with(2);
+
+        assert!(aggregate_signature
+            .aggregate(&[
+                (messages[0].clone(), &pks[0], &sigmas[0]),
+                (messages[0].clone(), &pks[1], &sigmas[1]),
+            ])
+            .is_err());
+        assert!(aggregate_signature.aggregate(&[]).is_err());
+    }
+}
@@ -0,0 +1,234 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains a blind signature scheme on top of the
+//! [`FDH`] signature scheme with the [`PSFGPV`].
+
+use super::{BlindSignatureScheme, FDH};
+use crate::{
+    construction::hash::{sha256::HashMatZq, HashInto},
+    primitive::psf::{PSF, PSFGPV},
+};
+use qfall_math::{
+    integer::{MatZ, Z},
+    integer_mod_q::{MatZq, Modulus},
+    rational::{MatQ, Q},
+};
+use serde::Serialize;
+
+/// This struct manages and stores the public parameters of a blind signature
+/// scheme, whose unblinded signatures are valid signatures of [`FDH::init_gpv`].
+///
+/// A message `m` is blinded by sampling a short `e` from the discrete Gaussian
+/// distribution of the [`PSFGPV`] and computing `u' = H(m) + A * e`.
+/// The signer samples a short preimage `σ'` of `u'` and the user unblinds it
+/// as `σ = σ' - e`, which satisfies `A * σ = H(m)`.
+/// As `A * e` is statistically close to uniform for an appropriate Gaussian
+/// parameter, `u'` hides `H(m)` from the signer.
+///
+/// **WARNING:** The unblinded signature is not distributed as a signature
+/// of [`FDH`], as its Gaussian parameter is `sqrt(2)` times larger. Its norm
+/// is still within the domain of the [`PSFGPV`] with overwhelming probability.
+///
+/// Attributes:
+/// - `fdh`: the [`FDH`] signature scheme, which verifies the unblinded signatures
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::construction::signature::{BlindFdh, BlindSignatureScheme, SignatureScheme};
+///
+/// let mut blind_fdh = BlindFdh::init_gpv(4, 113, 17);
+/// let (pk, sk) = blind_fdh.fdh.gen();
+/// let m = "Hello World!";
+///
+/// let (blinded, factor) = blind_fdh.blind(m, &pk);
+/// let blinded_sig = blind_fdh.sign_blind(&blinded, &sk, &pk);
+/// let sigma = blind_fdh.unblind(&blinded_sig, &factor);
+///
+/// assert!(blind_fdh.fdh.vfy(m.to_owned(), &sigma, &pk));
+/// ```
+#[derive(Serialize)]
+pub struct BlindFdh {
+    pub fdh: FDH<MatZq, (MatZ, MatQ), MatZ, MatZq, PSFGPV, HashMatZq>,
+}
+
+impl BlindFdh {
+    /// Initializes a [`BlindFdh`] instance with the [`FDH`] of [`FDH::init_gpv`].
+    ///
+    /// Parameters:
+    /// - `n`: The security parameter
+    /// - `q`: The modulus used for the G-Trapdoors
+    /// - `s`: The Gaussian parameter with which is sampled
+    ///
+    /// Returns a [`BlindFdh`] instance.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::signature::BlindFdh;
+    ///
+    /// let blind_fdh = BlindFdh::init_gpv(4, 113, 17);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `q <= 1`.
+    pub fn init_gpv(n: impl Into<Z>, q: impl Into<Modulus>, s: impl Into<Q>) -> Self {
+        Self {
+            fdh: FDH::init_gpv(n, q, s),
+        }
+    }
+}
+
+impl BlindSignatureScheme for BlindFdh {
+    type SecretKey = (MatZ, MatQ);
+    type PublicKey = MatZq;
+    type BlindedMessage = MatZq;
+    type BlindingFactor = MatZ;
+    type BlindedSignature = MatZ;
+    type Signature = MatZ;
+
+    /// Blinds the message `m` by following these steps:
+    /// - e <- D_{Z^m, s} using `samp_d` of the [`PSFGPV`]
+    /// - u' = H(m) + A * e
+    ///
+    /// Parameters:
+    /// - `m`: specifies the message that should be signed
+    /// - `pk`: specifies the public key `A` of the signer
+    ///
+    /// Returns the blinded message `u'` and the blinding factor `e`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::signature::{BlindFdh, BlindSignatureScheme, SignatureScheme};
+    ///
+    /// let mut blind_fdh = BlindFdh::init_gpv(4, 113, 17);
+    /// let (pk, _) = blind_fdh.fdh.gen();
+    ///
+    /// let (blinded, factor) = blind_fdh.blind("Hello World!", &pk);
+    /// ```
+    fn blind(&self, m: &str, pk: &Self::PublicKey) -> (Self::BlindedMessage, Self::BlindingFactor) {
+        let e = self.fdh.psf.samp_d();
+        let blinded = self.fdh.hash.hash(m) + pk * &e;
+
+        (blinded, e)
+    }
+
+    /// Signs the blinded message `u'` by sampling a short preimage `σ'` with
+    /// `A * σ' = u'` using `samp_p` of the [`PSFGPV`].
+    /// In contrast to [`FDH`], signatures are not stored, as the signer
+    /// can not recognize whether a blinded message was signed before.
+    ///
+    /// Parameters:
+    /// - `blinded`: specifies the blinded message `u'`
+    /// - `sk`: specifies the trapdoor of the signer
+    /// - `pk`: specifies the public key `A` of the signer
+    ///
+    /// Returns the blinded signature `σ'`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::signature::{BlindFdh, BlindSignatureScheme, SignatureScheme};
+    ///
+    /// let mut blind_fdh = BlindFdh::init_gpv(4, 113, 17);
+    /// let (pk, sk) = blind_fdh.fdh.gen();
+    /// let (blinded, factor) = blind_fdh.blind("Hello World!", &pk);
+    ///
+    /// let blinded_sig = blind_fdh.sign_blind(&blinded, &sk, &pk);
+    /// ```
+    fn sign_blind(
+        &self,
+        blinded: &Self::BlindedMessage,
+        sk: &Self::SecretKey,
+        pk: &Self::PublicKey,
+    ) -> Self::BlindedSignature {
+        self.fdh.psf.samp_p(pk, sk, blinded)
+    }
+
+    /// Unblinds the blinded signature `σ'` by computing `σ = σ' - e`.
+    ///
+    /// Parameters:
+    /// - `blinded_sig`: specifies the blinded signature `σ'`
+    /// - `factor`: specifies the blinding factor `e` returned by [`BlindFdh::blind`]
+    ///
+    /// Returns the signature `σ`, which is verified by [`FDH`].
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::signature::{BlindFdh, BlindSignatureScheme, SignatureScheme};
+    ///
+    /// let mut blind_fdh = BlindFdh::init_gpv(4, 113, 17);
+    /// let (pk, sk) = blind_fdh.fdh.gen();
+    /// let (blinded, factor) = blind_fdh.blind("Hello World!", &pk);
+    /// let blinded_sig = blind_fdh.sign_blind(&blinded, &sk, &pk);
+    ///
+    /// let sigma = blind_fdh.unblind(&blinded_sig, &factor);
+    /// ```
+    fn unblind(
+        &self,
+        blinded_sig: &Self::BlindedSignature,
+        factor: &Self::BlindingFactor,
+    ) -> Self::Signature {
+        blinded_sig - factor
+    }
+}
+
+#[cfg(test)]
+mod test_blind_fdh {
+    use super::BlindFdh;
+    use crate::construction::signature::{BlindSignatureScheme, SignatureScheme};
+
+    /// Ensures that unblinded signatures are accepted by the [`FDH`](super::FDH).
+    #[test]
+    fn unblinded_signature_valid() {
+        let mut blind_fdh = BlindFdh::init_gpv(4, 113, 17);
+        let (pk, sk) = blind_fdh.fdh.gen();
+
+        for i in 0..5 {
+            let m = format!("Hello World! {i}");
+
+            let (blinded, factor) = blind_fdh.blind(&m, &pk);
+            let blinded_sig = blind_fdh.sign_blind(&blinded, &sk, &pk);
+            let sigma = blind_fdh.unblind(&blinded_sig, &factor);
+
+            assert!(blind_fdh.fdh.vfy(m, &sigma, &pk));
+        }
+    }
+
+    /// Ensures that blinding the same message twice outputs different blinded
+    /// messages and that the blinded signature is no valid signature of the message.
+    #[test]
+    fn blinded_message_hides_message() {
+        let mut blind_fdh = BlindFdh::init_gpv(4, 113, 17);
+        let (pk, sk) = blind_fdh.fdh.gen();
+        let m = "Hello World!";
+
+        let (blinded_1, _) = blind_fdh.blind(m, &pk);
+        let (blinded_2, _) = blind_fdh.blind(m, &pk);
+        let blinded_sig = blind_fdh.sign_blind(&blinded_1, &sk, &pk);
+
+        assert_ne!(blinded_1, blinded_2);
+        assert!(!blind_fdh.fdh.vfy(m.to_owned(), &blinded_sig, &pk));
+    }
+
+    /// Ensures that unblinded signatures are not valid for other messages or keys
+    /// and that the signer does not store any signature.
+    #[test]
+    fn invalid_signature() {
+        let mut blind_fdh = BlindFdh::init_gpv(4, 113, 17);
+        let (pk, sk) = blind_fdh.fdh.gen();
+        let (pk_2, _) = blind_fdh.fdh.gen();
+        let m = "Hello World!";
+
+        let (blinded, factor) = blind_fdh.blind(m, &pk);
+        let blinded_sig = blind_fdh.sign_blind(&blinded, &sk, &pk);
+        let sigma = blind_fdh.unblind(&blinded_sig, &factor);
+
+        assert!(!blind_fdh.fdh.vfy(String::from("Hello World"), &sigma, &pk));
+        assert!(!blind_fdh.fdh.vfy(m.to_owned(), &sigma, &pk_2));
+        assert!(blind_fdh.fdh.storage.is_empty());
+    }
+}
@@ -11,12 +11,17 @@
 //! a corresponding hash function.
 //!
 //! Implementation of a [`FDH`]-signature scheme are thereby fairly easy,
-//! see [`FDH::init_gpv`] that works with every PSF and a corresponding hash function
+//! see [`FDH::init_gpv`] that works with every PSF and a corresponding hash function.
+//! [`StatelessFdh`] is the variant that does not store any signature.
 
 use super::SignatureScheme;
-use crate::{construction::hash::HashInto, primitive::psf::PSF};
+use crate::{
+    construction::hash::HashInto, error::CryptoError, primitive::psf::PSF,
+    utils::zeroize::ZeroizeEntries,
+};
 use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, marker::PhantomData};
+use zeroize::Zeroize;
 
 pub mod gpv;
 pub mod gpv_ring;
@@ -37,6 +42,8 @@ pub mod serialize;
 /// - `psf`: The PSF which has to implement the [`PSF`] trait and must also be
 ///     (de-)serializable.
 /// - `storage`: A Hashmap that safes all previously signed messages and their signature
+/// - `key_storage`: A Hashmap that safes all previously signed messages and their
+///     signature for each key handle used with [`FDH::sign_with_key`]
 /// - `hash`: The hash-function which has to map a string into the correct domain
 ///
 /// # Example
@@ -49,7 +56,7 @@ pub mod serialize;
 /// let m = "Hello World!";
 ///
 /// let (pk, sk) = fdh.gen();
-/// let sigma = fdh.sign(m.to_owned(), &sk, &pk);
+/// let sigma = fdh.sign(m.to_owned(), &sk, &pk).unwrap();
 ///
 /// assert!(fdh.vfy(m.to_owned(), &sigma, &pk));
 /// ```
@@ -64,6 +71,7 @@ pub struct FDH<
 > {
     pub psf: Box<T>,
     pub storage: HashMap<String, Domain>,
+    pub key_storage: HashMap<usize, HashMap<String, Domain>>,
     pub hash: Box<Hash>,
 
     // The parameters below can be ignored, they are just there for generic usage
@@ -75,6 +83,181 @@ pub struct FDH<
     pub _range_type: PhantomData<Range>,
 }
 
+impl<A, Trapdoor, Domain, Range, T, Hash> FDH<A, Trapdoor, Domain, Range, T, Hash>
+where
+    Domain: Clone + Serialize + for<'a> Deserialize<'a>,
+    Range: PartialEq<Range>,
+    T: PSF<A, Trapdoor, Domain, Range> + Serialize + for<'a> Deserialize<'a>,
+    Hash: HashInto<Range> + Serialize + for<'a> Deserialize<'a>,
+{
+    /// Generates an additional key pair by calling the `trap_gen` of the psf,
+    /// i.e. all key pairs share the same public parameters.
+    ///
+    /// Returns a key pair `(pk, sk)`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::signature::FDH;
+    /// use std::collections::HashMap;
+    /// let fdh = FDH::init_gpv(4, 113, 17);
+    ///
+    /// let mut key_map = HashMap::new();
+    /// key_map.insert(0, fdh.gen_keypair());
+    /// key_map.insert(1, fdh.gen_keypair());
+    /// ```
+    pub fn gen_keypair(&self) -> (A, Trapdoor) {
+        self.psf.trap_gen()
+    }
+
+    /// Signs the message `m` with the key pair stored under `handle` in `key_map`.
+    /// Signatures are stored per key handle, s.t. every message is signed
+    /// at most once under every key pair.
+    ///
+    /// Parameters:
+    /// - `handle`: the handle of the key pair used for signing
+    /// - `m`: the message that should be signed
+    /// - `key_map`: a map from key handles to key pairs `(pk, sk)`
+    ///
+    /// Returns the signature of `m` under the key pair stored under `handle`
+    /// or a [`CryptoError`] if there is no key pair for `handle`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::signature::FDH;
+    /// use std::collections::HashMap;
+    /// let mut fdh = FDH::init_gpv(4, 113, 17);
+    /// let mut key_map = HashMap::new();
+    /// key_map.insert(0, fdh.gen_keypair());
+    ///
+    /// let sigma = fdh.sign_with_key(0, String::from("Hello World!"), &key_map).unwrap();
+    /// ```
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] of type [`InvalidParameters`](CryptoError::InvalidParameters)
+    ///     if `key_map` does not contain a key pair for `handle`.
+    pub fn sign_with_key(
+        &mut self,
+        handle: usize,
+        m: String,
+        key_map: &HashMap<usize, (A, Trapdoor)>,
+    ) -> Result<Domain, CryptoError> {
+        let (pk, sk) = key_map
+            .get(&handle)
+            .ok_or_else(|| CryptoError::InvalidParameters {
+                message: format!("There is no key pair for handle {handle}."),
+            })?;
+
+        let storage = self.key_storage.entry(handle).or_default();
+        if let Some(sigma) = storage.get(&m) {
+            return Ok(sigma.clone());
+        }
+
+        let u = (self.hash).hash(&m);
+        let signature = self.psf.samp_p(pk, sk, &u);
+
+        storage.insert(m, signature.clone());
+        Ok(signature)
+    }
+
+    /// Verifies the signature `sigma` of `m` under the public key stored under
+    /// `handle` in `pk_map`.
+    ///
+    /// Parameters:
+    /// - `handle`: the handle of the public key used for verification
+    /// - `m`: the signed message
+    /// - `sigma`: the signature of `m`
+    /// - `pk_map`: a map from key handles to public keys
+    ///
+    /// Returns `true` if `sigma` is a valid signature of `m` under the public key
+    /// stored under `handle` and `false` otherwise, in particular if there is no
+    /// public key for `handle`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::signature::FDH;
+    /// use std::collections::HashMap;
+    /// let mut fdh = FDH::init_gpv(4, 113, 17);
+    /// let mut key_map = HashMap::new();
+    /// key_map.insert(0, fdh.gen_keypair());
+    /// let m = String::from("Hello World!");
+    /// let sigma = fdh.sign_with_key(0, m.clone(), &key_map).unwrap();
+    ///
+    /// let pk_map: HashMap<_, _> = key_map.into_iter().map(|(h, (pk, _))| (h, pk)).collect();
+    ///
+    /// assert!(fdh.vfy_with_key(0, m, &sigma, &pk_map));
+    /// ```
+    pub fn vfy_with_key(
+        &self,
+        handle: usize,
+        m: String,
+        sigma: &Domain,
+        pk_map: &HashMap<usize, A>,
+    ) -> bool {
+        match pk_map.get(&handle) {
+            Some(pk) => self.vfy(m, sigma, pk),
+            None => false,
+        }
+    }
+
+    /// Checks all signatures in `storage` against the public key `pk`.
+    /// Stored signatures become invalid if the key pair was rotated,
+    /// e.g. if a deserialized instance is used with a freshly generated key pair.
+    ///
+    /// Parameters:
+    /// - `pk`: the public key under which the stored signatures are verified
+    ///
+    /// Returns the sorted messages whose stored signature is invalid under `pk`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::signature::{FDH, SignatureScheme};
+    /// let mut fdh = FDH::init_gpv(4, 113, 17);
+    /// let (pk, sk) = fdh.gen();
+    /// let _ = fdh.sign(String::from("Hello World!"), &sk, &pk).unwrap();
+    ///
+    /// assert!(fdh.validate_storage(&pk).is_empty());
+    /// ```
+    pub fn validate_storage(&self, pk: &A) -> Vec<String> {
+        let mut invalid: Vec<String> = self
+            .storage
+            .iter()
+            .filter(|(m, sigma)| !self.vfy(m.to_string(), sigma, pk))
+            .map(|(m, _)| m.clone())
+            .collect();
+        invalid.sort();
+
+        invalid
+    }
+
+    /// Removes all signatures from `storage` that are invalid under the public key `pk`,
+    /// s.t. these messages are signed again by [`SignatureScheme::sign`].
+    ///
+    /// Parameters:
+    /// - `pk`: the public key under which the stored signatures are verified
+    ///
+    /// Returns the number of removed signatures.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::signature::{FDH, SignatureScheme};
+    /// let mut fdh = FDH::init_gpv(4, 113, 17);
+    /// let (pk, sk) = fdh.gen();
+    /// let _ = fdh.sign(String::from("Hello World!"), &sk, &pk).unwrap();
+    ///
+    /// let (pk, _) = fdh.gen();
+    ///
+    /// assert_eq!(1, fdh.purge_invalid_signatures(&pk));
+    /// ```
+    pub fn purge_invalid_signatures(&mut self, pk: &A) -> usize {
+        let invalid = self.validate_storage(pk);
+        for m in &invalid {
+            self.storage.remove(m);
+        }
+
+        invalid.len()
+    }
+}
+
 impl<A, Trapdoor, Domain, Range, T, Hash> SignatureScheme
     for FDH<A, Trapdoor, Domain, Range, T, Hash>
 where
@@ -88,26 +271,239 @@ where
     type Signature = Domain;
 
     /// Generates a trapdoor by calling the `trap_gen` of the psf
+    #[cfg_attr(feature = "logging", tracing::instrument(name = "gen", skip_all))]
     fn gen(&mut self) -> (Self::PublicKey, Self::SecretKey) {
-        self.psf.trap_gen()
+        let key_pair = self.psf.trap_gen();
+
+        #[cfg(feature = "logging")]
+        tracing::debug!("generated key pair");
+        key_pair
     }
 
     /// Firstly checks if the message has been signed before, and if, return that
-    /// signature, else it continues.
+    /// signature if it is valid under `pk`, else it continues.
     /// It hashes the message into the domain and then computes a signature using
     /// `samp_p` from the psf with the trapdoor.
-    fn sign(&mut self, m: String, sk: &Self::SecretKey, pk: &Self::PublicKey) -> Self::Signature {
+    /// A new signature is only stored if it is valid under `pk`.
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] of type [`VerificationFailed`](CryptoError::VerificationFailed)
+    ///     if the computed signature is invalid under `pk`, e.g. as `sk` does not belong to `pk`.
+    #[cfg_attr(
+        feature = "logging",
+        tracing::instrument(name = "sign", skip_all, fields(message = %m))
+    )]
+    fn sign(
+        &mut self,
+        m: String,
+        sk: &Self::SecretKey,
+        pk: &Self::PublicKey,
+    ) -> Result<Self::Signature, CryptoError> {
         // check if it is in the HashMap
         if let Some(sigma) = self.storage.get(&m) {
-            return sigma.clone();
+            if self.vfy(m.clone(), sigma, pk) {
+                #[cfg(feature = "logging")]
+                tracing::debug!(cached = true, "signed message");
+                return Ok(sigma.clone());
+            }
         }
 
         let u = (self.hash).hash(&m);
         let signature = self.psf.samp_p(pk, sk, &u);
+        if !self.vfy(m.clone(), &signature, pk) {
+            return Err(CryptoError::VerificationFailed);
+        }
+
+        #[cfg(feature = "logging")]
+        tracing::debug!(cached = false, "signed message");
 
         // insert signature in HashMap
         self.storage.insert(m, signature.clone());
-        signature
+        Ok(signature)
+    }
+
+    /// Checks if a signature is firstly within D_n, and then checks if
+    /// the signature is actually a valid preimage under `fa` of `hash(m)`.
+    #[cfg_attr(
+        feature = "logging",
+        tracing::instrument(name = "vfy", skip_all, fields(message = %m), ret)
+    )]
+    fn vfy(&self, m: String, sigma: &Self::Signature, pk: &Self::PublicKey) -> bool {
+        if !self.psf.check_domain(sigma) {
+            #[cfg(feature = "logging")]
+            tracing::debug!("signature is not in the domain");
+            return false;
+        }
+
+        let u = (self.hash).hash(&m);
+
+        self.psf.f_a(pk, sigma) == u
+    }
+}
+
+impl<A, Trapdoor, Domain, Range, T, Hash> Zeroize for FDH<A, Trapdoor, Domain, Range, T, Hash>
+where
+    Domain: ZeroizeEntries + Serialize + for<'a> Deserialize<'a>,
+    T: PSF<A, Trapdoor, Domain, Range> + Serialize + for<'a> Deserialize<'a>,
+    Hash: HashInto<Range> + Serialize + for<'a> Deserialize<'a>,
+{
+    /// Overwrites all stored signatures with zeros and removes them from the storage.
+    /// As [`FDH`] is generic over its signatures and an implementation of [`Drop`]
+    /// can not be restricted to signatures implementing [`ZeroizeEntries`],
+    /// it does not zeroize its storage on drop and does not implement
+    /// [`ZeroizeOnDrop`](zeroize::ZeroizeOnDrop).
+    /// Hence, this function has to be called explicitly before an [`FDH`] is dropped.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::signature::{FDH, SignatureScheme};
+    /// use zeroize::Zeroize;
+    ///
+    /// let mut fdh = FDH::init_gpv(4, 113, 17);
+    /// let (pk, sk) = fdh.gen();
+    /// let _ = fdh.sign(String::from("Hello World!"), &sk, &pk).unwrap();
+    ///
+    /// fdh.zeroize();
+    ///
+    /// assert!(fdh.storage.is_empty());
+    /// ```
+    fn zeroize(&mut self) {
+        for sigma in self.storage.values_mut() {
+            sigma.zeroize_entries();
+        }
+        for storage in self.key_storage.values_mut() {
+            for sigma in storage.values_mut() {
+                sigma.zeroize_entries();
+            }
+        }
+
+        self.storage.clear();
+        self.key_storage.clear();
+    }
+}
+
+/// This struct captures the same hash-then-sign signature scheme as [`FDH`],
+/// but without a storage, i.e. it is stateless.
+/// Every call to [`SignatureScheme::sign`] samples a fresh preimage of the hash
+/// of the message, s.t. the memory of long-lived instances does not grow with
+/// the number of signed messages.
+///
+/// **WARNING:** As signatures are not stored, signing the same message twice
+/// outputs two different preimages of the same hash value. Security is then
+/// only guaranteed if the PSF is collision-resistant, i.e. if it is hard to find
+/// two distinct short preimages, which holds for [`PSFGPV`](crate::primitive::psf::PSFGPV)
+/// under the SIS assumption with appropriate parameters.
+///
+/// Attributes
+/// - `psf`: The PSF which has to implement the [`PSF`] trait and must also be
+///     (de-)serializable.
+/// - `hash`: The hash-function which has to map a string into the correct domain
+///
+/// # Example
+/// ## Signature Scheme from [`PSFGPV`](crate::primitive::psf::PSFGPV)
+/// ```
+/// use qfall_crypto::construction::signature::{SignatureScheme, StatelessFdh};
+///
+/// let mut fdh = StatelessFdh::init_gpv(4, 113, 17);
+///
+/// let m = "Hello World!";
+///
+/// let (pk, sk) = fdh.gen();
+/// let sigma = fdh.sign(m.to_owned(), &sk, &pk).unwrap();
+///
+/// assert!(fdh.vfy(m.to_owned(), &sigma, &pk));
+/// ```
+#[derive(Serialize)]
+pub struct StatelessFdh<
+    A,
+    Trapdoor,
+    Domain,
+    Range,
+    T: PSF<A, Trapdoor, Domain, Range> + Serialize + for<'a> Deserialize<'a>,
+    Hash: HashInto<Range> + Serialize + for<'a> Deserialize<'a>,
+> {
+    pub psf: Box<T>,
+    pub hash: Box<Hash>,
+
+    // The parameters below can be ignored, they are just there for generic usage
+    #[serde(skip_serializing)]
+    pub _a_type: PhantomData<A>,
+    #[serde(skip_serializing)]
+    pub _trapdoor_type: PhantomData<Trapdoor>,
+    #[serde(skip_serializing)]
+    pub _domain_type: PhantomData<Domain>,
+    #[serde(skip_serializing)]
+    pub _range_type: PhantomData<Range>,
+}
+
+impl<A, Trapdoor, Domain, Range, T, Hash> From<FDH<A, Trapdoor, Domain, Range, T, Hash>>
+    for StatelessFdh<A, Trapdoor, Domain, Range, T, Hash>
+where
+    Domain: Serialize + for<'a> Deserialize<'a>,
+    T: PSF<A, Trapdoor, Domain, Range> + Serialize + for<'a> Deserialize<'a>,
+    Hash: HashInto<Range> + Serialize + for<'a> Deserialize<'a>,
+{
+    /// Turns an [`FDH`] into a [`StatelessFdh`] with the same PSF and hash function.
+    /// All stored signatures are dropped.
+    ///
+    /// Parameters:
+    /// - `fdh`: the stateful signature scheme
+    ///
+    /// Returns a [`StatelessFdh`] with the PSF and hash function of `fdh`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::signature::{StatelessFdh, FDH};
+    ///
+    /// let fdh = StatelessFdh::from(FDH::init_gpv(4, 113, 17));
+    /// ```
+    fn from(fdh: FDH<A, Trapdoor, Domain, Range, T, Hash>) -> Self {
+        Self {
+            psf: fdh.psf,
+            hash: fdh.hash,
+            _a_type: PhantomData,
+            _trapdoor_type: PhantomData,
+            _domain_type: PhantomData,
+            _range_type: PhantomData,
+        }
+    }
+}
+
+impl<A, Trapdoor, Domain, Range, T, Hash> SignatureScheme
+    for StatelessFdh<A, Trapdoor, Domain, Range, T, Hash>
+where
+    Range: PartialEq<Range>,
+    T: PSF<A, Trapdoor, Domain, Range> + Serialize + for<'a> Deserialize<'a>,
+    Hash: HashInto<Range> + Serialize + for<'a> Deserialize<'a>,
+{
+    type SecretKey = Trapdoor;
+    type PublicKey = A;
+    type Signature = Domain;
+
+    /// Generates a trapdoor by calling the `trap_gen` of the psf
+    fn gen(&mut self) -> (Self::PublicKey, Self::SecretKey) {
+        self.psf.trap_gen()
+    }
+
+    /// Hashes the message into the domain and then computes a fresh signature
+    /// using `samp_p` from the psf with the trapdoor.
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] of type [`VerificationFailed`](CryptoError::VerificationFailed)
+    ///     if the computed signature is invalid under `pk`, e.g. as `sk` does not belong to `pk`.
+    fn sign(
+        &mut self,
+        m: String,
+        sk: &Self::SecretKey,
+        pk: &Self::PublicKey,
+    ) -> Result<Self::Signature, CryptoError> {
+        let u = (self.hash).hash(&m);
+        let signature = self.psf.samp_p(pk, sk, &u);
+
+        if !self.vfy(m, &signature, pk) {
+            return Err(CryptoError::VerificationFailed);
+        }
+        Ok(signature)
     }
 
     /// Checks if a signature is firstly within D_n, and then checks if
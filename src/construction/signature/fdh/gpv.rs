@@ -9,9 +9,11 @@
 //! A classical implementation of the [`FDH`] scheme using the [`PSFGPV`]
 //! according to [\[1\]](<../index.html#:~:text=[1]>).
 
-use super::FDH;
+use super::{StatelessFdh, FDH};
 use crate::{
-    construction::hash::sha256::HashMatZq, primitive::psf::PSFGPV,
+    construction::hash::{blake3::HashMatZqBlake3, sha256::HashMatZq, Shake256Hash},
+    params::NistLevel,
+    primitive::psf::{PSFGPVBatch, PSFGPV},
     sample::g_trapdoor::gadget_parameters::GadgetParameters,
 };
 use qfall_math::{
@@ -44,7 +46,7 @@ impl FDH<MatZq, (MatZ, MatQ), MatZ, MatZq, PSFGPV, HashMatZq> {
     /// let mut fdh = FDH::init_gpv(4, 113, 17);
     /// let (pk, sk) = fdh.gen();
     ///
-    /// let sigma = fdh.sign(m.to_string(), &sk, &pk);
+    /// let sigma = fdh.sign(m.to_string(), &sk, &pk).unwrap();
     ///
     /// assert!(fdh.vfy(m.to_string(), &sigma, &pk));
     /// ```
@@ -58,10 +60,13 @@ impl FDH<MatZq, (MatZ, MatQ), MatZ, MatZq, PSFGPV, HashMatZq> {
         let psf = PSFGPV {
             gp: GadgetParameters::init_default(&n, &q),
             s: s.into(),
+            cdt: None,
+            cdt_cache: Default::default(),
         };
         Self {
             psf: Box::new(psf),
             storage: HashMap::new(),
+            key_storage: HashMap::new(),
             hash: Box::new(HashMatZq {
                 modulus: q,
                 rows: n_i64,
@@ -72,18 +77,238 @@ impl FDH<MatZq, (MatZ, MatQ), MatZ, MatZq, PSFGPV, HashMatZq> {
             _range_type: PhantomData,
         }
     }
+
+    /// Initializes an FDH signature scheme from a [`PSFGPV`] using [`FDH::init_gpv`]
+    /// with the parameter set of the NIST security `level`.
+    ///
+    /// Parameters:
+    /// - `level`: specifies the NIST security level
+    ///
+    /// Returns an explicit implementation of a FDH-signature scheme.
+    ///
+    /// # Example
+    /// ```
+    /// use qfall_crypto::{construction::signature::FDH, params::NistLevel};
+    ///
+    /// let fdh = FDH::from_nist_level(NistLevel::Level1);
+    /// ```
+    pub fn from_nist_level(level: NistLevel) -> Self {
+        let params = level.params();
+        Self::init_gpv(params.n as u64, params.q, params.sigma)
+    }
+}
+
+impl FDH<MatZq, (MatZ, MatQ), MatZ, MatZq, PSFGPV, HashMatZqBlake3> {
+    /// Initializes an FDH signature scheme from a [`PSFGPV`] like [`FDH::init_gpv`],
+    /// but hashes messages using the XOF of BLAKE3, see [`HashMatZqBlake3`].
+    ///
+    /// Parameters:
+    /// - `n`: The security parameter
+    /// - `q`: The modulus used for the G-Trapdoors
+    /// - `s`: The Gaussian parameter with which is sampled
+    ///
+    /// Returns an explicit implementation of a FDH-signature scheme.
+    ///
+    /// # Example
+    /// ```
+    /// use qfall_crypto::construction::signature::{FDH, SignatureScheme};
+    ///
+    /// let m = "Hello World!";
+    ///
+    /// let mut fdh = FDH::init_blake3_gpv(4, 113, 17);
+    /// let (pk, sk) = fdh.gen();
+    ///
+    /// let sigma = fdh.sign(m.to_string(), &sk, &pk).unwrap();
+    ///
+    /// assert!(fdh.vfy(m.to_string(), &sigma, &pk));
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `q <= 1`.
+    pub fn init_blake3_gpv(n: impl Into<Z>, q: impl Into<Modulus>, s: impl Into<Q>) -> Self {
+        let n = n.into();
+        let n_i64 = i64::try_from(&n).unwrap();
+        let q = q.into();
+        let psf = PSFGPV {
+            gp: GadgetParameters::init_default(&n, &q),
+            s: s.into(),
+            cdt: None,
+            cdt_cache: Default::default(),
+        };
+        Self {
+            psf: Box::new(psf),
+            storage: HashMap::new(),
+            key_storage: HashMap::new(),
+            hash: Box::new(HashMatZqBlake3 {
+                modulus: q,
+                rows: n_i64,
+                cols: 1,
+            }),
+            _a_type: PhantomData,
+            _trapdoor_type: PhantomData,
+            _range_type: PhantomData,
+        }
+    }
+}
+
+impl FDH<MatZq, (MatZ, MatQ), MatZ, MatZq, PSFGPV, Shake256Hash> {
+    /// Initializes an FDH signature scheme from a [`PSFGPV`] like [`FDH::init_gpv`],
+    /// but hashes messages using SHAKE-256, see [`Shake256Hash`].
+    ///
+    /// Parameters:
+    /// - `n`: The security parameter
+    /// - `q`: The modulus used for the G-Trapdoors
+    /// - `s`: The Gaussian parameter with which is sampled
+    ///
+    /// Returns an explicit implementation of a FDH-signature scheme.
+    ///
+    /// # Example
+    /// ```
+    /// use qfall_crypto::construction::signature::{FDH, SignatureScheme};
+    ///
+    /// let m = "Hello World!";
+    ///
+    /// let mut fdh = FDH::init_gpv_shake256(4, 113, 17);
+    /// let (pk, sk) = fdh.gen();
+    ///
+    /// let sigma = fdh.sign(m.to_string(), &sk, &pk).unwrap();
+    ///
+    /// assert!(fdh.vfy(m.to_string(), &sigma, &pk));
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `q <= 1`.
+    pub fn init_gpv_shake256(n: impl Into<Z>, q: impl Into<Modulus>, s: impl Into<Q>) -> Self {
+        let n = n.into();
+        let n_i64 = i64::try_from(&n).unwrap();
+        let q = q.into();
+        let psf = PSFGPV {
+            gp: GadgetParameters::init_default(&n, &q),
+            s: s.into(),
+            cdt: None,
+            cdt_cache: Default::default(),
+        };
+        Self {
+            psf: Box::new(psf),
+            storage: HashMap::new(),
+            key_storage: HashMap::new(),
+            hash: Box::new(Shake256Hash {
+                modulus: q,
+                rows: n_i64,
+                cols: 1,
+                degree: 1,
+            }),
+            _a_type: PhantomData,
+            _trapdoor_type: PhantomData,
+            _range_type: PhantomData,
+        }
+    }
+}
+
+impl FDH<MatZq, (MatZ, MatQ), MatZ, MatZq, PSFGPVBatch, HashMatZq> {
+    /// Initializes an FDH signature scheme from a [`PSFGPVBatch`], which hashes
+    /// messages into `Z_q^{n x k}` and signs them with a preimage of dimensions `m x k`.
+    /// For `k = 1` the scheme behaves like [`FDH::init_gpv`].
+    ///
+    /// Parameters:
+    /// - `n`: The security parameter
+    /// - `q`: The modulus used for the G-Trapdoors
+    /// - `s`: The Gaussian parameter with which is sampled
+    /// - `k`: The number of columns of the hash values and signatures
+    ///
+    /// Returns an explicit implementation of a FDH-signature scheme.
+    ///
+    /// # Example
+    /// ```
+    /// use qfall_crypto::construction::signature::{FDH, SignatureScheme};
+    ///
+    /// let m = "Hello World!";
+    ///
+    /// let mut fdh = FDH::init_gpv_batch(4, 113, 17, 2);
+    /// let (pk, sk) = fdh.gen();
+    ///
+    /// let sigma = fdh.sign(m.to_string(), &sk, &pk).unwrap();
+    ///
+    /// assert!(fdh.vfy(m.to_string(), &sigma, &pk));
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `q <= 1`.
+    /// - if `k < 1`.
+    pub fn init_gpv_batch(n: impl Into<Z>, q: impl Into<Modulus>, s: impl Into<Q>, k: i64) -> Self {
+        assert!(k >= 1, "The number of columns must be positive.");
+        let n = n.into();
+        let n_i64 = i64::try_from(&n).unwrap();
+        let q = q.into();
+        let psf = PSFGPVBatch {
+            psf: PSFGPV {
+                gp: GadgetParameters::init_default(&n, &q),
+                s: s.into(),
+                cdt: None,
+                cdt_cache: Default::default(),
+            },
+            k,
+        };
+        Self {
+            psf: Box::new(psf),
+            storage: HashMap::new(),
+            key_storage: HashMap::new(),
+            hash: Box::new(HashMatZq {
+                modulus: q,
+                rows: n_i64,
+                cols: k,
+            }),
+            _a_type: PhantomData,
+            _trapdoor_type: PhantomData,
+            _range_type: PhantomData,
+        }
+    }
+}
+
+impl StatelessFdh<MatZq, (MatZ, MatQ), MatZ, MatZq, PSFGPV, HashMatZq> {
+    /// Initializes a stateless FDH signature scheme from a [`PSFGPV`]
+    /// with the same PSF and hash function as [`FDH::init_gpv`].
+    ///
+    /// Parameters:
+    /// - `n`: The security parameter
+    /// - `q`: The modulus used for the G-Trapdoors
+    /// - `s`: The Gaussian parameter with which is sampled
+    ///
+    /// Returns an explicit implementation of a stateless FDH-signature scheme.
+    ///
+    /// # Example
+    /// ```
+    /// use qfall_crypto::construction::signature::{SignatureScheme, StatelessFdh};
+    ///
+    /// let m = "Hello World!";
+    ///
+    /// let mut fdh = StatelessFdh::init_gpv(4, 113, 17);
+    /// let (pk, sk) = fdh.gen();
+    ///
+    /// let sigma = fdh.sign(m.to_string(), &sk, &pk).unwrap();
+    ///
+    /// assert!(fdh.vfy(m.to_string(), &sigma, &pk));
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `q <= 1`.
+    pub fn init_gpv(n: impl Into<Z>, q: impl Into<Modulus>, s: impl Into<Q>) -> Self {
+        Self::from(FDH::init_gpv(n, q, s))
+    }
 }
 
 #[cfg(test)]
 mod test_fdh {
     use super::{HashMatZq, FDH, PSFGPV};
-    use crate::construction::signature::SignatureScheme;
+    use crate::{construction::signature::SignatureScheme, error::CryptoError};
     use qfall_math::{
         integer::{MatZ, Z},
         integer_mod_q::MatZq,
         rational::{MatQ, Q},
         traits::Pow,
     };
+    use std::collections::HashMap;
+    use zeroize::Zeroize;
 
     /// Ensure that the generated signature is valid.
     #[test]
@@ -101,9 +326,9 @@ mod test_fdh {
         for i in 0..10 {
             let m = format!("Hello World! {}", i);
 
-            let sigma = fdh.sign(m.to_owned(), &sk, &pk);
+            let sigma = fdh.sign(m.to_owned(), &sk, &pk).unwrap();
 
-            assert_eq!(&sigma, &fdh.sign(m.to_owned(), &sk, &pk));
+            assert_eq!(&sigma, &fdh.sign(m.to_owned(), &sk, &pk).unwrap());
             assert!(fdh.vfy(m.to_owned(), &sigma, &pk))
         }
     }
@@ -115,7 +340,7 @@ mod test_fdh {
 
         let m = "Hello World!";
         let (pk, sk) = fdh.gen();
-        let _ = fdh.sign(m.to_owned(), &sk, &pk);
+        let _ = fdh.sign(m.to_owned(), &sk, &pk).unwrap();
 
         assert!(fdh.storage.contains_key(m))
     }
@@ -128,12 +353,354 @@ mod test_fdh {
         // fill one entry in the HashMap
         let m = "Hello World!";
         let (pk, sk) = fdh.gen();
-        let _ = fdh.sign(m.to_owned(), &sk, &pk);
+        let _ = fdh.sign(m.to_owned(), &sk, &pk).unwrap();
+
+        let fdh_string = serde_json::to_string(&fdh).expect("Unable to create a json object");
+        let fdh_2: FDH<MatZq, (MatZ, MatQ), MatZ, MatZq, PSFGPV, HashMatZq> =
+            serde_json::from_str(&fdh_string).unwrap();
+
+        assert_eq!(fdh.storage, fdh_2.storage);
+    }
+
+    /// Ensure that all stored signatures are valid under the key pair used for signing.
+    #[test]
+    fn validate_storage_valid() {
+        let mut fdh = FDH::init_gpv(5, 1024, 10);
+
+        let (pk, sk) = fdh.gen();
+        for i in 0..3 {
+            let _ = fdh.sign(format!("Hello World! {i}"), &sk, &pk).unwrap();
+        }
+
+        assert!(fdh.validate_storage(&pk).is_empty());
+        assert_eq!(0, fdh.purge_invalid_signatures(&pk));
+        assert_eq!(3, fdh.storage.len());
+    }
+
+    /// Ensure that after a key rotation of a deserialized instance,
+    /// the old signatures are detected and purged.
+    #[test]
+    fn purge_after_key_rotation() {
+        let mut fdh = FDH::init_gpv(5, 1024, 10);
+
+        let (pk, sk) = fdh.gen();
+        for i in 0..3 {
+            let _ = fdh.sign(format!("Hello World! {i}"), &sk, &pk).unwrap();
+        }
+
+        let fdh_string = serde_json::to_string(&fdh).expect("Unable to create a json object");
+        let mut fdh: FDH<MatZq, (MatZ, MatQ), MatZ, MatZq, PSFGPV, HashMatZq> =
+            serde_json::from_str(&fdh_string).unwrap();
+        let (pk, sk) = fdh.gen();
+
+        assert_eq!(
+            vec!["Hello World! 0", "Hello World! 1", "Hello World! 2"],
+            fdh.validate_storage(&pk)
+        );
+        assert_eq!(3, fdh.purge_invalid_signatures(&pk));
+        assert!(fdh.storage.is_empty());
+
+        let m = String::from("Hello World! 0");
+        let sigma = fdh.sign(m.clone(), &sk, &pk).unwrap();
+        assert!(fdh.vfy(m, &sigma, &pk));
+    }
+
+    /// Ensures that `sign` rejects a secret key not belonging to the public key
+    /// and replaces stored signatures, which are invalid under the public key.
+    #[test]
+    fn sign_mismatching_key() {
+        let mut fdh = FDH::init_gpv(5, 1024, 10);
+        let (pk, sk) = fdh.gen();
+        let (pk_2, sk_2) = fdh.gen();
+        let m = String::from("Hello World!");
+        let sigma = fdh.sign(m.clone(), &sk, &pk).unwrap();
+
+        assert!(matches!(
+            fdh.sign(m.clone(), &sk, &pk_2),
+            Err(CryptoError::VerificationFailed)
+        ));
+        assert_eq!(Some(&sigma), fdh.storage.get(&m));
+
+        let sigma_2 = fdh.sign(m.clone(), &sk_2, &pk_2).unwrap();
+        assert!(fdh.vfy(m.clone(), &sigma_2, &pk_2));
+        assert_eq!(Some(&sigma_2), fdh.storage.get(&m));
+    }
+
+    /// Ensures that zeroizing removes all stored signatures
+    /// and that messages can still be signed afterwards.
+    #[test]
+    fn zeroize_storage() {
+        let mut fdh = FDH::init_gpv(4, 113, 17);
+        let (pk, sk) = fdh.gen();
+        let key_map = HashMap::from([(0, (pk.clone(), sk.clone()))]);
+        let m = String::from("Hello World!");
+        let _ = fdh.sign(m.clone(), &sk, &pk).unwrap();
+        let _ = fdh.sign_with_key(0, m.clone(), &key_map).unwrap();
+
+        fdh.zeroize();
+
+        assert!(fdh.storage.is_empty());
+        assert!(fdh.key_storage.is_empty());
+        let sigma = fdh.sign(m.clone(), &sk, &pk).unwrap();
+        assert!(fdh.vfy(m, &sigma, &pk));
+    }
+}
+
+#[cfg(test)]
+mod test_fdh_multi_key {
+    use super::{HashMatZq, FDH, PSFGPV};
+    use qfall_math::{integer::MatZ, integer_mod_q::MatZq, rational::MatQ};
+    use std::collections::HashMap;
+
+    /// Ensures that 5 independent key pairs sign and verify messages
+    /// and that signatures are only valid under the key they were generated with.
+    #[test]
+    fn five_independent_key_pairs() {
+        let mut fdh = FDH::init_gpv(4, 113, 17);
+        let key_map: HashMap<usize, (MatZq, (MatZ, MatQ))> =
+            (0..5).map(|handle| (handle, fdh.gen_keypair())).collect();
+        let pk_map: HashMap<usize, MatZq> = key_map
+            .iter()
+            .map(|(handle, (pk, _))| (*handle, pk.clone()))
+            .collect();
+        let m = String::from("Hello World!");
+
+        for handle in 0..5 {
+            let sigma = fdh.sign_with_key(handle, m.clone(), &key_map).unwrap();
+
+            assert_eq!(
+                sigma,
+                fdh.sign_with_key(handle, m.clone(), &key_map).unwrap()
+            );
+            for other in 0..5 {
+                assert_eq!(
+                    handle == other,
+                    fdh.vfy_with_key(other, m.clone(), &sigma, &pk_map)
+                );
+            }
+        }
+    }
+
+    /// Ensures that signatures are stored per key handle.
+    #[test]
+    fn storage_per_handle() {
+        let mut fdh = FDH::init_gpv(4, 113, 17);
+        let key_map: HashMap<usize, (MatZq, (MatZ, MatQ))> =
+            (0..2).map(|handle| (handle, fdh.gen_keypair())).collect();
+        let m = String::from("Hello World!");
+
+        let sigma_0 = fdh.sign_with_key(0, m.clone(), &key_map).unwrap();
+        let sigma_1 = fdh.sign_with_key(1, m.clone(), &key_map).unwrap();
+
+        assert_ne!(sigma_0, sigma_1);
+        assert_eq!(&sigma_0, fdh.key_storage[&0].get(&m).unwrap());
+        assert_eq!(&sigma_1, fdh.key_storage[&1].get(&m).unwrap());
+        assert!(fdh.storage.is_empty());
+    }
+
+    /// Ensures that unknown key handles are rejected.
+    #[test]
+    fn unknown_handle() {
+        let mut fdh = FDH::init_gpv(4, 113, 17);
+        let key_map: HashMap<usize, (MatZq, (MatZ, MatQ))> =
+            HashMap::from([(0, fdh.gen_keypair())]);
+        let pk_map: HashMap<usize, MatZq> = HashMap::from([(0, key_map[&0].0.clone())]);
+        let m = String::from("Hello World!");
+        let sigma = fdh.sign_with_key(0, m.clone(), &key_map).unwrap();
+
+        assert!(fdh.sign_with_key(1, m.clone(), &key_map).is_err());
+        assert!(!fdh.vfy_with_key(1, m, &sigma, &pk_map));
+    }
+
+    /// Ensures that the key storage is restored after deserialization.
+    #[test]
+    fn reload_key_storage() {
+        let mut fdh = FDH::init_gpv(4, 113, 17);
+        let key_map = HashMap::from([(3, fdh.gen_keypair())]);
+        let _ = fdh.sign_with_key(3, String::from("Hello World!"), &key_map);
 
         let fdh_string = serde_json::to_string(&fdh).expect("Unable to create a json object");
         let fdh_2: FDH<MatZq, (MatZ, MatQ), MatZ, MatZq, PSFGPV, HashMatZq> =
             serde_json::from_str(&fdh_string).unwrap();
 
+        assert_eq!(fdh.key_storage, fdh_2.key_storage);
+    }
+}
+
+#[cfg(test)]
+mod test_fdh_batch {
+    use super::FDH;
+    use crate::construction::signature::SignatureScheme;
+    use qfall_math::traits::GetNumColumns;
+
+    /// Ensures that signatures with `k` columns are valid for `k = 1, 2, 4`.
+    #[test]
+    fn cycle() {
+        for k in [1, 2, 4] {
+            let mut fdh = FDH::init_gpv_batch(4, 113, 17, k);
+            let (pk, sk) = fdh.gen();
+
+            for i in 0..5 {
+                let m = format!("Hello World! {i}");
+
+                let sigma = fdh.sign(m.clone(), &sk, &pk).unwrap();
+
+                assert_eq!(k, sigma.get_num_columns());
+                assert!(fdh.vfy(m, &sigma, &pk));
+            }
+        }
+    }
+
+    /// Ensures that a `k`-column signature is not valid for another message
+    /// and that a single column of it is rejected.
+    #[test]
+    fn invalid_signature() {
+        let mut fdh = FDH::init_gpv_batch(4, 113, 17, 2);
+        let (pk, sk) = fdh.gen();
+
+        let sigma = fdh.sign(String::from("Hello World!"), &sk, &pk).unwrap();
+
+        assert!(!fdh.vfy(String::from("Hello World"), &sigma, &pk));
+        assert!(!fdh.vfy(
+            String::from("Hello World!"),
+            &sigma.get_column(0).unwrap(),
+            &pk
+        ));
+    }
+}
+
+#[cfg(test)]
+mod test_fdh_blake3 {
+    use super::{HashMatZqBlake3, FDH, PSFGPV};
+    use crate::construction::signature::SignatureScheme;
+    use qfall_math::{integer::MatZ, integer_mod_q::MatZq, rational::MatQ};
+
+    /// Ensures that signatures are valid and that they are not valid for other messages.
+    #[test]
+    fn cycle() {
+        let mut fdh = FDH::init_blake3_gpv(4, 113, 17);
+        let (pk, sk) = fdh.gen();
+
+        for i in 0..5 {
+            let m = format!("Hello World! {i}");
+
+            let sigma = fdh.sign(m.clone(), &sk, &pk).unwrap();
+
+            assert!(fdh.vfy(m, &sigma, &pk));
+            assert!(!fdh.vfy(format!("Hello World {i}"), &sigma, &pk));
+        }
+    }
+
+    /// Ensures that the storage is restored after deserialization.
+    #[test]
+    fn reload_hashmap() {
+        let mut fdh = FDH::init_blake3_gpv(4, 113, 17);
+        let (pk, sk) = fdh.gen();
+        let _ = fdh.sign(String::from("Hello World!"), &sk, &pk).unwrap();
+
+        let fdh_string = serde_json::to_string(&fdh).expect("Unable to create a json object");
+        let fdh_2: FDH<MatZq, (MatZ, MatQ), MatZ, MatZq, PSFGPV, HashMatZqBlake3> =
+            serde_json::from_str(&fdh_string).unwrap();
+
         assert_eq!(fdh.storage, fdh_2.storage);
     }
 }
+
+#[cfg(test)]
+mod test_fdh_shake256 {
+    use super::FDH;
+    use crate::construction::signature::SignatureScheme;
+
+    /// Ensures that signatures are valid and that they are not valid for other messages.
+    #[test]
+    fn cycle() {
+        let mut fdh = FDH::init_gpv_shake256(4, 113, 17);
+        let (pk, sk) = fdh.gen();
+
+        for i in 0..5 {
+            let m = format!("Hello World! {i}");
+
+            let sigma = fdh.sign(m.clone(), &sk, &pk).unwrap();
+
+            assert!(fdh.vfy(m, &sigma, &pk));
+            assert!(!fdh.vfy(format!("Hello World {i}"), &sigma, &pk));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_stateless_fdh {
+    use super::{StatelessFdh, FDH};
+    use crate::construction::signature::SignatureScheme;
+
+    /// Ensures that signing the same message twice outputs two different
+    /// signatures that are both valid.
+    #[test]
+    fn fresh_signatures() {
+        let mut fdh = StatelessFdh::init_gpv(4, 113, 17);
+        let (pk, sk) = fdh.gen();
+        let m = String::from("Hello World!");
+
+        let sigma_1 = fdh.sign(m.clone(), &sk, &pk).unwrap();
+        let sigma_2 = fdh.sign(m.clone(), &sk, &pk).unwrap();
+
+        assert_ne!(sigma_1, sigma_2);
+        assert!(fdh.vfy(m.clone(), &sigma_1, &pk));
+        assert!(fdh.vfy(m, &sigma_2, &pk));
+    }
+
+    /// Ensures that signatures are not valid for other messages or keys.
+    #[test]
+    fn invalid_signature() {
+        let mut fdh = StatelessFdh::init_gpv(4, 113, 17);
+        let (pk, sk) = fdh.gen();
+        let (pk_2, _) = fdh.gen();
+
+        let sigma = fdh.sign(String::from("Hello World!"), &sk, &pk).unwrap();
+
+        assert!(!fdh.vfy(String::from("Hello World"), &sigma, &pk));
+        assert!(!fdh.vfy(String::from("Hello World!"), &sigma, &pk_2));
+    }
+
+    /// Ensures that signatures of an [`FDH`] are valid for the stateless
+    /// instance converted from it.
+    #[test]
+    fn compatible_with_fdh() {
+        let mut fdh = FDH::init_gpv(4, 113, 17);
+        let (pk, sk) = fdh.gen();
+        let m = String::from("Hello World!");
+        let sigma = fdh.sign(m.clone(), &sk, &pk).unwrap();
+
+        let stateless_fdh = StatelessFdh::from(fdh);
+
+        assert!(stateless_fdh.vfy(m, &sigma, &pk));
+    }
+}
+
+#[cfg(all(test, feature = "logging"))]
+mod test_logging {
+    use super::FDH;
+    use crate::construction::signature::SignatureScheme;
+    use tracing_test::traced_test;
+
+    /// Ensures that gen, sign and vfy emit their spans including the message
+    /// and the result of the verification.
+    #[test]
+    #[traced_test]
+    fn spans_emitted() {
+        let mut fdh = FDH::init_gpv(4, 113, 17);
+        let m = String::from("Hello");
+
+        let (pk, sk) = fdh.gen();
+        let sigma = fdh.sign(m.clone(), &sk, &pk).unwrap();
+        let _ = fdh.sign(m.clone(), &sk, &pk).unwrap();
+        let _ = fdh.vfy(m, &sigma, &pk);
+
+        assert!(logs_contain("gen: "));
+        assert!(logs_contain("sign{message=Hello}"));
+        assert!(logs_contain("cached=false"));
+        assert!(logs_contain("cached=true"));
+        assert!(logs_contain("vfy{message=Hello}"));
+        assert!(logs_contain("return=true"));
+    }
+}
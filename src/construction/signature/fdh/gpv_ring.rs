@@ -11,13 +11,14 @@
 
 use super::FDH;
 use crate::{
-    construction::hash::sha256::HashMatPolynomialRingZq, primitive::psf::PSFGPVRing,
-    sample::g_trapdoor::gadget_parameters::GadgetParametersRing,
+    construction::hash::sha256::HashMatPolynomialRingZq, error::CryptoError,
+    primitive::psf::PSFGPVRing, sample::g_trapdoor::gadget_parameters::GadgetParametersRing,
 };
 use qfall_math::{
     integer::{MatPolyOverZ, Z},
     integer_mod_q::{MatPolynomialRingZq, Modulus},
     rational::Q,
+    traits::Pow,
 };
 use std::{collections::HashMap, marker::PhantomData};
 
@@ -56,7 +57,7 @@ impl
     ///
     /// let m = &format!("Hello World!");
     ///
-    /// let sigma = fdh.sign(m.to_owned(), &sk, &pk);
+    /// let sigma = fdh.sign(m.to_owned(), &sk, &pk).unwrap();
     /// assert!(fdh.vfy(m.to_owned(), &sigma, &pk));
     /// ```
     ///
@@ -75,6 +76,7 @@ impl
         Self {
             psf: Box::new(psf),
             storage: HashMap::new(),
+            key_storage: HashMap::new(),
             hash: Box::new(HashMatPolynomialRingZq {
                 modulus,
                 rows: 1,
@@ -85,6 +87,104 @@ impl
             _range_type: PhantomData,
         }
     }
+
+    /// Returns the degree `n` of the ring and the number `m` of ring elements
+    /// of a signature.
+    fn ring_dimensions(&self) -> (Z, Z) {
+        let n = Z::from(self.psf.gp.modulus.get_degree());
+        let m = &self.psf.gp.k + 2;
+        (n, m)
+    }
+
+    /// Checks the public parameters for security according to Section 6
+    /// of [GPV08 - eprint](https://eprint.iacr.org/2007/432.pdf), i.e. two signatures
+    /// of the same hash differ by a non-trivial solution of the ring-SIS problem
+    /// with norm bound `β = 2 * s * sqrt(m * n)`, where `m = k + 2`.
+    ///
+    /// The required property is:
+    /// - q > 2 * s * sqrt(m * n)
+    ///
+    /// Returns an empty result if the public parameters guarantee security
+    /// or a [`CryptoError`] if the instance would not be secure.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::signature::FDH;
+    /// let fdh = FDH::init_gpv_ring(8, 65536, 100);
+    ///
+    /// assert!(fdh.check_security().is_ok());
+    /// ```
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] of type [`InvalidParameters`](CryptoError::InvalidParameters)
+    ///     if at least one parameter was not chosen appropriately for a
+    ///     secure ring FDH instance.
+    pub fn check_security(&self) -> Result<(), CryptoError> {
+        let (n, m) = self.ring_dimensions();
+        let q = Q::from(self.psf.gp.modulus.get_q());
+
+        // q > 2 * s * sqrt(m * n)
+        if q <= 2 * &self.psf.s * (&m * &n).sqrt() {
+            return Err(CryptoError::InvalidParameters {
+                message: String::from(
+                    "Security is not guaranteed as q <= 2 * s * sqrt(m * n), \
+                but q > 2 * s * sqrt(m * n) is required.",
+                ),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Checks the public parameters for correctness according to Theorem 5.5
+    /// of [MP12 - eprint](https://eprint.iacr.org/2011/501.pdf), i.e. preimages are
+    /// sampled from a discrete Gaussian with parameter `s`, which lie in the domain
+    /// of the [`PSFGPVRing`] with overwhelming probability.
+    /// The largest singular value of the trapdoor `(r, e)` is estimated as `2 * s_td * sqrt(n)`
+    /// and `ω(sqrt(log n))` is instantiated as `sqrt(log n)`.
+    ///
+    /// The required properties are:
+    /// - n > 1
+    /// - s >= (2 * s_td * sqrt(n) + 1) * sqrt(b^2 + 1) * sqrt(log n)
+    ///
+    /// Returns an empty result if the public parameters guarantee correctness
+    /// with overwhelming probability or a [`CryptoError`] if the instance would
+    /// not be correct.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::signature::FDH;
+    /// let fdh = FDH::init_gpv_ring(8, 65536, 100);
+    ///
+    /// assert!(fdh.check_correctness().is_ok());
+    /// ```
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] of type [`InvalidParameters`](CryptoError::InvalidParameters)
+    ///     if at least one parameter was not chosen appropriately for a
+    ///     correct ring FDH instance.
+    pub fn check_correctness(&self) -> Result<(), CryptoError> {
+        let (n, _) = self.ring_dimensions();
+        if n <= Z::ONE {
+            return Err(CryptoError::InvalidParameters {
+                message: String::from("n must be chosen bigger than 1."),
+            });
+        }
+
+        // s >= (2 * s_td * sqrt(n) + 1) * sqrt(b^2 + 1) * sqrt(log n)
+        let s_1 = 2 * &self.psf.s_td * n.sqrt() + 1;
+        let base_factor = (self.psf.gp.base.pow(2).unwrap() + Z::ONE).sqrt();
+        if self.psf.s < s_1 * base_factor * n.log(2).unwrap().sqrt() {
+            return Err(CryptoError::InvalidParameters {
+                message: String::from(
+                    "Correctness is not guaranteed as s < (2 * s_td * sqrt(n) + 1) * sqrt(b^2 + 1) * sqrt(log n), \
+                    but s >= (2 * s_td * sqrt(n) + 1) * sqrt(b^2 + 1) * sqrt(log n) is required.",
+                ),
+            });
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -92,7 +192,7 @@ mod test_fdh {
     use super::{PSFGPVRing, FDH};
     use crate::{
         construction::hash::sha256::HashMatPolynomialRingZq,
-        construction::signature::SignatureScheme,
+        construction::signature::SignatureScheme, error::CryptoError,
     };
     use qfall_math::{integer::MatPolyOverZ, integer_mod_q::MatPolynomialRingZq, rational::Q};
 
@@ -111,7 +211,7 @@ mod test_fdh {
         for i in 0..10 {
             let m = &format!("Hello World! {i}");
 
-            let sigma = fdh.sign(m.to_owned(), &sk, &pk);
+            let sigma = fdh.sign(m.to_owned(), &sk, &pk).unwrap();
 
             assert!(
                 fdh.vfy(m.to_owned(), &sigma, &pk),
@@ -129,8 +229,8 @@ mod test_fdh {
 
         let m = "Hello World!";
         let (pk, sk) = fdh.gen();
-        let sign_1 = fdh.sign(m.to_owned(), &sk, &pk);
-        let sign_2 = fdh.sign(m.to_owned(), &sk, &pk);
+        let sign_1 = fdh.sign(m.to_owned(), &sk, &pk).unwrap();
+        let sign_2 = fdh.sign(m.to_owned(), &sk, &pk).unwrap();
 
         assert!(fdh.storage.contains_key(m));
         assert_eq!(sign_1, sign_2);
@@ -144,7 +244,7 @@ mod test_fdh {
         // fill one entry in the HashMap
         let m = "Hello World!";
         let (pk, sk) = fdh.gen();
-        let _ = fdh.sign(m.to_owned(), &sk, &pk);
+        let _ = fdh.sign(m.to_owned(), &sk, &pk).unwrap();
 
         let fdh_string = serde_json::to_string(&fdh).expect("Unable to create a json object");
 
@@ -159,4 +259,53 @@ mod test_fdh {
 
         assert_eq!(fdh.storage, fdh_2.storage);
     }
+
+    /// Ensures that suitable parameters pass both checks and
+    /// that the resulting scheme signs and verifies correctly.
+    #[test]
+    fn suitable_parameters() {
+        let mut fdh = FDH::init_gpv_ring(N, 65536, compute_s());
+        assert!(fdh.check_security().is_ok());
+        assert!(fdh.check_correctness().is_ok());
+
+        let (pk, sk) = fdh.gen();
+        for i in 0..5 {
+            let m = format!("Hello World! {i}");
+            let sigma = fdh.sign(m.clone(), &sk, &pk).unwrap();
+
+            assert!(fdh.vfy(m, &sigma, &pk));
+        }
+    }
+
+    /// Ensures that unsuitable parameters are rejected by the checks.
+    #[test]
+    fn unsuitable_parameters() {
+        // q <= 2 * s * sqrt(m * n)
+        let fdh = FDH::init_gpv_ring(N, MODULUS, compute_s());
+        assert!(matches!(
+            fdh.check_security(),
+            Err(CryptoError::InvalidParameters { .. })
+        ));
+
+        // s < (2 * s_td * sqrt(n) + 1) * sqrt(b^2 + 1) * sqrt(log n)
+        let fdh = FDH::init_gpv_ring(N, 65536, 5);
+        assert!(matches!(
+            fdh.check_correctness(),
+            Err(CryptoError::InvalidParameters { .. })
+        ));
+    }
+
+    /// Ensures that signatures are rejected for other messages and public keys.
+    #[test]
+    fn invalid_signature() {
+        let mut fdh = FDH::init_gpv_ring(N, MODULUS, compute_s());
+        let (pk, sk) = fdh.gen();
+        let (pk_2, _) = fdh.gen();
+        let m = "Hello World!";
+
+        let sigma = fdh.sign(m.to_owned(), &sk, &pk).unwrap();
+
+        assert!(!fdh.vfy(String::from("Hello World"), &sigma, &pk));
+        assert!(!fdh.vfy(m.to_owned(), &sigma, &pk_2));
+    }
 }
@@ -28,12 +28,13 @@ where
         D: serde::Deserializer<'de>,
     {
         /// This enum defines the content of the struct to be generated using [`Deserialize`]
-        const FIELDS: &[&str] = &["psf", "storage", "hash"];
+        const FIELDS: &[&str] = &["psf", "storage", "key_storage", "hash"];
         #[derive(Deserialize)]
-        #[serde(field_identifier, rename_all = "lowercase")]
+        #[serde(field_identifier, rename_all = "snake_case")]
         enum Field {
             Psf,
             Storage,
+            KeyStorage,
             Hash,
         }
 
@@ -66,6 +67,7 @@ where
             {
                 let mut psf = None;
                 let mut storage = None;
+                let mut key_storage = None;
                 let mut hash = None;
                 while let Some(key) = map.next_key()? {
                     match key {
@@ -81,6 +83,12 @@ where
                             }
                             storage = Some(map.next_value()?);
                         }
+                        Field::KeyStorage => {
+                            if key_storage.is_some() {
+                                return Err(Error::duplicate_field("key_storage"));
+                            }
+                            key_storage = Some(map.next_value()?);
+                        }
                         Field::Hash => {
                             if hash.is_some() {
                                 return Err(Error::duplicate_field("hash"));
@@ -93,6 +101,8 @@ where
                 Ok(FDH {
                     psf: Box::new(psf.unwrap()),
                     storage: storage.unwrap(),
+                    // instances serialized before multi-key support have no key storage
+                    key_storage: key_storage.unwrap_or_default(),
                     hash: Box::new(hash.unwrap()),
                     _a_type: PhantomData,
                     _trapdoor_type: PhantomData,
@@ -133,7 +143,7 @@ mod test_deserialization {
         // fill one entry in the HashMap
         let m = "Hello World!";
         let (pk, sk) = fdh.gen();
-        let _ = fdh.sign(m.to_owned(), &sk, &pk);
+        let _ = fdh.sign(m.to_owned(), &sk, &pk).unwrap();
 
         let fdh_string = serde_json::to_string(&fdh).expect("Unable to create a json object");
         let fdh_2: Result<FDH<MatZq, (MatZ, MatQ), MatZ, MatZq, PSFGPV, HashMatZq>, _> =
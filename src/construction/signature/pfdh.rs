@@ -13,7 +13,7 @@
 //! works with every PSF and a corresponding hash function.
 
 use super::SignatureScheme;
-use crate::{construction::hash::HashInto, primitive::psf::PSF};
+use crate::{construction::hash::HashInto, error::CryptoError, primitive::psf::PSF};
 use qfall_math::{integer::Z, traits::Pow};
 use serde::{Deserialize, Serialize};
 use std::marker::PhantomData;
@@ -50,7 +50,7 @@ pub mod serialize;
 /// let m = "Hello World!";
 ///
 /// let (pk, sk) = pfdh.gen();
-/// let sigma = pfdh.sign(m.to_owned(), &sk, &pk);
+/// let sigma = pfdh.sign(m.to_owned(), &sk, &pk).unwrap();
 ///
 /// assert!(pfdh.vfy(m.to_owned(), &sigma, &pk));
 /// ```
@@ -98,13 +98,25 @@ where
     /// Firstly generate randomness
     /// It hashes the message and randomness into the domain and then computes a signature using
     /// `samp_p` from the psf with the trapdoor.
-    fn sign(&mut self, m: String, sk: &Self::SecretKey, pk: &Self::PublicKey) -> Self::Signature {
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`CryptoError`] of type [`VerificationFailed`](CryptoError::VerificationFailed)
+    ///     if the computed signature is invalid under `pk`, e.g. as `sk` does not belong to `pk`.
+    fn sign(
+        &mut self,
+        m: String,
+        sk: &Self::SecretKey,
+        pk: &Self::PublicKey,
+    ) -> Result<Self::Signature, CryptoError> {
         let randomness =
             Z::sample_uniform(0, Z::from(2).pow(&self.randomness_length).unwrap()).unwrap();
         let u = (self.hash).hash(&format!("{m} {randomness} {}", &self.randomness_length));
-        let signature_part1 = self.psf.samp_p(pk, sk, &u);
+        let signature = (self.psf.samp_p(pk, sk, &u), randomness);
 
-        (signature_part1, randomness)
+        if !self.vfy(m, &signature, pk) {
+            return Err(CryptoError::VerificationFailed);
+        }
+        Ok(signature)
     }
 
     /// Checks if a signature is firstly within D_n, and then checks if
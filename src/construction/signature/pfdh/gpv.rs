@@ -45,7 +45,7 @@ impl PFDH<MatZq, (MatZ, MatQ), MatZ, MatZq, PSFGPV, HashMatZq> {
     /// let m = "Hello World!";
     ///
     /// let (pk, sk) = pfdh.gen();
-    /// let sigma = pfdh.sign(m.to_owned(), &sk, &pk);
+    /// let sigma = pfdh.sign(m.to_owned(), &sk, &pk).unwrap();
     ///
     /// assert!(pfdh.vfy(m.to_owned(), &sigma, &pk));
     /// ```
@@ -64,6 +64,8 @@ impl PFDH<MatZq, (MatZ, MatQ), MatZ, MatZq, PSFGPV, HashMatZq> {
         let psf = PSFGPV {
             gp: GadgetParameters::init_default(&n, &q),
             s,
+            cdt: None,
+            cdt_cache: Default::default(),
         };
         let n = i64::try_from(&n).unwrap();
         Self {
@@ -104,7 +106,7 @@ mod test_pfdh {
         for i in 0..10 {
             let m = format!("Hello World! {}", i);
 
-            let sigma = pfdh.sign(m.to_owned(), &sk, &pk);
+            let sigma = pfdh.sign(m.to_owned(), &sk, &pk).unwrap();
 
             assert!(pfdh.vfy(m.to_owned(), &sigma, &pk))
         }
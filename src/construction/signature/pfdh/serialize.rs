@@ -134,7 +134,7 @@ mod test_deserialization {
 
         let m = "Hello World!";
         let (pk, sk) = pfdh.gen();
-        let signature = pfdh.sign(m.to_owned(), &sk, &pk);
+        let signature = pfdh.sign(m.to_owned(), &sk, &pk).unwrap();
 
         let pfdh_string = serde_json::to_string(&pfdh).expect("Unable to create a json object");
         let pfdh_2: Result<PFDH<MatZq, (MatZ, MatQ), MatZ, MatZq, PSFGPV, HashMatZq>, _> =
@@ -144,7 +144,7 @@ mod test_deserialization {
 
         //ensure signing still works
         let mut pfdh_2 = pfdh_2.unwrap();
-        let signature_2 = pfdh_2.sign(m.to_owned(), &sk, &pk);
+        let signature_2 = pfdh_2.sign(m.to_owned(), &sk, &pk).unwrap();
 
         //ensure verification still works
         assert!(pfdh_2.vfy(m.to_string(), &signature, &pk));
@@ -0,0 +1,279 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains a verifiable random function (VRF) based on the
+//! GPV-PSF [`PSFGPV`], i.e. a function whose outputs come with a
+//! publicly verifiable proof.
+
+use crate::{
+    construction::hash::{sha256::HashMatZq, HashInto},
+    primitive::psf::{PSF, PSFGPV},
+    sample::g_trapdoor::gadget_parameters::GadgetParameters,
+};
+use dashmap::DashMap;
+use qfall_math::{
+    integer::{MatZ, Z},
+    integer_mod_q::{MatZq, Modulus},
+    rational::{MatQ, Q},
+    traits::Pow,
+};
+use serde::{Deserialize, Serialize};
+
+/// This struct manages and stores the public parameters of a verifiable
+/// random function based on [`PSFGPV`].
+///
+/// The output for an input `x` is `H(x)`, where `H` hashes into the range of the PSF,
+/// and its proof is a short preimage of `H(x)` sampled with the trapdoor.
+/// Similar to [`FDH`](crate::construction::signature::FDH), every proof is stored
+/// such that the same key and input always result in the same output and proof.
+///
+/// Attributes:
+/// - `psf`: specifies the PSF used for generating keys and proofs
+/// - `hash`: the hash function mapping inputs into the range of `psf`
+/// - `storage`: stores all previously computed proofs
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::construction::vrf::VRF;
+///
+/// let vrf = VRF::new_from_n(4);
+/// let (pk, sk) = vrf.gen();
+///
+/// let (output, proof) = vrf.prove(&sk, &pk, "input");
+///
+/// assert!(vrf.verify(&pk, "input", &output, &proof));
+/// ```
+#[derive(Serialize, Deserialize)]
+pub struct VRF {
+    pub psf: PSFGPV,
+    hash: HashMatZq,
+    storage: DashMap<String, MatZ>,
+}
+
+impl VRF {
+    /// Initializes a [`VRF`] instance for security parameter `n`.
+    ///
+    /// The modulus `q` and the Gaussian parameter `s` are chosen as in
+    /// [`DualRegevIBE::new_from_n`](crate::construction::identity_based_encryption::DualRegevIBE::new_from_n).
+    ///
+    /// **WARNING:** Due to the [`PSF`] this schemes prove algorithm is slow for n > 5.
+    ///
+    /// Parameters:
+    /// - `n`: the security parameter
+    ///
+    /// Returns a [`VRF`] instance.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::vrf::VRF;
+    ///
+    /// let vrf = VRF::new_from_n(4);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `n < 2`.
+    pub fn new_from_n(n: impl Into<Z>) -> Self {
+        let n: Z = n.into();
+        if n < Z::from(2) {
+            panic!("Security parameter n has to be larger than 1");
+        }
+
+        let n_i64 = i64::try_from(&n).unwrap();
+        // these powers are chosen according to experience s.t. at least every
+        // fifth generation of public parameters outputs a valid pair
+        let power = match n_i64 {
+            2..=3 => 10,
+            4 => 7,
+            5..=7 => 6,
+            _ => 5,
+        };
+
+        // generate prime q in [n^power / 2, n^power]
+        let upper_bound: Z = n.pow(power).unwrap();
+        let lower_bound = upper_bound.div_ceil(2);
+        let q = Modulus::from(Z::sample_prime_uniform(&lower_bound, &upper_bound).unwrap());
+
+        let gadget = GadgetParameters::init_default(&n, &q);
+        let log_q = Z::from(&q).log_ceil(2).unwrap();
+        let m = &gadget.m_bar + &n * &log_q;
+        let s: Q = m.sqrt();
+
+        Self {
+            psf: PSFGPV {
+                gp: gadget,
+                s,
+                cdt: None,
+                cdt_cache: Default::default(),
+            },
+            hash: HashMatZq {
+                modulus: q,
+                rows: n_i64,
+                cols: 1,
+            },
+            storage: DashMap::new(),
+        }
+    }
+
+    /// Generates a key pair by calling `trap_gen` of the PSF.
+    ///
+    /// Returns the public key `A` and the trapdoor of `A` as secret key.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::vrf::VRF;
+    ///
+    /// let vrf = VRF::new_from_n(4);
+    /// let (pk, sk) = vrf.gen();
+    /// ```
+    pub fn gen(&self) -> (MatZq, (MatZ, MatQ)) {
+        self.psf.trap_gen()
+    }
+
+    /// Evaluates the VRF on `input` and proves the correctness of the output.
+    /// If `input` was evaluated for `pk` before, the stored proof is returned.
+    ///
+    /// Parameters:
+    /// - `sk`: the trapdoor of `pk`
+    /// - `pk`: the public key
+    /// - `input`: the input on which the VRF is evaluated
+    ///
+    /// Returns the output `H(input)` and a short preimage of it under `f_pk` as proof.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::vrf::VRF;
+    ///
+    /// let vrf = VRF::new_from_n(4);
+    /// let (pk, sk) = vrf.gen();
+    ///
+    /// let (output, proof) = vrf.prove(&sk, &pk, "input");
+    /// ```
+    pub fn prove(&self, sk: &(MatZ, MatQ), pk: &MatZq, input: &str) -> (MatZq, MatZ) {
+        let output = self.hash.hash(input);
+
+        let proof = self
+            .storage
+            .entry(format!("{pk} {input}"))
+            .or_insert_with(|| self.psf.samp_p(pk, sk, &output))
+            .clone();
+
+        (output, proof)
+    }
+
+    /// Verifies that `output` is the output of the VRF on `input` for `pk`
+    /// by checking that `output = H(input)`, `proof` is in the domain, and
+    /// `f_pk(proof) = output`.
+    ///
+    /// Parameters:
+    /// - `pk`: the public key
+    /// - `input`: the input on which the VRF was evaluated
+    /// - `output`: the claimed output
+    /// - `proof`: the proof of `output`
+    ///
+    /// Returns `true` if the proof is valid and `false` otherwise.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::vrf::VRF;
+    ///
+    /// let vrf = VRF::new_from_n(4);
+    /// let (pk, sk) = vrf.gen();
+    /// let (output, proof) = vrf.prove(&sk, &pk, "input");
+    ///
+    /// assert!(vrf.verify(&pk, "input", &output, &proof));
+    /// assert!(!vrf.verify(&pk, "other input", &output, &proof));
+    /// ```
+    pub fn verify(&self, pk: &MatZq, input: &str, output: &MatZq, proof: &MatZ) -> bool {
+        if !self.psf.check_domain(proof) || &self.hash.hash(input) != output {
+            return false;
+        }
+
+        &self.psf.f_a(pk, proof) == output
+    }
+}
+
+impl Default for VRF {
+    /// Initializes a [`VRF`] instance with `VRF::new_from_n(4)`.
+    /// This parameter choice is not secure as the dimension of the lattice is too small,
+    /// but it provides an efficient working example.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::vrf::VRF;
+    ///
+    /// let vrf = VRF::default();
+    /// ```
+    fn default() -> Self {
+        Self::new_from_n(4)
+    }
+}
+
+#[cfg(test)]
+mod test_vrf {
+    use super::VRF;
+    use qfall_math::{
+        integer::{MatZ, Z},
+        traits::GetNumRows,
+    };
+
+    /// Ensures that honestly generated outputs and proofs are accepted.
+    #[test]
+    fn correctness() {
+        let vrf = VRF::default();
+        let (pk, sk) = vrf.gen();
+
+        for input in ["", "input", "Hello World!"] {
+            let (output, proof) = vrf.prove(&sk, &pk, input);
+            assert!(vrf.verify(&pk, input, &output, &proof));
+        }
+    }
+
+    /// Ensures that the same key and input always result in the same output and proof.
+    #[test]
+    fn deterministic() {
+        let vrf = VRF::default();
+        let (pk, sk) = vrf.gen();
+
+        let (output_1, proof_1) = vrf.prove(&sk, &pk, "input");
+        let (output_2, proof_2) = vrf.prove(&sk, &pk, "input");
+
+        assert_eq!(output_1, output_2);
+        assert_eq!(proof_1, proof_2);
+    }
+
+    /// Ensures that different inputs result in different outputs and proofs,
+    /// which are not accepted for the other input.
+    #[test]
+    fn different_inputs() {
+        let vrf = VRF::default();
+        let (pk, sk) = vrf.gen();
+
+        let (output_1, proof_1) = vrf.prove(&sk, &pk, "input 1");
+        let (output_2, proof_2) = vrf.prove(&sk, &pk, "input 2");
+
+        assert_ne!(output_1, output_2);
+        assert_ne!(proof_1, proof_2);
+        assert!(!vrf.verify(&pk, "input 2", &output_1, &proof_1));
+        assert!(!vrf.verify(&pk, "input 1", &output_2, &proof_2));
+    }
+
+    /// Ensures that proofs outside of the domain and proofs for another key are rejected.
+    #[test]
+    fn invalid_proof() {
+        let vrf = VRF::default();
+        let (pk, sk) = vrf.gen();
+        let (other_pk, _) = vrf.gen();
+        let (output, proof) = vrf.prove(&sk, &pk, "input");
+
+        let zero = MatZ::new(proof.get_num_rows(), 1);
+
+        assert!(!vrf.verify(&other_pk, "input", &output, &proof));
+        assert!(!vrf.verify(&pk, "input", &output, &zero));
+        assert!(!vrf.verify(&pk, "input", &output, &(Z::from(1000) * &proof)));
+    }
+}
@@ -0,0 +1,76 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains the type [`CryptoError`] that covers all errors,
+//! which can occur while using constructions of this crate and are not
+//! covered by [`MathError`] directly.
+
+use qfall_math::error::MathError;
+use thiserror::Error;
+
+/// [`CryptoError`] defines all errors, which can be returned by
+/// constructions of this crate.
+///
+/// Possible entries:
+/// - `InvalidParameters` is thrown if a set of parameters does not satisfy
+///     the requirements of the construction, e.g. security or correctness
+/// - `DecryptionFailed` is thrown if a ciphertext could not be decrypted
+///     to a valid message
+/// - `InvalidParameterFile` is thrown if a parameter file could not be parsed
+/// - `SelfTestFailure` is thrown if a check of the self-test failed
+/// - `CiphertextReplayed` is thrown if a ciphertext was already seen recently
+/// - `VerificationFailed` is thrown if a signature could not be verified
+/// - `KeyExtractionFailed` is thrown if no valid secret key could be extracted
+/// - `IncompatibleParameters` is thrown if keys or ciphertexts do not belong
+///     to the parameters of the instance they are used with
+/// - `Io` is thrown if reading or writing a file failed
+/// - `Math` wraps a [`MathError`] returned by an underlying function
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::error::CryptoError;
+///
+/// let err = CryptoError::InvalidParameters {
+///     message: String::from("n must be larger than 1"),
+/// };
+/// assert_eq!("invalid parameters: n must be larger than 1", err.to_string());
+/// ```
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum CryptoError {
+    /// parameters do not satisfy the requirements of the construction
+    #[error("invalid parameters: {message}")]
+    InvalidParameters { message: String },
+    /// a ciphertext could not be decrypted to a valid message
+    #[error("decryption failed")]
+    DecryptionFailed,
+    /// a parameter file could not be parsed
+    #[error("invalid parameter file: {0}")]
+    InvalidParameterFile(String),
+    /// a check of the self-test failed
+    #[error("self-test failed: {0}")]
+    SelfTestFailure(String),
+    /// a ciphertext was already seen within the freshness window
+    #[error("the ciphertext was replayed")]
+    CiphertextReplayed,
+    /// a signature could not be verified
+    #[error("verification failed")]
+    VerificationFailed,
+    /// no valid secret key could be extracted
+    #[error("key extraction failed")]
+    KeyExtractionFailed,
+    /// keys or ciphertexts do not belong to the parameters of the instance
+    #[error("incompatible parameters")]
+    IncompatibleParameters,
+    /// reading or writing a file failed
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    /// an error of an underlying function of qFALL-math
+    #[error(transparent)]
+    Math(#[from] MathError),
+}
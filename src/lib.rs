@@ -22,6 +22,22 @@
 //! Our library has further primitives useful for prototyping such as
 //! [`PSFs`](primitive::psf::PSF) that can be used to implement constructions.
 //!
+//! ## Features
+//! - `logging`: emits spans and events via the [`tracing`](https://docs.rs/tracing) crate
+//!     for key generation, extraction, encryption, decryption, signing and verification
+//!     of [`DualRegevIBE`](construction::identity_based_encryption::DualRegevIBE)
+//!     and [`FDH`](construction::signature::FDH). Messages that are decrypted are never logged.
+//! - `side_channel_resistant`: blinds the multiplication with the short basis in
+//!     [`PSFGPV::samp_p`](primitive::psf::PSF::samp_p),
+//!     see [`blinded_inner_product`](utils::constant_time::blinded_inner_product).
+//!     If no table was precomputed, the first call computes a CDT over
+//!     `[-⌈12 * sqrt(2) * s⌉, ⌈12 * sqrt(2) * s⌉]`, i.e. about `34 * s` entries,
+//!     which is cached in `cdt_cache` and reused by all further calls.
+//! - `experimental_threshold_ibe`: enables the threshold variant `ThresholdIBE` of
+//!     [`DualRegevIBE`](construction::identity_based_encryption::DualRegevIBE).
+//!     **WARNING:** Its identity keys are deterministic and leak the master trapdoor,
+//!     i.e. it is insecure and only meant for prototyping distributed extraction.
+//!
 //! qFALL-crypto is free software: you can redistribute it and/or modify it under
 //! the terms of the Mozilla Public License Version 2.0 as published by the
 //! Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
@@ -33,7 +49,11 @@
 //! qFALL-crypto is co-developed together with qFALL-math which provides the basic
 //! foundation that is used to implement the cryptographic constructions.
 
+pub mod analysis;
 pub mod construction;
+pub mod error;
+pub mod params;
 pub mod primitive;
 pub mod sample;
+pub mod selftest;
 pub mod utils;
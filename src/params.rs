@@ -0,0 +1,215 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains standard parameter sets for the NIST security levels 1, 3 and 5,
+//! which target the security of Dilithium2, Dilithium3 and Dilithium5, i.e.
+//! at least 128, 192 and 256 bits of classical security according to
+//! [`overall_security`].
+//!
+//! All parameter sets are chosen s.t. [`DualRegevIBE`](crate::construction::identity_based_encryption::DualRegevIBE)
+//! and [`DualRegev`](crate::construction::pk_encryption::DualRegev) pass their
+//! security and correctness checks.
+//! Hence, `q >= 5 * σ * (m + 1)`, `σ = ⌈sqrt(m)⌉` and `α` is rounded down to three significant
+//! digits of `1/(2 * σ * sqrt(m + 1) * log(n))`.
+//!
+//! **WARNING:** The matrices of these parameter sets have more than `40000` columns.
+//! Generating keys is therefore very slow.
+
+use crate::analysis::security::overall_security;
+use serde::{Deserialize, Serialize};
+
+/// The public parameters of a NIST security level.
+///
+/// Attributes:
+/// - `n`: the security parameter, i.e. the number of rows of the public matrix
+/// - `q`: the prime modulus
+/// - `sigma`: the Gaussian parameter used for preimage sampling
+/// - `alpha`: the relative Gaussian parameter of the LWE error
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct NistParams {
+    pub n: usize,
+    pub q: u64,
+    pub sigma: f64,
+    pub alpha: f64,
+}
+
+/// Parameters targeting the security of Dilithium2, i.e. NIST level 1.
+pub const NIST_LEVEL_1: NistParams = NistParams {
+    n: 864,
+    q: 67108879,
+    sigma: 217.0,
+    alpha: 0.00000109,
+};
+
+/// Parameters targeting the security of Dilithium3, i.e. NIST level 3.
+pub const NIST_LEVEL_3: NistParams = NistParams {
+    n: 1216,
+    q: 134217757,
+    sigma: 262.0,
+    alpha: 0.000000712,
+};
+
+/// Parameters targeting the security of Dilithium5, i.e. NIST level 5.
+pub const NIST_LEVEL_5: NistParams = NistParams {
+    n: 1536,
+    q: 268435459,
+    sigma: 299.0,
+    alpha: 0.000000528,
+};
+
+/// The NIST security levels, for which parameter sets are provided.
+///
+/// Possible entries:
+/// - `Level1`: uses [`NIST_LEVEL_1`]
+/// - `Level3`: uses [`NIST_LEVEL_3`]
+/// - `Level5`: uses [`NIST_LEVEL_5`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NistLevel {
+    Level1,
+    Level3,
+    Level5,
+}
+
+impl NistLevel {
+    /// Returns the parameter set of the security level.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::params::{NistLevel, NIST_LEVEL_3};
+    ///
+    /// assert_eq!(NIST_LEVEL_3, NistLevel::Level3.params());
+    /// ```
+    pub fn params(&self) -> NistParams {
+        match self {
+            NistLevel::Level1 => NIST_LEVEL_1,
+            NistLevel::Level3 => NIST_LEVEL_3,
+            NistLevel::Level5 => NIST_LEVEL_5,
+        }
+    }
+
+    /// Returns the bit security targeted by the security level, i.e. `128`, `192` or `256`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::params::NistLevel;
+    ///
+    /// assert_eq!(192, NistLevel::Level3.target_bits());
+    /// ```
+    pub fn target_bits(&self) -> u32 {
+        match self {
+            NistLevel::Level1 => 128,
+            NistLevel::Level3 => 192,
+            NistLevel::Level5 => 256,
+        }
+    }
+}
+
+impl NistParams {
+    /// Returns the number of columns `m = m_bar + n * ⌈log q⌉` of a public key generated
+    /// with [`GadgetParameters::init_default`](crate::sample::g_trapdoor::gadget_parameters::GadgetParameters::init_default),
+    /// where `m_bar = n * ⌈log q⌉ + ⌈log n⌉^2`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::params::NIST_LEVEL_1;
+    ///
+    /// let m = NIST_LEVEL_1.m();
+    /// ```
+    pub fn m(&self) -> usize {
+        let log_q = (u64::BITS - (self.q - 1).leading_zeros()) as usize;
+        let log_n = (usize::BITS - (self.n - 1).leading_zeros()) as usize;
+
+        2 * self.n * log_q + log_n * log_n
+    }
+
+    /// Estimates the classical bit security of the LWE instance as the minimum
+    /// of the estimates of the primal and the dual attack of [`overall_security`].
+    /// As in [`recommend_params`](crate::analysis::recommend::recommend_params),
+    /// the attacks are estimated with at most `2n` samples.
+    ///
+    /// Returns the estimated bit security.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::params::NIST_LEVEL_1;
+    ///
+    /// assert!(NIST_LEVEL_1.estimate_security() >= 128.0);
+    /// ```
+    pub fn estimate_security(&self) -> f64 {
+        let estimate =
+            overall_security(self.n, self.m().min(2 * self.n), self.alpha, self.q as f64);
+
+        estimate.primal.min(estimate.dual)
+    }
+}
+
+#[cfg(test)]
+mod test_nist_params {
+    use super::{NistLevel, NIST_LEVEL_1};
+    use crate::{
+        construction::{
+            identity_based_encryption::DualRegevIBE, pk_encryption::DualRegev, signature::FDH,
+        },
+        sample::g_trapdoor::gadget_parameters::GadgetParameters,
+    };
+    use qfall_math::{integer::Z, rational::Q};
+
+    const LEVELS: [NistLevel; 3] = [NistLevel::Level1, NistLevel::Level3, NistLevel::Level5];
+
+    /// Ensures that `m` matches the number of columns used by [`DualRegevIBE`].
+    #[test]
+    fn m_matches_gadget_parameters() {
+        for level in LEVELS {
+            let params = level.params();
+            let gp = GadgetParameters::init_default(params.n as u64, params.q);
+
+            let m = &gp.m_bar + Z::from(params.n as u64) * &gp.k;
+
+            assert_eq!(Z::from(params.m() as u64), m);
+        }
+    }
+
+    /// Ensures that [`DualRegevIBE`] passes its security and correctness checks for all levels.
+    #[test]
+    fn dual_regev_ibe_valid() {
+        for level in LEVELS {
+            let ibe = DualRegevIBE::from_nist_level(level);
+
+            assert!(ibe.check_security().is_ok());
+            assert!(ibe.check_correctness().is_ok());
+        }
+    }
+
+    /// Ensures that [`DualRegev`] passes its security and correctness checks for all levels.
+    #[test]
+    fn dual_regev_valid() {
+        for level in LEVELS {
+            let dual_regev = DualRegev::from_nist_level(level);
+
+            assert!(dual_regev.check_security().is_ok());
+            assert!(dual_regev.check_correctness().is_ok());
+        }
+    }
+
+    /// Ensures that [`FDH`] uses the Gaussian parameter of the level.
+    #[test]
+    fn fdh_parameters() {
+        let fdh = FDH::from_nist_level(NistLevel::Level1);
+
+        assert_eq!(Q::from(NIST_LEVEL_1.sigma), fdh.psf.s);
+        assert_eq!(Z::from(NIST_LEVEL_1.n as u64), fdh.psf.gp.n);
+    }
+
+    /// Ensures that the estimated security reaches the targeted security of all levels.
+    #[test]
+    fn estimate_security() {
+        for level in LEVELS {
+            assert!(level.params().estimate_security() >= level.target_bits() as f64);
+        }
+    }
+}
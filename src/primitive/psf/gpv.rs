@@ -10,9 +10,17 @@
 //! using G-Trapdoors to generate a short basis and corresponding trapdoor.
 
 use super::PSF;
-use crate::sample::g_trapdoor::{
-    gadget_classical::gen_trapdoor, gadget_parameters::GadgetParameters,
-    short_basis_classical::gen_short_basis_for_trapdoor,
+#[cfg(feature = "side_channel_resistant")]
+use crate::utils::{constant_time::blinded_inner_product, math_util::ZqArith};
+use crate::{
+    sample::{
+        cdt::CdtGaussianSampler,
+        g_trapdoor::{
+            gadget_classical::gen_trapdoor, gadget_parameters::GadgetParameters,
+            short_basis_classical::gen_short_basis_for_trapdoor,
+        },
+    },
+    utils::conversion::q_to_f64,
 };
 use qfall_math::{
     integer::{MatZ, Z},
@@ -20,16 +28,32 @@ use qfall_math::{
     rational::{MatQ, Q},
     traits::{GetNumRows, Pow},
 };
+#[cfg(feature = "side_channel_resistant")]
+use qfall_math::{
+    integer_mod_q::{Modulus, Zq},
+    traits::{GetEntry, GetNumColumns, SetEntry},
+};
 use serde::{Deserialize, Serialize};
+use std::{f64::consts::SQRT_2, sync::OnceLock};
 
 /// A lattice-based implementation of a [`PSF`] according to
 /// [\[1\]](<index.html#:~:text=[1]>) using
 /// G-Trapdoors where D_n = {e ∈ Z^m | |e| <= s sqrt(m)}
 /// and R_n = Z_q^n.
 ///
+/// [`PSFGPV`] does not store any secret, as the trapdoor is returned by [`PSF::trap_gen`].
+/// Hence, it does not implement [`Zeroize`](zeroize::Zeroize). Instead, the trapdoor
+/// should be wrapped in a [`ZeroizingWrapper`](crate::utils::zeroize::ZeroizingWrapper).
+///
 /// Attributes
 /// - `gp`: Describes the gadget parameters with which the G-Trapdoor is generated
 /// - `s`: The Gaussian parameter with which is sampled
+/// - `cdt`: An optional precomputed [`CdtGaussianSampler`] used by [`PSF::samp_p`],
+///   see [`PSFGPV::with_precomputed_cdt`]. It is not serialized.
+/// - `cdt_cache`: The [`CdtGaussianSampler`] computed on the first call of [`PSF::samp_p`]
+///   if the feature `side_channel_resistant` is enabled and `cdt` is `None`.
+///   It is reused by all further calls, hence `s` must not be changed afterwards.
+///   It is not serialized and initialized with `Default::default()`.
 ///
 /// # Examples
 /// ```
@@ -41,6 +65,8 @@ use serde::{Deserialize, Serialize};
 /// let psf = PSFGPV {
 ///     gp: GadgetParameters::init_default(8, 64),
 ///     s: Q::from(12),
+///     cdt: None,
+///     cdt_cache: Default::default(),
 /// };
 ///
 /// let (a, td) = psf.trap_gen();
@@ -54,6 +80,104 @@ use serde::{Deserialize, Serialize};
 pub struct PSFGPV {
     pub gp: GadgetParameters,
     pub s: Q,
+    #[serde(skip)]
+    pub cdt: Option<CdtGaussianSampler>,
+    #[serde(skip)]
+    pub cdt_cache: OnceLock<CdtGaussianSampler>,
+}
+
+impl PSFGPV {
+    /// Precomputes a cumulative distribution table s.t. [`PSF::samp_p`]
+    /// samples with [`CdtGaussianSampler::sample_d`] instead of rejection sampling.
+    ///
+    /// In Klein's algorithm, the `i`-th coefficient is sampled with Gaussian parameter
+    /// `s / |b_i*|`, where `b_i*` is the `i`-th vector of the GSO of the short basis.
+    /// The table is computed for `sqrt(2) * s`, s.t. it serves as proposal distribution
+    /// for every coefficient whose parameter is smaller than `sqrt(2) * s`
+    /// with an acceptance rate of at least `1/sqrt(2)`.
+    /// All other coefficients fall back to rejection sampling.
+    ///
+    /// Returns the [`PSFGPV`] with a precomputed table for `s`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::primitive::psf::PSFGPV;
+    /// use qfall_crypto::sample::g_trapdoor::gadget_parameters::GadgetParameters;
+    /// use qfall_math::rational::Q;
+    /// use qfall_crypto::primitive::psf::PSF;
+    ///
+    /// let psf = PSFGPV {
+    ///     gp: GadgetParameters::init_default(8, 64),
+    ///     s: Q::from(12),
+    ///     cdt: None,
+    ///     cdt_cache: Default::default(),
+    /// }
+    /// .with_precomputed_cdt();
+    ///
+    /// let (a, td) = psf.trap_gen();
+    /// let domain_sample = psf.samp_d();
+    /// let range_fa = psf.f_a(&a, &domain_sample);
+    /// let preimage = psf.samp_p(&a, &td, &range_fa);
+    ///
+    /// assert_eq!(range_fa, psf.f_a(&a, &preimage));
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `s <= 0`.
+    pub fn with_precomputed_cdt(mut self) -> Self {
+        self.cdt = Some(CdtGaussianSampler::new(q_to_f64(&self.s) * SQRT_2));
+        self
+    }
+
+    /// Samples a lattice vector of `Λ(short_base)` from the discrete Gaussian
+    /// distribution with parameter `s` and `center` using Klein's algorithm.
+    /// The precomputed table of [`PSFGPV::with_precomputed_cdt`] is used if available.
+    #[cfg(not(feature = "side_channel_resistant"))]
+    fn sample_lattice_vector(
+        &self,
+        short_base: &MatZ,
+        short_base_gso: &MatQ,
+        center: &MatQ,
+    ) -> MatZ {
+        if let Some(cdt) = &self.cdt {
+            return cdt.sample_d_precomputed_gso(
+                short_base,
+                short_base_gso,
+                &self.gp.n,
+                center,
+                &self.s,
+            );
+        }
+
+        MatZ::sample_d_precomputed_gso(short_base, short_base_gso, &self.gp.n, center, &self.s)
+            .unwrap()
+    }
+
+    /// Samples a lattice vector of `Λ(short_base)` from the discrete Gaussian
+    /// distribution with parameter `s` and `center` using Klein's algorithm,
+    /// where the product of the short base with the sampled coefficients is blinded.
+    /// If no table was precomputed with [`PSFGPV::with_precomputed_cdt`],
+    /// a table is computed on the first call and cached in `cdt_cache`,
+    /// as the coefficients are required.
+    #[cfg(feature = "side_channel_resistant")]
+    fn sample_lattice_vector(
+        &self,
+        short_base: &MatZ,
+        short_base_gso: &MatQ,
+        center: &MatQ,
+    ) -> MatZ {
+        let cdt = match &self.cdt {
+            Some(cdt) => cdt,
+            None => self
+                .cdt_cache
+                .get_or_init(|| CdtGaussianSampler::new(q_to_f64(&self.s) * SQRT_2)),
+        };
+
+        blinded_lattice_vector(
+            short_base,
+            &cdt.sample_coefficients(short_base, short_base_gso, &self.gp.n, center, &self.s),
+        )
+    }
 }
 
 impl PSF<MatZq, (MatZ, MatQ), MatZ, MatZq> for PSFGPV {
@@ -71,6 +195,8 @@ impl PSF<MatZq, (MatZ, MatQ), MatZ, MatZq> for PSFGPV {
     /// let psf = PSFGPV {
     ///     gp: GadgetParameters::init_default(8, 64),
     ///     s: Q::from(12),
+    ///     cdt: None,
+    ///     cdt_cache: Default::default(),
     /// };
     ///
     /// let (a, (sh_b, sh_b_gso)) = psf.trap_gen();
@@ -100,6 +226,8 @@ impl PSF<MatZq, (MatZ, MatQ), MatZ, MatZq> for PSFGPV {
     /// let psf = PSFGPV {
     ///     gp: GadgetParameters::init_default(8, 64),
     ///     s: Q::from(12),
+    ///     cdt: None,
+    ///     cdt_cache: Default::default(),
     /// };
     /// let (a, td) = psf.trap_gen();
     ///
@@ -125,6 +253,11 @@ impl PSF<MatZq, (MatZ, MatQ), MatZ, MatZq> for PSFGPV {
     ///
     /// Returns a sample `e` from the domain on the conditioned discrete
     /// Gaussian distribution `f_a(a,e) = u`.
+    /// If a table was precomputed with [`PSFGPV::with_precomputed_cdt`],
+    /// it is used for sampling. If the feature `side_channel_resistant`
+    /// is enabled, the multiplication of the short base with the sampled coefficients
+    /// is blinded using [`blinded_inner_product`](crate::utils::constant_time::blinded_inner_product),
+    /// where a table is computed on the first call and reused if none was precomputed.
     ///
     /// # Examples
     /// ```
@@ -136,6 +269,8 @@ impl PSF<MatZq, (MatZ, MatQ), MatZ, MatZq> for PSFGPV {
     /// let psf = PSFGPV {
     ///     gp: GadgetParameters::init_default(8, 64),
     ///     s: Q::from(12),
+    ///     cdt: None,
+    ///     cdt_cache: Default::default(),
     /// };
     /// let (a, td) = psf.trap_gen();
     /// let domain_sample = psf.samp_d();
@@ -149,14 +284,7 @@ impl PSF<MatZq, (MatZ, MatQ), MatZ, MatZq> for PSFGPV {
 
         let center = MatQ::from(&(-1 * &sol));
 
-        sol + MatZ::sample_d_precomputed_gso(
-            short_base,
-            short_base_gso,
-            &self.gp.n,
-            &center,
-            &self.s,
-        )
-        .unwrap()
+        sol + self.sample_lattice_vector(short_base, short_base_gso, &center)
     }
 
     /// Implements the efficiently computable function `f_a` which here corresponds to
@@ -178,6 +306,8 @@ impl PSF<MatZq, (MatZ, MatQ), MatZ, MatZq> for PSFGPV {
     /// let psf = PSFGPV {
     ///     gp: GadgetParameters::init_default(8, 64),
     ///     s: Q::from(12),
+    ///     cdt: None,
+    ///     cdt_cache: Default::default(),
     /// };
     /// let (a, td) = psf.trap_gen();
     /// let domain_sample = psf.samp_d();
@@ -208,6 +338,8 @@ impl PSF<MatZq, (MatZ, MatQ), MatZ, MatZq> for PSFGPV {
     /// let psf = PSFGPV {
     ///     gp: GadgetParameters::init_default(8, 64),
     ///     s: Q::from(12),
+    ///     cdt: None,
+    ///     cdt_cache: Default::default(),
     /// };
     /// let (a, td) = psf.trap_gen();
     ///
@@ -223,14 +355,55 @@ impl PSF<MatZq, (MatZ, MatQ), MatZ, MatZq> for PSFGPV {
     }
 }
 
+/// Computes the lattice vector `B * z` using [`blinded_inner_product`] s.t.
+/// the short base `B` is hidden in the intermediate values.
+/// The product is computed modulo `2 * max|B_ij| * |z|_1 + 2`, which is larger than
+/// twice the absolute value of every entry of `B * z`, and lifted to its centered representative.
+#[cfg(feature = "side_channel_resistant")]
+fn blinded_lattice_vector(short_base: &MatZ, coefficients: &MatZ) -> MatZ {
+    let max_entry = (0..short_base.get_num_rows())
+        .flat_map(|i| (0..short_base.get_num_columns()).map(move |j| (i, j)))
+        .fold(Z::ZERO, |max, (i, j)| {
+            let entry: Z = short_base.get_entry(i, j).unwrap();
+            if entry.abs() > max {
+                entry.abs()
+            } else {
+                max
+            }
+        });
+    let l1_norm = (0..coefficients.get_num_rows()).fold(Z::ZERO, |sum, i| {
+        let entry: Z = coefficients.get_entry(i, 0).unwrap();
+        sum + entry.abs()
+    });
+    let modulus = Z::from(2) * max_entry * l1_norm + Z::from(2);
+    let q = Modulus::from(&modulus);
+
+    let product = blinded_inner_product(
+        &short_base.transpose(),
+        &MatZq::from((coefficients, &q)),
+        &mut rand::thread_rng(),
+    );
+
+    let mut out = MatZ::new(product.get_num_rows(), 1);
+    for i in 0..product.get_num_rows() {
+        let entry: Zq = product.get_entry(i, 0).unwrap();
+        let entry = ZqArith::reduce_centered(&entry.get_representative_0_modulus(), &modulus);
+        out.set_entry(i, 0, entry).unwrap();
+    }
+
+    out
+}
+
 #[cfg(test)]
 mod test_gpv_psf {
     use super::super::gpv::PSFGPV;
     use super::PSF;
-    use crate::sample::g_trapdoor::gadget_parameters::GadgetParameters;
-    use qfall_math::integer::MatZ;
+    use crate::sample::{
+        distribution::chi_squared_gaussian_test, g_trapdoor::gadget_parameters::GadgetParameters,
+    };
+    use qfall_math::integer::{MatZ, Z};
     use qfall_math::rational::Q;
-    use qfall_math::traits::{GetNumColumns, GetNumRows, SetEntry};
+    use qfall_math::traits::{GetEntry, GetNumColumns, GetNumRows, SetEntry};
 
     /// Ensures that `samp_d` actually computes values that are in D_n.
     #[test]
@@ -239,6 +412,8 @@ mod test_gpv_psf {
             let psf = PSFGPV {
                 gp: GadgetParameters::init_default(n, q),
                 s: Q::from(10),
+                cdt: None,
+                cdt_cache: Default::default(),
             };
 
             for _ in 0..5 {
@@ -255,6 +430,8 @@ mod test_gpv_psf {
             let psf = PSFGPV {
                 gp: GadgetParameters::init_default(n, q),
                 s: Q::from(10),
+                cdt: None,
+                cdt_cache: Default::default(),
             };
             let (a, r) = psf.trap_gen();
             let domain_sample = psf.samp_d();
@@ -266,6 +443,63 @@ mod test_gpv_psf {
         }
     }
 
+    /// Ensures that `samp_p` with a precomputed table computes preimages that are
+    /// also in the correct domain.
+    #[test]
+    fn samp_p_precomputed_cdt() {
+        for (n, q) in [(5, 256), (6, 128)] {
+            let psf = PSFGPV {
+                gp: GadgetParameters::init_default(n, q),
+                s: Q::from(10),
+                cdt: None,
+                cdt_cache: Default::default(),
+            }
+            .with_precomputed_cdt();
+            let (a, r) = psf.trap_gen();
+
+            for _ in 0..3 {
+                let domain_sample = psf.samp_d();
+                let range_fa = psf.f_a(&a, &domain_sample);
+
+                let preimage = psf.samp_p(&a, &r, &range_fa);
+                assert_eq!(range_fa, psf.f_a(&a, &preimage));
+                assert!(psf.check_domain(&preimage));
+            }
+        }
+    }
+
+    /// Ensures that every coordinate of preimages sampled by `samp_p` passes a
+    /// chi-squared test for `D_{Z, s}`.
+    /// The confidence level of each coordinate is corrected by the number of
+    /// coordinates, s.t. the test fails with probability about `1%`.
+    #[test]
+    fn samp_p_coordinates_gaussian() {
+        let psf = PSFGPV {
+            gp: GadgetParameters::init_default(2, 64),
+            s: Q::from(50),
+            cdt: None,
+            cdt_cache: Default::default(),
+        };
+        let (a, r) = psf.trap_gen();
+        let range_fa = psf.f_a(&a, &psf.samp_d());
+
+        let preimages: Vec<MatZ> = (0..10_000).map(|_| psf.samp_p(&a, &r, &range_fa)).collect();
+
+        let m = preimages[0].get_num_rows();
+        let confidence = 1.0 - 0.01 / m as f64;
+        for i in 0..m {
+            let coordinates: Vec<i64> = preimages
+                .iter()
+                .map(|preimage| {
+                    let entry: Z = preimage.get_entry(i, 0).unwrap();
+                    i64::try_from(&entry).unwrap()
+                })
+                .collect();
+
+            assert!(chi_squared_gaussian_test(&coordinates, 50.0, confidence));
+        }
+    }
+
     /// Ensures that `f_a` returns `a*sigma`.
     #[test]
     fn f_a_works_as_expected() {
@@ -273,6 +507,8 @@ mod test_gpv_psf {
             let psf = PSFGPV {
                 gp: GadgetParameters::init_default(n, q),
                 s: Q::from(10),
+                cdt: None,
+                cdt_cache: Default::default(),
             };
             let (a, _) = psf.trap_gen();
             let domain_sample = psf.samp_d();
@@ -289,6 +525,8 @@ mod test_gpv_psf {
         let psf = PSFGPV {
             gp: GadgetParameters::init_default(8, 128),
             s: Q::from(10),
+            cdt: None,
+            cdt_cache: Default::default(),
         };
         let (a, _) = psf.trap_gen();
         let not_in_domain = MatZ::new(a.get_num_columns(), 2);
@@ -304,6 +542,8 @@ mod test_gpv_psf {
         let psf = PSFGPV {
             gp: GadgetParameters::init_default(8, 128),
             s: Q::from(10),
+            cdt: None,
+            cdt_cache: Default::default(),
         };
         let (a, _) = psf.trap_gen();
         let not_in_domain = MatZ::new(a.get_num_columns() - 1, 1);
@@ -319,6 +559,8 @@ mod test_gpv_psf {
         let psf = PSFGPV {
             gp: GadgetParameters::init_default(8, 128),
             s: Q::from(10),
+            cdt: None,
+            cdt_cache: Default::default(),
         };
         let (a, _) = psf.trap_gen();
         let not_in_domain =
@@ -333,6 +575,8 @@ mod test_gpv_psf {
         let psf = PSFGPV {
             gp: GadgetParameters::init_default(8, 128),
             s: Q::from(10),
+            cdt: None,
+            cdt_cache: Default::default(),
         };
         let (a, _) = psf.trap_gen();
         let value = psf.s.round();
@@ -351,6 +595,8 @@ mod test_gpv_psf {
         let psf = PSFGPV {
             gp: GadgetParameters::init_default(8, 128),
             s: Q::from(10),
+            cdt: None,
+            cdt_cache: Default::default(),
         };
         let (a, _) = psf.trap_gen();
 
@@ -365,4 +611,46 @@ mod test_gpv_psf {
         assert!(!psf.check_domain(&too_short));
         assert!(!psf.check_domain(&entry_too_large));
     }
+
+    /// Ensures that the blinded computation of the lattice vector
+    /// matches the unblinded product.
+    #[cfg(feature = "side_channel_resistant")]
+    #[test]
+    fn blinded_lattice_vector_matches() {
+        let basis = MatZ::sample_uniform(10, 10, -20, 20).unwrap();
+        let coefficients = MatZ::sample_uniform(10, 1, -30, 30).unwrap();
+
+        assert_eq!(
+            &basis * &coefficients,
+            super::blinded_lattice_vector(&basis, &coefficients)
+        );
+    }
+
+    /// Ensures that `samp_p` of a [`PSFGPV`] without precomputed table
+    /// computes valid preimages using the blinded computation.
+    #[cfg(feature = "side_channel_resistant")]
+    #[test]
+    fn samp_p_blinded_without_cdt() {
+        let psf = PSFGPV {
+            gp: GadgetParameters::init_default(5, 256),
+            s: Q::from(10),
+            cdt: None,
+            cdt_cache: Default::default(),
+        };
+        let (a, r) = psf.trap_gen();
+        let range_fa = psf.f_a(&a, &psf.samp_d());
+
+        let preimage = psf.samp_p(&a, &r, &range_fa);
+
+        assert!(psf.cdt.is_none());
+        assert!(psf.cdt_cache.get().is_some());
+        assert_eq!(range_fa, psf.f_a(&a, &preimage));
+        assert!(psf.check_domain(&preimage));
+
+        // the cached table is reused by further calls
+        let cached = psf.cdt_cache.get().unwrap() as *const _;
+        let preimage = psf.samp_p(&a, &r, &range_fa);
+        assert_eq!(cached, psf.cdt_cache.get().unwrap() as *const _);
+        assert_eq!(range_fa, psf.f_a(&a, &preimage));
+    }
 }
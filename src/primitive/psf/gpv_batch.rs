@@ -0,0 +1,281 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! Implements a GPV PSF with `k`-column syndromes according to [\[1\]](<../index.html#:~:text=[1]>)
+//! by sampling a preimage of each column with [`PSFGPV`].
+
+use super::{PSF, PSFGPV};
+use qfall_math::{
+    integer::MatZ,
+    integer_mod_q::MatZq,
+    rational::MatQ,
+    traits::{Concatenate, GetNumColumns},
+};
+use serde::{Deserialize, Serialize};
+
+/// A lattice-based implementation of a [`PSF`] with
+/// D_n = {E ∈ Z^{m x k} | every column of E is in the domain of [`PSFGPV`]}
+/// and R_n = Z_q^{n x k}.
+/// Preimages are sampled column by column with the trapdoor of [`PSFGPV`].
+///
+/// Attributes
+/// - `psf`: The [`PSFGPV`] used for every column
+/// - `k`: The number of columns of the domain and the range
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::primitive::psf::{PSFGPV, PSFGPVBatch, PSF};
+/// use qfall_crypto::sample::g_trapdoor::gadget_parameters::GadgetParameters;
+/// use qfall_math::rational::Q;
+///
+/// let psf = PSFGPVBatch {
+///     psf: PSFGPV {
+///         gp: GadgetParameters::init_default(8, 64),
+///         s: Q::from(12),
+///         cdt: None,
+///         cdt_cache: Default::default(),
+///     },
+///     k: 2,
+/// };
+///
+/// let (a, td) = psf.trap_gen();
+/// let domain_sample = psf.samp_d();
+/// let range_fa = psf.f_a(&a, &domain_sample);
+/// let preimage = psf.samp_p(&a, &td, &range_fa);
+///
+/// assert!(psf.check_domain(&preimage));
+/// ```
+#[derive(Serialize, Deserialize)]
+pub struct PSFGPVBatch {
+    pub psf: PSFGPV,
+    pub k: i64,
+}
+
+impl PSF<MatZq, (MatZ, MatQ), MatZ, MatZq> for PSFGPVBatch {
+    /// Computes a G-Trapdoor using [`PSFGPV::trap_gen`](PSF::trap_gen).
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::primitive::psf::{PSFGPV, PSFGPVBatch, PSF};
+    /// use qfall_crypto::sample::g_trapdoor::gadget_parameters::GadgetParameters;
+    /// use qfall_math::rational::Q;
+    ///
+    /// let psf = PSFGPVBatch {
+    ///     psf: PSFGPV {
+    ///         gp: GadgetParameters::init_default(8, 64),
+    ///         s: Q::from(12),
+    ///         cdt: None,
+    ///         cdt_cache: Default::default(),
+    ///     },
+    ///     k: 2,
+    /// };
+    ///
+    /// let (a, (sh_b, sh_b_gso)) = psf.trap_gen();
+    /// ```
+    fn trap_gen(&self) -> (MatZq, (MatZ, MatQ)) {
+        self.psf.trap_gen()
+    }
+
+    /// Samples `k` columns in the domain using [`PSFGPV::samp_d`](PSF::samp_d).
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::primitive::psf::{PSFGPV, PSFGPVBatch, PSF};
+    /// use qfall_crypto::sample::g_trapdoor::gadget_parameters::GadgetParameters;
+    /// use qfall_math::rational::Q;
+    ///
+    /// let psf = PSFGPVBatch {
+    ///     psf: PSFGPV {
+    ///         gp: GadgetParameters::init_default(8, 64),
+    ///         s: Q::from(12),
+    ///         cdt: None,
+    ///         cdt_cache: Default::default(),
+    ///     },
+    ///     k: 2,
+    /// };
+    ///
+    /// let domain_sample = psf.samp_d();
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `k < 1`.
+    fn samp_d(&self) -> MatZ {
+        assert!(self.k >= 1, "The number of columns must be positive.");
+        let mut sample = self.psf.samp_d();
+        for _ in 1..self.k {
+            sample = sample.concat_horizontal(&self.psf.samp_d()).unwrap();
+        }
+        sample
+    }
+
+    /// Samples a preimage `E` with `f_a(a, E) = u` by sampling a preimage
+    /// of every column of `u` using [`PSFGPV::samp_p`](PSF::samp_p).
+    ///
+    /// *Note*: the provided parameters `a,r,u` must fit together,
+    /// otherwise unexpected behavior such as panics may occur.
+    ///
+    /// Parameters:
+    /// - `a`: The parity-check matrix
+    /// - `trapdoor`: The short base for `Λ^⟂(A)` and its precomputed GSO
+    /// - `u`: The syndrome from the range with `k` columns
+    ///
+    /// Returns a sample `E` from the domain on the conditioned discrete
+    /// Gaussian distribution `f_a(a,E) = u`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::primitive::psf::{PSFGPV, PSFGPVBatch, PSF};
+    /// use qfall_crypto::sample::g_trapdoor::gadget_parameters::GadgetParameters;
+    /// use qfall_math::rational::Q;
+    ///
+    /// let psf = PSFGPVBatch {
+    ///     psf: PSFGPV {
+    ///         gp: GadgetParameters::init_default(8, 64),
+    ///         s: Q::from(12),
+    ///         cdt: None,
+    ///         cdt_cache: Default::default(),
+    ///     },
+    ///     k: 2,
+    /// };
+    /// let (a, td) = psf.trap_gen();
+    /// let domain_sample = psf.samp_d();
+    /// let range_fa = psf.f_a(&a, &domain_sample);
+    ///
+    /// let preimage = psf.samp_p(&a, &td, &range_fa);
+    /// assert_eq!(range_fa, psf.f_a(&a, &preimage))
+    /// ```
+    fn samp_p(&self, a: &MatZq, trapdoor: &(MatZ, MatQ), u: &MatZq) -> MatZ {
+        let mut preimage = self.psf.samp_p(a, trapdoor, &u.get_column(0).unwrap());
+        for j in 1..u.get_num_columns() {
+            let column = self.psf.samp_p(a, trapdoor, &u.get_column(j).unwrap());
+            preimage = preimage.concat_horizontal(&column).unwrap();
+        }
+        preimage
+    }
+
+    /// Implements the efficiently computable function `f_a` which here corresponds to
+    /// `a*sigma`. The sigma must be from the domain, i.e. D_n.
+    ///
+    /// Parameters:
+    /// - `a`: The parity-check matrix of dimensions `n x m`
+    /// - `sigma`: A matrix of dimensions `m x k`
+    ///
+    /// Returns `a*sigma`
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::primitive::psf::{PSFGPV, PSFGPVBatch, PSF};
+    /// use qfall_crypto::sample::g_trapdoor::gadget_parameters::GadgetParameters;
+    /// use qfall_math::rational::Q;
+    ///
+    /// let psf = PSFGPVBatch {
+    ///     psf: PSFGPV {
+    ///         gp: GadgetParameters::init_default(8, 64),
+    ///         s: Q::from(12),
+    ///         cdt: None,
+    ///         cdt_cache: Default::default(),
+    ///     },
+    ///     k: 2,
+    /// };
+    /// let (a, td) = psf.trap_gen();
+    /// let domain_sample = psf.samp_d();
+    /// let range_fa = psf.f_a(&a, &domain_sample);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `sigma` is not in `D_n`.
+    fn f_a(&self, a: &MatZq, sigma: &MatZ) -> MatZq {
+        assert!(self.check_domain(sigma));
+        a * sigma
+    }
+
+    /// Checks whether `sigma` has `k` columns, each of which is in the domain of [`PSFGPV`].
+    ///
+    /// Parameters:
+    /// - `sigma`: The value for which is checked, if it is in the domain
+    ///
+    /// Returns true, if `sigma` is in D_n.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::primitive::psf::{PSFGPV, PSFGPVBatch, PSF};
+    /// use qfall_crypto::sample::g_trapdoor::gadget_parameters::GadgetParameters;
+    /// use qfall_math::rational::Q;
+    ///
+    /// let psf = PSFGPVBatch {
+    ///     psf: PSFGPV {
+    ///         gp: GadgetParameters::init_default(8, 64),
+    ///         s: Q::from(12),
+    ///         cdt: None,
+    ///         cdt_cache: Default::default(),
+    ///     },
+    ///     k: 2,
+    /// };
+    ///
+    /// let matrix = psf.samp_d();
+    ///
+    /// assert!(psf.check_domain(&matrix));
+    /// ```
+    fn check_domain(&self, sigma: &MatZ) -> bool {
+        sigma.get_num_columns() == self.k
+            && (0..self.k).all(|j| self.psf.check_domain(&sigma.get_column(j).unwrap()))
+    }
+}
+
+#[cfg(test)]
+mod test_gpv_batch_psf {
+    use super::{PSFGPVBatch, PSFGPV};
+    use crate::{primitive::psf::PSF, sample::g_trapdoor::gadget_parameters::GadgetParameters};
+    use qfall_math::{
+        integer::MatZ,
+        integer_mod_q::MatZq,
+        rational::Q,
+        traits::{GetNumColumns, GetNumRows},
+    };
+
+    /// Returns a [`PSFGPVBatch`] for `n = 5`, `q = 256` and `k` columns.
+    fn psf(k: i64) -> PSFGPVBatch {
+        PSFGPVBatch {
+            psf: PSFGPV {
+                gp: GadgetParameters::init_default(5, 256),
+                s: Q::from(10),
+                cdt: None,
+                cdt_cache: Default::default(),
+            },
+            k,
+        }
+    }
+
+    /// Ensures that `samp_p` computes preimages of `k`-column syndromes
+    /// that are in the domain.
+    #[test]
+    fn samp_p_preimage_and_domain() {
+        for k in [1, 2, 4] {
+            let psf = psf(k);
+            let (a, r) = psf.trap_gen();
+            let u = MatZq::sample_uniform(a.get_num_rows(), k, a.get_mod());
+
+            let preimage = psf.samp_p(&a, &r, &u);
+
+            assert_eq!(k, preimage.get_num_columns());
+            assert!(psf.check_domain(&preimage));
+            assert_eq!(u, psf.f_a(&a, &preimage));
+        }
+    }
+
+    /// Ensures that `check_domain` rejects matrices with the wrong number of columns.
+    #[test]
+    fn check_domain_wrong_columns() {
+        let psf = psf(2);
+        let (a, _) = psf.trap_gen();
+
+        assert!(psf.check_domain(&MatZ::new(a.get_num_columns(), 2)));
+        assert!(!psf.check_domain(&MatZ::new(a.get_num_columns(), 1)));
+        assert!(!psf.check_domain(&MatZ::new(a.get_num_columns(), 3)));
+    }
+}
@@ -9,4 +9,7 @@
 //! This module contains anything that should be easily samplable for lattice-based
 //! cryptography.
 
+pub mod cdt;
+pub mod distribution;
 pub mod g_trapdoor;
+pub mod modulus_switch;
@@ -0,0 +1,485 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains a sampler for discrete Gaussians over the integers,
+//! which precomputes a cumulative distribution table (CDT) once
+//! s.t. every sample only requires a binary search instead of rejection sampling.
+//!
+//! Samples with smaller Gaussian parameters and arbitrary centers are generated
+//! by rejection sampling with the table as proposal distribution, which
+//! enables a variant of Klein's algorithm for lattices in [`CdtGaussianSampler::sample_d`].
+
+use crate::utils::conversion::q_to_f64;
+use qfall_math::{
+    integer::{MatZ, Z},
+    rational::{MatQ, Q},
+    traits::{GetEntry, GetNumColumns, GetNumRows, SetEntry},
+};
+use std::f64::consts::PI;
+
+/// The tail cut `τ`, i.e. the table covers `[-⌈τ * s⌉, ⌈τ * s⌉]`.
+/// The probability mass outside of this interval is smaller than `2^{-200}`.
+const TAIL_CUT: f64 = 12.0;
+
+/// A sampler for the discrete Gaussian `D_{Z, s}` with a precomputed
+/// cumulative distribution table.
+///
+/// Attributes:
+/// - `s`: the Gaussian parameter of the table
+/// - `min`: the smallest value of the support of the table
+/// - `cdt`: the cumulative probabilities, i.e. `cdt[i] = Pr[X <= min + i]`
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::sample::cdt::CdtGaussianSampler;
+///
+/// let sampler = CdtGaussianSampler::new(10);
+///
+/// let sample = sampler.sample();
+/// let shifted = sampler.sample_shifted(5.0, 0.5);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct CdtGaussianSampler {
+    s: f64,
+    min: i64,
+    cdt: Vec<f64>,
+}
+
+impl CdtGaussianSampler {
+    /// Computes the cumulative distribution table of `D_{Z, s}`.
+    ///
+    /// Parameters:
+    /// - `s`: the Gaussian parameter of the table
+    ///
+    /// Returns a [`CdtGaussianSampler`] for `D_{Z, s}`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::sample::cdt::CdtGaussianSampler;
+    ///
+    /// let sampler = CdtGaussianSampler::new(3.5);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `s <= 0`.
+    pub fn new(s: impl Into<Q>) -> Self {
+        let s = q_to_f64(&s.into());
+        assert!(s > 0.0, "The Gaussian parameter has to be positive.");
+
+        let bound = (TAIL_CUT * s).ceil() as i64;
+        let weights: Vec<f64> = (-bound..=bound)
+            .map(|x| (-PI * (x * x) as f64 / (s * s)).exp())
+            .collect();
+        let total: f64 = weights.iter().sum();
+
+        let mut cumulative = 0.0;
+        let cdt = weights
+            .iter()
+            .map(|weight| {
+                cumulative += weight / total;
+                cumulative
+            })
+            .collect();
+
+        Self {
+            s,
+            min: -bound,
+            cdt,
+        }
+    }
+
+    /// Returns the Gaussian parameter `s` of the table.
+    pub fn get_s(&self) -> f64 {
+        self.s
+    }
+
+    /// Samples from `D_{Z, s}` by a binary search in the table.
+    ///
+    /// Returns a sample of `D_{Z, s}`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::sample::cdt::CdtGaussianSampler;
+    /// let sampler = CdtGaussianSampler::new(10);
+    ///
+    /// let sample = sampler.sample();
+    /// ```
+    pub fn sample(&self) -> i64 {
+        let uniform = rand::random::<f64>();
+        let index = self
+            .cdt
+            .partition_point(|probability| *probability <= uniform);
+
+        self.min + index.min(self.cdt.len() - 1) as i64
+    }
+
+    /// Samples from `D_{Z, s, c}` for a Gaussian parameter `s` smaller than the one of the
+    /// table using rejection sampling with proposal distribution `⌊c⌋ + D_{Z, s_table}`.
+    /// A proposal `⌊c⌋ + y` is accepted with probability `ρ_{s, c}(y) / (M * ρ_{s_table}(y))`,
+    /// where `M` is the maximum of this ratio over the reals.
+    /// The expected number of proposals is roughly `s_table / s`.
+    ///
+    /// Parameters:
+    /// - `s`: the Gaussian parameter of the sample
+    /// - `center`: the center `c` of the sample
+    ///
+    /// Returns a sample of `D_{Z, s, c}`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::sample::cdt::CdtGaussianSampler;
+    /// let sampler = CdtGaussianSampler::new(10);
+    ///
+    /// let sample = sampler.sample_shifted(5.0, 100.3);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `s <= 0` or `s` is not smaller than the Gaussian parameter of the table.
+    pub fn sample_shifted(&self, s: f64, center: f64) -> i64 {
+        assert!(
+            s > 0.0 && s < self.s,
+            "The Gaussian parameter has to be positive and smaller than the one of the table."
+        );
+        let shift = center.floor();
+        let center = center - shift;
+
+        // log(ρ_{s, c}(y) / ρ_{s_table}(y)) = -π((y - c)^2 / s^2 - y^2 / s_table^2)
+        let log_ratio = |y: f64| -PI * ((y - center).powi(2) / (s * s) - y * y / (self.s * self.s));
+        let maximizer = center / (s * s) / (1.0 / (s * s) - 1.0 / (self.s * self.s));
+        let log_max = log_ratio(maximizer);
+
+        loop {
+            let y = self.sample();
+            if rand::random::<f64>() < (log_ratio(y as f64) - log_max).exp() {
+                return y + shift as i64;
+            }
+        }
+    }
+
+    /// Samples from `D_{Λ(B), s, c}` using Klein's algorithm, where every coordinate
+    /// `z_i <- D_{Z, s / |b~_i|, c_i}` is sampled by [`CdtGaussianSampler::sample_shifted`].
+    /// The Gram-Schmidt orthogonalization of `basis` is computed in floating point arithmetic.
+    /// Coordinates whose Gaussian parameter is not smaller than the one of the
+    /// table are sampled using [`Z::sample_discrete_gauss`].
+    ///
+    /// Parameters:
+    /// - `basis`: the basis `B` of the lattice, whose columns are the basis vectors
+    /// - `n`: the security parameter used for the tail cut of [`Z::sample_discrete_gauss`]
+    /// - `center`: the center `c` of the distribution as a column vector
+    /// - `s`: the Gaussian parameter of the distribution
+    ///
+    /// Returns a lattice vector sampled from `D_{Λ(B), s, c}`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::sample::cdt::CdtGaussianSampler;
+    /// use qfall_math::{integer::{MatZ, Z}, rational::{MatQ, Q}};
+    /// let sampler = CdtGaussianSampler::new(20);
+    /// let basis = MatZ::identity(5, 5);
+    ///
+    /// let sample = sampler.sample_d(&basis, &Z::from(5), &MatQ::new(5, 1), &Q::from(10));
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `center` is not a column vector of the same dimension as the basis vectors.
+    /// - if an entry of `basis` does not fit into an [`i64`].
+    pub fn sample_d(&self, basis: &MatZ, n: &Z, center: &MatQ, s: &Q) -> MatZ {
+        let basis_vecs = columns_to_f64(basis);
+        let gso = gram_schmidt(&basis_vecs);
+
+        basis * self.klein_coefficients(&basis_vecs, &gso, n, center, s)
+    }
+
+    /// Samples from `D_{Λ(B), s, c}` like [`CdtGaussianSampler::sample_d`],
+    /// but uses the precomputed Gram-Schmidt orthogonalization `basis_gso` of `basis`
+    /// instead of computing it for every sample.
+    ///
+    /// Parameters:
+    /// - `basis`: the basis `B` of the lattice, whose columns are the basis vectors
+    /// - `basis_gso`: the Gram-Schmidt orthogonalization of `basis`
+    /// - `n`: the security parameter used for the tail cut of [`Z::sample_discrete_gauss`]
+    /// - `center`: the center `c` of the distribution as a column vector
+    /// - `s`: the Gaussian parameter of the distribution
+    ///
+    /// Returns a lattice vector sampled from `D_{Λ(B), s, c}`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::sample::cdt::CdtGaussianSampler;
+    /// use qfall_math::{integer::{MatZ, Z}, rational::{MatQ, Q}};
+    /// let sampler = CdtGaussianSampler::new(20);
+    /// let basis = MatZ::identity(5, 5);
+    /// let basis_gso = MatQ::from(&basis).gso();
+    ///
+    /// let sample = sampler.sample_d_precomputed_gso(
+    ///     &basis,
+    ///     &basis_gso,
+    ///     &Z::from(5),
+    ///     &MatQ::new(5, 1),
+    ///     &Q::from(10),
+    /// );
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `center` is not a column vector of the same dimension as the basis vectors.
+    /// - if `basis_gso` does not have the same dimensions as `basis`.
+    /// - if an entry of `basis` does not fit into an [`i64`].
+    pub fn sample_d_precomputed_gso(
+        &self,
+        basis: &MatZ,
+        basis_gso: &MatQ,
+        n: &Z,
+        center: &MatQ,
+        s: &Q,
+    ) -> MatZ {
+        basis * self.sample_coefficients(basis, basis_gso, n, center, s)
+    }
+
+    /// Samples the coefficients `z` of a lattice vector `B * z` distributed according
+    /// to `D_{Λ(B), s, c}` as described in [`CdtGaussianSampler::sample_d_precomputed_gso`].
+    ///
+    /// Returns the coefficient vector `z`.
+    ///
+    /// # Panics ...
+    /// - if `center` is not a column vector of the same dimension as the basis vectors.
+    /// - if `basis_gso` does not have the same dimensions as `basis`.
+    /// - if an entry of `basis` does not fit into an [`i64`].
+    pub(crate) fn sample_coefficients(
+        &self,
+        basis: &MatZ,
+        basis_gso: &MatQ,
+        n: &Z,
+        center: &MatQ,
+        s: &Q,
+    ) -> MatZ {
+        assert_eq!(basis.get_num_rows(), basis_gso.get_num_rows());
+        assert_eq!(basis.get_num_columns(), basis_gso.get_num_columns());
+        let gso: Vec<Vec<f64>> = (0..basis_gso.get_num_columns())
+            .map(|j| {
+                (0..basis_gso.get_num_rows())
+                    .map(|i| q_to_f64(&basis_gso.get_entry(i, j).unwrap()))
+                    .collect()
+            })
+            .collect();
+
+        self.klein_coefficients(&columns_to_f64(basis), &gso, n, center, s)
+    }
+
+    /// Runs Klein's algorithm on the basis vectors `basis_vecs` with
+    /// Gram-Schmidt orthogonalization `gso` and returns the coefficient vector `z`.
+    fn klein_coefficients(
+        &self,
+        basis_vecs: &[Vec<f64>],
+        gso: &[Vec<f64>],
+        n: &Z,
+        center: &MatQ,
+        s: &Q,
+    ) -> MatZ {
+        let rows = basis_vecs.first().map_or(0, |vector| vector.len()) as i64;
+        assert_eq!(rows, center.get_num_rows());
+        assert_eq!(1, center.get_num_columns());
+        let s_f64 = q_to_f64(s);
+
+        let mut center: Vec<f64> = (0..rows)
+            .map(|i| q_to_f64(&center.get_entry(i, 0).unwrap()))
+            .collect();
+
+        let mut coefficients = MatZ::new(basis_vecs.len(), 1);
+        for j in (0..basis_vecs.len()).rev() {
+            let norm_sqrd = inner_product(&gso[j], &gso[j]);
+            let center_j = inner_product(&center, &gso[j]) / norm_sqrd;
+            let s_j = s_f64 / norm_sqrd.sqrt();
+
+            let z_j = if s_j < self.s {
+                self.sample_shifted(s_j, center_j)
+            } else {
+                let sample = Z::sample_discrete_gauss(n, center_j, s_j).unwrap();
+                i64::try_from(&sample).unwrap()
+            };
+
+            for (c_i, b_i) in center.iter_mut().zip(&basis_vecs[j]) {
+                *c_i -= z_j as f64 * b_i;
+            }
+            coefficients.set_entry(j as i64, 0, z_j).unwrap();
+        }
+
+        coefficients
+    }
+}
+
+/// Returns the columns of `basis` as vectors of [`f64`].
+///
+/// # Panics ...
+/// - if an entry of `basis` does not fit into an [`i64`].
+fn columns_to_f64(basis: &MatZ) -> Vec<Vec<f64>> {
+    (0..basis.get_num_columns())
+        .map(|j| {
+            (0..basis.get_num_rows())
+                .map(|i| {
+                    let entry: Z = basis.get_entry(i, j).unwrap();
+                    i64::try_from(&entry).unwrap() as f64
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Returns the inner product of `a` and `b`.
+fn inner_product(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(a_i, b_i)| a_i * b_i).sum()
+}
+
+/// Computes the Gram-Schmidt orthogonalization of `vectors` using the
+/// modified Gram-Schmidt process for numerical stability.
+fn gram_schmidt(vectors: &[Vec<f64>]) -> Vec<Vec<f64>> {
+    let mut gso: Vec<Vec<f64>> = Vec::with_capacity(vectors.len());
+    for vector in vectors {
+        let mut orthogonal = vector.clone();
+        for previous in &gso {
+            let mu = inner_product(&orthogonal, previous) / inner_product(previous, previous);
+            for (o_i, p_i) in orthogonal.iter_mut().zip(previous) {
+                *o_i -= mu * p_i;
+            }
+        }
+        gso.push(orthogonal);
+    }
+    gso
+}
+
+#[cfg(test)]
+mod test_cdt_gaussian_sampler {
+    use super::CdtGaussianSampler;
+    use qfall_math::{
+        integer::{MatZ, Z},
+        rational::{MatQ, Q},
+        traits::{GetEntry, SetEntry},
+    };
+    use std::f64::consts::PI;
+
+    /// Returns the empirical mean and variance of `samples`.
+    fn mean_and_variance(samples: &[i64]) -> (f64, f64) {
+        let len = samples.len() as f64;
+        let mean = samples.iter().sum::<i64>() as f64 / len;
+        let variance = samples
+            .iter()
+            .map(|sample| (*sample as f64 - mean).powi(2))
+            .sum::<f64>()
+            / len;
+        (mean, variance)
+    }
+
+    /// Ensures that the table is a valid cumulative distribution function.
+    #[test]
+    fn table_is_cumulative() {
+        let sampler = CdtGaussianSampler::new(4);
+
+        assert!(sampler.cdt.windows(2).all(|w| w[0] <= w[1]));
+        assert!((sampler.cdt.last().unwrap() - 1.0).abs() < 1e-12);
+        assert_eq!(-48, sampler.min);
+    }
+
+    /// Ensures that the samples match `D_{Z, σ}`, i.e. the empirical frequencies of
+    /// `-2, ..., 2`, the mean and the variance `σ^2 / (2π)` are close to their expected values.
+    #[test]
+    fn distribution_matches_d_sigma() {
+        let s = 4.0;
+        let sampler = CdtGaussianSampler::new(s);
+        let samples: Vec<i64> = (0..20000).map(|_| sampler.sample()).collect();
+
+        let normalization: f64 = (-100..=100_i64)
+            .map(|x| (-PI * (x * x) as f64 / (s * s)).exp())
+            .sum();
+        for x in -2..=2_i64 {
+            let expected = (-PI * (x * x) as f64 / (s * s)).exp() / normalization;
+            let frequency =
+                samples.iter().filter(|sample| **sample == x).count() as f64 / samples.len() as f64;
+            assert!((frequency - expected).abs() < 0.02);
+        }
+        let (mean, variance) = mean_and_variance(&samples);
+        assert!(mean.abs() < 0.1);
+        assert!((variance - s * s / (2.0 * PI)).abs() < 0.2);
+    }
+
+    /// Ensures that shifted samples have the expected mean and variance.
+    #[test]
+    fn shifted_distribution() {
+        let sampler = CdtGaussianSampler::new(8);
+        let s = 5.0;
+
+        for center in [0.0, 0.5, -3.75, 100.25] {
+            let samples: Vec<i64> = (0..20000)
+                .map(|_| sampler.sample_shifted(s, center))
+                .collect();
+
+            let (mean, variance) = mean_and_variance(&samples);
+            assert!((mean - center).abs() < 0.1);
+            assert!((variance - s * s / (2.0 * PI)).abs() < 0.3);
+        }
+    }
+
+    /// Ensures that samples of Klein's algorithm are lattice vectors.
+    #[test]
+    fn sample_d_in_lattice() {
+        let sampler = CdtGaussianSampler::new(30);
+        let mut basis = MatZ::identity(3, 3);
+        basis.set_entry(0, 0, 7).unwrap();
+        basis.set_entry(0, 1, 3).unwrap();
+        let mut center = MatQ::new(3, 1);
+        center.set_entry(0, 0, Q::from((7, 2))).unwrap();
+
+        for _ in 0..10 {
+            let sample = sampler.sample_d(&basis, &Z::from(3), &center, &Q::from(20));
+
+            // the lattice consists of all vectors with x_0 = 3 * x_1 mod 7
+            let x_0: Z = sample.get_entry(0, 0).unwrap();
+            let x_1: Z = sample.get_entry(1, 0).unwrap();
+            assert_eq!(Z::ZERO, (x_0 - 3 * x_1).modulo(7));
+        }
+    }
+
+    /// Ensures that samples using a precomputed GSO are lattice vectors.
+    #[test]
+    fn sample_d_precomputed_gso_in_lattice() {
+        let sampler = CdtGaussianSampler::new(30);
+        let mut basis = MatZ::identity(3, 3);
+        basis.set_entry(0, 0, 7).unwrap();
+        basis.set_entry(0, 1, 3).unwrap();
+        let basis_gso = MatQ::from(&basis).gso();
+        let center = MatQ::new(3, 1);
+
+        for _ in 0..10 {
+            let sample = sampler.sample_d_precomputed_gso(
+                &basis,
+                &basis_gso,
+                &Z::from(3),
+                &center,
+                &Q::from(20),
+            );
+
+            let x_0: Z = sample.get_entry(0, 0).unwrap();
+            let x_1: Z = sample.get_entry(1, 0).unwrap();
+            assert_eq!(Z::ZERO, (x_0 - 3 * x_1).modulo(7));
+        }
+    }
+
+    /// Ensures that a non-positive Gaussian parameter results in a panic.
+    #[test]
+    #[should_panic]
+    fn invalid_s() {
+        let _ = CdtGaussianSampler::new(0);
+    }
+
+    /// Ensures that shifted samples with larger Gaussian parameters than the table are rejected.
+    #[test]
+    #[should_panic]
+    fn shifted_too_wide() {
+        let sampler = CdtGaussianSampler::new(2);
+
+        let _ = sampler.sample_shifted(2.0, 0.0);
+    }
+}
@@ -0,0 +1,14 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains statistical tests, which check whether samples
+//! follow a discrete distribution.
+
+mod chi_squared;
+
+pub use chi_squared::chi_squared_gaussian_test;
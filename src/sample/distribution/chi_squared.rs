@@ -0,0 +1,231 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains a chi-squared goodness-of-fit test for the
+//! discrete Gaussian distribution over the integers.
+
+use std::f64::consts::PI;
+
+/// The minimal expected number of samples per bin.
+/// Bins with fewer expected samples are merged with their neighbours,
+/// as the chi-squared approximation is inaccurate otherwise.
+const MIN_EXPECTED: f64 = 5.0;
+
+/// Runs a chi-squared goodness-of-fit test, which checks whether `samples` are
+/// drawn from the discrete Gaussian `D_{Z, s}` with center `0`, i.e.
+/// `Pr[x] ∝ exp(-π x^2 / s^2)`.
+///
+/// The support is split into bins, s.t. every bin is expected to contain at least
+/// `5` samples. The outermost bins contain the tails, i.e. all samples
+/// below or above the inner bins.
+/// Then, the statistic `Σ (O_i - E_i)^2 / E_i` of the observed counts `O_i` and
+/// expected counts `E_i` is compared to the `confidence`-quantile of the chi-squared
+/// distribution with `#bins - 1` degrees of freedom, which is approximated according
+/// to Wilson and Hilferty.
+///
+/// **WARNING:** This is a statistical test. Samples of `D_{Z, s}` are rejected
+/// with probability about `1 - confidence`.
+///
+/// Parameters:
+/// - `samples`: the samples that are tested
+/// - `s`: the Gaussian parameter of the discrete Gaussian
+/// - `confidence`: the confidence level in `(0, 1)`, e.g. `0.99`
+///
+/// Returns `true` if the test does not reject the hypothesis that `samples`
+/// are distributed according to `D_{Z, s}` and `false` otherwise.
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::sample::distribution::chi_squared_gaussian_test;
+/// use qfall_math::integer::Z;
+///
+/// let samples: Vec<i64> = (0..1000)
+///     .map(|_| i64::try_from(&Z::sample_discrete_gauss(128, 0, 4).unwrap()).unwrap())
+///     .collect();
+///
+/// let accepted = chi_squared_gaussian_test(&samples, 4.0, 0.99);
+/// ```
+///
+/// # Panics ...
+/// - if `s <= 0`.
+/// - if `confidence` is not in `(0, 1)`.
+/// - if `samples` contains fewer than `10` samples.
+pub fn chi_squared_gaussian_test(samples: &[i64], s: f64, confidence: f64) -> bool {
+    assert!(s > 0.0, "The Gaussian parameter has to be positive.");
+    assert!(
+        confidence > 0.0 && confidence < 1.0,
+        "The confidence level has to be in (0, 1)."
+    );
+    assert!(
+        samples.len() >= 2 * MIN_EXPECTED as usize,
+        "At least 10 samples are required."
+    );
+    let num_samples = samples.len() as f64;
+
+    // the probabilities of D_{Z, s} on [-bound, bound], the remaining mass is negligible
+    let bound = (12.0 * s).ceil() as i64;
+    let weights: Vec<f64> = (-bound..=bound)
+        .map(|x| (-PI * (x * x) as f64 / (s * s)).exp())
+        .collect();
+    let total: f64 = weights.iter().sum();
+
+    // merge values into bins of consecutive integers with at least `MIN_EXPECTED`
+    // expected samples, where `upper_ends[i]` is the largest value of bin `i`
+    let mut upper_ends = Vec::new();
+    let mut expected = Vec::new();
+    let mut current = 0.0;
+    for (x, weight) in (-bound..=bound).zip(&weights) {
+        current += num_samples * weight / total;
+        if current >= MIN_EXPECTED {
+            upper_ends.push(x);
+            expected.push(current);
+            current = 0.0;
+        }
+    }
+    // the remaining mass of the upper tail is added to the last bin
+    match expected.last_mut() {
+        Some(last) => *last += current,
+        None => return true,
+    }
+    *upper_ends.last_mut().unwrap() = i64::MAX;
+    if expected.len() < 2 {
+        return true;
+    }
+
+    let mut observed = vec![0.0; expected.len()];
+    for sample in samples {
+        observed[upper_ends.partition_point(|end| end < sample)] += 1.0;
+    }
+
+    let statistic: f64 = observed
+        .iter()
+        .zip(&expected)
+        .map(|(o, e)| (o - e) * (o - e) / e)
+        .sum();
+
+    statistic <= chi_squared_quantile((expected.len() - 1) as f64, confidence)
+}
+
+/// Approximates the `p`-quantile of the chi-squared distribution with `k` degrees
+/// of freedom according to Wilson and Hilferty, i.e.
+/// `k * (1 - 2/(9k) + z_p * sqrt(2/(9k)))^3` with the `p`-quantile `z_p` of
+/// the standard normal distribution.
+fn chi_squared_quantile(k: f64, p: f64) -> f64 {
+    let variance = 2.0 / (9.0 * k);
+    k * (1.0 - variance + normal_quantile(p) * variance.sqrt()).powi(3)
+}
+
+/// Approximates the `p`-quantile of the standard normal distribution with the
+/// rational approximation of Acklam, which has a relative error of less than `1.15 * 10^{-9}`.
+fn normal_quantile(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969_683_028_665_376e1,
+        2.209_460_984_245_205e2,
+        -2.759_285_104_469_687e2,
+        1.383_577_518_672_69e2,
+        -3.066_479_806_614_716e1,
+        2.506_628_277_459_239,
+    ];
+    const B: [f64; 5] = [
+        -5.447_609_879_822_406e1,
+        1.615_858_368_580_409e2,
+        -1.556_989_798_598_866e2,
+        6.680_131_188_771_972e1,
+        -1.328_068_155_288_572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784_894_002_430_293e-3,
+        -3.223_964_580_411_365e-1,
+        -2.400_758_277_161_838,
+        -2.549_732_539_343_734,
+        4.374_664_141_464_968,
+        2.938_163_982_698_783,
+    ];
+    const D: [f64; 4] = [
+        7.784_695_709_041_462e-3,
+        3.224_671_290_700_398e-1,
+        2.445_134_137_142_996,
+        3.754_408_661_907_416,
+    ];
+    const P_LOW: f64 = 0.02425;
+
+    let tail = |q: f64| {
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    };
+
+    if p < P_LOW {
+        tail((-2.0 * p.ln()).sqrt())
+    } else if p <= 1.0 - P_LOW {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    } else {
+        -tail((-2.0 * (1.0 - p).ln()).sqrt())
+    }
+}
+
+#[cfg(test)]
+mod test_chi_squared_gaussian_test {
+    use super::{chi_squared_gaussian_test, chi_squared_quantile, normal_quantile};
+    use qfall_math::integer::Z;
+
+    /// Returns `num` samples of `D_{Z, s}`.
+    fn samples(num: usize, s: f64) -> Vec<i64> {
+        (0..num)
+            .map(|_| i64::try_from(&Z::sample_discrete_gauss(128, 0, s).unwrap()).unwrap())
+            .collect()
+    }
+
+    /// Ensures that the quantiles match known values.
+    #[test]
+    fn quantiles() {
+        assert!((normal_quantile(0.5)).abs() < 1e-9);
+        assert!((normal_quantile(0.99) - 2.326_347_874).abs() < 1e-6);
+        assert!((normal_quantile(0.01) + 2.326_347_874).abs() < 1e-6);
+        // the exact 0.99-quantiles for 10 and 100 degrees of freedom
+        assert!((chi_squared_quantile(10.0, 0.99) - 23.209).abs() < 0.1);
+        assert!((chi_squared_quantile(100.0, 0.99) - 135.807).abs() < 0.1);
+    }
+
+    /// Ensures that samples of `D_{Z, s}` pass the test.
+    #[test]
+    fn correct_parameter() {
+        for s in [3.0, 10.0, 25.0] {
+            assert!(chi_squared_gaussian_test(&samples(10_000, s), s, 0.99));
+        }
+    }
+
+    /// Ensures that samples of `D_{Z, s}` fail the test for Gaussian parameters,
+    /// which differ by at least `20%`.
+    #[test]
+    fn wrong_parameter() {
+        for s in [3.0, 10.0, 25.0] {
+            let samples = samples(10_000, s);
+
+            assert!(!chi_squared_gaussian_test(&samples, 1.2 * s, 0.99));
+            assert!(!chi_squared_gaussian_test(&samples, 0.8 * s, 0.99));
+        }
+    }
+
+    /// Ensures that shifted samples fail the test.
+    #[test]
+    fn wrong_center() {
+        let samples: Vec<i64> = samples(10_000, 10.0).iter().map(|x| x + 2).collect();
+
+        assert!(!chi_squared_gaussian_test(&samples, 10.0, 0.99));
+    }
+
+    /// Ensures that the function panics for invalid confidence levels.
+    #[test]
+    #[should_panic]
+    fn invalid_confidence() {
+        let _ = chi_squared_gaussian_test(&samples(100, 3.0), 3.0, 1.0);
+    }
+}
@@ -34,10 +34,12 @@
 //!     estimates." International Conference on the Theory and Application of Cryptology and
 //!     Information Security. Berlin, Heidelberg: Springer Berlin Heidelberg, 2011.
 
+pub mod basis_quality;
 pub mod gadget_classical;
 pub mod gadget_default;
 pub mod gadget_parameters;
 pub mod gadget_ring;
 pub mod short_basis_classical;
 pub mod short_basis_ring;
+pub mod sparse_trapdoor;
 pub mod trapdoor_distribution;
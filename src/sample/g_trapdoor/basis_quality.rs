@@ -0,0 +1,305 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains functions to measure the quality of trapdoors and
+//! short bases, e.g. via their spectral norm `s_1` or their orthogonality defect.
+
+use qfall_math::{
+    integer::{MatZ, Z},
+    rational::Q,
+    traits::{GetEntry, GetNumColumns, GetNumRows},
+};
+
+/// The number of iterations used by [`trapdoor_spectral_norm`].
+const TRAPDOOR_ITERATIONS: usize = 100;
+
+/// Computes the Frobenius norm `sqrt(sum_{i,j} m_{i,j}^2)` of `m`,
+/// which is an upper bound on the spectral norm of `m`.
+///
+/// Parameters:
+/// - `m`: the matrix whose norm is computed
+///
+/// Returns the Frobenius norm of `m` as a [`Q`].
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::sample::g_trapdoor::basis_quality::frobenius_norm;
+/// use qfall_math::integer::MatZ;
+///
+/// let norm = frobenius_norm(&MatZ::identity(4, 4));
+/// ```
+pub fn frobenius_norm(m: &MatZ) -> Q {
+    let mut sum = Z::ZERO;
+    for j in 0..m.get_num_columns() {
+        sum = sum + m.get_column(j).unwrap().norm_eucl_sqrd().unwrap();
+    }
+    sum.sqrt()
+}
+
+/// Approximates the spectral norm `s_1(m) = max_{|x| = 1} |m * x|` of `m`
+/// via power iteration on `m^t * m` starting from a uniformly random vector.
+/// The approximation is a lower bound on `s_1(m)`, which converges to `s_1(m)`
+/// with an increasing number of iterations.
+///
+/// The computation is performed with floating point numbers, hence the
+/// result is only an approximation even after convergence.
+///
+/// Parameters:
+/// - `m`: the matrix whose spectral norm is approximated
+/// - `iterations`: the number of iterations of the power iteration
+///
+/// Returns an approximation of the spectral norm of `m` as a [`Q`].
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::sample::g_trapdoor::basis_quality::spectral_norm_power_iteration;
+/// use qfall_math::{integer::MatZ, rational::Q};
+/// use std::str::FromStr;
+///
+/// let m = MatZ::from_str("[[3, 0],[0, -5]]").unwrap();
+///
+/// let norm = spectral_norm_power_iteration(&m, 50);
+///
+/// assert!(Q::from(4.99) < norm && norm < Q::from(5.01));
+/// ```
+pub fn spectral_norm_power_iteration(m: &MatZ, iterations: usize) -> Q {
+    let rows = to_f64_rows(m);
+    let nr_columns = m.get_num_columns() as usize;
+
+    let mut v: Vec<f64> = (0..nr_columns)
+        .map(|_| rand::random::<f64>() - 0.5)
+        .collect();
+    let norm_v = norm(&v);
+    if norm_v == 0.0 {
+        return Q::ZERO;
+    }
+    v.iter_mut().for_each(|entry| *entry /= norm_v);
+
+    let mut estimate = 0.0;
+    for _ in 0..iterations.max(1) {
+        // w = m^t * m * v
+        let m_v: Vec<f64> = rows.iter().map(|row| dot(row, &v)).collect();
+        let mut w = vec![0.0; nr_columns];
+        for (row, m_v_i) in rows.iter().zip(m_v.iter()) {
+            for (w_j, row_j) in w.iter_mut().zip(row.iter()) {
+                *w_j += row_j * m_v_i;
+            }
+        }
+
+        // |m^t * m * v| converges to s_1(m)^2 for |v| = 1
+        let norm_w = norm(&w);
+        if norm_w == 0.0 {
+            return Q::ZERO;
+        }
+        estimate = norm_w.sqrt();
+        v = w.into_iter().map(|entry| entry / norm_w).collect();
+    }
+
+    Q::from(estimate)
+}
+
+/// Approximates the spectral norm `s_1(r)` of a G-trapdoor `r`
+/// using [`spectral_norm_power_iteration`] with 100 iterations.
+/// The smaller `s_1(r)`, the smaller the Gaussian parameter with which
+/// preimages can be sampled using `r`.
+///
+/// Parameters:
+/// - `r`: the trapdoor whose spectral norm is approximated
+///
+/// Returns an approximation of the spectral norm of `r` as a [`Q`].
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::sample::g_trapdoor::{
+///     basis_quality::trapdoor_spectral_norm, gadget_default::gen_trapdoor_default,
+/// };
+///
+/// let (_, r) = gen_trapdoor_default(10, 1024);
+///
+/// let norm = trapdoor_spectral_norm(&r);
+/// ```
+pub fn trapdoor_spectral_norm(r: &MatZ) -> Q {
+    spectral_norm_power_iteration(r, TRAPDOOR_ITERATIONS)
+}
+
+/// Computes the orthogonality defect `prod_i ||b_i|| / det(L(B))` of `basis`,
+/// whose columns `b_i` form a basis of the lattice `L(B)` with
+/// `det(L(B)) = sqrt(det(B^t * B))`.
+/// The orthogonality defect is `1` for an orthogonal basis and increases
+/// the more skewed the basis is.
+///
+/// Parameters:
+/// - `basis`: the basis whose quality is measured
+///
+/// Returns the orthogonality defect of `basis` as a [`Q`].
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::sample::g_trapdoor::basis_quality::orthogonality_defect;
+/// use qfall_math::{integer::MatZ, rational::Q};
+/// use std::str::FromStr;
+///
+/// let basis = MatZ::from_str("[[1, 3],[0, 4]]").unwrap();
+///
+/// let defect = orthogonality_defect(&basis);
+///
+/// assert!(Q::from(1.249) < defect && defect < Q::from(1.251));
+/// ```
+///
+/// # Panics ...
+/// - if the columns of `basis` are linearly dependent.
+pub fn orthogonality_defect(basis: &MatZ) -> Q {
+    // det(L(B))^2 = det(B^t * B) is computed exactly over the integers
+    let det_sqrd = (basis.transpose() * basis).det().unwrap();
+    assert!(
+        det_sqrd != Z::ZERO,
+        "The columns of the basis have to be linearly independent."
+    );
+
+    let mut norms_sqrd = Z::ONE;
+    for j in 0..basis.get_num_columns() {
+        norms_sqrd = norms_sqrd * basis.get_column(j).unwrap().norm_eucl_sqrd().unwrap();
+    }
+
+    (Q::from(norms_sqrd) / Q::from(det_sqrd)).sqrt()
+}
+
+/// Converts the rows of `m` into vectors of [`f64`].
+fn to_f64_rows(m: &MatZ) -> Vec<Vec<f64>> {
+    (0..m.get_num_rows())
+        .map(|i| {
+            (0..m.get_num_columns())
+                .map(|j| {
+                    let entry: Z = m.get_entry(i, j).unwrap();
+                    entry.to_string().parse().unwrap()
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Computes the inner product of `a` and `b`.
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b.iter()).map(|(a_i, b_i)| a_i * b_i).sum()
+}
+
+/// Computes the Euclidean norm of `v`.
+fn norm(v: &[f64]) -> f64 {
+    dot(v, v).sqrt()
+}
+
+#[cfg(test)]
+mod test_basis_quality {
+    use super::{frobenius_norm, spectral_norm_power_iteration, trapdoor_spectral_norm};
+    use crate::sample::g_trapdoor::gadget_default::gen_trapdoor_default;
+    use qfall_math::{integer::MatZ, rational::Q, traits::SetEntry};
+    use std::str::FromStr;
+
+    /// Ensures that the identity matrix has spectral norm 1.
+    #[test]
+    fn identity() {
+        let norm = spectral_norm_power_iteration(&MatZ::identity(10, 10), 10);
+
+        assert!(Q::from(0.999999) < norm && norm < Q::from(1.000001));
+        assert_eq!(Q::from(10).sqrt(), frobenius_norm(&MatZ::identity(10, 10)));
+    }
+
+    /// Ensures that a uniformly random `n x n` matrix with entries in `{-1, 1}`
+    /// has a spectral norm of roughly `sqrt(n)`,
+    /// i.e. in `[sqrt(n), 3 * sqrt(n)]`.
+    #[test]
+    fn random_matrix() {
+        let n = 64;
+        let mut m = MatZ::new(n, n);
+        for i in 0..n {
+            for j in 0..n {
+                let entry = if rand::random::<bool>() { 1 } else { -1 };
+                m.set_entry(i, j, entry).unwrap();
+            }
+        }
+
+        let norm = spectral_norm_power_iteration(&m, 100);
+
+        assert!(Q::from(7.99) <= norm);
+        assert!(norm <= Q::from(24));
+        assert!(norm <= frobenius_norm(&m));
+    }
+
+    /// Ensures that the zero matrix has spectral norm 0.
+    #[test]
+    fn zero() {
+        assert_eq!(Q::ZERO, spectral_norm_power_iteration(&MatZ::new(3, 4), 10));
+        assert_eq!(Q::ZERO, frobenius_norm(&MatZ::new(3, 4)));
+    }
+
+    /// Ensures that the spectral norm of a trapdoor is bounded by its Frobenius norm.
+    #[test]
+    fn trapdoor() {
+        let (_, r) = gen_trapdoor_default(10, 1024);
+
+        let norm = trapdoor_spectral_norm(&r);
+
+        assert!(Q::ZERO < norm);
+        assert!(norm <= frobenius_norm(&r));
+    }
+
+    /// Ensures that a non-square matrix is handled properly.
+    #[test]
+    fn non_square() {
+        let m = MatZ::from_str("[[0, 0, 0],[0, 7, 0]]").unwrap();
+
+        let norm = spectral_norm_power_iteration(&m, 50);
+
+        assert!(Q::from(6.99) < norm && norm < Q::from(7.01));
+    }
+}
+
+#[cfg(test)]
+mod test_orthogonality_defect {
+    use super::orthogonality_defect;
+    use qfall_math::{integer::MatZ, rational::Q};
+    use std::str::FromStr;
+
+    /// Ensures that the identity matrix has orthogonality defect 1.
+    #[test]
+    fn identity() {
+        let defect = orthogonality_defect(&MatZ::identity(10, 10));
+
+        assert!(Q::from(0.999999) < defect && defect < Q::from(1.000001));
+    }
+
+    /// Ensures that a scaled orthogonal basis, which is not square, has orthogonality defect 1.
+    #[test]
+    fn orthogonal_non_square() {
+        let basis = MatZ::from_str("[[3, 0],[0, 0],[0, -7]]").unwrap();
+
+        let defect = orthogonality_defect(&basis);
+
+        assert!(Q::from(0.999999) < defect && defect < Q::from(1.000001));
+    }
+
+    /// Ensures that a triangular matrix with large off-diagonal entries
+    /// has an orthogonality defect larger than 1.
+    #[test]
+    fn skewed_triangular() {
+        let basis = MatZ::from_str("[[1, 100, 100],[0, 1, 100],[0, 0, 1]]").unwrap();
+
+        let defect = orthogonality_defect(&basis);
+
+        assert!(Q::from(100) < defect);
+    }
+
+    /// Ensures that linearly dependent columns result in a panic.
+    #[test]
+    #[should_panic]
+    fn linearly_dependent() {
+        let basis = MatZ::from_str("[[1, 2],[2, 4]]").unwrap();
+
+        let _ = orthogonality_defect(&basis);
+    }
+}
@@ -9,13 +9,21 @@
 //! This module contains an implementation to generate a gadget trapdoor in a
 //! classical setting.
 
-use super::gadget_parameters::GadgetParameters;
+use super::{
+    gadget_parameters::{GadgetParameters, GadgetParametersModule},
+    short_basis_classical::{extend_short_basis, gen_short_basis_for_trapdoor},
+    trapdoor_distribution::PlusMinusOneZero,
+};
+use crate::utils::math_util::mat_inverse_mod;
 use qfall_math::{
     error::MathError,
     integer::{MatZ, Z},
     integer_mod_q::{MatZq, Zq},
+    rational::{MatQ, Q},
     traits::{Concatenate, GetEntry, GetNumColumns, GetNumRows, Pow, SetEntry, Tensor},
 };
+use rand::{Rng, RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
 use std::fmt::Display;
 
 /// Generates a trapdoor according to Algorithm 1 in [\[1\]](<../index.html#:~:text=[1]>).
@@ -57,16 +65,390 @@ pub fn gen_trapdoor(
     params: &GadgetParameters,
     a_bar: &MatZq,
     tag: &MatZq,
+) -> Result<(MatZq, MatZ), MathError> {
+    gen_trapdoor_with_rng(params, a_bar, tag, &mut rand::thread_rng())
+}
+
+/// Generates a trapdoor like [`gen_trapdoor`], where the trapdoor `R` is sampled
+/// from the distribution in `params` with the randomness of `rng`.
+fn gen_trapdoor_with_rng(
+    params: &GadgetParameters,
+    a_bar: &MatZq,
+    tag: &MatZq,
+    rng: &mut dyn RngCore,
 ) -> Result<(MatZq, MatZ), MathError> {
     let g = gen_gadget_mat(&params.n, &params.k, &params.base);
     let r = params
         .distribution
-        .sample(&params.m_bar, &(&params.n * &params.k));
+        .sample_with_rng(&params.m_bar, &(&params.n * &params.k), rng);
     // set A = [A_bar | HG - A_bar R]
     let a = a_bar.concat_horizontal(&(tag * g - a_bar * &r))?;
     Ok((a, r))
 }
 
+/// Generates a trapdoor using [`gen_trapdoor`] after validating that `tag`
+/// is invertible modulo `q`, which is required to sample preimages with the trapdoor.
+///
+/// Parameters:
+/// - `params`: all gadget parameters which are required to generate the trapdoor
+/// - `a_bar`: the matrix defining the first part of the G-Trapdoor
+/// - `tag`: the tag which is hidden within the matrix `A`
+///
+/// Returns a a parity-check matrix `a` derived from `a_bar` and its gadget-trapdoor `r`
+/// under the tag `tag`.
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::sample::g_trapdoor::{gadget_parameters::GadgetParameters, gadget_classical::gen_trapdoor_with_tag};
+/// use qfall_math::integer_mod_q::MatZq;
+/// use std::str::FromStr;
+///
+/// let params = GadgetParameters::init_default(2, 32);
+/// let a_bar = MatZq::sample_uniform(2, &params.m_bar, &params.q);
+/// let tag = MatZq::from_str("[[1, 3],[0, 1]] mod 32").unwrap();
+///
+/// let (a, r) = gen_trapdoor_with_tag(&params, &a_bar, &tag).unwrap();
+///
+/// let not_invertible = MatZq::from_str("[[2, 0],[0, 1]] mod 32").unwrap();
+/// assert!(gen_trapdoor_with_tag(&params, &a_bar, &not_invertible).is_err());
+/// ```
+///
+/// # Errors and Failures
+/// - Returns a [`MathError`] of type [`InvalidIntegerInput`](MathError::InvalidIntegerInput)
+///     if `tag` is not invertible modulo `q` according to [`mat_inverse_mod`].
+/// - Returns a [`MathError`] of type [`MismatchingMatrixDimension`](MathError::MismatchingMatrixDimension)
+///     if the matrices can not be concatenated due to mismatching dimensions.
+/// - Returns a [`MathError`] of type [`MismatchingModulus`](MathError::MismatchingModulus)
+///     if the matrices can not be concatenated due to mismatching moduli.
+///
+/// # Panics ...
+/// - if `params.k < 1` or it does not fit into an [`i64`].
+/// - if `params.n < 1`.
+pub fn gen_trapdoor_with_tag(
+    params: &GadgetParameters,
+    a_bar: &MatZq,
+    tag: &MatZq,
+) -> Result<(MatZq, MatZ), MathError> {
+    if mat_inverse_mod(tag).is_none() {
+        return Err(MathError::InvalidIntegerInput(format!(
+            "The tag {tag} is not invertible."
+        )));
+    }
+    gen_trapdoor(params, a_bar, tag)
+}
+
+/// Generates a trapdoor according to Algorithm 1 in [\[1\]](<../index.html#:~:text=[1]>)
+/// with tag `I_n` deterministically from `seed`, i.e. two calls with the same
+/// parameters and seed return the same matrices.
+/// All randomness is taken from a ChaCha20 RNG initialized with `seed`:
+/// - `A_bar` is sampled uniformly at random from `Z_q^{n x m_bar}`
+/// - `R` is sampled from the distribution in `params` using
+///     [`TrapdoorDistribution::sample_with_rng`](super::trapdoor_distribution::TrapdoorDistribution::sample_with_rng)
+///
+/// **WARNING:** The trapdoor is only as secret as `seed`. Hence, this function
+/// should only be used for reproducible tests and benchmarks.
+///
+/// Parameters:
+/// - `params`: all gadget parameters which are required to generate the trapdoor
+/// - `seed`: the seed from which all randomness is derived
+///
+/// Returns a parity-check matrix `a` and its gadget-trapdoor `r` under tag `I_n`.
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::sample::g_trapdoor::{gadget_parameters::GadgetParameters, gadget_classical::gen_trapdoor_seeded};
+///
+/// let params = GadgetParameters::init_default(10, 1024);
+///
+/// let (a, r) = gen_trapdoor_seeded(&params, &[42; 32]);
+/// assert_eq!((a, r), gen_trapdoor_seeded(&params, &[42; 32]));
+/// ```
+///
+/// # Panics ...
+/// - if `params.n`, `params.k` or `params.m_bar` is smaller than `1` or
+///     does not fit into an [`i64`].
+pub fn gen_trapdoor_seeded(params: &GadgetParameters, seed: &[u8; 32]) -> (MatZq, MatZ) {
+    let mut rng = ChaCha20Rng::from_seed(*seed);
+
+    let n = i64::try_from(&params.n).unwrap();
+    let m_bar = i64::try_from(&params.m_bar).unwrap();
+    let q = Z::from(&params.q);
+
+    // sample 128 additional bits s.t. the reduction modulo q
+    // yields a statistically close to uniform distribution
+    let nr_bits = q.bits() as usize + 128;
+    let mut a_bar = MatZ::new(n, m_bar);
+    for i in 0..n {
+        for j in 0..m_bar {
+            let bits: Vec<bool> = (0..nr_bits).map(|_| rng.gen()).collect();
+            a_bar.set_entry(i, j, Z::from_bits(&bits)).unwrap();
+        }
+    }
+    let a_bar = MatZq::from((&a_bar, &params.q));
+
+    let tag = MatZq::identity(n, n, &params.q);
+    gen_trapdoor_with_rng(params, &a_bar, &tag, &mut rng).unwrap()
+}
+
+/// Derives a parity-check matrix `A` for a user-supplied short matrix `R` such that
+/// `R` is a gadget-trapdoor of `A` with tag `I_n`. This is the inverse of
+/// [`gen_trapdoor`], i.e. it allows to import externally generated trapdoors.
+/// - Samples `A_bar` uniformly at random from `Z_q^{n x m_bar}`
+/// - Outputs `([a_bar | g - a_bar * r], r)` as a tuple of `(A,R)` after verifying
+///     the trapdoor equation `A * [R; I] = G`
+///
+/// As [`GadgetParameters`] do not specify a Gaussian parameter, every column of `R`
+/// has to have Euclidean norm at most `sqrt(m_bar)`, which is satisfied by every
+/// matrix sampled from [`PlusMinusOneZero`](super::trapdoor_distribution::PlusMinusOneZero).
+///
+/// Parameters:
+/// - `basis`: the short matrix `R ∈ Z^{m_bar x n*k}` which should become the trapdoor
+/// - `params`: all gadget parameters which are required to generate the trapdoor
+///
+/// Returns a parity-check matrix `a` and its gadget-trapdoor `r` under tag `I_n`.
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::sample::g_trapdoor::{gadget_parameters::GadgetParameters, gadget_classical::gen_public_from_basis};
+///
+/// let params = GadgetParameters::init_default(10, 1024);
+/// let basis = params.distribution.sample(&params.m_bar, &(&params.n * &params.k));
+///
+/// let (a, r) = gen_public_from_basis(&basis, &params).unwrap();
+/// assert_eq!(basis, r);
+/// ```
+///
+/// # Errors and Failures
+/// - Returns a [`MathError`] of type [`MismatchingMatrixDimension`](MathError::MismatchingMatrixDimension)
+///     if `basis` is not of dimensions `m_bar x n*k`.
+/// - Returns a [`MathError`] of type [`InvalidIntegerInput`](MathError::InvalidIntegerInput)
+///     if a column of `basis` has Euclidean norm larger than `sqrt(m_bar)`
+///     or the derived matrix does not satisfy the trapdoor equation.
+///
+/// # Panics ...
+/// - if `params.k < 1` or it does not fit into an [`i64`].
+/// - if `params.n < 1`.
+pub fn gen_public_from_basis(
+    basis: &MatZ,
+    params: &GadgetParameters,
+) -> Result<(MatZq, MatZ), MathError> {
+    let w = &params.n * &params.k;
+    if Z::from(basis.get_num_rows()) != params.m_bar || Z::from(basis.get_num_columns()) != w {
+        return Err(MathError::MismatchingMatrixDimension(format!(
+            "The basis has to be of dimensions {}x{}, but is of dimensions {}x{}.",
+            params.m_bar,
+            w,
+            basis.get_num_rows(),
+            basis.get_num_columns()
+        )));
+    }
+    for i in 0..basis.get_num_columns() {
+        if basis.get_column(i)?.norm_eucl_sqrd()? > params.m_bar {
+            return Err(MathError::InvalidIntegerInput(format!(
+                "The column {i} of the basis has Euclidean norm larger than sqrt(m_bar)."
+            )));
+        }
+    }
+
+    let a_bar = MatZq::sample_uniform(&params.n, &params.m_bar, &params.q);
+    let g = MatZq::from((
+        &gen_gadget_mat(&params.n, &params.k, &params.base),
+        &params.q,
+    ));
+    // set A = [A_bar | G - A_bar R]
+    let a = a_bar.concat_horizontal(&(&g - &a_bar * basis))?;
+
+    // ensure G = A * [R; I] (definition of a trapdoor)
+    let trapdoor = basis.concat_vertical(&MatZ::identity(&w, &w))?;
+    if &a * MatZq::from((&trapdoor, &params.q)) != g {
+        return Err(MathError::InvalidIntegerInput(String::from(
+            "The derived matrix does not satisfy the trapdoor equation.",
+        )));
+    }
+
+    Ok((a, basis.clone()))
+}
+
+/// Delegates the G-trapdoor `r` of `a` with tag `I_n` to the extended lattice
+/// `Λ^⟂([a | h_id])` following the bonsai tree technique of
+/// [\[4\]](<../index.html#:~:text=[4]>).
+/// - Computes a short basis `S` of `Λ^⟂(a)` from `r` using [`gen_short_basis_for_trapdoor`]
+/// - Extends `S` to a basis of `Λ^⟂([a | h_id])` using [`extend_short_basis`]
+///
+/// The Gram-Schmidt orthogonalization of the delegated basis is as short as the one of `S`.
+/// Hence, the delegated basis can be used to sample preimages of `[a | h_id]` with the
+/// same Gaussian parameter as for `a`, without revealing `r`.
+///
+/// Parameters:
+/// - `params`: the gadget parameters with which the trapdoor was generated
+/// - `a`: the parity check matrix
+/// - `r`: the trapdoor for `a` with tag `I_n`
+/// - `h_id`: the identity-based block with which `a` is extended
+///
+/// Returns the extended parity-check matrix `[a | h_id]` and a short basis of its lattice.
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::sample::g_trapdoor::{
+///     gadget_classical::delegate_trapdoor, gadget_default::gen_trapdoor_default,
+///     gadget_parameters::GadgetParameters,
+/// };
+/// use qfall_math::integer_mod_q::MatZq;
+///
+/// let params = GadgetParameters::init_default(10, 127);
+/// let (a, r) = gen_trapdoor_default(&params.n, 127);
+/// let h_id = MatZq::sample_uniform(&params.n, &(&params.n * &params.k), 127);
+///
+/// let (a_id, basis_id) = delegate_trapdoor(&params, &a, &r, &h_id);
+/// ```
+///
+/// # Panics ...
+/// - if the number of rows of `a` and `h_id` differ.
+/// - if `a` does not have full row rank.
+pub fn delegate_trapdoor(
+    params: &GadgetParameters,
+    a: &MatZq,
+    r: &MatZ,
+    h_id: &MatZq,
+) -> (MatZq, MatZ) {
+    let tag = MatZq::identity(&params.n, &params.n, &params.q);
+    let short_basis = gen_short_basis_for_trapdoor(params, &tag, a, r);
+    let delegated_basis = extend_short_basis(a, &short_basis, h_id);
+
+    (a.concat_horizontal(h_id).unwrap(), delegated_basis)
+}
+
+/// Generates a module G-trapdoor, which consists of `params.k` independent
+/// classical G-trapdoors generated by [`gen_trapdoor`] with tag `I_n`.
+/// The module parity-check matrix is `[A_1 | ... | A_k]`.
+///
+/// Parameters:
+/// - `params`: the parameters of the module and of each classical G-trapdoor
+/// - `a_bars`: the `k` matrices defining the first part of each G-Trapdoor
+///
+/// Returns the parity-check matrices `A_i` and their gadget-trapdoors `R_i`.
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::sample::g_trapdoor::{
+///     gadget_classical::gen_trapdoor_module, gadget_parameters::GadgetParametersModule,
+/// };
+/// use qfall_math::integer_mod_q::MatZq;
+///
+/// let params = GadgetParametersModule::init_default(2, 4, 1024);
+/// let a_bars: Vec<MatZq> = (0..2)
+///     .map(|_| MatZq::sample_uniform(4, &params.inner.m_bar, &params.inner.q))
+///     .collect();
+///
+/// let (pks, sks) = gen_trapdoor_module(&params, &a_bars);
+/// ```
+///
+/// # Panics ...
+/// - if the number of matrices in `a_bars` does not equal `params.k`.
+/// - if a matrix in `a_bars` is not of dimension `n x m_bar` or has a different modulus.
+pub fn gen_trapdoor_module(
+    params: &GadgetParametersModule,
+    a_bars: &[MatZq],
+) -> (Vec<MatZq>, Vec<MatZ>) {
+    assert_eq!(
+        params.k,
+        a_bars.len(),
+        "The number of matrices a_bar must equal the rank of the module."
+    );
+    let tag = MatZq::identity(&params.inner.n, &params.inner.n, &params.inner.q);
+
+    a_bars
+        .iter()
+        .map(|a_bar| gen_trapdoor(&params.inner, a_bar, &tag).unwrap())
+        .unzip()
+}
+
+/// Samples a preimage `x = [x_1; ...; x_k]` with `[A_1 | ... | A_k] * x = u`
+/// for a module G-trapdoor generated by [`gen_trapdoor_module`].
+/// The vectors `x_2, ..., x_k` are sampled from the discrete Gaussian distribution
+/// over `Z^m` with Gaussian parameter `sigma` and `x_1` is sampled
+/// from the discrete Gaussian distribution over the coset
+/// `{x_1 | A_1 * x_1 = u - sum_{i >= 2} A_i * x_i}` using the short basis
+/// derived from the trapdoor `R_1`.
+///
+/// The gadget parameters are derived from the dimensions of `A_1` and `R_1`
+/// assuming `base = 2`, as chosen by [`GadgetParameters::init_default`].
+///
+/// Parameters:
+/// - `pks`: the parity-check matrices `A_i`
+/// - `sks`: the gadget-trapdoors `R_i` of `A_i`
+/// - `u`: the syndrome of the preimage
+/// - `sigma`: the Gaussian parameter with which the preimage is sampled
+///
+/// Returns a preimage `x` of `u` w.r.t. `[A_1 | ... | A_k]`.
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::sample::g_trapdoor::{
+///     gadget_classical::{gen_trapdoor_module, samp_p_module},
+///     gadget_parameters::GadgetParametersModule,
+/// };
+/// use qfall_math::{integer_mod_q::MatZq, rational::Q, traits::Concatenate};
+///
+/// let params = GadgetParametersModule::init_default(2, 4, 1024);
+/// let a_bars: Vec<MatZq> = (0..2)
+///     .map(|_| MatZq::sample_uniform(4, &params.inner.m_bar, &params.inner.q))
+///     .collect();
+/// let (pks, sks) = gen_trapdoor_module(&params, &a_bars);
+/// let u = MatZq::sample_uniform(4, 1, &params.inner.q);
+///
+/// let x = samp_p_module(&pks, &sks, &u, &Q::from(100));
+///
+/// assert_eq!(u, pks[0].concat_horizontal(&pks[1]).unwrap() * x);
+/// ```
+///
+/// # Panics ...
+/// - if `pks` is empty or `pks` and `sks` have different lengths.
+/// - if the dimensions of `pks`, `sks`, and `u` do not match.
+pub fn samp_p_module(pks: &[MatZq], sks: &[MatZ], u: &MatZq, sigma: &Q) -> MatZ {
+    assert!(
+        !pks.is_empty() && pks.len() == sks.len(),
+        "The module trapdoor must consist of at least one matrix and one trapdoor per matrix."
+    );
+    let n = Z::from(pks[0].get_num_rows());
+    let q = pks[0].get_mod();
+
+    // x_i <- D_{Z^m, sigma} for i >= 2 and v = u - sum_{i >= 2} A_i * x_i
+    let mut v = u.clone();
+    let mut samples = Vec::with_capacity(pks.len() - 1);
+    for a_i in &pks[1..] {
+        let x_i = MatZ::sample_d_common(a_i.get_num_columns(), &n, sigma).unwrap();
+        v = v - a_i * &x_i;
+        samples.push(x_i);
+    }
+
+    let params = GadgetParameters {
+        n: n.clone(),
+        k: Z::from(sks[0].get_num_columns() / pks[0].get_num_rows()),
+        m_bar: Z::from(sks[0].get_num_rows()),
+        base: Z::from(2),
+        q: q.clone(),
+        distribution: Box::new(PlusMinusOneZero),
+    };
+    let tag = MatZq::identity(&n, &n, &q);
+    let short_base = gen_short_basis_for_trapdoor(&params, &tag, &pks[0], &sks[0]);
+    let short_base_gso = MatQ::from(&short_base).gso();
+
+    // x_1 <- D_{Λ_v(A_1), sigma}
+    let sol: MatZ = pks[0]
+        .solve_gaussian_elimination(&v)
+        .unwrap()
+        .get_representative_0_modulus();
+    let center = MatQ::from(&(-1 * &sol));
+    let mut x = sol
+        + MatZ::sample_d_precomputed_gso(&short_base, &short_base_gso, &n, &center, sigma).unwrap();
+
+    for x_i in samples {
+        x = x.concat_vertical(&x_i).unwrap();
+    }
+    x
+}
+
 /// Generates a gadget matrix based on its definition in [\[1\]](<../index.html#:~:text=[1]>).
 /// This corresponds to `I_n \oplus g^t` where `g` is a gadget vector for the `base`.
 ///
@@ -223,6 +605,75 @@ pub fn find_solution_gadget_mat(value: &MatZq, k: &Z, base: &Z) -> MatZ {
     out
 }
 
+/// Computes a short preimage `x` with `G * x = u mod q` for the gadget matrix `G = I_n \oplus g^t`
+/// without a trapdoor.
+/// As `G` is block-diagonal, the `i`-th block of length `k` of every column of `x`
+/// is the base-`b` decomposition of the `i`-th entry of the corresponding column of `u`,
+/// see [`find_solution_gadget_mat`]. Hence, every entry of `x` lies in `[0, b)`.
+///
+/// The base `b` is read from `G`, i.e. it is the second entry of the gadget vector.
+/// If `k = 1`, the base `2` is used.
+///
+/// Parameters:
+/// - `g`: the gadget matrix of dimensions `n x nk`
+/// - `u`: the matrix of dimensions `n x l` for which a preimage is computed
+///
+/// Returns a matrix `x` of dimensions `nk x l` with entries in `[0, b)` s.t. `G * x = u mod q`.
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::sample::g_trapdoor::gadget_classical::{gen_gadget_mat, sample_g};
+/// use qfall_math::integer_mod_q::MatZq;
+/// use qfall_math::integer::Z;
+/// use std::str::FromStr;
+///
+/// let g = MatZq::from((&gen_gadget_mat(3, 7, &Z::from(2)), 128));
+/// let u = MatZq::from_str("[[1, 42],[2, 30],[3, 127]] mod 128").unwrap();
+///
+/// let x = sample_g(&g, &u);
+///
+/// assert_eq!(u, g * x);
+/// ```
+///
+/// # Panics ...
+/// - if `g` is not a gadget matrix generated by [`gen_gadget_mat`].
+/// - if `u` does not have the same number of rows and modulus as `g`.
+/// - if the modulus is greater than `b^k`.
+pub fn sample_g(g: &MatZq, u: &MatZq) -> MatZ {
+    let n = g.get_num_rows();
+    let nk = g.get_num_columns();
+    assert_eq!(
+        0,
+        nk % n,
+        "The number of columns of g has to be a multiple of its rows."
+    );
+    assert_eq!(
+        n,
+        u.get_num_rows(),
+        "g and u need to have the same number of rows."
+    );
+    assert_eq!(
+        g.get_mod(),
+        u.get_mod(),
+        "g and u need to have the same modulus."
+    );
+
+    let k = nk / n;
+    let base = if k > 1 {
+        let entry: Zq = g.get_entry(0, 1).unwrap();
+        entry.get_representative_0_modulus()
+    } else {
+        Z::from(2)
+    };
+    assert_eq!(
+        *g,
+        MatZq::from((&gen_gadget_mat(n, k, &base), g.get_mod())),
+        "The provided matrix g is not a gadget matrix."
+    );
+
+    find_solution_gadget_mat(u, &Z::from(k), &base)
+}
+
 #[cfg(test)]
 mod test_gen_gadget_vec {
     use crate::sample::g_trapdoor::gadget_classical::gen_gadget_vec;
@@ -284,7 +735,7 @@ mod test_gen_gadget_mat {
 
 #[cfg(test)]
 mod test_gen_trapdoor {
-    use super::gen_trapdoor;
+    use super::{gen_trapdoor, gen_trapdoor_with_tag};
     use crate::sample::g_trapdoor::{
         gadget_classical::gen_gadget_mat, gadget_parameters::GadgetParameters,
     };
@@ -350,6 +801,22 @@ mod test_gen_trapdoor {
         );
     }
 
+    /// Assure that [`gen_trapdoor_with_tag`] accepts invertible tags
+    /// and rejects tags that are not invertible.
+    #[test]
+    fn with_tag_validated() {
+        let modulus = Modulus::from(32);
+        let params = GadgetParameters::init_default(10, &modulus);
+        let a_bar = MatZq::sample_uniform(10, &params.m_bar, &params.q);
+        let tag = calculate_invertible_tag(10, &modulus);
+        let mut not_invertible = tag.clone();
+        not_invertible.set_entry(0, 0, 2).unwrap();
+
+        assert!(gen_trapdoor_with_tag(&params, &a_bar, &tag).is_ok());
+        assert!(gen_trapdoor_with_tag(&params, &a_bar, &not_invertible).is_err());
+        assert!(gen_trapdoor_with_tag(&params, &a_bar, &MatZq::new(10, 10, &modulus)).is_err());
+    }
+
     /// Generates an invertible tag matrix (generates a diagonal matrix)
     fn calculate_invertible_tag(size: i64, modulus: &Modulus) -> MatZq {
         let max_value = Z::from(modulus);
@@ -368,6 +835,381 @@ mod test_gen_trapdoor {
     }
 }
 
+#[cfg(test)]
+mod test_gen_trapdoor_seeded {
+    use super::{gen_gadget_mat, gen_trapdoor, gen_trapdoor_seeded};
+    use crate::sample::g_trapdoor::{
+        gadget_parameters::GadgetParameters, trapdoor_distribution::TrapdoorDistribution,
+    };
+    use qfall_math::{
+        integer::{MatZ, Z},
+        integer_mod_q::MatZq,
+        traits::{Concatenate, GetEntry, GetNumColumns, GetNumRows, SetEntry},
+    };
+    use serde::{Deserialize, Serialize};
+
+    /// Ensures that two calls with the same seed output identical matrices.
+    #[test]
+    fn same_seed_same_output() {
+        let params = GadgetParameters::init_default(10, 1024);
+        let seed = [17; 32];
+
+        let (a_1, r_1) = gen_trapdoor_seeded(&params, &seed);
+        let (a_2, r_2) = gen_trapdoor_seeded(&params, &seed);
+
+        assert_eq!(a_1, a_2);
+        assert_eq!(r_1, r_2);
+        assert_eq!(a_1.to_string(), a_2.to_string());
+        assert_eq!(r_1.to_string(), r_2.to_string());
+    }
+
+    /// Ensures that different seeds output different matrices.
+    #[test]
+    fn different_seed_different_output() {
+        let params = GadgetParameters::init_default(10, 1024);
+
+        let (a_1, r_1) = gen_trapdoor_seeded(&params, &[1; 32]);
+        let (a_2, r_2) = gen_trapdoor_seeded(&params, &[2; 32]);
+
+        assert_ne!(a_1, a_2);
+        assert_ne!(r_1, r_2);
+    }
+
+    /// Assure that the trapdoor `r` returned from [`gen_trapdoor_seeded`]
+    /// is actually a trapdoor for `a` and has entries in `{-1, 0, 1}`.
+    #[test]
+    fn is_trapdoor() {
+        let params = GadgetParameters::init_default(10, 1024);
+
+        let (a, r) = gen_trapdoor_seeded(&params, &[42; 32]);
+
+        // generate the trapdoor for a from r as trapdoor = [[r],[I]]
+        let trapdoor = r
+            .concat_vertical(&MatZ::identity(
+                a.get_num_columns() - r.get_num_rows(),
+                r.get_num_columns(),
+            ))
+            .unwrap();
+
+        // ensure G = A*trapdoor (definition of a trapdoor)
+        let gadget_mat = gen_gadget_mat(&params.n, &params.k, &Z::from(2));
+        assert_eq!(
+            MatZq::from((&gadget_mat, &params.q)),
+            a * MatZq::from((&trapdoor, &params.q))
+        );
+        for i in 0..r.get_num_rows() {
+            for j in 0..r.get_num_columns() {
+                let entry: Z = r.get_entry(i, j).unwrap();
+                assert!(entry.abs() <= Z::ONE);
+            }
+        }
+    }
+
+    /// A distribution, which samples a matrix with all entries set to `2`.
+    #[derive(Serialize, Deserialize)]
+    struct Twos;
+
+    #[typetag::serde]
+    impl TrapdoorDistribution for Twos {
+        fn sample(&self, m_bar: &Z, w: &Z) -> MatZ {
+            let mut out_mat = MatZ::new(m_bar, w);
+            for i in 0..out_mat.get_num_rows() {
+                for j in 0..out_mat.get_num_columns() {
+                    out_mat.set_entry(i, j, 2).unwrap();
+                }
+            }
+            out_mat
+        }
+    }
+
+    /// Ensures that the trapdoor is sampled from the distribution in the
+    /// parameters, i.e. that it matches the trapdoor of [`gen_trapdoor`].
+    #[test]
+    fn respects_distribution() {
+        let params = GadgetParameters {
+            distribution: Box::new(Twos),
+            ..GadgetParameters::init_default(10, 1024)
+        };
+        let a_bar = MatZq::sample_uniform(&params.n, &params.m_bar, &params.q);
+        let tag = MatZq::identity(&params.n, &params.n, &params.q);
+
+        let (_, r) = gen_trapdoor_seeded(&params, &[42; 32]);
+        let (_, r_unseeded) = gen_trapdoor(&params, &a_bar, &tag).unwrap();
+
+        assert_eq!(Twos.sample(&params.m_bar, &(&params.n * &params.k)), r);
+        assert_eq!(r_unseeded, r);
+    }
+}
+
+#[cfg(test)]
+mod test_gen_public_from_basis {
+    use super::{gen_gadget_mat, gen_public_from_basis};
+    use crate::sample::g_trapdoor::gadget_parameters::GadgetParameters;
+    use qfall_math::{
+        integer::{MatZ, Z},
+        integer_mod_q::MatZq,
+        traits::{Concatenate, GetNumColumns, GetNumRows, SetEntry},
+    };
+
+    /// Ensures that a short basis is accepted and is a trapdoor of the derived matrix.
+    #[test]
+    fn valid_basis() {
+        let params = GadgetParameters::init_default(10, 1024);
+        let basis = params
+            .distribution
+            .sample(&params.m_bar, &(&params.n * &params.k));
+
+        let (a, r) = gen_public_from_basis(&basis, &params).unwrap();
+
+        let trapdoor = r
+            .concat_vertical(&MatZ::identity(
+                a.get_num_columns() - r.get_num_rows(),
+                r.get_num_columns(),
+            ))
+            .unwrap();
+        let gadget_mat = gen_gadget_mat(&params.n, &params.k, &Z::from(2));
+        assert_eq!(basis, r);
+        assert_eq!(
+            MatZq::from((&gadget_mat, &params.q)),
+            a * MatZq::from((&trapdoor, &params.q))
+        );
+    }
+
+    /// Ensures that a basis with a too long column is rejected.
+    #[test]
+    fn oversized_basis() {
+        let params = GadgetParameters::init_default(10, 1024);
+        let mut basis = params
+            .distribution
+            .sample(&params.m_bar, &(&params.n * &params.k));
+        basis.set_entry(0, 0, &params.m_bar).unwrap();
+
+        assert!(gen_public_from_basis(&basis, &params).is_err());
+    }
+
+    /// Ensures that a basis of wrong dimensions is rejected.
+    #[test]
+    fn mismatching_dimensions() {
+        let params = GadgetParameters::init_default(10, 1024);
+        let basis = MatZ::new(&params.m_bar, 1);
+
+        assert!(gen_public_from_basis(&basis, &params).is_err());
+    }
+}
+
+#[cfg(test)]
+mod test_delegate_trapdoor {
+    use super::delegate_trapdoor;
+    use crate::sample::g_trapdoor::{
+        gadget_default::gen_trapdoor_default, gadget_parameters::GadgetParameters,
+    };
+    use qfall_math::{
+        integer_mod_q::{MatZq, Modulus},
+        traits::{Concatenate, GetNumColumns, GetNumRows},
+    };
+
+    /// Ensures that the delegated basis is a square matrix whose columns
+    /// are all in `Λ^⟂([a | h_id])`.
+    #[test]
+    fn is_basis_of_extended_lattice() {
+        let q = Modulus::from(127);
+        let params = GadgetParameters::init_default(4, &q);
+        let (a, r) = gen_trapdoor_default(&params.n, &q);
+        let h_id = MatZq::sample_uniform(&params.n, &(&params.n * &params.k), &q);
+
+        let (a_id, basis_id) = delegate_trapdoor(&params, &a, &r, &h_id);
+
+        assert_eq!(a.concat_horizontal(&h_id).unwrap(), a_id);
+        assert_eq!(a_id.get_num_columns(), basis_id.get_num_rows());
+        assert_eq!(basis_id.get_num_rows(), basis_id.get_num_columns());
+        let zero_vec = MatZq::new(a_id.get_num_rows(), 1, &q);
+        for i in 0..basis_id.get_num_columns() {
+            assert_eq!(zero_vec, &a_id * basis_id.get_column(i).unwrap());
+        }
+    }
+
+    /// Ensures that the function panics if the number of rows differ.
+    #[test]
+    #[should_panic]
+    fn mismatching_rows() {
+        let q = Modulus::from(127);
+        let params = GadgetParameters::init_default(4, &q);
+        let (a, r) = gen_trapdoor_default(&params.n, &q);
+        let h_id = MatZq::sample_uniform(5, 3, &q);
+
+        let _ = delegate_trapdoor(&params, &a, &r, &h_id);
+    }
+}
+
+#[cfg(test)]
+mod test_gen_trapdoor_module {
+    use super::{gen_gadget_mat, gen_trapdoor_module, samp_p_module};
+    use crate::sample::g_trapdoor::gadget_parameters::GadgetParametersModule;
+    use qfall_math::{
+        integer::{MatZ, Z},
+        integer_mod_q::{MatZq, Zq},
+        rational::Q,
+        traits::{Concatenate, Distance, GetEntry, GetNumColumns},
+    };
+
+    /// Generates a module trapdoor of rank `k` with default parameters.
+    fn gen_module(k: usize, n: i64, q: i64) -> (GadgetParametersModule, Vec<MatZq>, Vec<MatZ>) {
+        let params = GadgetParametersModule::init_default(k, n, q);
+        let a_bars: Vec<MatZq> = (0..k)
+            .map(|_| MatZq::sample_uniform(n, &params.inner.m_bar, &params.inner.q))
+            .collect();
+        let (pks, sks) = gen_trapdoor_module(&params, &a_bars);
+        (params, pks, sks)
+    }
+
+    /// Ensures that each sub-trapdoor satisfies the trapdoor equation `A_i * [R_i; I] = G`.
+    #[test]
+    fn is_trapdoor() {
+        let (params, pks, sks) = gen_module(3, 4, 1024);
+        let gadget_mat = gen_gadget_mat(&params.inner.n, &params.inner.k, &Z::from(2));
+
+        assert_eq!(3, pks.len());
+        assert_eq!(3, sks.len());
+        for (a, r) in pks.iter().zip(sks.iter()) {
+            let trapdoor = r
+                .concat_vertical(&MatZ::identity(r.get_num_columns(), r.get_num_columns()))
+                .unwrap();
+            assert_eq!(
+                MatZq::from((&gadget_mat, &params.inner.q)),
+                a * MatZq::from((&trapdoor, &params.inner.q))
+            );
+        }
+        assert_ne!(sks[0], sks[1]);
+    }
+
+    /// Ensures that sampled preimages are preimages w.r.t. the module matrix.
+    #[test]
+    fn preimage() {
+        let (params, pks, sks) = gen_module(2, 4, 1024);
+        let u = MatZq::sample_uniform(4, 1, &params.inner.q);
+
+        let x = samp_p_module(&pks, &sks, &u, &Q::from(100));
+
+        let a = pks[0].concat_horizontal(&pks[1]).unwrap();
+        assert_eq!(u, a * x);
+    }
+
+    /// Ensures that a Dual Regev-style encryption under the module matrix
+    /// `[A_1 | A_2]` and a syndrome `u` can be decrypted with a preimage of `u`.
+    #[test]
+    fn module_lwe_decryption() {
+        let (params, pks, sks) = gen_module(2, 4, 65536);
+        let q = &params.inner.q;
+        let a = pks[0].concat_horizontal(&pks[1]).unwrap();
+        let u = MatZq::sample_uniform(4, 1, q);
+        let x = samp_p_module(&pks, &sks, &u, &Q::from(100));
+        let q_half = Z::from(q).div_floor(2);
+
+        for msg in [Z::ZERO, Z::ONE] {
+            // c_1^t = s^t * A + e_1^t and c_2 = s^t * u + msg * ⌊q/2⌋
+            let s_t = MatZq::sample_uniform(1, 4, q);
+            let e_1 = MatZ::sample_uniform(1, a.get_num_columns(), -1, 2).unwrap();
+            let c_1 = &s_t * &a + MatZq::from((&e_1, q));
+            let c_2: Zq = (&s_t * &u).get_entry(0, 0).unwrap();
+            let c_2 = c_2 + &msg * &q_half;
+
+            // c_2 - c_1^t * x = - e_1^t * x + msg * ⌊q/2⌋
+            let c_1_x: Zq = (c_1 * &x).get_entry(0, 0).unwrap();
+            let result = c_2 - c_1_x;
+            let dec = if result.distance(Z::ZERO) > result.distance(q_half.clone()) {
+                Z::ONE
+            } else {
+                Z::ZERO
+            };
+            assert_eq!(msg, dec);
+        }
+    }
+
+    /// Ensures that a mismatching number of matrices results in a panic.
+    #[test]
+    #[should_panic]
+    fn mismatching_rank() {
+        let params = GadgetParametersModule::init_default(2, 4, 1024);
+        let a_bar = MatZq::sample_uniform(4, &params.inner.m_bar, &params.inner.q);
+
+        let _ = gen_trapdoor_module(&params, &[a_bar]);
+    }
+}
+
+#[cfg(test)]
+mod test_sample_g {
+    use super::{gen_gadget_mat, sample_g};
+    use qfall_math::{
+        integer::Z,
+        integer_mod_q::MatZq,
+        rational::Q,
+        traits::{GetEntry, GetNumColumns, GetNumRows},
+    };
+
+    /// Ensures that `G * x = u mod q` holds for gadget matrices with different bases.
+    #[test]
+    fn is_preimage() {
+        for (n, k, base, q) in [
+            (4, 7, 2, 128),
+            (3, 10, 2, 1021),
+            (5, 4, 3, 81),
+            (2, 1, 2, 2),
+        ] {
+            let g = MatZq::from((&gen_gadget_mat(n, k, &Z::from(base)), q));
+            let u = MatZq::sample_uniform(n, 3, q);
+
+            let x = sample_g(&g, &u);
+
+            assert_eq!(n * k, x.get_num_rows());
+            assert_eq!(3, x.get_num_columns());
+            assert_eq!(u, g * x);
+        }
+    }
+
+    /// Ensures that all entries of the preimage lie in `[0, base)` and hence
+    /// every column has Euclidean norm at most `(base - 1) * sqrt(nk)`.
+    #[test]
+    fn bounded_norm() {
+        let (n, k, base) = (6, 10, Z::from(2));
+        let g = MatZq::from((&gen_gadget_mat(n, k, &base), 1024));
+        let u = MatZq::sample_uniform(n, 4, 1024);
+
+        let x = sample_g(&g, &u);
+
+        for i in 0..x.get_num_rows() {
+            for j in 0..x.get_num_columns() {
+                let entry: Z = x.get_entry(i, j).unwrap();
+                assert!(entry >= Z::ZERO && entry < base);
+            }
+        }
+        let bound = Q::from((&base - Z::ONE) * (&base - Z::ONE) * Z::from(n * k));
+        for j in 0..x.get_num_columns() {
+            let norm = x.get_column(j).unwrap().norm_eucl_sqrd().unwrap();
+            assert!(Q::from(norm) <= bound);
+        }
+    }
+
+    /// Ensures that a matrix, which is not a gadget matrix, is rejected.
+    #[test]
+    #[should_panic]
+    fn not_gadget_matrix() {
+        let g = MatZq::sample_uniform(4, 28, 128);
+        let u = MatZq::sample_uniform(4, 1, 128);
+
+        let _ = sample_g(&g, &u);
+    }
+
+    /// Ensures that a syndrome with a different modulus is rejected.
+    #[test]
+    #[should_panic]
+    fn different_modulus() {
+        let g = MatZq::from((&gen_gadget_mat(4, 7, &Z::from(2)), 128));
+        let u = MatZq::sample_uniform(4, 1, 127);
+
+        let _ = sample_g(&g, &u);
+    }
+}
+
 #[cfg(test)]
 mod test_find_solution_gadget {
     use super::find_solution_gadget_vec;
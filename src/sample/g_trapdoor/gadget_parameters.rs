@@ -13,7 +13,8 @@ use super::trapdoor_distribution::{
     PlusMinusOneZero, TrapdoorDistribution, TrapdoorDistributionRing,
 };
 use crate::{
-    sample::g_trapdoor::trapdoor_distribution::SampleZ, utils::common_moduli::new_anticyclic,
+    sample::g_trapdoor::trapdoor_distribution::SampleZ,
+    utils::common_moduli::{new_anticyclic, PolyModulus},
 };
 use qfall_math::{
     integer::Z,
@@ -51,6 +52,25 @@ pub struct GadgetParameters {
     pub distribution: Box<dyn TrapdoorDistribution>,
 }
 
+/// Collects all parameters which are necessary to compute a module G-trapdoor,
+/// which consists of `k` independent classical G-trapdoors.
+///
+/// Attributes:
+/// - `k`: the rank of the module, i.e. the number of classical G-trapdoors
+/// - `inner`: the [`GadgetParameters`] with which each classical G-trapdoor is generated
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::sample::g_trapdoor::gadget_parameters::GadgetParametersModule;
+///
+/// let params = GadgetParametersModule::init_default(2, 4, 1024);
+/// ```
+#[derive(Serialize, Deserialize)]
+pub struct GadgetParametersModule {
+    pub k: usize,
+    pub inner: GadgetParameters,
+}
+
 /// Collects all parameters which are necessary to compute a ring-based G-trapdoor.
 /// You can either use [`GadgetParametersRing::init_default`] or set all values
 /// and distributions yourself.
@@ -63,6 +83,8 @@ pub struct GadgetParameters {
 /// - `base`: the base with which the gadget-vector and matrix are generated
 /// - `modulus`: holds the modulus q and the polynomial that is used for reduction
 /// - `distribution`: the distribution from which the matrix `A_bar` is sampled
+/// - `ntt_friendly`: specifies whether `q` is a prime with `q ≡ 1 mod 2n`,
+///     s.t. polynomials modulo `X^n + 1` can be multiplied via the NTT
 ///
 /// # Examples
 /// ```
@@ -78,6 +100,8 @@ pub struct GadgetParametersRing {
     pub base: Z,
     pub modulus: ModulusPolynomialRingZq,
     pub distribution: Box<dyn TrapdoorDistributionRing>,
+    #[serde(default)]
+    pub ntt_friendly: bool,
 }
 
 impl GadgetParameters {
@@ -133,6 +157,39 @@ impl GadgetParameters {
     }
 }
 
+impl GadgetParametersModule {
+    /// Initializes default values for [`GadgetParametersModule`] to create a module
+    /// G-trapdoor of rank `k`, where each classical G-trapdoor is generated
+    /// according to [`GadgetParameters::init_default`].
+    ///
+    /// Parameters:
+    /// - `k`: the rank of the module
+    /// - `n`: the security parameter for the generation
+    /// - `q`: the modulus over which the TrapGen operates
+    ///
+    /// Returns an instantiation of default [`GadgetParametersModule`].
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::sample::g_trapdoor::gadget_parameters::GadgetParametersModule;
+    ///
+    /// let params = GadgetParametersModule::init_default(2, 4, 1024);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `k < 1`.
+    /// - if the security parameter `n` is not in `[1, i64::MAX]`.
+    /// - if `q <= 1`.
+    pub fn init_default(k: usize, n: impl Into<Z>, q: impl Into<Modulus>) -> Self {
+        assert!(k >= 1, "The rank k of the module must be at least 1.");
+
+        Self {
+            k,
+            inner: GadgetParameters::init_default(n, q),
+        }
+    }
+}
+
 impl GadgetParametersRing {
     /// Initializes default values for [`GadgetParametersRing`] to create a ring-based
     /// G-trapdoor. The parameters follow the ones in
@@ -163,13 +220,49 @@ impl GadgetParametersRing {
     /// - if the security parameter `n` is not in `[1, i64::MAX]`.
     /// - if `q <= 1`.
     pub fn init_default(n: impl Into<Z>, q: impl Into<Modulus>) -> Self {
+        // panic if n < 1 (security parameter must be positive) and not larger than
+        // [`i64`] because downstream matrices can be at most that size
+        Self::init_with_base(n, q, 2)
+    }
+
+    /// Initializes values for [`GadgetParametersRing`] as in [`GadgetParametersRing::init_default`],
+    /// but with gadget vector `g = (1, b, b^2, ..., b^{k-1})` for the provided base `b`.
+    ///
+    /// - `k = log_b_ceil(q)`, i.e. a larger base reduces the length of the gadget vector
+    ///     and hence the dimension of the trapdoor at the cost of larger entries
+    ///     in the gadget preimages
+    /// - `m_bar = 2 + k`
+    ///
+    /// Parameters:
+    /// - `n`: the security parameter for the generation
+    /// - `q`: the modulus over which the TrapGen operates
+    /// - `base`: the base with which the gadget vector is generated
+    ///
+    /// Returns an instantiation of GadgetParameters with the provided base.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::sample::g_trapdoor::gadget_parameters::GadgetParametersRing;
+    /// use qfall_math::integer::Z;
+    ///
+    /// let params = GadgetParametersRing::init_with_base(8, 81, 3);
+    ///
+    /// assert_eq!(Z::from(4), params.k);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if the security parameter `n` is not in `[1, i64::MAX]`.
+    /// - if `q <= 1`.
+    /// - if `base < 2`.
+    pub fn init_with_base(n: impl Into<Z>, q: impl Into<Modulus>, base: impl Into<Z>) -> Self {
         // panic if n < 1 (security parameter must be positive) and not larger than
         // [`i64`] because downstream matrices can be at most that size
         let q = q.into();
         let n = n.into();
+        let base = base.into();
         assert!(n >= Z::ONE && n <= Z::from(i64::MAX));
+        assert!(base >= Z::from(2), "The base has to be at least 2.");
 
-        let base = Z::from(2);
         let log_q = Z::from(&q).log_ceil(&base).unwrap();
 
         let poly_mod = new_anticyclic(&n, &q).unwrap();
@@ -181,10 +274,116 @@ impl GadgetParametersRing {
             base,
             modulus: poly_mod,
             distribution: Box::new(SampleZ),
+            ntt_friendly: false,
+        }
+    }
+
+    /// Initializes [`GadgetParametersRing`] as in [`GadgetParametersRing::init_default`],
+    /// where `q` is the smallest prime with `q ≡ 1 mod 2n` and `q >= λ * n`.
+    /// Then, `X^n + 1` splits into linear factors modulo `q` and polynomials can
+    /// be multiplied via the NTT in `O(n log n)` instead of `O(n^2)`.
+    /// The lower bound `λ * n` ensures that the gadget has at least `log(λ * n)` entries
+    /// and that `q` grows with the targeted security level.
+    ///
+    /// Parameters:
+    /// - `n`: the security parameter and degree of `X^n + 1`, which has to be a power of two
+    /// - `lambda`: the targeted security level
+    ///
+    /// Returns an instantiation of [`GadgetParametersRing`] with an NTT-friendly modulus.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::sample::g_trapdoor::gadget_parameters::GadgetParametersRing;
+    /// use qfall_math::integer::Z;
+    ///
+    /// let params = GadgetParametersRing::init_ntt_friendly(8, 128);
+    ///
+    /// assert_eq!(Z::from(1), params.modulus.get_q().modulo(16));
+    /// assert!(params.ntt_friendly);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `n` is not a power of two.
+    /// - if `lambda < 1`.
+    /// - if `q` does not fit into an [`u64`].
+    pub fn init_ntt_friendly(n: usize, lambda: u32) -> Self {
+        assert!(n.is_power_of_two(), "n has to be a power of two.");
+        assert!(lambda >= 1, "The security level has to be positive.");
+
+        let two_n = 2 * n as u64;
+        let lower_bound = (n as u64) * lambda as u64;
+        // smallest q >= lower_bound with q ≡ 1 mod 2n
+        let mut q = lower_bound.saturating_sub(1).div_ceil(two_n) * two_n + 1;
+        while !is_prime(q) {
+            q = q.checked_add(two_n).unwrap();
+        }
+
+        let mut params = Self::init_default(n as u64, Z::from(q));
+        params.ntt_friendly = true;
+        params
+    }
+
+    /// Initializes default values for [`GadgetParametersRing`] to create a ring-based
+    /// G-trapdoor over the ring `Z_q[X]/(Φ_m(X))`, where `Φ_m` is the cyclotomic
+    /// polynomial of conductor `m`.
+    /// All other parameters are chosen as in [`GadgetParametersRing::init_default`],
+    /// where `n = φ(m)` is the degree of `Φ_m`.
+    ///
+    /// Parameters:
+    /// - `conductor`: the conductor `m` of the cyclotomic polynomial
+    /// - `q`: the modulus over which the TrapGen operates
+    ///
+    /// Returns an instantiation of default GadgetParameters over the
+    /// cyclotomic ring of conductor `m`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::sample::g_trapdoor::gadget_parameters::GadgetParametersRing;
+    /// use qfall_math::integer::Z;
+    ///
+    /// // Φ_12(X) = X^4 - X^2 + 1
+    /// let params = GadgetParametersRing::init_cyclotomic(12, 17);
+    ///
+    /// assert_eq!(Z::from(4), params.n);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `conductor < 1`.
+    /// - if `q <= 1`.
+    pub fn init_cyclotomic(conductor: usize, q: impl Into<Modulus>) -> Self {
+        let q = q.into();
+        let poly_modulus = PolyModulus::Cyclotomic(conductor);
+
+        let base = Z::from(2);
+        let log_q = Z::from(&q).log_ceil(&base).unwrap();
+
+        Self {
+            n: Z::from(poly_modulus.degree() as u64),
+            k: log_q.clone(),
+            m_bar: log_q + 2,
+            base,
+            modulus: poly_modulus.to_modulus(&q).unwrap(),
+            distribution: Box::new(SampleZ),
+            ntt_friendly: false,
         }
     }
 }
 
+/// Checks whether `value` is prime by trial division.
+fn is_prime(value: u64) -> bool {
+    if value < 2 {
+        return false;
+    }
+    let mut divisor = 2;
+    while divisor * divisor <= value {
+        if value % divisor == 0 {
+            return false;
+        }
+        divisor += 1;
+    }
+    true
+}
+
 #[cfg(test)]
 mod test_default_parameter {
     use crate::sample::g_trapdoor::gadget_parameters::GadgetParameters;
@@ -211,3 +410,117 @@ mod test_default_parameter {
         }
     }
 }
+
+#[cfg(test)]
+mod test_init_ntt_friendly {
+    use super::{is_prime, GadgetParametersRing};
+    use crate::sample::g_trapdoor::gadget_ring::gen_trapdoor_ring_lwe;
+    use qfall_math::{
+        integer::{MatPolyOverZ, PolyOverZ, Z},
+        integer_mod_q::MatPolynomialRingZq,
+        traits::{Concatenate, GetEntry, Pow},
+    };
+
+    /// Ensures that the selected modulus is the smallest prime with `q ≡ 1 mod 2n`
+    /// and `q >= λ * n`.
+    #[test]
+    fn smallest_ntt_friendly_prime() {
+        for (n, lambda) in [(4, 128), (8, 80), (16, 128), (64, 256)] {
+            let params = GadgetParametersRing::init_ntt_friendly(n, lambda);
+            let q = i64::try_from(&params.modulus.get_q()).unwrap() as u64;
+
+            assert!(params.ntt_friendly);
+            assert_eq!(1, q % (2 * n as u64));
+            assert!(is_prime(q));
+            assert!(q >= n as u64 * lambda as u64);
+            let mut smaller = q - 2 * n as u64;
+            while smaller >= n as u64 * lambda as u64 {
+                assert!(!is_prime(smaller));
+                smaller -= 2 * n as u64;
+            }
+        }
+    }
+
+    /// Ensures that a known NTT-friendly prime is found.
+    #[test]
+    fn known_prime() {
+        // 257 = 2 * 128 + 1 is prime and 257 >= 128 * 2
+        let params = GadgetParametersRing::init_ntt_friendly(128, 2);
+
+        assert_eq!(Z::from(257), params.modulus.get_q());
+        assert_eq!(Z::from(128), params.n);
+    }
+
+    /// Ensures that the ring trapdoor still satisfies the trapdoor equation
+    /// `A * [[e],[r],[I]] = g^t`.
+    #[test]
+    fn is_trapdoor() {
+        let params = GadgetParametersRing::init_ntt_friendly(8, 16);
+        let a_bar = PolyOverZ::sample_uniform(&params.n, 0, params.modulus.get_q()).unwrap();
+
+        let (a, r, e) = gen_trapdoor_ring_lwe(&params, &a_bar, 10).unwrap();
+
+        let trapdoor = e
+            .concat_vertical(&r)
+            .unwrap()
+            .concat_vertical(&MatPolyOverZ::identity(&params.k, &params.k))
+            .unwrap();
+        let trapdoor = MatPolynomialRingZq::from((&trapdoor, &params.modulus));
+        let res: MatPolynomialRingZq = &a * &trapdoor;
+
+        for i in 0..i64::try_from(&params.k).unwrap() {
+            let res_entry: PolyOverZ = res.get_entry(0, i).unwrap();
+            assert_eq!(res_entry, PolyOverZ::from(params.base.pow(i).unwrap()));
+        }
+    }
+
+    /// Ensures that the function panics if `n` is not a power of two.
+    #[test]
+    #[should_panic]
+    fn not_power_of_two() {
+        let _ = GadgetParametersRing::init_ntt_friendly(6, 128);
+    }
+}
+
+#[cfg(test)]
+mod test_init_with_base {
+    use super::GadgetParametersRing;
+    use qfall_math::integer::Z;
+
+    /// Ensures that `k` and `m_bar` depend on the base as expected.
+    #[test]
+    fn dimensions() {
+        for (q, base, k) in [
+            (81, 3, 4),
+            (82, 3, 5),
+            (64, 4, 3),
+            (65, 4, 4),
+            (1024, 2, 10),
+        ] {
+            let params = GadgetParametersRing::init_with_base(8, q, base);
+
+            assert_eq!(Z::from(base), params.base);
+            assert_eq!(Z::from(k), params.k);
+            assert_eq!(Z::from(k + 2), params.m_bar);
+            assert_eq!(Z::from(8), params.n);
+        }
+    }
+
+    /// Ensures that the default parameters use base `2`.
+    #[test]
+    fn default_is_base_two() {
+        let default = GadgetParametersRing::init_default(8, 1024);
+        let base_two = GadgetParametersRing::init_with_base(8, 1024, 2);
+
+        assert_eq!(default.base, base_two.base);
+        assert_eq!(default.k, base_two.k);
+        assert_eq!(default.m_bar, base_two.m_bar);
+    }
+
+    /// Ensures that the function panics if the base is smaller than `2`.
+    #[test]
+    #[should_panic]
+    fn base_too_small() {
+        let _ = GadgetParametersRing::init_with_base(8, 1024, 1);
+    }
+}
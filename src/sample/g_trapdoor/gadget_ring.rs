@@ -208,6 +208,50 @@ mod test_gen_trapdoor_ring {
             assert_eq!(res_entry.get_coeff(0).unwrap(), params.base.pow(i).unwrap())
         }
     }
+
+    /// Assure that the trapdoor returned from [`gen_trapdoor_ring_lwe`] is actually a
+    /// trapdoor for `a` over cyclotomic rings other than `X^n + 1`.
+    #[test]
+    fn is_trapdoor_cyclotomic() {
+        for conductor in [8, 12, 15] {
+            let params = GadgetParametersRing::init_cyclotomic(conductor, 32);
+            let a_bar = PolyOverZ::sample_uniform(&params.n, 0, params.modulus.get_q()).unwrap();
+
+            let (a, r, e) = gen_trapdoor_ring_lwe(&params, &a_bar, 10).unwrap();
+
+            let trapdoor =
+                MatPolynomialRingZq::from((&compute_trapdoor(&r, &e, &params.k), &params.modulus));
+            let res: MatPolynomialRingZq = &a * &trapdoor;
+
+            for i in 0..(&params.k).try_into().unwrap() {
+                let res_entry: PolyOverZ = res.get_entry(0, i).unwrap();
+                assert_eq!(res_entry, PolyOverZ::from(params.base.pow(i).unwrap()));
+            }
+        }
+    }
+
+    /// Assure that the trapdoor returned from [`gen_trapdoor_ring_lwe`] is actually a
+    /// trapdoor for `a` if the gadget vector uses base `3` or `4`.
+    #[test]
+    fn is_trapdoor_non_binary_base() {
+        for (q, base) in [(81, 3), (100, 3), (64, 4), (200, 4)] {
+            let params = GadgetParametersRing::init_with_base(6, q, base);
+            let a_bar = PolyOverZ::sample_uniform(&params.n, 0, params.modulus.get_q()).unwrap();
+
+            let (a, r, e) = gen_trapdoor_ring_lwe(&params, &a_bar, 10).unwrap();
+
+            let trapdoor =
+                MatPolynomialRingZq::from((&compute_trapdoor(&r, &e, &params.k), &params.modulus));
+            let res: MatPolynomialRingZq = &a * &trapdoor;
+
+            assert_eq!(params.k, Z::from(res.get_num_columns()));
+            assert_eq!(&params.m_bar, &Z::from(a.get_num_columns()));
+            for i in 0..(&params.k).try_into().unwrap() {
+                let res_entry: PolyOverZ = res.get_entry(0, i).unwrap();
+                assert_eq!(res_entry, PolyOverZ::from(params.base.pow(i).unwrap()));
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -236,4 +280,21 @@ mod test_find_solution_gadget_ring {
 
         assert_eq!(u, gadget.dot_product(&solution).unwrap())
     }
+
+    /// Ensures that the algorithm finds a correct solution for base `3`.
+    #[test]
+    fn is_correct_solution_base_three() {
+        let gp = GadgetParametersRing::init_with_base(3, 81, 3);
+
+        let gadget = gen_gadget_ring(&gp.k, &gp.base);
+        let gadget = MatPolynomialRingZq::from((&gadget, &gp.modulus));
+
+        let u = PolyOverZ::from_str("3  5 80 12").unwrap();
+        let u = PolynomialRingZq::from((&u, &gp.modulus));
+
+        let solution = find_solution_gadget_ring(&u, &gp.k, &gp.base);
+        let solution = MatPolynomialRingZq::from((&solution, &gp.modulus));
+
+        assert_eq!(u, gadget.dot_product(&solution).unwrap())
+    }
 }
@@ -59,6 +59,69 @@ pub fn gen_short_basis_for_trapdoor(
     sa_l * sa_r
 }
 
+/// Extends a short basis `S` of `Λ^⟂(a)` to a basis of `Λ^⟂([a | b])`
+/// according to Lemma 3.2 (ExtBasis) in the eprint version of
+/// [CHKP10](https://eprint.iacr.org/2009/351.pdf).
+///
+/// The basis is generated as `[ S | W, 0 | I ]`, where `W` is a solution of
+/// `aW = -b mod q`. Its Gram-Schmidt orthogonalization consists of the one of `S`
+/// and unit vectors, i.e. the extended basis is as short as `S` after orthogonalization.
+///
+/// Parameters:
+/// - `a`: the parity check matrix of the lattice with short basis `short_basis`
+/// - `short_basis`: a short basis of `Λ^⟂(a)`
+/// - `b`: the matrix with which `a` is extended
+///
+/// Returns a basis of `Λ^⟂([a | b])`, whose orthogonalized length
+/// equals the one of `short_basis`.
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::sample::g_trapdoor::{gadget_parameters::GadgetParameters,
+/// gadget_default::gen_trapdoor_default};
+/// use qfall_crypto::sample::g_trapdoor::short_basis_classical::{
+///     extend_short_basis, gen_short_basis_for_trapdoor,
+/// };
+/// use qfall_math::integer_mod_q::MatZq;
+///
+/// let params = GadgetParameters::init_default(10, 127);
+/// let (a, r) = gen_trapdoor_default(&params.n, 127);
+/// let tag = MatZq::identity(&params.n, &params.n, 127);
+/// let short_basis = gen_short_basis_for_trapdoor(&params, &tag, &a, &r);
+/// let b = MatZq::sample_uniform(&params.n, 20, 127);
+///
+/// let extended_basis = extend_short_basis(&a, &short_basis, &b);
+/// ```
+///
+/// # Panics ...
+/// - if the number of rows of `a` and `b` differ.
+/// - if `a` does not have full row rank.
+pub fn extend_short_basis(a: &MatZq, short_basis: &MatZ, b: &MatZq) -> MatZ {
+    assert_eq!(
+        a.get_num_rows(),
+        b.get_num_rows(),
+        "The matrices a and b have to have the same number of rows."
+    );
+
+    // compute `W` with `aW = -b mod q` column by column
+    let minus_b = Z::MINUS_ONE * b;
+    let mut w = MatZ::new(a.get_num_columns(), b.get_num_columns());
+    for j in 0..b.get_num_columns() {
+        let solution = a
+            .solve_gaussian_elimination(&minus_b.get_column(j).unwrap())
+            .unwrap()
+            .get_representative_0_modulus();
+        w.set_column(j, &solution, 0).unwrap();
+    }
+
+    let zero = MatZ::new(b.get_num_columns(), short_basis.get_num_columns());
+    let identity = MatZ::identity(b.get_num_columns(), b.get_num_columns());
+
+    let upper = short_basis.concat_horizontal(&w).unwrap();
+    let lower = zero.concat_horizontal(&identity).unwrap();
+    upper.concat_vertical(&lower).unwrap()
+}
+
 /// Computes [ I | R, 0 | I ]
 fn gen_sa_l(r: &MatZ) -> MatZ {
     let left = MatZ::identity(r.get_num_rows() + r.get_num_columns(), r.get_num_rows());
@@ -68,7 +131,7 @@ fn gen_sa_l(r: &MatZ) -> MatZ {
 }
 
 /// Computes `[ 0 | I, S' | W ]`
-fn gen_sa_r(params: &GadgetParameters, tag: &MatZq, a: &MatZq) -> MatZ {
+pub(crate) fn gen_sa_r(params: &GadgetParameters, tag: &MatZq, a: &MatZq) -> MatZ {
     let mut s = compute_s(params);
     // if `base^k = q`, then the reverse of `S` has a shorter diagonalization
     if params.base.pow(&params.k).unwrap() == Z::from(&params.q) {
@@ -486,3 +549,77 @@ mod test_compute_w {
         assert_eq!(gw, Z::MINUS_ONE * rhs)
     }
 }
+
+#[cfg(test)]
+mod test_extend_short_basis {
+    use super::{extend_short_basis, gen_short_basis_for_trapdoor};
+    use crate::sample::g_trapdoor::{
+        gadget_default::gen_trapdoor_default, gadget_parameters::GadgetParameters,
+    };
+    use qfall_math::{
+        integer::MatZ,
+        integer_mod_q::{MatZq, Modulus},
+        rational::{MatQ, Q},
+        traits::{Concatenate, GetNumColumns, GetNumRows},
+    };
+
+    /// Ensures that every vector of the extended basis is in `Λ^⟂([a | b])`
+    /// and that its orthogonalized length equals the one of the original basis.
+    #[test]
+    fn is_basis_same_orthogonalized_length() {
+        for n in [2, 5] {
+            let q = Modulus::from(127);
+            let params = GadgetParameters::init_default(n, &q);
+            let (a, r) = gen_trapdoor_default(&params.n, &q);
+            let tag = MatZq::identity(&params.n, &params.n, &q);
+            let short_basis = gen_short_basis_for_trapdoor(&params, &tag, &a, &r);
+            let b = MatZq::sample_uniform(&params.n, 7, &q);
+
+            let extended_basis = extend_short_basis(&a, &short_basis, &b);
+
+            let a_b = a.concat_horizontal(&b).unwrap();
+            let zero_vec = MatZq::new(a.get_num_rows(), 1, &q);
+            assert_eq!(
+                short_basis.get_num_rows() + 7,
+                extended_basis.get_num_rows()
+            );
+            assert_eq!(
+                extended_basis.get_num_rows(),
+                extended_basis.get_num_columns()
+            );
+            for i in 0..extended_basis.get_num_columns() {
+                assert_eq!(zero_vec, &a_b * extended_basis.get_column(i).unwrap())
+            }
+
+            let max_orthogonalized_length = |basis: &MatZ| {
+                let orthogonalized_basis = MatQ::from(basis).gso();
+                let mut max = Q::ZERO;
+                for i in 0..orthogonalized_basis.get_num_columns() {
+                    let b_tilde_i = orthogonalized_basis.get_column(i).unwrap();
+                    let length = b_tilde_i.norm_eucl_sqrd().unwrap();
+                    if length > max {
+                        max = length;
+                    }
+                }
+                max
+            };
+            assert_eq!(
+                max_orthogonalized_length(&short_basis),
+                max_orthogonalized_length(&extended_basis)
+            );
+        }
+    }
+
+    /// Ensures that `extend_short_basis` panics if the number of rows differ.
+    #[test]
+    #[should_panic]
+    fn mismatching_rows() {
+        let q = Modulus::from(127);
+        let params = GadgetParameters::init_default(2, &q);
+        let (a, r) = gen_trapdoor_default(&params.n, &q);
+        let tag = MatZq::identity(&params.n, &params.n, &q);
+        let short_basis = gen_short_basis_for_trapdoor(&params, &tag, &a, &r);
+
+        let _ = extend_short_basis(&a, &short_basis, &MatZq::new(3, 4, &q));
+    }
+}
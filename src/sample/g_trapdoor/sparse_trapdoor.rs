@@ -0,0 +1,330 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains a sparse representation of a G-Trapdoor `R`,
+//! which only stores the nonzero entries of `R`, and preimage sampling
+//! using this representation.
+
+use super::{
+    gadget_parameters::GadgetParameters, short_basis_classical::gen_sa_r,
+    trapdoor_distribution::PlusMinusOneZero,
+};
+use qfall_math::{
+    integer::{MatZ, Z},
+    integer_mod_q::MatZq,
+    rational::{MatQ, Q},
+    traits::{Concatenate, GetEntry, GetNumColumns, GetNumRows, SetEntry},
+};
+use serde::{Deserialize, Serialize};
+
+/// A G-Trapdoor `R ∈ Z^{m_bar x n*k}` storing only its nonzero entries.
+///
+/// Attributes:
+/// - `entries`: the nonzero entries `(row, column, value)` of `R` sorted by row and column
+/// - `rows`: the number of rows of `R`
+/// - `cols`: the number of columns of `R`
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::sample::g_trapdoor::{
+///     gadget_default::gen_trapdoor_default, sparse_trapdoor::SparseTrapdoor,
+/// };
+///
+/// let (_, r) = gen_trapdoor_default(10, 1024);
+/// let sparse_r = SparseTrapdoor::from(&r);
+///
+/// assert_eq!(r, sparse_r.to_dense());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SparseTrapdoor {
+    pub entries: Vec<(usize, usize, i32)>,
+    pub rows: usize,
+    pub cols: usize,
+}
+
+impl From<&MatZ> for SparseTrapdoor {
+    /// Converts a dense trapdoor into a [`SparseTrapdoor`] by dropping all zero entries.
+    ///
+    /// Parameters:
+    /// - `r`: the dense trapdoor
+    ///
+    /// Returns the [`SparseTrapdoor`] containing the nonzero entries of `r`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::sample::g_trapdoor::sparse_trapdoor::SparseTrapdoor;
+    /// use qfall_math::integer::MatZ;
+    /// use std::str::FromStr;
+    ///
+    /// let r = MatZ::from_str("[[1, 0, 0],[0, 0, -1]]").unwrap();
+    /// let sparse_r = SparseTrapdoor::from(&r);
+    ///
+    /// assert_eq!(vec![(0, 0, 1), (1, 2, -1)], sparse_r.entries);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if an entry of `r` does not fit into an [`i32`].
+    fn from(r: &MatZ) -> Self {
+        let rows = r.get_num_rows();
+        let cols = r.get_num_columns();
+
+        let mut entries = vec![];
+        for i in 0..rows {
+            for j in 0..cols {
+                let entry: Z = r.get_entry(i, j).unwrap();
+                if entry != Z::ZERO {
+                    let value = i32::try_from(i64::try_from(&entry).unwrap()).unwrap();
+                    entries.push((i as usize, j as usize, value));
+                }
+            }
+        }
+
+        Self {
+            entries,
+            rows: rows as usize,
+            cols: cols as usize,
+        }
+    }
+}
+
+impl From<MatZ> for SparseTrapdoor {
+    /// Converts a dense trapdoor into a [`SparseTrapdoor`] by dropping all zero entries.
+    ///
+    /// # Panics ...
+    /// - if an entry of `r` does not fit into an [`i32`].
+    fn from(r: MatZ) -> Self {
+        Self::from(&r)
+    }
+}
+
+impl SparseTrapdoor {
+    /// Converts the [`SparseTrapdoor`] back into a dense matrix.
+    ///
+    /// Returns the dense trapdoor `R`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::sample::g_trapdoor::sparse_trapdoor::SparseTrapdoor;
+    /// use qfall_math::integer::MatZ;
+    /// use std::str::FromStr;
+    ///
+    /// let r = MatZ::from_str("[[1, 0, 0],[0, 0, -1]]").unwrap();
+    ///
+    /// assert_eq!(r, SparseTrapdoor::from(&r).to_dense());
+    /// ```
+    pub fn to_dense(&self) -> MatZ {
+        let mut r = MatZ::new(self.rows, self.cols);
+        for (i, j, value) in &self.entries {
+            r.set_entry(*i, *j, *value).unwrap();
+        }
+        r
+    }
+
+    /// Computes `R * rhs` by only iterating over the nonzero entries of `R`,
+    /// i.e. row `i` of the result is `Σ_{(i, j, v)} v * rhs_j` for the rows `rhs_j` of `rhs`.
+    ///
+    /// Parameters:
+    /// - `rhs`: the matrix with `cols` rows that is multiplied from the right
+    ///
+    /// Returns the matrix `R * rhs`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::sample::g_trapdoor::sparse_trapdoor::SparseTrapdoor;
+    /// use qfall_math::integer::MatZ;
+    /// use std::str::FromStr;
+    ///
+    /// let r = MatZ::from_str("[[1, 0, 0],[0, 0, -1]]").unwrap();
+    /// let rhs = MatZ::from_str("[[1, 2],[3, 4],[5, 6]]").unwrap();
+    ///
+    /// assert_eq!(&r * &rhs, SparseTrapdoor::from(&r).mul_dense(&rhs));
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if the number of rows of `rhs` is not `cols`.
+    pub fn mul_dense(&self, rhs: &MatZ) -> MatZ {
+        assert_eq!(
+            self.cols as i64,
+            rhs.get_num_rows(),
+            "The number of rows of the right-hand side has to match the number of columns of R."
+        );
+        let num_cols = rhs.get_num_columns();
+
+        let mut result_rows = vec![MatZ::new(1, num_cols); self.rows];
+        for (i, j, value) in &self.entries {
+            let rhs_row = rhs.get_submatrix(*j, *j, 0, num_cols - 1).unwrap();
+            result_rows[*i] = &result_rows[*i] + Z::from(*value) * rhs_row;
+        }
+
+        let mut result = result_rows[0].clone();
+        for row in &result_rows[1..] {
+            result = result.concat_vertical(row).unwrap();
+        }
+        result
+    }
+
+    /// Computes the short basis `[ I | R, 0 | I ] * [ 0 | I, S' | W ]` of `Λ^⟂(A)`
+    /// as [`gen_short_basis_for_trapdoor`](super::short_basis_classical::gen_short_basis_for_trapdoor),
+    /// where the product with `R` is computed using [`SparseTrapdoor::mul_dense`].
+    fn short_basis(&self, params: &GadgetParameters, tag: &MatZq, a: &MatZq) -> MatZ {
+        let sa_r = gen_sa_r(params, tag, a);
+        let num_cols = sa_r.get_num_columns();
+        let m_bar = self.rows as i64;
+
+        let top = sa_r.get_submatrix(0, m_bar - 1, 0, num_cols - 1).unwrap();
+        let bottom = sa_r
+            .get_submatrix(m_bar, sa_r.get_num_rows() - 1, 0, num_cols - 1)
+            .unwrap();
+
+        (top + self.mul_dense(&bottom))
+            .concat_vertical(&bottom)
+            .unwrap()
+    }
+}
+
+/// Samples a preimage `x` with `A * x = u` from the discrete Gaussian distribution
+/// with Gaussian parameter `sigma` using a [`SparseTrapdoor`] of `A`.
+/// The preimage is distributed identically to a preimage sampled by
+/// [`samp_p_module`](super::gadget_classical::samp_p_module) with the dense trapdoor,
+/// as the same short basis is computed with sparse multiplications by `R`.
+///
+/// The gadget parameters are derived from the dimensions of `A` and `R`
+/// assuming `base = 2` and `tag = I_n`, as chosen by [`GadgetParameters::init_default`].
+///
+/// Parameters:
+/// - `a`: the parity-check matrix
+/// - `sparse_r`: the sparse G-Trapdoor of `a`
+/// - `u`: the syndrome of the preimage
+/// - `sigma`: the Gaussian parameter with which the preimage is sampled
+///
+/// Returns a preimage `x` of `u` w.r.t. `a`.
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::sample::g_trapdoor::{
+///     gadget_default::gen_trapdoor_default,
+///     sparse_trapdoor::{samp_p_sparse, SparseTrapdoor},
+/// };
+/// use qfall_math::{integer_mod_q::MatZq, rational::Q};
+///
+/// let (a, r) = gen_trapdoor_default(4, 1024);
+/// let u = MatZq::sample_uniform(4, 1, 1024);
+///
+/// let x = samp_p_sparse(&a, &SparseTrapdoor::from(&r), &u, &Q::from(100));
+///
+/// assert_eq!(u, a * x);
+/// ```
+///
+/// # Panics ...
+/// - if the dimensions of `a`, `sparse_r`, and `u` do not match.
+pub fn samp_p_sparse(a: &MatZq, sparse_r: &SparseTrapdoor, u: &MatZq, sigma: &Q) -> MatZ {
+    let n = Z::from(a.get_num_rows());
+    let q = a.get_mod();
+
+    let params = GadgetParameters {
+        n: n.clone(),
+        k: Z::from(sparse_r.cols as i64 / a.get_num_rows()),
+        m_bar: Z::from(sparse_r.rows as i64),
+        base: Z::from(2),
+        q: q.clone(),
+        distribution: Box::new(PlusMinusOneZero),
+    };
+    let tag = MatZq::identity(&n, &n, &q);
+    let short_base = sparse_r.short_basis(&params, &tag, a);
+    let short_base_gso = MatQ::from(&short_base).gso();
+
+    // x <- D_{Λ_u(A), sigma}
+    let sol: MatZ = a
+        .solve_gaussian_elimination(u)
+        .unwrap()
+        .get_representative_0_modulus();
+    let center = MatQ::from(&(-1 * &sol));
+    sol + MatZ::sample_d_precomputed_gso(&short_base, &short_base_gso, &n, &center, sigma).unwrap()
+}
+
+#[cfg(test)]
+mod test_sparse_trapdoor {
+    use super::{samp_p_sparse, SparseTrapdoor};
+    use crate::sample::g_trapdoor::{
+        gadget_classical::samp_p_module, gadget_default::gen_trapdoor_default,
+        gadget_parameters::GadgetParameters, short_basis_classical::gen_short_basis_for_trapdoor,
+    };
+    use qfall_math::{
+        integer::{MatZ, Z},
+        integer_mod_q::MatZq,
+        rational::Q,
+        traits::{GetNumColumns, GetNumRows},
+    };
+
+    /// Ensures that converting a trapdoor into a [`SparseTrapdoor`] and back is lossless
+    /// and that only nonzero entries are stored.
+    #[test]
+    fn round_trip() {
+        let (_, r) = gen_trapdoor_default(10, 1024);
+
+        let sparse_r = SparseTrapdoor::from(&r);
+
+        assert_eq!(r, sparse_r.to_dense());
+        assert_eq!(r.get_num_rows(), sparse_r.rows as i64);
+        assert_eq!(r.get_num_columns(), sparse_r.cols as i64);
+        assert!(sparse_r.entries.iter().all(|(_, _, value)| *value != 0));
+        assert!(sparse_r.entries.len() < (r.get_num_rows() * r.get_num_columns()) as usize);
+    }
+
+    /// Ensures that the sparse multiplication matches the dense multiplication.
+    #[test]
+    fn mul_dense() {
+        let (_, r) = gen_trapdoor_default(5, 256);
+        let rhs = MatZ::sample_uniform(r.get_num_columns(), 7, -50, 50).unwrap();
+
+        assert_eq!(&r * &rhs, SparseTrapdoor::from(&r).mul_dense(&rhs));
+    }
+
+    /// Ensures that the short basis computed with the sparse trapdoor
+    /// matches the short basis computed with the dense trapdoor.
+    #[test]
+    fn same_short_basis() {
+        let params = GadgetParameters::init_default(5, 256);
+        let (a, r) = gen_trapdoor_default(5, 256);
+        let tag = MatZq::identity(5, 5, 256);
+
+        let sparse_basis = SparseTrapdoor::from(&r).short_basis(&params, &tag, &a);
+
+        assert_eq!(
+            gen_short_basis_for_trapdoor(&params, &tag, &a, &r),
+            sparse_basis
+        );
+    }
+
+    /// Ensures that `samp_p_sparse` samples preimages and that their average
+    /// squared norm matches the one of preimages sampled with the dense trapdoor
+    /// within statistical noise.
+    #[test]
+    fn same_distribution_as_dense() {
+        let (a, r) = gen_trapdoor_default(2, 64);
+        let sparse_r = SparseTrapdoor::from(&r);
+        let u = MatZq::sample_uniform(2, 1, 64);
+        let sigma = Q::from(20);
+        let samples = 200;
+
+        let mut norm_sparse = Z::ZERO;
+        let mut norm_dense = Z::ZERO;
+        for _ in 0..samples {
+            let x_sparse = samp_p_sparse(&a, &sparse_r, &u, &sigma);
+            let x_dense = samp_p_module(&[a.clone()], &[r.clone()], &u, &sigma);
+            assert_eq!(u, &a * &x_sparse);
+
+            norm_sparse = norm_sparse + x_sparse.norm_eucl_sqrd().unwrap();
+            norm_dense = norm_dense + x_dense.norm_eucl_sqrd().unwrap();
+        }
+
+        // the average squared norms differ by less than 20%
+        let ratio = Q::from(norm_sparse) / Q::from(norm_dense);
+        assert!(ratio > Q::from((4, 5)) && ratio < Q::from((6, 5)));
+    }
+}
@@ -12,14 +12,17 @@
 use qfall_math::{
     integer::{MatPolyOverZ, MatZ, PolyOverZ, Z},
     rational::Q,
-    traits::SetEntry,
+    traits::{GetNumColumns, GetNumRows, SetEntry},
 };
+use rand::{Rng, RngCore};
 use serde::{Deserialize, Serialize};
 
 /// This trait should be implemented by all distributions which should be
 /// used to generate a trapdoor.
+/// Distributions have to be [`Send`] and [`Sync`] such that
+/// the constructions using them can be shared between threads.
 #[typetag::serde]
-pub trait TrapdoorDistribution {
+pub trait TrapdoorDistribution: Send + Sync {
     /// Sample from a matrix according to a predefined distribution.
     ///
     /// Parameters:
@@ -28,12 +31,30 @@ pub trait TrapdoorDistribution {
     ///
     /// Returns a matrix which is sampled according to the defined distribution.
     fn sample(&self, m_bar: &Z, w: &Z) -> MatZ;
+
+    /// Sample from a matrix according to a predefined distribution,
+    /// where all randomness is taken from `rng`, s.t. a seeded `rng`
+    /// yields reproducible matrices.
+    ///
+    /// The default implementation ignores `rng` and calls [`TrapdoorDistribution::sample`].
+    /// Hence, distributions have to override this function to be reproducible.
+    ///
+    /// Parameters:
+    /// - `m_bar`: number of rows of the matrix that is sampled
+    /// - `w`: number of columns of the matrix that is sampled
+    /// - `rng`: the random number generator from which the randomness is taken
+    ///
+    /// Returns a matrix which is sampled according to the defined distribution.
+    fn sample_with_rng(&self, m_bar: &Z, w: &Z, rng: &mut dyn RngCore) -> MatZ {
+        let _ = rng;
+        self.sample(m_bar, w)
+    }
 }
 
 /// This trait should be implemented by all distributions which should be
 /// used to generate a trapdoor over a ring.
 #[typetag::serde]
-pub trait TrapdoorDistributionRing {
+pub trait TrapdoorDistributionRing: Send + Sync {
     /// Sample a matrix of polynomials of length `n` with entries sampled
     /// using a predefined distribution.
     ///
@@ -80,9 +101,43 @@ impl TrapdoorDistribution for PlusMinusOneZero {
     /// # Panics...
     /// - if `m_bar` or `w` does not fit into in `i64` or is smaller than `1`.
     fn sample(&self, m_bar: &Z, w: &Z) -> MatZ {
-        let mat_1 = MatZ::sample_uniform(m_bar, w, 0, 2).unwrap();
-        let mat_2 = MatZ::sample_uniform(m_bar, w, 0, 2).unwrap();
-        mat_1 - mat_2
+        self.sample_with_rng(m_bar, w, &mut rand::thread_rng())
+    }
+
+    /// Sample a matrix where each entry has probability `1/2` for being `0`
+    /// and `1/4` each for `+/-1` using the randomness of `rng`.
+    ///
+    /// Parameters:
+    /// - `m_bar`: number of columns of the matrix
+    /// - `w`: number of rows of the matrix
+    /// - `rng`: the random number generator from which the randomness is taken
+    ///
+    /// Returns a matrix where each entry is sampled independently with probability
+    /// `1/2` for `0` and `1/4` each for `+/-1`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::sample::g_trapdoor::trapdoor_distribution::{PlusMinusOneZero, TrapdoorDistribution};
+    ///
+    /// let mat = PlusMinusOneZero.sample_with_rng(&42.into(), &24.into(), &mut rand::thread_rng());
+    /// ```
+    ///
+    /// # Panics...
+    /// - if `m_bar` or `w` does not fit into in `i64` or is smaller than `1`.
+    fn sample_with_rng(&self, m_bar: &Z, w: &Z, rng: &mut dyn RngCore) -> MatZ {
+        let mut out_mat = MatZ::new(m_bar, w);
+        for i in 0..out_mat.get_num_rows() {
+            for j in 0..out_mat.get_num_columns() {
+                let entry = match rng.gen_range(0..4) {
+                    0 => -1,
+                    1 => 1,
+                    _ => 0,
+                };
+                out_mat.set_entry(i, j, entry).unwrap();
+            }
+        }
+
+        out_mat
     }
 }
 
@@ -122,6 +177,44 @@ impl TrapdoorDistributionRing for SampleZ {
     }
 }
 
+/// Samples a matrix `R ∈ Z^{n x m}` with entries independently distributed as `D_{Z, σ}`,
+/// i.e. a trapdoor-like matrix with known statistical properties,
+/// which can be used for security simulations and as test trapdoors.
+/// For a G-trapdoor with gadget matrix of width `n * ⌈log q⌉`, choose `m = n * ⌈log q⌉`.
+///
+/// Parameters:
+/// - `n`: number of rows of the matrix, which is also used as security parameter
+///     for the tail cut of [`Z::sample_discrete_gauss`]
+/// - `m`: number of columns of the matrix
+/// - `sigma`: the Gaussian parameter of the entries
+///
+/// Returns a matrix with entries sampled independently from `D_{Z, σ}`.
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::sample::g_trapdoor::trapdoor_distribution::sample_trapdoor_distribution;
+/// use qfall_math::rational::Q;
+///
+/// let r = sample_trapdoor_distribution(10, 80, &Q::from(3));
+/// ```
+///
+/// # Panics ...
+/// - if `n <= 1` or `m < 1`.
+/// - if `sigma <= 0`.
+pub fn sample_trapdoor_distribution(n: usize, m: usize, sigma: &Q) -> MatZ {
+    assert!(m >= 1, "The number of columns must be positive.");
+    let n_z = Z::from(n as u64);
+    let mut out_mat = MatZ::new(n, m);
+    for i in 0..n {
+        for j in 0..m {
+            let sample = Z::sample_discrete_gauss(&n_z, 0, sigma).unwrap();
+            out_mat.set_entry(i, j, &sample).unwrap();
+        }
+    }
+
+    out_mat
+}
+
 #[cfg(test)]
 mod test_pm_one_zero {
     use super::PlusMinusOneZero;
@@ -173,3 +266,51 @@ mod test_sample_z {
         }
     }
 }
+
+#[cfg(test)]
+mod test_sample_trapdoor_distribution {
+    use super::sample_trapdoor_distribution;
+    use qfall_math::{
+        integer::Z,
+        rational::Q,
+        traits::{GetEntry, GetNumColumns, GetNumRows},
+    };
+
+    /// Ensures that the matrix has dimensions `n x (n * log q)`.
+    #[test]
+    fn dimensions() {
+        let (n, log_q) = (10, 8);
+
+        let r = sample_trapdoor_distribution(n, n * log_q, &Q::from(3));
+
+        assert_eq!(n as i64, r.get_num_rows());
+        assert_eq!((n * log_q) as i64, r.get_num_columns());
+    }
+
+    /// Ensures that the infinity norm is bounded by `σ * sqrt(log(1/ε))` for `ε = 2^{-80}`,
+    /// which holds except with probability about `2^{-350}` per entry.
+    #[test]
+    fn infinity_norm_bounded() {
+        let sigma = Q::from(3);
+        let bound = &sigma * Q::from(80).sqrt();
+
+        for _ in 0..5 {
+            let r = sample_trapdoor_distribution(10, 80, &sigma);
+
+            for i in 0..r.get_num_rows() {
+                for j in 0..r.get_num_columns() {
+                    let entry: Z = r.get_entry(i, j).unwrap();
+                    assert!(Q::from(entry.abs()) <= bound);
+                }
+            }
+        }
+    }
+
+    /// Ensures that the entries are not all zero, i.e. that the Gaussian parameter is used.
+    #[test]
+    fn not_zero() {
+        let r = sample_trapdoor_distribution(10, 80, &Q::from(3));
+
+        assert!(!r.is_zero());
+    }
+}
@@ -0,0 +1,148 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains a function to switch the modulus of LWE ciphertexts
+//! to a smaller modulus, as used e.g. for bootstrapping.
+
+use qfall_math::{
+    integer::{MatZ, Z},
+    integer_mod_q::{MatZq, Modulus},
+    traits::{GetEntry, GetNumColumns, GetNumRows, SetEntry},
+};
+
+/// Switches the modulus of `c` from `q` to `new_q` by
+/// rounding each entry, i.e. `c'_i = round(c_i * new_q / q)`.
+///
+/// Measured in multiples of `q / new_q`, the rounding error of each entry
+/// is at most `1/2`, i.e. `c'_i * q / new_q` differs from `c_i`
+/// by at most `(q / new_q) / 2`.
+/// Hence, the noise of a ciphertext scaled down to `new_q` grows by at most
+/// `1/2` times the `l_1`-norm of the secret key used for decryption.
+///
+/// Parameters:
+/// - `c`: the matrix, e.g. a ciphertext, whose modulus should be switched
+/// - `new_q`: the modulus of the output, which must not be larger than `q`
+///
+/// Returns the matrix `round(c * new_q / q)` with modulus `new_q`.
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::sample::modulus_switch::modulus_switch;
+/// use qfall_math::integer_mod_q::{MatZq, Modulus};
+/// use std::str::FromStr;
+///
+/// let c = MatZq::from_str("[[0],[50],[99]] mod 100").unwrap();
+///
+/// let c_switched = modulus_switch(&c, &Modulus::from(10));
+///
+/// assert_eq!(MatZq::from_str("[[0],[5],[0]] mod 10").unwrap(), c_switched);
+/// ```
+///
+/// # Panics ...
+/// - if `new_q` is larger than the modulus of `c`.
+pub fn modulus_switch(c: &MatZq, new_q: &Modulus) -> MatZq {
+    let q = Z::from(&c.get_mod());
+    let new_q_z = Z::from(new_q);
+    assert!(
+        new_q_z <= q,
+        "The new modulus {new_q_z} must not be larger than the old modulus {q}."
+    );
+
+    let c = c.get_representative_0_modulus();
+    let two_q = Z::from(2) * &q;
+    let mut out = MatZ::new(c.get_num_rows(), c.get_num_columns());
+    for i in 0..c.get_num_rows() {
+        for j in 0..c.get_num_columns() {
+            let entry: Z = c.get_entry(i, j).unwrap();
+            // round(c_i * new_q / q) = ⌊(2 * c_i * new_q + q) / (2 * q)⌋
+            let rounded = (Z::from(2) * entry * &new_q_z + &q).div_floor(&two_q);
+            out.set_entry(i, j, rounded).unwrap();
+        }
+    }
+
+    MatZq::from((&out, new_q))
+}
+
+#[cfg(test)]
+mod test_modulus_switch {
+    use super::modulus_switch;
+    use crate::construction::pk_encryption::{DualRegev, PKEncryptionScheme};
+    use qfall_math::{
+        integer::Z,
+        integer_mod_q::{MatZq, Modulus},
+        traits::{GetEntry, GetNumRows},
+    };
+    use std::str::FromStr;
+
+    /// Ensures that each entry is rounded to the closest multiple of `q / new_q`.
+    #[test]
+    fn rounding() {
+        let c = MatZq::from_str("[[0, 4, 5, 6],[94, 95, 96, 99]] mod 100").unwrap();
+
+        let c_switched = modulus_switch(&c, &Modulus::from(10));
+
+        assert_eq!(
+            MatZq::from_str("[[0, 0, 1, 1],[9, 0, 0, 0]] mod 10").unwrap(),
+            c_switched
+        );
+    }
+
+    /// Ensures that the rounding error of each entry is at most `(q / new_q) / 2`.
+    #[test]
+    fn rounding_error_bound() {
+        let q = Z::from(u32::MAX);
+        let new_q = Modulus::from(1009);
+        let c = MatZq::sample_uniform(50, 1, &q);
+
+        let c_switched = modulus_switch(&c, &new_q).get_representative_0_modulus();
+        let c = c.get_representative_0_modulus();
+
+        for i in 0..c.get_num_rows() {
+            let entry: Z = c.get_entry(i, 0).unwrap();
+            let entry_switched: Z = c_switched.get_entry(i, 0).unwrap();
+            // the distance is computed modulo q * new_q, as entries close to q
+            // may be rounded to new_q = 0 mod new_q
+            let error = (entry_switched * &q - entry * Z::from(&new_q)).abs();
+            let wrapped_error = &q * Z::from(&new_q) - &error;
+            assert!(Z::from(2) * error <= q || Z::from(2) * wrapped_error <= q);
+        }
+    }
+
+    /// Ensures that [`DualRegev`] ciphertexts can be decrypted after switching
+    /// to a smaller modulus if the noise is small enough.
+    #[test]
+    fn dual_regev_decryption() {
+        let dual_regev = DualRegev::new(13, 154, 2147483647, 0.00001);
+        let small_dual_regev = DualRegev::new(13, 154, 65537, 0.00001);
+        let (pk, sk) = dual_regev.gen();
+
+        for msg in [Z::ZERO, Z::ONE] {
+            let cipher = dual_regev.enc(&pk, &msg);
+            let cipher_switched = modulus_switch(&cipher, &Modulus::from(65537));
+
+            assert_eq!(msg, small_dual_regev.dec(&sk, &cipher_switched));
+        }
+    }
+
+    /// Ensures that switching to the same modulus does not change the matrix.
+    #[test]
+    fn same_modulus() {
+        let c = MatZq::sample_uniform(5, 5, 127);
+
+        assert_eq!(c, modulus_switch(&c, &Modulus::from(127)));
+    }
+
+    /// Ensures that a larger modulus results in a panic.
+    #[test]
+    #[should_panic]
+    fn larger_modulus() {
+        let c = MatZq::sample_uniform(2, 2, 127);
+
+        let _ = modulus_switch(&c, &Modulus::from(128));
+    }
+}
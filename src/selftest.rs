@@ -0,0 +1,138 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains a self-test, which checks that the main primitives
+//! of this crate work correctly in the current environment, e.g. at service startup.
+
+use crate::{
+    construction::{
+        identity_based_encryption::DualRegevIBE,
+        signature::{SignatureScheme, FDH},
+    },
+    error::CryptoError,
+    primitive::psf::{PSF, PSFGPV},
+    sample::g_trapdoor::{
+        gadget_classical::gen_gadget_mat, gadget_default::gen_trapdoor_default,
+        gadget_parameters::GadgetParameters,
+    },
+};
+use qfall_math::{
+    integer::{MatZ, Z},
+    rational::Q,
+    traits::Concatenate,
+};
+
+/// Runs a quick self-test of the main primitives of this crate.
+///
+/// The following checks are performed in this order:
+/// 1. `DualRegevIBE::default()` passes `check_security` and `check_correctness`
+/// 2. a sign and verify cycle of `FDH::init_gpv(4, 113, 17)` succeeds
+/// 3. `gen_trapdoor_default(5, 1024)` outputs a valid G-Trapdoor, i.e. `A * [R; I] = G`
+/// 4. a preimage sampled by [`PSF::samp_p`] of [`PSFGPV`] passes [`PSF::check_domain`]
+///
+/// Returns an empty result if all checks succeeded or a [`CryptoError`]
+/// naming the first check that failed.
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::selftest::run_security_self_test;
+///
+/// assert!(run_security_self_test().is_ok());
+/// ```
+///
+/// # Errors and Failures
+/// - Returns a [`CryptoError`] of type [`SelfTestFailure`](CryptoError::SelfTestFailure)
+///     containing the name of the first check that failed.
+pub fn run_security_self_test() -> Result<(), CryptoError> {
+    check("dual_regev_ibe_parameters", dual_regev_ibe_parameters)?;
+    check("fdh_sign_verify", fdh_sign_verify)?;
+    check("trapdoor_equation", trapdoor_equation)?;
+    check("psf_preimage_domain", psf_preimage_domain)?;
+
+    Ok(())
+}
+
+/// Runs the check `test` and converts a failure into a [`CryptoError`] named `name`.
+fn check(name: &str, test: fn() -> bool) -> Result<(), CryptoError> {
+    if test() {
+        Ok(())
+    } else {
+        Err(CryptoError::SelfTestFailure(String::from(name)))
+    }
+}
+
+/// Checks whether the default [`DualRegevIBE`] parameters are secure and correct.
+fn dual_regev_ibe_parameters() -> bool {
+    let ibe = DualRegevIBE::default();
+
+    ibe.check_security().is_ok() && ibe.check_correctness().is_ok()
+}
+
+/// Checks whether a signature of the default FDH signature scheme is verified.
+fn fdh_sign_verify() -> bool {
+    let m = String::from("qFALL-crypto self-test");
+    let mut fdh = FDH::init_gpv(4, 113, 17);
+    let (pk, sk) = fdh.gen();
+
+    match fdh.sign(m.clone(), &sk, &pk) {
+        Ok(sigma) => fdh.vfy(m, &sigma, &pk),
+        Err(_) => false,
+    }
+}
+
+/// Checks whether `A * [R; I] = G` holds for a G-Trapdoor generated with default parameters.
+fn trapdoor_equation() -> bool {
+    let (n, k) = (5, 10);
+    let (a, r) = gen_trapdoor_default(n, 2_i64.pow(k));
+
+    let trapdoor = r
+        .concat_vertical(&MatZ::identity(n * k as i64, n * k as i64))
+        .unwrap();
+
+    gen_gadget_mat(n, k, &Z::from(2)) == (a * trapdoor).get_representative_0_modulus()
+}
+
+/// Checks whether a preimage sampled by [`PSFGPV`] is in the domain of the PSF.
+fn psf_preimage_domain() -> bool {
+    let psf = PSFGPV {
+        gp: GadgetParameters::init_default(8, 64),
+        s: Q::from(12),
+        cdt: None,
+        cdt_cache: Default::default(),
+    };
+    let (a, td) = psf.trap_gen();
+    let domain_sample = psf.samp_d();
+    let range_fa = psf.f_a(&a, &domain_sample);
+
+    let preimage = psf.samp_p(&a, &td, &range_fa);
+
+    psf.check_domain(&preimage)
+}
+
+#[cfg(test)]
+mod test_self_test {
+    use super::{check, run_security_self_test};
+    use crate::error::CryptoError;
+
+    /// Ensures that the self-test succeeds in the current environment.
+    #[test]
+    fn succeeds() {
+        assert!(run_security_self_test().is_ok());
+    }
+
+    /// Ensures that a failing check returns an error containing its name.
+    #[test]
+    fn failure_name() {
+        let result = check("failing_check", || false);
+
+        match result {
+            Err(CryptoError::SelfTestFailure(name)) => assert_eq!("failing_check", name),
+            _ => panic!("The failing check did not return a SelfTestFailure."),
+        }
+    }
+}
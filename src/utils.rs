@@ -10,5 +10,11 @@
 //!
 //! This can include specialized implementations for certain parameter sets, such as rotation matrices.
 
+pub mod cca_oracle;
 pub mod common_moduli;
+pub mod constant_time;
+pub mod conversion;
+pub mod math_util;
 pub mod rotation_matrix;
+pub mod scheme_summary;
+pub mod zeroize;
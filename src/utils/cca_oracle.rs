@@ -0,0 +1,156 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains a simulator of the decryption oracle of the IND-CCA2 game,
+//! which can be used to test constructions against CCA2 adversaries.
+
+use crate::construction::pk_encryption::PKEncryptionSchemeMut;
+use qfall_math::integer::Z;
+
+/// Simulates the decryption oracle of the IND-CCA2 game, which decrypts
+/// any ciphertext except for the challenge ciphertext.
+///
+/// Attributes:
+/// - `scheme`: the scheme used for decryption
+/// - `sk`: the secret key used for decryption
+/// - `challenge_cipher`: the challenge ciphertext, which must not be decrypted
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::construction::pk_encryption::{CCSfromIBE, PKEncryptionSchemeMut};
+/// use qfall_crypto::utils::cca_oracle::CCAOracle;
+/// use qfall_math::integer::Z;
+///
+/// let mut scheme = CCSfromIBE::init_dr_pfdh_from_n(4);
+/// let (pk, sk) = scheme.gen();
+/// let challenge_cipher = scheme.enc(&pk, 1);
+/// let other_cipher = scheme.enc(&pk, 1);
+///
+/// let mut oracle = CCAOracle::new(scheme, sk, &challenge_cipher);
+///
+/// assert_eq!(None, oracle.dec_oracle(&challenge_cipher));
+/// assert_eq!(Some(Z::ONE), oracle.dec_oracle(&other_cipher));
+/// ```
+pub struct CCAOracle<S: PKEncryptionSchemeMut> {
+    scheme: S,
+    sk: S::SecretKey,
+    challenge_cipher: S::Cipher,
+}
+
+impl<S> CCAOracle<S>
+where
+    S: PKEncryptionSchemeMut,
+    S::Cipher: Clone + PartialEq,
+{
+    /// Instantiates a decryption oracle for the challenge ciphertext `challenge_cipher`.
+    ///
+    /// Parameters:
+    /// - `scheme`: the scheme used for decryption
+    /// - `sk`: the secret key used for decryption
+    /// - `challenge_cipher`: the challenge ciphertext, which must not be decrypted
+    ///
+    /// Returns a [`CCAOracle`] instance.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pk_encryption::{CCSfromIBE, PKEncryptionSchemeMut};
+    /// use qfall_crypto::utils::cca_oracle::CCAOracle;
+    /// let mut scheme = CCSfromIBE::init_dr_pfdh_from_n(4);
+    /// let (pk, sk) = scheme.gen();
+    /// let challenge_cipher = scheme.enc(&pk, 1);
+    ///
+    /// let oracle = CCAOracle::new(scheme, sk, &challenge_cipher);
+    /// ```
+    pub fn new(scheme: S, sk: S::SecretKey, challenge_cipher: &S::Cipher) -> Self {
+        Self {
+            scheme,
+            sk,
+            challenge_cipher: challenge_cipher.clone(),
+        }
+    }
+
+    /// Decrypts `cipher` unless it equals the challenge ciphertext.
+    ///
+    /// Parameters:
+    /// - `cipher`: the ciphertext that should be decrypted
+    ///
+    /// Returns [`None`] if `cipher` is the challenge ciphertext or is rejected
+    /// by the scheme and the decryption of `cipher` otherwise.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pk_encryption::{CCSfromIBE, PKEncryptionSchemeMut};
+    /// use qfall_crypto::utils::cca_oracle::CCAOracle;
+    /// let mut scheme = CCSfromIBE::init_dr_pfdh_from_n(4);
+    /// let (pk, sk) = scheme.gen();
+    /// let challenge_cipher = scheme.enc(&pk, 1);
+    /// let mut oracle = CCAOracle::new(scheme, sk, &challenge_cipher);
+    ///
+    /// assert!(oracle.dec_oracle(&challenge_cipher).is_none());
+    /// ```
+    pub fn dec_oracle(&mut self, cipher: &S::Cipher) -> Option<Z> {
+        if cipher == &self.challenge_cipher {
+            return None;
+        }
+        self.scheme.dec(&self.sk, cipher).ok()
+    }
+
+    /// Replaces the challenge ciphertext, e.g. to start a new round of the game.
+    ///
+    /// Parameters:
+    /// - `challenge_cipher`: the new challenge ciphertext
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::construction::pk_encryption::{CCSfromIBE, PKEncryptionSchemeMut};
+    /// use qfall_crypto::utils::cca_oracle::CCAOracle;
+    /// let mut scheme = CCSfromIBE::init_dr_pfdh_from_n(4);
+    /// let (pk, sk) = scheme.gen();
+    /// let challenge_cipher = scheme.enc(&pk, 1);
+    /// let new_challenge_cipher = scheme.enc(&pk, 0);
+    /// let mut oracle = CCAOracle::new(scheme, sk, &challenge_cipher);
+    ///
+    /// oracle.set_challenge(&new_challenge_cipher);
+    ///
+    /// assert!(oracle.dec_oracle(&challenge_cipher).is_some());
+    /// assert!(oracle.dec_oracle(&new_challenge_cipher).is_none());
+    /// ```
+    pub fn set_challenge(&mut self, challenge_cipher: &S::Cipher) {
+        self.challenge_cipher = challenge_cipher.clone();
+    }
+
+    /// Returns a mutable reference to the underlying scheme, e.g. to
+    /// encrypt further messages during the game.
+    pub fn scheme_mut(&mut self) -> &mut S {
+        &mut self.scheme
+    }
+}
+
+#[cfg(test)]
+mod test_cca_oracle {
+    use super::CCAOracle;
+    use crate::construction::pk_encryption::{CCSfromIBE, PKEncryptionSchemeMut};
+    use qfall_math::integer::Z;
+
+    /// Ensures that the challenge ciphertext is not decrypted and
+    /// all other ciphertexts are decrypted correctly.
+    #[test]
+    fn challenge_restriction() {
+        let mut scheme = CCSfromIBE::init_dr_pfdh_from_n(4);
+        let (pk, sk) = scheme.gen();
+        let challenge_cipher = scheme.enc(&pk, 0);
+        let cipher_zero = scheme.enc(&pk, 0);
+        let cipher_one = scheme.enc(&pk, 1);
+
+        let mut oracle = CCAOracle::new(scheme, sk, &challenge_cipher);
+
+        assert_eq!(None, oracle.dec_oracle(&challenge_cipher));
+        assert_eq!(Some(Z::ZERO), oracle.dec_oracle(&cipher_zero));
+        assert_eq!(Some(Z::ONE), oracle.dec_oracle(&cipher_one));
+    }
+}
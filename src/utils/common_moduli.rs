@@ -11,11 +11,200 @@
 
 use qfall_math::{
     error::MathError,
+    integer::Z,
     integer_mod_q::{Modulus, ModulusPolynomialRingZq, PolyOverZq},
     traits::SetCoefficient,
 };
+use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 
+/// Describes the modulus polynomial of a polynomial ring.
+///
+/// Possible entries:
+/// - `XnPlus1(n)` describes the polynomial `X^n + 1`
+/// - `Cyclotomic(m)` describes the `m`-th cyclotomic polynomial `Φ_m(X)`,
+///     whose degree is Euler's totient `φ(m)`
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::utils::common_moduli::PolyModulus;
+/// use qfall_math::integer_mod_q::PolyOverZq;
+///
+/// // X^4 + 1 = Φ_8(X)
+/// let poly_mod = PolyModulus::Cyclotomic(8).to_modulus(17).unwrap();
+/// let anticyclic = PolyModulus::XnPlus1(4).to_modulus(17).unwrap();
+///
+/// assert_eq!(PolyOverZq::from(&anticyclic), PolyOverZq::from(&poly_mod));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PolyModulus {
+    XnPlus1(usize),
+    Cyclotomic(usize),
+}
+
+impl PolyModulus {
+    /// Returns the degree of the modulus polynomial.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::utils::common_moduli::PolyModulus;
+    ///
+    /// assert_eq!(4, PolyModulus::XnPlus1(4).degree());
+    /// assert_eq!(4, PolyModulus::Cyclotomic(12).degree());
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if the conductor of a cyclotomic polynomial is `0`.
+    pub fn degree(&self) -> usize {
+        match self {
+            PolyModulus::XnPlus1(n) => *n,
+            PolyModulus::Cyclotomic(m) => {
+                assert!(*m >= 1, "The conductor must be at least 1.");
+                (1..=*m).filter(|i| gcd(*i, *m) == 1).count()
+            }
+        }
+    }
+
+    /// Outputs the modulus polynomial as a [`ModulusPolynomialRingZq`] modulo `q`.
+    ///
+    /// Parameters:
+    /// - `q`: specifies the modulus of the modulus polynomial
+    ///
+    /// Returns the [`ModulusPolynomialRingZq`] described by `self` or
+    /// a [`MathError`] if the degree does not fit into an [`i64`].
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::utils::common_moduli::PolyModulus;
+    ///
+    /// let poly_mod = PolyModulus::Cyclotomic(12).to_modulus(17).unwrap();
+    /// ```
+    ///
+    /// # Errors and Failures
+    /// - Returns a [`MathError`] of type [`OutOfBounds`](MathError::OutOfBounds) if
+    ///     the degree does not fit into an [`i64`].
+    ///
+    /// # Panics ...
+    /// - if the `q` is not larger than `1`.
+    /// - if the conductor of a cyclotomic polynomial is `0`.
+    pub fn to_modulus(&self, q: impl Into<Modulus>) -> Result<ModulusPolynomialRingZq, MathError> {
+        match self {
+            PolyModulus::XnPlus1(n) => new_anticyclic(*n as u64, q),
+            PolyModulus::Cyclotomic(m) => new_cyclotomic(*m, q),
+        }
+    }
+}
+
+/// Outputs a [`ModulusPolynomialRingZq`] of the form `Φ_m(X) mod q`, where `Φ_m`
+/// is the `m`-th cyclotomic polynomial.
+///
+/// `Φ_m` is computed as `prod_{d | m} (X^d - 1)^{μ(m / d)}`,
+/// where `μ` denotes the Möbius function.
+///
+/// Parameters:
+/// - `m`: specifies the conductor of the cyclotomic polynomial
+/// - `q`: specifies the modulus of the modulus polynomial
+///
+/// Returns a [`ModulusPolynomialRingZq`] of the form `Φ_m(X) mod q` or
+/// a [`MathError`] if the degree does not fit into an [`i64`].
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::utils::common_moduli::new_cyclotomic;
+///
+/// // Φ_12(X) = X^4 - X^2 + 1
+/// let poly_mod = new_cyclotomic(12, 17);
+/// ```
+///
+/// # Errors and Failures
+/// - Returns a [`MathError`] of type [`OutOfBounds`](MathError::OutOfBounds) if
+///     the degree does not fit into an [`i64`].
+///
+/// # Panics ...
+/// - if the `q` is not larger than `1`.
+/// - if `m` is `0`.
+pub fn new_cyclotomic(
+    m: usize,
+    q: impl Into<Modulus>,
+) -> Result<ModulusPolynomialRingZq, MathError> {
+    assert!(m >= 1, "The conductor must be at least 1.");
+    let q = q.into();
+
+    // X^d - 1 with μ(m / d) = 1 are multiplied and with μ(m / d) = -1 divided out,
+    // which is exact as the result is a polynomial
+    let divisors: Vec<usize> = (1..=m).filter(|d| m % d == 0).collect();
+    let mut coeffs = vec![Z::ONE];
+    for d in &divisors {
+        if mobius(m / d) == 1 {
+            coeffs = mul_x_d_minus_one(&coeffs, *d);
+        }
+    }
+    for d in &divisors {
+        if mobius(m / d) == -1 {
+            coeffs = div_x_d_minus_one(&coeffs, *d);
+        }
+    }
+
+    let mut poly = PolyOverZq::from((0, &q));
+    for (i, coeff) in coeffs.iter().enumerate().rev() {
+        poly.set_coeff(i as u64, coeff)?;
+    }
+    Ok(ModulusPolynomialRingZq::from(&poly))
+}
+
+/// Returns the greatest common divisor of `a` and `b`.
+fn gcd(mut a: usize, mut b: usize) -> usize {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Returns the Möbius function `μ(n)`, i.e. `0` if `n` is not square-free and
+/// `(-1)^r` if `n` is the product of `r` distinct primes.
+fn mobius(mut n: usize) -> i8 {
+    let mut result = 1;
+    let mut p = 2;
+    while p * p <= n {
+        if n % p == 0 {
+            n /= p;
+            if n % p == 0 {
+                return 0;
+            }
+            result = -result;
+        }
+        p += 1;
+    }
+    if n > 1 {
+        result = -result;
+    }
+    result
+}
+
+/// Multiplies the polynomial with coefficients `coeffs` by `X^d - 1`.
+fn mul_x_d_minus_one(coeffs: &[Z], d: usize) -> Vec<Z> {
+    let mut out = vec![Z::ZERO; coeffs.len() + d];
+    for (i, coeff) in coeffs.iter().enumerate() {
+        out[i + d] = &out[i + d] + coeff;
+        out[i] = &out[i] - coeff;
+    }
+    out
+}
+
+/// Divides the polynomial with coefficients `coeffs` by `X^d - 1`,
+/// assuming that the division is exact.
+fn div_x_d_minus_one(coeffs: &[Z], d: usize) -> Vec<Z> {
+    let mut remainder = coeffs.to_vec();
+    let mut out = vec![Z::ZERO; coeffs.len() - d];
+    for i in (d..coeffs.len()).rev() {
+        let coeff = remainder[i].clone();
+        // subtract coeff * X^{i - d} * (X^d - 1)
+        remainder[i - d] = &remainder[i - d] + &coeff;
+        out[i - d] = coeff;
+    }
+    out
+}
+
 /// Outputs a [`ModulusPolynomialRingZq`] of the form `X^n + 1 mod q`.
 ///
 /// Parameters:
@@ -197,3 +386,80 @@ mod test_new_cyclic {
         let _ = new_cyclic(2, 0);
     }
 }
+
+#[cfg(test)]
+mod test_new_cyclotomic {
+    use super::{new_anticyclic, new_cyclic, new_cyclotomic, PolyModulus};
+    use qfall_math::integer_mod_q::{ModulusPolynomialRingZq, PolyOverZq};
+    use std::str::FromStr;
+
+    /// Converts `poly_mod` into a [`PolyOverZq`] to compare it.
+    fn to_poly(poly_mod: ModulusPolynomialRingZq) -> PolyOverZq {
+        PolyOverZq::from(&poly_mod)
+    }
+
+    /// Ensures that the cyclotomic polynomials of small conductors are computed correctly.
+    #[test]
+    fn correct_polynomial() {
+        // Φ_1 = X - 1
+        assert_eq!(
+            to_poly(new_cyclic(1, 17).unwrap()),
+            to_poly(new_cyclotomic(1, 17).unwrap())
+        );
+        // Φ_8 = X^4 + 1
+        assert_eq!(
+            to_poly(new_anticyclic(4, 17).unwrap()),
+            to_poly(new_cyclotomic(8, 17).unwrap())
+        );
+        // Φ_12 = X^4 - X^2 + 1
+        assert_eq!(
+            PolyOverZq::from_str("5  1 0 16 0 1 mod 17").unwrap(),
+            to_poly(new_cyclotomic(12, 17).unwrap())
+        );
+        // Φ_7 = X^6 + X^5 + X^4 + X^3 + X^2 + X + 1
+        assert_eq!(
+            PolyOverZq::from_str("7  1 1 1 1 1 1 1 mod 17").unwrap(),
+            to_poly(new_cyclotomic(7, 17).unwrap())
+        );
+    }
+
+    /// Ensures that the degree of the cyclotomic polynomial is Euler's totient.
+    #[test]
+    fn degree() {
+        for (m, phi) in [
+            (1, 1),
+            (8, 4),
+            (12, 4),
+            (15, 8),
+            (30, 8),
+            (64, 32),
+            (105, 48),
+        ] {
+            let poly_mod = new_cyclotomic(m, 17).unwrap();
+
+            assert_eq!(phi, PolyModulus::Cyclotomic(m).degree());
+            assert_eq!(phi as i64, poly_mod.get_degree());
+        }
+    }
+
+    /// Ensures that [`PolyModulus`] outputs the described polynomials.
+    #[test]
+    fn poly_modulus() {
+        assert_eq!(
+            to_poly(new_anticyclic(16, 17).unwrap()),
+            to_poly(PolyModulus::XnPlus1(16).to_modulus(17).unwrap())
+        );
+        assert_eq!(
+            to_poly(new_cyclotomic(12, 17).unwrap()),
+            to_poly(PolyModulus::Cyclotomic(12).to_modulus(17).unwrap())
+        );
+        assert_eq!(16, PolyModulus::XnPlus1(16).degree());
+    }
+
+    /// Ensures that the conductor `0` results in a panic.
+    #[test]
+    #[should_panic]
+    fn invalid_conductor() {
+        let _ = new_cyclotomic(0, 17);
+    }
+}
@@ -0,0 +1,227 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains branch-free implementations of operations,
+//! whose running time should not depend on secret values.
+
+use qfall_math::{
+    integer::{MatZ, Z},
+    integer_mod_q::{MatZq, Modulus},
+    traits::{GetNumColumns, GetNumRows, Pow, SetEntry},
+};
+use rand::RngCore;
+
+/// Rounds `x mod q` to the nearest multiple of `q/2` and outputs
+/// whether this multiple is `q/2`, i.e. it decodes a bit hidden in `x`.
+/// The rounding is computed without branching on `x` as
+/// `((2 * (x mod q) + ⌊q/2⌋) / q) mod 2`.
+/// Hence, the output is `1` if and only if `q/4 <= x mod q < 3q/4`.
+///
+/// **WARNING:** The arithmetic of [`Z`] itself is not guaranteed to be constant-time,
+/// this function only avoids branches on the value of `x`.
+///
+/// Parameters:
+/// - `x`: the value that should be rounded
+/// - `q`: the modulus
+///
+/// Returns `0` if `x mod q` is closer to `0` and `1` if it is closer to `q/2`.
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::utils::constant_time::constant_time_round;
+/// use qfall_math::integer::Z;
+///
+/// assert_eq!(Z::ZERO, constant_time_round(&Z::from(1), &Z::from(17)));
+/// assert_eq!(Z::ONE, constant_time_round(&Z::from(9), &Z::from(17)));
+/// ```
+///
+/// # Panics ...
+/// - if `q < 1`.
+pub fn constant_time_round(x: &Z, q: &Z) -> Z {
+    assert!(q >= &Z::ONE, "The modulus q must be positive.");
+
+    let x_mod_q = x.modulo(q);
+    (Z::from(2) * x_mod_q + q.div_floor(2))
+        .div_floor(q)
+        .modulo(2)
+}
+
+/// Computes `sk^t * u mod q` without multiplying `sk` directly by following these steps:
+/// - R <- Z_q^{k x l} uniform using `rng`
+/// - sk^t * u = (sk + R)^t * u - R^t * u
+///
+/// Hence, the intermediate values `sk + R`, `(sk + R)^t * u` and `R^t * u` are
+/// uniform and independent of `sk` on their own.
+///
+/// **WARNING:** The arithmetic of [`MatZq`] itself is not guaranteed to be constant-time,
+/// this function only hides `sk` in the operands of the multiplications.
+///
+/// Parameters:
+/// - `sk`: the secret matrix of dimension `k x l`
+/// - `u`: the public matrix of dimension `k x t`, whose modulus `q` is used
+/// - `rng`: the source of randomness for the blinding term `R`
+///
+/// Returns `sk^t * u mod q` as a [`MatZq`] of dimension `l x t`.
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::utils::constant_time::blinded_inner_product;
+/// use qfall_math::{integer::MatZ, integer_mod_q::MatZq};
+/// use std::str::FromStr;
+/// let sk = MatZ::from_str("[[1, -2],[3, 4]]").unwrap();
+/// let u = MatZq::from_str("[[5],[6]] mod 17").unwrap();
+///
+/// let product = blinded_inner_product(&sk, &u, &mut rand::thread_rng());
+///
+/// assert_eq!(MatZq::from_str("[[6],[14]] mod 17").unwrap(), product);
+/// ```
+///
+/// # Panics ...
+/// - if the number of rows of `sk` and `u` differ.
+pub fn blinded_inner_product(sk: &MatZ, u: &MatZq, rng: &mut impl RngCore) -> MatZq {
+    let modulus = u.get_mod();
+    let r = sample_uniform_with_rng(sk.get_num_rows(), sk.get_num_columns(), &modulus, rng);
+
+    let blinded_sk = MatZq::from((sk, &modulus)) + &r;
+
+    blinded_sk.transpose() * u - r.transpose() * u
+}
+
+/// Samples a matrix of dimension `rows x columns` with entries uniform in `Z_q`
+/// from `rng`, where every entry is reduced from `⌈log q⌉ + 64` uniform bits
+/// s.t. the statistical distance to the uniform distribution is at most `2^{-64}`.
+pub(crate) fn sample_uniform_with_rng(
+    rows: i64,
+    columns: i64,
+    modulus: &Modulus,
+    rng: &mut impl RngCore,
+) -> MatZq {
+    let q = Z::from(modulus);
+    let limbs = u64::try_from(&q.log_ceil(2).unwrap()).unwrap() / 64 + 2;
+    let base = Z::from(2).pow(64).unwrap();
+
+    let mut out = MatZq::new(rows, columns, modulus);
+    for i in 0..rows {
+        for j in 0..columns {
+            let mut entry = Z::ZERO;
+            for _ in 0..limbs {
+                entry = entry * &base + Z::from(rng.next_u64());
+            }
+            out.set_entry(i, j, entry.modulo(&q)).unwrap();
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod test_constant_time_round {
+    use super::constant_time_round;
+    use qfall_math::integer::Z;
+
+    /// Ensures that the boundaries `0, q/4, q/2, 3q/4, q - 1` are rounded correctly.
+    #[test]
+    fn boundaries() {
+        // (q, [x = 0, ⌊q/4⌋, ⌊q/2⌋, ⌊3q/4⌋, q - 1])
+        let cases = [
+            (7, [0, 0, 1, 1, 0]),
+            (8, [0, 1, 1, 0, 0]),
+            (13, [0, 0, 1, 1, 0]),
+            (16, [0, 1, 1, 0, 0]),
+            (17, [0, 0, 1, 1, 0]),
+        ];
+
+        for (q, expected) in cases {
+            let xs = [0, q / 4, q / 2, 3 * q / 4, q - 1];
+            for (x, bit) in xs.iter().zip(expected.iter()) {
+                assert_eq!(
+                    Z::from(*bit),
+                    constant_time_round(&Z::from(*x), &Z::from(q)),
+                    "x = {x}, q = {q}"
+                );
+            }
+        }
+    }
+
+    /// Ensures that exactly the values in `[q/4, 3q/4)` are rounded to `1`
+    /// for all values of several small moduli.
+    #[test]
+    fn all_values() {
+        for q in [2, 3, 4, 5, 7, 8, 11, 16, 31, 64, 101] {
+            for x in 0..q {
+                let expected = i64::from(q <= 4 * x && 4 * x < 3 * q);
+
+                assert_eq!(
+                    Z::from(expected),
+                    constant_time_round(&Z::from(x), &Z::from(q))
+                );
+            }
+        }
+    }
+
+    /// Ensures that values outside of `[0, q)` are reduced first.
+    #[test]
+    fn reduction() {
+        let q = Z::from(17);
+
+        assert_eq!(Z::ONE, constant_time_round(&Z::from(9 + 17 * 5), &q));
+        assert_eq!(Z::ONE, constant_time_round(&Z::from(-8), &q));
+        assert_eq!(Z::ZERO, constant_time_round(&Z::from(-1), &q));
+    }
+
+    /// Ensures that a non-positive modulus results in a panic.
+    #[test]
+    #[should_panic]
+    fn invalid_modulus() {
+        let _ = constant_time_round(&Z::ONE, &Z::ZERO);
+    }
+}
+
+#[cfg(test)]
+mod test_blinded_inner_product {
+    use super::blinded_inner_product;
+    use qfall_math::{
+        integer::MatZ,
+        integer_mod_q::{MatZq, Modulus},
+    };
+    use rand::{rngs::StdRng, SeedableRng};
+
+    /// Ensures that the blinded product matches the unblinded product `sk^t * u`.
+    #[test]
+    fn matches_unblinded() {
+        let mut rng = StdRng::seed_from_u64(42);
+        for q in [17, 7681, u64::MAX] {
+            let modulus = Modulus::from(q);
+            let sk = MatZ::sample_uniform(12, 8, -50, 50).unwrap();
+            let u = MatZq::sample_uniform(12, 3, &modulus);
+
+            let unblinded = MatZq::from((&sk, &modulus)).transpose() * &u;
+
+            assert_eq!(unblinded, blinded_inner_product(&sk, &u, &mut rng));
+        }
+    }
+
+    /// Ensures that the blinded product of the identity is `u` itself.
+    #[test]
+    fn identity() {
+        let u = MatZq::sample_uniform(5, 1, 101);
+
+        let product = blinded_inner_product(&MatZ::identity(5, 5), &u, &mut rand::thread_rng());
+
+        assert_eq!(u, product);
+    }
+
+    /// Ensures that the function panics if the dimensions do not match.
+    #[test]
+    #[should_panic]
+    fn dimension_mismatch() {
+        let u = MatZq::sample_uniform(4, 1, 101);
+
+        let _ = blinded_inner_product(&MatZ::identity(5, 5), &u, &mut rand::thread_rng());
+    }
+}
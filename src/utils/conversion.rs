@@ -0,0 +1,33 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains conversions of types of qFALL-math into primitive types,
+//! which are needed for floating point estimates.
+
+use qfall_math::rational::Q;
+
+/// Converts `value` into an [`f64`], which may lose precision.
+///
+/// Parameters:
+/// - `value`: the rational that should be converted
+///
+/// Returns the closest [`f64`] to `numerator / denominator` up to rounding errors.
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::utils::conversion::q_to_f64;
+/// use qfall_math::rational::Q;
+///
+/// assert_eq!(0.25, q_to_f64(&Q::from((1, 4))));
+/// ```
+pub fn q_to_f64(value: &Q) -> f64 {
+    let numerator: f64 = value.get_numerator().to_string().parse().unwrap();
+    let denominator: f64 = value.get_denominator().to_string().parse().unwrap();
+
+    numerator / denominator
+}
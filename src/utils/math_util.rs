@@ -0,0 +1,528 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains modular inverses of integers and matrices,
+//! which are e.g. needed to validate tags of G-Trapdoors, and the arithmetic in `Z_q`
+//! used to encode and decode bits in LWE-based encryption schemes
+//! together with the probability that decoding a bit fails.
+
+use crate::utils::constant_time::constant_time_round;
+use qfall_math::{
+    integer::Z,
+    integer_mod_q::MatZq,
+    traits::{GetEntry, GetNumColumns, GetNumRows, SetEntry},
+};
+
+/// Computes the inverse of `a` modulo `m` using the extended Euclidean algorithm.
+///
+/// Parameters:
+/// - `a`: the value that is inverted
+/// - `m`: the modulus
+///
+/// Returns the inverse of `a` in `[0, m)` or `None` if `gcd(a, m) != 1` or `m < 1`.
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::utils::math_util::mod_inverse;
+/// use qfall_math::integer::Z;
+///
+/// assert_eq!(Some(Z::from(5)), mod_inverse(&Z::from(3), &Z::from(7)));
+/// assert_eq!(None, mod_inverse(&Z::from(4), &Z::from(6)));
+/// ```
+pub fn mod_inverse(a: &Z, m: &Z) -> Option<Z> {
+    if m < &Z::ONE {
+        return None;
+    }
+
+    // invariant: old_s * a = old_r mod m and s * a = r mod m
+    let (mut old_r, mut r) = (a.modulo(m), m.clone());
+    let (mut old_s, mut s) = (Z::ONE, Z::ZERO);
+    while r != Z::ZERO {
+        let quotient = old_r.div_floor(&r);
+        (old_r, r) = (r.clone(), old_r - &quotient * &r);
+        (old_s, s) = (s.clone(), old_s - quotient * &s);
+    }
+
+    // `old_r` is `gcd(a, m)`, where `gcd(0, 1) = 1`
+    if old_r == Z::ONE {
+        Some(old_s.modulo(m))
+    } else {
+        None
+    }
+}
+
+/// Computes the inverse of the square matrix `a` modulo `q` using Gauss-Jordan elimination.
+/// In every column, the first row whose entry is a unit modulo `q` is chosen as pivot.
+///
+/// **WARNING:** For composite `q`, an invertible matrix may have columns
+/// without a unit entry despite being invertible, e.g. `[[2, 3], [3, 2]] mod 6`.
+/// For such matrices `None` is returned.
+///
+/// Parameters:
+/// - `a`: the matrix that is inverted
+///
+/// Returns the inverse of `a` or `None` if `a` is not square or no inverse was found.
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::utils::math_util::mat_inverse_mod;
+/// use qfall_math::integer_mod_q::MatZq;
+/// use std::str::FromStr;
+///
+/// let a = MatZq::from_str("[[2, 1],[1, 1]] mod 7").unwrap();
+///
+/// let a_inv = mat_inverse_mod(&a).unwrap();
+/// assert_eq!(MatZq::identity(2, 2, 7), a * a_inv);
+/// ```
+pub fn mat_inverse_mod(a: &MatZq) -> Option<MatZq> {
+    let size = a.get_num_rows();
+    if size != a.get_num_columns() {
+        return None;
+    }
+    let q = Z::from(&a.get_mod());
+
+    // the augmented matrix `[a | I]` as rows of entries in `[0, q)`
+    let mut rows: Vec<Vec<Z>> = (0..size)
+        .map(|i| {
+            let mut row: Vec<Z> = (0..size).map(|j| a.get_entry(i, j).unwrap()).collect();
+            row.extend((0..size).map(|j| if i == j { Z::ONE } else { Z::ZERO }));
+            row
+        })
+        .collect();
+
+    let size = size as usize;
+    for column in 0..size {
+        // find a pivot, which is a unit modulo q
+        let (pivot_row, pivot_inv) = (column..size)
+            .find_map(|row| mod_inverse(&rows[row][column], &q).map(|inv| (row, inv)))?;
+        rows.swap(column, pivot_row);
+
+        for entry in rows[column].iter_mut() {
+            *entry = (&*entry * &pivot_inv).modulo(&q);
+        }
+
+        let pivot = rows[column].clone();
+        for (row_index, row) in rows.iter_mut().enumerate() {
+            if row_index == column || row[column] == Z::ZERO {
+                continue;
+            }
+            let factor = row[column].clone();
+            for (entry, pivot_entry) in row.iter_mut().zip(&pivot) {
+                *entry = (&*entry - &factor * pivot_entry).modulo(&q);
+            }
+        }
+    }
+
+    let mut inverse = MatZq::new(size, size, a.get_mod());
+    for (i, row) in rows.iter().enumerate() {
+        for (j, entry) in row[size..].iter().enumerate() {
+            inverse.set_entry(i, j, entry).unwrap();
+        }
+    }
+    Some(inverse)
+}
+
+/// Collects the arithmetic in `Z_q` that is needed to hide a bit in an LWE sample
+/// and to recover it, as in the Dual Regev encryption of
+/// [GPV08 - eprint](https://eprint.iacr.org/2007/432.pdf), Section 7.1.
+/// All outputs are given in the centered representation `[-q/2, q/2)`.
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::utils::math_util::ZqArith;
+/// use qfall_math::integer::Z;
+/// let q = Z::from(17);
+///
+/// let encoded = ZqArith::scale_by_half_q(&Z::ONE, &q);
+/// let noisy = encoded + Z::from(3);
+///
+/// assert_eq!(Z::ONE, ZqArith::round_to_bit(&noisy, &q));
+/// ```
+pub struct ZqArith;
+
+impl ZqArith {
+    /// Reduces `v` modulo `q` into the centered representation `[-q/2, q/2)`.
+    ///
+    /// Parameters:
+    /// - `v`: the value that is reduced
+    /// - `q`: the modulus
+    ///
+    /// Returns the unique representative of `v mod q` in `[-q/2, q/2)`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::utils::math_util::ZqArith;
+    /// use qfall_math::integer::Z;
+    ///
+    /// assert_eq!(Z::from(-3), ZqArith::reduce_centered(&Z::from(4), &Z::from(7)));
+    /// assert_eq!(Z::from(-4), ZqArith::reduce_centered(&Z::from(4), &Z::from(8)));
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `q < 1`.
+    pub fn reduce_centered(v: &Z, q: &Z) -> Z {
+        assert!(q >= &Z::ONE, "The modulus q must be positive.");
+
+        let v_mod_q = v.modulo(q);
+        if Z::from(2) * &v_mod_q >= *q {
+            v_mod_q - q
+        } else {
+            v_mod_q
+        }
+    }
+
+    /// Encodes the bit `v mod 2` as `(v mod 2) * ⌊q/2⌋`.
+    ///
+    /// Parameters:
+    /// - `v`: the value whose least significant bit is encoded
+    /// - `q`: the modulus
+    ///
+    /// Returns `(v mod 2) * ⌊q/2⌋` in the centered representation `[-q/2, q/2)`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::utils::math_util::ZqArith;
+    /// use qfall_math::integer::Z;
+    ///
+    /// assert_eq!(Z::from(8), ZqArith::scale_by_half_q(&Z::ONE, &Z::from(17)));
+    /// assert_eq!(Z::ZERO, ZqArith::scale_by_half_q(&Z::from(2), &Z::from(17)));
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `q < 1`.
+    pub fn scale_by_half_q(v: &Z, q: &Z) -> Z {
+        ZqArith::reduce_centered(&(v.modulo(2) * q.div_floor(2)), q)
+    }
+
+    /// Decodes the bit hidden in `v`, i.e. outputs `0` if `v mod q` is closer to `0`
+    /// and `1` if it is closer to `⌊q/2⌋`.
+    /// For the representative `c` of `v` in `[-q/2, q/2)`, this corresponds to
+    /// outputting `1` if and only if `c >= q/4` or `c < -q/4`.
+    /// The rounding is computed by [`constant_time_round`] without branching on `v`.
+    ///
+    /// Parameters:
+    /// - `v`: the value that is rounded
+    /// - `q`: the modulus
+    ///
+    /// Returns the decoded bit as a [`Z`].
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::utils::math_util::ZqArith;
+    /// use qfall_math::integer::Z;
+    ///
+    /// assert_eq!(Z::ZERO, ZqArith::round_to_bit(&Z::from(-4), &Z::from(17)));
+    /// assert_eq!(Z::ONE, ZqArith::round_to_bit(&Z::from(-5), &Z::from(17)));
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `q < 1`.
+    pub fn round_to_bit(v: &Z, q: &Z) -> Z {
+        constant_time_round(v, q)
+    }
+}
+
+/// Computes an upper bound on the complementary error function `erfc(x)`
+/// for `x >= 0` using the Chebyshev approximation from
+/// Numerical Recipes, which has a relative error of less than `1.2 * 10^{-7}`.
+/// The approximation is scaled by this relative error to obtain an upper bound.
+pub(crate) fn erfc(x: f64) -> f64 {
+    let t = 1.0 / (1.0 + 0.5 * x.abs());
+    let poly = -x * x - 1.265_512_23
+        + t * (1.000_023_68
+            + t * (0.374_091_96
+                + t * (0.096_784_18
+                    + t * (-0.186_288_06
+                        + t * (0.278_868_07
+                            + t * (-1.135_203_98
+                                + t * (1.488_515_87 + t * (-0.822_152_23 + t * 0.170_872_77))))))));
+
+    t * poly.exp() * (1.0 + 1.2e-7)
+}
+
+/// Computes the probability that a bit encoded by [`ZqArith::scale_by_half_q`]
+/// is decoded incorrectly by [`ZqArith::round_to_bit`], if a Gaussian noise with
+/// standard deviation `sigma` is added, i.e. the probability that the noise
+/// reduced into `[-q/2, q/2)` is not in `[-q/4, q/4)`.
+///
+/// For `sigma < q`, the probability mass of all intervals `±[kq + q/4, kq + 3q/4)`
+/// is summed up using the complementary error function.
+/// Otherwise, the Fourier series of the Gaussian reduced modulo `q` is evaluated, i.e.
+/// `1/2 - 2/π * Σ_{j odd} (-1)^{(j - 1)/2} * exp(-2π^2 * j^2 * sigma^2 / q^2) / j`,
+/// which converges quickly for large `sigma`.
+/// Hence, the probability tends to `1/2` for large `sigma`.
+///
+/// Parameters:
+/// - `q`: the modulus
+/// - `sigma`: the standard deviation of the noise
+///
+/// Returns the decoding failure probability in `[0, 1/2]`.
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::utils::math_util::rounding_failure_probability;
+///
+/// assert!(rounding_failure_probability(1024.0, 10.0) < 0.5_f64.powi(40));
+/// assert!((rounding_failure_probability(1024.0, 1_000_000.0) - 0.5).abs() < 1e-9);
+/// ```
+pub fn rounding_failure_probability(q: f64, sigma: f64) -> f64 {
+    if sigma <= 0.0 {
+        return 0.0;
+    }
+
+    let prob = if sigma < q {
+        let scale = 1.0 / (sigma * std::f64::consts::SQRT_2);
+        let mut prob = 0.0;
+        let mut k = 0.0;
+        // erfc(x) < 2^{-1000} for x > 27
+        while (k * q + q / 4.0) * scale <= 27.0 {
+            prob += erfc((k * q + q / 4.0) * scale) - erfc((k * q + 3.0 * q / 4.0) * scale);
+            k += 1.0;
+        }
+        prob
+    } else {
+        let exponent = -2.0 * (std::f64::consts::PI * sigma / q).powi(2);
+        let mut sum = 0.0;
+        let mut j = 1.0_f64;
+        loop {
+            let term = (exponent * j * j).exp() / j;
+            if term < 1e-20 {
+                break;
+            }
+            sum += if j % 4.0 == 1.0 { term } else { -term };
+            j += 2.0;
+        }
+        0.5 - 2.0 / std::f64::consts::PI * sum
+    };
+
+    prob.clamp(0.0, 0.5)
+}
+
+#[cfg(test)]
+mod test_mod_inverse {
+    use super::mod_inverse;
+    use qfall_math::integer::Z;
+
+    /// Ensures that inverses are computed correctly.
+    #[test]
+    fn correctness() {
+        assert_eq!(Some(Z::from(5)), mod_inverse(&Z::from(3), &Z::from(7)));
+        assert_eq!(Some(Z::from(2)), mod_inverse(&Z::from(-3), &Z::from(7)));
+        assert_eq!(Some(Z::ONE), mod_inverse(&Z::from(8), &Z::from(7)));
+        assert_eq!(Some(Z::from(5)), mod_inverse(&Z::from(5), &Z::from(6)));
+    }
+
+    /// Ensures that `None` is returned if the value is not a unit.
+    #[test]
+    fn not_invertible() {
+        assert_eq!(None, mod_inverse(&Z::from(4), &Z::from(6)));
+        assert_eq!(None, mod_inverse(&Z::ZERO, &Z::from(7)));
+        assert_eq!(None, mod_inverse(&Z::from(14), &Z::from(7)));
+        assert_eq!(None, mod_inverse(&Z::from(3), &Z::ZERO));
+    }
+
+    /// Ensures that `a * mod_inverse(a, m) = 1 mod m` for all units modulo `m = 100`.
+    #[test]
+    fn all_units() {
+        let m = Z::from(100);
+        for a in 1..100 {
+            let a = Z::from(a);
+            match mod_inverse(&a, &m) {
+                Some(inverse) => assert_eq!(Z::ONE, (a * inverse).modulo(&m)),
+                None => {
+                    assert!((1..100).all(|b| (&a * Z::from(b)).modulo(&m) != Z::ONE))
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_mat_inverse_mod {
+    use super::mat_inverse_mod;
+    use qfall_math::integer_mod_q::MatZq;
+    use std::str::FromStr;
+
+    /// Ensures that the inverse of the identity is the identity.
+    #[test]
+    fn identity() {
+        let identity = MatZq::identity(5, 5, 13);
+
+        assert_eq!(Some(identity.clone()), mat_inverse_mod(&identity));
+    }
+
+    /// Ensures that the inverse of a known invertible matrix is computed correctly.
+    #[test]
+    fn known_2x2() {
+        let a = MatZq::from_str("[[2, 1],[1, 1]] mod 7").unwrap();
+        let a_inv = MatZq::from_str("[[1, 6],[6, 2]] mod 7").unwrap();
+
+        assert_eq!(Some(a_inv), mat_inverse_mod(&a));
+    }
+
+    /// Ensures that a row swap is performed if the first entry is not a unit
+    /// and that composite moduli are supported.
+    #[test]
+    fn pivot_swap_composite_modulus() {
+        let a = MatZq::from_str("[[2, 1],[1, 0]] mod 6").unwrap();
+
+        let a_inv = mat_inverse_mod(&a).unwrap();
+
+        assert_eq!(MatZq::identity(2, 2, 6), &a * &a_inv);
+        assert_eq!(MatZq::identity(2, 2, 6), a_inv * a);
+    }
+
+    /// Ensures that `None` is returned for singular and non-square matrices.
+    #[test]
+    fn not_invertible() {
+        let singular = MatZq::from_str("[[1, 2],[2, 4]] mod 7").unwrap();
+        let not_unit = MatZq::from_str("[[2, 0],[0, 1]] mod 4").unwrap();
+        let non_square = MatZq::from_str("[[1, 0, 0],[0, 1, 0]] mod 7").unwrap();
+
+        assert_eq!(None, mat_inverse_mod(&singular));
+        assert_eq!(None, mat_inverse_mod(&not_unit));
+        assert_eq!(None, mat_inverse_mod(&non_square));
+    }
+
+    /// Ensures that random invertible matrices are inverted correctly.
+    #[test]
+    fn random_matrices() {
+        for _ in 0..10 {
+            let a = MatZq::sample_uniform(6, 6, 101);
+            if let Some(a_inv) = mat_inverse_mod(&a) {
+                assert_eq!(MatZq::identity(6, 6, 101), &a * &a_inv);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_zq_arith {
+    use super::ZqArith;
+    use qfall_math::integer::Z;
+
+    /// Ensures that centered representatives are in `[-q/2, q/2)`
+    /// and that the boundaries are mapped correctly.
+    #[test]
+    fn reduce_centered_boundaries() {
+        // (q, v, expected)
+        let cases = [
+            (7, 3, 3),
+            (7, 4, -3),
+            (7, -3, -3),
+            (7, -4, 3),
+            (8, 3, 3),
+            (8, 4, -4),
+            (8, -4, -4),
+            (8, -5, 3),
+            (8, 16, 0),
+            (1, 5, 0),
+        ];
+
+        for (q, v, expected) in cases {
+            assert_eq!(
+                Z::from(expected),
+                ZqArith::reduce_centered(&Z::from(v), &Z::from(q)),
+                "v = {v}, q = {q}"
+            );
+        }
+    }
+
+    /// Ensures that all centered representatives are congruent to the input
+    /// and lie in `[-q/2, q/2)`.
+    #[test]
+    fn reduce_centered_range() {
+        for q in [2, 3, 16, 17] {
+            let q = Z::from(q);
+            for v in -40..40 {
+                let v = Z::from(v);
+                let c = ZqArith::reduce_centered(&v, &q);
+
+                assert_eq!(v.modulo(&q), c.modulo(&q));
+                assert!(Z::from(-2) * &c <= q);
+                assert!(Z::from(2) * &c < q);
+            }
+        }
+    }
+
+    /// Ensures that bits are scaled to `0` and `⌊q/2⌋` in centered representation.
+    #[test]
+    fn scale_by_half_q() {
+        assert_eq!(Z::ZERO, ZqArith::scale_by_half_q(&Z::ZERO, &Z::from(17)));
+        assert_eq!(Z::from(8), ZqArith::scale_by_half_q(&Z::ONE, &Z::from(17)));
+        assert_eq!(
+            Z::from(8),
+            ZqArith::scale_by_half_q(&Z::from(-1), &Z::from(17))
+        );
+        // ⌊16/2⌋ = 8 is represented by -8 in [-8, 8)
+        assert_eq!(Z::from(-8), ZqArith::scale_by_half_q(&Z::ONE, &Z::from(16)));
+    }
+
+    /// Ensures that the rounding matches the decryption formula of GPV08,
+    /// i.e. outputs `0` iff `v mod q` is closer to `0` than to `⌊q/2⌋`,
+    /// where `c >= q/4` or `c < -q/4` for the centered representative `c` yields `1`.
+    #[test]
+    fn round_to_bit_matches_formula() {
+        for q in [2, 3, 7, 8, 16, 17, 101] {
+            let q = Z::from(q);
+            for v in 0..i64::try_from(&q).unwrap() {
+                let v = Z::from(v);
+                let c = ZqArith::reduce_centered(&v, &q);
+                let expected = if Z::from(4) * &c >= q || Z::from(4) * &c < Z::MINUS_ONE * &q {
+                    Z::ONE
+                } else {
+                    Z::ZERO
+                };
+
+                assert_eq!(expected, ZqArith::round_to_bit(&v, &q), "v = {v}, q = {q}");
+            }
+        }
+    }
+
+    /// Ensures that encoded bits with noise smaller than `q/4` are decoded correctly.
+    #[test]
+    fn round_to_bit_with_noise() {
+        let q = Z::from(101);
+        for bit in [Z::ZERO, Z::ONE] {
+            for noise in -24..=24 {
+                let v = ZqArith::scale_by_half_q(&bit, &q) + Z::from(noise);
+
+                assert_eq!(bit, ZqArith::round_to_bit(&v, &q));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_rounding_failure_probability {
+    use super::rounding_failure_probability;
+
+    /// Ensures that the probability vanishes for small noise and tends to `1/2` for large noise.
+    #[test]
+    fn limits() {
+        assert_eq!(0.0, rounding_failure_probability(1024.0, 0.0));
+        assert!(rounding_failure_probability(1024.0, 1.0) < 0.5_f64.powi(100));
+        assert!((rounding_failure_probability(1024.0, 1e9) - 0.5).abs() < 1e-9);
+    }
+
+    /// Ensures that the probability grows with the noise and that both evaluation
+    /// methods coincide close to `sigma = q`.
+    #[test]
+    fn monotone_and_continuous() {
+        let q = 1024.0;
+        let probs: Vec<f64> = [50.0, 100.0, 200.0, 400.0, 800.0]
+            .iter()
+            .map(|sigma| rounding_failure_probability(q, *sigma))
+            .collect();
+
+        assert!(probs.windows(2).all(|pair| pair[0] < pair[1]));
+        let below = rounding_failure_probability(q, q - 1e-6);
+        let above = rounding_failure_probability(q, q);
+        assert!((below - above).abs() < 1e-6);
+    }
+}
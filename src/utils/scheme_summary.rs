@@ -0,0 +1,304 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains summaries of the key sizes, ciphertext sizes and
+//! running times of schemes, which help to compare schemes before choosing one.
+//!
+//! All sizes are measured as the length of the JSON serialization of the
+//! respective values and all timings are measured for a single execution.
+//! Hence, the timings are only a rough indication.
+
+use crate::{
+    construction::{
+        hash::sha256::HashMatZq,
+        identity_based_encryption::{DualRegevIBE, IBEScheme},
+        pk_encryption::{CCSfromIBE, DualRegev, PKEncryptionScheme, PKEncryptionSchemeMut},
+        signature::{SignatureScheme, FDH, PFDH},
+    },
+    primitive::psf::PSFGPV,
+};
+use qfall_math::{
+    integer::{MatZ, Z},
+    integer_mod_q::MatZq,
+    rational::MatQ,
+};
+use serde::Serialize;
+use std::time::Instant;
+
+/// The key sizes, ciphertext sizes and running times of a scheme instantiated
+/// for a specific security parameter.
+///
+/// Attributes:
+/// - `scheme_name`: the name of the scheme
+/// - `security_level`: the targeted bit security provided by the caller
+/// - `pk_bytes`: the size of the (master) public key in bytes
+/// - `sk_bytes`: the size of the (master) secret key in bytes
+/// - `cipher_bytes`: the size of a ciphertext of a single bit in bytes,
+///     which is `0` for signature schemes
+/// - `sig_bytes`: the size of a signature in bytes, if the scheme uses signatures
+/// - `setup_ms`: the running time of the key generation in milliseconds
+/// - `enc_ms`: the running time of encryption, or signing, in milliseconds
+/// - `dec_ms`: the running time of decryption, or verification, in milliseconds
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SchemeSummary {
+    pub scheme_name: String,
+    pub security_level: u32,
+    pub pk_bytes: usize,
+    pub sk_bytes: usize,
+    pub cipher_bytes: usize,
+    pub sig_bytes: Option<usize>,
+    pub setup_ms: f64,
+    pub enc_ms: f64,
+    pub dec_ms: f64,
+}
+
+/// This trait should be implemented by schemes, which can summarize
+/// their key sizes, ciphertext sizes and running times.
+pub trait SummarizeScheme {
+    /// Instantiates the scheme for security parameter `n`, generates a key pair
+    /// and measures the sizes of keys, ciphertexts and signatures
+    /// and the running times of all operations.
+    ///
+    /// Parameters:
+    /// - `n`: the security parameter from which the parameters are generated
+    /// - `security_level`: the targeted bit security, which is stored in the summary
+    ///
+    /// Returns a [`SchemeSummary`] of the scheme.
+    fn scheme_summary(n: usize, security_level: u32) -> SchemeSummary;
+}
+
+/// Executes `f` and returns its output together with its running time in milliseconds.
+fn time_ms<T>(f: impl FnOnce() -> T) -> (T, f64) {
+    let start = Instant::now();
+    let out = f();
+    (out, start.elapsed().as_secs_f64() * 1000.0)
+}
+
+/// Returns the length of the JSON serialization of `value` in bytes.
+fn serialized_len(value: &impl Serialize) -> usize {
+    serde_json::to_vec(value).unwrap().len()
+}
+
+impl SummarizeScheme for DualRegev {
+    /// Summarizes [`DualRegev::new_from_n`].
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::{construction::pk_encryption::DualRegev, utils::scheme_summary::SummarizeScheme};
+    ///
+    /// let summary = DualRegev::scheme_summary(10, 128);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `n < 10`.
+    fn scheme_summary(n: usize, security_level: u32) -> SchemeSummary {
+        let scheme = DualRegev::new_from_n(n as u64);
+
+        let ((pk, sk), setup_ms) = time_ms(|| scheme.gen());
+        let (cipher, enc_ms) = time_ms(|| scheme.enc(&pk, 1));
+        let (_, dec_ms) = time_ms(|| scheme.dec(&sk, &cipher));
+
+        SchemeSummary {
+            scheme_name: String::from("DualRegev"),
+            security_level,
+            pk_bytes: serialized_len(&pk),
+            sk_bytes: serialized_len(&sk),
+            cipher_bytes: serialized_len(&cipher),
+            sig_bytes: None,
+            setup_ms,
+            enc_ms,
+            dec_ms,
+        }
+    }
+}
+
+impl SummarizeScheme for DualRegevIBE {
+    /// Summarizes [`DualRegevIBE::new_from_n`], where the sizes of the master keys
+    /// are measured and the running time of decryption includes
+    /// the extraction of the identity secret key.
+    ///
+    /// **WARNING:** Due to the [`PSFGPV`] the extraction is slow for `n > 5`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::{
+    ///     construction::identity_based_encryption::DualRegevIBE,
+    ///     utils::scheme_summary::SummarizeScheme,
+    /// };
+    ///
+    /// let summary = DualRegevIBE::scheme_summary(4, 128);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `n < 2`.
+    fn scheme_summary(n: usize, security_level: u32) -> SchemeSummary {
+        let scheme = DualRegevIBE::new_from_n(n as u64).unwrap();
+        let identity = String::from("identity");
+
+        let ((pk, sk), setup_ms) = time_ms(|| scheme.setup());
+        let (cipher, enc_ms) = time_ms(|| scheme.enc(&pk, &identity, 1));
+        let (_, dec_ms) = time_ms(|| {
+            let id_sk = scheme.extract(&pk, &sk, &identity).unwrap();
+            scheme.dec(&id_sk, &cipher).unwrap()
+        });
+
+        SchemeSummary {
+            scheme_name: String::from("DualRegevIBE"),
+            security_level,
+            pk_bytes: serialized_len(&pk),
+            sk_bytes: serialized_len(&sk),
+            cipher_bytes: serialized_len(&cipher),
+            sig_bytes: None,
+            setup_ms,
+            enc_ms,
+            dec_ms,
+        }
+    }
+}
+
+impl SummarizeScheme for FDH<MatZq, (MatZ, MatQ), MatZ, MatZq, PSFGPV, HashMatZq> {
+    /// Summarizes [`FDH::init_gpv`] with the modulus and Gaussian parameter
+    /// chosen by [`DualRegevIBE::new_from_n`].
+    /// As [`FDH`] is a signature scheme, `enc_ms` denotes the running time of signing,
+    /// `dec_ms` the running time of verification and `cipher_bytes = 0`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::{construction::signature::FDH, utils::scheme_summary::SummarizeScheme};
+    ///
+    /// let summary = FDH::scheme_summary(4, 128);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `n < 2`.
+    fn scheme_summary(n: usize, security_level: u32) -> SchemeSummary {
+        let params = DualRegevIBE::new_from_n(n as u64).unwrap();
+        let mut scheme = FDH::init_gpv(n as u64, &params.dual_regev.q, &params.psf.s);
+        let m = String::from("Hello World!");
+
+        let ((pk, sk), setup_ms) = time_ms(|| scheme.gen());
+        let (sigma, enc_ms) = time_ms(|| scheme.sign(m.clone(), &sk, &pk).unwrap());
+        let (_, dec_ms) = time_ms(|| scheme.vfy(m, &sigma, &pk));
+
+        SchemeSummary {
+            scheme_name: String::from("FDH"),
+            security_level,
+            pk_bytes: serialized_len(&pk),
+            sk_bytes: serialized_len(&sk),
+            cipher_bytes: 0,
+            sig_bytes: Some(serialized_len(&sigma)),
+            setup_ms,
+            enc_ms,
+            dec_ms,
+        }
+    }
+}
+
+impl SummarizeScheme
+    for CCSfromIBE<DualRegevIBE, PFDH<MatZq, (MatZ, MatQ), MatZ, MatZq, PSFGPV, HashMatZq>>
+{
+    /// Summarizes [`CCSfromIBE::init_dr_pfdh_from_n`], where `sig_bytes` denotes
+    /// the size of the one-time signature contained in every ciphertext.
+    ///
+    /// **WARNING:** Due to the [`PSFGPV`] the decryption is slow for `n > 5`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::{
+    ///     construction::pk_encryption::CCSfromIBE,
+    ///     utils::scheme_summary::SummarizeScheme,
+    /// };
+    ///
+    /// let summary = CCSfromIBE::scheme_summary(4, 128);
+    /// ```
+    ///
+    /// # Panics ...
+    /// - if `n < 4`.
+    fn scheme_summary(n: usize, security_level: u32) -> SchemeSummary {
+        let mut scheme = CCSfromIBE::init_dr_pfdh_from_n(n as u64);
+
+        let ((pk, sk), setup_ms) = time_ms(|| scheme.gen());
+        let (cipher, enc_ms) = time_ms(|| scheme.enc(&pk, 1));
+        let (_, dec_ms) = time_ms(|| scheme.dec(&sk, &cipher));
+
+        SchemeSummary {
+            scheme_name: String::from("CCSfromIBE"),
+            security_level,
+            pk_bytes: serialized_len(&pk),
+            sk_bytes: serialized_len(&sk),
+            cipher_bytes: serialized_len(&cipher),
+            sig_bytes: Some(serialized_len(&cipher.2)),
+            setup_ms,
+            enc_ms,
+            dec_ms,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_scheme_summary {
+    use super::{SchemeSummary, SummarizeScheme};
+    use crate::construction::{
+        identity_based_encryption::DualRegevIBE,
+        pk_encryption::{CCSfromIBE, DualRegev},
+        signature::FDH,
+    };
+
+    /// Ensures that the sizes of the keys are non-zero and
+    /// that all timings are positive.
+    fn assert_valid(summary: &SchemeSummary) {
+        assert!(summary.pk_bytes > 0);
+        assert!(summary.sk_bytes > 0);
+        assert!(summary.setup_ms > 0.0);
+        assert!(summary.enc_ms > 0.0);
+        assert!(summary.dec_ms > 0.0);
+    }
+
+    /// Ensures that the summary of [`DualRegev`] is valid.
+    #[test]
+    fn dual_regev() {
+        let summary = DualRegev::scheme_summary(10, 128);
+
+        assert_valid(&summary);
+        assert_eq!("DualRegev", summary.scheme_name);
+        assert_eq!(128, summary.security_level);
+        assert!(summary.cipher_bytes > 0);
+        assert_eq!(None, summary.sig_bytes);
+    }
+
+    /// Ensures that the summary of [`DualRegevIBE`] is valid.
+    #[test]
+    fn dual_regev_ibe() {
+        let summary = DualRegevIBE::scheme_summary(4, 128);
+
+        assert_valid(&summary);
+        assert!(summary.cipher_bytes > 0);
+        assert_eq!(None, summary.sig_bytes);
+    }
+
+    /// Ensures that the summary of [`FDH`] is valid and contains a signature size.
+    #[test]
+    fn fdh() {
+        let summary = FDH::scheme_summary(4, 128);
+
+        assert_valid(&summary);
+        assert_eq!(0, summary.cipher_bytes);
+        assert!(summary.sig_bytes.unwrap() > 0);
+    }
+
+    /// Ensures that the summary of [`CCSfromIBE`] is valid and that its ciphertexts
+    /// are larger than the contained signatures.
+    #[test]
+    fn ccs_from_ibe() {
+        let summary = CCSfromIBE::scheme_summary(4, 128);
+
+        assert_valid(&summary);
+        assert!(summary.cipher_bytes > summary.sig_bytes.unwrap());
+        assert!(summary.sig_bytes.unwrap() > 0);
+    }
+}
@@ -0,0 +1,255 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! This module contains the [`ZeroizingWrapper`], which overwrites secret
+//! matrices like trapdoors with zeros once they go out of scope.
+//!
+//! As the matrices of `qfall_math` do not implement [`Zeroize`], their entries
+//! are overwritten using [`ZeroizeEntries`].
+//!
+//! **WARNING:** Entries are stored by FLINT. Small entries are stored inline
+//! and hence overwritten, but memory of entries, whose absolute value is
+//! larger than `2^62`, is released by FLINT without being cleared.
+//!
+//! Constructions, which store secrets of generic types, e.g. the storage of
+//! [`FDH`](crate::construction::signature::FDH), only implement [`Zeroize`]
+//! and have to be zeroized explicitly, as they can not zeroize on drop.
+
+use qfall_math::{
+    integer::{MatPolyOverZ, MatZ, PolyOverZ, Z},
+    integer_mod_q::MatZq,
+    rational::{MatQ, Q},
+    traits::{GetNumColumns, GetNumRows, SetEntry},
+};
+use std::ops::{Deref, DerefMut};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// This trait should be implemented by every type containing secret matrices,
+/// which can be overwritten with zeros.
+pub trait ZeroizeEntries {
+    /// Overwrites all entries with zeros.
+    fn zeroize_entries(&mut self);
+}
+
+/// Implements [`ZeroizeEntries`] for a matrix type by setting all entries to `$zero`.
+macro_rules! zeroize_entries_matrix {
+    ($matrix:ty, $zero:expr) => {
+        impl ZeroizeEntries for $matrix {
+            /// Overwrites all entries of the matrix with zeros.
+            fn zeroize_entries(&mut self) {
+                for i in 0..self.get_num_rows() {
+                    for j in 0..self.get_num_columns() {
+                        self.set_entry(i, j, $zero).unwrap();
+                    }
+                }
+            }
+        }
+    };
+}
+
+zeroize_entries_matrix!(MatZ, Z::ZERO);
+zeroize_entries_matrix!(MatZq, Z::ZERO);
+zeroize_entries_matrix!(MatQ, Q::ZERO);
+zeroize_entries_matrix!(MatPolyOverZ, PolyOverZ::default());
+
+impl<A: ZeroizeEntries, B: ZeroizeEntries> ZeroizeEntries for (A, B) {
+    /// Overwrites all entries of both components with zeros.
+    fn zeroize_entries(&mut self) {
+        self.0.zeroize_entries();
+        self.1.zeroize_entries();
+    }
+}
+
+/// Wraps a secret value, s.t. all its entries are overwritten with zeros
+/// once it goes out of scope. The wrapper dereferences to the secret value,
+/// hence it can be used wherever a reference to the secret value is expected.
+///
+/// # Examples
+/// ```
+/// use qfall_crypto::{
+///     primitive::psf::{PSF, PSFGPV},
+///     sample::g_trapdoor::gadget_parameters::GadgetParameters,
+///     utils::zeroize::ZeroizingWrapper,
+/// };
+/// use qfall_math::rational::Q;
+///
+/// let psf = PSFGPV {
+///     gp: GadgetParameters::init_default(8, 64),
+///     s: Q::from(12),
+///     cdt: None,
+///     cdt_cache: Default::default(),
+/// };
+/// let (a, td) = psf.trap_gen();
+/// let td = ZeroizingWrapper::from(td);
+///
+/// let range_fa = psf.f_a(&a, &psf.samp_d());
+/// let preimage = psf.samp_p(&a, &td, &range_fa);
+///
+/// assert_eq!(range_fa, psf.f_a(&a, &preimage));
+/// // the trapdoor is overwritten with zeros here
+/// ```
+pub struct ZeroizingWrapper<T: ZeroizeEntries>(T);
+
+impl<T: ZeroizeEntries> From<T> for ZeroizingWrapper<T> {
+    /// Wraps `value` into a [`ZeroizingWrapper`].
+    ///
+    /// Parameters:
+    /// - `value`: the secret value
+    ///
+    /// Returns a [`ZeroizingWrapper`] containing `value`.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::utils::zeroize::ZeroizingWrapper;
+    /// use qfall_math::integer::MatZ;
+    ///
+    /// let secret = ZeroizingWrapper::from(MatZ::identity(2, 2));
+    /// ```
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T: ZeroizeEntries> Deref for ZeroizingWrapper<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: ZeroizeEntries> DerefMut for ZeroizingWrapper<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T: ZeroizeEntries> Zeroize for ZeroizingWrapper<T> {
+    /// Overwrites all entries of the secret value with zeros.
+    ///
+    /// # Examples
+    /// ```
+    /// use qfall_crypto::utils::zeroize::ZeroizingWrapper;
+    /// use qfall_math::integer::MatZ;
+    /// use zeroize::Zeroize;
+    ///
+    /// let mut secret = ZeroizingWrapper::from(MatZ::identity(2, 2));
+    /// secret.zeroize();
+    ///
+    /// assert!(secret.is_zero());
+    /// ```
+    fn zeroize(&mut self) {
+        self.0.zeroize_entries();
+    }
+}
+
+impl<T: ZeroizeEntries> Drop for ZeroizingWrapper<T> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl<T: ZeroizeEntries> ZeroizeOnDrop for ZeroizingWrapper<T> {}
+
+#[cfg(test)]
+mod test_zeroizing_wrapper {
+    use super::{ZeroizeEntries, ZeroizingWrapper};
+    use crate::{
+        primitive::psf::{PSF, PSFGPV},
+        sample::g_trapdoor::gadget_parameters::GadgetParameters,
+    };
+    use qfall_math::{
+        integer::{MatZ, Z},
+        integer_mod_q::MatZq,
+        rational::{MatQ, Q},
+        traits::{GetEntry, GetNumColumns, GetNumRows},
+    };
+    use std::{cell::RefCell, rc::Rc};
+    use zeroize::Zeroize;
+
+    /// A secret, whose memory is shared with the test s.t. it can be inspected
+    /// after the secret was dropped.
+    struct SharedSecret(Rc<RefCell<Vec<u8>>>);
+
+    impl ZeroizeEntries for SharedSecret {
+        fn zeroize_entries(&mut self) {
+            self.0.borrow_mut().as_mut_slice().zeroize();
+        }
+    }
+
+    /// Ensures that all entries of matrices and tuples are overwritten with zeros.
+    #[test]
+    fn zeroize_matrices() {
+        let mut secret = ZeroizingWrapper::from((
+            MatZ::sample_uniform(4, 3, 1, 100).unwrap(),
+            (
+                MatQ::from(&MatZ::sample_uniform(2, 2, 1, 100).unwrap()),
+                MatZq::sample_uniform(3, 1, 17),
+            ),
+        ));
+        let entry: Z = secret.0 .0.get_entry(3, 2).unwrap();
+        assert_ne!(Z::ZERO, entry);
+
+        secret.zeroize();
+
+        let (mat_z, (mat_q, mat_zq)) = &*secret;
+        assert!(mat_z.is_zero());
+        assert!(mat_q.is_zero());
+        assert!(mat_zq.is_zero());
+    }
+
+    /// Ensures that a wrapped [`MatZ`] is overwritten with zeros
+    /// and keeps its dimensions.
+    #[test]
+    fn zeroize_wrapped_mat_z() {
+        let mut secret = ZeroizingWrapper::from(MatZ::sample_uniform(3, 2, 1, 100).unwrap());
+        assert!(!secret.is_zero());
+
+        secret.zeroize();
+
+        assert!(secret.is_zero());
+        assert_eq!(3, secret.get_num_rows());
+        assert_eq!(2, secret.get_num_columns());
+    }
+
+    /// Ensures that a wrapped trapdoor `(MatZ, MatQ)` of [`PSFGPV`] is overwritten
+    /// with zeros, i.e. the short base and its GSO.
+    #[test]
+    fn zeroize_wrapped_trapdoor() {
+        let psf = PSFGPV {
+            gp: GadgetParameters::init_default(8, 64),
+            s: Q::from(12),
+            cdt: None,
+            cdt_cache: Default::default(),
+        };
+        let (_, td) = psf.trap_gen();
+        let mut td = ZeroizingWrapper::from(td);
+        assert!(!td.0.is_zero());
+        assert!(!td.1.is_zero());
+
+        td.zeroize();
+
+        let (short_base, short_base_gso) = &*td;
+        assert!(short_base.is_zero());
+        assert!(short_base_gso.is_zero());
+    }
+
+    /// Ensures that the memory of the secret is overwritten with zeros when the
+    /// wrapper goes out of scope by inspecting the shared memory afterwards.
+    #[test]
+    fn zeroize_on_drop() {
+        let memory = Rc::new(RefCell::new(vec![42_u8; 32]));
+
+        {
+            let _secret = ZeroizingWrapper::from(SharedSecret(Rc::clone(&memory)));
+        }
+
+        // the memory is still allocated, as `memory` holds a reference to it
+        assert!(memory.borrow().iter().all(|byte| *byte == 0));
+    }
+}
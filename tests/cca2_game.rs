@@ -0,0 +1,64 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! Runs the IND-CCA2 game against [`CCSfromIBE`] using the [`CCAOracle`].
+
+use qfall_crypto::{
+    construction::pk_encryption::{CCSfromIBE, PKEncryptionSchemeMut},
+    utils::cca_oracle::CCAOracle,
+};
+use qfall_math::{
+    integer::Z,
+    integer_mod_q::Zq,
+    traits::{GetEntry, SetEntry},
+};
+
+/// Runs 100 rounds of the IND-CCA2 game against [`CCSfromIBE::init_dr_pfdh_from_n`].
+/// In each round, the adversary
+/// - queries the oracle on the challenge ciphertext, which must be rejected,
+/// - queries the oracle on a mauled challenge ciphertext, whose signature is invalid,
+/// - guesses the encrypted bit using the oracle's answers.
+///
+/// As the oracle does not reveal information about the challenge bit,
+/// the adversary wins with probability `1/2`.
+/// The probability that it wins fewer than 30 or more than 70 rounds is below `10^{-4}`.
+#[test]
+fn cca2_game() {
+    let mut scheme = CCSfromIBE::init_dr_pfdh_from_n(4);
+    let (pk, sk) = scheme.gen();
+    let initial_cipher = scheme.enc(&pk, 0);
+    let mut oracle = CCAOracle::new(scheme, sk, &initial_cipher);
+
+    let mut wins = 0;
+    for _ in 0..100 {
+        let bit = Z::from(rand::random::<bool>() as i64);
+        let challenge_cipher = oracle.scheme_mut().enc(&pk, &bit);
+        oracle.set_challenge(&challenge_cipher);
+
+        // the challenge ciphertext itself can not be decrypted
+        assert_eq!(None, oracle.dec_oracle(&challenge_cipher));
+
+        // a mauled challenge ciphertext is rejected due to its invalid signature
+        let mut mauled_cipher = challenge_cipher.clone();
+        let entry: Zq = mauled_cipher.1.get_entry(0, 0).unwrap();
+        mauled_cipher.1.set_entry(0, 0, entry + Z::ONE).unwrap();
+        assert_eq!(None, oracle.dec_oracle(&mauled_cipher));
+
+        // the answers do not depend on the challenge bit, hence the adversary
+        // can only guess
+        let guess = Z::from(rand::random::<bool>() as i64);
+        if guess == bit {
+            wins += 1;
+        }
+    }
+
+    assert!(
+        (30..=70).contains(&wins),
+        "The adversary won {wins} out of 100 rounds."
+    );
+}
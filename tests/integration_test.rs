@@ -0,0 +1,75 @@
+// Copyright © 2024 qFALL-crypto contributors
+//
+// This file is part of qFALL-crypto.
+//
+// qFALL-crypto is free software: you can redistribute it and/or modify it under
+// the terms of the Mozilla Public License Version 2.0 as published by the
+// Mozilla Foundation. See <https://mozilla.org/en-US/MPL/2.0/>.
+
+//! Runs the full lifecycle of [`CCSfromIBE`] including serde round-trips
+//! of the scheme's state.
+
+use qfall_crypto::{
+    construction::pk_encryption::{CCSfromIBE, PKEncryptionSchemeMut},
+    error::CryptoError,
+};
+use qfall_math::{
+    integer::Z,
+    traits::{GetEntry, SetEntry},
+};
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Serializes `value` to JSON and deserializes it again.
+fn serde_roundtrip<T: Serialize + DeserializeOwned>(value: &T) -> T {
+    let json = serde_json::to_string(value).unwrap();
+    serde_json::from_str(&json).unwrap()
+}
+
+/// Runs the following scenario for [`CCSfromIBE::init_dr_pfdh_from_n`]:
+/// - generate 3 key pairs
+/// - encrypt 100 random bits distributed over all key pairs
+/// - decrypt all ciphertexts and verify the messages
+/// - verify that a ciphertext with a single flipped bit is rejected
+/// - serialize and deserialize the scheme
+/// - verify that all ciphertexts generated before the serialization still decrypt
+#[test]
+fn ccs_from_ibe_lifecycle() {
+    let mut scheme = CCSfromIBE::init_dr_pfdh_from_n(4);
+    let key_pairs: Vec<_> = (0..3).map(|_| scheme.gen()).collect();
+
+    let mut ciphers = Vec::new();
+    for i in 0..100 {
+        let (pk, _) = &key_pairs[i % key_pairs.len()];
+        let message = Z::from(rand::random::<bool>() as i64);
+        let cipher = scheme.enc(pk, &message);
+        ciphers.push((i % key_pairs.len(), message, cipher));
+    }
+
+    for (key_index, message, cipher) in &ciphers {
+        let (_, sk) = &key_pairs[*key_index];
+        assert_eq!(message, &scheme.dec(sk, cipher).unwrap());
+    }
+
+    // flip the least significant bit of the first entry of the IBE ciphertext
+    let (key_index, _, cipher) = &ciphers[0];
+    let mut tampered_cipher = cipher.clone();
+    let entry: Z = tampered_cipher.1.get_entry(0, 0).unwrap();
+    let flipped_entry = if entry.modulo(2) == Z::ZERO {
+        entry + Z::ONE
+    } else {
+        entry - Z::ONE
+    };
+    tampered_cipher.1.set_entry(0, 0, flipped_entry).unwrap();
+    assert!(matches!(
+        scheme.dec(&key_pairs[*key_index].1, &tampered_cipher),
+        Err(CryptoError::VerificationFailed)
+    ));
+
+    let mut scheme = serde_roundtrip(&scheme);
+    let key_pairs = serde_roundtrip(&key_pairs);
+
+    for (key_index, message, cipher) in &ciphers {
+        let (_, sk) = &key_pairs[*key_index];
+        assert_eq!(message, &scheme.dec(sk, cipher).unwrap());
+    }
+}